@@ -43,6 +43,12 @@ def create_parser() -> argparse.ArgumentParser:
         help="Specify main audio file (required if multiple audio files exist)",
     )
 
+    parser.add_argument(
+        "--output-dir",
+        type=Path,
+        help="Directory to write the config to (default: the recording directory)",
+    )
+
     return parser
 
 
@@ -72,7 +78,12 @@ def generate_config_command(args) -> int:
 
         # Generate configuration
         generator = CinemonConfigGenerator()
-        config_path = generator.generate_config_from_preset(recording_dir, args.preset, **overrides)
+        if args.output_dir:
+            config_path = generator.generate_config_from_preset(
+                recording_dir, args.preset, output_dir=args.output_dir, **overrides
+            )
+        else:
+            config_path = generator.generate_config_from_preset(recording_dir, args.preset, **overrides)
 
         print(f"✅ Configuration generated: {config_path}")
         print(f"   Preset: {args.preset}")
@@ -5,7 +5,7 @@
 
 import copy
 from pathlib import Path
-from typing import Any, Dict, Union
+from typing import Any, Dict, Optional, Union
 
 import yaml
 from setka_common.config.yaml_config import (
@@ -48,7 +48,11 @@ class CinemonConfigGenerator:
         self.preset_manager = PresetManager(preset_dir)
 
     def generate_config_from_preset(
-        self, recording_dir: Union[str, Path], preset_name: str, **overrides
+        self,
+        recording_dir: Union[str, Path],
+        preset_name: str,
+        output_dir: Optional[Union[str, Path]] = None,
+        **overrides,
     ) -> Path:
         """
         Generate configuration from preset template.
@@ -56,6 +60,7 @@ class CinemonConfigGenerator:
         Args:
             recording_dir: Path to recording directory
             preset_name: Name of preset to use
+            output_dir: Directory to write the config to, the recording directory by default
             **overrides: Parameter overrides (seed, main_audio, etc.)
 
         Returns:
@@ -97,7 +102,9 @@ class CinemonConfigGenerator:
         )
 
         # Generate output file path
-        output_file = recording_dir / f"animation_config_{preset_name}.yaml"
+        output_dir = Path(output_dir) if output_dir else recording_dir
+        output_dir.mkdir(parents=True, exist_ok=True)
+        output_file = output_dir / f"animation_config_{preset_name}.yaml"
 
         # Write YAML configuration
         self._write_yaml_config(config, output_file)
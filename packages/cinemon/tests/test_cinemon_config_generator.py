@@ -215,6 +215,25 @@ class TestCinemonConfigGeneratorPreset:
         minimal_path = generator.generate_preset(recording_dir, "minimal")
         assert minimal_path.name == "animation_config_minimal.yaml"
 
+    def test_generate_config_into_output_dir(self, tmp_path):
+        """Test that the config can be written outside the recording directory."""
+        recording_dir = tmp_path / "recording_20250105_143022"
+        extracted_dir = recording_dir / "extracted"
+        extracted_dir.mkdir(parents=True)
+
+        (extracted_dir / "Camera1.mp4").touch()
+        (extracted_dir / "main_audio.m4a").touch()
+
+        output_dir = recording_dir / ".fermata" / "tmp" / "generate_config"
+        generator = CinemonConfigGenerator()
+        config_path = generator.generate_config_from_preset(
+            recording_dir, "minimal", output_dir=output_dir
+        )
+
+        assert config_path == output_dir / "animation_config_minimal.yaml"
+        assert config_path.exists()
+        assert not (recording_dir / "animation_config_minimal.yaml").exists()
+
     def test_generate_preset_overwrites_existing_file(self, tmp_path):
         """Test that generate_preset overwrites existing config file."""
         recording_dir = tmp_path / "recording_20250105_143022"
@@ -0,0 +1,237 @@
+use crate::commands::operations::{execute_named_step, execute_next_step};
+use crate::commands::recordings::AppConfig;
+use crate::models::{NextStep, Recording};
+use crate::services::{
+    status_summary, update_recording_status, BlenderProgress, CustomStep, CustomSteps, FileScanner, ProcessResult, ProgressCallback,
+    SettingsStore, StallCallback, UploadConfig
+};
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Settings file to use instead of the one the app keeps in its config directory
+pub const SETTINGS_PATH_ENV: &str = "FERMATA_SETTINGS_PATH";
+
+/// Config directory name of the app, from tauri.conf.json's identifier
+const APP_IDENTIFIER: &str = "com.setka.fermata";
+
+const USAGE: &str = "Usage:
+  fermata-cli list [--json]
+  fermata-cli run <recording> <step|next> [--profile <upload profile>]
+  fermata-cli status <recording> [--json]
+  fermata-cli serve    HTTP API on FERMATA_HTTP_API_ADDR, with the http-api feature
+
+FERMATA_PROFILE=<config profile> runs with that profile instead of the active one";
+
+#[derive(Debug, PartialEq)]
+enum CliCommand {
+    List { json: bool },
+    Run { recording: String, step: String, profile: Option<String> },
+    Status { recording: String, json: bool },
+    Serve,
+}
+
+#[derive(Serialize)]
+struct RecordingReport {
+    #[serde(flatten)]
+    recording: Recording,
+    summary: String,
+    available_steps: Vec<String>,
+}
+
+/// Entry point of the fermata-cli binary; returns the process exit code
+pub fn run(args: Vec<String>) -> i32 {
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+
+    let settings = SettingsStore::new(settings_path());
+    let config = AppConfig::load(&settings);
+    let outcome = match command {
+        CliCommand::List { json } => list(&config, &settings, json),
+        CliCommand::Status { recording, json } => status(&recording, &config, &settings, json),
+        CliCommand::Run { recording, step, profile } => run_step(&recording, &step, profile.as_deref(), &config, &settings),
+        CliCommand::Serve => serve(config, settings),
+    };
+    match outcome {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            1
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<CliCommand, String> {
+    let mut json = false;
+    let mut profile = None;
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--profile" => profile = Some(args.next().ok_or("--profile needs a value")?.clone()),
+            flag if flag.starts_with("--") => return Err(format!("Unknown option: {}", flag)),
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    match positional.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["list"] => Ok(CliCommand::List { json }),
+        ["status", recording] => Ok(CliCommand::Status { recording: recording.to_string(), json }),
+        ["run", recording, step] => Ok(CliCommand::Run { recording: recording.to_string(), step: step.to_string(), profile }),
+        ["serve"] => Ok(CliCommand::Serve),
+        [] => Err("Missing command".to_string()),
+        _ => Err(format!("Unexpected arguments: {}", positional.join(" "))),
+    }
+}
+
+/// The settings file the app uses, so the CLI sees the same hooks, custom steps and profiles
+fn settings_path() -> PathBuf {
+    if let Some(path) = std::env::var_os(SETTINGS_PATH_ENV) {
+        return PathBuf::from(path);
+    }
+    let home = || PathBuf::from(std::env::var_os("HOME").unwrap_or_default());
+    let config_dir = if cfg!(target_os = "windows") {
+        PathBuf::from(std::env::var_os("APPDATA").unwrap_or_default())
+    } else if cfg!(target_os = "macos") {
+        home().join("Library").join("Application Support")
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from).unwrap_or_else(|| home().join(".config"))
+    };
+    config_dir.join(APP_IDENTIFIER).join("settings.json")
+}
+
+fn report(recording: Recording, config: &AppConfig, custom_steps: &[CustomStep]) -> RecordingReport {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let upload_target = UploadConfig::recording_profile(&recording.path);
+    RecordingReport {
+        summary: status_summary(&recording, config.locale, now, upload_target.as_deref()),
+        available_steps: CustomSteps::available_steps(&recording, custom_steps),
+        recording,
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize output: {}", e))?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn list(config: &AppConfig, settings: &SettingsStore, json: bool) -> Result<(), String> {
+    let custom_steps = CustomSteps::list(settings)?;
+    let reports: Vec<RecordingReport> = FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .map(|recording| report(recording, config, &custom_steps))
+        .collect();
+    if json {
+        return print_json(&reports);
+    }
+    for report in &reports {
+        println!("{}\t{}", report.recording.name, report.summary);
+    }
+    Ok(())
+}
+
+fn status(recording_name: &str, config: &AppConfig, settings: &SettingsStore, json: bool) -> Result<(), String> {
+    let recording_path = config.recording_path(recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    let mut recording = Recording::from_path(recording_path)
+        .map_err(|e| format!("Failed to load recording '{}': {}", recording_name, e))?;
+    update_recording_status(&mut recording);
+
+    let report = report(recording, config, &CustomSteps::list(settings)?);
+    if json {
+        return print_json(&report);
+    }
+    println!("{}", report.summary);
+    if !report.available_steps.is_empty() {
+        println!("Available steps: {}", report.available_steps.join(", "));
+    }
+    Ok(())
+}
+
+fn run_step(recording_name: &str, step: &str, profile: Option<&str>, config: &AppConfig, settings: &SettingsStore) -> Result<(), String> {
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| format!("Failed to start runtime: {}", e))?;
+    let on_progress: ProgressCallback = Arc::new(print_progress);
+    let on_stall: StallCallback = Arc::new(print_stall);
+    let (next_step, result) = runtime.block_on(async {
+        if step == "next" {
+            execute_next_step(recording_name, config, settings, Some(on_progress), Some(on_stall)).await
+        } else {
+            execute_named_step(recording_name, step, profile, config, settings, Some(on_progress), Some(on_stall)).await
+        }
+    })?;
+    finish_step(recording_name, &next_step, result?)
+}
+
+#[cfg(feature = "http-api")]
+fn serve(config: AppConfig, settings: SettingsStore) -> Result<(), String> {
+    crate::commands::http_api::serve_headless(config, settings)
+}
+
+#[cfg(not(feature = "http-api"))]
+fn serve(_config: AppConfig, _settings: SettingsStore) -> Result<(), String> {
+    Err("fermata-cli was built without the http-api feature".to_string())
+}
+
+fn finish_step(recording_name: &str, step: &NextStep, result: ProcessResult) -> Result<(), String> {
+    if !result.stdout.is_empty() {
+        println!("{}", result.stdout.trim_end());
+    }
+    if result.success {
+        eprintln!("✅ Completed {} for {}", step.label().to_lowercase(), recording_name);
+        Ok(())
+    } else {
+        Err(format!("Failed to execute {}: {}", step.label().to_lowercase(), result.stderr.trim_end()))
+    }
+}
+
+/// Render progress on one stderr line, so stdout stays clean for scripts
+fn print_progress(progress: &BlenderProgress) {
+    let total = progress.total_frames.map(|total| format!("/{}", total)).unwrap_or_default();
+    let eta = progress.eta_seconds.map(|eta| format!(", ETA {}s", eta)).unwrap_or_default();
+    eprint!("\r🎬 Frame {}{}{}   ", progress.current_frame, total, eta);
+    let _ = std::io::stderr().flush();
+}
+
+fn print_stall(step: &NextStep, seconds: u64) {
+    eprintln!("\n🐢 {} wrote nothing for {} s, it may be stuck", step.label().to_lowercase(), seconds);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(&args("list")), Ok(CliCommand::List { json: false }));
+        assert_eq!(
+            parse_args(&args("status 2024-01-01 --json")),
+            Ok(CliCommand::Status { recording: "2024-01-01".to_string(), json: true })
+        );
+        assert_eq!(
+            parse_args(&args("run 2024-01-01 upload --profile youtube")),
+            Ok(CliCommand::Run { recording: "2024-01-01".to_string(), step: "upload".to_string(), profile: Some("youtube".to_string()) })
+        );
+        assert_eq!(parse_args(&args("serve")), Ok(CliCommand::Serve));
+        assert!(parse_args(&args("run 2024-01-01")).is_err());
+        assert!(parse_args(&args("run 2024-01-01 upload --profile")).is_err());
+        assert!(parse_args(&args("list --verbose")).is_err());
+        assert!(parse_args(&[]).is_err());
+    }
+}
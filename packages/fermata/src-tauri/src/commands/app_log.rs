@@ -0,0 +1,28 @@
+use crate::commands::recordings::AppConfig;
+use crate::services::{ActivityEntry, ActivityFilter, ActivityLog, AppLog, AppLogEntry, FileManager, DEFAULT_LOG_TAIL_LINES};
+use tauri::{AppHandle, Manager, State};
+
+/// Last entries of the app's log file, optionally only `level_filter` ("warn")
+/// and more severe ones
+#[tauri::command]
+pub fn get_app_log(tail_lines: Option<usize>, level_filter: Option<String>, app: AppHandle) -> Result<Vec<AppLogEntry>, String> {
+    let min_level = level_filter
+        .map(|level| level.parse::<log::Level>().map_err(|_| format!("Unknown log level '{}'", level)))
+        .transpose()?;
+    let log_dir = app.path().app_log_dir().map_err(|e| e.to_string())?;
+    AppLog::tail(&log_dir, tail_lines.unwrap_or(DEFAULT_LOG_TAIL_LINES), min_level)
+}
+
+/// Commands that changed the library, newest first, from every app sharing it
+#[tauri::command]
+pub fn get_activity_log(filter: Option<ActivityFilter>, config: State<AppConfig>) -> Result<Vec<ActivityEntry>, String> {
+    ActivityLog::read(&config.recordings_path, &filter.unwrap_or_default())
+}
+
+/// Open the directory with the log files, to attach them to bug reports
+#[tauri::command]
+pub fn open_log_folder(app: AppHandle) -> Result<(), String> {
+    let log_dir = app.path().app_log_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&log_dir).map_err(|e| format!("Failed to create {}: {}", log_dir.display(), e))?;
+    FileManager::open_folder(&log_dir)
+}
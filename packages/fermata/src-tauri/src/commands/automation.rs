@@ -0,0 +1,179 @@
+use crate::commands::operations::run_specific_step;
+use crate::commands::recordings::AppConfig;
+use crate::models::NextStep;
+use crate::services::{
+    AutomationRule, AutomationRules, CustomStep, CustomSteps, FileScanner, PipelineTemplate, PipelineTemplates, SettingsStore, SnoozeStore, StepHook,
+    StepHooks, UploadBlockStore
+};
+use chrono::{Local, NaiveDateTime, Timelike};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+/// How often the scheduler wakes up to look for due rules
+const SCHEDULER_TICK: Duration = Duration::from_secs(20);
+
+/// List automation rules from the settings file
+#[tauri::command]
+pub fn list_automation_rules(settings: State<SettingsStore>) -> Result<Vec<AutomationRule>, String> {
+    AutomationRules::list(&settings)
+}
+
+/// Enable or disable a single automation rule
+#[tauri::command]
+pub fn set_automation_rule_enabled(
+    id: String,
+    enabled: bool,
+    settings: State<SettingsStore>
+) -> Result<AutomationRule, String> {
+    log::info!("⏰ Automation rule '{}' {}", id, if enabled { "enabled" } else { "disabled" });
+    AutomationRules::set_enabled(&settings, &id, enabled)
+}
+
+/// List the shell hooks run before and after pipeline steps
+#[tauri::command]
+pub fn list_step_hooks(settings: State<SettingsStore>) -> Result<Vec<StepHook>, String> {
+    StepHooks::list(&settings)
+}
+
+/// Replace the step hooks; every hook is validated before anything is saved
+#[tauri::command]
+pub fn save_step_hooks(hooks: Vec<StepHook>, settings: State<SettingsStore>) -> Result<(), String> {
+    log::info!("🪝 Saving {} step hooks", hooks.len());
+    StepHooks::save(&settings, &hooks)
+}
+
+/// List the user-defined pipeline steps
+#[tauri::command]
+pub fn list_custom_steps(settings: State<SettingsStore>) -> Result<Vec<CustomStep>, String> {
+    CustomSteps::list(&settings)
+}
+
+/// Replace the user-defined pipeline steps; every step is validated before anything is saved
+#[tauri::command]
+pub fn save_custom_steps(steps: Vec<CustomStep>, settings: State<SettingsStore>) -> Result<(), String> {
+    log::info!("🧩 Saving {} custom steps", steps.len());
+    CustomSteps::save(&settings, &steps)
+}
+
+/// List the named pipeline templates
+#[tauri::command]
+pub fn list_pipeline_templates(settings: State<SettingsStore>) -> Result<Vec<PipelineTemplate>, String> {
+    PipelineTemplates::list(&settings)
+}
+
+/// Replace the pipeline templates; every template is validated before anything is saved
+#[tauri::command]
+pub fn save_pipeline_templates(templates: Vec<PipelineTemplate>, settings: State<SettingsStore>) -> Result<(), String> {
+    log::info!("🧩 Saving {} pipeline templates", templates.len());
+    PipelineTemplates::save(&settings, &templates)
+}
+
+/// Name of the template a recording follows, if any
+#[tauri::command]
+pub fn get_recording_pipeline_template(recording_name: String, config: State<AppConfig>) -> Result<Option<String>, String> {
+    Ok(PipelineTemplates::recording_template(&config.recording_path(&recording_name)))
+}
+
+/// Make a recording follow a pipeline template, or the whole pipeline again with `None`
+#[tauri::command]
+pub fn set_recording_pipeline_template(
+    recording_name: String,
+    template: Option<String>,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<(), String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    if let Some(name) = &template {
+        if !PipelineTemplates::list(&settings)?.iter().any(|t| &t.name == name) {
+            return Err(format!("Pipeline template '{}' not found", name));
+        }
+    }
+    log::info!("🧩 '{}' follows pipeline template {:?}", recording_name, template);
+    PipelineTemplates::set_recording_template(&recording_path, template.as_deref())
+}
+
+/// Run due automation rules once per minute for the lifetime of the app
+pub fn start_automation_scheduler(app: &AppHandle) {
+    let app = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let mut last_minute: Option<NaiveDateTime> = None;
+        loop {
+            let now = Local::now().naive_local();
+            let minute = now.with_second(0).and_then(|t| t.with_nanosecond(0));
+
+            if minute.is_some() && minute != last_minute {
+                last_minute = minute;
+                run_due_rules(&app, &now);
+            }
+            tokio::time::sleep(SCHEDULER_TICK).await;
+        }
+    });
+}
+
+/// Start every due rule in the background so a long step doesn't hold up the schedule
+fn run_due_rules(app: &AppHandle, now: &NaiveDateTime) {
+    let rules = match AutomationRules::list(&app.state::<SettingsStore>()) {
+        Ok(rules) => rules,
+        Err(e) => {
+            log::warn!("Automation rules unavailable: {}", e);
+            return;
+        }
+    };
+
+    for rule in rules.into_iter().filter(|rule| rule.is_due(now)) {
+        if let Err(e) = rule.validate() {
+            log::warn!("Skipping automation rule '{}': {}", rule.id, e);
+            continue;
+        }
+        tauri::async_runtime::spawn(run_rule(app.clone(), rule));
+    }
+}
+
+/// Run a rule against the matching recordings, one recording at a time
+async fn run_rule(app: AppHandle, rule: AutomationRule) {
+    let recordings = FileScanner::scan_roots(&app.state::<AppConfig>().recordings_roots);
+    let mut selected = rule.select(&recordings);
+    selected.retain(|recording| match SnoozeStore::active(&recording.path) {
+        Some(_) => {
+            log::info!("⏰ [{}] Skipping '{}', snoozed", rule.id, recording.name);
+            false
+        }
+        None => true,
+    });
+    if rule.step.parse::<NextStep>() == Ok(NextStep::Upload) {
+        selected.retain(|recording| match UploadBlockStore::load(&recording.path) {
+            Ok(Some(block)) => {
+                log::warn!("⏰ [{}] Skipping '{}', upload blocked: {}", rule.id, recording.name, block.reason);
+                false
+            }
+            Ok(None) => true,
+            Err(e) => {
+                log::warn!("⏰ [{}] Skipping '{}': {}", rule.id, recording.name, e);
+                false
+            }
+        });
+    }
+    log::info!("⏰ Automation rule '{}' running {} on {} recordings", rule.id, rule.step, selected.len());
+
+    for recording in selected {
+        let result = run_specific_step(
+            recording.name.clone(),
+            rule.step.clone(),
+            None,
+            app.clone(),
+            app.state(),
+            app.state(),
+            app.state(),
+        )
+        .await;
+
+        match result {
+            Ok(message) => log::info!("⏰ [{}] {}", rule.id, message),
+            Err(e) => log::error!("⏰ [{}] {} failed for '{}': {}", rule.id, rule.step, recording.name, e),
+        }
+    }
+}
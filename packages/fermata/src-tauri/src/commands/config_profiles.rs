@@ -0,0 +1,77 @@
+use crate::commands::storage::running_recordings;
+use crate::services::{ConfigProfile, ConfigProfiles, JobQueue, SettingsBundle, SettingsStore};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+
+/// Saved config profiles and the one the app starts with
+#[tauri::command]
+pub fn list_config_profiles(settings: State<SettingsStore>) -> Result<ConfigProfiles, String> {
+    ConfigProfiles::load(&settings)
+}
+
+/// Create a config profile or replace the one with the same name
+#[tauri::command]
+pub fn save_config_profile(profile: ConfigProfile, settings: State<SettingsStore>) -> Result<ConfigProfiles, String> {
+    let mut profiles = ConfigProfiles::load(&settings)?;
+    log::info!("💾 Saving config profile '{}'", profile.name);
+    profiles.upsert(profile)?;
+    profiles.save(&settings)?;
+    Ok(profiles)
+}
+
+/// Remove a config profile other than the active one
+#[tauri::command]
+pub fn delete_config_profile(name: String, settings: State<SettingsStore>) -> Result<ConfigProfiles, String> {
+    let mut profiles = ConfigProfiles::load(&settings)?;
+    profiles.remove(&name)?;
+    profiles.save(&settings)?;
+    Ok(profiles)
+}
+
+/// Switch to a config profile, or back to the environment with `None`, and restart
+/// the app so every path is read again; refused while a job is running
+#[tauri::command]
+pub fn switch_config_profile(
+    name: Option<String>,
+    app: AppHandle,
+    settings: State<SettingsStore>,
+    queue: State<JobQueue>
+) -> Result<(), String> {
+    let running = running_recordings(&queue);
+    if !running.is_empty() {
+        return Err(format!("Cannot switch profiles while jobs run for: {}", running.join(", ")));
+    }
+
+    let mut profiles = ConfigProfiles::load(&settings)?;
+    if let Some(toolchain) = profiles.switch(name.as_deref())?.and_then(|profile| profile.toolchain.clone()) {
+        toolchain.save(&settings)?;
+    }
+    profiles.save(&settings)?;
+    log::info!("🔀 Switched to config profile {}, restarting", name.as_deref().unwrap_or("(environment)"));
+    app.request_restart();
+    Ok(())
+}
+
+/// Write config profiles, upload profiles, step hooks and the other portable
+/// settings to one JSON file; returns the settings keys written
+#[tauri::command]
+pub fn export_settings(path: String, settings: State<SettingsStore>) -> Result<Vec<String>, String> {
+    let path = PathBuf::from(path);
+    if !path.parent().is_some_and(Path::is_dir) {
+        return Err(format!("Folder of {} does not exist", path.display()));
+    }
+    let bundle = SettingsBundle::collect(&settings)?;
+    bundle.write(&path)?;
+    log::info!("📦 Exported {} settings to {}", bundle.settings.len(), path.display());
+    Ok(bundle.settings.keys().cloned().collect())
+}
+
+/// Replace the settings held by a file from export_settings; returns the settings
+/// keys replaced. A switched-to profile takes effect after a restart.
+#[tauri::command]
+pub fn import_settings(path: String, settings: State<SettingsStore>) -> Result<Vec<String>, String> {
+    let path = PathBuf::from(path);
+    let imported = SettingsBundle::read(&path)?.apply(&settings)?;
+    log::info!("📦 Imported {} from {}", imported.join(", "), path.display());
+    Ok(imported)
+}
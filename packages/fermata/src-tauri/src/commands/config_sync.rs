@@ -0,0 +1,296 @@
+use crate::commands::recordings::AppConfig;
+use crate::models::NextStep;
+use crate::services::{
+    detect_in_progress_status, merge_preserving_overrides, read_yaml, validate_animation_config, write_yaml, ConfigDrift,
+    ConfigStore, ConfigSync, ConfigVersion, ProcessEnvironment, ProcessRunner, SettingsStore, StepLock, StepScratch, Toolchain, DEFAULT_CONFIG_HISTORY
+};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+/// Whether a recording's animation configs still match its extracted sources
+#[derive(Debug, Serialize)]
+pub struct ConfigDriftReport {
+    pub recording: String,
+    /// The .blend project was set up from sources that changed since
+    pub setup_stale: bool,
+    pub drifts: Vec<ConfigDrift>,
+}
+
+/// Outcome of regenerating an animation config
+#[derive(Debug, Serialize)]
+pub struct ConfigRegeneration {
+    pub config: String,
+    pub preset: String,
+    pub added_sources: Vec<String>,
+    pub removed_sources: Vec<String>,
+    /// The config before regeneration, now in the config history
+    pub backup: String,
+    /// Setup render has to run again to rebuild the .blend project
+    pub setup_stale: bool,
+}
+
+/// An animation config as text, for the config editor
+#[derive(Debug, Serialize)]
+pub struct AnimationConfigText {
+    pub preset: String,
+    /// Path relative to the recording directory
+    pub file: String,
+    pub yaml: String,
+}
+
+/// Outcome of saving an edited animation config
+#[derive(Debug, Serialize)]
+pub struct AnimationConfigSaved {
+    pub file: String,
+    /// The version it replaced, now in the config history
+    pub backup: Option<String>,
+    /// A .blend project exists and needs a blend setup to pick up the edit
+    pub blend_outdated: bool,
+}
+
+/// Compare animation configs with the extracted sources and mark the setup stale on mismatch
+#[tauri::command]
+pub fn get_config_drift(recording_name: String, config: State<AppConfig>) -> Result<ConfigDriftReport, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    let drifts = ConfigSync::mark_if_drifted(&recording_path)?;
+    Ok(ConfigDriftReport {
+        recording: recording_name,
+        setup_stale: ConfigSync::is_setup_stale(&recording_path),
+        drifts,
+    })
+}
+
+/// Regenerate an animation config for the current sources with cinemon, then carry
+/// over the user's edits (layout, animations of sources that still exist, ...)
+#[tauri::command]
+pub async fn regenerate_config_preserving_overrides(
+    recording_name: String,
+    preset: Option<String>,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>
+) -> Result<ConfigRegeneration, String> {
+    log::info!("🧩 Regenerating config of '{}' (preset: {:?})", recording_name, preset);
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    if let Some(status) = detect_in_progress_status(&recording_path) {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, status));
+    }
+    // Held until the regeneration finishes, so no step runs on the recording meanwhile
+    let _lock = StepLock::acquire(&recording_path, &NextStep::GenerateConfig)?;
+
+    let drifts = ConfigSync::mark_if_drifted(&recording_path)?;
+    let (config_path, preset) = select_config(&recording_path, preset.as_deref(), &drifts)?;
+    let config_name = config_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let drift = drifts.iter().find(|d| d.config == config_name);
+
+    let old = read_yaml(&config_path)?;
+    let main_audio = old
+        .get("project")
+        .and_then(|p| p.get("main_audio"))
+        .and_then(|a| a.as_str())
+        .map(str::to_string);
+    // A config still at the recording root would be overwritten by cinemon
+    if config_path == recording_path.join(ConfigStore::file_name(&preset)) {
+        ConfigStore::adopt(&recording_path, &preset)?;
+    }
+
+    let runner = ProcessRunner::new(
+        config.cli_paths.workspace_root.clone(),
+        config.cli_paths.uv_path.clone()
+    )
+    .with_environment(ProcessEnvironment::load(&settings)?.for_step(Some(&NextStep::GenerateConfig)))
+    .with_toolchain(Toolchain::load(&settings)?);
+    // Kept for inspection when the refresh fails
+    let scratch = StepScratch::new(&recording_path, &NextStep::GenerateConfig);
+    let result = runner
+        .run_cinemon_generate_config(&recording_path, &preset, main_audio.as_deref(), scratch.path())
+        .await
+        .map_err(|e| format!("Command execution failed: {}", e))?;
+    if !result.success {
+        return Err(format!("cinemon-generate-config failed: {}", result.stderr));
+    }
+
+    let generated = ConfigStore::find_generated(&recording_path, &preset, result.config_file())
+        .ok_or_else(|| format!("cinemon finished without writing an animation config for preset '{}'", preset))?;
+    let (config_path, backup) = ConfigStore::adopt_file(&recording_path, &generated, &preset)?;
+    scratch.finish(true);
+    let backup = backup.ok_or_else(|| "Previous config missing from the config store".to_string())?;
+    let generated = match read_yaml(&config_path) {
+        Ok(generated) => generated,
+        Err(e) => {
+            restore_backup(&backup, &config_path);
+            return Err(e);
+        }
+    };
+
+    let removed_sources = drift.map(|d| d.removed_sources.clone()).unwrap_or_default();
+    let merged = merge_preserving_overrides(&old, &generated, &removed_sources);
+    if let Err(e) = write_yaml(&config_path, &merged) {
+        restore_backup(&backup, &config_path);
+        return Err(e);
+    }
+
+    log::info!("🧩 Regenerated {} preserving overrides", config_name);
+    Ok(ConfigRegeneration {
+        config: config_name,
+        preset,
+        added_sources: drift.map(|d| d.added_sources.clone()).unwrap_or_default(),
+        removed_sources,
+        backup: backup.strip_prefix(&recording_path).unwrap_or(&backup).to_string_lossy().to_string(),
+        setup_stale: ConfigSync::is_setup_stale(&recording_path),
+    })
+}
+
+/// Read an animation config for editing; without a preset the most recent one
+#[tauri::command]
+pub fn read_animation_config(
+    recording_name: String,
+    preset: Option<String>,
+    config: State<AppConfig>
+) -> Result<AnimationConfigText, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    let (config_path, preset) = match preset {
+        Some(preset) => ConfigStore::configs(&recording_path)
+            .into_iter()
+            .find(|(_, p)| *p == preset)
+            .ok_or_else(|| format!("No animation config for preset '{}'", preset))?,
+        None => ConfigStore::latest(&recording_path)
+            .ok_or_else(|| "No animation config yet - run generate config first".to_string())?,
+    };
+    let yaml = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read {}: {}", config_path.display(), e))?;
+
+    Ok(AnimationConfigText {
+        preset,
+        file: relative(&recording_path, &config_path),
+        yaml,
+    })
+}
+
+/// Validate and save an edited animation config; the text is stored as written so
+/// the user's comments and formatting survive
+#[tauri::command]
+pub fn write_animation_config(
+    recording_name: String,
+    preset: String,
+    yaml_text: String,
+    config: State<AppConfig>
+) -> Result<AnimationConfigSaved, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    if let Some(status) = detect_in_progress_status(&recording_path) {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, status));
+    }
+    validate_animation_config(&yaml_text).map_err(|errors| errors.join("\n"))?;
+
+    let (config_path, backup) = ConfigStore::save_edited(&recording_path, &preset, &yaml_text)?;
+    log::info!("📝 Saved edited {} config of '{}'", preset, recording_name);
+    Ok(AnimationConfigSaved {
+        file: relative(&recording_path, &config_path),
+        backup: backup.map(|b| relative(&recording_path, &b)),
+        blend_outdated: fs::read_dir(recording_path.join("blender"))
+            .map(|entries| entries.flatten().any(|e| e.path().extension().is_some_and(|x| x == "blend")))
+            .unwrap_or(false),
+    })
+}
+
+fn relative(recording_path: &Path, path: &Path) -> String {
+    path.strip_prefix(recording_path).unwrap_or(path).to_string_lossy().to_string()
+}
+
+/// The requested preset's config, else the drifted one, else the only one
+fn select_config(
+    recording_path: &Path,
+    preset: Option<&str>,
+    drifts: &[ConfigDrift]
+) -> Result<(PathBuf, String), String> {
+    let configs = ConfigSync::configs(recording_path);
+
+    if let Some(preset) = preset {
+        return configs
+            .into_iter()
+            .find(|(_, p)| p == preset)
+            .ok_or_else(|| format!("No animation config for preset '{}'", preset));
+    }
+    if let Some(drift) = drifts.first() {
+        if let Some(config) = configs.iter().find(|(_, p)| *p == drift.preset) {
+            return Ok(config.clone());
+        }
+    }
+
+    match configs.len() {
+        0 => Err("No animation config to regenerate - run setup render first".to_string()),
+        1 => Ok(configs.into_iter().next().unwrap()),
+        _ => Err("Several animation configs found; choose a preset".to_string()),
+    }
+}
+
+/// Superseded animation configs of a recording, newest first
+#[tauri::command]
+pub fn get_config_history(recording_name: String, config: State<AppConfig>) -> Result<Vec<ConfigVersion>, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    Ok(ConfigStore::history(&recording_path))
+}
+
+/// Delete superseded configs, keeping the newest `keep` versions per preset;
+/// returns the removed files relative to the recording
+#[tauri::command]
+pub fn prune_config_history(
+    recording_name: String,
+    keep: Option<usize>,
+    config: State<AppConfig>
+) -> Result<Vec<String>, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    let removed = ConfigStore::prune(&recording_path, keep.unwrap_or(DEFAULT_CONFIG_HISTORY))?;
+    log::info!("🗂️ Pruned {} superseded configs of '{}'", removed.len(), recording_name);
+    Ok(removed)
+}
+
+fn restore_backup(backup: &Path, config_path: &Path) {
+    if let Err(e) = fs::copy(backup, config_path) {
+        log::error!("Failed to restore {} from backup: {}", config_path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_select_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+        assert!(select_config(recording, None, &[]).is_err());
+
+        fs::write(recording.join("animation_config_minimal.yaml"), "project: {}").unwrap();
+        assert_eq!(select_config(recording, None, &[]).unwrap().1, "minimal");
+
+        fs::write(recording.join("animation_config_beat-switch.yaml"), "project: {}").unwrap();
+        assert!(select_config(recording, None, &[]).is_err());
+        assert_eq!(select_config(recording, Some("beat-switch"), &[]).unwrap().1, "beat-switch");
+        assert!(select_config(recording, Some("multi-pip"), &[]).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use crate::commands::recordings::AppConfig;
+use crate::models::{CustomFieldDefinition, CustomFieldValue, Recording};
+use crate::services::{CustomFields, CustomFieldsStore, FileScanner};
+use std::path::Path;
+use tauri::State;
+
+/// List the custom field definitions from the app configuration
+#[tauri::command]
+pub fn get_custom_field_definitions(config: State<AppConfig>) -> Result<Vec<CustomFieldDefinition>, String> {
+    Ok(config.custom_fields.clone())
+}
+
+/// Get all custom field values set on a recording
+#[tauri::command]
+pub fn get_custom_fields(recording_name: String, config: State<AppConfig>) -> Result<CustomFields, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    CustomFieldsStore::load(&recording_path)
+}
+
+/// Set (or clear, when value is None) a custom field on a recording
+#[tauri::command]
+pub fn set_custom_field(
+    recording_name: String,
+    field: String,
+    value: Option<CustomFieldValue>,
+    config: State<AppConfig>
+) -> Result<CustomFields, String> {
+    set_custom_field_impl(&recording_name, &field, value, config.recording_root(&recording_name), &config.custom_fields)
+}
+
+/// Find recordings whose custom fields match a query, optionally limited to one field
+#[tauri::command]
+pub fn search_recordings_by_custom_fields(
+    query: String,
+    field: Option<String>,
+    config: State<AppConfig>
+) -> Result<Vec<Recording>, String> {
+    let mut recordings = FileScanner::scan_roots(&config.recordings_roots);
+    config.annotate_lifecycle(&mut recordings);
+    Ok(filter_by_custom_fields(recordings, &query, field.as_deref()))
+}
+
+/// Internal implementation for testing
+fn set_custom_field_impl(
+    recording_name: &str,
+    field: &str,
+    value: Option<CustomFieldValue>,
+    recordings_path: &Path,
+    definitions: &[CustomFieldDefinition]
+) -> Result<CustomFields, String> {
+    let definition = definitions
+        .iter()
+        .find(|d| d.name == field)
+        .ok_or_else(|| format!("Unknown custom field '{}'", field))?;
+
+    let recording_path = recordings_path.join(recording_name);
+    if !recording_path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    let mut fields = CustomFieldsStore::load(&recording_path)?;
+    match value {
+        Some(value) => {
+            definition.validate(&value)?;
+            fields.insert(field.to_string(), value);
+        }
+        None => {
+            fields.remove(field);
+        }
+    }
+
+    CustomFieldsStore::save(&recording_path, &fields)?;
+    log::info!("Updated custom field '{}' for recording '{}'", field, recording_name);
+    Ok(fields)
+}
+
+fn filter_by_custom_fields(recordings: Vec<Recording>, query: &str, field: Option<&str>) -> Vec<Recording> {
+    recordings
+        .into_iter()
+        .filter(|recording| {
+            let fields = CustomFieldsStore::load(&recording.path).unwrap_or_default();
+            fields
+                .iter()
+                .filter(|(name, _)| field.map_or(true, |f| f == name.as_str()))
+                .any(|(_, value)| value.matches_query(query))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn definitions() -> Vec<CustomFieldDefinition> {
+        CustomFieldDefinition::parse_list("client:text,setlist:list").unwrap()
+    }
+
+    #[test]
+    fn test_set_custom_field_validates_type() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("rec")).unwrap();
+
+        let result = set_custom_field_impl("rec", "client", Some(CustomFieldValue::Number(1.0)), temp_dir.path(), &definitions());
+        assert!(result.is_err());
+
+        let result = set_custom_field_impl("rec", "unknown", Some(CustomFieldValue::Text("x".to_string())), temp_dir.path(), &definitions());
+        assert!(result.unwrap_err().contains("Unknown custom field"));
+    }
+
+    #[test]
+    fn test_set_and_clear_custom_field() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("rec")).unwrap();
+
+        let fields = set_custom_field_impl("rec", "client", Some(CustomFieldValue::Text("ACME".to_string())), temp_dir.path(), &definitions()).unwrap();
+        assert_eq!(fields.get("client"), Some(&CustomFieldValue::Text("ACME".to_string())));
+
+        let fields = set_custom_field_impl("rec", "client", None, temp_dir.path(), &definitions()).unwrap();
+        assert!(fields.is_empty());
+    }
+}
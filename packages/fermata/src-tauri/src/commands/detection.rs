@@ -0,0 +1,17 @@
+use crate::services::{RecordingDetection, SettingsStore};
+use tauri::State;
+
+/// What makes a directory in a recordings root a recording
+#[tauri::command]
+pub fn get_recording_detection(settings: State<SettingsStore>) -> Result<RecordingDetection, String> {
+    RecordingDetection::load(&settings)
+}
+
+/// Takes effect for the next scan
+#[tauri::command]
+pub fn set_recording_detection(detection: RecordingDetection, settings: State<SettingsStore>) -> Result<RecordingDetection, String> {
+    detection.save(&settings)?;
+    detection.clone().apply();
+    log::info!("🔎 Recording markers: {}", detection.markers.join(", "));
+    Ok(detection)
+}
@@ -0,0 +1,20 @@
+use crate::services::{ProcessEnvironment, SettingsStore};
+use tauri::State;
+
+/// Environment variables set for pipeline processes, globally and per step
+#[tauri::command]
+pub fn get_process_environment(settings: State<SettingsStore>) -> Result<ProcessEnvironment, String> {
+    ProcessEnvironment::load(&settings)
+}
+
+/// Takes effect for the next process a step starts
+#[tauri::command]
+pub fn set_process_environment(environment: ProcessEnvironment, settings: State<SettingsStore>) -> Result<ProcessEnvironment, String> {
+    environment.save(&settings)?;
+    log::info!(
+        "🌱 Process environment: {} global variables, overrides for {} steps",
+        environment.global.len(),
+        environment.steps.len()
+    );
+    Ok(environment)
+}
@@ -0,0 +1,494 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, State};
+use crate::commands::recordings::AppConfig;
+use crate::commands::video::find_playable_video;
+use crate::services::{
+    detect_in_progress_status, write_atomic, ArchiveManifest, ExportScope, RecordingArchive, SpacePreflight, AUDIO_EXTENSIONS, CUSTOM_FIELDS_FILE,
+};
+
+/// Event emitted with an ArchiveProgress while export_recording writes an archive
+pub const EXPORT_PROGRESS_EVENT: &str = "export-progress";
+
+/// Folders of the editing handoff layout, numbered so they sort in a sensible order
+pub const MASTER_DIR: &str = "01_master";
+pub const STEMS_DIR: &str = "02_stems";
+pub const VIDEO_SOURCES_DIR: &str = "03_video_sources";
+pub const MARKERS_DIR: &str = "04_markers";
+pub const TRANSCRIPT_DIR: &str = "05_transcript";
+
+/// Timeline frame rate assumed for the Resolve XML (fermata does not probe media)
+const RESOLVE_TIMEBASE: u32 = 30;
+
+const VIDEO_EXTENSIONS: [&str; 4] = ["mkv", "mp4", "avi", "mov"];
+const TRANSCRIPT_EXTENSIONS: [&str; 3] = ["srt", "vtt", "txt"];
+
+/// A single marker taken from the audio analysis
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ExportMarker {
+    pub time: f64,
+    pub duration: f64,
+    pub kind: String,
+    pub label: String,
+}
+
+/// What was written to the export folder
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct EditingExportReport {
+    pub export_path: PathBuf,
+    pub master: Option<String>,
+    pub stems: Vec<String>,
+    pub video_sources: Vec<String>,
+    pub markers: usize,
+    pub transcripts: Vec<String>,
+    pub resolve_xml: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Where an archive was written and what it contains
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingExport {
+    pub archive_path: PathBuf,
+    pub manifest: ArchiveManifest,
+}
+
+/// Package a recording into `<destination>/<recording>.tar` for cold storage,
+/// emitting EXPORT_PROGRESS_EVENT while large files are copied
+#[tauri::command]
+pub async fn export_recording(
+    recording_name: String,
+    destination: String,
+    include: ExportScope,
+    app: AppHandle,
+    config: State<'_, AppConfig>
+) -> Result<RecordingExport, String> {
+    log::info!("📦 Archiving recording '{}' ({:?}) to {}", recording_name, include, destination);
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    if let Some(status) = detect_in_progress_status(&recording_path) {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, status));
+    }
+    match SpacePreflight::export(&recording_path, Path::new(&destination), include) {
+        Ok(preflight) => preflight.ensure()?,
+        Err(e) => log::warn!("Skipping free space check: {}", e),
+    }
+
+    let created_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (archive_path, manifest) = tauri::async_runtime::spawn_blocking(move || {
+        let destination = Path::new(&destination);
+        RecordingArchive::export(&recording_path, &recording_name, destination, include, created_at, |progress| {
+            if let Err(e) = app.emit(EXPORT_PROGRESS_EVENT, progress) {
+                log::error!("Failed to emit {}: {}", EXPORT_PROGRESS_EVENT, e);
+            }
+        })
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))??;
+
+    log::info!(
+        "📦 Archived {} files ({} MB) to {}",
+        manifest.files.len(),
+        manifest.total_bytes / (1024 * 1024),
+        archive_path.display()
+    );
+    Ok(RecordingExport { archive_path, manifest })
+}
+
+/// Tauri command to export a recording into an editing-friendly folder layout
+#[tauri::command]
+pub fn export_for_editing(
+    recording_name: String,
+    destination: String,
+    include_resolve_xml: Option<bool>,
+    config: State<AppConfig>
+) -> Result<EditingExportReport, String> {
+    log::info!("📦 Exporting recording '{}' for editing to {}", recording_name, destination);
+    export_for_editing_impl(
+        &config.recording_path(&recording_name),
+        Path::new(&destination),
+        include_resolve_xml.unwrap_or(false),
+    )
+}
+
+/// Lay out master, stems, markers and transcripts under `<destination>/<recording name>/`
+pub fn export_for_editing_impl(
+    recording_path: &Path,
+    destination: &Path,
+    include_resolve_xml: bool
+) -> Result<EditingExportReport, String> {
+    let recording_name = recording_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| "Invalid recording directory name".to_string())?;
+
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    let export_path = destination.join(recording_name);
+    if export_path.exists() {
+        return Err(format!("Export target already exists: {}", export_path.display()));
+    }
+    fs::create_dir_all(&export_path)
+        .map_err(|e| format!("Failed to create export directory {}: {}", export_path.display(), e))?;
+
+    let mut report = EditingExportReport {
+        export_path: export_path.clone(),
+        master: None,
+        stems: Vec::new(),
+        video_sources: Vec::new(),
+        markers: 0,
+        transcripts: Vec::new(),
+        resolve_xml: None,
+        warnings: Vec::new(),
+    };
+
+    // Master video
+    match find_playable_video(recording_path, recording_name) {
+        Some(master) => report.master = Some(place_file(&master, &export_path.join(MASTER_DIR))?),
+        None => report.warnings.push("No master video found".to_string()),
+    }
+
+    // Extracted stems and per-source videos
+    let extracted = files_with_extensions(&recording_path.join("extracted"), &AUDIO_EXTENSIONS);
+    for stem in &extracted {
+        report.stems.push(place_file(stem, &export_path.join(STEMS_DIR))?);
+    }
+    for video in files_with_extensions(&recording_path.join("extracted"), &VIDEO_EXTENSIONS) {
+        report.video_sources.push(place_file(&video, &export_path.join(VIDEO_SOURCES_DIR))?);
+    }
+    if extracted.is_empty() {
+        report.warnings.push("No extracted audio stems found, run the extract step first".to_string());
+    }
+
+    // Markers from the audio analysis
+    let analysis_files = files_with_extensions(&recording_path.join("analysis"), &["json"]);
+    let markers_dir = export_path.join(MARKERS_DIR);
+    let mut markers = Vec::new();
+    for analysis_file in &analysis_files {
+        place_file(analysis_file, &markers_dir)?;
+        match read_json(analysis_file) {
+            Some(analysis) => markers.extend(markers_from_analysis(&analysis)),
+            None => report.warnings.push(format!("Could not parse {}", analysis_file.display())),
+        }
+    }
+    if markers.is_empty() {
+        report.warnings.push("No markers found in audio analysis".to_string());
+    } else {
+        markers.sort_by(|a, b| a.time.total_cmp(&b.time));
+        write_file(&markers_dir.join("markers.csv"), &markers_to_csv(&markers))?;
+    }
+    report.markers = markers.len();
+
+    // Transcripts next to the recording or its extracted media
+    let mut transcripts = files_with_extensions(recording_path, &TRANSCRIPT_EXTENSIONS);
+    transcripts.extend(files_with_extensions(&recording_path.join("extracted"), &TRANSCRIPT_EXTENSIONS));
+    for transcript in transcripts {
+        report.transcripts.push(place_file(&transcript, &export_path.join(TRANSCRIPT_DIR))?);
+    }
+
+    // Custom fields travel with the export so the editor sees client, setlist, etc.
+    let custom_fields = recording_path.join(CUSTOM_FIELDS_FILE);
+    if custom_fields.exists() {
+        fs::copy(&custom_fields, export_path.join("custom_fields.json"))
+            .map_err(|e| format!("Failed to copy custom fields: {}", e))?;
+    }
+
+    if include_resolve_xml {
+        match &report.master {
+            Some(master) => {
+                let duration = analysis_files
+                    .iter()
+                    .filter_map(|f| read_json(f))
+                    .find_map(|a| a.get("duration").and_then(Value::as_f64));
+                let master_path = export_path.join(MASTER_DIR).join(master);
+                let xml = resolve_xml(recording_name, &master_path, duration, &markers);
+                let xml_name = format!("{}.xml", recording_name);
+                write_file(&export_path.join(&xml_name), &xml)?;
+                report.resolve_xml = Some(xml_name);
+            }
+            None => report.warnings.push("Skipped Resolve XML: no master video".to_string()),
+        }
+    }
+
+    log::info!(
+        "✅ Exported '{}' for editing: {} stems, {} markers, {} warnings",
+        recording_name, report.stems.len(), report.markers, report.warnings.len()
+    );
+    Ok(report)
+}
+
+/// Markers for section starts and beats from a beatrix analysis file
+pub fn markers_from_analysis(analysis: &Value) -> Vec<ExportMarker> {
+    let events = &analysis["animation_events"];
+    let mut markers = Vec::new();
+
+    if let Some(sections) = events["sections"].as_array() {
+        for (index, section) in sections.iter().enumerate() {
+            let Some(start) = section["start"].as_f64() else {
+                continue;
+            };
+            let end = section["end"].as_f64().unwrap_or(start);
+            let label = section["label"]
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("section_{}", index + 1));
+            markers.push(ExportMarker {
+                time: start,
+                duration: (end - start).max(0.0),
+                kind: "section".to_string(),
+                label,
+            });
+        }
+    }
+
+    let beats = events["beats"]
+        .as_array()
+        .or_else(|| analysis["tempo"]["beat_times"].as_array());
+    if let Some(beats) = beats {
+        for (index, beat) in beats.iter().filter_map(Value::as_f64).enumerate() {
+            markers.push(ExportMarker {
+                time: beat,
+                duration: 0.0,
+                kind: "beat".to_string(),
+                label: format!("beat_{}", index + 1),
+            });
+        }
+    }
+
+    markers
+}
+
+fn markers_to_csv(markers: &[ExportMarker]) -> String {
+    let mut csv = String::from("time,duration,type,label\n");
+    for marker in markers {
+        csv.push_str(&format!(
+            "{:.3},{:.3},{},{}\n",
+            marker.time, marker.duration, marker.kind, marker.label.replace(',', " ")
+        ));
+    }
+    csv
+}
+
+/// Final Cut Pro 7 XML, which DaVinci Resolve imports as a timeline with markers.
+/// Only section markers are included; beats would flood the timeline.
+fn resolve_xml(name: &str, master_path: &Path, duration: Option<f64>, markers: &[ExportMarker]) -> String {
+    let frames = |seconds: f64| (seconds * RESOLVE_TIMEBASE as f64).round() as u64;
+    let duration_frames = duration.map(frames).unwrap_or(0);
+    let rate = format!("<rate><timebase>{}</timebase><ntsc>FALSE</ntsc></rate>", RESOLVE_TIMEBASE);
+    let file_name = master_path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+
+    let marker_xml: String = markers
+        .iter()
+        .filter(|m| m.kind == "section")
+        .map(|m| {
+            format!(
+                "      <marker><name>{}</name><comment></comment><in>{}</in><out>-1</out></marker>\n",
+                xml_escape(&m.label),
+                frames(m.time)
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE xmeml>
+<xmeml version="5">
+  <sequence>
+    <name>{name}</name>
+    <duration>{duration}</duration>
+    {rate}
+    <media>
+      <video>
+        <track>
+          <clipitem id="master">
+            <name>{file_name}</name>
+            <start>0</start>
+            <end>{duration}</end>
+            <in>0</in>
+            <out>{duration}</out>
+            <file id="master-file">
+              <name>{file_name}</name>
+              <pathurl>{path}</pathurl>
+              {rate}
+            </file>
+          </clipitem>
+        </track>
+      </video>
+    </media>
+{markers}  </sequence>
+</xmeml>
+"#,
+        name = xml_escape(name),
+        duration = duration_frames,
+        rate = rate,
+        file_name = xml_escape(file_name),
+        path = xml_escape(&file_url(master_path)),
+        markers = marker_xml,
+    )
+}
+
+/// `file://` URL of an absolute path, percent-encoded as NLEs expect in pathurl
+fn file_url(path: &Path) -> String {
+    let mut path = path.to_string_lossy().to_string();
+    if cfg!(windows) {
+        path = path.replace('\\', "/");
+    }
+    let encoded: String = path
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => (b as char).to_string(),
+            // Keeps the drive letter of Windows paths, C:/...
+            b':' => ":".to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect();
+    if encoded.starts_with('/') {
+        format!("file://{}", encoded)
+    } else {
+        format!("file:///{}", encoded)
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Copy a file into the target directory. The export is edited independently of the
+/// recording, so it gets its own copy; fs::copy clones the data where the filesystem can.
+fn place_file(source: &Path, target_dir: &Path) -> Result<String, String> {
+    fs::create_dir_all(target_dir)
+        .map_err(|e| format!("Failed to create {}: {}", target_dir.display(), e))?;
+
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| format!("Invalid file path: {}", source.display()))?;
+    let target = target_dir.join(file_name);
+
+    fs::copy(source, &target)
+        .map_err(|e| format!("Failed to copy {} to {}: {}", source.display(), target.display(), e))?;
+
+    Ok(file_name.to_string_lossy().to_string())
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    write_atomic(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn files_with_extensions(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_file())
+                .filter(|p| {
+                    p.extension()
+                        .and_then(|e| e.to_str())
+                        .is_some_and(|e| extensions.contains(&e.to_lowercase().as_str()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup_recording(root: &Path, name: &str) -> PathBuf {
+        let recording_path = root.join(name);
+        fs::create_dir_all(recording_path.join("extracted")).unwrap();
+        fs::create_dir_all(recording_path.join("analysis")).unwrap();
+        fs::write(recording_path.join(format!("{}.mkv", name)), "video").unwrap();
+        fs::write(recording_path.join("extracted/Mic.m4a"), "audio").unwrap();
+        fs::write(recording_path.join("extracted/Camera.mp4"), "camera").unwrap();
+        fs::write(recording_path.join("extracted/Mic.srt"), "1\n00:00:01,000 --> 00:00:02,000\nHi\n").unwrap();
+        fs::write(
+            recording_path.join("analysis/main_audio_analysis.json"),
+            r#"{
+                "duration": 10.0,
+                "tempo": {"bpm": 120.0, "beat_times": [0.5, 1.0]},
+                "animation_events": {
+                    "beats": [0.5, 1.0],
+                    "sections": [{"start": 0.0, "end": 4.0, "label": "intro"}, {"start": 4.0, "end": 10.0, "label": "verse"}]
+                }
+            }"#,
+        )
+        .unwrap();
+        recording_path
+    }
+
+    #[test]
+    fn test_export_for_editing_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording_path = setup_recording(temp_dir.path(), "gig");
+        let destination = temp_dir.path().join("handoff");
+
+        let report = export_for_editing_impl(&recording_path, &destination, true).unwrap();
+        let export_path = destination.join("gig");
+
+        assert_eq!(report.master.as_deref(), Some("gig.mkv"));
+        assert_eq!(report.stems, vec!["Mic.m4a"]);
+        assert_eq!(report.video_sources, vec!["Camera.mp4"]);
+        assert_eq!(report.transcripts, vec!["Mic.srt"]);
+        assert_eq!(report.markers, 4);
+        assert!(export_path.join(MASTER_DIR).join("gig.mkv").exists());
+        assert!(export_path.join(MARKERS_DIR).join("main_audio_analysis.json").exists());
+
+        let csv = fs::read_to_string(export_path.join(MARKERS_DIR).join("markers.csv")).unwrap();
+        assert!(csv.contains("4.000,6.000,section,verse"));
+
+        let xml = fs::read_to_string(export_path.join("gig.xml")).unwrap();
+        assert!(xml.contains("<duration>300</duration>"));
+        assert!(xml.contains("<marker><name>verse</name><comment></comment><in>120</in>"));
+        assert!(!xml.contains("beat_1"));
+        assert!(xml.contains(&format!("<pathurl>{}</pathurl>", file_url(&export_path.join(MASTER_DIR).join("gig.mkv")))));
+
+        fs::write(export_path.join(MASTER_DIR).join("gig.mkv"), "edited").unwrap();
+        assert_ne!(fs::read_to_string(recording_path.join("gig.mkv")).unwrap(), "edited");
+    }
+
+    #[test]
+    fn test_file_url_is_percent_encoded() {
+        assert_eq!(file_url(Path::new("/home/me/Koncert ąę/gig #1.mkv")), "file:///home/me/Koncert%20%C4%85%C4%99/gig%20%231.mkv");
+    }
+
+    #[test]
+    fn test_export_refuses_existing_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording_path = setup_recording(temp_dir.path(), "gig");
+        let destination = temp_dir.path().join("handoff");
+        fs::create_dir_all(destination.join("gig")).unwrap();
+
+        let result = export_for_editing_impl(&recording_path, &destination, false);
+        assert!(result.unwrap_err().contains("already exists"));
+    }
+
+    #[test]
+    fn test_markers_from_analysis_falls_back_to_tempo_beats() {
+        let analysis = serde_json::json!({"tempo": {"beat_times": [0.25]}, "animation_events": {}});
+        let markers = markers_from_analysis(&analysis);
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].kind, "beat");
+    }
+}
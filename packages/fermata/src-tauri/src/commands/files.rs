@@ -0,0 +1,40 @@
+use crate::commands::recordings::AppConfig;
+use crate::services::{BlendVersions, FileManager};
+use std::path::Path;
+use tauri::State;
+
+/// Open a recording's directory in the system file manager
+#[tauri::command]
+pub fn open_recording_folder(recording_name: String, config: State<AppConfig>) -> Result<(), String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    log::info!("📂 Opening folder {}", recording_path.display());
+    FileManager::open_folder(&recording_path)
+}
+
+/// Open the active .blend project of a recording in the configured Blender, for
+/// tweaking it by hand before rendering
+#[tauri::command]
+pub fn open_in_blender(recording_name: String, config: State<AppConfig>) -> Result<(), String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    let blend_file = BlendVersions::active(&recording_path)
+        .ok_or_else(|| "No .blend file found - run setup render step first".to_string())?;
+
+    log::info!("🎨 Opening {} in Blender", blend_file.display());
+    FileManager::open_in_blender(&config.cli_paths.blender_path, &blend_file)
+}
+
+/// Show a file, e.g. the blend file or render output, in the system file manager
+#[tauri::command]
+pub fn reveal_file(path: String, config: State<AppConfig>) -> Result<(), String> {
+    let file = FileManager::resolve_within_roots(Path::new(&path), &config.recordings_roots)?;
+
+    log::info!("📂 Revealing {}", file.display());
+    FileManager::reveal(&file)
+}
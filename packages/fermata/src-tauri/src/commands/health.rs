@@ -0,0 +1,93 @@
+use crate::commands::recordings::{refresh_recordings_in_background, AppConfig};
+use crate::services::{
+    check_disk_space, check_writable, tool_version, HealthCheck, HealthReport, HealthStatus, ProcessEnvironment, ProcessRunner, RepairOutcome,
+    RepairReport, ScanSnapshotStore, SettingsStore, Toolchain, WORKSPACE_PACKAGES
+};
+use tauri::{AppHandle, Manager, State};
+
+/// Check the tools and paths the pipeline depends on, for the startup diagnostics panel
+#[tauri::command]
+pub async fn run_health_check(config: State<'_, AppConfig>, settings: State<'_, SettingsStore>) -> Result<HealthReport, String> {
+    log::info!("🩺 Running health check");
+    let toolchain = Toolchain::load(&settings)?;
+    let runner = ProcessRunner::new(
+        config.cli_paths.workspace_root.clone(),
+        config.cli_paths.uv_path.clone()
+    )
+    .with_environment(ProcessEnvironment::load(&settings)?.for_step(None))
+    .with_toolchain(toolchain.clone());
+    let mut checks = Vec::new();
+
+    let version = runner.toolchain_version().await;
+    let toolchain_available = version.is_ok();
+    checks.push(HealthCheck::from_result("toolchain", toolchain.label(), version, true));
+
+    let workspace_root = &config.cli_paths.workspace_root;
+    let workspace = if workspace_root.join("pyproject.toml").exists() {
+        Ok(workspace_root.display().to_string())
+    } else {
+        Err(format!("No pyproject.toml in {}", workspace_root.display()))
+    };
+    let workspace_available = workspace.is_ok();
+    checks.push(HealthCheck::from_result("workspace", "Workspace root", workspace, true));
+
+    for package in WORKSPACE_PACKAGES {
+        let result = if toolchain_available && workspace_available {
+            runner.check_package(package).await.map(|_| "available".to_string())
+        } else {
+            Err(format!("Skipped: {} or workspace unavailable", toolchain.label()))
+        };
+        checks.push(HealthCheck::from_result(&format!("package_{}", package), package, result, true));
+    }
+
+    // Optional tools: Blender repairs paths in .blend files, ffmpeg transcodes uploads,
+    // ffprobe aligns sources without start times in metadata.json
+    checks.push(HealthCheck::from_result(
+        "blender",
+        "Blender",
+        tool_version(&config.cli_paths.blender_path, "--version").await,
+        false
+    ));
+    checks.push(HealthCheck::from_result("ffmpeg", "ffmpeg", tool_version("ffmpeg", "-version").await, false));
+    checks.push(HealthCheck::from_result("ffprobe", "ffprobe", tool_version("ffprobe", "-version").await, false));
+
+    checks.push(HealthCheck::from_result(
+        "recordings_path",
+        "Recordings directory",
+        check_writable(&config.recordings_path),
+        true
+    ));
+    checks.push(check_disk_space(&config.recordings_path, config.min_free_space_gb));
+
+    for check in checks.iter().filter(|c| c.status != HealthStatus::Ok) {
+        log::warn!("🩺 {}: {:?} - {}", check.label, check.status, check.detail);
+    }
+    Ok(HealthReport::new(checks))
+}
+
+/// Safe-mode recovery for when the library shows up empty or broken: validate the
+/// settings, check the recordings roots, clear an unreadable recordings cache and
+/// rebuild unreadable state files. Doesn't rely on scanning; with `dry_run` only reports.
+#[tauri::command]
+pub fn diagnose_and_repair(
+    dry_run: Option<bool>,
+    app: AppHandle,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<RepairReport, String> {
+    let mut report = RepairReport::new(dry_run.unwrap_or(false));
+    report.check_settings(&settings);
+    report.check_roots(&config.recordings_roots);
+    if let Some(snapshots) = app.try_state::<ScanSnapshotStore>() {
+        report.check_scan_cache(&snapshots);
+    }
+    report.check_recordings(&config.recordings_roots);
+
+    for action in report.actions.iter().filter(|a| a.outcome != RepairOutcome::Ok) {
+        log::warn!("🛠️ {}: {:?} - {}", action.check, action.outcome, action.detail);
+    }
+    if report.repaired() && !report.dry_run {
+        tauri::async_runtime::spawn(refresh_recordings_in_background(app));
+    }
+    Ok(report)
+}
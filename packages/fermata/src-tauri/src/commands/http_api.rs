@@ -0,0 +1,255 @@
+use crate::commands::jobs::list_jobs;
+use crate::commands::observer::{check_command, record_activity};
+use crate::commands::operations::{available_steps_impl, execute_named_step, run_specific_step};
+use crate::commands::recordings::{get_recordings, recording_details_impl, AppConfig};
+use crate::models::{NextStep, Recording};
+use crate::services::{FileScanner, Job, JobQueue, SettingsStore};
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+/// Address to serve the API on, e.g. "0.0.0.0:8765"; the API is off without it
+pub const HTTP_API_ADDR_ENV: &str = "FERMATA_HTTP_API_ADDR";
+
+/// Token clients send as `Authorization: Bearer <token>`; required to start the API
+pub const HTTP_API_TOKEN_ENV: &str = "FERMATA_HTTP_API_TOKEN";
+
+/// Shortest token accepted, so a guessable one cannot open the pipeline to the network
+const MIN_TOKEN_LENGTH: usize = 16;
+
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl ApiError {
+    /// Refused because the app only observes the library; recorded in the activity log either way
+    fn check_command(backend: &ApiBackend, command: &str, arguments: serde_json::Value) -> Result<(), Self> {
+        let config = backend.config();
+        let refused = check_command(config, command).err();
+        record_activity(config, "http-api", command, arguments, refused.clone());
+        refused.map_or(Ok(()), |e| Err(Self(StatusCode::FORBIDDEN, e)))
+    }
+}
+
+/// What the API serves: the running app, or the settings `fermata-cli serve` loaded
+#[derive(Clone)]
+enum ApiBackend {
+    App(AppHandle),
+    Headless(Arc<(AppConfig, SettingsStore)>),
+}
+
+impl ApiBackend {
+    fn config(&self) -> &AppConfig {
+        match self {
+            ApiBackend::App(app) => app.state::<AppConfig>().inner(),
+            ApiBackend::Headless(state) => &state.0,
+        }
+    }
+
+    fn settings(&self) -> &SettingsStore {
+        match self {
+            ApiBackend::App(app) => app.state::<SettingsStore>().inner(),
+            ApiBackend::Headless(state) => &state.1,
+        }
+    }
+
+    /// Jobs are run by the app's job worker, which a headless server doesn't have
+    fn app(&self) -> Result<&AppHandle, ApiError> {
+        match self {
+            ApiBackend::App(app) => Ok(app),
+            ApiBackend::Headless(_) => Err(ApiError(StatusCode::NOT_IMPLEMENTED, "Jobs need the desktop app's job worker".to_string())),
+        }
+    }
+}
+
+impl From<String> for ApiError {
+    fn from(message: String) -> Self {
+        let status = if message.contains("not found") { StatusCode::NOT_FOUND } else { StatusCode::BAD_REQUEST };
+        Self(status, message)
+    }
+}
+
+#[derive(Deserialize)]
+struct RunStepRequest {
+    /// Upload profile for the upload step
+    profile: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct QueueJobRequest {
+    recording_name: String,
+    target_step: String,
+    preset: Option<String>,
+}
+
+/// Serve the API from the running app when FERMATA_HTTP_API_ADDR is set. Handlers call
+/// the Tauri commands with the app's state, so both share one implementation.
+pub fn start_http_api(app: &AppHandle) {
+    let Ok(addr) = std::env::var(HTTP_API_ADDR_ENV) else {
+        return;
+    };
+    let token = match api_token() {
+        Ok(token) => token,
+        Err(e) => {
+            log::error!("HTTP API disabled: {}", e);
+            return;
+        }
+    };
+
+    let router = router(ApiBackend::App(app.clone()), token);
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = serve(&addr, router).await {
+            log::error!("HTTP API stopped: {}", e);
+        }
+    });
+}
+
+/// Serve the API without the desktop app, for a pipeline machine nobody sits at
+/// (`fermata-cli serve`). Steps run in this process; jobs need the app.
+pub fn serve_headless(config: AppConfig, settings: SettingsStore) -> Result<(), String> {
+    let addr = std::env::var(HTTP_API_ADDR_ENV).map_err(|_| format!("{} is not set", HTTP_API_ADDR_ENV))?;
+    let router = router(ApiBackend::Headless(Arc::new((config, settings))), api_token()?);
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| format!("Failed to start runtime: {}", e))?;
+    runtime.block_on(serve(&addr, router))
+}
+
+fn api_token() -> Result<String, String> {
+    let token = std::env::var(HTTP_API_TOKEN_ENV).unwrap_or_default();
+    if token.len() < MIN_TOKEN_LENGTH {
+        return Err(format!("{} must be at least {} characters", HTTP_API_TOKEN_ENV, MIN_TOKEN_LENGTH));
+    }
+    Ok(token)
+}
+
+async fn serve(addr: &str, router: Router) -> Result<(), String> {
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| format!("Cannot listen on {}: {}", addr, e))?;
+    log::info!("🌐 HTTP API listening on {}", addr);
+    axum::serve(listener, router).await.map_err(|e| e.to_string())
+}
+
+fn router(backend: ApiBackend, token: String) -> Router {
+    Router::new()
+        .route("/api/recordings", get(recordings))
+        .route("/api/recordings/:name", get(recording))
+        .route("/api/recordings/:name/steps", get(available_steps))
+        .route("/api/recordings/:name/steps/:step", post(run_step))
+        .route("/api/jobs", get(jobs).post(queue_job))
+        .layer(middleware::from_fn_with_state(token, require_token))
+        .with_state(backend)
+}
+
+async fn require_token(State(token): State<String>, request: Request, next: Next) -> Response {
+    let header = request.headers().get(header::AUTHORIZATION).and_then(|value| value.to_str().ok());
+    if !authorized(header, &token) {
+        return ApiError(StatusCode::UNAUTHORIZED, "Missing or wrong API token".to_string()).into_response();
+    }
+    next.run(request).await
+}
+
+/// Compares in constant time, so response timing does not leak the token
+fn authorized(header: Option<&str>, token: &str) -> bool {
+    let Some(given) = header.and_then(|value| value.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    given.len() == token.len() && given.bytes().zip(token.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+async fn recordings(State(backend): State<ApiBackend>) -> Result<Json<Vec<Recording>>, ApiError> {
+    let recordings = match &backend {
+        ApiBackend::App(app) => get_recordings(None, app.state(), app.state())?,
+        ApiBackend::Headless(_) => {
+            let config = backend.config();
+            let mut recordings = FileScanner::scan_roots(&config.recordings_roots);
+            config.annotate_lifecycle(&mut recordings);
+            recordings
+        }
+    };
+    Ok(Json(recordings))
+}
+
+async fn recording(State(backend): State<ApiBackend>, Path(name): Path<String>) -> Result<Json<Recording>, ApiError> {
+    Ok(Json(recording_details_impl(&name, backend.config(), backend.settings())?))
+}
+
+async fn available_steps(State(backend): State<ApiBackend>, Path(name): Path<String>) -> Result<Json<Vec<String>>, ApiError> {
+    Ok(Json(available_steps_impl(&name, backend.config(), backend.settings())?))
+}
+
+/// Start a step and answer right away; progress shows in the recording status and step logs
+async fn run_step(
+    State(backend): State<ApiBackend>,
+    Path((name, step)): Path<(String, String)>,
+    body: Option<Json<RunStepRequest>>,
+) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    let profile = body.and_then(|Json(body)| body.profile);
+    let arguments = json!({ "recordingName": name, "step": step, "profile": profile });
+    ApiError::check_command(&backend, "run_specific_step", arguments)?;
+    // Available steps are ids, "setup_render" or a custom step's id
+    let available = available_steps_impl(&name, backend.config(), backend.settings())?;
+    if !available.contains(&step) {
+        return Err(ApiError(StatusCode::CONFLICT, format!("Step '{}' cannot run for '{}' now", step, name)));
+    }
+
+    let response = json!({ "recording_name": name, "step": step });
+    let task = async move {
+        let result = match &backend {
+            ApiBackend::App(app) => run_specific_step(name.clone(), step.clone(), profile, app.clone(), app.state(), app.state(), app.state()).await,
+            ApiBackend::Headless(state) => execute_named_step(&name, &step, profile.as_deref(), &state.0, &state.1, None, None)
+                .await
+                .and_then(|(_, result)| result)
+                .and_then(|result| if result.success { Ok(String::new()) } else { Err(result.stderr) }),
+        };
+        if let Err(e) = result {
+            log::warn!("🌐 Step {} of '{}' started over HTTP failed: {}", step, name, e);
+        }
+    };
+    tauri::async_runtime::spawn(task);
+    Ok((StatusCode::ACCEPTED, Json(response)))
+}
+
+async fn jobs(State(backend): State<ApiBackend>) -> Result<Json<Vec<Job>>, ApiError> {
+    let app = backend.app()?;
+    Ok(Json(list_jobs(app.state(), app.state())?))
+}
+
+async fn queue_job(State(backend): State<ApiBackend>, Json(request): Json<QueueJobRequest>) -> Result<(StatusCode, Json<Job>), ApiError> {
+    let arguments = json!({ "recordingName": request.recording_name, "targetStep": request.target_step, "preset": request.preset });
+    ApiError::check_command(&backend, "schedule_job", arguments)?;
+    let app = backend.app()?;
+    request.target_step.parse::<NextStep>()?;
+    if !backend.config().recording_path(&request.recording_name).is_dir() {
+        return Err(ApiError(StatusCode::NOT_FOUND, format!("Recording '{}' not found", request.recording_name)));
+    }
+    app.state::<JobQueue>()
+        .enqueue(&request.recording_name, &request.target_step, request.preset, "http-api")
+        .map(|job| (StatusCode::CREATED, Json(job)))
+        .ok_or_else(|| ApiError(StatusCode::CONFLICT, format!("'{}' already has a queued job", request.recording_name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authorized() {
+        let token = "0123456789abcdef";
+        assert!(authorized(Some("Bearer 0123456789abcdef"), token));
+        assert!(!authorized(Some("Bearer 0123456789abcdeX"), token));
+        assert!(!authorized(Some("Bearer 0123"), token));
+        assert!(!authorized(Some("0123456789abcdef"), token));
+        assert!(!authorized(None, token));
+    }
+}
@@ -0,0 +1,501 @@
+use crate::commands::operations::{run_next_step, run_setup_render, run_specific_step};
+use crate::commands::recordings::AppConfig;
+use crate::models::{NextStep, Recording, RecordingStatus, SetupRenderOptions};
+use crate::services::{
+    estimate_remaining, is_stall_error, BacklogPlan, find_interrupted_step, message, message_with, processes_suspended, recording_video_size,
+    resolve_interrupted_step as resolve_step, resume_processes, suspend_processes, update_recording_status, within_target, AutoIngestPolicy, CurrentLocale, FileScanner, IngestTracker, InterruptedStep, Job,
+    JobAttempt, JobQueue, JobStatus, JobWindow, Notifier, BacklogSkip, PipelineTemplates, RecordingChange, RetryPlan, ScanOptions, SettingsStore, SnoozeStore, StallAction, StallDetection,
+    StatusDetector, UploadRetryPolicy
+};
+use chrono::Timelike;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+/// How often candidate recordings are checked for a growing .mkv
+const INGEST_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often the job worker looks for scheduled jobs that became due
+const SCHEDULE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// New recordings waiting for OBS to finish writing them
+#[derive(Default)]
+pub struct AutoIngestState {
+    tracker: Mutex<IngestTracker>,
+}
+
+/// List queued, running and recently finished jobs, unfinished ones with an ETA
+#[tauri::command]
+pub fn list_jobs(queue: State<JobQueue>, config: State<AppConfig>) -> Result<Vec<Job>, String> {
+    let mut jobs = queue.list();
+    if jobs.iter().all(Job::is_finished) {
+        return Ok(jobs);
+    }
+
+    let recordings = FileScanner::scan_roots_with(&config.recordings_roots, &ScanOptions::fast());
+    let paths: Vec<&Path> = recordings.iter().map(|r| r.path.as_path()).collect();
+    for job in jobs.iter_mut().filter(|job| !job.is_finished()) {
+        if let Ok(target) = job.target_step.parse::<NextStep>() {
+            job.estimated_ms = estimate_remaining(&config.recording_path(&job.recording_name), &target, &paths);
+        }
+    }
+    Ok(jobs)
+}
+
+/// Queue a job that starts no earlier than `run_after` (Unix time), or right away without it
+#[tauri::command]
+pub fn schedule_job(
+    recording_name: String,
+    target_step: String,
+    preset: Option<String>,
+    run_after: Option<u64>,
+    queue: State<JobQueue>
+) -> Result<Job, String> {
+    target_step.parse::<NextStep>()?;
+    let job = queue
+        .enqueue_at(&recording_name, &target_step, preset, "manual", run_after)
+        .ok_or_else(|| format!("'{}' already has a queued job", recording_name))?;
+    log::info!("🗓️ Job {} for '{}' up to {} scheduled after {:?}", job.id, recording_name, target_step, run_after);
+    Ok(job)
+}
+
+/// Plan jobs taking every recording made since `since` (YYYY-MM-DD) up to `target_status`,
+/// following `template` or else each recording's own; call once to see the plan, then
+/// with `confirm: true` to queue it. The job worker runs the jobs one at a time.
+#[tauri::command]
+pub fn process_backlog(
+    since: String,
+    target_status: RecordingStatus,
+    template: Option<String>,
+    confirm: Option<bool>,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>,
+    queue: State<JobQueue>
+) -> Result<BacklogPlan, String> {
+    let template = match &template {
+        Some(name) => Some(
+            PipelineTemplates::list(&settings)?
+                .into_iter()
+                .find(|t| &t.name == name)
+                .ok_or_else(|| format!("Pipeline template '{}' not found", name))?,
+        ),
+        None => None,
+    };
+    let recordings = FileScanner::scan_roots(&config.recordings_roots);
+    let mut plan = BacklogPlan::build(&recordings, &since, &target_status, template.as_ref(), &settings)?;
+
+    // A recording with a queued or running job is not queued twice
+    let busy: Vec<String> = queue
+        .list()
+        .into_iter()
+        .filter(|job| matches!(job.status, JobStatus::Queued | JobStatus::Running))
+        .map(|job| job.recording_name)
+        .collect();
+    let (items, queued): (Vec<_>, Vec<_>) = plan.items.into_iter().partition(|item| !busy.contains(&item.recording));
+    plan.items = items;
+    plan.skipped.extend(queued.into_iter().map(|item| BacklogSkip { recording: item.recording, reason: "Already has a job".to_string() }));
+    if !confirm.unwrap_or(false) {
+        return Ok(plan);
+    }
+
+    let target_step = plan.target_step.id();
+    for item in &plan.items {
+        if let Some(template) = &template {
+            PipelineTemplates::set_recording_template(&config.recording_path(&item.recording), Some(&template.name))?;
+        }
+        if let Some(job) = queue.enqueue(&item.recording, &target_step, item.preset.clone(), "backlog") {
+            plan.jobs.push(job);
+        }
+    }
+    log::info!("🗓️ Queued {} backlog jobs since {} up to {}", plan.jobs.len(), plan.since, target_step);
+    Ok(plan)
+}
+
+/// Queued jobs waiting for their start time, soonest first
+#[tauri::command]
+pub fn list_scheduled_jobs(queue: State<JobQueue>) -> Result<Vec<Job>, String> {
+    let mut jobs: Vec<Job> = queue
+        .list()
+        .into_iter()
+        .filter(|j| j.status == JobStatus::Queued && j.run_after.is_some())
+        .collect();
+    jobs.sort_by_key(|j| j.run_after);
+    Ok(jobs)
+}
+
+#[tauri::command]
+pub fn reschedule_job(id: u64, run_after: Option<u64>, queue: State<JobQueue>) -> Result<Job, String> {
+    queue.reschedule(id, run_after)
+}
+
+#[tauri::command]
+pub fn cancel_job(id: u64, queue: State<JobQueue>) -> Result<Job, String> {
+    let job = queue.cancel(id)?;
+    log::info!("🗓️ Job {} for '{}' cancelled", job.id, job.recording_name);
+    Ok(job)
+}
+
+/// What pause_all_jobs holds back
+#[derive(Debug, Clone, Serialize)]
+pub struct PipelinePause {
+    /// No queued job starts
+    pub queue_paused: bool,
+    /// Running processes are stopped where they are
+    pub processes_suspended: bool,
+    /// Processes stopped or continued by the call
+    pub processes: usize,
+}
+
+/// Stop starting queued jobs and, with `suspend_processes`, freeze the running steps'
+/// processes (Unix only) so the CPU is free right away; resume_all_jobs picks up where they were
+#[tauri::command]
+pub fn pause_all_jobs(suspend_processes: bool, queue: State<JobQueue>) -> Result<PipelinePause, String> {
+    queue.set_paused(true);
+    let processes = if suspend_processes { self::suspend_processes()? } else { 0 };
+    log::info!("⏸️ Pipeline paused, {} processes suspended", processes);
+    Ok(PipelinePause { queue_paused: true, processes_suspended: processes_suspended(), processes })
+}
+
+#[tauri::command]
+pub fn resume_all_jobs(queue: State<JobQueue>) -> Result<PipelinePause, String> {
+    let processes = resume_processes();
+    queue.set_paused(false);
+    log::info!("▶️ Pipeline resumed, {} processes continued", processes);
+    Ok(PipelinePause { queue_paused: false, processes_suspended: false, processes })
+}
+
+/// Queue a job that was interrupted by the app quitting or crashing again
+#[tauri::command]
+pub fn resume_job(id: u64, queue: State<JobQueue>) -> Result<Job, String> {
+    let job = queue.resume(id)?;
+    log::info!("🗓️ Job {} for '{}' resumed", job.id, job.recording_name);
+    Ok(job)
+}
+
+/// Steps the app quit or crashed during, with the processes they left running
+#[tauri::command]
+pub fn list_interrupted_steps(config: State<AppConfig>) -> Result<Vec<InterruptedStep>, String> {
+    Ok(interrupted_steps(&config))
+}
+
+/// Release the lock of an interrupted step so the recording can be processed again,
+/// with `kill_orphans` first stopping the processes it left running
+#[tauri::command]
+pub fn resolve_interrupted_step(
+    recording_name: String,
+    kill_orphans: bool,
+    config: State<AppConfig>
+) -> Result<InterruptedStep, String> {
+    let interrupted = resolve_step(&config.recording_path(&recording_name), kill_orphans)?;
+    log::info!("🧹 Resolved interrupted '{}' of '{}' (killed {:?})", interrupted.step, recording_name, interrupted.orphan_pids);
+    Ok(interrupted)
+}
+
+fn interrupted_steps(config: &AppConfig) -> Vec<InterruptedStep> {
+    FileScanner::scan_roots_with(&config.recordings_roots, &ScanOptions::fast())
+        .iter()
+        .filter_map(|recording| find_interrupted_step(&recording.path))
+        .collect()
+}
+
+/// Tell the user about work the last run of the app left unfinished, which waits
+/// for resume_job or resolve_interrupted_step instead of running again on its own
+pub fn report_interrupted_work(app: &AppHandle) {
+    let app = app.clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let jobs = app.state::<JobQueue>().list().into_iter().filter(|j| j.status == JobStatus::Interrupted).count();
+        let steps = interrupted_steps(&app.state::<AppConfig>());
+        for step in &steps {
+            log::warn!(
+                "⚠️ '{}' of '{}' was interrupted, still running: {:?}",
+                step.step, step.recording_name, step.orphan_pids
+            );
+        }
+        let count = jobs + steps.len();
+        if count > 0 {
+            let locale = app.state::<CurrentLocale>().get();
+            app.state::<Notifier>().notify(
+                &message_with(locale, "notify.interrupted", &[("count", &count.to_string())]),
+                message(locale, "notify.interrupted_hint"),
+            );
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_job_window(settings: State<SettingsStore>) -> Result<JobWindow, String> {
+    JobWindow::load(&settings)
+}
+
+#[tauri::command]
+pub fn set_job_window(window: JobWindow, settings: State<SettingsStore>) -> Result<JobWindow, String> {
+    log::info!("🗓️ Job window: {:?}", window);
+    window.save(&settings)?;
+    Ok(window)
+}
+
+#[tauri::command]
+pub fn get_upload_retry_policy(settings: State<SettingsStore>) -> Result<UploadRetryPolicy, String> {
+    UploadRetryPolicy::load(&settings)
+}
+
+#[tauri::command]
+pub fn set_upload_retry_policy(policy: UploadRetryPolicy, settings: State<SettingsStore>) -> Result<UploadRetryPolicy, String> {
+    log::info!("🔁 Upload retries: {:?}", policy);
+    policy.save(&settings)?;
+    Ok(policy)
+}
+
+#[tauri::command]
+pub fn get_stall_detection(settings: State<SettingsStore>) -> Result<StallDetection, String> {
+    StallDetection::load(&settings)
+}
+
+#[tauri::command]
+pub fn set_stall_detection(detection: StallDetection, settings: State<SettingsStore>) -> Result<StallDetection, String> {
+    log::info!("🐢 Stall detection: {:?}", detection);
+    detection.save(&settings)?;
+    Ok(detection)
+}
+
+#[tauri::command]
+pub fn get_auto_ingest_policy(settings: State<SettingsStore>) -> Result<AutoIngestPolicy, String> {
+    AutoIngestPolicy::load(&settings)
+}
+
+#[tauri::command]
+pub fn set_auto_ingest_policy(
+    policy: AutoIngestPolicy,
+    settings: State<SettingsStore>
+) -> Result<AutoIngestPolicy, String> {
+    log::info!("📥 Auto-ingest policy: {:?}", policy);
+    policy.save(&settings)?;
+    Ok(policy)
+}
+
+/// Called by the recordings watcher: fresh recordings become auto-ingest candidates
+pub fn track_new_recording(app: &AppHandle, change: &RecordingChange) {
+    let state = app.state::<AutoIngestState>();
+    let mut tracker = match state.tracker.lock() {
+        Ok(tracker) => tracker,
+        Err(_) => {
+            log::error!("Auto-ingest tracker lock poisoned");
+            return;
+        }
+    };
+
+    match &change.recording {
+        // Only recordings that were not there before, not ones that changed
+        Some(recording) if recording.status == RecordingStatus::Recorded && tracker.is_new(&change.name) => {
+            let enabled = AutoIngestPolicy::load(&app.state::<SettingsStore>())
+                .map(|policy| policy.enabled)
+                .unwrap_or(false);
+            if enabled {
+                tracker.observe(&change.name, recording_video_size(&recording.path), Instant::now());
+            }
+        }
+        Some(_) => tracker.forget(&change.name),
+        None => tracker.remove(&change.name),
+    }
+}
+
+/// Queue candidates once their video stopped growing for the policy's settle time
+pub fn start_auto_ingest(app: &AppHandle) {
+    // Recordings already in the library are not new, whatever their status
+    let existing = FileScanner::scan_roots_with(&app.state::<AppConfig>().recordings_roots, &ScanOptions::fast());
+    match app.state::<AutoIngestState>().tracker.lock() {
+        Ok(mut tracker) => tracker.seed(existing.into_iter().map(|r| r.name)),
+        Err(_) => log::error!("Auto-ingest tracker lock poisoned"),
+    }
+
+    let app = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(INGEST_POLL_INTERVAL).await;
+            enqueue_settled_recordings(&app);
+        }
+    });
+}
+
+fn enqueue_settled_recordings(app: &AppHandle) {
+    let policy = match AutoIngestPolicy::load(&app.state::<SettingsStore>()) {
+        Ok(policy) if policy.enabled => policy,
+        Ok(_) => return,
+        Err(e) => {
+            log::warn!("Auto-ingest policy unavailable: {}", e);
+            return;
+        }
+    };
+
+    let config = app.state::<AppConfig>();
+    let settled = {
+        let state = app.state::<AutoIngestState>();
+        let mut tracker = match state.tracker.lock() {
+            Ok(tracker) => tracker,
+            Err(_) => {
+                log::error!("Auto-ingest tracker lock poisoned");
+                return;
+            }
+        };
+        let now = Instant::now();
+        for name in tracker.candidates() {
+            tracker.observe(&name, recording_video_size(&config.recording_path(&name)), now);
+        }
+        tracker.drain_settled(policy.settle_time(), now)
+    };
+
+    let queue = app.state::<JobQueue>();
+    for name in settled {
+        // Someone may have started it by hand, or snoozed it, in the meantime
+        if StatusDetector::detect_status(&config.recording_path(&name)) != RecordingStatus::Recorded {
+            continue;
+        }
+        if SnoozeStore::active(&config.recording_path(&name)).is_some() {
+            log::info!("📥 Auto-ingest skips '{}', snoozed", name);
+            continue;
+        }
+        if let Some(job) = queue.enqueue(&name, &policy.target_step, policy.preset.clone(), "auto-ingest") {
+            log::info!("📥 Auto-ingest queued job {} for '{}' up to {}", job.id, name, job.target_step);
+        }
+    }
+}
+
+/// Process queued jobs one at a time for the lifetime of the app
+pub fn start_job_worker(app: &AppHandle) {
+    let app = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let queue = app.state::<JobQueue>();
+        loop {
+            while let Some(job) = queue.start_next(unix_now()) {
+                log::info!("🏃 Job {} started for '{}'", job.id, job.recording_name);
+                match run_job(&app, &job).await {
+                    Ok(None) => {
+                        log::info!("✅ Job {} finished for '{}'", job.id, job.recording_name);
+                        queue.finish(job.id, Ok(()));
+                    }
+                    // Stopped between steps, or its step was stopped, because the app quits
+                    _ if queue.is_shutting_down() => {
+                        log::info!("👋 Job {} for '{}' stopped by quitting", job.id, job.recording_name);
+                        queue.stop_for_quit(Some(job.id));
+                    }
+                    Ok(Some(run_after)) => {
+                        log::info!("🌙 Job {} for '{}' continues at {}", job.id, job.recording_name, run_after);
+                        queue.defer(job.id, run_after);
+                    }
+                    Err(e) => {
+                        log::error!("❌ Job {} failed for '{}': {}", job.id, job.recording_name, e);
+                        queue.finish(job.id, Err(e));
+                    }
+                }
+            }
+            // Scheduled jobs become due without anyone queueing
+            let _ = tokio::time::timeout(SCHEDULE_POLL_INTERVAL, queue.wait()).await;
+        }
+    });
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Run next steps until the recording has passed the job's target step, or its
+/// pipeline template ends, whichever comes first. Stops early
+/// with the time to continue at when the next step has to wait for the job window
+/// or a failed upload is retried later. A step killed as stalled may run once more.
+async fn run_job(app: &AppHandle, job: &Job) -> Result<Option<u64>, String> {
+    let target: NextStep = job.target_step.parse()?;
+    let recording_path = app.state::<AppConfig>().recording_path(&job.recording_name);
+    let template = PipelineTemplates::for_recording(&app.state::<SettingsStore>(), &recording_path)?;
+    let mut previous_step: Option<NextStep> = None;
+
+    loop {
+        if app.state::<JobQueue>().is_shutting_down() {
+            return Ok(Some(unix_now()));
+        }
+        let mut recording = Recording::from_path(recording_path.clone())
+            .map_err(|e| format!("Failed to load recording '{}': {}", job.recording_name, e))?;
+        update_recording_status(&mut recording);
+
+        let next_step = match &template {
+            Some(template) => template.next_step(&recording, &[]),
+            None => recording.get_next_step(),
+        };
+        let step = match next_step {
+            // Every platform failed last time; upload again
+            Some(NextStep::Retry) if job.failed_attempts("upload") > 0 => NextStep::Upload,
+            // A step killed as stalled, run once more
+            Some(NextStep::Retry) => match RetryPlan::for_recording(&recording.path).resolve(&job.recording_name) {
+                Ok(step) if job.failed_attempts(&step.id()) > 0 && within_target(&step, &target) => step,
+                _ => return Err(format!("Recording is in failed state: {:?}", recording.status)),
+            },
+            Some(step) if within_target(&step, &target) => step,
+            _ => return Ok(None),
+        };
+        if previous_step.as_ref() == Some(&step) {
+            return Err(format!("{} did not advance the recording", step.label()));
+        }
+        let window = JobWindow::load(&app.state::<SettingsStore>())?;
+        let wait = window.wait_seconds(&step, chrono::Local::now().num_seconds_from_midnight())?;
+        if wait > 0 {
+            return Ok(Some(unix_now() + wait));
+        }
+
+        let result = match (&step, &job.preset) {
+            (NextStep::SetupRender, Some(preset)) => {
+                let options = SetupRenderOptions { preset: preset.clone(), ..SetupRenderOptions::default() };
+                run_setup_render(&job.recording_name, options, app, &app.state(), &app.state()).await
+            }
+            // run_next_step would resolve the retry to the render
+            (NextStep::Upload, _) if recording.get_next_step() == Some(NextStep::Retry) => {
+                let name = job.recording_name.clone();
+                run_specific_step(name, "upload".to_string(), None, app.clone(), app.state(), app.state(), app.state()).await
+            }
+            _ => run_next_step(job.recording_name.clone(), app.clone(), app.state(), app.state(), app.state()).await,
+        };
+        match result {
+            Ok(_) => previous_step = Some(step),
+            Err(e) if step == NextStep::Upload => return retry_upload_later(app, job, e),
+            Err(e) if is_stall_error(&e) && job.failed_attempts(&step.id()) == 0 => retry_stalled_step(app, job, &step, e)?,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Record a step killed as stalled with the job, so the next round runs it again
+/// once when stall detection is set to retry
+fn retry_stalled_step(app: &AppHandle, job: &Job, step: &NextStep, error: String) -> Result<(), String> {
+    if StallDetection::load(&app.state::<SettingsStore>())?.action != StallAction::Retry {
+        return Err(error);
+    }
+    let now = unix_now();
+    log::warn!("🐢 {} of '{}' stalled, running it again", step.label(), job.recording_name);
+    let attempt = JobAttempt { step: step.id(), failed_at: now, error, retry_at: Some(now) };
+    app.state::<JobQueue>().record_attempt(job.id, attempt);
+    Ok(())
+}
+
+/// Record a failed upload with the job and pick the time to try again, backing off
+/// exponentially; transient failures only, up to the configured number of retries
+fn retry_upload_later(app: &AppHandle, job: &Job, error: String) -> Result<Option<u64>, String> {
+    let policy = UploadRetryPolicy::load(&app.state::<SettingsStore>())?;
+    let now = unix_now();
+    let retry_at = policy.retry_delay(job.failed_attempts("upload") + 1, &error).map(|delay| now + delay);
+
+    let attempt = JobAttempt { step: "upload".to_string(), failed_at: now, error: error.clone(), retry_at };
+    app.state::<JobQueue>().record_attempt(job.id, attempt);
+    match retry_at {
+        Some(retry_at) => {
+            log::warn!("🔁 Upload of '{}' failed, retrying in {} s: {}", job.recording_name, retry_at - now, error);
+            Ok(Some(retry_at))
+        }
+        None => Err(error),
+    }
+}
@@ -0,0 +1,31 @@
+use crate::services::{message_catalog, CurrentLocale, Locale, SettingsStore, LOCALE_ENV};
+use std::collections::BTreeMap;
+use tauri::State;
+
+/// Language of statuses, step names and notifications
+#[tauri::command]
+pub fn get_locale(locale: State<CurrentLocale>) -> Result<Locale, String> {
+    Ok(locale.get())
+}
+
+/// Switch the language and remember the choice; FERMATA_LOCALE still wins on the next start
+#[tauri::command]
+pub fn set_locale(locale: Locale, current: State<CurrentLocale>, settings: State<SettingsStore>) -> Result<(), String> {
+    locale.save(&settings)?;
+    current.set(locale);
+    if std::env::var_os(LOCALE_ENV).is_some() {
+        log::warn!("🌐 {} is set and will override {:?} after a restart", LOCALE_ENV, locale);
+    }
+    log::info!("🌐 Locale set to {:?}", locale);
+    Ok(())
+}
+
+/// Message templates by key, e.g. "status.rendered" or "step.upload", in the
+/// given language or the current one
+#[tauri::command]
+pub fn get_message_catalog(
+    locale: Option<Locale>,
+    current: State<CurrentLocale>
+) -> Result<BTreeMap<&'static str, &'static str>, String> {
+    Ok(message_catalog(locale.unwrap_or_else(|| current.get())))
+}
@@ -0,0 +1,24 @@
+use crate::commands::recordings::AppConfig;
+use crate::services::{StepLogEntry, StepLogs};
+use tauri::State;
+
+/// Stored step output logs of a recording, newest first
+#[tauri::command]
+pub fn list_step_logs(recording_name: String, config: State<AppConfig>) -> Result<Vec<StepLogEntry>, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    Ok(StepLogs::list(&recording_path))
+}
+
+/// Content of one step log; `tail_lines` limits it to the last lines
+#[tauri::command]
+pub fn read_step_log(
+    recording_name: String,
+    log_id: String,
+    tail_lines: Option<usize>,
+    config: State<AppConfig>
+) -> Result<String, String> {
+    StepLogs::read(&config.recording_path(&recording_name), &log_id, tail_lines)
+}
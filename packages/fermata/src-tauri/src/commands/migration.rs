@@ -0,0 +1,157 @@
+use crate::commands::recordings::AppConfig;
+use crate::commands::rename::repair_text_paths_impl;
+use crate::commands::storage::running_recordings;
+use crate::models::RecordingStatus;
+use crate::services::{
+    detect_in_progress_status, directory_size, ConfigProfiles, FileScanner, JobQueue, LayoutMigration, LayoutMove, MigrationOptions, RecordingsLocation,
+    RecordingsMigration, RootMigrationReport, SettingsStore, SpacePreflight, StatusDetector,
+};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, State};
+
+/// Event emitted with a MigrationProgress while migrate_recordings copies files
+pub const RECORDINGS_MIGRATION_PROGRESS_EVENT: &str = "recordings-migration-progress";
+
+/// What a layout migration did, or would do in dry-run mode
+#[derive(Debug, Serialize)]
+pub struct MigrationReport {
+    pub recording: String,
+    pub dry_run: bool,
+    pub moves: Vec<LayoutMove>,
+    pub skipped: Vec<String>,
+    pub status: RecordingStatus,
+}
+
+/// Move a legacy recording layout into the current structure
+#[tauri::command]
+pub fn migrate_recording_layout(
+    recording_name: String,
+    dry_run: Option<bool>,
+    config: State<AppConfig>
+) -> Result<MigrationReport, String> {
+    log::info!("📦 Migrating layout of '{}' (dry_run: {:?})", recording_name, dry_run);
+    migrate_recording_layout_impl(&config.recording_path(&recording_name), dry_run.unwrap_or(false))
+}
+
+/// Copy or move every recording of the primary root to `new_root`, verifying sizes
+/// and repairing absolute paths in the copies. Once nothing was skipped the new root
+/// is saved as the recordings location, which takes effect after a restart.
+#[tauri::command]
+pub async fn migrate_recordings(
+    new_root: String,
+    move_files: bool,
+    leave_symlinks: Option<bool>,
+    app: AppHandle,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>,
+    queue: State<'_, JobQueue>
+) -> Result<RootMigrationReport, String> {
+    let old_root = config.recordings_path.clone();
+    let new_root = PathBuf::from(new_root.trim());
+    log::info!("🚚 Migrating recordings from {} to {} (move: {})", old_root.display(), new_root.display(), move_files);
+    if !new_root.is_absolute() {
+        return Err(format!("New recordings location must be an absolute path: {}", new_root.display()));
+    }
+    if new_root.starts_with(&old_root) || old_root.starts_with(&new_root) {
+        return Err(format!("{} and {} must not contain each other", new_root.display(), old_root.display()));
+    }
+    std::fs::create_dir_all(&new_root).map_err(|e| format!("Failed to create {}: {}", new_root.display(), e))?;
+
+    let names: Vec<String> = FileScanner::scan_recordings(&old_root).into_iter().map(|r| r.name).collect();
+    let total_bytes = names.iter().map(|name| directory_size(&old_root.join(name))).sum();
+    match SpacePreflight::check("migrate recordings", &new_root, total_bytes, 0, format!("{} recordings", names.len())) {
+        Ok(preflight) => preflight.ensure()?,
+        Err(e) => log::warn!("Skipping free space check: {}", e),
+    }
+
+    let running = running_recordings(&queue);
+    let options = MigrationOptions { move_files, leave_symlinks: leave_symlinks.unwrap_or(false) };
+    let (source, target) = (old_root.clone(), new_root.clone());
+    let mut report = tauri::async_runtime::spawn_blocking(move || {
+        RecordingsMigration::migrate(&source, &target, &names, &running, options, |progress| {
+            if let Err(e) = app.emit(RECORDINGS_MIGRATION_PROGRESS_EVENT, progress) {
+                log::error!("Failed to emit {}: {}", RECORDINGS_MIGRATION_PROGRESS_EVENT, e);
+            }
+        })
+    })
+    .await
+    .map_err(|e| format!("Migration task failed: {}", e))?;
+
+    for migrated in &report.migrated {
+        let old_path = old_root.join(&migrated.name).to_string_lossy().to_string();
+        match repair_text_paths_impl(&new_root.join(&migrated.name), Some(&old_path), false) {
+            Ok(repair) => report.warnings.extend(repair.warnings),
+            Err(e) => report.warnings.push(format!("Failed to repair paths of '{}': {}", migrated.name, e)),
+        }
+    }
+
+    if report.skipped.is_empty() {
+        RecordingsLocation { recordings_path: Some(new_root.clone()) }.save(&settings)?;
+        // Profiles pointing at the old root would otherwise bring it back after the restart
+        let mut profiles = ConfigProfiles::load(&settings)?;
+        if profiles.move_recordings_path(&old_root, &new_root) {
+            profiles.save(&settings)?;
+        }
+        report.config_updated = true;
+    } else {
+        report.warnings.push("Recordings location unchanged until the skipped recordings are migrated too".to_string());
+    }
+    for warning in &report.warnings {
+        log::warn!("{}", warning);
+    }
+    log::info!("🚚 Migrated {} recordings, {} skipped", report.migrated.len(), report.skipped.len());
+    Ok(report)
+}
+
+pub fn migrate_recording_layout_impl(recording_path: &Path, dry_run: bool) -> Result<MigrationReport, String> {
+    let recording = recording_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| "Invalid recording directory name".to_string())?;
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording));
+    }
+    if let Some(status) = detect_in_progress_status(recording_path) {
+        return Err(format!("Recording '{}' is busy: {:?}", recording, status));
+    }
+
+    let moves = LayoutMigration::plan(recording_path);
+    let skipped = if dry_run || moves.is_empty() {
+        Vec::new()
+    } else {
+        LayoutMigration::apply(recording_path, &moves)?
+    };
+
+    Ok(MigrationReport {
+        recording,
+        dry_run,
+        moves,
+        skipped,
+        status: StatusDetector::detect_status(recording_path),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_dry_run_leaves_files_in_place() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path().join("gig");
+        fs::create_dir_all(recording.join("extracted")).unwrap();
+        fs::write(recording.join("gig.mkv"), "video").unwrap();
+        fs::write(recording.join("extracted/Mic.m4a"), "audio").unwrap();
+        fs::write(recording.join("extracted/Mic_analysis.json"), "{}").unwrap();
+
+        let preview = migrate_recording_layout_impl(&recording, true).unwrap();
+        assert_eq!(preview.moves.len(), 1);
+        assert!(recording.join("extracted/Mic_analysis.json").exists());
+
+        let report = migrate_recording_layout_impl(&recording, false).unwrap();
+        assert_eq!(report.status, RecordingStatus::Analyzed);
+    }
+}
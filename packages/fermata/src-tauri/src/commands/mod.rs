@@ -2,3 +2,33 @@ pub mod recordings;
 pub mod operations;
 pub mod rename;
 pub mod video;
+pub mod custom_fields;
+pub mod render;
+pub mod uploads;
+pub mod export;
+pub mod storage;
+pub mod notifications;
+pub mod automation;
+pub mod migration;
+pub mod jobs;
+pub mod sources;
+pub mod config_sync;
+pub mod logs;
+pub mod health;
+pub mod stats;
+pub mod sessions;
+pub mod files;
+pub mod obs;
+pub mod priority;
+pub mod environment;
+pub mod toolchain;
+pub mod app_log;
+pub mod detection;
+pub mod recent;
+pub mod config_profiles;
+pub mod locale;
+pub mod observer;
+pub mod tray;
+pub mod polling;
+#[cfg(feature = "http-api")]
+pub mod http_api;
@@ -0,0 +1,21 @@
+use crate::services::{NotificationSettings, Notifier, SettingsStore, NOTIFICATION_SETTINGS_KEY};
+use tauri::State;
+
+/// Check whether step notifications are turned on
+#[tauri::command]
+pub fn get_notifications_enabled(notifier: State<Notifier>) -> Result<bool, String> {
+    Ok(notifier.is_enabled())
+}
+
+/// Turn step notifications on or off and remember the choice
+#[tauri::command]
+pub fn set_notifications_enabled(
+    enabled: bool,
+    notifier: State<Notifier>,
+    settings: State<SettingsStore>
+) -> Result<(), String> {
+    settings.set(NOTIFICATION_SETTINGS_KEY, &NotificationSettings { enabled })?;
+    notifier.set_enabled(enabled);
+    log::info!("🔔 Notifications {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use crate::commands::jobs::track_new_recording;
+use crate::commands::recordings::{AppConfig, RECORDING_CHANGED_EVENT};
+use crate::models::Recording;
+use crate::services::{
+    recording_name_for_path, update_recording_status, ConfigProfiles, ObsClient, ObsConnection, ObsOutputPath, ObsStatus,
+    RecordingChange, RecordingsLocation, SettingsStore
+};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Event emitted with an ObsRecordingEvent when OBS starts, pauses or stops recording
+pub const OBS_RECORDING_EVENT: &str = "obs-recording-state";
+
+/// Wait before reconnecting to OBS, and between checks while the connection is disabled
+const OBS_RECONNECT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// What the OBS listener learned, for get_obs_status
+#[derive(Default)]
+pub struct ObsState {
+    last_output_path: Mutex<Option<String>>,
+}
+
+#[tauri::command]
+pub fn get_obs_connection(settings: State<SettingsStore>) -> Result<ObsConnection, String> {
+    ObsConnection::load(&settings)
+}
+
+/// Save the connection; the listener picks it up on its next reconnect
+#[tauri::command]
+pub fn save_obs_connection(connection: ObsConnection, settings: State<SettingsStore>) -> Result<ObsConnection, String> {
+    log::info!("🎥 OBS connection: {}:{} (enabled: {})", connection.host, connection.port, connection.enabled);
+    connection.save(&settings)?;
+    Ok(connection)
+}
+
+/// Recording and streaming state of OBS; not connected, with the reason, when
+/// the connection is disabled or OBS cannot be reached
+#[tauri::command]
+pub async fn get_obs_status(settings: State<'_, SettingsStore>, state: State<'_, ObsState>) -> Result<ObsStatus, String> {
+    let connection = ObsConnection::load(&settings)?;
+    let last_output_path = state.last_output_path.lock().unwrap().clone();
+    if !connection.enabled {
+        return Ok(ObsStatus {
+            error: Some("OBS connection is disabled".to_string()),
+            last_output_path,
+            ..ObsStatus::default()
+        });
+    }
+
+    let status = match ObsClient::connect(&connection, false).await {
+        Ok(mut client) => client.status().await,
+        Err(e) => Err(e),
+    };
+    Ok(match status {
+        Ok(status) => ObsStatus { last_output_path, ..status },
+        Err(e) => ObsStatus { error: Some(e), last_output_path, ..ObsStatus::default() },
+    })
+}
+
+/// Outcome of sync_recordings_path_with_obs
+#[derive(serde::Serialize)]
+pub struct ObsPathSync {
+    pub obs: ObsOutputPath,
+    /// The recordings location now points at the OBS folder, effective after a restart
+    pub restart_required: bool,
+}
+
+/// Folder OBS Studio records to, read from its profile config
+#[tauri::command]
+pub fn get_obs_output_path() -> Result<ObsOutputPath, String> {
+    ObsOutputPath::discover()
+}
+
+/// Point Fermata at the folder OBS records to. An active config profile with its own
+/// recordings path gets the new path, otherwise the recordings location does.
+#[tauri::command]
+pub fn sync_recordings_path_with_obs(config: State<AppConfig>, settings: State<SettingsStore>) -> Result<ObsPathSync, String> {
+    let obs = ObsOutputPath::discover()?;
+    if !obs.exists {
+        return Err(format!("OBS records to {}, which does not exist", obs.path.display()));
+    }
+    if obs.path == config.recordings_path {
+        return Ok(ObsPathSync { obs, restart_required: false });
+    }
+
+    let mut profiles = ConfigProfiles::load(&settings)?;
+    let active = profiles.active_name();
+    match profiles.profiles.iter_mut().find(|p| Some(&p.name) == active.as_ref() && p.recordings_path.is_some()) {
+        Some(profile) => {
+            profile.recordings_path = Some(obs.path.clone());
+            profiles.save(&settings)?;
+        }
+        None => RecordingsLocation { recordings_path: Some(obs.path.clone()) }.save(&settings)?,
+    }
+    log::info!("🎥 Recordings location synced with OBS profile '{}': {}", obs.profile, obs.path.display());
+    Ok(ObsPathSync { obs, restart_required: true })
+}
+
+/// Listen to OBS for the lifetime of the app, reconnecting when it goes away
+pub fn start_obs_listener(app: &AppHandle) {
+    let app = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let connection = ObsConnection::load(&app.state::<SettingsStore>()).unwrap_or_else(|e| {
+                log::warn!("OBS connection settings unavailable: {}", e);
+                ObsConnection::default()
+            });
+            if connection.enabled {
+                if let Err(e) = listen_to_obs(&app, &connection).await {
+                    log::info!("🎥 OBS listener: {}", e);
+                }
+            }
+            tokio::time::sleep(OBS_RECONNECT_INTERVAL).await;
+        }
+    });
+}
+
+async fn listen_to_obs(app: &AppHandle, connection: &ObsConnection) -> Result<(), String> {
+    let mut client = ObsClient::connect(connection, true).await?;
+    log::info!("🎥 Listening to OBS at {}:{}", connection.host, connection.port);
+    loop {
+        let event = client.next_recording_event().await?;
+        log::info!("🎥 OBS recording state: {}", event.state);
+        if let Some(path) = event.finished_file() {
+            *app.state::<ObsState>().last_output_path.lock().unwrap() = Some(path.to_string());
+            surface_finished_recording(app, Path::new(path));
+        }
+        if let Err(e) = app.emit(OBS_RECORDING_EVENT, &event) {
+            log::error!("Failed to emit {}: {}", OBS_RECORDING_EVENT, e);
+        }
+    }
+}
+
+/// Report the recording OBS just finished as changed, without waiting for the
+/// watcher's debounce, so it shows up and becomes an auto-ingest candidate at once
+fn surface_finished_recording(app: &AppHandle, output_path: &Path) {
+    let config = app.state::<AppConfig>();
+    let Some(name) = config
+        .recordings_roots
+        .iter()
+        .find_map(|root| recording_name_for_path(root, output_path))
+        .filter(|name| config.recording_path(name).is_dir())
+    else {
+        log::info!("🎥 {} is not inside a recording directory", output_path.display());
+        return;
+    };
+
+    let recording = match Recording::from_path(config.recording_path(&name)) {
+        Ok(mut recording) => {
+            update_recording_status(&mut recording);
+            recording
+        }
+        Err(e) => {
+            log::warn!("Failed to load recording '{}': {}", name, e);
+            return;
+        }
+    };
+    let change = RecordingChange { name, recording: Some(recording) };
+    track_new_recording(app, &change);
+    if let Err(e) = app.emit(RECORDING_CHANGED_EVENT, change) {
+        log::error!("Failed to emit {}: {}", RECORDING_CHANGED_EVENT, e);
+    }
+}
+
@@ -0,0 +1,198 @@
+use crate::commands::recordings::AppConfig;
+use crate::services::{find_active_step, ActiveStep, ActivityEntry, ActivityLog, FileScanner, ScanOptions};
+use serde_json::{json, Value};
+use tauri::ipc::{Invoke, InvokeBody};
+use tauri::{Manager, Runtime, State};
+
+/// Commands an observer may call. They read the library, apart from caches such as
+/// waveforms, or change only this app's own preferences. Anything else, including
+/// commands added later, is refused until it is listed here, and recorded in the activity log.
+const READ_ONLY_COMMANDS: &[&str] = &[
+    "get_recordings",
+    "query_recordings",
+    "get_cached_recordings",
+    "refresh_recordings",
+    "get_recording_details",
+    "get_recording_tree",
+    "get_recording_sizes",
+    "get_status_summary_text",
+    "get_recordings_by_status",
+    "get_recordings_calendar",
+    "get_attention_items",
+    "list_snoozed_recordings",
+    "get_app_config",
+    "preview_delete",
+    "suggest_name",
+    "preview_bulk_delete",
+    "list_trash",
+    "get_available_steps",
+    "get_next_step",
+    "get_retry_plan",
+    "preview_step",
+    "validate_step_options",
+    "get_subtitles",
+    "get_analyze_options",
+    "list_extract_sources",
+    "get_extract_options",
+    "list_animation_presets",
+    "validate_pipeline_integrity",
+    "verify_recording",
+    "preflight_disk_space",
+    "estimate_outputs",
+    "get_rename_history",
+    "get_playable_video_path",
+    "open_video_external",
+    "get_external_player_command",
+    "get_custom_field_definitions",
+    "get_custom_fields",
+    "search_recordings_by_custom_fields",
+    "get_render_progress",
+    "get_render_preview",
+    "get_render_settings",
+    "list_render_versions",
+    "list_blend_versions",
+    "list_render_devices",
+    "list_benchmarks",
+    "get_render_device",
+    "get_render_host",
+    "get_upload_history",
+    "get_upload_results",
+    "copy_upload_link",
+    "list_upload_profiles",
+    "get_upload_bandwidth",
+    "get_upload_metadata",
+    "get_disk_space",
+    "get_reclaimable_artifacts",
+    "get_storage_report",
+    "find_duplicates",
+    "get_notifications_enabled",
+    "set_notifications_enabled",
+    "get_run_in_background",
+    "set_run_in_background",
+    "get_queue_paused",
+    "confirm_quit",
+    "window_focused",
+    "get_polling_settings",
+    "set_polling_settings",
+    "get_locale",
+    "set_locale",
+    "get_message_catalog",
+    "list_automation_rules",
+    "list_step_hooks",
+    "list_custom_steps",
+    "list_pipeline_templates",
+    "get_recording_pipeline_template",
+    "list_jobs",
+    "list_scheduled_jobs",
+    "list_interrupted_steps",
+    "list_active_steps",
+    "get_job_window",
+    "get_upload_retry_policy",
+    "get_stall_detection",
+    "get_auto_ingest_policy",
+    "get_source_offsets",
+    "get_audio_waveform",
+    "get_timeline_markers",
+    "get_config_drift",
+    "get_config_history",
+    "read_animation_config",
+    "list_step_logs",
+    "read_step_log",
+    "run_health_check",
+    "get_pipeline_stats",
+    "get_status_counts",
+    "estimate_step_duration",
+    "export_report",
+    "list_sessions",
+    "get_session_recordings",
+    "open_recording_folder",
+    "reveal_file",
+    "get_obs_connection",
+    "get_obs_status",
+    "get_obs_output_path",
+    "get_process_priority",
+    "get_process_environment",
+    "get_toolchain",
+    "get_app_log",
+    "open_log_folder",
+    "get_recording_detection",
+    "get_recent_recordings",
+    "pin_recording",
+    "unpin_recording",
+    "list_config_profiles",
+    "export_settings",
+    "get_activity_log",
+];
+
+/// Refuse `command` when the app only observes the library
+pub fn check_command(config: &AppConfig, command: &str) -> Result<(), String> {
+    if config.observer && !READ_ONLY_COMMANDS.contains(&command) {
+        return Err(format!("'{}' is not available in read-only observer mode", command));
+    }
+    Ok(())
+}
+
+/// Append a command that changes the library to the activity log of the primary root
+pub fn record_activity(config: &AppConfig, source: &str, command: &str, arguments: Value, refused: Option<String>) {
+    let entry = ActivityEntry::new(source, command, arguments, refused);
+    if let Err(e) = ActivityLog::append(&config.recordings_path, &entry) {
+        log::warn!("{}", e);
+    }
+}
+
+/// Wrap the app's command handler so an observer can only call read-only commands,
+/// and every other command is recorded in the activity log
+pub fn observer_guard<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        let webview = invoke.message.webview();
+        let Some(config) = webview.try_state::<AppConfig>() else {
+            return handler(invoke);
+        };
+        let command = invoke.message.command();
+        let refused = check_command(&config, command).err();
+        if !READ_ONLY_COMMANDS.contains(&command) {
+            let arguments = match invoke.message.payload() {
+                InvokeBody::Json(arguments) => arguments.clone(),
+                InvokeBody::Raw(bytes) => json!({ "bytes": bytes.len() }),
+            };
+            record_activity(&config, "app", command, arguments, refused.clone());
+        }
+        match refused {
+            Some(e) => {
+                log::warn!("👀 {}", e);
+                invoke.resolver.reject(e);
+                true
+            }
+            None => handler(invoke),
+        }
+    }
+}
+
+/// Steps running in the library, including those of another app sharing it
+#[tauri::command]
+pub fn list_active_steps(config: State<AppConfig>) -> Result<Vec<ActiveStep>, String> {
+    Ok(FileScanner::scan_roots_with(&config.recordings_roots, &ScanOptions::fast())
+        .iter()
+        .filter_map(|recording| find_active_step(&recording.path))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observer_only_reads() {
+        let mut config = AppConfig::default();
+        assert!(check_command(&config, "delete_recording").is_ok());
+
+        config.observer = true;
+        assert!(check_command(&config, "get_recordings").is_ok());
+        assert!(check_command(&config, "read_step_log").is_ok());
+        for command in ["delete_recording", "rename_recording", "run_specific_step", "schedule_job", "some_new_command"] {
+            assert!(check_command(&config, command).unwrap_err().contains("observer"));
+        }
+    }
+}
@@ -1,386 +1,2208 @@
-use crate::models::{Recording, RecordingStatus, NextStep};
-use crate::services::{FileScanner, ProcessRunner, ProcessResult};
-use crate::commands::recordings::AppConfig;
-use tauri::State;
-use serde::{Serialize, Deserialize};
+use crate::models::{describe_field_errors, FieldError, Recording, RecordingStatus, NextStep, SetupRenderOptions, StepOptions, UploadResults};
+use crate::services::{
+    complete_frame_sequence, detect_in_progress_status, record_checksums, verify_checksums, ArtifactChecksum, RecordingVerification, extracted_audio_files, find_frame_sequence, find_main_audio, rendered_videos, AnalyzeOptions, BlenderProgressSnapshot, BlenderProgressTracker, ConfigStore, ConfigSync, FileScanner, FrameRange, FrameSequence, LineCallback, Notifier,
+    find_blend_files, IntegrityFinding, PipelineIntegrity, PresetCatalog, PresetInfo, render_size_history, SpacePreflight, attach_hook_runs, command_line, hook_environment, step_environment, CustomStep, CustomSteps, PipelineTemplate, PipelineTemplates, HookRun, HookTiming, StepHook, StepHooks, PreviewOptions, PreviewStatus, ProgressCallback, RenderDevice, RenderDeviceSettings, RenderPreview, RenderSettings, ResumePlan, StateManifest, StepLock, ProcessRunner, ProcessResult, SettingsStore, StatusDetector, StepArtifacts, StepLogs,
+    TranscodeProfile, UploadBlockStore, UploadConfig, UploadMetadataStore, UploadProfile, FAILURE_MARKERS, AUDIO_EXTENSIONS, RecentRecordings, RENDER_DEVICE_KEY, RENDER_VIDEO_EXTENSIONS, RESUMED_RENDER_FILE,
+    validate_animation_config, write_vtt, SubtitleFiles, SUBTITLES_DIR,
+    normalized_audio_path, prefer_normalized_audio, validate_target_lufs, DEFAULT_TARGET_LUFS, NORMALIZED_AUDIO_DIR, NORMALIZE_AUDIO_STEP,
+    ExecutionBackend, ProcessEnvironment, ProcessPriority, Toolchain, RENDER_HOST_KEY, write_atomic, RetryPlan, ExtractOptions, ExtractSource,
+    RenderRunOptions, RenderVersions, BlendVersions, FinalizeInput, FinalizeOptions,
+    PostProcessOptions, gb_to_bytes, BenchmarkDevice, BenchmarkLog, BenchmarkReport, BenchmarkTiming, encode_args, sample_audio_args, BENCHMARK_AUDIO_FILE, BENCHMARK_AUDIO_SECONDS, BENCHMARK_ENCODE_FRAMES, BENCHMARK_RENDER_FRAMES, StallCallback, StallDetection, StepOutcome, recording_date, OutputEstimate, UploadBandwidth, CurrentLocale, CommandPreview, DryRun, StepScratch, POST_PROCESSED_DIR
+};
+use crate::commands::recordings::{emit_status_change, AppConfig};
+use crate::commands::render::render_progress_emitter;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, State};
+use serde::Serialize;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct RenderOptions {
-    pub preset: String,
-    pub main_audio: Option<String>,
+/// Outcome of revert_step; without confirmation it only lists what would be removed
+#[derive(Debug, Serialize)]
+pub struct RevertReport {
+    pub confirmed: bool,
+    pub removed: Vec<String>,
+    pub status: RecordingStatus,
 }
 
-impl Default for RenderOptions {
-    fn default() -> Self {
-        Self {
-            preset: "beat-switch".to_string(),  // Zachowanie kompatybilności
-            main_audio: None,
-        }
+/// How the processes of a step are launched, see runner_options_for
+#[derive(Default)]
+struct RunnerOptions {
+    render_device: Option<RenderDevice>,
+    render_host: ExecutionBackend,
+    priority: ProcessPriority,
+    environment: Vec<(String, String)>,
+    toolchain: Toolchain,
+    stall_detection: StallDetection,
+    on_stall: Option<StallCallback>,
+}
+
+impl RunnerOptions {
+    fn runner(&self, config: &AppConfig, step: &NextStep) -> ProcessRunner {
+        ProcessRunner::new(
+            config.cli_paths.workspace_root.clone(),
+            config.cli_paths.uv_path.clone()
+        )
+        .with_limits(config.step_timeouts.limits_for(step))
+        .with_render_device(self.render_device.clone())
+        .with_priority(self.priority.clone())
+        .with_environment(self.environment.clone())
+        .with_toolchain(self.toolchain.clone())
+    }
+
+    /// Runner for `step` of the recording, held in its step lock and watched for stalls
+    fn step_runner(&self, config: &AppConfig, step: &NextStep, recording: &Recording) -> ProcessRunner {
+        self.runner(config, step)
+            .with_step_lock(&recording.path)
+            .with_stall_watch(self.stall_detection.watch_for(&recording.path, step, self.on_stall.clone()))
     }
 }
 
+/// Emitted when a running step's output stops growing, see StallDetection
+pub const STEP_STALLED_EVENT: &str = "step-stalled";
+
+/// Payload of the `step-stalled` event
+#[derive(Debug, Clone, Serialize)]
+pub struct StepStalledEvent {
+    pub recording_name: String,
+    /// Step id, e.g. "render"
+    pub step: String,
+    /// Without new bytes in the step's output
+    pub seconds: u64,
+}
+
+/// Forward stalled steps of a recording to the frontend as STEP_STALLED_EVENT
+fn step_stall_emitter(app: &AppHandle, recording_name: &str) -> StallCallback {
+    let app = app.clone();
+    let recording_name = recording_name.to_string();
+    Arc::new(move |step: &NextStep, seconds: u64| {
+        let event = StepStalledEvent { recording_name: recording_name.clone(), step: step.id(), seconds };
+        if let Err(e) = app.emit(STEP_STALLED_EVENT, event) {
+            log::error!("Failed to emit {}: {}", STEP_STALLED_EVENT, e);
+        }
+    })
+}
+
 /// Run the next step in the pipeline for a specific recording
 #[tauri::command]
-pub async fn run_next_step(recording_name: String, config: State<'_, AppConfig>) -> Result<String, String> {
+pub async fn run_next_step(
+    recording_name: String,
+    app: AppHandle,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>,
+    notifier: State<'_, Notifier>
+) -> Result<String, String> {
     log::info!("🚀 [run_next_step] Called for recording: {}", recording_name);
 
+    let on_progress = render_progress_emitter(&app, &recording_name);
+    let on_stall = step_stall_emitter(&app, &recording_name);
+    let (next_step, result) = execute_next_step(&recording_name, &config, &settings, Some(on_progress), Some(on_stall)).await?;
+    notify_step_result(&app, &notifier, &recording_name, &next_step, &result);
+    let result = result?;
+
+    if result.success {
+        Ok(format!("Successfully completed {} for {}", next_step.label().to_lowercase(), recording_name))
+    } else {
+        Err(format!("Failed to execute {}: {}", next_step.label().to_lowercase(), result.stderr))
+    }
+}
+
+/// Find and run the next step of a recording, custom steps included. The outer error
+/// means nothing ran; otherwise the step that ran comes back with its result.
+/// Shared by the run_next_step command and fermata-cli.
+pub async fn execute_next_step(
+    recording_name: &str,
+    config: &AppConfig,
+    settings: &SettingsStore,
+    on_progress: Option<ProgressCallback>,
+    on_stall: Option<StallCallback>
+) -> Result<(NextStep, Result<ProcessResult, String>), String> {
     // Get the recording details first
-    log::info!("📁 [run_next_step] Scanning recordings from: {}", config.recordings_path.display());
-    let recordings = FileScanner::scan_recordings(&config.recordings_path);
+    log::info!("📁 [run_next_step] Scanning recordings from: {:?}", config.recordings_roots);
+    let recordings = FileScanner::scan_roots(&config.recordings_roots);
     log::info!("🔍 [run_next_step] Found {} recordings total", recordings.len());
 
     let recording = recordings
         .into_iter()
         .find(|r| r.name == recording_name)
-        .ok_or_else(|| {
-            log::error!("❌ [run_next_step] Recording '{}' not found", recording_name);
-            format!("Recording '{}' not found", recording_name)
-        })?;
-
-    log::info!("✅ [run_next_step] Found recording: {}, status: {:?}", recording.name, recording.status);
+        .ok_or_else(|| {
+            log::error!("❌ [run_next_step] Recording '{}' not found", recording_name);
+            format!("Recording '{}' not found", recording_name)
+        })?;
+
+    log::info!("✅ [run_next_step] Found recording: {}, status: {:?}", recording.name, recording.status);
+    RecentRecordings::record_use(settings, recording_name);
+
+    if recording.status.is_in_progress() {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, recording.status));
+    }
+
+    // Determine next step, custom steps and the recording's template included
+    let custom_steps = CustomSteps::list(settings)?;
+    let template = PipelineTemplates::for_recording(settings, &recording.path)?;
+    let next_step = PipelineTemplates::next_step(&recording, &custom_steps, template.as_ref())
+        .ok_or_else(|| format!("No next step available for recording '{}'", recording_name))?;
+    let next_step = match next_step {
+        NextStep::Retry => RetryPlan::for_recording(&recording.path).resolve(recording_name)?,
+        step => step,
+    };
+
+    log::info!("Next step for '{}': {:?}", recording_name, next_step);
+
+    // Execute the step
+    let runner_options = RunnerOptions { on_stall, ..runner_options_for(&next_step, settings)? };
+    let hooks = StepHooks::list(settings)?;
+    let result = match (&next_step, &template) {
+        (NextStep::Custom(id), _) => match CustomSteps::find(&custom_steps, id) {
+            Some(custom) => execute_custom_step(&recording, custom, config, &hooks, &runner_options).await,
+            None => Err(format!("Unknown step: {}", id)),
+        },
+        (NextStep::SetupRender, Some(PipelineTemplate { preset: Some(preset), .. })) => {
+            execute_step_with_preset(&recording, &next_step, config, settings, preset, None, None).await
+        }
+        (NextStep::Render, Some(PipelineTemplate { preview: Some(preview), .. })) => {
+            render_template_preview(&recording, *preview, config, settings).await
+        }
+        (NextStep::Upload, Some(template)) if !template.upload_profiles.is_empty() => {
+            upload_to_profiles(&recording, &template.upload_profiles, config, settings, &hooks).await
+        }
+        _ => {
+            let upload_profile = upload_profile_for(&recording, &next_step, None, config, settings)?;
+            execute_step(&recording, &next_step, config, &hooks, upload_profile.as_ref(), Some(runner_options), on_progress).await
+        }
+    };
+    Ok((next_step, result))
+}
+
+/// The Render step of a template that only previews
+async fn render_template_preview(
+    recording: &Recording,
+    options: PreviewOptions,
+    config: &AppConfig,
+    settings: &SettingsStore
+) -> Result<ProcessResult, String> {
+    let preview = run_render_preview(recording, options, config, settings).await?;
+    Ok(match preview.status {
+        PreviewStatus::Done => ProcessResult {
+            success: true,
+            exit_code: Some(0),
+            stdout: format!("Preview rendered to {}\n", preview.output_dir.display()),
+            ..ProcessResult::error(String::new())
+        },
+        PreviewStatus::Failed(error) => ProcessResult::error(error),
+        PreviewStatus::Running => ProcessResult::error("Preview render did not finish".to_string()),
+    })
+}
+
+/// Upload to each of a template's profiles in turn, stopping at the first that fails
+async fn upload_to_profiles(
+    recording: &Recording,
+    profiles: &[String],
+    config: &AppConfig,
+    settings: &SettingsStore,
+    hooks: &[StepHook]
+) -> Result<ProcessResult, String> {
+    let mut phases = Vec::new();
+    for name in profiles {
+        let profile = UploadConfig::resolve(settings, &config.cli_paths.workspace_root, &recording.path, Some(name))?;
+        let runner_options = runner_options_for(&NextStep::Upload, settings)?;
+        let result = execute_step(recording, &NextStep::Upload, config, hooks, Some(&profile), Some(runner_options), None).await?;
+        let success = result.success;
+        phases.push((name.as_str(), result));
+        if !success {
+            break;
+        }
+    }
+    Ok(ProcessResult::from_phases(phases))
+}
+
+/// Steps that can be run for a recording, custom steps from the settings included
+#[tauri::command]
+pub fn get_available_steps(
+    recording_name: String,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<Vec<String>, String> {
+    available_steps_impl(&recording_name, &config, &settings)
+}
+
+pub fn available_steps_impl(recording_name: &str, config: &AppConfig, settings: &SettingsStore) -> Result<Vec<String>, String> {
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    Ok(CustomSteps::available_steps(&recording, &CustomSteps::list(settings)?))
+}
+
+/// Step run_next_step would run, following the recording's pipeline template;
+/// None once the recording is done
+#[tauri::command]
+pub fn get_next_step(
+    recording_name: String,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<Option<NextStep>, String> {
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    let template = PipelineTemplates::for_recording(&settings, &recording.path)?;
+    Ok(PipelineTemplates::next_step(&recording, &CustomSteps::list(&settings)?, template.as_ref()))
+}
+
+/// Step Retry would run for a failed recording, with candidates to pick from when it is unknown
+#[tauri::command]
+pub fn get_retry_plan(recording_name: String, config: State<AppConfig>) -> Result<RetryPlan, String> {
+    let path = config.recording_path(&recording_name);
+    if !path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    Ok(RetryPlan::for_recording(&path))
+}
+
+/// Subtitle files the transcribe step left, `None` when the recording was not transcribed
+#[tauri::command]
+pub fn get_subtitles(recording_name: String, config: State<AppConfig>) -> Result<Option<SubtitleFiles>, String> {
+    Ok(SubtitleFiles::find(&config.recording_path(&recording_name)))
+}
+
+/// Run a specific step for a recording
+#[tauri::command]
+pub async fn run_specific_step(
+    recording_name: String,
+    step: String,
+    profile: Option<String>,
+    app: AppHandle,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>,
+    notifier: State<'_, Notifier>
+) -> Result<String, String> {
+    log::info!("🚀 [run_specific_step] Called for recording: {}, step: {}", recording_name, step);
+
+    let on_progress = render_progress_emitter(&app, &recording_name);
+    let on_stall = step_stall_emitter(&app, &recording_name);
+    let (next_step, result) =
+        execute_named_step(&recording_name, &step, profile.as_deref(), &config, &settings, Some(on_progress), Some(on_stall)).await?;
+    notify_step_result(&app, &notifier, &recording_name, &next_step, &result);
+    let result = result?;
+
+    if result.success {
+        Ok(format!("Successfully completed {} for {}", step, recording_name))
+    } else {
+        Err(format!("Failed to execute {}: {}", step, result.stderr))
+    }
+}
+
+/// Run a step by its id ("render", "retry", a custom step id, ...) after checking it
+/// can run now; errors and results are split as in execute_next_step.
+/// Shared by the run_specific_step command and fermata-cli.
+pub async fn execute_named_step(
+    recording_name: &str,
+    step: &str,
+    profile: Option<&str>,
+    config: &AppConfig,
+    settings: &SettingsStore,
+    on_progress: Option<ProgressCallback>,
+    on_stall: Option<StallCallback>
+) -> Result<(NextStep, Result<ProcessResult, String>), String> {
+    // Get the recording details first
+    let recordings = FileScanner::scan_roots(&config.recordings_roots);
+    let recording = recordings
+        .into_iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    RecentRecordings::record_use(settings, recording_name);
+
+    if recording.status.is_in_progress() {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, recording.status));
+    }
+
+    // Validate that the step can be run
+    let custom_steps = CustomSteps::list(settings)?;
+    let custom_step = CustomSteps::find(&custom_steps, step);
+    let can_run = match custom_step {
+        Some(custom) => custom.can_run(&recording),
+        None => recording.can_run_step(step),
+    };
+    if !can_run {
+        return Err(format!("Step '{}' cannot be run for recording '{}' in current status: {:?}",
+                          step, recording_name, recording.status));
+    }
+
+    // Parse step to NextStep enum
+    let next_step = match custom_step {
+        Some(custom) => custom.next_step(),
+        None => parse_step(&recording, step)?,
+    };
+
+    log::info!("Executing step {:?} for '{}'", next_step, recording_name);
+
+    // Uploading again is deliberate, never a repeat of the default target
+    if next_step == NextStep::Upload && recording.status == RecordingStatus::Uploaded && profile.is_none() {
+        return Err(format!("Recording '{}' is already uploaded; choose an upload profile to upload it again", recording_name));
+    }
+
+    // Execute the step
+    let upload_profile = upload_profile_for(&recording, &next_step, profile, config, settings)?;
+    let runner_options = RunnerOptions { on_stall, ..runner_options_for(&next_step, settings)? };
+    let hooks = StepHooks::list(settings)?;
+    // A retried custom step
+    let custom_step = custom_step.or_else(|| match &next_step {
+        NextStep::Custom(id) => CustomSteps::find(&custom_steps, id),
+        _ => None,
+    });
+    let result = match custom_step {
+        Some(custom) => execute_custom_step(&recording, custom, config, &hooks, &runner_options).await,
+        None => execute_step(&recording, &next_step, config, &hooks, upload_profile.as_ref(), Some(runner_options), on_progress).await,
+    };
+    Ok((next_step, result))
+}
+
+/// The built-in step a step id names; "retry" resolves to the step that failed
+fn parse_step(recording: &Recording, step: &str) -> Result<NextStep, String> {
+    Ok(match step.to_lowercase().as_str() {
+        "analyze" => NextStep::Analyze,
+        "setup_render" | "setup-render" => NextStep::SetupRender,
+        "generate_config" | "generate-config" => NextStep::GenerateConfig,
+        "blend_setup" | "blend-setup" => NextStep::BlendSetup,
+        "render" => NextStep::Render,
+        "upload" => NextStep::Upload,
+        "transcribe" => NextStep::Transcribe,
+        "retry" => match recording.status {
+            RecordingStatus::Failed(_) => RetryPlan::for_recording(&recording.path).resolve(&recording.name)?,
+            _ => return Err("Retry only available for failed recordings".to_string()),
+        },
+        _ => return Err(format!("Unknown step: {}", step)),
+    })
+}
+
+/// What running a step would do, see preview_step
+#[derive(Debug, Serialize)]
+pub struct StepPreview {
+    pub step: NextStep,
+    /// In the order the step runs them, hooks included
+    pub commands: Vec<CommandPreview>,
+    /// Files and directories the step writes
+    pub outputs: Vec<PathBuf>,
+    /// Where running the step may differ from the preview
+    pub notes: Vec<String>,
+}
+
+/// Show the commands a step would run, with their working directory, environment
+/// and outputs, without running anything or changing the recording
+#[tauri::command]
+pub async fn preview_step(
+    recording_name: String,
+    step: String,
+    options: Option<SetupRenderOptions>,
+    profile: Option<String>,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>
+) -> Result<StepPreview, String> {
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+
+    let custom_steps = CustomSteps::list(&settings)?;
+    let next_step = match CustomSteps::find(&custom_steps, &step) {
+        Some(custom) => custom.next_step(),
+        None => parse_step(&recording, &step)?,
+    };
+    let custom_step = match &next_step {
+        NextStep::Custom(id) => CustomSteps::find(&custom_steps, id),
+        _ => None,
+    };
+    let upload_profile = upload_profile_for(&recording, &next_step, profile.as_deref(), &config, &settings)?;
+    preview_step_impl(&recording, &next_step, custom_step, options.as_ref(), upload_profile.as_ref(), &config, &settings).await
+}
+
+/// Go through a step the way execute_step and execute_step_with_preset do, with
+/// runners that only note their commands and without the files written in between
+async fn preview_step_impl(
+    recording: &Recording,
+    step: &NextStep,
+    custom: Option<&CustomStep>,
+    options: Option<&SetupRenderOptions>,
+    upload_profile: Option<&UploadProfile>,
+    config: &AppConfig,
+    settings: &SettingsStore
+) -> Result<StepPreview, String> {
+    let runner_options = runner_options_for(step, settings)?;
+    let dry_run = DryRun::default();
+    let runner = runner_options.runner(config, step).with_dry_run(&dry_run);
+    let hooks = StepHooks::list(settings)?;
+    let mut outputs = Vec::new();
+    let mut notes = Vec::new();
+
+    run_step_hooks(&runner, recording, step, &hooks, HookTiming::Before, None).await;
+    let result = match (custom, step) {
+        (Some(custom), _) => {
+            outputs.extend(custom.outputs.iter().map(|output| recording.path.join(output)));
+            let env = step_environment(recording, step, None);
+            runner.run_shell_command(&command_line(&custom.command), &env, &recording.path).await
+        }
+        (None, NextStep::Analyze) => {
+            let audio_files = extracted_audio_files(&recording.path)?;
+            let audio_file = find_main_audio(&audio_files, &config.main_audio_file)
+                .or(audio_files.first())
+                .ok_or_else(|| "No audio file found in extracted directory - run extract step first".to_string())?;
+            let analyze_options = AnalyzeOptions::load(&recording.path);
+            analyze_options.validate()?;
+            outputs.push(recording.path.join("analysis"));
+            runner.run_beatrix_analyze(&recording.path, audio_file, &analyze_options).await
+        }
+        (None, NextStep::SetupRender | NextStep::GenerateConfig | NextStep::BlendSetup) => {
+            let config_path = match (step, options.and_then(|options| options.config_path.as_deref())) {
+                (NextStep::SetupRender | NextStep::BlendSetup, Some(config_path)) => Some(user_animation_config(recording, config_path)?),
+                (NextStep::BlendSetup, None) => {
+                    let stored = match options {
+                        Some(options) => ConfigStore::configs(&recording.path).into_iter().find(|(_, preset)| *preset == options.preset),
+                        None => ConfigStore::latest(&recording.path),
+                    };
+                    let (config_path, _) = stored.ok_or_else(|| "No animation config found - run generate config step first".to_string())?;
+                    Some(config_path)
+                }
+                _ => None,
+            };
+            if *step != NextStep::GenerateConfig {
+                outputs.push(recording.path.join("blender"));
+                if BlendVersions::active(&recording.path).is_some() {
+                    notes.push("The current Blender project is kept as a version before it is replaced".to_string());
+                }
+            }
+            match config_path {
+                Some(config_path) => runner.run_cinemon_blend_setup(&recording.path, &config_path).await,
+                None => {
+                    let main_audio = match options {
+                        Some(options) => options.main_audio.clone(),
+                        None => setup_main_audio(recording, config)?,
+                    };
+                    let main_audio = prefer_normalized_audio(&recording.path, main_audio);
+                    let preset = options.map_or("beat-switch", |options| options.preset.as_str());
+                    outputs.insert(0, ConfigStore::config_path(&recording.path, preset));
+                    if *step == NextStep::GenerateConfig {
+                        runner.run_cinemon_config(&recording.path, preset, main_audio.as_deref()).await
+                    } else {
+                        runner.run_cinemon_render(&recording.path, preset, main_audio.as_deref()).await
+                    }
+                }
+            }
+        }
+        (None, NextStep::Render) => {
+            let blend_file = find_blend_file(&recording.path)?;
+            let render_settings = RenderSettings::load(&recording.path);
+            render_settings.validate()?;
+            let render_dir = recording.path.join("blender").join("render");
+            match complete_frame_sequence(&recording.path) {
+                Some(sequence) => {
+                    let start = sequence.frames.first().copied().unwrap_or(1);
+                    let fps = render_settings.fps.map(f64::from).unwrap_or_else(|| {
+                        notes.push("The frame rate is read from the Blender project when the step runs; 30 fps is shown".to_string());
+                        30.0
+                    });
+                    let output = render_dir.join(RESUMED_RENDER_FILE);
+                    let audio = main_audio_path(&recording.path, config);
+                    outputs.push(output.clone());
+                    let partial = StepScratch::new(&recording.path, step).partial(&output);
+                    runner.run_ffmpeg_encode_frames(&sequence, start, fps, audio.as_deref(), &partial).await
+                }
+                None if runner_options.render_host.is_remote() => {
+                    let host = &runner_options.render_host;
+                    let (Some(push), Some(pull)) = (host.push_args(&recording.path), host.pull_args(&recording.path, "blender/render")) else {
+                        return Err(format!("Cannot place {} on the render host", recording.path.display()));
+                    };
+                    let remote_runner = runner_options
+                        .runner(config, step)
+                        .with_dry_run(&dry_run)
+                        .with_backend(host.clone(), &recording.path);
+                    outputs.push(render_dir);
+                    runner.run_rsync(&push).await.map_err(|e| e.to_string())?;
+                    remote_runner
+                        .run_blender_render(&config.cli_paths.blender_path, &blend_file, None, &render_settings, Arc::new(|_: &str| {}))
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    runner.run_rsync(&pull).await
+                }
+                None => {
+                    outputs.push(render_dir);
+                    runner.run_blender_render(&config.cli_paths.blender_path, &blend_file, None, &render_settings, Arc::new(|_: &str| {})).await
+                }
+            }
+        }
+        (None, NextStep::Upload) => {
+            let version = RenderVersions::promoted(&recording.path);
+            let video = rendered_videos(&recording.path.join("blender").join(&version))
+                .into_iter()
+                .next()
+                .ok_or_else(|| "No rendered video found - run the render step first".to_string())?;
+            let profile = upload_profile.ok_or_else(|| "No upload profile selected for upload step".to_string())?;
+            let metadata = UploadMetadataStore::load_or_default(&recording.path, &recording.name)?;
+            metadata.validate()?;
+            if let Some(block) = UploadBlockStore::load(&recording.path)? {
+                notes.push(format!("Upload blocked: {}", block.reason));
+            }
+
+            let scratch = StepScratch::new(&recording.path, step);
+            let mut video_path = video;
+            if let Some(post_process) = profile.post_process.as_ref().filter(|options| !options.is_empty()) {
+                post_process.validate()?;
+                let output = PostProcessOptions::output_path(&recording.path, &video_path);
+                if !post_process.is_fresh(&recording.path, &video_path, &output) {
+                    let work_dir = recording.path.join(POST_PROCESSED_DIR);
+                    outputs.push(output.clone());
+                    runner
+                        .run_ffmpeg_post_process(&work_dir, post_process.ffmpeg_args(&video_path, &scratch.partial(&output)))
+                        .await
+                        .map_err(|e| e.to_string())?;
+                }
+                video_path = output;
+            }
+            if let Some(transcode) = &profile.transcode {
+                let source_name = RenderVersions::transcode_source_name(&version, &video_path);
+                let transcoded = UploadConfig::transcoded_path(&recording.path, &profile.name, &source_name)?;
+                if !UploadConfig::is_transcode_fresh(&video_path, &transcoded) {
+                    outputs.push(transcoded.clone());
+                    runner
+                        .run_ffmpeg_transcode(&video_path, &scratch.partial(&transcoded), transcode)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                }
+                video_path = transcoded;
+            }
+
+            outputs.push(recording.path.join("uploads").join("upload_results.json"));
+            runner.run_medusa_upload(&video_path, &profile.config_path, &metadata).await
+        }
+        (None, NextStep::Transcribe) => {
+            let audio = main_audio_path(&recording.path, config)
+                .ok_or_else(|| "No main audio found in extracted directory - run extract step first".to_string())?;
+            let output_dir = recording.path.join(SUBTITLES_DIR);
+            outputs.push(output_dir.clone());
+            runner.run_whisper_transcribe(&config.cli_paths.whisper_path, &audio, &output_dir).await
+        }
+        (None, NextStep::Extract) => return Err("Extract step not implemented in fermata - use obsession package".to_string()),
+        (None, NextStep::Retry) => return Err("Retry step should be resolved to specific step before execution".to_string()),
+        (None, NextStep::Custom(id)) => return Err(format!("Custom step '{}' is not defined", id)),
+    }
+    .map_err(|e| format!("Command execution failed: {}", e))?;
+    run_step_hooks(&runner, recording, step, &hooks, HookTiming::After, Some(&result)).await;
+
+    Ok(StepPreview { step: step.clone(), commands: dry_run.commands(), outputs, notes })
+}
+
+/// Report a finished step as a desktop notification and a `status-changed` event
+fn notify_step_result(
+    app: &AppHandle,
+    notifier: &Notifier,
+    recording_name: &str,
+    step: &NextStep,
+    result: &Result<ProcessResult, String>
+) {
+    let error = match result {
+        Ok(result) if result.success => None,
+        Ok(result) => Some(result.stderr.as_str()),
+        Err(e) => Some(e.as_str()),
+    };
+    notifier.step_finished(app.state::<CurrentLocale>().get(), recording_name, step, error);
+
+    let recording_path = app.state::<AppConfig>().recording_path(recording_name);
+    let status = recording_path.is_dir().then(|| StatusDetector::detect_status(&recording_path));
+    emit_status_change(app, recording_name, status);
+}
+
+/// Preferred compute device for the render step
+fn render_device_for(step: &NextStep, settings: &SettingsStore) -> Result<Option<RenderDevice>, String> {
+    if *step != NextStep::Render {
+        return Ok(None);
+    }
+    Ok(settings.get::<RenderDeviceSettings>(RENDER_DEVICE_KEY)?.device)
+}
+
+/// Process priority, environment and toolchain for every step, and device and machine for the render step
+fn runner_options_for(step: &NextStep, settings: &SettingsStore) -> Result<RunnerOptions, String> {
+    let render_host = if *step == NextStep::Render {
+        let host: ExecutionBackend = settings.get(RENDER_HOST_KEY)?;
+        host.validate()?;
+        host
+    } else {
+        ExecutionBackend::Local
+    };
+    Ok(RunnerOptions {
+        render_device: render_device_for(step, settings)?,
+        render_host,
+        priority: ProcessPriority::load(settings)?,
+        environment: ProcessEnvironment::load(settings)?.for_step(Some(step)),
+        toolchain: Toolchain::load(settings)?,
+        stall_detection: StallDetection::load(settings)?,
+        on_stall: None,
+    })
+}
+
+/// Upload profile for the upload step, chosen by run profile, recording profile or default
+fn upload_profile_for(
+    recording: &Recording,
+    step: &NextStep,
+    profile: Option<&str>,
+    config: &AppConfig,
+    settings: &SettingsStore
+) -> Result<Option<UploadProfile>, String> {
+    if *step != NextStep::Upload {
+        return Ok(None);
+    }
+    UploadConfig::resolve(settings, &config.cli_paths.workspace_root, &recording.path, profile).map(Some)
+}
+
+/// Transcode into the upload's scratch directory and move the file into place only
+/// once ffmpeg succeeded, so an interrupted run is never mistaken for a finished artifact
+async fn transcode_for_upload(
+    runner: &ProcessRunner,
+    recording: &Recording,
+    source: &Path,
+    target: &Path,
+    transcode: &TranscodeProfile
+) -> Result<ProcessResult, String> {
+    let parent = target.parent().ok_or_else(|| "Invalid transcode target".to_string())?;
+    std::fs::create_dir_all(parent)
+        .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+
+    let scratch = StepScratch::new(&recording.path, &NextStep::Upload);
+    scratch.create()?;
+    let partial = scratch.partial(target);
+    let result = runner
+        .run_ffmpeg_transcode(source, &partial, transcode)
+        .await
+        .map_err(|e| format!("Transcode failed to start: {}", e))?;
+
+    if result.success {
+        std::fs::rename(&partial, target)
+            .map_err(|e| format!("Failed to move transcoded file into place: {}", e))?;
+    }
+    scratch.finish(result.success);
+    Ok(result)
+}
+
+/// Copy of `video` with the overlays burned in, made unless a fresh one exists.
+/// The result is None when the existing copy was reused.
+async fn post_process_video(
+    runner: &ProcessRunner,
+    recording: &Recording,
+    video: &Path,
+    options: &PostProcessOptions
+) -> Result<(PathBuf, Option<ProcessResult>), String> {
+    let output = PostProcessOptions::output_path(&recording.path, video);
+    if options.is_fresh(&recording.path, video, &output) {
+        return Ok((output, None));
+    }
+
+    let title = UploadMetadataStore::load_or_default(&recording.path, &recording.name)?
+        .title
+        .unwrap_or_else(|| recording.name.clone());
+    let work_dir = options.prepare(&recording.path, &title, recording_date(recording).0)?;
+    let scratch = StepScratch::new(&recording.path, &NextStep::Upload);
+    scratch.create()?;
+    let partial = scratch.partial(&output);
+    let result = runner
+        .run_ffmpeg_post_process(&work_dir, options.ffmpeg_args(video, &partial))
+        .await
+        .map_err(|e| format!("Post-processing failed to start: {}", e))?;
+
+    if result.success {
+        std::fs::rename(&partial, &output)
+            .map_err(|e| format!("Failed to move post-processed file into place: {}", e))?;
+        options.mark_done(&recording.path)?;
+    }
+    scratch.finish(result.success);
+    Ok((output, Some(result)))
+}
+
+/// Burn subtitles or a title card into a copy of the render, the one an upload
+/// profile with the same options would upload
+#[tauri::command]
+pub async fn post_process_render(
+    recording_name: String,
+    options: PostProcessOptions,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>
+) -> Result<PathBuf, String> {
+    options.validate()?;
+    if options.is_empty() {
+        return Err("Pick subtitles, a title card or both to burn in".to_string());
+    }
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    if recording.status.is_in_progress() {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, recording.status));
+    }
+    let render_dir = recording.path.join("blender").join(RenderVersions::promoted(&recording.path));
+    let video = rendered_videos(&render_dir)
+        .into_iter()
+        .next()
+        .ok_or_else(|| "No rendered video found - run the render step first".to_string())?;
+
+    let runner = runner_options_for(&NextStep::Upload, &settings)?.runner(&config, &NextStep::Upload);
+    let (output, result) = post_process_video(&runner, &recording, &video, &options).await?;
+    if let Some(result) = result.filter(|result| !result.success) {
+        return Err(match &result.timeout {
+            Some(timeout) => format!("Post-processing timed out: {}", timeout),
+            None => format!("Failed to post-process render: {}", result.stderr),
+        });
+    }
+    log::info!("🔥 Post-processed render of '{}': {}", recording_name, output.display());
+    Ok(output)
+}
+
+/// Medusa reports an upload only on stdout; append it to uploads/upload_results.json
+/// after the earlier uploads, tagged with its profile and time
+fn record_upload(recording_path: &Path, result: &ProcessResult, profile: &str) {
+    let Some(StepOutcome::Upload { uploads }) = &result.outcome else {
+        return;
+    };
+    let timestamp = chrono::Local::now().to_rfc3339();
+    let entries = uploads
+        .iter()
+        .cloned()
+        .map(|mut entry| {
+            entry.timestamp.get_or_insert_with(|| timestamp.clone());
+            entry
+        })
+        .collect();
+    let latest = UploadResults { entries, extra: Default::default() };
+    let earlier = StatusDetector::read_upload_results(recording_path)
+        .unwrap_or(UploadResults { entries: Vec::new(), extra: Default::default() });
+
+    let path = recording_path.join("uploads").join("upload_results.json");
+    let written = earlier
+        .merged_with(latest, profile)
+        .to_json()
+        .and_then(|content| {
+            std::fs::create_dir_all(recording_path.join("uploads"))
+                .and_then(|_| write_atomic(&path, content))
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = written {
+        log::warn!("Failed to record the upload in {}: {}", path.display(), e);
+    }
+}
+
+/// Keep the full step output with the recording; the app log may be long gone.
+/// A successful step also updates the state manifest.
+fn write_step_log(recording: &Recording, step: &NextStep, result: &ProcessResult) {
+    log_step_log_write(StepLogs::write(&recording.path, step, result));
+    record_state(recording, step, result);
+}
+
+fn record_state(recording: &Recording, step: &NextStep, result: &ProcessResult) {
+    let recorded = if result.success {
+        StateManifest::record_step(&recording.path, step).map(|_| ())
+    } else {
+        StateManifest::record_failure(&recording.path, step)
+    };
+    if let Err(e) = recorded {
+        log::warn!("{}", e);
+    }
+}
+
+fn write_step_log_with_options(recording: &Recording, step: &NextStep, result: &ProcessResult, options: Option<&serde_json::Value>) {
+    log_step_log_write(StepLogs::write_with_options(&recording.path, step, result, options));
+    record_state(recording, step, result);
+}
+
+fn log_step_log_write(written: Result<String, String>) {
+    match written {
+        Ok(id) => log::info!("📝 Step output saved to {}", id),
+        Err(e) => log::warn!("{}", e),
+    }
+}
+
+/// Main audio to pass to cinemon: None for a single audio track, the configured
+/// main audio when there are several
+fn setup_main_audio(recording: &Recording, config: &AppConfig) -> Result<Option<String>, String> {
+    // Check if we have multiple audio files and use configured main audio
+    let extracted_dir = recording.path.join("extracted");
+    if !extracted_dir.exists() {
+        // No extracted directory, use basic render
+        return Ok(None);
+    }
+
+    let audio_files = extracted_audio_files(&recording.path)?;
+
+    log::info!("🎵 Found {} audio files for setup render: {:?}", audio_files.len(), audio_files);
+
+    if audio_files.len() <= 1 {
+        // Single audio file, use without --main-audio parameter
+        return Ok(None);
+    }
+
+    // Use configured main audio file if available
+    if let Some(main_audio) = find_main_audio(&audio_files, &config.main_audio_file) {
+        log::info!("🎯 Using configured main audio: {}", main_audio);
+        Ok(Some(main_audio.clone()))
+    } else {
+        log::warn!("⚠️ Multiple audio files found but main audio '{}' not available in: {:?}", config.main_audio_file, audio_files);
+        Err(format!("Multiple audio files found: {:?}. Configure FERMATA_MAIN_AUDIO environment variable to specify which one to use.", audio_files))
+    }
+}
+
+/// The project Blender renders: the active version in blender/
+fn find_blend_file(recording_path: &Path) -> Result<PathBuf, String> {
+    // Check if blender project exists
+    let blender_dir = recording_path.join("blender");
+    if !blender_dir.exists() {
+        return Err("Blender project not found - run setup render step first".to_string());
+    }
+
+    BlendVersions::active(recording_path).ok_or_else(|| "No .blend file found in blender directory".to_string())
+}
+
+/// Extracted audio to put under a video encoded from frames: the configured
+/// main audio, or the only audio track when there is just one
+fn main_audio_path(recording_path: &Path, config: &AppConfig) -> Option<PathBuf> {
+    let audio_files = extracted_audio_files(recording_path).ok()?;
+    let main_audio = match &audio_files[..] {
+        [single] => Some(single),
+        files => find_main_audio(files, &config.main_audio_file),
+    }?;
+    Some(recording_path.join("extracted").join(main_audio))
+}
+
+/// Parse Blender's output into progress, persisting the latest snapshot with the
+/// recording and passing it on to `on_progress`
+fn render_progress_reporter(recording_path: &Path, on_progress: Option<ProgressCallback>) -> LineCallback {
+    let tracker = Mutex::new(BlenderProgressTracker::new());
+    let started = Instant::now();
+    let recording_path = recording_path.to_path_buf();
+
+    Arc::new(move |line: &str| {
+        let Some(progress) = tracker.lock().unwrap().feed(line, started.elapsed().as_secs_f64()) else {
+            return;
+        };
+
+        let snapshot = BlenderProgressSnapshot {
+            progress: progress.clone(),
+            updated_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+        if let Err(e) = snapshot.save(&recording_path) {
+            log::warn!("{}", e);
+        }
+        if let Some(on_progress) = &on_progress {
+            on_progress(&progress);
+        }
+    })
+}
+
+/// Free space check for a render or upload, None for steps that write little
+fn space_preflight(recording: &Recording, step: &NextStep, config: &AppConfig, upload_profile: Option<&UploadProfile>) -> Result<Option<SpacePreflight>, String> {
+    match (step, upload_profile) {
+        (NextStep::Render, _) => {
+            // Renders also keep the free space threshold, below which new renders are paused
+            let reserve_bytes = gb_to_bytes(config.min_free_space_gb);
+            let recordings = FileScanner::scan_roots(&config.recordings_roots);
+            let history = render_size_history(recordings.iter().filter(|r| r.path != recording.path).map(|r| r.path.as_path()));
+            let estimate = OutputEstimate::for_recording(recording, &RenderSettings::load(&recording.path), None, None);
+            SpacePreflight::render(&recording.path, &history, estimate.render.as_ref(), reserve_bytes).map(Some)
+        }
+        (NextStep::Upload, Some(profile)) => {
+            let estimate = OutputEstimate::for_recording(recording, &RenderSettings::load(&recording.path), Some(profile), None);
+            SpacePreflight::upload(&recording.path, &profile.name, profile.transcode.as_ref(), estimate.upload.as_ref(), 0).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Fail before a render or upload starts when its output would not fit
+fn ensure_disk_space(recording: &Recording, step: &NextStep, config: &AppConfig, upload_profile: Option<&UploadProfile>) -> Result<(), String> {
+    match space_preflight(recording, step, config, upload_profile) {
+        Ok(Some(preflight)) => preflight.ensure(),
+        Ok(None) => Ok(()),
+        Err(e) => {
+            log::warn!("Skipping free space check: {}", e);
+            Ok(())
+        }
+    }
+}
+
+/// Run the enabled hooks of a step in order; before hooks stop at the first failure
+async fn run_step_hooks(
+    runner: &ProcessRunner,
+    recording: &Recording,
+    step: &NextStep,
+    hooks: &[StepHook],
+    when: HookTiming,
+    step_result: Option<&ProcessResult>
+) -> Vec<HookRun> {
+    let env = hook_environment(recording, step, when, step_result);
+    let mut runs = Vec::new();
+    for hook in hooks.iter().filter(|hook| hook.applies_to(step, when)) {
+        let result = runner
+            .run_shell_command(&hook.command_line(), &env, &recording.path)
+            .await
+            .unwrap_or_else(|e| ProcessResult::error(format!("Failed to run hook: {}", e)));
+        let failed = !result.success;
+        if failed {
+            log::warn!("🪝 Hook '{}' failed with exit code {:?}", hook.command, result.exit_code);
+        }
+        runs.push(HookRun { when, command: hook.command.clone(), result });
+        if failed && when == HookTiming::Before {
+            break;
+        }
+    }
+    runs
+}
+
+/// The result of a step whose before hook failed, so the step did not run
+fn failed_before_hook(before_hooks: &[HookRun]) -> Option<ProcessResult> {
+    let failed = before_hooks.iter().find(|run| !run.result.success)?;
+    let reason = failed
+        .result
+        .stderr
+        .lines()
+        .last()
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("exit code {:?}", failed.result.exit_code));
+    let mut result = ProcessResult::error(format!("Before hook '{}' failed: {}", failed.command, reason));
+    attach_hook_runs(&mut result, before_hooks, &[]);
+    Some(result)
+}
+
+/// Execute a specific pipeline step
+async fn execute_step(
+    recording: &Recording,
+    step: &NextStep,
+    config: &AppConfig,
+    hooks: &[StepHook],
+    upload_profile: Option<&UploadProfile>,
+    runner_options: Option<RunnerOptions>,
+    on_progress: Option<ProgressCallback>
+) -> Result<ProcessResult, String> {
+    let runner_options = runner_options.unwrap_or_default();
+    let runner = runner_options.step_runner(config, step, recording);
+
+    if *step == NextStep::Upload {
+        if let Some(block) = UploadBlockStore::load(&recording.path)? {
+            return Err(format!("Upload blocked for '{}': {}", recording.name, block.reason));
+        }
+    }
+    ensure_disk_space(recording, step, config, upload_profile)?;
+
+    // Held until the step finishes; also makes detect_status report it as running
+    let _lock = StepLock::acquire(&recording.path, step)?;
+
+    let before_hooks = run_step_hooks(&runner, recording, step, hooks, HookTiming::Before, None).await;
+    if let Some(result) = failed_before_hook(&before_hooks) {
+        write_step_log(recording, step, &result);
+        return Ok(result);
+    }
+
+    // cinemon writes the project under a fixed name; version the current one so it isn't overwritten
+    if matches!(step, NextStep::SetupRender | NextStep::BlendSetup) {
+        BlendVersions::adopt(&recording.path)?;
+    }
+
+    // Recorded in the step log when the step takes options
+    let mut step_options = None;
+    let mut result = match step {
+        NextStep::Extract => {
+            // Note: Extract step is typically done by obsession, not part of fermata scope;
+            // ExtractOptions::cli_args holds the obsession flags for the sources picked here
+            return Err("Extract step not implemented in fermata - use obsession package".to_string());
+        }
+        NextStep::Analyze => {
+            // Look for audio file in extracted directory
+            let extracted_dir = recording.path.join("extracted");
+            if !extracted_dir.exists() {
+                return Err("Extracted directory not found - run extract step first".to_string());
+            }
+
+            log::info!("🔍 Searching for audio files in: {}", extracted_dir.display());
+
+            let audio_files = extracted_audio_files(&recording.path)?;
+            log::info!("🎵 Found {} audio files: {:?}", audio_files.len(), audio_files);
+
+            if audio_files.is_empty() {
+                // List all files in directory for debugging
+                let all_files: Vec<_> = std::fs::read_dir(&extracted_dir)
+                    .map(|entries| entries.flatten().map(|e| e.file_name().to_string_lossy().to_string()).collect())
+                    .unwrap_or_default();
+                log::warn!("❌ No audio files found. All files in directory: {:?}", all_files);
+                return Err(format!(
+                    "No audio file ({}) found in extracted directory. Found files: {:?}",
+                    AUDIO_EXTENSIONS.join(", "),
+                    all_files
+                ));
+            }
+
+            // Prefer the configured main audio, else the first track
+            let audio_file = find_main_audio(&audio_files, &config.main_audio_file).unwrap_or(&audio_files[0]);
+            log::info!("🎯 Using audio file: {}", audio_file);
+            let options = AnalyzeOptions::load(&recording.path);
+            options.validate()?;
+            step_options = serde_json::to_value(&options).ok();
+            runner.run_beatrix_analyze(&recording.path, audio_file, &options).await
+        }
+        NextStep::SetupRender | NextStep::GenerateConfig => {
+            // Check if analysis exists
+            if !recording.path.join("analysis").exists() {
+                return Err("Analysis directory not found - run analyze step first".to_string());
+            }
+
+            let main_audio = prefer_normalized_audio(&recording.path, setup_main_audio(recording, config)?);
+            if *step == NextStep::SetupRender {
+                runner.run_cinemon_render(&recording.path, "beat-switch", main_audio.as_deref()).await
+            } else {
+                runner.run_cinemon_config(&recording.path, "beat-switch", main_audio.as_deref()).await
+            }
+        }
+        NextStep::BlendSetup => {
+            let (config_path, preset) = ConfigStore::latest(&recording.path)
+                .ok_or_else(|| "No animation config found - run generate config step first".to_string())?;
+            log::info!("🎬 Using {} config for blend setup", preset);
+            runner.run_cinemon_blend_setup(&recording.path, &config_path).await
+        }
+        NextStep::Render => {
+            let blend_file = find_blend_file(&recording.path)?;
+            let settings = RenderSettings::load(&recording.path);
+            settings.validate()?;
+            let preset = ConfigStore::latest(&recording.path).map(|(_, preset)| preset);
+            step_options = serde_json::to_value(RenderRunOptions { preset, settings: settings.clone() }).ok();
+            match complete_frame_sequence(&recording.path) {
+                Some(sequence) => {
+                    log::info!("🎞️ All {} frames of '{}' exist, encoding only", sequence.frames.len(), recording.name);
+                    let start = sequence.frames.first().copied().unwrap_or(1);
+                    let fps = match settings.fps {
+                        Some(fps) => f64::from(fps),
+                        None => runner.run_blender_frame_range(&config.cli_paths.blender_path, &blend_file).await
+                            .ok()
+                            .and_then(|probe| FrameRange::find_in(&probe.stdout))
+                            .and_then(|range| range.fps)
+                            .unwrap_or(30.0),
+                    };
+                    encode_frame_sequence(&runner, recording, config, &sequence, start, fps).await
+                        .map(|result| ProcessResult::from_phases(vec![("encode", result)]))
+                }
+                None if runner_options.render_host.is_remote() => {
+                    let on_line = render_progress_reporter(&recording.path, on_progress);
+                    render_on_host(recording, config, &runner_options, &blend_file, &settings, on_line).await
+                }
+                None => {
+                    let on_line = render_progress_reporter(&recording.path, on_progress);
+                    runner.run_blender_render(&config.cli_paths.blender_path, &blend_file, None, &settings, on_line).await
+                }
+            }
+        }
+        NextStep::Upload => {
+            // Check if render output exists, in the version promoted for upload
+            let version = RenderVersions::promoted(&recording.path);
+            let render_dir = recording.path.join("blender").join(&version);
+            if !render_dir.exists() {
+                return Err("Render directory not found - run render step first".to_string());
+            }
+
+            // Find video file, also in subfolders
+            let video_files = rendered_videos(&render_dir);
+            if video_files.is_empty() {
+                let hint = if find_frame_sequence(&render_dir).is_some() {
+                    " - only frames were rendered, run the render step to encode them"
+                } else {
+                    ""
+                };
+                return Err(format!("No video file ({}) found in render directory{}", RENDER_VIDEO_EXTENSIONS.join(", "), hint));
+            }
+
+            let profile = upload_profile
+                .ok_or_else(|| "No upload profile selected for upload step".to_string())?;
+            let metadata = UploadMetadataStore::load_or_default(&recording.path, &recording.name)?;
+            metadata.validate()?;
+
+            // Ran before the upload itself, in this order; fresh copies are reused
+            let mut phases = Vec::new();
+            let mut video_path = video_files[0].clone();
+            if let Some(options) = profile.post_process.as_ref().filter(|options| !options.is_empty()) {
+                let (post_processed, result) = post_process_video(&runner, recording, &video_path, options).await?;
+                if let Some(result) = result {
+                    let success = result.success;
+                    phases.push(("post_process", result));
+                    if !success {
+                        let result = ProcessResult::from_phases(phases);
+                        write_step_log(recording, step, &result);
+                        if let Some(timeout) = &result.timeout {
+                            return Err(format!("Post-processing timed out: {}", timeout));
+                        }
+                        return Ok(result);
+                    }
+                }
+                video_path = post_processed;
+            }
+            if let Some(transcode) = &profile.transcode {
+                let source_name = RenderVersions::transcode_source_name(&version, &video_path);
+                let transcoded = UploadConfig::transcoded_path(&recording.path, &profile.name, &source_name)?;
+                if !UploadConfig::is_transcode_fresh(&video_path, &transcoded) {
+                    let result = transcode_for_upload(&runner, recording, &video_path, &transcoded, transcode).await?;
+                    let success = result.success;
+                    phases.push(("transcode", result));
+                    if !success {
+                        let result = ProcessResult::from_phases(phases);
+                        write_step_log(recording, step, &result);
+                        if let Some(timeout) = &result.timeout {
+                            return Err(format!("Transcode timed out: {}", timeout));
+                        }
+                        return Ok(result);
+                    }
+                }
+                video_path = transcoded;
+            }
+
+            let upload = runner.run_medusa_upload(&video_path, &profile.config_path, &metadata).await;
+            if let Ok(result) = &upload {
+                record_upload(&recording.path, result, &profile.name);
+            }
+            if phases.is_empty() {
+                upload
+            } else {
+                upload.map(|upload| {
+                    phases.push(("upload", upload));
+                    ProcessResult::from_phases(phases)
+                })
+            }
+        }
+        NextStep::Transcribe => {
+            let audio = main_audio_path(&recording.path, config)
+                .ok_or_else(|| "No main audio found in extracted directory - run extract step first".to_string())?;
+            let output_dir = recording.path.join(SUBTITLES_DIR);
+            std::fs::create_dir_all(&output_dir)
+                .map_err(|e| format!("Failed to create {}: {}", output_dir.display(), e))?;
+            let result = runner.run_whisper_transcribe(&config.cli_paths.whisper_path, &audio, &output_dir).await;
+            // Whisper names the file after the audio; the VTT copy is for players
+            if result.as_ref().is_ok_and(|result| result.success) {
+                let srt = SubtitleFiles::find(&recording.path)
+                    .and_then(|files| files.srt)
+                    .ok_or_else(|| "Whisper finished without writing an SRT file".to_string())?;
+                write_vtt(&srt)?;
+            }
+            result
+        }
+        NextStep::Retry => {
+            return Err("Retry step should be resolved to specific step before execution".to_string());
+        }
+        NextStep::Custom(id) => {
+            return Err(format!("Custom step '{}' runs from its definition, not as a built-in step", id));
+        }
+    }
+    .map_err(|e| format!("Command execution failed: {}", e))?;
+    let after_hooks = run_step_hooks(&runner, recording, step, hooks, HookTiming::After, Some(&result)).await;
+    attach_hook_runs(&mut result, &before_hooks, &after_hooks);
+    write_step_log_with_options(recording, step, &result, step_options.as_ref());
+    if let Some(timeout) = &result.timeout {
+        return Err(format!("{} timed out: {}", step.label(), timeout));
+    }
+
+    if matches!(step, NextStep::SetupRender | NextStep::BlendSetup) && result.success {
+        finish_setup(&recording.path);
+    }
+
+    Ok(result)
+}
+
+/// A fresh setup matches the current sources again, and its project becomes the active version
+fn finish_setup(recording_path: &Path) {
+    ConfigSync::clear_stale(recording_path);
+    match BlendVersions::adopt(recording_path) {
+        Ok(adopted) => adopted.iter().for_each(|file| log::info!("🗂️ Blender project saved as {}", file)),
+        Err(e) => log::warn!("{}", e),
+    }
+}
+
+/// Run a custom step from the settings. A command that succeeds without creating
+/// the output markers fails the step, otherwise it would stay pending forever.
+async fn execute_custom_step(
+    recording: &Recording,
+    custom: &CustomStep,
+    config: &AppConfig,
+    hooks: &[StepHook],
+    runner_options: &RunnerOptions
+) -> Result<ProcessResult, String> {
+    let step = custom.next_step();
+    let runner = runner_options.runner(config, &step).with_step_lock(&recording.path);
+
+    if !custom.inputs_ready(&recording.path) {
+        return Err(format!("Step '{}' needs {} - run the earlier steps first", custom.id, custom.inputs.join(", ")));
+    }
+    let _lock = StepLock::acquire(&recording.path, &step)?;
+
+    let before_hooks = run_step_hooks(&runner, recording, &step, hooks, HookTiming::Before, None).await;
+    if let Some(result) = failed_before_hook(&before_hooks) {
+        write_step_log(recording, &step, &result);
+        return Ok(result);
+    }
+
+    log::info!("🧩 Running custom step '{}' for '{}'", custom.id, recording.name);
+    let env = step_environment(recording, &step, None);
+    let mut result = runner
+        .run_shell_command(&command_line(&custom.command), &env, &recording.path)
+        .await
+        .map_err(|e| format!("Command execution failed: {}", e))?;
+    if result.success && !custom.is_done(&recording.path) {
+        result.success = false;
+        result.stderr.push_str(&format!("\nStep '{}' finished without creating {}", custom.id, custom.outputs.join(", ")));
+    }
+
+    let after_hooks = run_step_hooks(&runner, recording, &step, hooks, HookTiming::After, Some(&result)).await;
+    attach_hook_runs(&mut result, &before_hooks, &after_hooks);
+    write_step_log(recording, &step, &result);
+    if let Some(timeout) = &result.timeout {
+        return Err(format!("{} timed out: {}", custom.name, timeout));
+    }
+    Ok(result)
+}
+
+/// Render on a remote host: mirror the recording there, render its copy and fetch
+/// blender/render back, also after a failed render so finished frames can be resumed
+async fn render_on_host(
+    recording: &Recording,
+    config: &AppConfig,
+    options: &RunnerOptions,
+    blend_file: &Path,
+    settings: &RenderSettings,
+    on_line: LineCallback
+) -> anyhow::Result<ProcessResult> {
+    let host = &options.render_host;
+    let (Some(push), Some(pull)) = (host.push_args(&recording.path), host.pull_args(&recording.path, "blender/render")) else {
+        anyhow::bail!("Cannot place {} on the render host", recording.path.display());
+    };
+    log::info!("🛰️ Rendering '{}' on {:?}", recording.name, host);
+
+    let sync_runner = ProcessRunner::new(config.cli_paths.workspace_root.clone(), config.cli_paths.uv_path.clone());
+    let pushed = sync_runner.run_rsync(&push).await?;
+    if !pushed.success {
+        return Ok(ProcessResult::from_phases(vec![("sync_to_host", pushed)]));
+    }
+
+    let remote_runner = options.runner(config, &NextStep::Render).with_backend(host.clone(), &recording.path);
+    let rendered = remote_runner.run_blender_render(&config.cli_paths.blender_path, blend_file, None, settings, on_line).await?;
+    let pulled = sync_runner.run_rsync(&pull).await?;
+
+    // Report the render's outcome and output, failing too when its output did not come back
+    let fetched = pulled.success;
+    let fetch_error = pulled.stderr.clone();
+    let mut result = ProcessResult::from_phases(vec![("sync_to_host", pushed), ("render", rendered.clone()), ("sync_from_host", pulled)]);
+    result.success = rendered.success && fetched;
+    result.stdout = rendered.stdout;
+    result.stderr = rendered.stderr;
+    result.exit_code = rendered.exit_code;
+    result.timeout = rendered.timeout;
+    if !fetched {
+        if !result.stderr.is_empty() && !result.stderr.ends_with('\n') {
+            result.stderr.push('\n');
+        }
+        result.stderr.push_str(&format!("Fetching the render from the host failed: {}", fetch_error));
+    }
+    Ok(result)
+}
+
+/// Continue an interrupted image-sequence render: render only the frames missing
+/// from blender/render/, then encode all frames into the final video
+#[tauri::command]
+pub async fn resume_render(
+    recording_name: String,
+    app: AppHandle,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>,
+    notifier: State<'_, Notifier>
+) -> Result<ResumePlan, String> {
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    if recording.status.is_in_progress() {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, recording.status));
+    }
+
+    let runner_options = RunnerOptions {
+        on_stall: Some(step_stall_emitter(&app, &recording.name)),
+        ..runner_options_for(&NextStep::Render, &settings)?
+    };
+    let on_progress = render_progress_emitter(&app, &recording.name);
+    let result = resume_render_impl(&recording, &config, &runner_options, on_progress).await;
+    let step_result = result.as_ref().map(|(_, r)| r.clone()).map_err(|e| e.clone());
+    notify_step_result(&app, &notifier, &recording.name, &NextStep::Render, &step_result);
+
+    let (plan, result) = result?;
+    if let Some(timeout) = &result.timeout {
+        return Err(format!("Render timed out: {}", timeout));
+    }
+    if !result.success {
+        return Err(format!("Failed to resume render: {}", result.stderr));
+    }
+    Ok(plan)
+}
+
+async fn resume_render_impl(
+    recording: &Recording,
+    config: &AppConfig,
+    runner_options: &RunnerOptions,
+    on_progress: ProgressCallback
+) -> Result<(ResumePlan, ProcessResult), String> {
+    ensure_disk_space(recording, &NextStep::Render, config, None)?;
+    let blend_file = find_blend_file(&recording.path)?;
+    let render_dir = recording.path.join("blender").join("render");
+    let sequence = find_frame_sequence(&render_dir)
+        .ok_or_else(|| "No rendered frames found - resuming needs an image-sequence render, run the render step instead".to_string())?;
+
+    let settings = RenderSettings::load(&recording.path);
+    settings.validate()?;
+
+    let _lock = StepLock::acquire(&recording.path, &NextStep::Render)?;
+    let runner = runner_options.step_runner(config, &NextStep::Render, recording);
+    let blender = &config.cli_paths.blender_path;
+
+    let probe = runner.run_blender_frame_range(blender, &blend_file).await
+        .map_err(|e| format!("Command execution failed: {}", e))?;
+    let range = FrameRange::find_in(&probe.stdout)
+        .ok_or_else(|| format!("Could not read the frame range from {}", blend_file.display()))?;
+    if range.step != 1 {
+        return Err(format!("Frame step {} cannot be encoded as one sequence", range.step));
+    }
+
+    let plan = sequence.plan(&range);
+    log::info!(
+        "🎥 Resuming render of '{}': {} of {} frames present, missing {:?}",
+        recording.name, plan.existing_frames, plan.total_frames, plan.missing_ranges
+    );
+
+    let mut phases = vec![("frame_range".to_string(), probe)];
+    for (start, end) in &plan.missing_ranges {
+        let on_line = render_progress_reporter(&recording.path, Some(on_progress.clone()));
+        let result = runner.run_blender_render(blender, &blend_file, Some((*start, *end)), &settings, on_line).await
+            .map_err(|e| format!("Command execution failed: {}", e))?;
+        let success = result.success;
+        phases.push((format!("render_{}-{}", start, end), result));
+        if !success {
+            break;
+        }
+    }
+
+    if phases.iter().all(|(_, r)| r.success) {
+        let fps = settings.fps.map(f64::from).or(range.fps).unwrap_or(30.0);
+        let result = encode_frame_sequence(&runner, recording, config, &sequence, range.start, fps).await
+            .map_err(|e| format!("Encode failed to start: {}", e))?;
+        phases.push(("encode".to_string(), result));
+    }
+
+    let result = ProcessResult::from_phases(phases.iter().map(|(name, r)| (name.as_str(), r.clone())).collect());
+    write_step_log(recording, &NextStep::Render, &result);
+    Ok((plan, result))
+}
+
+/// Encode the frames of an image-sequence render, or remux or transcode a rendered
+/// video, into blender/render/final.mp4
+#[tauri::command]
+pub async fn finalize_render(
+    recording_name: String,
+    options: Option<FinalizeOptions>,
+    app: AppHandle,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>,
+    notifier: State<'_, Notifier>
+) -> Result<ProcessResult, String> {
+    let options = options.unwrap_or_default();
+    options.validate()?;
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    if recording.status.is_in_progress() {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, recording.status));
+    }
+
+    let runner_options = runner_options_for(&NextStep::Render, &settings)?;
+    let result = finalize_render_impl(&recording, &config, &runner_options, &options).await;
+    notify_step_result(&app, &notifier, &recording.name, &NextStep::Render, &result);
+
+    let result = result?;
+    if let Some(timeout) = &result.timeout {
+        return Err(format!("Finalizing timed out: {}", timeout));
+    }
+    if !result.success {
+        return Err(format!("Failed to finalize render: {}", result.stderr));
+    }
+    Ok(result)
+}
+
+async fn finalize_render_impl(
+    recording: &Recording,
+    config: &AppConfig,
+    runner_options: &RunnerOptions,
+    options: &FinalizeOptions
+) -> Result<ProcessResult, String> {
+    ensure_disk_space(recording, &NextStep::Render, config, None)?;
+    let render_dir = recording.path.join("blender").join("render");
+    let input = FinalizeInput::find(&render_dir)?;
+    let audio = if options.mux_main_audio {
+        Some(main_audio_path(&recording.path, config)
+            .ok_or_else(|| "No main audio found in extracted directory - run extract step first or keep the render's audio".to_string())?)
+    } else {
+        None
+    };
+
+    let _lock = StepLock::acquire(&recording.path, &NextStep::Render)?;
+    let runner = runner_options.runner(config, &NextStep::Render).with_step_lock(&recording.path);
+    let fps = match (options.fps.or(RenderSettings::load(&recording.path).fps.map(f64::from)), &input) {
+        (Some(fps), _) => fps,
+        (None, FinalizeInput::Frames(_)) => {
+            let blend_file = find_blend_file(&recording.path)?;
+            runner.run_blender_frame_range(&config.cli_paths.blender_path, &blend_file).await
+                .ok()
+                .and_then(|probe| FrameRange::find_in(&probe.stdout))
+                .and_then(|range| range.fps)
+                .unwrap_or(30.0)
+        }
+        (None, FinalizeInput::Video(_)) => 30.0,
+    };
+
+    // Written to the scratch directory first so a failed encode never looks like a finished render
+    let output = FinalizeOptions::output_path(&render_dir);
+    let scratch = StepScratch::new(&recording.path, &NextStep::Render);
+    scratch.create()?;
+    let partial = scratch.partial(&output);
+    let encoded = runner.run_ffmpeg_finalize(options.ffmpeg_args(&input, fps, audio.as_deref(), &partial)).await
+        .map_err(|e| format!("Command execution failed: {}", e))?;
+    if encoded.success {
+        std::fs::rename(&partial, &output)
+            .map_err(|e| format!("Failed to move finalized video into place: {}", e))?;
+    }
+    scratch.finish(encoded.success);
+
+    let result = ProcessResult::from_phases(vec![("finalize", encoded)]);
+    write_step_log_with_options(recording, &NextStep::Render, &result, serde_json::to_value(options).ok().as_ref());
+    Ok(result)
+}
+
+/// Encode a frame sequence into blender/render/final.mp4 with the main audio.
+/// The video is written to the render's scratch directory first so a failed
+/// encode never looks like a finished render.
+async fn encode_frame_sequence(
+    runner: &ProcessRunner,
+    recording: &Recording,
+    config: &AppConfig,
+    sequence: &FrameSequence,
+    start: u32,
+    fps: f64
+) -> anyhow::Result<ProcessResult> {
+    let output = recording.path.join("blender").join("render").join(RESUMED_RENDER_FILE);
+    let scratch = StepScratch::new(&recording.path, &NextStep::Render);
+    scratch.create().map_err(anyhow::Error::msg)?;
+    let partial = scratch.partial(&output);
+    let audio = main_audio_path(&recording.path, config);
+    let result = runner.run_ffmpeg_encode_frames(sequence, start, fps, audio.as_deref(), &partial).await?;
+    if result.success {
+        std::fs::rename(&partial, &output)
+            .map_err(|e| anyhow::anyhow!("Failed to move encoded video into place: {}", e))?;
+    }
+    scratch.finish(result.success);
+    Ok(result)
+}
+
+/// Render a low-resolution, every-Nth-frame preview into blender/render_preview/.
+/// Its state is kept in its own sidecar and never changes the recording status.
+#[tauri::command]
+pub async fn render_preview(
+    recording_name: String,
+    scale_percent: u32,
+    frame_step: u32,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>
+) -> Result<RenderPreview, String> {
+    let options = PreviewOptions::new(scale_percent, frame_step)?;
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    if recording.status.is_in_progress() {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, recording.status));
+    }
+    run_render_preview(&recording, options, &config, &settings).await
+}
+
+/// Render a preview of a recording's project, replacing the last preview
+async fn run_render_preview(
+    recording: &Recording,
+    options: PreviewOptions,
+    config: &AppConfig,
+    settings: &SettingsStore
+) -> Result<RenderPreview, String> {
+    // Held until Blender is done, so no setup rewrites or re-versions the project meanwhile
+    let _lock = StepLock::acquire(&recording.path, &NextStep::Render)?;
+    let blend_file = find_blend_file(&recording.path)?;
+
+    let mut preview = RenderPreview::started(&recording.path, options);
+    // Frames of an earlier preview with another step would mix into this one
+    if preview.output_dir.exists() {
+        std::fs::remove_dir_all(&preview.output_dir)
+            .map_err(|e| format!("Failed to clear {}: {}", preview.output_dir.display(), e))?;
+    }
+    preview.save(&recording.path)?;
+
+    let runner = runner_options_for(&NextStep::Render, settings)?.runner(config, &NextStep::Render);
+    let status = match runner.run_blender_preview(&config.cli_paths.blender_path, &blend_file, &options, &preview.output_dir).await {
+        Ok(result) if result.success => PreviewStatus::Done,
+        Ok(result) => match &result.timeout {
+            Some(timeout) => PreviewStatus::Failed(format!("Preview timed out: {}", timeout)),
+            None => PreviewStatus::Failed(result.stderr),
+        },
+        Err(e) => PreviewStatus::Failed(format!("Command execution failed: {}", e)),
+    };
+    preview.finish(status);
+    preview.save(&recording.path)?;
+
+    Ok(preview)
+}
+
+/// Delete the artifacts of a step and everything downstream so it can be redone.
+/// Call once without `confirm` to preview, then again with `confirm: true`.
+#[tauri::command]
+pub fn revert_step(
+    recording_name: String,
+    step: String,
+    confirm: Option<bool>,
+    config: State<'_, AppConfig>
+) -> Result<RevertReport, String> {
+    revert_step_impl(&config.recording_path(&recording_name), &step, confirm.unwrap_or(false))
+}
+
+/// Internal implementation for testing
+fn revert_step_impl(recording_path: &Path, step: &str, confirm: bool) -> Result<RevertReport, String> {
+    if !recording_path.is_dir() {
+        return Err(format!("Recording not found: {}", recording_path.display()));
+    }
+
+    let next_step: NextStep = step.parse()?;
+    if StepArtifacts::downstream_steps(&next_step).is_empty() {
+        return Err(format!("Step '{}' cannot be reverted", step));
+    }
+
+    // Holding the lock both refuses a running step and blocks new ones while deleting
+    let lock = StepLock::acquire(recording_path, &next_step)?;
+
+    let mut artifacts = StepArtifacts::artifacts_from_step(recording_path, &next_step);
+    artifacts.extend(FAILURE_MARKERS.iter().map(|m| recording_path.join(m)).filter(|p| p.exists()));
+
+    let removed = if confirm {
+        log::info!("⏪ Reverting '{}' for {}: {:?}", step, recording_path.display(), artifacts);
+        StepArtifacts::remove(&artifacts)?
+    } else {
+        artifacts
+    };
+
+    let removed = removed
+        .iter()
+        .map(|p| p.strip_prefix(recording_path).unwrap_or(p).to_string_lossy().to_string())
+        .collect();
+
+    // Release before detecting, otherwise the lock itself reports the step as running
+    drop(lock);
+    if confirm {
+        StateManifest::refresh(recording_path)?;
+    }
+    Ok(RevertReport {
+        confirmed: confirm,
+        removed,
+        status: StatusDetector::detect_status(recording_path),
+    })
+}
+
+/// Estimated space a render or upload of a recording needs and whether it fits,
+/// so the UI can warn before starting. None for steps that write little.
+#[tauri::command]
+pub fn preflight_disk_space(
+    recording_name: String,
+    step: String,
+    profile: Option<String>,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<Option<SpacePreflight>, String> {
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    let step = match step.to_lowercase().as_str() {
+        "render" => NextStep::Render,
+        "upload" => NextStep::Upload,
+        _ => return Ok(None),
+    };
+    let upload_profile = upload_profile_for(&recording, &step, profile.as_deref(), &config, &settings)?;
+    space_preflight(&recording, &step, &config, upload_profile.as_ref())
+}
+
+/// Expected render and upload size of a recording and how long the upload takes.
+/// `render_settings` and `upload_profile` default to the recording's own, so the UI
+/// can show the effect of changing them before saving.
+#[tauri::command]
+pub fn estimate_outputs(
+    recording_name: String,
+    render_settings: Option<RenderSettings>,
+    upload_profile: Option<String>,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<OutputEstimate, String> {
+    let recordings = FileScanner::scan_roots(&config.recordings_roots);
+    let recording = recordings
+        .iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    let render_settings = render_settings.unwrap_or_else(|| RenderSettings::load(&recording.path));
+    let profile = UploadConfig::resolve(&settings, &config.cli_paths.workspace_root, &recording.path, upload_profile.as_deref())?;
+    let bandwidth = UploadBandwidth::load(&settings)?.resolve(recordings.iter().map(|r| r.path.as_path()));
+    Ok(OutputEstimate::for_recording(recording, &render_settings, Some(&profile), bandwidth))
+}
+
+/// Regenerate the state manifest of a recording from its files, e.g. after
+/// editing the recording outside the app
+#[tauri::command]
+pub fn rebuild_state(recording_name: String, config: State<AppConfig>) -> Result<StateManifest, String> {
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    if recording.status.is_in_progress() {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, recording.status));
+    }
+    StateManifest::rebuild(&recording.path)
+}
+
+/// Hash the raw video and final render into the state manifest, e.g. before the
+/// originals are deleted or the library is copied to another drive
+#[tauri::command]
+pub async fn checksum_recording(recording_name: String, config: State<'_, AppConfig>) -> Result<Vec<ArtifactChecksum>, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    if let Some(status) = detect_in_progress_status(&recording_path) {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, status));
+    }
+    // Hashes gigabytes of video, keep it off the main thread
+    let checksums = tauri::async_runtime::spawn_blocking(move || record_checksums(&recording_path))
+        .await
+        .map_err(|e| format!("Checksum task failed: {}", e))??;
+    log::info!("🔏 Recorded {} checksums for '{}'", checksums.len(), recording_name);
+    Ok(checksums)
+}
+
+/// Re-hash the artifacts checksum_recording covered and report any that changed or are gone
+#[tauri::command]
+pub async fn verify_recording(recording_name: String, config: State<'_, AppConfig>) -> Result<RecordingVerification, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    let verification = tauri::async_runtime::spawn_blocking(move || verify_checksums(&recording_path, &recording_name))
+        .await
+        .map_err(|e| format!("Verify task failed: {}", e))??;
+    if !verification.ok {
+        log::warn!("⚠️ '{}' failed verification: {:?}", verification.recording, verification.checks);
+    }
+    Ok(verification)
+}
+
+/// Normalize the loudness of every extracted audio track into extracted/normalized/,
+/// which the render setup then prefers. OBS track levels vary a lot between sessions.
+#[tauri::command]
+pub async fn normalize_audio(
+    recording_name: String,
+    target_lufs: Option<f64>,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>
+) -> Result<ProcessResult, String> {
+    let runner = ProcessRunner::new(
+        config.cli_paths.workspace_root.clone(),
+        config.cli_paths.uv_path.clone()
+    )
+    .with_priority(ProcessPriority::load(&settings)?)
+    .with_environment(ProcessEnvironment::load(&settings)?.for_step(None));
+    normalize_audio_impl(&config.recording_path(&recording_name), target_lufs, &runner).await
+}
+
+/// Normalize under the step lock, like any step writing into the recording. ffmpeg
+/// writes into the scratch directory, so prefer_normalized_audio never picks a
+/// track cut short by a crash or cancel.
+async fn normalize_audio_impl(recording_path: &Path, target_lufs: Option<f64>, runner: &ProcessRunner) -> Result<ProcessResult, String> {
+    let target_lufs = target_lufs.unwrap_or(DEFAULT_TARGET_LUFS);
+    validate_target_lufs(target_lufs)?;
+    if let Some(status) = detect_in_progress_status(recording_path) {
+        return Err(format!("Recording is busy: {:?}", status));
+    }
+    let audio_files = extracted_audio_files(recording_path)?;
+    if audio_files.is_empty() {
+        return Err(format!("No audio files ({}) found in extracted directory", AUDIO_EXTENSIONS.join(", ")));
+    }
+    let step = NextStep::Custom(NORMALIZE_AUDIO_STEP.to_string());
+    let _lock = StepLock::acquire(recording_path, &step)?;
+
+    let output_dir = recording_path.join("extracted").join(NORMALIZED_AUDIO_DIR);
+    std::fs::create_dir_all(&output_dir).map_err(|e| format!("Failed to create {}: {}", output_dir.display(), e))?;
+    let scratch = StepScratch::new(recording_path, &step);
+    scratch.create()?;
+
+    let mut phases = Vec::new();
+    for audio_file in &audio_files {
+        let input = recording_path.join("extracted").join(audio_file);
+        let output = normalized_audio_path(recording_path, audio_file);
+        let partial = scratch.partial(&output);
+        let result = runner
+            .run_ffmpeg_loudnorm(&input, &partial, target_lufs)
+            .await
+            .map_err(|e| format!("Command execution failed: {}", e))?;
+        let success = result.success;
+        if success {
+            std::fs::rename(&partial, &output)
+                .map_err(|e| format!("Failed to move normalized {} into place: {}", audio_file, e))?;
+        }
+        phases.push((audio_file.as_str(), result));
+        if !success {
+            break;
+        }
+    }
+    let result = ProcessResult::from_phases(phases);
+    scratch.finish(result.success);
+    Ok(result)
+}
+
+/// Look for artifacts that do not fit together, e.g. analysis/ without extracted/
+/// or a .blend referencing media that no longer exists. Blender is optional: when
+/// it cannot run, the .blend files are skipped with a finding saying so.
+#[tauri::command]
+pub async fn validate_pipeline_integrity(
+    recording_name: String,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>
+) -> Result<Vec<IntegrityFinding>, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    log::info!("🧩 Validating pipeline integrity of '{}'", recording_name);
+    let mut findings = PipelineIntegrity::check_files(&recording_path);
+
+    let runner = ProcessRunner::new(
+        config.cli_paths.workspace_root.clone(),
+        config.cli_paths.uv_path.clone()
+    )
+    .with_environment(ProcessEnvironment::load(&settings)?.for_step(None));
+    for blend_file in find_blend_files(&recording_path) {
+        match runner.run_blender_list_media(&config.cli_paths.blender_path, &recording_path.join(&blend_file)).await {
+            Ok(result) if result.success => findings.extend(PipelineIntegrity::missing_media(&blend_file, &result.stdout)),
+            Ok(result) => findings.push(IntegrityFinding {
+                check: "blend_unreadable".to_string(),
+                detail: format!("Blender could not open {}: {}", blend_file, result.stderr.lines().last().unwrap_or("unknown error")),
+                file: Some(blend_file.clone()),
+                suggested_action: "blend_setup".to_string(),
+            }),
+            Err(e) => findings.push(IntegrityFinding {
+                check: "blend_not_checked".to_string(),
+                detail: format!("Media of {} not checked, Blender failed to run: {}", blend_file, e),
+                file: Some(blend_file.clone()),
+                suggested_action: "run_health_check".to_string(),
+            }),
+        }
+    }
+    Ok(findings)
+}
+
+/// Check the options of a step the way run_specific_step_with_options does,
+/// without running anything; no errors means they're valid
+#[tauri::command]
+pub fn validate_step_options(step: String, options: Option<serde_json::Value>) -> Vec<FieldError> {
+    StepOptions::parse(&step, &options.unwrap_or_default()).err().unwrap_or_default()
+}
+
+/// Run a step with options checked against the step's model: analyze options,
+/// setuprender options, render settings or upload options. Invalid options are
+/// refused with every bad field listed, see validate_step_options.
+#[tauri::command]
+pub async fn run_specific_step_with_options(
+    recording_name: String,
+    step: String,
+    options: Option<serde_json::Value>,
+    profile: Option<String>,
+    app: AppHandle,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>
+) -> Result<String, String> {
+    log::info!("🚀 [run_specific_step_with_options] Called for recording: {}, step: {}, options: {:?}", recording_name, step, options);
+    let options = StepOptions::parse(&step, &options.unwrap_or_default()).map_err(|errors| describe_field_errors(&errors))?;
+
+    match options {
+        StepOptions::SetupRender(opts) => run_setup_render(&recording_name, opts, &app, &config, &settings).await,
+        StepOptions::Analyze(opts) => run_analyze_with_options(recording_name, opts, app.clone(), config, settings).await,
+        StepOptions::Render(render_settings) => {
+            let recording = FileScanner::scan_roots(&config.recordings_roots)
+                .into_iter()
+                .find(|r| r.name == recording_name)
+                .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+            if recording.status.is_in_progress() {
+                return Err(format!("Recording '{}' is busy: {:?}", recording_name, recording.status));
+            }
+            if !recording.can_run_step("render") {
+                return Err(format!("Step 'render' cannot be run for recording '{}' in current status: {:?}",
+                                  recording_name, recording.status));
+            }
+            render_settings.save(&recording.path)?;
+            let notifier = app.state::<Notifier>();
+            run_specific_step(recording_name, step, profile, app.clone(), config, settings, notifier).await
+        }
+        StepOptions::Upload(opts) => {
+            let notifier = app.state::<Notifier>();
+            run_specific_step(recording_name, step, opts.profile.or(profile), app.clone(), config, settings, notifier).await
+        }
+        StepOptions::None => {
+            // Zachować istniejące step handling dla innych kroków
+            let notifier = app.state::<Notifier>();
+            run_specific_step(recording_name, step, profile, app.clone(), config, settings, notifier).await
+        }
+    }
+}
+
+/// Render setup with a preset, or from an existing animation config when the options name one
+pub async fn run_setup_render(
+    recording_name: &str,
+    opts: SetupRenderOptions,
+    app: &AppHandle,
+    config: &AppConfig,
+    settings: &SettingsStore
+) -> Result<String, String> {
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
 
-    // Determine next step
-    let next_step = recording
-        .get_next_step()
-        .ok_or_else(|| format!("No next step available for recording '{}'", recording_name))?;
+    if let Some(config_path) = &opts.config_path {
+        let config_path = user_animation_config(&recording, config_path)?;
+        let step = NextStep::BlendSetup;
+        let result = execute_step_with_preset(&recording, &step, config, settings, &opts.preset, None, Some(&config_path)).await;
+        notify_step_result(app, &app.state::<Notifier>(), &recording.name, &step, &result);
+        let result = result?;
 
-    log::info!("Next step for '{}': {:?}", recording_name, next_step);
+        return if result.success {
+            Ok(format!("✅ Render setup completed with config: {}", config_path.display()))
+        } else {
+            Err(format!("❌ Render setup failed: {}", result.stderr))
+        };
+    }
 
-    // Execute the step
-    let result = execute_step(&recording, &next_step, &config).await?;
+    let result = execute_step_with_preset(&recording, &NextStep::SetupRender, config, settings, &opts.preset, opts.main_audio.as_deref(), None).await;
+    notify_step_result(app, &app.state::<Notifier>(), &recording.name, &NextStep::SetupRender, &result);
+    let result = result?;
 
     if result.success {
-        Ok(format!("Successfully completed {} for {}", next_step.to_string().to_lowercase(), recording_name))
+        Ok(format!("✅ Render setup completed with preset: {}", opts.preset))
     } else {
-        Err(format!("Failed to execute {}: {}", next_step.to_string().to_lowercase(), result.stderr))
+        Err(format!("❌ Render setup failed: {}", result.stderr))
     }
 }
 
-/// Run a specific step for a recording
+/// Save beatrix options for a recording and run the analyze step with them;
+/// later analyze runs reuse the saved options
 #[tauri::command]
-pub async fn run_specific_step(
+pub async fn run_analyze_with_options(
     recording_name: String,
-    step: String,
-    config: State<'_, AppConfig>
+    options: AnalyzeOptions,
+    app: AppHandle,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>
 ) -> Result<String, String> {
-    log::info!("🚀 [run_specific_step] Called for recording: {}, step: {}", recording_name, step);
-
-    // Get the recording details first
-    let recordings = FileScanner::scan_recordings(&config.recordings_path);
-    let recording = recordings
+    options.validate()?;
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
         .into_iter()
         .find(|r| r.name == recording_name)
         .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
-
-    // Validate that the step can be run
-    if !recording.can_run_step(&step) {
-        return Err(format!("Step '{}' cannot be run for recording '{}' in current status: {:?}",
-                          step, recording_name, recording.status));
+    if recording.status.is_in_progress() {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, recording.status));
     }
+    if !recording.can_run_step("analyze") {
+        return Err(format!("Step 'analyze' cannot be run for recording '{}' in current status: {:?}",
+                          recording_name, recording.status));
+    }
+    options.save(&recording.path)?;
 
-    // Parse step to NextStep enum
-    let next_step = match step.to_lowercase().as_str() {
-        "analyze" => NextStep::Analyze,
-        "setup_render" | "setup-render" => NextStep::SetupRender,
-        "render" => NextStep::Render,
-        "upload" => NextStep::Upload,
-        "retry" => {
-            // For retry, determine what step to retry based on current status
-            match recording.status {
-                RecordingStatus::Failed(_) => {
-                    // Try to determine what step failed and retry it
-                    if recording.path.join("blender").join("render").exists() {
-                        NextStep::Render
-                    } else if recording.path.join("blender").exists() {
-                        NextStep::SetupRender
-                    } else if recording.path.join("analysis").exists() {
-                        NextStep::SetupRender
-                    } else if recording.path.join("extracted").exists() {
-                        NextStep::Analyze
-                    } else {
-                        return Err("Cannot determine retry step".to_string());
-                    }
-                }
-                _ => return Err("Retry only available for failed recordings".to_string()),
-            }
-        }
-        _ => return Err(format!("Unknown step: {}", step)),
-    };
+    let notifier = app.state::<Notifier>();
+    run_specific_step(recording_name, "analyze".to_string(), None, app.clone(), config, settings, notifier).await
+}
 
-    log::info!("Executing step {:?} for '{}'", next_step, recording_name);
+/// Beatrix options saved for a recording, the defaults when none were set
+#[tauri::command]
+pub fn get_analyze_options(recording_name: String, config: State<AppConfig>) -> Result<AnalyzeOptions, String> {
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .find(|r| r.name == recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    Ok(AnalyzeOptions::load(&recording.path))
+}
 
-    // Execute the step
-    let result = execute_step(&recording, &next_step, &config).await?;
+/// OBS sources of a recording the extract step can pick from
+#[tauri::command]
+pub fn list_extract_sources(recording_name: String, config: State<AppConfig>) -> Result<Vec<ExtractSource>, String> {
+    ExtractOptions::sources(&config.recording_path(&recording_name))
+}
 
-    if result.success {
-        Ok(format!("Successfully completed {} for {}", step, recording_name))
-    } else {
-        Err(format!("Failed to execute {}: {}", step, result.stderr))
+/// Obsession options saved for a recording, the defaults when none were set
+#[tauri::command]
+pub fn get_extract_options(recording_name: String, config: State<AppConfig>) -> Result<ExtractOptions, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
     }
+    Ok(ExtractOptions::load(&recording_path))
 }
 
-/// Execute a specific pipeline step
-async fn execute_step(
-    recording: &Recording,
-    step: &NextStep,
-    config: &AppConfig
-) -> Result<ProcessResult, String> {
+/// Save which sources the extract step extracts for a recording
+#[tauri::command]
+pub fn set_extract_options(recording_name: String, options: ExtractOptions, config: State<AppConfig>) -> Result<ExtractOptions, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    options.save(&recording_path)?;
+    log::info!("🎛️ Extract options for '{}': {:?}", recording_name, options.cli_args());
+    Ok(options)
+}
+
+/// Animation presets with their descriptions, listed once and then served from state
+#[tauri::command]
+pub async fn list_animation_presets(
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>,
+    catalog: State<'_, PresetCatalog>
+) -> Result<Vec<PresetInfo>, String> {
+    if let Some(presets) = catalog.get() {
+        return Ok(presets);
+    }
+    refresh_animation_presets(config, settings, catalog).await
+}
+
+/// Ask cinemon for the presets again, e.g. after adding a custom one
+#[tauri::command]
+pub async fn refresh_animation_presets(
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>,
+    catalog: State<'_, PresetCatalog>
+) -> Result<Vec<PresetInfo>, String> {
     let runner = ProcessRunner::new(
         config.cli_paths.workspace_root.clone(),
         config.cli_paths.uv_path.clone()
-    );
-
-    let result = match step {
-        NextStep::Extract => {
-            // Note: Extract step is typically done by obsession, not part of fermata scope
-            return Err("Extract step not implemented in fermata - use obsession package".to_string());
-        }
-        NextStep::Analyze => {
-            // Look for audio file in extracted directory
-            let extracted_dir = recording.path.join("extracted");
-            if !extracted_dir.exists() {
-                return Err("Extracted directory not found - run extract step first".to_string());
-            }
-
-            log::info!("🔍 Searching for audio files in: {}", extracted_dir.display());
+    )
+    .with_environment(ProcessEnvironment::load(&settings)?.for_step(None))
+    .with_toolchain(Toolchain::load(&settings)?);
+    let result = runner.list_cinemon_presets().await.map_err(|e| e.to_string())?;
+    if !result.success {
+        return Err(format!("Failed to list presets: {}", result.stderr));
+    }
 
-            // Find audio file (typically .m4a)
-            let audio_files: Vec<_> = std::fs::read_dir(&extracted_dir)
-                .map_err(|e| format!("Failed to read extracted directory: {}", e))?
-                .filter_map(|entry| {
-                    let entry = entry.ok()?;
-                    let path = entry.path();
-                    log::info!("📁 Found file: {:?}", path);
-                    if path.extension()?.to_str()? == "m4a" {
-                        path.file_name()?.to_str().map(|s| s.to_string())
-                    } else {
-                        None
-                    }
-                })
-                .collect();
+    let presets_dir = PresetCatalog::presets_dir(&config.cli_paths.workspace_root);
+    let presets: Vec<PresetInfo> = PresetInfo::parse_names(&result.stdout)
+        .iter()
+        .map(|name| PresetInfo::load(name, &presets_dir))
+        .collect();
+    catalog.set(presets.clone());
+    Ok(presets)
+}
 
-            log::info!("🎵 Found {} audio files: {:?}", audio_files.len(), audio_files);
+/// A hand-maintained animation config, relative to the recording unless absolute.
+/// It has to exist and pass the same schema check as configs edited in the GUI.
+fn user_animation_config(recording: &Recording, config_path: &str) -> Result<PathBuf, String> {
+    let path = recording.path.join(config_path);
+    if !path.is_file() {
+        return Err(format!("Animation config not found: {}", path.display()));
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    validate_animation_config(&content)
+        .map_err(|errors| format!("Invalid animation config {}:\n{}", path.display(), errors.join("\n")))?;
+    Ok(path)
+}
 
-            if audio_files.is_empty() {
-                // List all files in directory for debugging
-                if let Ok(entries) = std::fs::read_dir(&extracted_dir) {
-                    let all_files: Vec<_> = entries
-                        .filter_map(|e| e.ok())
-                        .map(|e| e.file_name().to_string_lossy().to_string())
-                        .collect();
-                    log::warn!("❌ No .m4a files found. All files in directory: {:?}", all_files);
-                    return Err(format!("No audio file (.m4a) found in extracted directory. Found files: {:?}", all_files));
-                } else {
-                    return Err("No audio file (.m4a) found in extracted directory".to_string());
-                }
-            }
+/// Execute a specific pipeline step with preset options. Blend setup uses
+/// `config_path` when given, otherwise the stored config of the preset.
+async fn execute_step_with_preset(
+    recording: &Recording,
+    step: &NextStep,
+    config: &AppConfig,
+    settings: &SettingsStore,
+    preset: &str,
+    main_audio: Option<&str>,
+    config_path: Option<&Path>
+) -> Result<ProcessResult, String> {
+    let hooks = &StepHooks::list(settings)?;
+    let runner_options = runner_options_for(step, settings)?;
+    let runner = runner_options.step_runner(config, step, recording);
 
-            let audio_file = &audio_files[0]; // Take first audio file
-            log::info!("🎯 Using audio file: {}", audio_file);
-            runner.run_beatrix_analyze(&recording.path, audio_file).await
-        }
-        NextStep::SetupRender => {
-            // Check if analysis exists
-            if !recording.path.join("analysis").exists() {
+    match step {
+        NextStep::SetupRender | NextStep::GenerateConfig | NextStep::BlendSetup => {
+            let config_path = if let (NextStep::BlendSetup, Some(config_path)) = (step, config_path) {
+                Some(config_path.to_path_buf())
+            } else if *step == NextStep::BlendSetup {
+                let (config_path, _) = ConfigStore::configs(&recording.path)
+                    .into_iter()
+                    .find(|(_, p)| p == preset)
+                    .ok_or_else(|| format!("No animation config for preset '{}' - run generate config step first", preset))?;
+                Some(config_path)
+            } else if !recording.path.join("analysis").exists() {
+                // Check if analysis exists
                 return Err("Analysis directory not found - run analyze step first".to_string());
-            }
-
-            // Check if we have multiple audio files and use configured main audio
-            let extracted_dir = recording.path.join("extracted");
-            if extracted_dir.exists() {
-                let audio_files: Vec<_> = std::fs::read_dir(&extracted_dir)
-                    .map_err(|e| format!("Failed to read extracted directory: {}", e))?
-                    .filter_map(|entry| {
-                        let entry = entry.ok()?;
-                        let path = entry.path();
-                        if path.extension()?.to_str()? == "m4a" {
-                            path.file_name()?.to_str().map(|s| s.to_string())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-
-                log::info!("🎵 Found {} audio files for setup render: {:?}", audio_files.len(), audio_files);
-
-                if audio_files.len() > 1 {
-                    // Use configured main audio file if available
-                    if !config.main_audio_file.is_empty() && audio_files.contains(&config.main_audio_file) {
-                        log::info!("🎯 Using configured main audio: {}", config.main_audio_file);
-                        runner.run_cinemon_render(&recording.path, "beat-switch", Some(&config.main_audio_file)).await
-                    } else {
-                        log::warn!("⚠️ Multiple audio files found but main audio '{}' not available in: {:?}", config.main_audio_file, audio_files);
-                        return Err(format!("Multiple audio files found: {:?}. Configure FERMATA_MAIN_AUDIO environment variable to specify which one to use.", audio_files));
-                    }
-                } else {
-                    // Single audio file, use without --main-audio parameter
-                    runner.run_cinemon_render(&recording.path, "beat-switch", None).await
-                }
             } else {
-                // No extracted directory, use basic render
-                runner.run_cinemon_render(&recording.path, "beat-switch", None).await
-            }
-        }
-        NextStep::Render => {
-            // Check if blender project exists
-            let blender_dir = recording.path.join("blender");
-            if !blender_dir.exists() {
-                return Err("Blender project not found - run setup render step first".to_string());
-            }
-
-            // Find .blend file
-            let blend_files: Vec<_> = std::fs::read_dir(&blender_dir)
-                .map_err(|e| format!("Failed to read blender directory: {}", e))?
-                .filter_map(|entry| {
-                    let entry = entry.ok()?;
-                    let path = entry.path();
-                    if path.extension()?.to_str()? == "blend" {
-                        Some(path)
-                    } else {
-                        None
-                    }
-                })
-                .collect();
+                None
+            };
 
-            if blend_files.is_empty() {
-                return Err("No .blend file found in blender directory".to_string());
+            let _lock = StepLock::acquire(&recording.path, step)?;
+            let before_hooks = run_step_hooks(&runner, recording, step, hooks, HookTiming::Before, None).await;
+            if let Some(result) = failed_before_hook(&before_hooks) {
+                write_step_log(recording, step, &result);
+                return Ok(result);
             }
 
-            // For now, return error asking user to render manually
-            // TODO: Implement automatic Blender rendering
-            return Err("Manual Blender rendering required. Open the .blend file and render manually, or implement automatic rendering.".to_string());
-        }
-        NextStep::Upload => {
-            // Check if render output exists
-            let render_dir = recording.path.join("blender").join("render");
-            if !render_dir.exists() {
-                return Err("Render directory not found - run render step first".to_string());
+            // cinemon writes the project under a fixed name; version the current one so it isn't overwritten
+            if *step != NextStep::GenerateConfig {
+                BlendVersions::adopt(&recording.path)?;
             }
 
-            // Find video file
-            let video_files: Vec<_> = std::fs::read_dir(&render_dir)
-                .map_err(|e| format!("Failed to read render directory: {}", e))?
-                .filter_map(|entry| {
-                    let entry = entry.ok()?;
-                    let path = entry.path();
-                    if path.extension()?.to_str()? == "mp4" {
-                        Some(path)
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-
-            if video_files.is_empty() {
-                return Err("No video file (.mp4) found in render directory".to_string());
+            let main_audio = prefer_normalized_audio(&recording.path, main_audio.map(str::to_string));
+            let main_audio = main_audio.as_deref();
+            log::info!("🎬 Running {} with preset: {}, main_audio: {:?}", step, preset, main_audio);
+            let mut result = match (step, config_path) {
+                (NextStep::GenerateConfig, _) => runner.run_cinemon_config(&recording.path, preset, main_audio).await,
+                (NextStep::BlendSetup, Some(config_path)) => runner.run_cinemon_blend_setup(&recording.path, &config_path).await,
+                _ => runner.run_cinemon_render(&recording.path, preset, main_audio).await,
             }
-
-            // For MVP, use a default config - in future this should be configurable
-            let config_path = config.cli_paths.workspace_root.join("packages/medusa/examples/config_example.json");
-            if !config_path.exists() {
-                return Err("Medusa config not found - check medusa package setup".to_string());
+            .map_err(|e| format!("Command execution failed: {}", e))?;
+            let after_hooks = run_step_hooks(&runner, recording, step, hooks, HookTiming::After, Some(&result)).await;
+            attach_hook_runs(&mut result, &before_hooks, &after_hooks);
+            write_step_log(recording, step, &result);
+            if let Some(timeout) = &result.timeout {
+                return Err(format!("{} timed out: {}", step.label(), timeout));
             }
-
-            runner.run_medusa_upload(&video_files[0], &config_path).await
+            if result.success && *step != NextStep::GenerateConfig {
+                finish_setup(&recording.path);
+            }
+            Ok(result)
         }
-        NextStep::Retry => {
-            return Err("Retry step should be resolved to specific step before execution".to_string());
+        _ => {
+            // Fallback to regular execute_step for other steps
+            execute_step(recording, step, config, hooks, None, Some(runner_options), None).await
         }
     }
-    .map_err(|e| format!("Command execution failed: {}", e))?;
+}
 
-    Ok(result)
+/// Generate the animation config of a preset without touching the Blender project
+#[tauri::command]
+pub async fn generate_render_config(
+    recording_name: String,
+    preset: String,
+    main_audio: Option<String>,
+    app: AppHandle,
+    config: State<'_, AppConfig>
+) -> Result<String, String> {
+    run_setup_substep(&recording_name, &NextStep::GenerateConfig, Some(preset), main_audio, &app, &config).await
 }
 
+/// Build the Blender project from an existing config, leaving the config as it is.
+/// Without a preset the most recently written config is used.
 #[tauri::command]
-pub async fn run_specific_step_with_options(
+pub async fn setup_blend(
     recording_name: String,
-    step: String,
-    options: Option<RenderOptions>,
+    preset: Option<String>,
+    app: AppHandle,
     config: State<'_, AppConfig>
 ) -> Result<String, String> {
-    log::info!("🚀 [run_specific_step_with_options] Called for recording: {}, step: {}, options: {:?}", recording_name, step, options);
+    run_setup_substep(&recording_name, &NextStep::BlendSetup, preset, None, &app, &config).await
+}
 
-    // Get the recording details first
-    let recordings = FileScanner::scan_recordings(&config.recordings_path);
-    let recording = recordings
+async fn run_setup_substep(
+    recording_name: &str,
+    step: &NextStep,
+    preset: Option<String>,
+    main_audio: Option<String>,
+    app: &AppHandle,
+    config: &AppConfig
+) -> Result<String, String> {
+    let recording = FileScanner::scan_roots(&config.recordings_roots)
         .into_iter()
         .find(|r| r.name == recording_name)
         .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
-
-    match step.as_str() {
-        "setuprender" => {
-            let opts = options.unwrap_or_default();
-            let result = execute_step_with_preset(&recording, &NextStep::SetupRender, &config, &opts.preset, opts.main_audio.as_deref()).await?;
-
-            if result.success {
-                Ok(format!("✅ Render setup completed with preset: {}", opts.preset))
-            } else {
-                Err(format!("❌ Render setup failed: {}", result.stderr))
-            }
-        },
-        _ => {
-            // Zachować istniejące step handling dla innych kroków
-            run_specific_step(recording_name, step, config).await
-        }
+    if recording.status.is_in_progress() {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, recording.status));
+    }
+    if !recording.can_run_step(&step.id()) {
+        return Err(format!("Step '{}' cannot be run for recording '{}' in current status: {:?}",
+                          step, recording_name, recording.status));
     }
-}
 
-#[tauri::command]
-pub async fn list_animation_presets(config: State<'_, AppConfig>) -> Result<Vec<String>, String> {
-    let runner = ProcessRunner::new(
-        config.cli_paths.workspace_root.clone(),
-        config.cli_paths.uv_path.clone()
-    );
-    let result = runner.list_cinemon_presets().await.map_err(|e| e.to_string())?;
+    let preset = match preset {
+        Some(preset) => preset,
+        None => ConfigStore::latest(&recording.path)
+            .map(|(_, preset)| preset)
+            .ok_or_else(|| "No animation config found - run generate config step first".to_string())?,
+    };
+
+    let result = execute_step_with_preset(&recording, step, config, &app.state::<SettingsStore>(), &preset, main_audio.as_deref(), None).await;
+    notify_step_result(app, &app.state::<Notifier>(), &recording.name, step, &result);
+    let result = result?;
 
     if result.success {
-        // Parse preset names from output
-        let presets: Vec<String> = result.stdout
-            .lines()
-            .filter_map(|line| {
-                if line.trim().starts_with("  ") && !line.contains("Available presets:") {
-                    Some(line.trim().to_string())
-                } else {
-                    None
-                }
-            })
-            .collect();
-        Ok(presets)
+        Ok(format!("✅ {} completed with preset: {}", step.label(), preset))
     } else {
-        Err(format!("Failed to list presets: {}", result.stderr))
+        Err(format!("❌ {} failed: {}", step.label(), result.stderr))
     }
 }
 
-/// Execute a specific pipeline step with preset options
-async fn execute_step_with_preset(
-    recording: &Recording,
-    step: &NextStep,
-    config: &AppConfig,
-    preset: &str,
-    main_audio: Option<&str>
-) -> Result<ProcessResult, String> {
-    let runner = ProcessRunner::new(
-        config.cli_paths.workspace_root.clone(),
-        config.cli_paths.uv_path.clone()
-    );
+/// Time beatrix, Blender and ffmpeg on a short synthetic workload, to compare machines
+/// and to check renders use the GPU before queuing a long one. The report is kept in
+/// the recordings root next to those of the other machines.
+#[tauri::command]
+pub async fn run_benchmark(config: State<'_, AppConfig>, settings: State<'_, SettingsStore>) -> Result<BenchmarkReport, String> {
+    let work_dir = std::env::temp_dir().join(format!("fermata-benchmark-{}", std::process::id()));
+    let extracted = work_dir.join("extracted");
+    let render_dir = work_dir.join("render");
+    for dir in [&extracted, &render_dir] {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    log::info!("⏱️ Running the pipeline benchmark in {}", work_dir.display());
+    let analyze_runner = runner_options_for(&NextStep::Analyze, &settings)?.runner(&config, &NextStep::Analyze);
+    let render_runner = runner_options_for(&NextStep::Render, &settings)?.runner(&config, &NextStep::Render);
 
-    match step {
-        NextStep::SetupRender => {
-            // Check if analysis exists
-            if !recording.path.join("analysis").exists() {
-                return Err("Analysis directory not found - run analyze step first".to_string());
-            }
+    let beatrix = match analyze_runner.run_ffmpeg_benchmark(&extracted, sample_audio_args(&extracted.join(BENCHMARK_AUDIO_FILE))).await {
+        Ok(sample) if sample.success => analyze_runner.run_beatrix_analyze(&work_dir, BENCHMARK_AUDIO_FILE, &AnalyzeOptions::default()).await,
+        _ => Ok(ProcessResult::error("ffmpeg could not generate the sample clip".to_string())),
+    };
+    let blender = render_runner.run_blender_benchmark(&config.cli_paths.blender_path, &render_dir).await;
+    let render_device = blender.as_ref().ok().and_then(|result| BenchmarkDevice::parse(&result.stdout));
+    let ffmpeg = render_runner.run_ffmpeg_benchmark(&work_dir, encode_args(&work_dir.join("encode.mp4"))).await;
+    if let Err(e) = std::fs::remove_dir_all(&work_dir) {
+        log::warn!("Failed to remove {}: {}", work_dir.display(), e);
+    }
 
-            log::info!("🎬 Setting up render with preset: {}, main_audio: {:?}", preset, main_audio);
-            runner.run_cinemon_render(&recording.path, preset, main_audio).await
-                .map_err(|e| format!("Command execution failed: {}", e))
-        }
-        _ => {
-            // Fallback to regular execute_step for other steps
-            execute_step(recording, step, config).await
-        }
+    let report = BenchmarkReport::new(
+        vec![
+            BenchmarkTiming::from_result("beatrix", &format!("Analyze a {} s sample clip", BENCHMARK_AUDIO_SECONDS), beatrix, BENCHMARK_AUDIO_SECONDS as f64, "x realtime"),
+            BenchmarkTiming::from_result("blender", &format!("Render {} Cycles frames at 720p", BENCHMARK_RENDER_FRAMES), blender, BENCHMARK_RENDER_FRAMES as f64, "frames/s"),
+            BenchmarkTiming::from_result("ffmpeg", &format!("Encode {} frames of 1080p H.264", BENCHMARK_ENCODE_FRAMES), ffmpeg, BENCHMARK_ENCODE_FRAMES as f64, "frames/s"),
+        ],
+        render_device,
+    );
+    log::info!("⏱️ Benchmark finished, rendering on the {}", if report.gpu_used { "GPU" } else { "CPU" });
+    if let Err(e) = BenchmarkLog::append(&config.recordings_path, &report) {
+        log::warn!("Failed to keep the benchmark report: {}", e);
     }
+    Ok(report)
+}
+
+/// Benchmark reports of every machine sharing the library, newest first
+#[tauri::command]
+pub fn list_benchmarks(config: State<AppConfig>) -> Result<Vec<BenchmarkReport>, String> {
+    BenchmarkLog::read(&config.recordings_path)
 }
 
 #[cfg(test)]
@@ -392,11 +2214,23 @@ mod tests {
     fn create_test_config(temp_dir: &TempDir) -> AppConfig {
         AppConfig {
             recordings_path: temp_dir.path().to_path_buf(),
+            recordings_roots: vec![temp_dir.path().to_path_buf()],
             cli_paths: crate::commands::recordings::CliPaths {
                 uv_path: "echo".to_string(), // Use echo for testing
                 workspace_root: temp_dir.path().to_path_buf(),
+                blender_path: "blender".to_string(),
+                whisper_path: "whisper".to_string(),
             },
             main_audio_file: "".to_string(), // Default to empty for testing
+            custom_fields: Vec::new(),
+            watch_debounce_ms: 0,
+            min_free_space_gb: 0,
+            step_timeouts: crate::services::StepTimeouts::default(),
+            locale: crate::services::Locale::En,
+            lifecycle_policy: crate::services::LifecyclePolicy::default(),
+            symlink_policy: crate::services::SymlinkPolicy::default(),
+            profile: None,
+            observer: false,
         }
     }
 
@@ -436,6 +2270,11 @@ mod tests {
             status,
             last_updated: std::time::SystemTime::now().duration_since(std::time::SystemTime::UNIX_EPOCH).unwrap().as_secs(),
             file_sizes: std::collections::HashMap::new(),
+            upload_block: None,
+            root: temp_dir.path().to_path_buf(),
+            lifecycle: None,
+            media: None,
+            tree: None,
         }
     }
 
@@ -455,9 +2294,18 @@ mod tests {
                 status: RecordingStatus::Extracted,
                 last_updated: std::time::SystemTime::now().duration_since(std::time::SystemTime::UNIX_EPOCH).unwrap().as_secs(),
                 file_sizes: std::collections::HashMap::new(),
+                upload_block: None,
+                root: temp_dir.path().to_path_buf(),
+                lifecycle: None,
+                media: None,
+                tree: None,
             },
             &NextStep::Analyze,
-            &config
+            &config,
+            &[],
+            None,
+            None,
+            None
         ).await;
 
         assert!(result.is_ok());
@@ -480,6 +2328,33 @@ mod tests {
         assert!(extracted_recording.can_run_step("analyze"));
     }
 
+    #[test]
+    fn test_revert_step_requires_confirmation() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = create_test_recording(&temp_dir, "test_recording", RecordingStatus::Rendered);
+
+        let preview = revert_step_impl(&recording.path, "analyze", false).unwrap();
+        assert!(!preview.confirmed);
+        assert!(preview.removed.contains(&"analysis".to_string()));
+        assert_eq!(preview.status, RecordingStatus::Rendered);
+
+        let report = revert_step_impl(&recording.path, "analyze", true).unwrap();
+        assert!(report.confirmed);
+        assert_eq!(report.status, RecordingStatus::Extracted);
+        assert!(!recording.path.join("blender").join("render").exists());
+    }
+
+    #[test]
+    fn test_revert_step_refuses_while_running() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = create_test_recording(&temp_dir, "test_recording", RecordingStatus::Analyzed);
+        let _lock = StepLock::acquire(&recording.path, &NextStep::SetupRender).unwrap();
+
+        let result = revert_step_impl(&recording.path, "analyze", true);
+        assert!(result.unwrap_err().contains("busy"));
+        assert!(recording.path.join("analysis").exists());
+    }
+
     #[tokio::test]
     async fn test_missing_dependencies() {
         let temp_dir = TempDir::new().unwrap();
@@ -488,8 +2363,145 @@ mod tests {
         // Try to analyze without extracted directory
         let recording = create_test_recording(&temp_dir, "test_recording", RecordingStatus::Recorded);
 
-        let result = execute_step(&recording, &NextStep::Analyze, &config).await;
+        let result = execute_step(&recording, &NextStep::Analyze, &config, &[], None, None, None).await;
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Extracted directory not found"));
     }
+
+    #[tokio::test]
+    async fn test_upload_blocked_recording() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let recording = create_test_recording(&temp_dir, "test_recording", RecordingStatus::Rendered);
+        UploadBlockStore::set(&recording.path, "Unreleased material").unwrap();
+
+        let result = execute_step(&recording, &NextStep::Upload, &config, &[], None, None, None).await;
+        assert_eq!(result.unwrap_err(), "Upload blocked for 'test_recording': Unreleased material");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_failing_before_hook_fails_step() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let recording = create_test_recording(&temp_dir, "test_recording", RecordingStatus::Extracted);
+        let hooks = vec![StepHook {
+            step: "analyze".to_string(),
+            when: HookTiming::Before,
+            command: "echo checking {recording}; exit 3".to_string(),
+            enabled: true,
+        }];
+
+        let result = execute_step(&recording, &NextStep::Analyze, &config, &hooks, None, None, None).await.unwrap();
+        assert!(!result.success);
+        assert!(result.stderr.starts_with("Before hook 'echo checking {recording}; exit 3' failed"));
+        assert!(result.stdout.contains("checking test_recording"));
+        assert!(!recording.path.join("analysis").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_setup_with_preset_makes_new_project_active() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+        let recording = create_test_recording(&temp_dir, "gig", RecordingStatus::Analyzed);
+        fs::create_dir_all(recording.path.join("blender")).unwrap();
+        fs::write(recording.path.join("blender/v001_gig.blend"), "first").unwrap();
+        BlendVersions::set_active(&recording.path, "v001_gig.blend").unwrap();
+        // A project left unversioned by an earlier setup
+        fs::write(recording.path.join("blender/gig.blend"), "second").unwrap();
+        // Stands in for cinemon writing the new project
+        let hooks = vec![StepHook {
+            step: "blend_setup".to_string(),
+            when: HookTiming::After,
+            command: "echo third > \"$FERMATA_RECORDING_PATH/blender/gig.blend\"".to_string(),
+            enabled: true,
+        }];
+        StepHooks::save(&settings, &hooks).unwrap();
+        let config_path = recording.path.join("animation_config_beat-switch.yaml");
+        fs::write(&config_path, "project: {}\n").unwrap();
+
+        let result = execute_step_with_preset(&recording, &NextStep::BlendSetup, &config, &settings, "beat-switch", None, Some(&config_path))
+            .await
+            .unwrap();
+        assert!(result.success, "{}", result.stderr);
+        assert_eq!(fs::read_to_string(recording.path.join("blender/v002_gig.blend")).unwrap(), "second");
+        let active = BlendVersions::active(&recording.path).unwrap();
+        assert!(active.ends_with("v003_gig.blend"));
+        assert_eq!(fs::read_to_string(active).unwrap().trim(), "third");
+    }
+
+    #[tokio::test]
+    async fn test_normalize_audio_refuses_while_busy() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let recording = create_test_recording(&temp_dir, "gig", RecordingStatus::Extracted);
+        let runner = ProcessRunner::new(config.cli_paths.workspace_root.clone(), config.cli_paths.uv_path.clone());
+        let _lock = StepLock::acquire(&recording.path, &NextStep::Analyze).unwrap();
+
+        let result = normalize_audio_impl(&recording.path, None, &runner).await;
+        assert!(result.unwrap_err().contains("busy"));
+        assert!(!recording.path.join("extracted").join(NORMALIZED_AUDIO_DIR).exists());
+    }
+
+    #[tokio::test]
+    async fn test_preview_step_runs_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+        let recording = create_test_recording(&temp_dir, "test recording", RecordingStatus::Extracted);
+
+        let preview = preview_step_impl(&recording, &NextStep::Analyze, None, None, None, &config, &settings).await.unwrap();
+        assert_eq!(preview.outputs, vec![recording.path.join("analysis")]);
+        let [command] = &preview.commands[..] else { panic!("expected one command: {:?}", preview.commands) };
+        assert_eq!(command.program, "echo");
+        assert_eq!(command.working_dir.as_deref(), Some(temp_dir.path()));
+        assert!(command.args.contains(&recording.path.join("extracted").join("audio.m4a").to_string_lossy().to_string()));
+        assert!(command.command_line.contains(&format!("'{}'", recording.path.join("analysis").display())));
+        assert!(!recording.path.join("analysis").exists());
+
+        let error = preview_step_impl(&recording, &NextStep::Render, None, None, None, &config, &settings).await.unwrap_err();
+        assert!(error.contains("Blender project not found"));
+    }
+
+    #[test]
+    fn test_user_animation_config_is_validated() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = create_test_recording(&temp_dir, "test_recording", RecordingStatus::Analyzed);
+
+        let missing = user_animation_config(&recording, "my_config.yaml").unwrap_err();
+        assert!(missing.starts_with("Animation config not found"));
+
+        fs::write(recording.path.join("my_config.yaml"), "project: {fps: 30}\n").unwrap();
+        let invalid = user_animation_config(&recording, "my_config.yaml").unwrap_err();
+        assert!(invalid.contains("Missing required section: layout"));
+
+        let config = "project: {fps: 30}\nlayout: {type: grid}\nstrip_animations: {}\n";
+        fs::write(recording.path.join("my_config.yaml"), config).unwrap();
+        let path = user_animation_config(&recording, "my_config.yaml").unwrap();
+        assert_eq!(path, recording.path.join("my_config.yaml"));
+    }
+
+    #[test]
+    fn test_record_upload_appends_medusa_outcome() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = create_test_recording(&temp_dir, "test_recording", RecordingStatus::Rendered);
+        let upload = |url: &str| ProcessResult {
+            outcome: StepOutcome::from_medusa(&format!("✅ Upload successful: {}\n", url)),
+            ..ProcessResult::dry_run()
+        };
+
+        record_upload(&recording.path, &ProcessResult::error("❌ Upload failed: quota".to_string()), "public");
+        assert!(StatusDetector::read_upload_results(&recording.path).is_none());
+
+        record_upload(&recording.path, &upload("https://youtu.be/abc"), "public");
+        record_upload(&recording.path, &upload("https://youtu.be/def"), "unlisted");
+        let results = StatusDetector::read_upload_results(&recording.path).unwrap();
+        let links: Vec<_> = results.entries.iter().filter_map(|e| e.link()).collect();
+        assert_eq!(links, ["https://youtu.be/abc", "https://youtu.be/def"]);
+        assert_eq!(results.entries[1].extra["profile"], "unlisted");
+        assert!(results.entries.iter().all(|e| e.timestamp.is_some()));
+        assert_eq!(StatusDetector::detect_from_files(&recording.path), RecordingStatus::Uploaded);
+    }
 }
@@ -0,0 +1,94 @@
+use crate::commands::recordings::{forward_recording_change, AppConfig, WatcherState};
+use crate::services::{load_recording_change, PollingMode, PollingSettings, RecordingsPoller, SettingsStore};
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+/// Rescan the recordings roots every `interval_seconds` of the polling settings
+pub fn start_recordings_poller(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let settings = load_settings(&app);
+            tokio::time::sleep(Duration::from_secs(settings.interval_seconds)).await;
+            let roots = polled_roots(&app, settings.mode);
+            if !roots.is_empty() {
+                poll_recordings(&app, roots).await;
+            }
+        }
+    });
+}
+
+fn load_settings(app: &AppHandle) -> PollingSettings {
+    PollingSettings::load(&app.state::<SettingsStore>()).unwrap_or_else(|e| {
+        log::warn!("{}", e);
+        PollingSettings::default()
+    })
+}
+
+/// Roots the timer rescans; in fallback mode the watched ones report changes themselves
+fn polled_roots(app: &AppHandle, mode: PollingMode) -> Vec<PathBuf> {
+    let roots = app.state::<AppConfig>().recordings_roots.clone();
+    match mode {
+        PollingMode::Off => Vec::new(),
+        PollingMode::Always => roots,
+        PollingMode::Fallback => {
+            let watchers = app.try_state::<WatcherState>();
+            roots
+                .into_iter()
+                .filter(|root| !watchers.as_ref().is_some_and(|w| w.is_watched(root)))
+                .collect()
+        }
+    }
+}
+
+/// Rescan `roots` and emit a `recording-changed` event per changed recording;
+/// returns how many changed
+async fn poll_recordings(app: &AppHandle, roots: Vec<PathBuf>) -> usize {
+    let poller_app = app.clone();
+    let changes = tauri::async_runtime::spawn_blocking(move || {
+        let poller = poller_app.state::<RecordingsPoller>();
+        roots
+            .iter()
+            .flat_map(|root| poller.poll(root).into_iter().map(|name| load_recording_change(root, name)))
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap_or_else(|e| {
+        log::warn!("Polling the recordings failed: {}", e);
+        Vec::new()
+    });
+
+    let count = changes.len();
+    if count > 0 {
+        log::info!("🔁 Polling found {} changed recordings", count);
+    }
+    for change in changes {
+        forward_recording_change(app, change);
+    }
+    count
+}
+
+/// The window came back into focus; rescan every root unless polling is off,
+/// since watchers may have missed changes made while the machine slept
+#[tauri::command]
+pub async fn window_focused(app: AppHandle) -> Result<usize, String> {
+    if load_settings(&app).mode == PollingMode::Off {
+        return Ok(0);
+    }
+    let roots = polled_roots(&app, PollingMode::Always);
+    Ok(poll_recordings(&app, roots).await)
+}
+
+#[tauri::command]
+pub fn get_polling_settings(settings: State<SettingsStore>) -> Result<PollingSettings, String> {
+    PollingSettings::load(&settings)
+}
+
+/// Takes effect from the next poll
+#[tauri::command]
+pub fn set_polling_settings(polling: PollingSettings, settings: State<SettingsStore>) -> Result<(), String> {
+    polling.save(&settings)?;
+    log::info!("🔁 Polling {:?} every {}s", polling.mode, polling.interval_seconds);
+    Ok(())
+}
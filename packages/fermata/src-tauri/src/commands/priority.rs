@@ -0,0 +1,16 @@
+use crate::services::{ProcessPriority, SettingsStore};
+use tauri::State;
+
+/// Niceness, I/O class and CPU cores the pipeline's processes run with
+#[tauri::command]
+pub fn get_process_priority(settings: State<SettingsStore>) -> Result<ProcessPriority, String> {
+    ProcessPriority::load(&settings)
+}
+
+/// Takes effect for the next process a step starts
+#[tauri::command]
+pub fn set_process_priority(priority: ProcessPriority, settings: State<SettingsStore>) -> Result<ProcessPriority, String> {
+    priority.save(&settings)?;
+    log::info!("🐢 Process priority: {:?}", priority);
+    Ok(priority)
+}
@@ -0,0 +1,40 @@
+use crate::commands::recordings::AppConfig;
+use crate::services::{FileScanner, RecentRecording, RecentRecordings, SettingsStore};
+use tauri::State;
+
+/// Pinned and recently opened or processed recordings, pinned first; recordings
+/// no longer on disk are left out
+#[tauri::command]
+pub fn get_recent_recordings(config: State<AppConfig>, settings: State<SettingsStore>) -> Result<Vec<RecentRecording>, String> {
+    Ok(RecentRecordings::load(&settings)?
+        .list()
+        .into_iter()
+        .filter(|entry| FileScanner::find_root(&config.recordings_roots, &entry.name).is_some())
+        .collect())
+}
+
+#[tauri::command]
+pub fn pin_recording(
+    recording_name: String,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<Vec<RecentRecording>, String> {
+    if FileScanner::find_root(&config.recordings_roots, &recording_name).is_none() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    set_pinned(&recording_name, true, &settings)
+}
+
+#[tauri::command]
+pub fn unpin_recording(recording_name: String, settings: State<SettingsStore>) -> Result<Vec<RecentRecording>, String> {
+    set_pinned(&recording_name, false, &settings)
+}
+
+fn set_pinned(recording_name: &str, pinned: bool, settings: &SettingsStore) -> Result<Vec<RecentRecording>, String> {
+    let mut recent = RecentRecordings::load(settings)?;
+    if recent.set_pinned(recording_name, pinned) {
+        recent.save(settings)?;
+        log::info!("📌 {} '{}'", if pinned { "Pinned" } else { "Unpinned" }, recording_name);
+    }
+    Ok(recent.list())
+}
@@ -1,20 +1,53 @@
-use crate::models::Recording;
-use crate::services::FileScanner;
-use std::path::PathBuf;
-use tauri::State;
+use crate::commands::jobs::track_new_recording;
+use crate::models::{CustomFieldDefinition, FileTreeNode, Recording, RecordingSnooze, RecordingStatus};
+use crate::services::{
+    attention_items, default_main_audio, recordings_calendar, CalendarDay, CalendarRange, detect_in_progress_status, directory_size, probe_video_geometry, status_summary, AttentionItem, BulkDeletePreview, BulkDeleteResult, BulkDeleteStaging, CloneReport, ConfigProfile, ConfigProfiles, sanitize_recording_name, suggest_recording_name, unique_recording_name, DeletePreview, JobQueue, quarantine_recording as quarantine, QuarantineEntry, JobStatus, CloneScope, ConfigSync, DEFAULT_STALLED_AFTER_DAYS, DEFAULT_TREE_DEPTH, file_tree, FileScanner, LifecyclePolicy, CurrentLocale, Locale, LOCALE_ENV, RecordingClone, RecordingPage, RecordingQuery, RecordingDetection, RecentRecordings, RecordingImport, RecordingsLocation, RecordingChange, RecordingsWatcher,
+    ScanOptions, ScanSnapshot, ScanSnapshotStore, SnoozeStore, StatusDetector, StatusTracker, SettingsStore, StepTimeouts, SymlinkPolicy, Trash, TrashEntry, UploadConfig
+};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Event emitted with a fresh ScanSnapshot after a background refresh
+pub const RECORDINGS_UPDATED_EVENT: &str = "recordings-updated";
+
+/// Event emitted with a RecordingChange when the watcher sees a recording change on disk
+pub const RECORDING_CHANGED_EVENT: &str = "recording-changed";
+
+/// Event emitted with a StatusChange when a recording moves to another status
+pub const STATUS_CHANGED_EVENT: &str = "status-changed";
 
 /// Configuration state for the app
 #[derive(Debug)]
 pub struct AppConfig {
+    /// Primary recordings root; new recordings are created here
     pub recordings_path: PathBuf,
+    /// All recordings roots, starting with `recordings_path`
+    pub recordings_roots: Vec<PathBuf>,
     pub cli_paths: CliPaths,
     pub main_audio_file: String,
+    pub custom_fields: Vec<CustomFieldDefinition>,
+    pub watch_debounce_ms: u64,
+    pub min_free_space_gb: u64,
+    pub step_timeouts: StepTimeouts,
+    pub locale: Locale,
+    pub lifecycle_policy: LifecyclePolicy,
+    pub symlink_policy: SymlinkPolicy,
+    /// Config profile applied over the environment, see ConfigProfiles
+    pub profile: Option<String>,
+    /// Browse a library another app processes without changing it, see commands::observer
+    pub observer: bool,
 }
 
 #[derive(Debug)]
 pub struct CliPaths {
     pub uv_path: String,
     pub workspace_root: PathBuf,
+    pub blender_path: String,
+    pub whisper_path: String,
 }
 
 impl Default for AppConfig {
@@ -33,48 +66,412 @@ impl Default for AppConfig {
                 std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string()) + "/Videos/obs-recordings"
             });
 
+        // Further roots on other drives, separated like PATH entries
+        let mut recordings_roots = vec![PathBuf::from(&recordings_path_str)];
+        if let Some(extra) = std::env::var_os("FERMATA_EXTRA_RECORDINGS_PATHS") {
+            for root in std::env::split_paths(&extra) {
+                if !root.as_os_str().is_empty() && !recordings_roots.contains(&root) {
+                    recordings_roots.push(root);
+                }
+            }
+        }
+
         let workspace_root_str = std::env::var("FERMATA_WORKSPACE_ROOT")
             .unwrap_or_else(|e| {
                 log::warn!("FERMATA_WORKSPACE_ROOT not found: {}", e);
                 std::env::current_dir().unwrap_or_default().to_string_lossy().to_string()
             });
 
+        // Blender used headless to repair paths inside .blend projects
+        let blender_path = std::env::var("FERMATA_BLENDER_PATH")
+            .unwrap_or_else(|_| "blender".to_string());
+
+        // Whisper CLI for the optional transcribe step
+        let whisper_path = std::env::var("FERMATA_WHISPER_PATH")
+            .unwrap_or_else(|_| "whisper".to_string());
+
         let main_audio_file = std::env::var("FERMATA_MAIN_AUDIO")
-            .unwrap_or_else(|_| "Przechwytywanie wejścia dźwięku (PulseAudio).m4a".to_string());
+            .unwrap_or_else(|_| default_main_audio().to_string());
+
+        // Custom recording fields, e.g. "client:text,setlist:list,paid:boolean"
+        let custom_fields = std::env::var("FERMATA_CUSTOM_FIELDS")
+            .map(|spec| {
+                CustomFieldDefinition::parse_list(&spec).unwrap_or_else(|e| {
+                    log::error!("Invalid FERMATA_CUSTOM_FIELDS: {}", e);
+                    Vec::new()
+                })
+            })
+            .unwrap_or_default();
+
+        // Quiet period before a burst of filesystem events is reported as one change
+        let watch_debounce_ms = std::env::var("FERMATA_WATCH_DEBOUNCE_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1500);
+
+        // Below this much free space new renders are paused
+        let min_free_space_gb = std::env::var("FERMATA_MIN_FREE_SPACE_GB")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(20);
+
+        // Hung steps are killed after their time limit or after going quiet for too long
+        let idle_timeout_min = std::env::var("FERMATA_IDLE_TIMEOUT_MIN")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(StepTimeouts::default().idle_minutes);
+        let step_timeouts = StepTimeouts::parse(
+            &std::env::var("FERMATA_STEP_TIMEOUTS").unwrap_or_default(),
+            idle_timeout_min
+        )
+        .unwrap_or_else(|e| {
+            log::error!("Invalid FERMATA_STEP_TIMEOUTS: {}", e);
+            StepTimeouts {
+                idle_minutes: idle_timeout_min,
+                ..StepTimeouts::default()
+            }
+        });
+
+        // Language of text generated in the backend, e.g. status summaries; load()
+        // falls back to the one picked in the app
+        let locale = std::env::var(LOCALE_ENV)
+            .ok()
+            .and_then(|v| Locale::parse(&v))
+            .unwrap_or(Locale::En);
+
+        // Thresholds of the time-based lifecycle badges
+        let default_policy = LifecyclePolicy::default();
+        let lifecycle_policy = LifecyclePolicy {
+            new_within_hours: std::env::var("FERMATA_NEW_WITHIN_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default_policy.new_within_hours),
+            archivable_after_days: std::env::var("FERMATA_ARCHIVABLE_AFTER_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default_policy.archivable_after_days),
+        };
+
+        // Whether linked recordings and files (e.g. render outputs on a NAS) are scanned
+        let symlink_policy = std::env::var("FERMATA_SYMLINKS")
+            .ok()
+            .and_then(|v| SymlinkPolicy::parse(&v))
+            .unwrap_or_default();
+
+        // Read-only mode for a library shared with the app that processes it, e.g. on a NAS
+        let observer = std::env::var("FERMATA_OBSERVER")
+            .is_ok_and(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"));
 
         log::info!("Final config - recordings_path: {}", recordings_path_str);
+        log::info!("Final config - recordings_roots: {:?}", recordings_roots);
         log::info!("Final config - workspace_root: {}", workspace_root_str);
+        log::info!("Final config - blender_path: {}", blender_path);
+        log::info!("Final config - whisper_path: {}", whisper_path);
         log::info!("Final config - main_audio_file: {}", main_audio_file);
+        log::info!("Final config - custom_fields: {:?}", custom_fields);
+        log::info!("Final config - watch_debounce_ms: {}", watch_debounce_ms);
+        log::info!("Final config - min_free_space_gb: {}", min_free_space_gb);
+        log::info!("Final config - step_timeouts: {:?}", step_timeouts);
+        log::info!("Final config - locale: {:?}", locale);
+        log::info!("Final config - lifecycle_policy: {:?}", lifecycle_policy);
+        log::info!("Final config - symlink_policy: {:?}", symlink_policy);
+        log::info!("Final config - observer: {}", observer);
 
         // Default configuration - can be overridden by user settings
         AppConfig {
             recordings_path: PathBuf::from(recordings_path_str),
+            recordings_roots,
             cli_paths: CliPaths {
                 uv_path: "uv".to_string(),
                 workspace_root: PathBuf::from(workspace_root_str),
+                blender_path,
+                whisper_path,
             },
             main_audio_file,
+            custom_fields,
+            watch_debounce_ms,
+            min_free_space_gb,
+            step_timeouts,
+            locale,
+            lifecycle_policy,
+            symlink_policy,
+            profile: None,
+            observer,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Config from the environment, with the recordings location chosen in the app
+    /// replacing the primary root and the active config profile applied over both.
+    /// Applies the symlink policy and detection rules to all scans.
+    pub fn load(settings: &SettingsStore) -> Self {
+        let mut config = Self::default();
+        config.symlink_policy.apply();
+        config.locale = Locale::resolve(settings);
+        match RecordingDetection::load(settings) {
+            Ok(detection) => detection.apply(),
+            Err(e) => log::warn!("{}", e),
+        }
+        match RecordingsLocation::load(settings) {
+            Ok(RecordingsLocation { recordings_path: Some(path) }) => {
+                log::info!("Recordings location from settings: {}", path.display());
+                config.set_primary_root(path);
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("{}", e),
+        }
+        match ConfigProfiles::load(settings) {
+            Ok(profiles) => {
+                if let Some(name) = profiles.active_name() {
+                    match profiles.find(&name) {
+                        Some(profile) => config.apply_profile(profile),
+                        None => log::warn!("Config profile '{}' not found, using the environment", name),
+                    }
+                }
+            }
+            Err(e) => log::warn!("{}", e),
+        }
+        config
+    }
+
+    fn set_primary_root(&mut self, path: PathBuf) {
+        let previous = std::mem::replace(&mut self.recordings_path, path.clone());
+        self.recordings_roots.retain(|root| *root != previous && *root != path);
+        self.recordings_roots.insert(0, path);
+    }
+
+    fn apply_profile(&mut self, profile: &ConfigProfile) {
+        log::info!("Config profile: {}", profile.name);
+        if let Some(path) = &profile.recordings_path {
+            self.set_primary_root(path.clone());
+        }
+        if let Some(root) = &profile.workspace_root {
+            self.cli_paths.workspace_root = root.clone();
+        }
+        if let Some(uv_path) = &profile.uv_path {
+            self.cli_paths.uv_path = uv_path.clone();
         }
+        self.profile = Some(profile.name.clone());
+    }
+
+    /// Root holding the named recording; the primary root when no root has it yet
+    pub fn recording_root(&self, recording_name: &str) -> &Path {
+        FileScanner::find_root(&self.recordings_roots, recording_name).unwrap_or(&self.recordings_path)
+    }
+
+    /// Directory of the named recording in whichever root holds it
+    pub fn recording_path(&self, recording_name: &str) -> PathBuf {
+        self.recording_root(recording_name).join(recording_name)
+    }
+
+    /// Fill in lifecycle badges as of now
+    pub fn annotate_lifecycle(&self, recordings: &mut [Recording]) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.lifecycle_policy.annotate(recordings, now);
     }
 }
 
-/// Get all recordings from the configured directory
+/// One-sentence status of a recording in the configured language, for screen
+/// readers and notifications
 #[tauri::command]
-pub fn get_recordings(config: State<AppConfig>) -> Result<Vec<Recording>, String> {
-    log::info!("Scanning recordings from: {}", config.recordings_path.display());
+pub fn get_status_summary_text(name: String, config: State<AppConfig>, locale: State<CurrentLocale>) -> Result<String, String> {
+    let recording_path = config.recording_path(&name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", name));
+    }
 
-    let recordings = FileScanner::scan_recordings(&config.recordings_path);
+    let mut recording = Recording::from_path(recording_path)
+        .map_err(|e| format!("Failed to load recording '{}': {}", name, e))?;
+    crate::services::update_recording_status(&mut recording);
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let upload_target = UploadConfig::recording_profile(&recording.path);
+    Ok(status_summary(&recording, locale.get(), now, upload_target.as_deref()))
+}
+
+/// Get all recordings from the configured directory. Without `include_sizes` only
+/// statuses are read; sizes come from the last snapshot for recordings unchanged
+/// since, and get_recording_sizes fetches the rest on demand.
+#[tauri::command]
+pub fn get_recordings(
+    options: Option<ScanOptions>,
+    config: State<AppConfig>,
+    snapshots: State<ScanSnapshotStore>
+) -> Result<Vec<Recording>, String> {
+    log::info!("Scanning recordings from: {:?}", config.recordings_roots);
+
+    let options = options.unwrap_or_default();
+    let mut recordings = FileScanner::scan_roots_with(&config.recordings_roots, &options);
+    config.annotate_lifecycle(&mut recordings);
+    if !options.include_sizes {
+        if let Some(snapshot) = snapshots.load(&config.recordings_path) {
+            snapshot.fill_sizes(&mut recordings);
+        }
+    }
 
     log::info!("Found {} recordings", recordings.len());
+    if let Err(e) = snapshots.save(&ScanSnapshot::new(&config.recordings_path, recordings.clone())) {
+        log::warn!("{}", e);
+    }
     Ok(recordings)
 }
 
+/// Filter, sort and page recordings. Served from the last scan snapshot unless
+/// `refresh` is set or no snapshot exists yet, in which case the roots are rescanned.
+#[tauri::command]
+pub fn query_recordings(
+    filter: RecordingQuery,
+    refresh: Option<bool>,
+    config: State<AppConfig>,
+    snapshots: State<ScanSnapshotStore>
+) -> Result<RecordingPage, String> {
+    let cached = if refresh.unwrap_or(false) { None } else { snapshots.load(&config.recordings_path) };
+    let recordings = match cached {
+        Some(snapshot) => snapshot.recordings,
+        None => {
+            let mut recordings = FileScanner::scan_roots(&config.recordings_roots);
+            config.annotate_lifecycle(&mut recordings);
+            if let Err(e) = snapshots.save(&ScanSnapshot::new(&config.recordings_path, recordings.clone())) {
+                log::warn!("{}", e);
+            }
+            recordings
+        }
+    };
+
+    let page = FileScanner::query(&recordings, &filter);
+    log::info!("Query matched {} recordings, returning page {} ({} items)", page.total, page.page, page.items.len());
+    Ok(page)
+}
+
+/// Get the last persisted scan result (marked stale) without touching the filesystem
+#[tauri::command]
+pub fn get_cached_recordings(
+    config: State<AppConfig>,
+    snapshots: State<ScanSnapshotStore>
+) -> Result<Option<ScanSnapshot>, String> {
+    Ok(snapshots.load(&config.recordings_path))
+}
+
+/// Start a background rescan; the result arrives as a `recordings-updated` event
+#[tauri::command]
+pub fn refresh_recordings(app: AppHandle) -> Result<(), String> {
+    tauri::async_runtime::spawn(refresh_recordings_in_background(app));
+    Ok(())
+}
+
+/// Rescan the recordings directory off the main thread, persist and emit the result
+pub async fn refresh_recordings_in_background(app: AppHandle) {
+    let (recordings_path, roots) = {
+        let config = app.state::<AppConfig>();
+        (config.recordings_path.clone(), config.recordings_roots.clone())
+    };
+    log::info!("🔄 Background refresh of recordings from: {:?}", roots);
+
+    let mut recordings = match tauri::async_runtime::spawn_blocking(move || FileScanner::scan_roots(&roots)).await {
+        Ok(recordings) => recordings,
+        Err(e) => {
+            log::error!("Background scan failed: {}", e);
+            return;
+        }
+    };
+
+    app.state::<AppConfig>().annotate_lifecycle(&mut recordings);
+    app.state::<StatusTracker>().seed(&recordings);
+    let snapshot = ScanSnapshot::new(&recordings_path, recordings);
+    if let Err(e) = app.state::<ScanSnapshotStore>().save(&snapshot) {
+        log::warn!("{}", e);
+    }
+
+    if let Err(e) = app.emit(RECORDINGS_UPDATED_EVENT, snapshot) {
+        log::error!("Failed to emit {}: {}", RECORDINGS_UPDATED_EVENT, e);
+    }
+}
+
+/// Keeps the filesystem watchers alive for the lifetime of the app
+pub struct WatcherState {
+    watchers: Mutex<Vec<RecordingsWatcher>>,
+}
+
+impl WatcherState {
+    pub fn new(watchers: Vec<RecordingsWatcher>) -> Self {
+        Self { watchers: Mutex::new(watchers) }
+    }
+
+    /// Whether changes under `root` arrive from a filesystem watcher
+    pub fn is_watched(&self, root: &Path) -> bool {
+        self.watchers.lock().unwrap().iter().any(|watcher| watcher.root() == root)
+    }
+}
+
+/// Start watching every recordings root and forward coalesced changes to the frontend
+pub fn start_recordings_watchers(app: &AppHandle) -> Vec<RecordingsWatcher> {
+    let roots = app.state::<AppConfig>().recordings_roots.clone();
+    roots
+        .into_iter()
+        .filter_map(|root| start_recordings_watcher(app, root))
+        .collect()
+}
+
+/// Watch one recordings root
+fn start_recordings_watcher(app: &AppHandle, root: PathBuf) -> Option<RecordingsWatcher> {
+    let config = app.state::<AppConfig>();
+    let emitter = app.clone();
+
+    match RecordingsWatcher::start(
+        root.clone(),
+        Duration::from_millis(config.watch_debounce_ms),
+        move |change| forward_recording_change(&emitter, change),
+    ) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            log::warn!("Recordings watcher disabled for {}: {}", root.display(), e);
+            None
+        }
+    }
+}
+
+/// Pass a change found by a watcher or by polling on to the frontend as a
+/// `recording-changed` event, and `status-changed` when the status moved
+pub fn forward_recording_change(app: &AppHandle, change: RecordingChange) {
+    track_new_recording(app, &change);
+    if let Some(recording) = &change.recording {
+        if let Err(e) = ConfigSync::mark_if_drifted(&recording.path) {
+            log::warn!("{}", e);
+        }
+    }
+    emit_status_change(app, &change.name, change.recording.as_ref().map(|r| r.status.clone()));
+    if let Err(e) = app.emit(RECORDING_CHANGED_EVENT, change) {
+        log::error!("Failed to emit {}: {}", RECORDING_CHANGED_EVENT, e);
+    }
+}
+
+/// Emit `status-changed` when `status` differs from the last status seen of the
+/// recording; None means the recording is gone
+pub fn emit_status_change(app: &AppHandle, recording_name: &str, status: Option<RecordingStatus>) {
+    if let Some(change) = app.state::<StatusTracker>().observe(recording_name, status) {
+        if let Err(e) = app.emit(STATUS_CHANGED_EVENT, change) {
+            log::error!("Failed to emit {}: {}", STATUS_CHANGED_EVENT, e);
+        }
+    }
+}
+
 /// Get details for a specific recording by name
 #[tauri::command]
-pub fn get_recording_details(name: String, config: State<AppConfig>) -> Result<Recording, String> {
+pub fn get_recording_details(name: String, config: State<AppConfig>, settings: State<SettingsStore>) -> Result<Recording, String> {
+    recording_details_impl(&name, &config, &settings)
+}
+
+pub fn recording_details_impl(name: &str, config: &AppConfig, settings: &SettingsStore) -> Result<Recording, String> {
     log::info!("Getting details for recording: {}", name);
 
-    let recording_path = config.recordings_path.join(&name);
+    let recording_path = config.recording_path(name);
     log::info!("Looking for recording at path: {}", recording_path.display());
 
     if !recording_path.exists() {
@@ -87,8 +484,17 @@ pub fn get_recording_details(name: String, config: State<AppConfig>) -> Result<R
 
     log::info!("Created recording with {} file_sizes entries", recording.file_sizes.len());
 
+    let media = crate::services::MediaProbe::for_recording(&recording.path, &recording.name);
+    if media.main.is_none() {
+        // Remembered for the status below and later scans
+        crate::services::MediaProbe::check_main_video(&recording.path, &recording.name);
+    }
+    recording.media = Some(media);
+
     // Update with current status
     crate::services::update_recording_status(&mut recording);
+    config.annotate_lifecycle(std::slice::from_mut(&mut recording));
+    RecentRecordings::record_use(settings, name);
 
     log::info!("After status update: {} file_sizes entries", recording.file_sizes.len());
     for (path, size) in &recording.file_sizes {
@@ -98,36 +504,155 @@ pub fn get_recording_details(name: String, config: State<AppConfig>) -> Result<R
     Ok(recording)
 }
 
+/// Files of a recording as a tree with per-directory sizes, for the file browser
+#[tauri::command]
+pub fn get_recording_tree(recording_name: String, max_depth: Option<usize>, config: State<AppConfig>) -> Result<FileTreeNode, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    Ok(file_tree(&recording_path, max_depth.unwrap_or(DEFAULT_TREE_DEPTH)))
+}
+
+/// File sizes of one recording, for lists scanned without them
+#[tauri::command]
+pub fn get_recording_sizes(recording_name: String, config: State<AppConfig>) -> Result<HashMap<String, u64>, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    Ok(StatusDetector::get_file_info(&recording_path))
+}
+
 /// Get recordings filtered by status
 #[tauri::command]
 pub fn get_recordings_by_status(status_filter: String, config: State<AppConfig>) -> Result<Vec<Recording>, String> {
     log::info!("Getting recordings filtered by status: {}", status_filter);
 
-    let all_recordings = FileScanner::scan_recordings(&config.recordings_path);
+    let mut all_recordings = FileScanner::scan_roots(&config.recordings_roots);
+    config.annotate_lifecycle(&mut all_recordings);
     let filtered = FileScanner::filter_by_status(&all_recordings, &status_filter);
 
     Ok(filtered)
 }
 
-/// Get recordings that need attention (failed or incomplete)
+/// Recordings bucketed by the day they were recorded, for the calendar view
 #[tauri::command]
-pub fn get_recordings_needing_attention(config: State<AppConfig>) -> Result<Vec<Recording>, String> {
+pub async fn get_recordings_calendar(range: Option<CalendarRange>, config: State<'_, AppConfig>) -> Result<Vec<CalendarDay>, String> {
+    let roots = config.recordings_roots.clone();
+    let recordings = tauri::async_runtime::spawn_blocking(move || FileScanner::scan_roots_with(&roots, &ScanOptions::fast()))
+        .await
+        .map_err(|e| format!("Calendar scan failed: {}", e))?;
+    recordings_calendar(&recordings, &range.unwrap_or_default())
+}
+
+/// Recordings that need a person: failed, stalled for `stalled_after_days`
+/// (7 by default) or missing the input of a step that already ran. Snoozed and
+/// ignored recordings are left out.
+#[tauri::command]
+pub fn get_attention_items(stalled_after_days: Option<u64>, config: State<AppConfig>) -> Result<Vec<AttentionItem>, String> {
     log::info!("Getting recordings that need attention");
 
-    let all_recordings = FileScanner::scan_recordings(&config.recordings_path);
-    let needing_attention = FileScanner::get_recordings_needing_attention(&all_recordings);
+    let recordings = FileScanner::scan_roots(&config.recordings_roots);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    Ok(attention_items(&recordings, stalled_after_days.unwrap_or(DEFAULT_STALLED_AFTER_DAYS), now))
+}
+
+/// A recording kept off the attention list, from list_snoozed_recordings
+#[derive(Debug, serde::Serialize)]
+pub struct SnoozedRecording {
+    pub recording: String,
+    pub snooze: RecordingSnooze,
+}
+
+/// Keep a recording off the attention list and out of automatic processing until
+/// `until`, a Unix timestamp in seconds
+#[tauri::command]
+pub fn snooze_recording(recording_name: String, until: u64, config: State<AppConfig>) -> Result<RecordingSnooze, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    log::info!("😴 Snoozing '{}' until {}", recording_name, until);
+    SnoozeStore::set(&recording_path, Some(until))
+}
+
+/// Keep a recording off the attention list and out of automatic processing until unsnoozed
+#[tauri::command]
+pub fn ignore_recording(recording_name: String, config: State<AppConfig>) -> Result<RecordingSnooze, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    log::info!("🙈 Ignoring '{}'", recording_name);
+    SnoozeStore::set(&recording_path, None)
+}
 
-    Ok(needing_attention)
+/// Clear a snooze or ignore flag
+#[tauri::command]
+pub fn unsnooze_recording(recording_name: String, config: State<AppConfig>) -> Result<(), String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    log::info!("⏰ Unsnoozing '{}'", recording_name);
+    SnoozeStore::clear(&recording_path)
 }
 
-/// Delete a recording by removing its entire directory
+/// Recordings whose snooze or ignore flag is in effect
 #[tauri::command]
-pub fn delete_recording(recording_name: String, config: State<AppConfig>) -> Result<(), String> {
-    delete_recording_impl(&recording_name, &config.recordings_path)
+pub fn list_snoozed_recordings(config: State<AppConfig>) -> Result<Vec<SnoozedRecording>, String> {
+    Ok(FileScanner::scan_roots(&config.recordings_roots)
+        .into_iter()
+        .filter_map(|recording| {
+            SnoozeStore::active(&recording.path).map(|snooze| SnoozedRecording { recording: recording.name, snooze })
+        })
+        .collect())
+}
+
+/// Id of the queued job currently running on a recording
+fn running_job(queue: &JobQueue, recording_name: &str) -> Option<u64> {
+    queue
+        .list()
+        .into_iter()
+        .find(|job| job.recording_name == recording_name && job.status == JobStatus::Running)
+        .map(|job| job.id)
+}
+
+/// Size up the deletion of a recording; nothing is removed until delete_recording
+/// confirms the returned token
+#[tauri::command]
+pub fn preview_delete(
+    recording_name: String,
+    config: State<AppConfig>,
+    staging: State<BulkDeleteStaging>,
+    queue: State<JobQueue>
+) -> Result<DeletePreview, String> {
+    let running_job = running_job(&queue, &recording_name);
+    staging.preview_recording(config.recording_root(&recording_name), &recording_name, running_job)
+}
+
+/// Delete a recording previewed with preview_delete by moving its directory to the trash
+#[tauri::command]
+pub fn delete_recording(
+    recording_name: String,
+    token: String,
+    config: State<AppConfig>,
+    staging: State<BulkDeleteStaging>,
+    queue: State<JobQueue>
+) -> Result<(), String> {
+    if let Some(job) = running_job(&queue, &recording_name) {
+        return Err(format!("Recording '{}' is being processed by job {}; cancel or wait for it first", recording_name, job));
+    }
+    let previewed_bytes = staging.confirm_recording(&token, &recording_name)?;
+    delete_recording_impl(&recording_name, config.recording_root(&recording_name), previewed_bytes)
 }
 
 /// Internal implementation for testing
-fn delete_recording_impl(recording_name: &str, recordings_path: &std::path::Path) -> Result<(), String> {
+fn delete_recording_impl(recording_name: &str, recordings_path: &std::path::Path, previewed_bytes: u64) -> Result<(), String> {
     log::info!("Attempting to delete recording: {}", recording_name);
 
     let recording_path = recordings_path.join(recording_name);
@@ -144,8 +669,15 @@ fn delete_recording_impl(recording_name: &str, recordings_path: &std::path::Path
         return Err(error_msg);
     }
 
-    // Remove the entire recording directory
-    std::fs::remove_dir_all(&recording_path)
+    if let Some(status) = detect_in_progress_status(&recording_path) {
+        return Err(format!("Recording '{}' is busy ({:?})", recording_name, status));
+    }
+    if directory_size(&recording_path) > previewed_bytes {
+        return Err(format!("Recording '{}' changed since the preview; preview the deletion again", recording_name));
+    }
+
+    // Soft delete - the recording can be restored until the trash is emptied
+    Trash::move_to_trash(recordings_path, recording_name)
         .map_err(|e| {
             let error_msg = format!("Failed to delete recording '{}': {}", recording_name, e);
             log::error!("{}", error_msg);
@@ -156,6 +688,165 @@ fn delete_recording_impl(recording_name: &str, recordings_path: &std::path::Path
     Ok(())
 }
 
+/// Move a recording with unreadable files out of the recordings root into its
+/// .quarantine directory, where scans and the pipeline no longer see it
+#[tauri::command]
+pub fn quarantine_recording(recording_name: String, config: State<AppConfig>, queue: State<JobQueue>) -> Result<QuarantineEntry, String> {
+    if let Some(job) = running_job(&queue, &recording_name) {
+        return Err(format!("Recording '{}' is being processed by job {}; cancel or wait for it first", recording_name, job));
+    }
+    let root = FileScanner::find_root(&config.recordings_roots, &recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    let recording_path = root.join(&recording_name);
+    if let Some(status) = detect_in_progress_status(&recording_path) {
+        return Err(format!("Recording '{}' is busy ({:?})", recording_name, status));
+    }
+    quarantine(root, &recording_name, StatusDetector::check_for_corruption(&recording_path))
+}
+
+/// Size up a deletion of several recordings; nothing is removed until
+/// execute_bulk_delete confirms the returned token
+#[tauri::command]
+pub fn preview_bulk_delete(
+    names: Vec<String>,
+    config: State<AppConfig>,
+    staging: State<BulkDeleteStaging>
+) -> Result<BulkDeletePreview, String> {
+    log::info!("Previewing deletion of {} recordings", names.len());
+    staging.preview(&config.recordings_roots, &names)
+}
+
+/// Move the recordings of a previewed bulk delete to the trash
+#[tauri::command]
+pub fn execute_bulk_delete(
+    token: String,
+    config: State<AppConfig>,
+    staging: State<BulkDeleteStaging>
+) -> Result<BulkDeleteResult, String> {
+    let result = staging.execute(&config.recordings_roots, &token)?;
+    log::info!(
+        "🗑️ Bulk deleted {} recordings ({} MB), {} skipped",
+        result.deleted.len(),
+        result.freed_bytes / (1024 * 1024),
+        result.errors.len()
+    );
+    Ok(result)
+}
+
+/// Register a video recorded outside OBS as a new recording, ready for the extract step.
+/// The name is sanitized and gets a "-2" style suffix when it is taken; the returned
+/// recording carries the final name.
+#[tauri::command]
+pub async fn import_recording(
+    source_path: String,
+    new_name: String,
+    config: State<'_, AppConfig>
+) -> Result<Recording, String> {
+    let new_name = unique_recording_name(&sanitize_recording_name(&new_name), |name| {
+        FileScanner::find_root(&config.recordings_roots, name).is_some()
+    });
+    log::info!("📥 Importing {} as recording '{}'", source_path, new_name);
+    let recordings_path = config.recordings_path.clone();
+
+    // Copying across filesystems can take a while for a long video
+    let recording_path = tauri::async_runtime::spawn_blocking(move || {
+        let source = PathBuf::from(&source_path);
+        let geometry = probe_video_geometry(&source).unwrap_or_else(|| {
+            log::warn!("Could not probe {}, assuming a 1920x1080 canvas", source.display());
+            Default::default()
+        });
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        RecordingImport::import(&recordings_path, &source, &new_name, geometry, timestamp)
+    })
+    .await
+    .map_err(|e| format!("Import task failed: {}", e))??;
+
+    let mut recording = Recording::from_path(recording_path)
+        .map_err(|e| format!("Failed to load imported recording: {}", e))?;
+    crate::services::update_recording_status(&mut recording);
+    config.annotate_lifecycle(std::slice::from_mut(&mut recording));
+    log::info!("✅ Imported recording '{}'", recording.name);
+    Ok(recording)
+}
+
+/// Copy a recording up to `scope` under a new name, e.g. to compare two presets on the same material
+#[tauri::command]
+pub async fn clone_recording(
+    name: String,
+    new_name: String,
+    scope: CloneScope,
+    config: State<'_, AppConfig>
+) -> Result<CloneReport, String> {
+    log::info!("📑 Cloning recording '{}' as '{}' ({:?})", name, new_name, scope);
+    ensure_name_available(&config, &new_name)?;
+    // The clone stays next to its source, on the same drive
+    let recordings_path = config.recording_root(&name).to_path_buf();
+
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        RecordingClone::clone(&recordings_path, &name, &new_name, scope)
+    })
+    .await
+    .map_err(|e| format!("Clone task failed: {}", e))??;
+
+    log::info!("✅ Cloned {} files ({} bytes) into '{}'", report.files, report.copied_bytes, report.name);
+    Ok(report)
+}
+
+/// Descriptive, unused name for a recording from its metadata: recording date and OBS
+/// scene, e.g. "2025-06-14 Main Stage", suffixed with "-2" and so on when taken
+#[tauri::command]
+pub fn suggest_name(recording_name: String, config: State<AppConfig>) -> Result<String, String> {
+    let recording_path = config.recording_path(&recording_name);
+    let recording = Recording::from_path(recording_path.clone())
+        .map_err(|e| format!("Recording '{}' not found: {}", recording_name, e))?;
+    let base = suggest_recording_name(&recording_path, recording.last_updated);
+    Ok(unique_recording_name(&base, |name| {
+        name != recording_name && FileScanner::find_root(&config.recordings_roots, name).is_some()
+    }))
+}
+
+/// Recording names must be unique across all roots
+fn ensure_name_available(config: &AppConfig, name: &str) -> Result<(), String> {
+    match FileScanner::find_root(&config.recordings_roots, name) {
+        Some(root) => Err(format!("Recording with name '{}' already exists in {}", name, root.display())),
+        None => Ok(()),
+    }
+}
+
+/// List recordings currently in the trash of any root
+#[tauri::command]
+pub fn list_trash(config: State<AppConfig>) -> Result<Vec<TrashEntry>, String> {
+    let mut entries: Vec<TrashEntry> = config.recordings_roots.iter().flat_map(|root| Trash::list(root)).collect();
+    entries.sort_by_key(|entry| Reverse(entry.deleted_at));
+    Ok(entries)
+}
+
+/// Restore a trashed recording under its original name, in the root it was deleted from
+#[tauri::command]
+pub fn restore_recording(trash_id: String, config: State<AppConfig>) -> Result<String, String> {
+    let root = config
+        .recordings_roots
+        .iter()
+        .find(|root| Trash::list(root).iter().any(|e| e.id == trash_id))
+        .ok_or_else(|| format!("Trash entry '{}' not found", trash_id))?;
+    let name = trash_id.split_once('_').map(|(_, name)| name).unwrap_or(&trash_id);
+    ensure_name_available(&config, name)?;
+    Trash::restore(root, &trash_id)
+}
+
+/// Permanently remove trashed recordings (all, or only those older than the given days)
+#[tauri::command]
+pub fn empty_trash(older_than_days: Option<u64>, config: State<AppConfig>) -> Result<Vec<TrashEntry>, String> {
+    let mut removed = Vec::new();
+    for root in &config.recordings_roots {
+        removed.extend(Trash::empty(root, older_than_days)?);
+    }
+    Ok(removed)
+}
+
 /// Update the recordings path configuration
 #[tauri::command]
 pub fn update_recordings_path(new_path: String, _config: State<AppConfig>) -> Result<String, String> {
@@ -180,11 +871,23 @@ pub fn update_recordings_path(new_path: String, _config: State<AppConfig>) -> Re
 pub fn get_app_config(config: State<AppConfig>) -> Result<AppConfigDto, String> {
     Ok(AppConfigDto {
         recordings_path: config.recordings_path.to_string_lossy().to_string(),
+        recordings_roots: config.recordings_roots.iter().map(|r| r.to_string_lossy().to_string()).collect(),
         cli_paths: CliPathsDto {
             uv_path: config.cli_paths.uv_path.clone(),
             workspace_root: config.cli_paths.workspace_root.to_string_lossy().to_string(),
+            blender_path: config.cli_paths.blender_path.clone(),
+            whisper_path: config.cli_paths.whisper_path.clone(),
         },
         main_audio_file: config.main_audio_file.clone(),
+        custom_fields: config.custom_fields.clone(),
+        watch_debounce_ms: config.watch_debounce_ms,
+        min_free_space_gb: config.min_free_space_gb,
+        step_timeouts: config.step_timeouts.clone(),
+        locale: config.locale,
+        lifecycle_policy: config.lifecycle_policy.clone(),
+        symlink_policy: config.symlink_policy,
+        profile: config.profile.clone(),
+        observer: config.observer,
     })
 }
 
@@ -192,14 +895,28 @@ pub fn get_app_config(config: State<AppConfig>) -> Result<AppConfigDto, String>
 #[derive(serde::Serialize)]
 pub struct AppConfigDto {
     pub recordings_path: String,
+    pub recordings_roots: Vec<String>,
     pub cli_paths: CliPathsDto,
     pub main_audio_file: String,
+    pub custom_fields: Vec<CustomFieldDefinition>,
+    pub watch_debounce_ms: u64,
+    pub min_free_space_gb: u64,
+    pub step_timeouts: StepTimeouts,
+    pub locale: Locale,
+    pub lifecycle_policy: LifecyclePolicy,
+    pub symlink_policy: SymlinkPolicy,
+    /// Name of the config profile in use, None when running on the environment alone
+    pub profile: Option<String>,
+    /// Read-only observer of a shared library; changing commands are refused
+    pub observer: bool,
 }
 
 #[derive(serde::Serialize)]
 pub struct CliPathsDto {
     pub uv_path: String,
     pub workspace_root: String,
+    pub blender_path: String,
+    pub whisper_path: String,
 }
 
 // All old problematic tests removed
@@ -221,10 +938,12 @@ mod delete_tests {
         std::fs::write(recording_dir.join("test_file.txt"), "test content").unwrap();
 
         // Test our delete function
-        let result = delete_recording_impl("test_recording", &temp_dir);
+        assert!(delete_recording_impl("test_recording", &temp_dir, 0).unwrap_err().contains("changed since the preview"));
+        let result = delete_recording_impl("test_recording", &temp_dir, 12);
 
         assert!(result.is_ok());
         assert!(!recording_dir.exists());
+        assert_eq!(Trash::list(&temp_dir)[0].name, "test_recording");
 
         // Cleanup
         let _ = std::fs::remove_dir_all(&temp_dir);
@@ -236,7 +955,7 @@ mod delete_tests {
         std::fs::create_dir_all(&temp_dir).unwrap();
 
         // Test error case - try to delete nonexistent recording
-        let result = delete_recording_impl("nonexistent_recording", &temp_dir);
+        let result = delete_recording_impl("nonexistent_recording", &temp_dir, 0);
 
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("not found"));
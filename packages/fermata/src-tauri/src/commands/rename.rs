@@ -1,13 +1,418 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::fs;
+use serde::Serialize;
 use tauri::State;
 use crate::commands::recordings::AppConfig;
+use crate::services::{
+    detect_in_progress_status, expand_name_pattern, files_with_path_references, FileScanner, find_blend_files, rewrite_json_strings,
+    rewrite_text, validate_recording_name, NameFields, RenameJournal, RenameJournalEntry, write_atomic, PathRepair, ProcessEnvironment, ProcessRunner, RecentRecordings, Sessions, SettingsStore, BLENDER_REMAP_MARKER,
+};
+
+/// A file whose embedded path references were (or would be) rewritten
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PathRewrite {
+    pub file: String,
+    pub occurrences: usize,
+}
+
+/// What a rename did, or would do in dry-run mode
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RenameReport {
+    pub old_name: String,
+    pub new_name: String,
+    pub dry_run: bool,
+    pub rewrites: Vec<PathRewrite>,
+    /// Files that embed paths but cannot be rewritten as text (e.g. .blend)
+    pub needs_manual_repair: Vec<String>,
+    pub warnings: Vec<String>,
+}
 
 /// Tauri command to rename a recording
 #[tauri::command]
-pub fn rename_recording(old_name: String, new_name: String, config: State<AppConfig>) -> Result<(), String> {
-    log::info!("Renaming recording '{}' to '{}'", old_name, new_name);
-    rename_recording_impl(&old_name, &new_name, &config.recordings_path)
+pub fn rename_recording(
+    old_name: String,
+    new_name: String,
+    dry_run: Option<bool>,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<RenameReport, String> {
+    log::info!("Renaming recording '{}' to '{}' (dry_run: {:?})", old_name, new_name, dry_run);
+    // Renaming happens inside the recording's root; the new name must be free in all roots
+    if let Some(root) = FileScanner::find_root(&config.recordings_roots, &new_name) {
+        if old_name != new_name {
+            return Err(format!("Recording with name '{}' already exists in {}", new_name, root.display()));
+        }
+    }
+    let recordings_path = config.recording_root(&old_name);
+    if dry_run.unwrap_or(false) {
+        return preview_rename_impl(&old_name, &new_name, recordings_path);
+    }
+
+    let report = rename_recording_impl(&old_name, &new_name, recordings_path)?;
+    record_renames(&settings, std::slice::from_ref(&report))?;
+    Ok(report)
+}
+
+/// Renames recorded for undo, oldest first
+#[tauri::command]
+pub fn get_rename_history(settings: State<SettingsStore>) -> Result<Vec<RenameJournalEntry>, String> {
+    Ok(RenameJournal::load(&settings)?.list().to_vec())
+}
+
+/// Tauri command to back out the latest rename that gave a recording its current
+/// name: the directory, the main file and rewritten path references go back
+#[tauri::command]
+pub fn undo_last_rename(
+    recording_name: String,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<RenameReport, String> {
+    undo_last_rename_impl(&recording_name, &config.recordings_roots, &settings)
+}
+
+fn undo_last_rename_impl(recording_name: &str, roots: &[PathBuf], settings: &SettingsStore) -> Result<RenameReport, String> {
+    let mut journal = RenameJournal::load(settings)?;
+    let entry = journal
+        .last_for(recording_name)
+        .cloned()
+        .ok_or_else(|| format!("No rename of '{}' to undo", recording_name))?;
+    log::info!("Undoing rename of '{}' to '{}'", entry.old_name, recording_name);
+    if let Some(root) = FileScanner::find_root(roots, &entry.old_name) {
+        return Err(format!("Cannot undo: recording with name '{}' already exists in {}", entry.old_name, root.display()));
+    }
+    let root = FileScanner::find_root(roots, recording_name)
+        .ok_or_else(|| format!("Recording '{}' not found", recording_name))?;
+    if let Some(status) = detect_in_progress_status(&root.join(recording_name)) {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, status));
+    }
+
+    let report = reverse_rename(&entry, root)?;
+    journal.remove_last_for(recording_name);
+    journal.save(settings)?;
+    let mut sessions = Sessions::load(settings)?;
+    if sessions.rename_recording(recording_name, &entry.old_name) {
+        sessions.save(settings)?;
+    }
+    Ok(report)
+}
+
+/// Replay a journaled rename backwards: the files it rewrote, then the main file, then
+/// the directory. Files the rename didn't touch keep their contents.
+fn reverse_rename(entry: &RenameJournalEntry, recordings_path: &Path) -> Result<RenameReport, String> {
+    let (old_name, new_name) = (entry.old_name.as_str(), entry.new_name.as_str());
+    validate_rename(new_name, old_name, recordings_path)?;
+    let old_dir = recordings_path.join(old_name);
+    let new_dir = recordings_path.join(new_name);
+
+    let old_dir_str = old_dir.to_string_lossy().to_string();
+    let new_dir_str = new_dir.to_string_lossy().to_string();
+    let replacements = [
+        (new_dir_str.as_str(), old_dir_str.as_str()),
+        (&*format!("{}.mkv", new_name), &*format!("{}.mkv", old_name)),
+    ];
+    let planned = plan_rewrites_in(&new_dir, &replacements)
+        .into_iter()
+        .filter(|plan| entry.files_touched.contains(&plan.rewrite.file))
+        .collect();
+    let (rewrites, warnings) = write_planned_rewrites(&new_dir, planned);
+    for warning in &warnings {
+        log::warn!("{}", warning);
+    }
+
+    rename_main_recording_file(&new_dir, &new_dir, new_name, old_name)?;
+    fs::rename(&new_dir, &old_dir).map_err(|e| format!("Failed to rename recording directory: {}", e))?;
+
+    log::info!("Undid rename of '{}' to '{}'", old_name, new_name);
+    Ok(RenameReport {
+        old_name: new_name.to_string(),
+        new_name: old_name.to_string(),
+        dry_run: false,
+        rewrites,
+        needs_manual_repair: find_blend_files(&old_dir),
+        warnings,
+    })
+}
+
+/// Keep renamed recordings in their sessions and journal the renames for undo
+fn record_renames(settings: &SettingsStore, reports: &[RenameReport]) -> Result<(), String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut sessions = Sessions::load(settings)?;
+    let mut sessions_changed = false;
+    let mut journal = RenameJournal::load(settings)?;
+    let mut recent = RecentRecordings::load(settings)?;
+    let mut recent_changed = false;
+    for report in reports {
+        sessions_changed |= sessions.rename_recording(&report.old_name, &report.new_name);
+        recent_changed |= recent.rename_recording(&report.old_name, &report.new_name);
+        journal.record(RenameJournalEntry {
+            old_name: report.old_name.clone(),
+            new_name: report.new_name.clone(),
+            renamed_at: now,
+            files_touched: report.rewrites.iter().map(|r| r.file.clone()).collect(),
+        });
+    }
+    if sessions_changed {
+        sessions.save(settings)?;
+    }
+    if recent_changed {
+        recent.save(settings)?;
+    }
+    journal.save(settings)
+}
+
+/// One recording of a bulk rename and the name the pattern gives it
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BulkRenameEntry {
+    pub old_name: String,
+    pub new_name: String,
+    /// Why the recording can't take its new name; any error blocks the whole batch
+    pub error: Option<String>,
+}
+
+/// Preview of a bulk rename, or what it did
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BulkRenameReport {
+    pub dry_run: bool,
+    pub entries: Vec<BulkRenameEntry>,
+    /// Renames done; empty in dry-run mode and for unchanged names
+    pub renamed: Vec<RenameReport>,
+}
+
+/// Tauri command to rename many recordings after a pattern with {date}, {scene},
+/// {index} and {original} tokens. Either every recording is renamed or, when one
+/// fails, those already renamed are renamed back.
+#[tauri::command]
+pub fn bulk_rename(
+    pattern: String,
+    recordings: Vec<String>,
+    dry_run: Option<bool>,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<BulkRenameReport, String> {
+    log::info!("Renaming {} recordings after '{}' (dry_run: {:?})", recordings.len(), pattern, dry_run);
+    let entries = plan_bulk_rename(&pattern, &recordings, &config.recordings_roots)?;
+    let dry_run = dry_run.unwrap_or(false);
+    if dry_run {
+        return Ok(BulkRenameReport { dry_run, entries, renamed: Vec::new() });
+    }
+    if let Some(entry) = entries.iter().find(|e| e.error.is_some()) {
+        return Err(format!("Cannot rename '{}': {}", entry.old_name, entry.error.as_deref().unwrap_or_default()));
+    }
+
+    let renamed = apply_bulk_rename(&entries, &config.recordings_roots)?;
+    record_renames(&settings, &renamed)?;
+    Ok(BulkRenameReport { dry_run, entries, renamed })
+}
+
+/// New name of every recording, in the order given; only a bad pattern fails the whole plan
+pub fn plan_bulk_rename(pattern: &str, recordings: &[String], roots: &[PathBuf]) -> Result<Vec<BulkRenameEntry>, String> {
+    let width = recordings.len().to_string().len();
+    let mut entries: Vec<BulkRenameEntry> = Vec::new();
+    for (i, old_name) in recordings.iter().enumerate() {
+        let Some(root) = FileScanner::find_root(roots, old_name) else {
+            entries.push(BulkRenameEntry {
+                old_name: old_name.clone(),
+                new_name: old_name.clone(),
+                error: Some(format!("Recording '{}' not found", old_name)),
+            });
+            continue;
+        };
+        let recording_path = root.join(old_name);
+        let modified = fs::metadata(&recording_path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs());
+        let fields = NameFields::read(&recording_path, modified);
+        let new_name = expand_name_pattern(pattern, &fields, old_name, i + 1, width)?;
+
+        let error = if entries.iter().any(|e| e.old_name == *old_name) {
+            Some("listed more than once".to_string())
+        } else if let Err(e) = validate_recording_name(&new_name) {
+            Some(e)
+        } else if let Some(other) = entries.iter().find(|e| e.new_name == new_name) {
+            Some(format!("'{}' is also the new name of '{}'", new_name, other.old_name))
+        } else if new_name != *old_name && FileScanner::find_root(roots, &new_name).is_some() {
+            Some(format!("Recording with name '{}' already exists", new_name))
+        } else {
+            None
+        };
+        entries.push(BulkRenameEntry { old_name: old_name.clone(), new_name, error });
+    }
+    Ok(entries)
+}
+
+/// Rename every changed entry, renaming the finished ones back when one fails
+pub fn apply_bulk_rename(entries: &[BulkRenameEntry], roots: &[PathBuf]) -> Result<Vec<RenameReport>, String> {
+    let mut done: Vec<(RenameReport, &Path)> = Vec::new();
+    for entry in entries.iter().filter(|e| e.old_name != e.new_name) {
+        let result = FileScanner::find_root(roots, &entry.old_name)
+            .ok_or_else(|| format!("Recording '{}' not found", entry.old_name))
+            .and_then(|root| rename_recording_impl(&entry.old_name, &entry.new_name, root).map(|report| (report, root)));
+        match result {
+            Ok(renamed) => done.push(renamed),
+            Err(e) => {
+                let mut message = format!("Failed to rename '{}' to '{}': {}", entry.old_name, entry.new_name, e);
+                for (report, root) in done.iter().rev() {
+                    if let Err(rollback_err) = rename_recording_impl(&report.new_name, &report.old_name, root) {
+                        message.push_str(&format!("; renaming '{}' back also failed: {}", report.new_name, rollback_err));
+                    }
+                }
+                log::error!("{} (rolled back {} renames)", message, done.len());
+                return Err(message);
+            }
+        }
+    }
+    Ok(done.into_iter().map(|(report, _)| report).collect())
+}
+
+/// A .blend project whose paths were (or would be) remapped by Blender
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BlendRepair {
+    pub file: String,
+    /// Paths Blender changed; None in dry-run mode or when Blender failed
+    pub remapped: Option<usize>,
+}
+
+/// What a path repair did, or would do in dry-run mode
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PathRepairReport {
+    pub recording: String,
+    pub dry_run: bool,
+    /// Previous recording locations still referenced by generated files
+    pub stale_roots: Vec<String>,
+    pub rewrites: Vec<PathRewrite>,
+    pub blend_files: Vec<BlendRepair>,
+    pub warnings: Vec<String>,
+}
+
+/// Rewrite absolute paths in generated artifacts after a recording was renamed or
+/// moved, so old projects still render. `old_path` adds a previous location that
+/// the configs no longer mention (e.g. when only a .blend file references it).
+#[tauri::command]
+pub async fn repair_paths(
+    recording_name: String,
+    old_path: Option<String>,
+    dry_run: Option<bool>,
+    config: State<'_, AppConfig>,
+    settings: State<'_, SettingsStore>
+) -> Result<PathRepairReport, String> {
+    log::info!("🔧 Repairing paths of '{}' (old_path: {:?}, dry_run: {:?})", recording_name, old_path, dry_run);
+    let recording_path = config.recording_path(&recording_name);
+    let mut report = repair_text_paths_impl(&recording_path, old_path.as_deref(), dry_run.unwrap_or(false))?;
+    if report.dry_run || report.stale_roots.is_empty() {
+        return Ok(report);
+    }
+
+    let runner = ProcessRunner::new(
+        config.cli_paths.workspace_root.clone(),
+        config.cli_paths.uv_path.clone()
+    )
+    .with_environment(ProcessEnvironment::load(&settings)?.for_step(None));
+    for blend in report.blend_files.iter_mut() {
+        let blend_path = recording_path.join(&blend.file);
+        match runner.run_blender_remap_paths(&config.cli_paths.blender_path, &blend_path, &recording_path, &report.stale_roots).await {
+            Ok(result) if result.success => blend.remapped = parse_remapped_count(&result.stdout),
+            Ok(result) => report.warnings.push(format!(
+                "Blender failed to repair {}: {}",
+                blend.file,
+                result.stderr.lines().last().unwrap_or("unknown error")
+            )),
+            Err(e) => report.warnings.push(format!("Failed to run Blender for {}: {}", blend.file, e)),
+        }
+    }
+
+    for warning in &report.warnings {
+        log::warn!("{}", warning);
+    }
+    Ok(report)
+}
+
+/// Rewrite metadata and animation configs; .blend files are only listed
+pub fn repair_text_paths_impl(
+    recording_path: &Path,
+    old_path: Option<&str>,
+    dry_run: bool
+) -> Result<PathRepairReport, String> {
+    let recording = recording_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| "Invalid recording directory name".to_string())?;
+    if !recording_path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording));
+    }
+    if let Some(status) = detect_in_progress_status(recording_path) {
+        return Err(format!("Recording '{}' is busy: {:?}", recording, status));
+    }
+
+    let mut stale_roots = PathRepair::stale_roots(recording_path);
+    if let Some(old_path) = old_path {
+        let old_path = old_path.trim_end_matches(['/', '\\']).to_string();
+        if !old_path.is_empty() && Path::new(&old_path) != recording_path && !stale_roots.contains(&old_path) {
+            stale_roots.push(old_path);
+            stale_roots.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        }
+    }
+
+    let replacements = PathRepair::replacements(recording_path, &stale_roots);
+    let replacement_refs: Vec<(&str, &str)> = replacements
+        .iter()
+        .map(|(from, to)| (from.as_str(), to.as_str()))
+        .collect();
+    let planned = plan_rewrites_in(recording_path, &replacement_refs);
+
+    let (rewrites, warnings) = if dry_run {
+        (planned.into_iter().map(|p| p.rewrite).collect(), Vec::new())
+    } else {
+        write_planned_rewrites(recording_path, planned)
+    };
+
+    let blend_files = find_blend_files(recording_path)
+        .into_iter()
+        .map(|file| BlendRepair { file, remapped: None })
+        .collect();
+
+    Ok(PathRepairReport {
+        recording,
+        dry_run,
+        stale_roots,
+        rewrites,
+        blend_files,
+        warnings,
+    })
+}
+
+/// Number printed by the Blender remap script
+fn parse_remapped_count(stdout: &str) -> Option<usize> {
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix(BLENDER_REMAP_MARKER))
+        .and_then(|count| count.trim().parse().ok())
+}
+
+/// Report what a rename would change without touching the filesystem
+pub fn preview_rename_impl(
+    old_name: &str,
+    new_name: &str,
+    recordings_path: &Path
+) -> Result<RenameReport, String> {
+    validate_rename(old_name, new_name, recordings_path)?;
+
+    let old_dir = recordings_path.join(old_name);
+    let new_dir = recordings_path.join(new_name);
+    let planned = plan_path_rewrites(&old_dir, &new_dir, old_name, new_name);
+
+    Ok(RenameReport {
+        old_name: old_name.to_string(),
+        new_name: new_name.to_string(),
+        dry_run: true,
+        rewrites: planned.iter().map(|p| p.rewrite.clone()).collect(),
+        needs_manual_repair: find_blend_files(&old_dir),
+        warnings: Vec::new(),
+    })
 }
 
 /// Internal implementation for renaming recording directory
@@ -15,6 +420,50 @@ pub fn rename_recording_impl(
     old_name: &str,
     new_name: &str,
     recordings_path: &Path
+) -> Result<RenameReport, String> {
+    validate_rename(old_name, new_name, recordings_path)?;
+
+    let old_dir = recordings_path.join(old_name);
+    let new_dir = recordings_path.join(new_name);
+
+    // Compute new file contents up front so a rename never leaves half-read files behind
+    let planned = plan_path_rewrites(&old_dir, &new_dir, old_name, new_name);
+    let needs_manual_repair = find_blend_files(&old_dir);
+
+    // Atomic rename operation
+    fs::rename(&old_dir, &new_dir)
+        .map_err(|e| format!("Failed to rename recording directory: {}", e))?;
+
+    // Rename main recording file if it exists and matches directory name
+    if let Err(e) = rename_main_recording_file(&old_dir, &new_dir, old_name, new_name) {
+        // If file rename fails, try to rollback directory rename
+        if let Err(rollback_err) = fs::rename(&new_dir, &old_dir) {
+            return Err(format!("Failed to rename recording file: {} (rollback also failed: {})", e, rollback_err));
+        }
+        return Err(format!("Failed to rename recording file: {}", e));
+    }
+
+    let (rewrites, warnings) = write_planned_rewrites(&new_dir, planned);
+
+    for warning in &warnings {
+        log::warn!("{}", warning);
+    }
+
+    log::info!("Successfully renamed recording '{}' to '{}'", old_name, new_name);
+    Ok(RenameReport {
+        old_name: old_name.to_string(),
+        new_name: new_name.to_string(),
+        dry_run: false,
+        rewrites,
+        needs_manual_repair,
+        warnings,
+    })
+}
+
+fn validate_rename(
+    old_name: &str,
+    new_name: &str,
+    recordings_path: &Path
 ) -> Result<(), String> {
     // Validation
     if old_name.is_empty() {
@@ -24,6 +473,7 @@ pub fn rename_recording_impl(
     if new_name.is_empty() {
         return Err("New recording name cannot be empty".to_string());
     }
+    validate_recording_name(new_name)?;
 
     if old_name == new_name {
         return Err("Cannot rename to the same name".to_string());
@@ -46,21 +496,71 @@ pub fn rename_recording_impl(
         return Err(format!("Recording with name '{}' already exists", new_name));
     }
 
-    // Atomic rename operation
-    fs::rename(&old_dir, &new_dir)
-        .map_err(|e| format!("Failed to rename recording directory: {}", e))?;
+    Ok(())
+}
 
-    // Rename main recording file if it exists and matches directory name
-    if let Err(e) = rename_main_recording_file(&old_dir, &new_dir, old_name, new_name) {
-        // If file rename fails, try to rollback directory rename
-        if let Err(rollback_err) = fs::rename(&new_dir, &old_dir) {
-            return Err(format!("Failed to rename recording file: {} (rollback also failed: {})", e, rollback_err));
+/// New content for a file with rewritten path references
+struct PlannedRewrite {
+    rewrite: PathRewrite,
+    content: String,
+}
+
+/// Compute rewritten contents for every file in `old_dir` that references the old location
+fn plan_path_rewrites(
+    old_dir: &Path,
+    new_dir: &Path,
+    old_name: &str,
+    new_name: &str
+) -> Vec<PlannedRewrite> {
+    let old_dir_str = old_dir.to_string_lossy().to_string();
+    let new_dir_str = new_dir.to_string_lossy().to_string();
+    let replacements = [
+        (old_dir_str.as_str(), new_dir_str.as_str()),
+        (&*format!("{}.mkv", old_name), &*format!("{}.mkv", new_name)),
+    ];
+    plan_rewrites_in(old_dir, &replacements)
+}
+
+/// Compute rewritten contents for every path-referencing file in `dir`
+fn plan_rewrites_in(dir: &Path, replacements: &[(&str, &str)]) -> Vec<PlannedRewrite> {
+    let mut planned = Vec::new();
+    for file in files_with_path_references(dir) {
+        let Ok(content) = fs::read_to_string(&file) else {
+            continue;
+        };
+
+        let is_json = file.extension().is_some_and(|e| e == "json");
+        let rewritten = if is_json {
+            rewrite_json_strings(&content, replacements)
+        } else {
+            Some(rewrite_text(&content, replacements))
+        };
+
+        if let Some((new_content, occurrences)) = rewritten {
+            if occurrences > 0 {
+                let relative = file.strip_prefix(dir).unwrap_or(&file).to_string_lossy().to_string();
+                planned.push(PlannedRewrite {
+                    rewrite: PathRewrite { file: relative, occurrences },
+                    content: new_content,
+                });
+            }
         }
-        return Err(format!("Failed to rename recording file: {}", e));
     }
+    planned
+}
 
-    log::info!("Successfully renamed recording '{}' to '{}'", old_name, new_name);
-    Ok(())
+/// Write planned rewrites into `dir`, collecting failures as warnings
+fn write_planned_rewrites(dir: &Path, planned: Vec<PlannedRewrite>) -> (Vec<PathRewrite>, Vec<String>) {
+    let mut rewrites = Vec::new();
+    let mut warnings = Vec::new();
+    for plan in planned {
+        let target = dir.join(&plan.rewrite.file);
+        match write_atomic(&target, &plan.content) {
+            Ok(()) => rewrites.push(plan.rewrite),
+            Err(e) => warnings.push(format!("Failed to update {}: {}", target.display(), e)),
+        }
+    }
+    (rewrites, warnings)
 }
 
 /// Rename the main recording file if it matches the directory name
@@ -143,6 +643,44 @@ mod tests {
         let _ = fs::remove_dir_all(&temp_dir);
     }
 
+    #[test]
+    fn test_rename_recording_rewrites_path_references() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let old_dir = setup_test_recording(root, "old_recording");
+
+        let old_dir_str = old_dir.to_string_lossy().to_string();
+        fs::write(
+            old_dir.join("metadata.json"),
+            serde_json::json!({ "recording_path": old_dir_str, "file": "old_recording.mkv" }).to_string(),
+        ).unwrap();
+        fs::write(
+            old_dir.join("animation_config_beat-switch.yaml"),
+            format!("project:\n  video_files: [{}/extracted/cam.mp4]\n", old_dir_str),
+        ).unwrap();
+        fs::create_dir_all(old_dir.join("blender")).unwrap();
+        fs::write(old_dir.join("blender/project.blend"), "binary").unwrap();
+
+        let preview = preview_rename_impl("old_recording", "new_recording", root).unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.rewrites.len(), 2);
+        assert!(old_dir.exists(), "Dry run must not rename anything");
+
+        let report = rename_recording_impl("old_recording", "new_recording", root).unwrap();
+        assert_eq!(report.rewrites, preview.rewrites);
+        assert_eq!(report.needs_manual_repair, vec![format!("blender{}project.blend", std::path::MAIN_SEPARATOR)]);
+
+        let new_dir = root.join("new_recording");
+        let metadata: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(new_dir.join("metadata.json")).unwrap()).unwrap();
+        assert_eq!(metadata["recording_path"], new_dir.to_string_lossy().to_string());
+        assert_eq!(metadata["file"], "new_recording.mkv");
+
+        let yaml = fs::read_to_string(new_dir.join("animation_config_beat-switch.yaml")).unwrap();
+        assert!(yaml.contains(&new_dir.to_string_lossy().to_string()));
+        assert!(!yaml.contains("old_recording"));
+    }
+
     #[test]
     fn test_rename_recording_source_not_exists() {
         let temp_dir = std::env::temp_dir().join("fermata_rename_test_no_source");
@@ -287,4 +825,122 @@ mod tests {
         // Cleanup
         let _ = fs::remove_dir_all(&temp_dir);
     }
+
+    #[test]
+    fn test_repair_paths_after_external_move() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let recording = temp_dir.path().join("gig");
+        fs::create_dir_all(recording.join("blender")).unwrap();
+        fs::write(recording.join("gig.mkv"), "video").unwrap();
+        fs::write(recording.join("blender/gig.blend"), "").unwrap();
+        fs::write(
+            recording.join("metadata.json"),
+            r#"{"sources": {"Mic": "/old/gig/extracted/Mic.m4a"}}"#,
+        ).unwrap();
+
+        let preview = repair_text_paths_impl(&recording, None, true).unwrap();
+        assert_eq!(preview.stale_roots, vec!["/old/gig"]);
+        assert_eq!(preview.rewrites.len(), 1);
+        assert_eq!(preview.blend_files[0].file, "blender/gig.blend");
+        assert!(fs::read_to_string(recording.join("metadata.json")).unwrap().contains("/old/gig"));
+
+        let report = repair_text_paths_impl(&recording, Some("/older/gig/"), false).unwrap();
+        assert_eq!(report.stale_roots, vec!["/older/gig", "/old/gig"]);
+        let metadata = fs::read_to_string(recording.join("metadata.json")).unwrap();
+        assert!(metadata.contains(&recording.join("extracted/Mic.m4a").to_string_lossy().to_string()));
+        assert!(PathRepair::stale_roots(&recording).is_empty());
+    }
+
+    #[test]
+    fn test_parse_remapped_count() {
+        assert_eq!(parse_remapped_count("Blender 4.1\nFERMATA_REMAPPED 7\n"), Some(7));
+        assert_eq!(parse_remapped_count("Blender quit"), None);
+    }
+
+    #[test]
+    fn test_plan_bulk_rename() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let roots = [temp_dir.path().to_path_buf()];
+        for name in ["fri", "sat", "gig 2"] {
+            setup_test_recording(temp_dir.path(), name);
+        }
+        let names: Vec<String> = ["fri", "sat", "mon"].iter().map(|n| n.to_string()).collect();
+
+        let entries = plan_bulk_rename("gig {index}", &names, &roots).unwrap();
+        assert_eq!(entries[0].new_name, "gig 1");
+        assert_eq!(entries[0].error, None);
+        assert!(entries[1].error.as_deref().unwrap().contains("already exists"));
+        assert!(entries[2].error.as_deref().unwrap().contains("not found"));
+
+        let same = plan_bulk_rename("gig", &names[..2], &roots).unwrap();
+        assert!(same[1].error.as_deref().unwrap().contains("also the new name of 'fri'"));
+        assert!(plan_bulk_rename("{day}", &names, &roots).is_err());
+    }
+
+    #[test]
+    fn test_apply_bulk_rename_rolls_back() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let roots = [root.to_path_buf()];
+        for name in ["fri", "sat"] {
+            setup_test_recording(root, name);
+        }
+        let names = vec!["fri".to_string(), "sat".to_string()];
+        let entries = plan_bulk_rename("{original} live", &names, &roots).unwrap();
+        assert!(entries.iter().all(|e| e.error.is_none()));
+
+        // Something takes the second name between preview and apply
+        fs::create_dir_all(root.join("sat live")).unwrap();
+        assert!(apply_bulk_rename(&entries, &roots).is_err());
+        assert!(root.join("fri/fri.mkv").exists());
+        assert!(!root.join("fri live").exists());
+
+        fs::remove_dir_all(root.join("sat live")).unwrap();
+        let renamed = apply_bulk_rename(&entries, &roots).unwrap();
+        assert_eq!(renamed.len(), 2);
+        assert!(root.join("sat live/sat live.mkv").exists());
+    }
+
+    #[test]
+    fn test_undo_last_rename_reverses_journaled_rename() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().join("recordings");
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+        let old_dir = setup_test_recording(&root, "gig");
+        let metadata = serde_json::json!({ "recording_path": old_dir.to_string_lossy(), "file": "gig.mkv" }).to_string();
+        fs::write(old_dir.join("metadata.json"), &metadata).unwrap();
+
+        let report = rename_recording_impl("gig", "oops", &root).unwrap();
+        record_renames(&settings, &[report]).unwrap();
+        // Written after the rename, so the undo leaves it alone
+        let later = format!("audio: {}/extracted/Mic.m4a\n", root.join("oops").display());
+        fs::write(root.join("oops").join("animation_config_later.yaml"), &later).unwrap();
+
+        let roots = vec![root.clone()];
+        let undone = undo_last_rename_impl("oops", &roots, &settings).unwrap();
+        assert_eq!((undone.old_name.as_str(), undone.new_name.as_str()), ("oops", "gig"));
+        assert!(!root.join("oops").exists());
+        assert!(old_dir.join("gig.mkv").exists());
+        let restored: serde_json::Value = serde_json::from_str(&fs::read_to_string(old_dir.join("metadata.json")).unwrap()).unwrap();
+        assert_eq!(restored, serde_json::from_str::<serde_json::Value>(&metadata).unwrap());
+        assert_eq!(fs::read_to_string(old_dir.join("animation_config_later.yaml")).unwrap(), later);
+        assert!(RenameJournal::load(&settings).unwrap().list().is_empty());
+        assert!(undo_last_rename_impl("gig", &roots, &settings).unwrap_err().contains("No rename"));
+    }
+
+    #[test]
+    fn test_renaming_back_restores_path_references() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let old_dir = setup_test_recording(root, "gig");
+        let metadata = serde_json::json!({ "recording_path": old_dir.to_string_lossy(), "file": "gig.mkv" }).to_string();
+        fs::write(old_dir.join("metadata.json"), &metadata).unwrap();
+
+        let report = rename_recording_impl("gig", "oops", root).unwrap();
+        assert_eq!(report.rewrites.len(), 1);
+        rename_recording_impl("oops", "gig", root).unwrap();
+        assert!(old_dir.join("gig.mkv").exists());
+        let restored: serde_json::Value = serde_json::from_str(&fs::read_to_string(old_dir.join("metadata.json")).unwrap()).unwrap();
+        assert_eq!(restored, serde_json::from_str::<serde_json::Value>(&metadata).unwrap());
+    }
 }
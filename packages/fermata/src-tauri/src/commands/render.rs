@@ -0,0 +1,224 @@
+use crate::commands::recordings::AppConfig;
+use crate::models::NextStep;
+use crate::services::{
+    attach_render as attach_render_file, detect_in_progress_status, AttachedRender, BlendVersion, BlendVersions, BlenderProgress, BlenderProgressSnapshot, FrameCount, FrameCounter, ProcessRunner, ProgressCallback, RenderDevice,
+    RenderDeviceSettings, RenderPreview, RenderSettings, RenderVersion, RenderVersions, SettingsStore, RENDER_DEVICE_KEY, ExecutionBackend, ProcessEnvironment, RENDER_HOST_KEY
+};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+
+/// Event emitted with structured progress while Blender renders
+pub const RENDER_PROGRESS_EVENT: &str = "render-progress";
+
+/// Payload of the `render-progress` event
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderProgressEvent {
+    pub recording_name: String,
+    #[serde(flatten)]
+    pub progress: BlenderProgress,
+}
+
+/// Render progress for a recording, cheap enough to poll every few seconds
+#[derive(Debug, Serialize)]
+pub struct RenderProgress {
+    pub rendered_frames: usize,
+    pub latest_frame: Option<u32>,
+    /// Last progress reported by Blender, also after the app was restarted
+    pub last_snapshot: Option<BlenderProgressSnapshot>,
+}
+
+impl From<FrameCount> for RenderProgress {
+    fn from(count: FrameCount) -> Self {
+        Self {
+            rendered_frames: count.frames,
+            latest_frame: count.latest_frame,
+            last_snapshot: None,
+        }
+    }
+}
+
+/// Get the number of frames rendered so far for a recording
+#[tauri::command]
+pub fn get_render_progress(
+    recording_name: String,
+    config: State<AppConfig>,
+    frame_counter: State<FrameCounter>
+) -> Result<RenderProgress, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    let render_dir = recording_path.join("blender").join("render");
+    Ok(RenderProgress {
+        last_snapshot: BlenderProgressSnapshot::load(&recording_path),
+        ..frame_counter.count(&render_dir).into()
+    })
+}
+
+/// State of the last preview render, if one was started
+#[tauri::command]
+pub fn get_render_preview(recording_name: String, config: State<AppConfig>) -> Result<Option<RenderPreview>, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    Ok(RenderPreview::load(&recording_path))
+}
+
+/// Output overrides used when rendering a recording
+#[tauri::command]
+pub fn get_render_settings(recording_name: String, config: State<AppConfig>) -> Result<RenderSettings, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    Ok(RenderSettings::load(&recording_path))
+}
+
+/// Validate and save output overrides for a recording's render
+#[tauri::command]
+pub fn set_render_settings(recording_name: String, settings: RenderSettings, config: State<AppConfig>) -> Result<(), String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    settings.save(&recording_path)?;
+    log::info!("🎛️ Saved render settings for '{}': {:?}", recording_name, settings);
+    Ok(())
+}
+
+/// Render outputs kept side by side, e.g. blender/render and blender/render_v2, with
+/// the preset and settings each was rendered with
+#[tauri::command]
+pub fn list_render_versions(recording_name: String, config: State<AppConfig>) -> Result<Vec<RenderVersion>, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    Ok(RenderVersions::list(&recording_path))
+}
+
+/// Pick the render version the Upload step uses
+#[tauri::command]
+pub fn promote_render(recording_name: String, version: String, config: State<AppConfig>) -> Result<RenderVersion, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    let promoted = RenderVersions::promote(&recording_path, &version)?;
+    log::info!("⭐ Render version '{}' of '{}' will be uploaded", version, recording_name);
+    Ok(promoted)
+}
+
+/// Bring in a video rendered on another machine: copy (or move) it into blender/render,
+/// probe it and mark the recording Rendered so it can be uploaded
+#[tauri::command]
+pub async fn attach_render(
+    recording_name: String,
+    source_video_path: String,
+    move_file: bool,
+    config: State<'_, AppConfig>
+) -> Result<AttachedRender, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    if let Some(status) = detect_in_progress_status(&recording_path) {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, status));
+    }
+    // Copying a video across volumes takes a while
+    let source = PathBuf::from(source_video_path);
+    let attached = tauri::async_runtime::spawn_blocking(move || attach_render_file(&recording_path, &source, move_file))
+        .await
+        .map_err(|e| format!("Attach task failed: {}", e))??;
+    log::info!("📎 Attached render {} to '{}' from {}", attached.video, recording_name, attached.source);
+    Ok(attached)
+}
+
+/// Blender projects of a recording, one per setup run
+#[tauri::command]
+pub fn list_blend_versions(recording_name: String, config: State<AppConfig>) -> Result<Vec<BlendVersion>, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    Ok(BlendVersions::list(&recording_path))
+}
+
+/// Pick the Blender project the Render step uses
+#[tauri::command]
+pub fn set_active_blend(recording_name: String, file: String, config: State<AppConfig>) -> Result<BlendVersion, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    let active = BlendVersions::set_active(&recording_path, &file)?;
+    log::info!("🗂️ '{}' renders {} from now on", recording_name, file);
+    Ok(active)
+}
+
+/// Compute devices Blender can render on, the CPU first
+#[tauri::command]
+pub async fn list_render_devices(config: State<'_, AppConfig>, settings: State<'_, SettingsStore>) -> Result<Vec<RenderDevice>, String> {
+    let runner = ProcessRunner::new(
+        config.cli_paths.workspace_root.clone(),
+        config.cli_paths.uv_path.clone()
+    )
+    .with_environment(ProcessEnvironment::load(&settings)?.for_step(Some(&NextStep::Render)));
+    let result = runner.run_blender_list_devices(&config.cli_paths.blender_path).await
+        .map_err(|e| format!("Failed to start Blender: {}", e))?;
+    if !result.success {
+        return Err(format!("Failed to list render devices: {}", result.stderr));
+    }
+    Ok(RenderDevice::parse_list(&result.stdout))
+}
+
+/// Preferred render device, None when the .blend file decides
+#[tauri::command]
+pub fn get_render_device(settings: State<SettingsStore>) -> Result<Option<RenderDevice>, String> {
+    Ok(settings.get::<RenderDeviceSettings>(RENDER_DEVICE_KEY)?.device)
+}
+
+/// Set the preferred render device; None restores the .blend file's choice
+#[tauri::command]
+pub fn set_render_device(device: Option<RenderDevice>, settings: State<SettingsStore>) -> Result<(), String> {
+    if let Some(device) = &device {
+        device.validate()?;
+    }
+    settings.set(RENDER_DEVICE_KEY, &RenderDeviceSettings { device: device.clone() })?;
+    log::info!("🖥️ Render device set to {}", device.as_ref().map_or("the .blend file's choice", |d| d.name.as_str()));
+    Ok(())
+}
+
+/// Machine the render step runs on
+#[tauri::command]
+pub fn get_render_host(settings: State<SettingsStore>) -> Result<ExecutionBackend, String> {
+    settings.get(RENDER_HOST_KEY)
+}
+
+/// Set the machine the render step runs on; Local renders on this one
+#[tauri::command]
+pub fn set_render_host(host: ExecutionBackend, settings: State<SettingsStore>) -> Result<(), String> {
+    host.validate()?;
+    settings.set(RENDER_HOST_KEY, &host)?;
+    log::info!("🛰️ Render host set to {:?}", host);
+    Ok(())
+}
+
+/// Forward render progress of a recording to the frontend as RENDER_PROGRESS_EVENT
+pub fn render_progress_emitter(app: &AppHandle, recording_name: &str) -> ProgressCallback {
+    let app = app.clone();
+    let recording_name = recording_name.to_string();
+    Arc::new(move |progress: &BlenderProgress| {
+        let event = RenderProgressEvent {
+            recording_name: recording_name.clone(),
+            progress: progress.clone(),
+        };
+        if let Err(e) = app.emit(RENDER_PROGRESS_EVENT, event) {
+            log::error!("Failed to emit {}: {}", RENDER_PROGRESS_EVENT, e);
+        }
+    })
+}
@@ -0,0 +1,108 @@
+use crate::commands::recordings::AppConfig;
+use crate::models::{NextStep, Recording};
+use crate::services::{update_recording_status, FileScanner, Job, JobQueue, Session, Sessions, SettingsStore};
+use tauri::State;
+
+#[tauri::command]
+pub fn list_sessions(settings: State<SettingsStore>) -> Result<Vec<Session>, String> {
+    Ok(Sessions::load(&settings)?.list().to_vec())
+}
+
+#[tauri::command]
+pub fn create_session(name: String, settings: State<SettingsStore>) -> Result<Session, String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let mut sessions = Sessions::load(&settings)?;
+    let session = sessions.create(&name, now)?;
+    sessions.save(&settings)?;
+    log::info!("🎫 Created session '{}'", session.id);
+    Ok(session)
+}
+
+/// Delete a session; its recordings stay untouched
+#[tauri::command]
+pub fn delete_session(session_id: String, settings: State<SettingsStore>) -> Result<Session, String> {
+    let mut sessions = Sessions::load(&settings)?;
+    let session = sessions.delete(&session_id)?;
+    sessions.save(&settings)?;
+    Ok(session)
+}
+
+/// Add recordings to a session, taking them out of their previous session
+#[tauri::command]
+pub fn assign_to_session(
+    session_id: String,
+    recording_names: Vec<String>,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<Session, String> {
+    if let Some(name) = recording_names
+        .iter()
+        .find(|name| FileScanner::find_root(&config.recordings_roots, name).is_none())
+    {
+        return Err(format!("Recording '{}' not found", name));
+    }
+
+    let mut sessions = Sessions::load(&settings)?;
+    let session = sessions.assign(&session_id, &recording_names)?;
+    sessions.save(&settings)?;
+    Ok(session)
+}
+
+#[tauri::command]
+pub fn remove_from_session(recording_names: Vec<String>, settings: State<SettingsStore>) -> Result<(), String> {
+    let mut sessions = Sessions::load(&settings)?;
+    sessions.unassign(&recording_names);
+    sessions.save(&settings)
+}
+
+/// Current state of the recordings in a session; members no longer on disk are skipped
+#[tauri::command]
+pub fn get_session_recordings(
+    session_id: String,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<Vec<Recording>, String> {
+    let sessions = Sessions::load(&settings)?;
+    let mut recordings: Vec<Recording> = sessions
+        .get(&session_id)?
+        .recordings
+        .iter()
+        .filter(|name| FileScanner::find_root(&config.recordings_roots, name).is_some())
+        .filter_map(|name| Recording::from_path(config.recording_path(name)).ok())
+        .collect();
+    for recording in &mut recordings {
+        update_recording_status(recording);
+    }
+    config.annotate_lifecycle(&mut recordings);
+    Ok(recordings)
+}
+
+/// Queue jobs running every recording of a session up to `target_step`.
+/// Recordings that already have a queued or running job are skipped.
+#[tauri::command]
+pub fn queue_session(
+    session_id: String,
+    target_step: String,
+    preset: Option<String>,
+    settings: State<SettingsStore>,
+    queue: State<JobQueue>
+) -> Result<Vec<Job>, String> {
+    if target_step.parse::<NextStep>()? == NextStep::Retry {
+        return Err("'retry' cannot be a session target".to_string());
+    }
+    let sessions = Sessions::load(&settings)?;
+    let session = sessions.get(&session_id)?;
+
+    let source = format!("session:{}", session.id);
+    let jobs: Vec<Job> = session
+        .recordings
+        .iter()
+        .filter_map(|name| queue.enqueue(name, &target_step, preset.clone(), &source))
+        .collect();
+    log::info!("🎫 Queued {} jobs for session '{}' up to {}", jobs.len(), session.id, target_step);
+    Ok(jobs)
+}
@@ -0,0 +1,67 @@
+use crate::commands::recordings::AppConfig;
+use crate::services::{audio_envelope, main_audio_start, AudioWaveform, SourceOffsets, TimelineMarkers};
+use tauri::State;
+
+/// Per-source offsets relative to the main audio, found by cross-correlating their audio,
+/// so trims and markers placed on the main audio can be mapped onto each video source
+#[tauri::command]
+pub async fn get_source_offsets(
+    recording_name: String,
+    config: State<'_, AppConfig>
+) -> Result<SourceOffsets, String> {
+    log::info!("⏱️ Getting source offsets for '{}'", recording_name);
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    let main_audio = config.main_audio_file.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        SourceOffsets::load_or_compute(&recording_path, &main_audio, audio_envelope)
+    })
+    .await
+    .map_err(|e| format!("Source offset task failed: {}", e))?
+}
+
+/// Peaks of an extracted audio track for drawing its waveform under the beat markers;
+/// decoded with ffmpeg once per resolution and then read from the cache
+#[tauri::command]
+pub async fn get_audio_waveform(
+    recording_name: String,
+    audio_file: String,
+    resolution: usize,
+    config: State<'_, AppConfig>
+) -> Result<AudioWaveform, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    AudioWaveform::load_or_generate(&recording_path, &audio_file, resolution).await
+}
+
+/// Beat ticks, sections and energy peaks of the main audio analysis in seconds of
+/// the main video, for the scrubber in the details view
+#[tauri::command]
+pub async fn get_timeline_markers(
+    recording_name: String,
+    config: State<'_, AppConfig>
+) -> Result<TimelineMarkers, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    let main_audio = config.main_audio_file.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let offset = match SourceOffsets::load_or_compute(&recording_path, &main_audio, audio_envelope) {
+            Ok(offsets) => main_audio_start(&offsets),
+            Err(e) => {
+                log::warn!("Timeline markers without source offsets: {}", e);
+                None
+            }
+        };
+        TimelineMarkers::load(&recording_path, &main_audio, offset)
+    })
+    .await
+    .map_err(|e| format!("Timeline markers task failed: {}", e))?
+}
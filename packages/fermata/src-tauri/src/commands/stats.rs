@@ -0,0 +1,106 @@
+use crate::commands::recordings::AppConfig;
+use crate::models::NextStep;
+use crate::services::{
+    recording_video_size, render_report, status_counts, step_duration_history, write_atomic, FileScanner, PipelineStats, ReportFormat,
+    ReportRow, ScanOptions, StatusTracker, StepEstimate
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+/// Counts, storage and step timings across all recordings for the dashboard
+#[tauri::command]
+pub async fn get_pipeline_stats(config: State<'_, AppConfig>) -> Result<PipelineStats, String> {
+    let roots = config.recordings_roots.clone();
+
+    // Reads every recording's step logs, keep it off the main thread
+    tauri::async_runtime::spawn_blocking(move || {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        PipelineStats::collect(&FileScanner::scan_roots(&roots), now)
+    })
+    .await
+    .map_err(|e| format!("Stats task failed: {}", e))
+}
+
+/// Number of recordings per status name for the sidebar badges, e.g. "Failed": 2;
+/// keep them current with the `status-changed` event
+#[tauri::command]
+pub async fn get_status_counts(
+    config: State<'_, AppConfig>,
+    tracker: State<'_, StatusTracker>
+) -> Result<BTreeMap<String, usize>, String> {
+    let roots = config.recordings_roots.clone();
+    let recordings = tauri::async_runtime::spawn_blocking(move || FileScanner::scan_roots_with(&roots, &ScanOptions::fast()))
+        .await
+        .map_err(|e| format!("Status scan failed: {}", e))?;
+    tracker.seed(&recordings);
+    Ok(status_counts(&recordings))
+}
+
+/// How long a step will take on a recording, learned from earlier runs on all
+/// recordings and scaled by the recording's video size; None before a first run
+#[tauri::command]
+pub async fn estimate_step_duration(
+    recording_name: String,
+    step: String,
+    config: State<'_, AppConfig>
+) -> Result<Option<StepEstimate>, String> {
+    let step = step.parse::<NextStep>()?.id();
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    let roots = config.recordings_roots.clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let recordings = FileScanner::scan_roots_with(&roots, &ScanOptions::fast());
+        let history = step_duration_history(recordings.iter().map(|r| r.path.as_path()), &step);
+        StepEstimate::from_history(&step, recording_video_size(&recording_path), &history)
+    })
+    .await
+    .map_err(|e| format!("Estimate task failed: {}", e))
+}
+
+/// Where a pipeline report was written
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ReportExport {
+    pub path: PathBuf,
+    pub recordings: usize,
+}
+
+/// Write a report of every recording matching the status `filter` (all without it)
+/// with step durations, sizes and upload URLs to `destination`, as JSON or CSV
+#[tauri::command]
+pub async fn export_report(
+    format: ReportFormat,
+    filter: Option<String>,
+    destination: String,
+    config: State<'_, AppConfig>
+) -> Result<ReportExport, String> {
+    let path = PathBuf::from(&destination);
+    if !path.parent().is_some_and(Path::is_dir) {
+        return Err(format!("Folder of {} does not exist", destination));
+    }
+    let roots = config.recordings_roots.clone();
+
+    // Reads every recording's step logs, keep it off the main thread
+    let rows = tauri::async_runtime::spawn_blocking(move || {
+        let recordings = FileScanner::scan_roots(&roots);
+        let recordings = match filter {
+            Some(filter) => FileScanner::filter_by_status(&recordings, &filter),
+            None => recordings,
+        };
+        recordings.iter().map(ReportRow::collect).collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| format!("Report task failed: {}", e))?;
+
+    let content = render_report(&rows, format)?;
+    write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    log::info!("📊 Exported a report of {} recordings to {}", rows.len(), path.display());
+    Ok(ReportExport { path, recordings: rows.len() })
+}
@@ -0,0 +1,195 @@
+use crate::commands::recordings::AppConfig;
+use crate::models::RecordingStatus;
+use crate::services::{
+    detect_in_progress_status, find_duplicates as group_duplicates, BatchCleanup, BatchReport, Cleanup, CleanupReport, CleanupTarget,
+    DiskSpace, DuplicateGroup, FileScanner, JobQueue, JobStatus, ReclaimableArtifact, ReclaimableArtifacts, StatusDetector, StorageReport,
+    message, message_with, CurrentLocale, Notifier
+};
+use std::cmp::Reverse;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Event emitted with a DiskSpace whenever the recordings volume enters or leaves low-disk mode
+pub const LOW_DISK_SPACE_EVENT: &str = "low-disk-space";
+
+/// How often the background monitor checks free space
+const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Get free space on the recordings volume and whether renders are paused
+#[tauri::command]
+pub fn get_disk_space(config: State<AppConfig>) -> Result<DiskSpace, String> {
+    DiskSpace::check(&config.recordings_path, config.min_free_space_gb)
+}
+
+/// List regenerable artifacts that can be deleted to free space, largest first
+#[tauri::command]
+pub fn get_reclaimable_artifacts(config: State<AppConfig>) -> Result<Vec<ReclaimableArtifact>, String> {
+    let mut artifacts: Vec<ReclaimableArtifact> = config
+        .recordings_roots
+        .iter()
+        .flat_map(|root| ReclaimableArtifacts::find(root))
+        .collect();
+    artifacts.sort_by_key(|artifact| Reverse(artifact.size_bytes));
+    Ok(artifacts)
+}
+
+/// Disk usage per recording and pipeline stage, with totals
+#[tauri::command]
+pub fn get_storage_report(config: State<AppConfig>) -> Result<StorageReport, String> {
+    let recordings = FileScanner::scan_roots(&config.recordings_roots);
+    Ok(StorageReport::from_recordings(&recordings))
+}
+
+/// Recordings across the library that hold the same raw video or start time,
+/// with which copy to keep and which to merge or delete
+#[tauri::command]
+pub async fn find_duplicates(config: State<'_, AppConfig>) -> Result<Vec<DuplicateGroup>, String> {
+    let roots = config.recordings_roots.clone();
+    // May hash gigabytes of video, keep it off the main thread
+    let groups = tauri::async_runtime::spawn_blocking(move || group_duplicates(&FileScanner::scan_roots(&roots)))
+        .await
+        .map_err(|e| format!("Duplicate search failed: {}", e))?;
+    log::info!("👯 Found {} groups of duplicate recordings", groups.len());
+    Ok(groups)
+}
+
+/// Remove intermediate artifacts of a recording; with `dry_run` only report what would be freed
+#[tauri::command]
+pub fn cleanup_recording(
+    recording_name: String,
+    targets: Vec<CleanupTarget>,
+    dry_run: Option<bool>,
+    config: State<AppConfig>
+) -> Result<CleanupReport, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    ensure_cleanup_allowed(&recording_path, &recording_name, &targets)?;
+
+    let report = Cleanup::run(&recording_path, &recording_name, &targets, dry_run.unwrap_or(false))?;
+    log_cleanup(&report);
+    Ok(report)
+}
+
+/// Clean up every recording uploaded at least `older_than_days` days ago: remove
+/// `targets` from it, or move the whole recording to the trash when no targets are given
+#[tauri::command]
+pub fn cleanup_uploaded(
+    older_than_days: u64,
+    targets: Option<Vec<CleanupTarget>>,
+    dry_run: Option<bool>,
+    config: State<AppConfig>,
+    queue: State<JobQueue>
+) -> Result<BatchReport, String> {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let report = BatchCleanup::cleanup_uploaded(
+        &config.recordings_roots,
+        older_than_days,
+        targets.as_deref(),
+        &running_recordings(&queue),
+        now,
+        dry_run.unwrap_or(false)
+    );
+    log_batch(&report);
+    Ok(report)
+}
+
+/// Move several recordings to the trash at once; with `dry_run` only report them
+#[tauri::command]
+pub fn delete_recordings(
+    names: Vec<String>,
+    dry_run: Option<bool>,
+    config: State<AppConfig>,
+    queue: State<JobQueue>
+) -> Result<BatchReport, String> {
+    let report = BatchCleanup::delete(&config.recordings_roots, &names, &running_recordings(&queue), dry_run.unwrap_or(false));
+    log_batch(&report);
+    Ok(report)
+}
+
+/// Recordings a queued job is running on
+pub fn running_recordings(queue: &JobQueue) -> Vec<String> {
+    queue
+        .list()
+        .into_iter()
+        .filter(|job| job.status == JobStatus::Running)
+        .map(|job| job.recording_name)
+        .collect()
+}
+
+fn log_batch(report: &BatchReport) {
+    let mb = report.freed_bytes / (1024 * 1024);
+    let verb = if report.dry_run { "would free" } else { "freed" };
+    log::info!(
+        "🧹 Batch cleanup {} {} MB: {} recordings trashed, {} cleaned, {} skipped",
+        verb,
+        mb,
+        report.trashed.len(),
+        report.cleaned.len(),
+        report.skipped.len()
+    );
+}
+
+fn ensure_cleanup_allowed(recording_path: &Path, recording_name: &str, targets: &[CleanupTarget]) -> Result<(), String> {
+    if let Some(status) = detect_in_progress_status(recording_path) {
+        return Err(format!("Recording '{}' is busy: {:?}", recording_name, status));
+    }
+    if let Some(target) = targets.iter().find(|t| t.requires_upload()) {
+        if StatusDetector::detect_status(recording_path) != RecordingStatus::Uploaded {
+            return Err(format!("{:?} can only be cleaned up after '{}' is uploaded", target, recording_name));
+        }
+    }
+    Ok(())
+}
+
+fn log_cleanup(report: &CleanupReport) {
+    let mb = report.freed_bytes / (1024 * 1024);
+    if report.dry_run {
+        log::info!("🧹 Cleanup of '{}' would free {} MB", report.recording, mb);
+    } else {
+        log::info!("🧹 Cleaned up '{}', freed {} MB", report.recording, mb);
+    }
+}
+
+/// Poll free space in the background and alert the frontend when low-disk mode changes,
+/// with a desktop notification when it starts
+pub fn start_disk_monitor(app: &AppHandle) {
+    let app = app.clone();
+
+    std::thread::spawn(move || {
+        let mut was_low = false;
+        loop {
+            let config = app.state::<AppConfig>();
+            match DiskSpace::check(&config.recordings_path, config.min_free_space_gb) {
+                Ok(space) if space.low != was_low => {
+                    was_low = space.low;
+                    if space.low {
+                        log::warn!(
+                            "💾 Low disk space: {} MB free, renders paused",
+                            space.available_bytes / (1024 * 1024)
+                        );
+                        let locale = app.state::<CurrentLocale>().get();
+                        let free = (space.available_bytes / (1024 * 1024 * 1024)).to_string();
+                        app.state::<Notifier>().notify(
+                            &message_with(locale, "notify.low_disk", &[("free", &free)]),
+                            message(locale, "notify.low_disk_hint"),
+                        );
+                    } else {
+                        log::info!("💾 Disk space recovered, renders resumed");
+                    }
+                    if let Err(e) = app.emit(LOW_DISK_SPACE_EVENT, space) {
+                        log::error!("Failed to emit {}: {}", LOW_DISK_SPACE_EVENT, e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Disk space check failed: {}", e),
+            }
+            std::thread::sleep(DISK_CHECK_INTERVAL);
+        }
+    });
+}
@@ -0,0 +1,16 @@
+use crate::services::{SettingsStore, Toolchain};
+use tauri::State;
+
+/// How the pipeline runs the workspace's Python packages
+#[tauri::command]
+pub fn get_toolchain(settings: State<SettingsStore>) -> Result<Toolchain, String> {
+    Toolchain::load(&settings)
+}
+
+/// Takes effect for the next process a step starts
+#[tauri::command]
+pub fn set_toolchain(toolchain: Toolchain, settings: State<SettingsStore>) -> Result<Toolchain, String> {
+    toolchain.save(&settings)?;
+    log::info!("🐍 Toolchain: {}", toolchain.label());
+    Ok(toolchain)
+}
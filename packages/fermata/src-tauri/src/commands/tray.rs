@@ -0,0 +1,240 @@
+use crate::services::{
+    held_step_locks, message, message_with, terminate_processes, BackgroundSettings, CurrentLocale, JobQueue, Locale, SettingsStore
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, CloseRequestApi, Emitter, Manager, RunEvent, State, Window, Wry};
+
+/// Emitted when quitting would stop running work; the window asks the user and calls confirm_quit
+pub const QUIT_REQUESTED_EVENT: &str = "quit-requested";
+
+/// How often the tray menu picks up the number of running jobs
+const TRAY_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Label of the main window in tauri.conf.json
+const MAIN_WINDOW: &str = "main";
+
+/// How long stopped processes get to exit after SIGTERM before they are killed
+const QUIT_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// How often a quit waiting for the running steps checks on them
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Payload of QUIT_REQUESTED_EVENT
+#[derive(Debug, Clone, Serialize)]
+pub struct QuitRequest {
+    pub running: usize,
+}
+
+/// What quitting does with running work, as the user answered QUIT_REQUESTED_EVENT
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuitMode {
+    /// Let the running steps finish, then quit; their jobs continue on the next launch
+    Wait,
+    /// Stop the running processes; their jobs wait for resume_job on the next launch
+    #[default]
+    Stop,
+    /// Stop the running processes; their jobs are queued again on the next launch
+    Persist,
+}
+
+/// Tray menu entries that change while the app runs
+pub struct TrayState {
+    running: MenuItem<Wry>,
+    show: MenuItem<Wry>,
+    pause: CheckMenuItem<Wry>,
+    quit: MenuItem<Wry>,
+}
+
+/// Pipeline work quitting would stop: running jobs, or steps started by hand
+pub fn running_work(app: &AppHandle) -> usize {
+    app.state::<JobQueue>().running_count().max(held_step_locks())
+}
+
+/// Add the tray icon with its quick actions; jobs run on app state, so they carry on
+/// while the window is hidden
+pub fn start_tray(app: &AppHandle) -> tauri::Result<()> {
+    let locale = app.state::<CurrentLocale>().get();
+    let paused = app.state::<JobQueue>().is_paused();
+    let running = MenuItem::with_id(app, "running", running_label(locale, 0), false, None::<&str>)?;
+    let show = MenuItem::with_id(app, "show", message(locale, "tray.show"), true, None::<&str>)?;
+    let pause = CheckMenuItem::with_id(app, "pause", message(locale, "tray.pause_queue"), true, paused, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", message(locale, "tray.quit"), true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&running, &PredefinedMenuItem::separator(app)?, &show, &pause, &quit])?;
+
+    let mut tray = TrayIconBuilder::with_id("main")
+        .menu(&menu)
+        .tooltip("Fermata")
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            "show" => show_window(app),
+            "pause" => {
+                let queue = app.state::<JobQueue>();
+                set_paused(app, !queue.is_paused());
+            }
+            "quit" => request_quit(app),
+            _ => {}
+        });
+    if let Some(icon) = app.default_window_icon() {
+        tray = tray.icon(icon.clone());
+    }
+    tray.build(app)?;
+    app.manage(TrayState { running, show, pause, quit });
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TRAY_REFRESH_INTERVAL).await;
+            refresh_tray(&app);
+        }
+    });
+    Ok(())
+}
+
+fn running_label(locale: Locale, count: usize) -> String {
+    message_with(locale, "tray.jobs_running", &[("count", &count.to_string())])
+}
+
+/// Bring the menu up to date with the running jobs, the queue and the language
+fn refresh_tray(app: &AppHandle) {
+    let Some(tray) = app.try_state::<TrayState>() else {
+        return;
+    };
+    let locale = app.state::<CurrentLocale>().get();
+    let updated = tray
+        .running
+        .set_text(running_label(locale, running_work(app)))
+        .and_then(|_| tray.show.set_text(message(locale, "tray.show")))
+        .and_then(|_| tray.pause.set_text(message(locale, "tray.pause_queue")))
+        .and_then(|_| tray.pause.set_checked(app.state::<JobQueue>().is_paused()))
+        .and_then(|_| tray.quit.set_text(message(locale, "tray.quit")));
+    if let Err(e) = updated {
+        log::warn!("Failed to update the tray menu: {}", e);
+    }
+}
+
+fn set_paused(app: &AppHandle, paused: bool) {
+    app.state::<JobQueue>().set_paused(paused);
+    log::info!("🗓️ Job queue {}", if paused { "paused" } else { "resumed" });
+    refresh_tray(app);
+}
+
+fn show_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        let shown = window.show().and_then(|_| window.unminimize()).and_then(|_| window.set_focus());
+        if let Err(e) = shown {
+            log::warn!("Failed to show the window: {}", e);
+        }
+    }
+}
+
+/// Quit right away when nothing runs, otherwise let the window ask first
+fn request_quit(app: &AppHandle) {
+    let running = running_work(app);
+    if running == 0 {
+        app.exit(0);
+        return;
+    }
+    show_window(app);
+    if let Err(e) = app.emit(QUIT_REQUESTED_EVENT, QuitRequest { running }) {
+        log::error!("Failed to emit {}: {}", QUIT_REQUESTED_EVENT, e);
+    }
+}
+
+/// Closing the main window hides it when running in the background, and asks first
+/// when quitting would stop running work
+pub fn handle_close_requested(window: &Window, api: &CloseRequestApi) {
+    if window.label() != MAIN_WINDOW {
+        return;
+    }
+    let app = window.app_handle();
+    let background = app
+        .try_state::<SettingsStore>()
+        .and_then(|settings| BackgroundSettings::load(&settings).ok())
+        .is_some_and(|settings| settings.run_in_background);
+    if background {
+        api.prevent_close();
+        if let Err(e) = window.hide() {
+            log::warn!("Failed to hide the window: {}", e);
+        }
+        log::info!("🫥 Window hidden, {} jobs keep running", running_work(app));
+        return;
+    }
+
+    let running = running_work(app);
+    if running > 0 {
+        api.prevent_close();
+        if let Err(e) = app.emit(QUIT_REQUESTED_EVENT, QuitRequest { running }) {
+            log::error!("Failed to emit {}: {}", QUIT_REQUESTED_EVENT, e);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_run_in_background(settings: State<SettingsStore>) -> Result<bool, String> {
+    Ok(BackgroundSettings::load(&settings)?.run_in_background)
+}
+
+/// Keep the app and its jobs running in the tray when the window closes
+#[tauri::command]
+pub fn set_run_in_background(enabled: bool, settings: State<SettingsStore>) -> Result<(), String> {
+    BackgroundSettings { run_in_background: enabled }.save(&settings)?;
+    log::info!("🫥 Run in background {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_queue_paused(queue: State<JobQueue>) -> Result<bool, String> {
+    Ok(queue.is_paused())
+}
+
+/// Hold back queued jobs, or let them start again
+#[tauri::command]
+pub fn set_queue_paused(paused: bool, app: AppHandle) -> Result<(), String> {
+    set_paused(&app, paused);
+    Ok(())
+}
+
+/// Quit after the user chose what happens to the running work, see QuitMode
+#[tauri::command]
+pub async fn confirm_quit(mode: Option<QuitMode>, app: AppHandle) -> Result<(), String> {
+    let mode = mode.unwrap_or_default();
+    let queue = app.state::<JobQueue>();
+    log::warn!("👋 Quitting with {} jobs running: {:?}", running_work(&app), mode);
+    queue.begin_shutdown(mode != QuitMode::Stop);
+
+    if mode == QuitMode::Wait {
+        while running_work(&app) > 0 {
+            tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+        }
+    } else {
+        queue.stop_for_quit(None);
+        let stopped = tauri::async_runtime::spawn_blocking(|| terminate_processes(QUIT_GRACE_PERIOD))
+            .await
+            .map_err(|e| format!("Stopping the running processes failed: {}", e))?;
+        log::info!("👋 Stopped {} running processes", stopped);
+    }
+    app.exit(0);
+    Ok(())
+}
+
+/// Ask first when the app is about to exit with work running, e.g. on Cmd+Q, and make
+/// sure no blender or uv process outlives it however it exits
+pub fn handle_run_event(app: &AppHandle, event: RunEvent) {
+    match event {
+        // Exits the app itself requests carry a code, e.g. from confirm_quit
+        RunEvent::ExitRequested { code: None, api, .. } if running_work(app) > 0 && !app.state::<JobQueue>().is_shutting_down() => {
+            api.prevent_exit();
+            request_quit(app);
+        }
+        RunEvent::Exit => {
+            let stopped = terminate_processes(QUIT_GRACE_PERIOD);
+            if stopped > 0 {
+                log::warn!("👋 Stopped {} processes still running at exit", stopped);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,288 @@
+use crate::commands::recordings::AppConfig;
+use crate::models::{RecordingStatus, UploadBlock, UploadEntry, UploadMetadata, UploadResults};
+use crate::services::{
+    FileScanner, SettingsStore, StatusDetector, UploadBandwidth, UploadBlockStore, UploadConfig, UploadMetadataStore, UploadProfile
+};
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tauri::State;
+
+/// Get all uploads recorded for a recording, oldest first
+#[tauri::command]
+pub fn get_upload_history(recording_name: String, config: State<AppConfig>) -> Result<Vec<UploadEntry>, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    Ok(StatusDetector::read_upload_results(&recording_path)
+        .map(|results| results.history())
+        .unwrap_or_default())
+}
+
+/// Get the parsed upload results (platform, video id, URL, publish status) for a recording
+#[tauri::command]
+pub fn get_upload_results(recording_name: String, config: State<AppConfig>) -> Result<Option<UploadResults>, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    Ok(StatusDetector::read_upload_results(&recording_path))
+}
+
+/// Copy the link of an uploaded video to the system clipboard and return it
+#[tauri::command]
+pub fn copy_upload_link(
+    recording_name: String,
+    platform: Option<String>,
+    config: State<AppConfig>
+) -> Result<String, String> {
+    let recording_path = config.recording_path(&recording_name);
+    let link = StatusDetector::read_upload_results(&recording_path)
+        .and_then(|results| results.link(platform.as_deref()))
+        .ok_or_else(|| format!("No uploaded video link found for recording '{}'", recording_name))?;
+
+    copy_to_clipboard(&link)?;
+    log::info!("🔗 Copied upload link for '{}': {}", recording_name, link);
+    Ok(link)
+}
+
+/// Get the title, description, tags and privacy the next upload will use
+#[tauri::command]
+pub fn get_upload_metadata(recording_name: String, config: State<AppConfig>) -> Result<UploadMetadata, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    UploadMetadataStore::load_or_default(&recording_path, &recording_name)
+}
+
+/// Save upload metadata for a recording
+#[tauri::command]
+pub fn set_upload_metadata(
+    recording_name: String,
+    metadata: UploadMetadata,
+    config: State<AppConfig>
+) -> Result<(), String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    metadata.validate()?;
+    log::info!("📝 Saving upload metadata for '{}'", recording_name);
+    UploadMetadataStore::save(&recording_path, &metadata)
+}
+
+/// Upload metadata of one recording changed by a bulk update
+#[derive(Debug, Serialize)]
+pub struct MetadataReplacement {
+    pub recording: String,
+    pub replacements: usize,
+}
+
+/// Find and replace text in the titles and descriptions of every recording that is
+/// not uploaded yet; `filter` narrows them down by status like `get_recordings_by_status`
+#[tauri::command]
+pub fn bulk_update_upload_metadata(
+    filter: Option<String>,
+    find: String,
+    replace: String,
+    config: State<AppConfig>
+) -> Result<Vec<MetadataReplacement>, String> {
+    if find.is_empty() {
+        return Err("Text to find cannot be empty".to_string());
+    }
+
+    log::info!("📝 Replacing '{}' with '{}' in pending upload metadata (filter: {:?})", find, replace, filter);
+    let recordings = FileScanner::scan_roots(&config.recordings_roots);
+    let recordings = FileScanner::filter_by_status(&recordings, filter.as_deref().unwrap_or(""));
+    let pending: Vec<_> = recordings
+        .iter()
+        .filter(|r| r.status != RecordingStatus::Uploaded)
+        .map(|r| (r.name.as_str(), r.path.as_path()))
+        .collect();
+
+    let updated = replace_in_metadata(&pending, &find, &replace)?;
+    log::info!("📝 Updated upload metadata of {} recordings", updated.len());
+    Ok(updated)
+}
+
+/// Apply the replacement to every recording before saving any, so an invalid
+/// result (e.g. an emptied title) leaves all metadata untouched
+fn replace_in_metadata(recordings: &[(&str, &Path)], find: &str, replace: &str) -> Result<Vec<MetadataReplacement>, String> {
+    let mut changed = Vec::new();
+    for (name, path) in recordings {
+        let mut metadata = UploadMetadataStore::load_or_default(path, name)?;
+        let replacements = metadata.replace_text(find, replace);
+        if replacements == 0 {
+            continue;
+        }
+        metadata.validate().map_err(|e| format!("'{}': {}", name, e))?;
+        changed.push((name, path, metadata, replacements));
+    }
+
+    changed
+        .into_iter()
+        .map(|(name, path, metadata, replacements)| {
+            UploadMetadataStore::save(path, &metadata)?;
+            Ok(MetadataReplacement {
+                recording: name.to_string(),
+                replacements,
+            })
+        })
+        .collect()
+}
+
+/// Flag a recording as "do not upload"; the Upload step refuses to run until cleared
+#[tauri::command]
+pub fn set_upload_block(
+    recording_name: String,
+    reason: String,
+    config: State<AppConfig>
+) -> Result<UploadBlock, String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    log::info!("🚫 Blocking uploads of '{}': {}", recording_name, reason);
+    UploadBlockStore::set(&recording_path, &reason)
+}
+
+/// Remove the "do not upload" flag
+#[tauri::command]
+pub fn clear_upload_block(recording_name: String, config: State<AppConfig>) -> Result<(), String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    log::info!("✅ Clearing upload block of '{}'", recording_name);
+    UploadBlockStore::clear(&recording_path)
+}
+
+/// List the registered Medusa upload profiles, including the built-in default
+#[tauri::command]
+pub fn list_upload_profiles(
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<Vec<UploadProfile>, String> {
+    UploadConfig::list(&settings, &config.cli_paths.workspace_root)
+}
+
+/// Register or update a named upload profile
+#[tauri::command]
+pub fn save_upload_profile(profile: UploadProfile, settings: State<SettingsStore>) -> Result<(), String> {
+    log::info!("💾 Saving upload profile '{}'", profile.name);
+    UploadConfig::save_profile(&settings, profile)
+}
+
+/// Remove a registered upload profile
+#[tauri::command]
+pub fn delete_upload_profile(name: String, settings: State<SettingsStore>) -> Result<(), String> {
+    UploadConfig::remove_profile(&settings, &name)
+}
+
+#[tauri::command]
+pub fn get_upload_bandwidth(settings: State<SettingsStore>) -> Result<UploadBandwidth, String> {
+    UploadBandwidth::load(&settings)
+}
+
+/// Set the upload speed upload times are estimated with; unset measures it from earlier uploads
+#[tauri::command]
+pub fn set_upload_bandwidth(bandwidth: UploadBandwidth, settings: State<SettingsStore>) -> Result<(), String> {
+    bandwidth.save(&settings)
+}
+
+/// Pick the upload profile a recording uses by default, or clear it with `None`
+#[tauri::command]
+pub fn set_recording_upload_profile(
+    recording_name: String,
+    profile: Option<String>,
+    config: State<AppConfig>,
+    settings: State<SettingsStore>
+) -> Result<(), String> {
+    let recording_path = config.recording_path(&recording_name);
+    if !recording_path.exists() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+
+    if let Some(name) = &profile {
+        let profiles = UploadConfig::list(&settings, &config.cli_paths.workspace_root)?;
+        if !profiles.iter().any(|p| &p.name == name) {
+            return Err(format!("Upload profile '{}' not found", name));
+        }
+    }
+
+    UploadConfig::set_recording_profile(&recording_path, profile.as_deref())
+}
+
+fn clipboard_commands() -> Vec<(&'static str, Vec<&'static str>)> {
+    if cfg!(target_os = "macos") {
+        vec![("pbcopy", vec![])]
+    } else if cfg!(target_os = "windows") {
+        vec![("clip", vec![])]
+    } else {
+        vec![
+            ("wl-copy", vec![]),
+            ("xclip", vec!["-selection", "clipboard"]),
+            ("xsel", vec!["--clipboard", "--input"]),
+        ]
+    }
+}
+
+/// Pipe text into the first available clipboard tool
+fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    for (program, args) in clipboard_commands() {
+        let Ok(mut child) = Command::new(program).args(&args).stdin(Stdio::piped()).spawn() else {
+            continue;
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .map_err(|e| format!("Failed to write to {}: {}", program, e))?;
+        }
+
+        let status = child.wait().map_err(|e| format!("Failed to run {}: {}", program, e))?;
+        if status.success() {
+            return Ok(());
+        }
+    }
+
+    Err("No clipboard tool available".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_replace_in_metadata_is_all_or_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("gig_1");
+        let second = temp_dir.path().join("gig_2");
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+        let metadata = |title: &str| UploadMetadata {
+            title: Some(title.to_string()),
+            ..UploadMetadata::default()
+        };
+        UploadMetadataStore::save(&first, &metadata("Live #setka23")).unwrap();
+        UploadMetadataStore::save(&second, &metadata("#setka23")).unwrap();
+        let recordings = [("gig_1", first.as_path()), ("gig_2", second.as_path())];
+
+        assert!(replace_in_metadata(&recordings, "#setka23", "").is_err());
+        assert_eq!(UploadMetadataStore::load(&first).unwrap().unwrap().title.as_deref(), Some("Live #setka23"));
+
+        let updated = replace_in_metadata(&recordings, "#setka23", "#setka24").unwrap();
+        assert_eq!(updated.len(), 2);
+        assert_eq!(UploadMetadataStore::load(&second).unwrap().unwrap().title.as_deref(), Some("#setka24"));
+    }
+}
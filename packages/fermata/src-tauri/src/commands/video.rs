@@ -1,17 +1,89 @@
-use tauri::State;
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{Manager, Runtime, State, UriSchemeContext, UriSchemeResponder};
 use crate::commands::recordings::AppConfig;
+use crate::services::{
+    content_type, read_chunk, recording_name_from_uri_path, video_url, ExternalPlayerSettings, PlayerCommand,
+    SettingsStore, EXTERNAL_PLAYER_KEY
+};
+use serde::Serialize;
 use std::process::Command;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-/// Get the path to the main video file to play for a recording
+/// Video to play for a recording
+#[derive(Debug, Serialize)]
+pub struct PlayableVideo {
+    /// Streaming URL for the in-app <video> element
+    pub url: String,
+    /// File on disk, for opening in an external player
+    pub path: String,
+}
+
+/// Get the video to play for a recording
 #[tauri::command]
-pub fn get_playable_video_path(recording_name: String, config: State<AppConfig>) -> Result<String, String> {
-    let recording_path = config.recordings_path.join(&recording_name);
+pub fn get_playable_video_path(recording_name: String, config: State<AppConfig>) -> Result<PlayableVideo, String> {
+    let recording_path = config.recording_path(&recording_name);
 
     if !recording_path.exists() {
         return Err(format!("Recording '{}' not found", recording_name));
     }
 
+    let path = find_playable_video(&recording_path, &recording_name)
+        .ok_or_else(|| format!("No playable video file found for recording '{}'", recording_name))?;
+    Ok(PlayableVideo {
+        url: video_url(&recording_name),
+        path: path.to_string_lossy().to_string(),
+    })
+}
+
+/// Handler of the video protocol: streams a recording's playable video with
+/// byte-range support so the <video> element can seek
+pub fn handle_video_request<R: Runtime>(
+    ctx: UriSchemeContext<'_, R>,
+    request: Request<Vec<u8>>,
+    responder: UriSchemeResponder,
+) {
+    let app = ctx.app_handle().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let config = app.state::<AppConfig>();
+        responder.respond(video_response(&config, &request));
+    });
+}
+
+fn video_response(config: &AppConfig, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let Some(recording_name) = recording_name_from_uri_path(request.uri().path()) else {
+        return status_response(StatusCode::BAD_REQUEST);
+    };
+    let Some(file) = find_playable_video(&config.recording_path(&recording_name), &recording_name) else {
+        return status_response(StatusCode::NOT_FOUND);
+    };
+    let range = request.headers().get(header::RANGE).and_then(|v| v.to_str().ok());
+
+    match read_chunk(&file, range) {
+        Ok(Some(chunk)) => Response::builder()
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(header::CONTENT_TYPE, content_type(&file))
+            .header(header::ACCEPT_RANGES, "bytes")
+            .header(header::CONTENT_RANGE, chunk.content_range())
+            .header(header::CONTENT_LENGTH, chunk.data.len())
+            .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+            .body(chunk.data)
+            .unwrap_or_else(|_| status_response(StatusCode::INTERNAL_SERVER_ERROR)),
+        Ok(None) => status_response(StatusCode::RANGE_NOT_SATISFIABLE),
+        Err(e) => {
+            log::error!("Failed to stream {}: {}", file.display(), e);
+            status_response(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+fn status_response(status: StatusCode) -> Response<Vec<u8>> {
+    let mut response = Response::new(Vec::new());
+    *response.status_mut() = status;
+    response
+}
+
+/// Find the rendered final video, falling back to the main OBS recording
+pub fn find_playable_video(recording_path: &Path, recording_name: &str) -> Option<PathBuf> {
     // Priority 1: Check for rendered final.mp4 or *_final.mp4
     let render_dir = recording_path.join("blender").join("render");
     if render_dir.exists() {
@@ -21,7 +93,7 @@ pub fn get_playable_video_path(recording_name: String, config: State<AppConfig>)
                 if file_path.is_file() {
                     if let Some(file_name) = file_path.file_name().and_then(|n| n.to_str()) {
                         if file_name == "final.mp4" || file_name.ends_with("_final.mp4") {
-                            return Ok(file_path.to_string_lossy().to_string());
+                            return Some(file_path);
                         }
                     }
                 }
@@ -31,7 +103,7 @@ pub fn get_playable_video_path(recording_name: String, config: State<AppConfig>)
 
     // Priority 2: Look for main OBS recording file (.mkv, .mp4, .avi)
     let video_extensions = ["mkv", "mp4", "avi", "mov"];
-    if let Ok(entries) = std::fs::read_dir(&recording_path) {
+    if let Ok(entries) = std::fs::read_dir(recording_path) {
         let mut video_files = Vec::new();
 
         // Collect all video files
@@ -49,29 +121,58 @@ pub fn get_playable_video_path(recording_name: String, config: State<AppConfig>)
         // First priority: files that match the recording name
         for file_path in &video_files {
             if let Some(file_stem) = file_path.file_stem() {
-                if file_stem == recording_name.as_str() {
-                    return Ok(file_path.to_string_lossy().to_string());
+                if file_stem == recording_name {
+                    return Some(file_path.clone());
                 }
             }
         }
 
         // Second priority: any video file found
         if let Some(first_video) = video_files.first() {
-            return Ok(first_video.to_string_lossy().to_string());
+            return Some(first_video.clone());
         }
     }
 
-    Err(format!("No playable video file found for recording '{}'", recording_name))
+    None
+}
+
+/// Get the configured external player command template, if any
+#[tauri::command]
+pub fn get_external_player_command(settings: State<SettingsStore>) -> Result<Option<String>, String> {
+    Ok(settings.get::<ExternalPlayerSettings>(EXTERNAL_PLAYER_KEY)?.command)
 }
 
-/// Open video file in external system player
+/// Set the external player command template; None or blank restores the OS default
 #[tauri::command]
-pub fn open_video_external(file_path: String) -> Result<(), String> {
+pub fn set_external_player_command(command: Option<String>, settings: State<SettingsStore>) -> Result<(), String> {
+    let command = command.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+    if let Some(template) = &command {
+        PlayerCommand::parse(template)?;
+    }
+    settings.set(EXTERNAL_PLAYER_KEY, &ExternalPlayerSettings { command: command.clone() })?;
+    log::info!("🎬 External player set to {}", command.as_deref().unwrap_or("the system default"));
+    Ok(())
+}
+
+/// Open video file in the configured player, or the system default player
+#[tauri::command]
+pub fn open_video_external(file_path: String, settings: State<SettingsStore>) -> Result<(), String> {
     let path = Path::new(&file_path);
     if !path.exists() {
         return Err(format!("Video file not found: {}", file_path));
     }
 
+    if let Some(template) = settings.get::<ExternalPlayerSettings>(EXTERNAL_PLAYER_KEY)?.command {
+        let player = PlayerCommand::parse(&template)
+            .map_err(|e| format!("Invalid external player command: {}", e))?;
+        println!("🔗 [Rust] Opening video with {}: {}", player.program, file_path);
+        return Command::new(&player.program)
+            .args(player.args_for(path))
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("Failed to start {}: {}", player.program, e));
+    }
+
     println!("🔗 [Rust] Opening video in external player: {}", file_path);
 
     #[cfg(target_os = "linux")]
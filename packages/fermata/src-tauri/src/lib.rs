@@ -1,45 +1,390 @@
-mod models;
-mod services;
-mod commands;
+pub mod models;
+pub mod services;
+pub mod commands;
+pub mod cli;
 
 use commands::recordings::{
-    AppConfig, get_recordings, get_recording_details, get_recordings_by_status,
-    get_recordings_needing_attention, update_recordings_path, get_app_config, delete_recording
+    AppConfig, get_recordings, query_recordings, get_recording_details, get_recording_tree, get_recording_sizes, get_recordings_by_status, get_recordings_calendar,
+    get_attention_items, snooze_recording, ignore_recording, unsnooze_recording, list_snoozed_recordings, update_recordings_path, get_app_config, preview_delete, delete_recording, quarantine_recording,
+    get_cached_recordings, refresh_recordings, refresh_recordings_in_background,
+    start_recordings_watchers, WatcherState, list_trash, restore_recording, empty_trash, get_status_summary_text,
+    import_recording, suggest_name, clone_recording, preview_bulk_delete, execute_bulk_delete
 };
-use commands::operations::{run_next_step, run_specific_step, run_specific_step_with_options, list_animation_presets};
-use commands::rename::rename_recording;
-use commands::video::{get_playable_video_path, open_video_external};
+use commands::operations::{
+    run_next_step, run_specific_step, get_available_steps, get_next_step, get_retry_plan, get_subtitles, run_specific_step_with_options, validate_step_options, preview_step, run_analyze_with_options, get_analyze_options, list_extract_sources, get_extract_options, set_extract_options, list_animation_presets, refresh_animation_presets, revert_step, rebuild_state, checksum_recording, verify_recording, normalize_audio, validate_pipeline_integrity, preflight_disk_space, estimate_outputs, resume_render, finalize_render, post_process_render, render_preview,
+    generate_render_config, setup_blend, run_benchmark, list_benchmarks
+};
+use commands::rename::{bulk_rename, get_rename_history, rename_recording, repair_paths, undo_last_rename};
+use commands::video::{
+    get_playable_video_path, open_video_external, get_external_player_command, set_external_player_command,
+    handle_video_request
+};
+use commands::custom_fields::{
+    get_custom_field_definitions, get_custom_fields, set_custom_field, search_recordings_by_custom_fields
+};
+use commands::render::{
+    get_render_progress, get_render_preview, get_render_settings, set_render_settings, list_render_versions, promote_render, attach_render, list_blend_versions, set_active_blend,
+    list_render_devices, get_render_device, set_render_device, get_render_host, set_render_host
+};
+use commands::uploads::{
+    get_upload_history, get_upload_results, copy_upload_link, list_upload_profiles, save_upload_profile,
+    delete_upload_profile, set_recording_upload_profile, get_upload_bandwidth, set_upload_bandwidth, get_upload_metadata, set_upload_metadata,
+    set_upload_block, clear_upload_block, bulk_update_upload_metadata
+};
+use commands::export::{export_for_editing, export_recording};
+use commands::storage::{
+    get_disk_space, get_reclaimable_artifacts, get_storage_report, find_duplicates, cleanup_recording, cleanup_uploaded, delete_recordings,
+    start_disk_monitor
+};
+use commands::notifications::{get_notifications_enabled, set_notifications_enabled};
+use commands::locale::{get_locale, set_locale, get_message_catalog};
+use commands::observer::{list_active_steps, observer_guard};
+use commands::tray::{
+    confirm_quit, get_queue_paused, get_run_in_background, handle_close_requested, handle_run_event, set_queue_paused, set_run_in_background, start_tray
+};
+use commands::automation::{
+    list_automation_rules, set_automation_rule_enabled, list_step_hooks, save_step_hooks, list_custom_steps, save_custom_steps,
+    list_pipeline_templates, save_pipeline_templates, get_recording_pipeline_template, set_recording_pipeline_template, start_automation_scheduler
+};
+use commands::polling::{get_polling_settings, set_polling_settings, start_recordings_poller, window_focused};
+use commands::migration::{migrate_recording_layout, migrate_recordings};
+#[cfg(feature = "http-api")]
+use commands::http_api::start_http_api;
+use commands::priority::{get_process_priority, set_process_priority};
+use commands::environment::{get_process_environment, set_process_environment};
+use commands::toolchain::{get_toolchain, set_toolchain};
+use commands::app_log::{get_activity_log, get_app_log, open_log_folder};
+use commands::detection::{get_recording_detection, set_recording_detection};
+use commands::recent::{get_recent_recordings, pin_recording, unpin_recording};
+use commands::config_profiles::{list_config_profiles, save_config_profile, delete_config_profile, switch_config_profile, export_settings, import_settings};
+use commands::obs::{get_obs_connection, save_obs_connection, get_obs_status, get_obs_output_path, sync_recordings_path_with_obs, start_obs_listener, ObsState};
+use commands::jobs::{
+    list_jobs, schedule_job, process_backlog, list_scheduled_jobs, reschedule_job, cancel_job, resume_job, pause_all_jobs, resume_all_jobs, list_interrupted_steps, resolve_interrupted_step, get_job_window, set_job_window, get_upload_retry_policy, set_upload_retry_policy, get_stall_detection, set_stall_detection,
+    get_auto_ingest_policy, set_auto_ingest_policy, report_interrupted_work, start_auto_ingest, start_job_worker, AutoIngestState
+};
+use commands::sources::{get_audio_waveform, get_source_offsets, get_timeline_markers};
+use commands::config_sync::{
+    get_config_drift, regenerate_config_preserving_overrides, get_config_history, prune_config_history,
+    read_animation_config, write_animation_config
+};
+use commands::logs::{list_step_logs, read_step_log};
+use commands::health::{diagnose_and_repair, run_health_check};
+use commands::stats::{get_pipeline_stats, get_status_counts, estimate_step_duration, export_report};
+use commands::sessions::{
+    list_sessions, create_session, delete_session, assign_to_session, remove_from_session, get_session_recordings,
+    queue_session
+};
+use commands::files::{open_in_blender, open_recording_folder, reveal_file};
+use services::{
+    BulkDeleteStaging, CurrentLocale, DesktopNotifications, FrameCounter, JobQueue, NotificationSettings, Notifier, PresetCatalog, RecordingsPoller, ScanSnapshotStore, SettingsStore, StatusTracker,
+    APP_LOG_KEEP_FILES, APP_LOG_MAX_BYTES, APP_LOG_NAME, NOTIFICATION_SETTINGS_KEY, VIDEO_PROTOCOL
+};
+use tauri::Manager;
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
   tauri::Builder::default()
-    .manage(AppConfig::default())
-    .invoke_handler(tauri::generate_handler![
+    .manage(FrameCounter::new())
+    .manage(JobQueue::new())
+    .manage(AutoIngestState::default())
+    .manage(ObsState::default())
+    .manage(BulkDeleteStaging::new())
+    .manage(StatusTracker::default())
+    .manage(PresetCatalog::new())
+    .manage(RecordingsPoller::default())
+    .plugin(tauri_plugin_notification::init())
+    .register_asynchronous_uri_scheme_protocol(VIDEO_PROTOCOL, handle_video_request)
+    .invoke_handler(observer_guard(tauri::generate_handler![
       get_recordings,
+      query_recordings,
+      get_cached_recordings,
+      refresh_recordings,
       get_recording_details,
+      get_recording_tree,
+      get_recording_sizes,
+      get_status_summary_text,
       get_recordings_by_status,
-      get_recordings_needing_attention,
+      get_recordings_calendar,
+      get_attention_items,
+      snooze_recording,
+      ignore_recording,
+      unsnooze_recording,
+      list_snoozed_recordings,
       update_recordings_path,
       get_app_config,
+      preview_delete,
       delete_recording,
+      quarantine_recording,
+      import_recording,
+      suggest_name,
+      clone_recording,
+      preview_bulk_delete,
+      execute_bulk_delete,
+      list_trash,
+      restore_recording,
+      empty_trash,
       run_next_step,
       run_specific_step,
+      get_available_steps,
+      get_next_step,
+      get_retry_plan,
+      get_subtitles,
       run_specific_step_with_options,
+      validate_step_options,
+      preview_step,
+      run_analyze_with_options,
+      get_analyze_options,
+      list_extract_sources,
+      get_extract_options,
+      set_extract_options,
       list_animation_presets,
+      refresh_animation_presets,
+      revert_step,
+      rebuild_state,
+      checksum_recording,
+      verify_recording,
+      normalize_audio,
+      validate_pipeline_integrity,
+      preflight_disk_space,
+      estimate_outputs,
+      resume_render,
+      finalize_render,
+      post_process_render,
+      render_preview,
+      generate_render_config,
+      setup_blend,
+      run_benchmark,
+      list_benchmarks,
       rename_recording,
+      bulk_rename,
+      get_rename_history,
+      undo_last_rename,
+      repair_paths,
       get_playable_video_path,
-      open_video_external
-    ])
+      open_video_external,
+      get_external_player_command,
+      set_external_player_command,
+      get_custom_field_definitions,
+      get_custom_fields,
+      set_custom_field,
+      search_recordings_by_custom_fields,
+      get_render_progress,
+      get_render_preview,
+      get_render_settings,
+      set_render_settings,
+      list_render_versions,
+      promote_render,
+      attach_render,
+      list_blend_versions,
+      set_active_blend,
+      list_render_devices,
+      get_render_device,
+      set_render_device,
+      get_render_host,
+      set_render_host,
+      get_upload_history,
+      get_upload_results,
+      copy_upload_link,
+      list_upload_profiles,
+      save_upload_profile,
+      delete_upload_profile,
+      set_recording_upload_profile,
+      get_upload_bandwidth,
+      set_upload_bandwidth,
+      get_upload_metadata,
+      set_upload_metadata,
+      set_upload_block,
+      clear_upload_block,
+      bulk_update_upload_metadata,
+      export_for_editing,
+      export_recording,
+      get_disk_space,
+      get_reclaimable_artifacts,
+      get_storage_report,
+      find_duplicates,
+      cleanup_recording,
+      cleanup_uploaded,
+      delete_recordings,
+      get_notifications_enabled,
+      set_notifications_enabled,
+      get_run_in_background,
+      set_run_in_background,
+      get_queue_paused,
+      set_queue_paused,
+      confirm_quit,
+      window_focused,
+      get_polling_settings,
+      set_polling_settings,
+      get_locale,
+      set_locale,
+      get_message_catalog,
+      list_automation_rules,
+      set_automation_rule_enabled,
+      list_step_hooks,
+      save_step_hooks,
+      list_custom_steps,
+      list_pipeline_templates,
+      save_pipeline_templates,
+      get_recording_pipeline_template,
+      set_recording_pipeline_template,
+      save_custom_steps,
+      migrate_recording_layout,
+      migrate_recordings,
+      list_jobs,
+      schedule_job,
+      process_backlog,
+      list_scheduled_jobs,
+      reschedule_job,
+      cancel_job,
+      resume_job,
+      pause_all_jobs,
+      resume_all_jobs,
+      list_interrupted_steps,
+      resolve_interrupted_step,
+      list_active_steps,
+      get_job_window,
+      set_job_window,
+      get_upload_retry_policy,
+      set_upload_retry_policy,
+      get_stall_detection,
+      set_stall_detection,
+      get_auto_ingest_policy,
+      set_auto_ingest_policy,
+      get_source_offsets,
+      get_audio_waveform,
+      get_timeline_markers,
+      get_config_drift,
+      regenerate_config_preserving_overrides,
+      get_config_history,
+      prune_config_history,
+      read_animation_config,
+      write_animation_config,
+      list_step_logs,
+      read_step_log,
+      run_health_check,
+      diagnose_and_repair,
+      get_pipeline_stats,
+      get_status_counts,
+      estimate_step_duration,
+      export_report,
+      list_sessions,
+      create_session,
+      delete_session,
+      assign_to_session,
+      remove_from_session,
+      get_session_recordings,
+      queue_session,
+      open_recording_folder,
+      open_in_blender,
+      reveal_file,
+      get_obs_connection,
+      save_obs_connection,
+      get_obs_status,
+      get_obs_output_path,
+      sync_recordings_path_with_obs,
+      get_process_priority,
+      set_process_priority,
+      get_process_environment,
+      set_process_environment,
+      get_toolchain,
+      set_toolchain,
+      get_app_log,
+      get_activity_log,
+      open_log_folder,
+      get_recording_detection,
+      set_recording_detection,
+      get_recent_recordings,
+      pin_recording,
+      unpin_recording,
+      list_config_profiles,
+      save_config_profile,
+      delete_config_profile,
+      switch_config_profile,
+      export_settings,
+      import_settings
+    ]))
+    .on_window_event(|window, event| {
+      if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+        handle_close_requested(window, api);
+      }
+    })
     .setup(|app| {
-      if cfg!(debug_assertions) {
-        app.handle().plugin(
-          tauri_plugin_log::Builder::default()
-            .level(log::LevelFilter::Info)
-            .build(),
-        )?;
+      // Release builds log only to the rotating file in the app log dir, which
+      // get_app_log reads; debug builds also log to the terminal
+      let log_file = Target::new(TargetKind::LogDir { file_name: Some(APP_LOG_NAME.to_string()) });
+      let log_targets = if cfg!(debug_assertions) {
+        vec![Target::new(TargetKind::Stdout), log_file]
+      } else {
+        vec![log_file]
+      };
+      app.handle().plugin(
+        tauri_plugin_log::Builder::default()
+          .level(log::LevelFilter::Info)
+          .targets(log_targets)
+          .max_file_size(APP_LOG_MAX_BYTES)
+          .rotation_strategy(RotationStrategy::KeepSome(APP_LOG_KEEP_FILES))
+          .build(),
+      )?;
+
+      let settings_path = app.path().app_config_dir()?.join("settings.json");
+      let settings = SettingsStore::new(settings_path);
+      let notification_settings: NotificationSettings = settings
+        .get(NOTIFICATION_SETTINGS_KEY)
+        .unwrap_or_else(|e| {
+          log::warn!("{}", e);
+          NotificationSettings::default()
+        });
+      app.manage(Notifier::new(
+        Box::new(DesktopNotifications::new(app.handle().clone())),
+        notification_settings.enabled,
+      ));
+      let config = AppConfig::load(&settings);
+      let observer = config.observer;
+      app.manage(CurrentLocale::new(config.locale));
+      app.manage(config);
+      app.manage(settings);
+
+      // Serve the last scan immediately and refresh it in the background
+      let snapshot_path = app.path().app_cache_dir()?.join("last_scan.json");
+      app.manage(ScanSnapshotStore::new(snapshot_path));
+
+      // Scheduled and unfinished jobs survive restarts
+      let jobs_path = app.path().app_data_dir()?.join("jobs.json");
+      match app.state::<JobQueue>().attach_store(jobs_path) {
+        Ok(restored) if restored > 0 => log::info!("🗓️ Restored {} queued jobs", restored),
+        Ok(_) => {}
+        Err(e) => log::warn!("{}", e),
+      }
+      if !observer {
+        report_interrupted_work(app.handle());
       }
+      tauri::async_runtime::spawn(refresh_recordings_in_background(app.handle().clone()));
+
+      let watchers = start_recordings_watchers(app.handle());
+      app.manage(WatcherState::new(watchers));
+      // Covers roots the watchers couldn't watch, such as some network shares
+      start_recordings_poller(app.handle());
+
+      start_disk_monitor(app.handle());
+      // An observer leaves processing to the app it shares the library with
+      if observer {
+        log::info!("👀 Read-only observer mode: jobs and automation are off");
+      } else {
+        start_automation_scheduler(app.handle());
+        start_job_worker(app.handle());
+        start_auto_ingest(app.handle());
+      }
+      start_obs_listener(app.handle());
+      if let Err(e) = start_tray(app.handle()) {
+        log::warn!("Failed to add the tray icon: {}", e);
+      }
+      #[cfg(feature = "http-api")]
+      start_http_api(app.handle());
+
       Ok(())
     })
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .build(tauri::generate_context!())
+    .expect("error while building tauri application")
+    .run(handle_run_event);
 }
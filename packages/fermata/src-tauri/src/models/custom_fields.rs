@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+/// Type of a user-defined recording field
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CustomFieldType {
+    Text,
+    Number,
+    Boolean,
+    List,
+}
+
+/// A custom metadata field declared in the app configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomFieldDefinition {
+    pub name: String,
+    pub field_type: CustomFieldType,
+}
+
+/// Value stored for a custom field on a recording
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum CustomFieldValue {
+    Boolean(bool),
+    Number(f64),
+    List(Vec<String>),
+    Text(String),
+}
+
+impl CustomFieldDefinition {
+    /// Parse a definition list like `client:text,setlist:list,paid:boolean`
+    pub fn parse_list(spec: &str) -> Result<Vec<Self>, String> {
+        let mut definitions: Vec<Self> = Vec::new();
+
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, type_name) = entry.split_once(':').unwrap_or((entry, "text"));
+            let name = name.trim();
+
+            if name.is_empty() {
+                return Err(format!("Custom field without a name: '{}'", entry));
+            }
+
+            let field_type = match type_name.trim().to_lowercase().as_str() {
+                "text" | "string" => CustomFieldType::Text,
+                "number" => CustomFieldType::Number,
+                "boolean" | "bool" => CustomFieldType::Boolean,
+                "list" => CustomFieldType::List,
+                other => return Err(format!("Unknown type '{}' for custom field '{}'", other, name)),
+            };
+
+            if definitions.iter().any(|d| d.name == name) {
+                return Err(format!("Custom field '{}' is defined more than once", name));
+            }
+
+            definitions.push(Self { name: name.to_string(), field_type });
+        }
+
+        Ok(definitions)
+    }
+
+    /// Check that a value matches this field's declared type
+    pub fn validate(&self, value: &CustomFieldValue) -> Result<(), String> {
+        let matches = matches!(
+            (&self.field_type, value),
+            (CustomFieldType::Text, CustomFieldValue::Text(_))
+                | (CustomFieldType::Number, CustomFieldValue::Number(_))
+                | (CustomFieldType::Boolean, CustomFieldValue::Boolean(_))
+                | (CustomFieldType::List, CustomFieldValue::List(_))
+        );
+
+        if matches {
+            Ok(())
+        } else {
+            Err(format!("Field '{}' expects a {:?} value, got {:?}", self.name, self.field_type, value))
+        }
+    }
+}
+
+impl CustomFieldValue {
+    /// Case-insensitive substring match used by recording search
+    pub fn matches_query(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        match self {
+            CustomFieldValue::Text(text) => text.to_lowercase().contains(&query),
+            CustomFieldValue::List(items) => items.iter().any(|i| i.to_lowercase().contains(&query)),
+            CustomFieldValue::Number(n) => n.to_string() == query,
+            CustomFieldValue::Boolean(b) => b.to_string() == query,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_definition_list() {
+        let defs = CustomFieldDefinition::parse_list("client:text, setlist:list,paid:bool,license").unwrap();
+
+        assert_eq!(defs.len(), 4);
+        assert_eq!(defs[1].field_type, CustomFieldType::List);
+        assert_eq!(defs[2].field_type, CustomFieldType::Boolean);
+        assert_eq!(defs[3].field_type, CustomFieldType::Text);
+    }
+
+    #[test]
+    fn test_parse_definition_list_errors() {
+        assert!(CustomFieldDefinition::parse_list("client:date").is_err());
+        assert!(CustomFieldDefinition::parse_list("client,client").is_err());
+        assert!(CustomFieldDefinition::parse_list("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validate_and_match() {
+        let def = CustomFieldDefinition { name: "setlist".to_string(), field_type: CustomFieldType::List };
+        let value = CustomFieldValue::List(vec!["Intro".to_string(), "Finale".to_string()]);
+
+        assert!(def.validate(&value).is_ok());
+        assert!(def.validate(&CustomFieldValue::Text("Intro".to_string())).is_err());
+        assert!(value.matches_query("fin"));
+        assert!(!value.matches_query("encore"));
+    }
+}
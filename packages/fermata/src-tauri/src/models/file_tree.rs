@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// A file or directory of a recording, for the file browser
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FileTreeNode {
+    pub name: String,
+    /// Relative to the recording with "/" separators, "" for the recording itself
+    pub path: String,
+    pub is_dir: bool,
+    /// For directories, the total of all files below
+    pub size_bytes: u64,
+    /// Latest modification below, Unix seconds
+    pub modified: u64,
+    pub file_count: usize,
+    /// Pipeline stage as classified for storage reports, or "mixed"
+    pub stage: String,
+    /// Below max_depth: sizes are counted but the children aren't listed
+    pub truncated: bool,
+    pub children: Vec<FileTreeNode>,
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a recording is in its life, shown as a badge in recording lists
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum Lifecycle {
+    /// Recorded recently and not touched yet
+    New,
+    /// Somewhere in the pipeline, including failed and running steps
+    InProgress,
+    /// Rendered and allowed to be uploaded
+    ReadyToPublish,
+    /// Uploaded recently
+    Published,
+    /// Uploaded long enough ago that intermediate files can go
+    Archivable,
+}
+
+/// Lifecycle badge with the recording's age
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LifecycleBadge {
+    pub stage: Lifecycle,
+    /// Days since the recording directory last changed
+    pub age_days: u64,
+}
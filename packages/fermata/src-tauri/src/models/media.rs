@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Technical details of one video file, as reported by ffprobe
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct MediaInfo {
+    pub duration_secs: Option<f64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fps: Option<f64>,
+    pub video_codec: Option<String>,
+    /// Codec of the first audio stream
+    pub audio_codec: Option<String>,
+    pub audio_tracks: usize,
+}
+
+/// Probed media of a recording, shown in the details view
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RecordingMedia {
+    /// The OBS recording
+    pub main: Option<MediaInfo>,
+    /// The video in blender/render/
+    pub rendered: Option<MediaInfo>,
+}
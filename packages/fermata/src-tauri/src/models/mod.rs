@@ -1,3 +1,21 @@
 pub mod recording;
+pub mod custom_fields;
+pub mod upload_results;
+pub mod upload_metadata;
+pub mod upload_block;
+pub mod snooze;
+pub mod lifecycle;
+pub mod media;
+pub mod file_tree;
+pub mod step_options;
 
 pub use recording::*;
+pub use custom_fields::*;
+pub use upload_results::*;
+pub use upload_metadata::*;
+pub use upload_block::*;
+pub use snooze::*;
+pub use lifecycle::*;
+pub use media::*;
+pub use file_tree::*;
+pub use step_options::*;
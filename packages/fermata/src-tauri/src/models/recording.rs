@@ -1,6 +1,7 @@
+use crate::models::{FileTreeNode, LifecycleBadge, RecordingMedia, UploadBlock};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -10,6 +11,21 @@ pub struct Recording {
     pub status: RecordingStatus,
     pub last_updated: u64, // Unix timestamp in seconds
     pub file_sizes: HashMap<String, u64>,
+    /// Set when the recording must never be published
+    #[serde(default)]
+    pub upload_block: Option<UploadBlock>,
+    /// Recordings root the recording was found in
+    #[serde(default)]
+    pub root: PathBuf,
+    /// Lifecycle badge, filled in by commands that list recordings
+    #[serde(default)]
+    pub lifecycle: Option<LifecycleBadge>,
+    /// Probed video details, filled in by the details command
+    #[serde(default)]
+    pub media: Option<RecordingMedia>,
+    /// File tree, filled in by scans that ask for it
+    #[serde(default)]
+    pub tree: Option<FileTreeNode>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -17,10 +33,47 @@ pub enum RecordingStatus {
     Recorded,       // .mkv exists
     Extracted,      // extracted/ exists
     Analyzed,       // analysis/ exists
+    ConfigGenerated, // animation_config_*.yaml exists, no .blend yet
     SetupRendered,  // blender/*.blend exists (cinemon done)
+    FramesRendered, // blender/render/ holds every frame, not encoded into a video yet
     Rendered,       // blender/render/*.mp4 exists (blender rendering done)
     Uploaded,       // uploads/ exists
     Failed(String),
+    Corrupted(String), // unreadable metadata or video, retrying won't help
+    Analyzing,       // beatrix analysis running
+    SettingUpRender, // cinemon setup running
+    Rendering,       // blender render running
+    Uploading,       // medusa upload running
+    Transcribing,    // whisper transcription running
+    RunningStep(String), // custom step running, by id
+}
+
+impl RecordingStatus {
+    /// Transient status shown while the given step is running
+    pub fn in_progress(step: &NextStep) -> Option<Self> {
+        match step {
+            NextStep::Analyze => Some(RecordingStatus::Analyzing),
+            NextStep::GenerateConfig | NextStep::BlendSetup | NextStep::SetupRender => Some(RecordingStatus::SettingUpRender),
+            NextStep::Render => Some(RecordingStatus::Rendering),
+            NextStep::Upload => Some(RecordingStatus::Uploading),
+            NextStep::Transcribe => Some(RecordingStatus::Transcribing),
+            NextStep::Custom(id) => Some(RecordingStatus::RunningStep(id.clone())),
+            NextStep::Extract | NextStep::Retry => None,
+        }
+    }
+
+    /// Check if a pipeline step is currently running for this recording
+    pub fn is_in_progress(&self) -> bool {
+        matches!(
+            self,
+            RecordingStatus::Analyzing
+                | RecordingStatus::SettingUpRender
+                | RecordingStatus::Rendering
+                | RecordingStatus::Uploading
+                | RecordingStatus::Transcribing
+                | RecordingStatus::RunningStep(_)
+        )
+    }
 }
 
 impl Recording {
@@ -42,6 +95,11 @@ impl Recording {
             status: RecordingStatus::Recorded, // Will be updated by status detection
             last_updated: last_updated.duration_since(SystemTime::UNIX_EPOCH)?.as_secs(),
             file_sizes: HashMap::new(), // Will be populated by file scanner
+            upload_block: None,
+            root: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+            lifecycle: None,
+            media: None,
+            tree: None,
         })
     }
 
@@ -51,10 +109,22 @@ impl Recording {
             RecordingStatus::Recorded => Some(NextStep::Extract),
             RecordingStatus::Extracted => Some(NextStep::Analyze),
             RecordingStatus::Analyzed => Some(NextStep::SetupRender),
+            RecordingStatus::ConfigGenerated => Some(NextStep::BlendSetup),
             RecordingStatus::SetupRendered => Some(NextStep::Render),
+            // Render only encodes the frames then
+            RecordingStatus::FramesRendered => Some(NextStep::Render),
             RecordingStatus::Rendered => Some(NextStep::Upload),
             RecordingStatus::Uploaded => None,
             RecordingStatus::Failed(_) => Some(NextStep::Retry),
+            // Needs a person: fix the files or quarantine the recording
+            RecordingStatus::Corrupted(_) => None,
+            // Nothing can be started while a step is still running
+            RecordingStatus::Analyzing
+            | RecordingStatus::SettingUpRender
+            | RecordingStatus::Rendering
+            | RecordingStatus::Uploading
+            | RecordingStatus::Transcribing
+            | RecordingStatus::RunningStep(_) => None,
         }
     }
 
@@ -64,9 +134,26 @@ impl Recording {
             "extract" => matches!(self.status, RecordingStatus::Recorded | RecordingStatus::Failed(_)),
             "analyze" => matches!(self.status, RecordingStatus::Extracted | RecordingStatus::Failed(_)),
             "setup_render" | "setup-render" => matches!(self.status, RecordingStatus::Analyzed | RecordingStatus::Failed(_)),
-            "render" => matches!(self.status, RecordingStatus::SetupRendered | RecordingStatus::Failed(_)),
-            "upload" => matches!(self.status, RecordingStatus::Rendered | RecordingStatus::Failed(_)),
+            "generate_config" | "generate-config" => matches!(
+                self.status,
+                RecordingStatus::Analyzed | RecordingStatus::ConfigGenerated | RecordingStatus::Failed(_)
+            ),
+            // Also after setup, to rebuild the project from a hand-edited config
+            "blend_setup" | "blend-setup" => matches!(
+                self.status,
+                RecordingStatus::ConfigGenerated | RecordingStatus::SetupRendered | RecordingStatus::Failed(_)
+            ),
+            "render" => matches!(
+                self.status,
+                RecordingStatus::SetupRendered | RecordingStatus::FramesRendered | RecordingStatus::Failed(_)
+            ),
+            // Also again once uploaded, e.g. to another platform; earlier uploads are kept
+            "upload" => matches!(self.status, RecordingStatus::Rendered | RecordingStatus::Uploaded | RecordingStatus::Failed(_)),
             "retry" => matches!(self.status, RecordingStatus::Failed(_)),
+            // Optional, needs only the extracted audio
+            "transcribe" => {
+                !matches!(self.status, RecordingStatus::Recorded | RecordingStatus::Corrupted(_)) && !self.status.is_in_progress()
+            }
             _ => false,
         }
     }
@@ -76,7 +163,11 @@ impl Recording {
         let mut steps = Vec::new();
 
         if let Some(next_step) = self.get_next_step() {
-            steps.push(next_step.to_string());
+            steps.push(next_step.id());
+        }
+
+        if self.can_run_step("transcribe") && !matches!(self.status, RecordingStatus::Failed(_)) {
+            steps.push("transcribe".to_string());
         }
 
         // Add manual step options based on current status
@@ -104,43 +195,84 @@ impl Recording {
 pub enum NextStep {
     Extract,
     Analyze,
+    /// GenerateConfig followed by BlendSetup
     SetupRender,
+    /// Generate the cinemon animation config (YAML)
+    GenerateConfig,
+    /// Build the .blend project from an existing config
+    BlendSetup,
     Render,
     Upload,
+    /// Optional: subtitles from the main audio with whisper
+    Transcribe,
     Retry,
+    /// User-defined step from the settings, by id
+    Custom(String),
 }
 
 impl NextStep {
-    pub fn to_string(&self) -> String {
+    /// Name shown in the UI and in messages, e.g. "Setup Render"
+    pub fn label(&self) -> String {
         match self {
             NextStep::Extract => "Extract".to_string(),
             NextStep::Analyze => "Analyze".to_string(),
             NextStep::SetupRender => "Setup Render".to_string(),
+            NextStep::GenerateConfig => "Generate Config".to_string(),
+            NextStep::BlendSetup => "Blend Setup".to_string(),
             NextStep::Render => "Render".to_string(),
             NextStep::Upload => "Upload".to_string(),
+            NextStep::Transcribe => "Transcribe".to_string(),
             NextStep::Retry => "Retry".to_string(),
+            NextStep::Custom(id) => id.clone(),
+        }
+    }
+
+    /// Name used by the frontend, in settings and in marker files, e.g. "setup_render"
+    pub fn id(&self) -> String {
+        match self {
+            NextStep::Extract => "extract".to_string(),
+            NextStep::Analyze => "analyze".to_string(),
+            NextStep::SetupRender => "setup_render".to_string(),
+            NextStep::GenerateConfig => "generate_config".to_string(),
+            NextStep::BlendSetup => "blend_setup".to_string(),
+            NextStep::Render => "render".to_string(),
+            NextStep::Upload => "upload".to_string(),
+            NextStep::Transcribe => "transcribe".to_string(),
+            NextStep::Retry => "retry".to_string(),
+            NextStep::Custom(id) => id.clone(),
+        }
+    }
+}
+
+impl std::str::FromStr for NextStep {
+    type Err = String;
+
+    /// Parse a step name as used by the frontend and in marker files
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "extract" => Ok(NextStep::Extract),
+            "analyze" => Ok(NextStep::Analyze),
+            "setup_render" | "setup-render" | "setuprender" => Ok(NextStep::SetupRender),
+            "generate_config" | "generate-config" | "generateconfig" => Ok(NextStep::GenerateConfig),
+            "blend_setup" | "blend-setup" | "blendsetup" => Ok(NextStep::BlendSetup),
+            "render" => Ok(NextStep::Render),
+            "upload" => Ok(NextStep::Upload),
+            "transcribe" => Ok(NextStep::Transcribe),
+            "retry" => Ok(NextStep::Retry),
+            _ => Err(format!("Unknown step: {}", s)),
         }
     }
 }
 
 impl std::fmt::Display for NextStep {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            NextStep::Extract => write!(f, "extract"),
-            NextStep::Analyze => write!(f, "analyze"),
-            NextStep::SetupRender => write!(f, "setup_render"),
-            NextStep::Render => write!(f, "render"),
-            NextStep::Upload => write!(f, "upload"),
-            NextStep::Retry => write!(f, "retry"),
-        }
+        f.write_str(&self.id())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::Path;
-
     #[test]
     fn test_recording_creation_from_valid_path() {
         let path = PathBuf::from("/test/stream_20240115_120000");
@@ -148,6 +280,7 @@ mod tests {
 
         assert_eq!(recording.name, "stream_20240115_120000");
         assert_eq!(recording.path, path);
+        assert_eq!(recording.root, PathBuf::from("/test"));
         assert_eq!(recording.status, RecordingStatus::Recorded);
         assert!(recording.file_sizes.is_empty());
     }
@@ -168,6 +301,11 @@ mod tests {
             status: RecordingStatus::Recorded,
             last_updated: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs(),
             file_sizes: HashMap::new(),
+            upload_block: None,
+            root: PathBuf::from("/"),
+            lifecycle: None,
+            media: None,
+            tree: None,
         };
 
         // Test each status transition
@@ -181,6 +319,9 @@ mod tests {
         recording.status = RecordingStatus::Analyzed;
         assert_eq!(recording.get_next_step(), Some(NextStep::SetupRender));
 
+        recording.status = RecordingStatus::ConfigGenerated;
+        assert_eq!(recording.get_next_step(), Some(NextStep::BlendSetup));
+
         recording.status = RecordingStatus::SetupRendered;
         assert_eq!(recording.get_next_step(), Some(NextStep::Render));
 
@@ -192,6 +333,41 @@ mod tests {
 
         recording.status = RecordingStatus::Failed("test error".to_string());
         assert_eq!(recording.get_next_step(), Some(NextStep::Retry));
+
+        recording.status = RecordingStatus::Analyzing;
+        assert_eq!(recording.get_next_step(), None);
+    }
+
+    #[test]
+    fn test_in_progress_statuses_block_steps() {
+        let recording = Recording {
+            name: "test".to_string(),
+            path: PathBuf::from("/test"),
+            status: RecordingStatus::Rendering,
+            last_updated: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs(),
+            file_sizes: HashMap::new(),
+            upload_block: None,
+            root: PathBuf::from("/"),
+            lifecycle: None,
+            media: None,
+            tree: None,
+        };
+
+        assert!(recording.status.is_in_progress());
+        assert!(!recording.can_run_step("render"));
+        assert!(recording.get_available_steps().is_empty());
+        assert_eq!(RecordingStatus::in_progress(&NextStep::Render), Some(RecordingStatus::Rendering));
+        assert_eq!(RecordingStatus::in_progress(&NextStep::Extract), None);
+    }
+
+    #[test]
+    fn test_next_step_from_str() {
+        assert_eq!("analyze".parse::<NextStep>(), Ok(NextStep::Analyze));
+        assert_eq!("setup-render".parse::<NextStep>(), Ok(NextStep::SetupRender));
+        assert_eq!(format!("{}", NextStep::SetupRender).parse::<NextStep>(), Ok(NextStep::SetupRender));
+        assert_eq!("blend-setup".parse::<NextStep>(), Ok(NextStep::BlendSetup));
+        assert_eq!(format!("{}", NextStep::GenerateConfig).parse::<NextStep>(), Ok(NextStep::GenerateConfig));
+        assert!("bogus".parse::<NextStep>().is_err());
     }
 
     #[test]
@@ -202,6 +378,11 @@ mod tests {
             status: RecordingStatus::Extracted,
             last_updated: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs(),
             file_sizes: HashMap::new(),
+            upload_block: None,
+            root: PathBuf::from("/"),
+            lifecycle: None,
+            media: None,
+            tree: None,
         };
 
         // Test valid step for current status
@@ -215,20 +396,29 @@ mod tests {
         assert!(recording.can_run_step("render"));
         assert!(recording.can_run_step("upload"));
         assert!(recording.can_run_step("retry"));
+
+        recording.status = RecordingStatus::Uploaded;
+        assert!(recording.can_run_step("upload"));
+        assert!(!recording.can_run_step("render"));
     }
 
     #[test]
     fn test_get_available_steps() {
-        let mut recording = Recording {
+        let recording = Recording {
             name: "test".to_string(),
             path: PathBuf::from("/test"),
             status: RecordingStatus::Analyzed,
             last_updated: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs(),
             file_sizes: HashMap::new(),
+            upload_block: None,
+            root: PathBuf::from("/"),
+            lifecycle: None,
+            media: None,
+            tree: None,
         };
 
         let steps = recording.get_available_steps();
-        assert!(steps.contains(&"render".to_string())); // Next step
+        assert!(steps.contains(&"setup_render".to_string())); // Next step, by id
         assert!(steps.contains(&"extract".to_string())); // Manual re-run
         assert!(steps.contains(&"analyze".to_string())); // Manual re-run
     }
@@ -246,11 +436,11 @@ mod tests {
 
     #[test]
     fn test_next_step_display() {
-        assert_eq!(NextStep::Extract.to_string(), "Extract");
-        assert_eq!(NextStep::Analyze.to_string(), "Analyze");
-        assert_eq!(NextStep::SetupRender.to_string(), "Setup Render");
-        assert_eq!(NextStep::Render.to_string(), "Render");
-        assert_eq!(NextStep::Upload.to_string(), "Upload");
-        assert_eq!(NextStep::Retry.to_string(), "Retry");
+        assert_eq!(NextStep::Extract.label(), "Extract");
+        assert_eq!(NextStep::Analyze.label(), "Analyze");
+        assert_eq!(NextStep::SetupRender.label(), "Setup Render");
+        assert_eq!(NextStep::Render.label(), "Render");
+        assert_eq!(NextStep::Upload.label(), "Upload");
+        assert_eq!(NextStep::Retry.label(), "Retry");
     }
 }
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Keeps a recording off the attention list and out of automatic processing,
+/// e.g. b-roll that is left half-processed on purpose
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecordingSnooze {
+    /// Unix timestamp in seconds; None ignores the recording until the flag is cleared
+    pub until: Option<u64>,
+    /// Unix timestamp in seconds
+    pub snoozed_at: u64,
+}
+
+impl RecordingSnooze {
+    /// Still in effect at `now`; an expired snooze is left for the next one to replace
+    pub fn is_active(&self, now: u64) -> bool {
+        self.until.map_or(true, |until| now < until)
+    }
+}
@@ -0,0 +1,182 @@
+use crate::models::NextStep;
+use crate::services::{AnalyzeOptions, RenderSettings};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// One invalid field of step options, for the GUI form to show next to it
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FieldError {
+    /// Serde name of the field; empty when the options as a whole are wrong
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: impl Into<String>) -> Self {
+        Self { field: field.to_string(), message: message.into() }
+    }
+}
+
+/// "field: message" pairs of `errors`, for commands that can only return a string
+pub fn describe_field_errors(errors: &[FieldError]) -> String {
+    let described: Vec<String> = errors
+        .iter()
+        .map(|e| match e.field.as_str() {
+            "" => e.message.clone(),
+            field => format!("{}: {}", field, e.message),
+        })
+        .collect();
+    format!("Invalid options - {}", described.join("; "))
+}
+
+/// Options of the setuprender step; config_path skips config generation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SetupRenderOptions {
+    pub preset: String,
+    pub main_audio: Option<String>,
+    /// Existing animation config to build the Blender project from; when set,
+    /// config generation is skipped and the preset is ignored
+    pub config_path: Option<String>,
+}
+
+impl Default for SetupRenderOptions {
+    fn default() -> Self {
+        Self {
+            preset: "beat-switch".to_string(),  // Zachowanie kompatybilności
+            main_audio: None,
+            config_path: None,
+        }
+    }
+}
+
+impl SetupRenderOptions {
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        if self.preset.trim().is_empty() {
+            errors.push(FieldError::new("preset", "Choose an animation preset"));
+        }
+        if self.main_audio.as_deref().is_some_and(|audio| audio.trim().is_empty()) {
+            errors.push(FieldError::new("main_audio", "Leave unset to use the configured main audio"));
+        }
+        if self.config_path.as_deref().is_some_and(|path| path.trim().is_empty()) {
+            errors.push(FieldError::new("config_path", "Leave unset to generate the config from the preset"));
+        }
+        errors
+    }
+}
+
+/// Options of the upload step
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct UploadOptions {
+    /// Upload profile to use instead of the recording's or the default one
+    pub profile: Option<String>,
+}
+
+impl UploadOptions {
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        match self.profile.as_deref() {
+            Some(profile) if profile.trim().is_empty() => vec![FieldError::new("profile", "Leave unset to use the default profile")],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Options run_specific_step_with_options got for a step, checked before anything runs
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepOptions {
+    Analyze(AnalyzeOptions),
+    SetupRender(SetupRenderOptions),
+    Render(RenderSettings),
+    Upload(UploadOptions),
+    /// No options; the step runs with what the recording has saved
+    None,
+}
+
+impl StepOptions {
+    /// Options for `step` from the frontend, with every unknown field, wrong type and
+    /// out-of-range value reported. Render setup without options uses the default preset.
+    pub fn parse(step: &str, options: &Value) -> Result<Self, Vec<FieldError>> {
+        let step = step.parse::<NextStep>().ok();
+        if options.is_null() {
+            return Ok(match step {
+                Some(NextStep::SetupRender) => StepOptions::SetupRender(SetupRenderOptions::default()),
+                _ => StepOptions::None,
+            });
+        }
+        match step {
+            Some(NextStep::Analyze) => checked(options, AnalyzeOptions::field_errors).map(StepOptions::Analyze),
+            Some(NextStep::SetupRender) => checked(options, SetupRenderOptions::field_errors).map(StepOptions::SetupRender),
+            Some(NextStep::Render) => checked(options, RenderSettings::field_errors).map(StepOptions::Render),
+            Some(NextStep::Upload) => checked(options, UploadOptions::field_errors).map(StepOptions::Upload),
+            _ => Err(vec![FieldError::new("", "This step takes no options")]),
+        }
+    }
+}
+
+/// Parse `options` field by field, so each bad field gets its own error, then check the values
+fn checked<T>(options: &Value, field_errors: fn(&T) -> Vec<FieldError>) -> Result<T, Vec<FieldError>>
+where
+    T: DeserializeOwned + Serialize + Default,
+{
+    let Value::Object(fields) = options else {
+        return Err(vec![FieldError::new("", "Options must be an object")]);
+    };
+    let known = match serde_json::to_value(T::default()) {
+        Ok(Value::Object(known)) => known,
+        _ => Map::new(),
+    };
+    let mut errors = Vec::new();
+    let mut valid = Map::new();
+    for (field, value) in fields {
+        if !known.contains_key(field) {
+            errors.push(FieldError::new(field, "Unknown field"));
+            continue;
+        }
+        let single: Map<String, Value> = [(field.clone(), value.clone())].into_iter().collect();
+        match serde_json::from_value::<T>(Value::Object(single)) {
+            Ok(_) => {
+                valid.insert(field.clone(), value.clone());
+            }
+            Err(e) => errors.push(FieldError::new(field, e.to_string())),
+        }
+    }
+    // Values are checked on the fields that parsed, without repeating fields already reported
+    let parsed: T = serde_json::from_value(Value::Object(valid)).map_err(|e| vec![FieldError::new("", e.to_string())])?;
+    for error in field_errors(&parsed) {
+        if !errors.iter().any(|e| e.field == error.field) {
+            errors.push(error);
+        }
+    }
+    if errors.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_reports_each_bad_field() {
+        let errors = StepOptions::parse("analyze", &json!({"tempo_min": "fast", "tempo_max": 500.0, "tempo": 120})).unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["tempo", "tempo_min", "tempo_max"]);
+
+        let errors = StepOptions::parse("render", &json!({"width": 1921, "height": 1080, "fps": 0})).unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["width", "fps"]);
+
+        let options = StepOptions::parse("setuprender", &json!({"preset": "minimal", "main_audio": "Mic.m4a"})).unwrap();
+        assert!(matches!(options, StepOptions::SetupRender(SetupRenderOptions { ref preset, .. }) if preset == "minimal"));
+        assert_eq!(StepOptions::parse("setup_render", &Value::Null), Ok(StepOptions::SetupRender(SetupRenderOptions::default())));
+        assert_eq!(StepOptions::parse("render", &Value::Null), Ok(StepOptions::None));
+        assert!(StepOptions::parse("extract", &json!({"profile": "a"})).is_err());
+        assert!(describe_field_errors(&errors).contains("fps: Frame rate 0 is outside 1 - 240"));
+    }
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// "Do not upload" flag: hard-blocks the Upload step, e.g. for copyrighted
+/// music or unreleased material
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UploadBlock {
+    pub reason: String,
+    /// Unix timestamp in seconds
+    pub blocked_at: u64,
+}
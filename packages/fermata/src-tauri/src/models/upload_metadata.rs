@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+/// Privacy values accepted by `medusa upload --privacy`
+pub const UPLOAD_PRIVACY_OPTIONS: [&str; 3] = ["private", "unlisted", "public"];
+
+/// YouTube rejects longer titles
+const MAX_TITLE_LENGTH: usize = 100;
+
+/// Title, description, tags and privacy used for a recording's upload
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UploadMetadata {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub privacy: Option<String>,
+}
+
+impl UploadMetadata {
+    /// Metadata prefilled from the recording, used until the user edits it
+    pub fn for_recording(recording_name: &str) -> Self {
+        Self {
+            title: Some(recording_name.to_string()),
+            ..Self::default()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(title) = &self.title {
+            if title.trim().is_empty() {
+                return Err("Upload title cannot be empty".to_string());
+            }
+            if title.chars().count() > MAX_TITLE_LENGTH {
+                return Err(format!("Upload title is longer than {} characters", MAX_TITLE_LENGTH));
+            }
+        }
+
+        if let Some(privacy) = &self.privacy {
+            if !UPLOAD_PRIVACY_OPTIONS.contains(&privacy.as_str()) {
+                return Err(format!(
+                    "Invalid privacy '{}', expected one of: {}",
+                    privacy,
+                    UPLOAD_PRIVACY_OPTIONS.join(", ")
+                ));
+            }
+        }
+
+        if self.tags.iter().any(|tag| tag.contains(',')) {
+            return Err("Tags cannot contain commas".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Replace every occurrence of `find` in the title and description; returns the number replaced
+    pub fn replace_text(&mut self, find: &str, replace: &str) -> usize {
+        if find.is_empty() {
+            return 0;
+        }
+
+        let mut count = 0;
+        for text in [&mut self.title, &mut self.description].into_iter().flatten() {
+            let matches = text.matches(find).count();
+            if matches > 0 {
+                *text = text.replace(find, replace);
+                count += matches;
+            }
+        }
+        count
+    }
+
+    /// Arguments for `medusa upload`; unset values keep medusa's own defaults
+    pub fn medusa_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(title) = &self.title {
+            args.extend(["--title".to_string(), title.clone()]);
+        }
+        if let Some(description) = &self.description {
+            args.extend(["--description".to_string(), description.clone()]);
+        }
+        if let Some(privacy) = &self.privacy {
+            args.extend(["--privacy".to_string(), privacy.clone()]);
+        }
+
+        let tags: Vec<&str> = self.tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()).collect();
+        if !tags.is_empty() {
+            args.extend(["--tags".to_string(), tags.join(",")]);
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_medusa_args() {
+        let metadata = UploadMetadata {
+            title: Some("Live at Setka".to_string()),
+            description: None,
+            tags: vec!["live".to_string(), " ".to_string(), "jazz".to_string()],
+            privacy: Some("unlisted".to_string()),
+        };
+
+        assert_eq!(
+            metadata.medusa_args(),
+            vec!["--title", "Live at Setka", "--privacy", "unlisted", "--tags", "live,jazz"]
+        );
+    }
+
+    #[test]
+    fn test_replace_text() {
+        let mut metadata = UploadMetadata {
+            title: Some("Live at Setka feat. Acme Sponsr".to_string()),
+            description: Some("Thanks Acme Sponsr! #setka2023 #live".to_string()),
+            tags: vec!["Acme Sponsr".to_string()],
+            privacy: None,
+        };
+
+        assert_eq!(metadata.replace_text("Acme Sponsr", "Acme Sponsor"), 2);
+        assert_eq!(metadata.title.as_deref(), Some("Live at Setka feat. Acme Sponsor"));
+        assert_eq!(metadata.description.as_deref(), Some("Thanks Acme Sponsor! #setka2023 #live"));
+        assert_eq!(metadata.tags, vec!["Acme Sponsr"]);
+        assert_eq!(metadata.replace_text("", "x"), 0);
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_privacy_and_comma_tags() {
+        let mut metadata = UploadMetadata::for_recording("rec");
+        assert!(metadata.validate().is_ok());
+
+        metadata.privacy = Some("friends".to_string());
+        assert!(metadata.validate().unwrap_err().contains("Invalid privacy"));
+
+        metadata.privacy = None;
+        metadata.tags = vec!["a,b".to_string()];
+        assert!(metadata.validate().is_err());
+    }
+}
@@ -0,0 +1,366 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// One upload to one platform, normalized across all known file versions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UploadEntry {
+    pub platform: String,
+    #[serde(default, alias = "video_id")]
+    pub upload_id: Option<String>,
+    #[serde(default = "default_success")]
+    pub success: bool,
+    #[serde(default, alias = "url", alias = "video_url")]
+    pub media_url: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default, alias = "uploaded_at")]
+    pub timestamp: Option<String>,
+    /// Visibility on the platform (public, unlisted, private)
+    #[serde(default, alias = "privacy_status")]
+    pub publish_status: Option<String>,
+    /// Fields this version of fermata does not know about, kept verbatim
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+fn default_success() -> bool {
+    true
+}
+
+impl UploadEntry {
+    /// Link to the published media, derived from the upload id for YouTube when no URL was stored
+    pub fn link(&self) -> Option<String> {
+        if let Some(url) = &self.media_url {
+            return Some(url.clone());
+        }
+        match (self.platform.to_lowercase().as_str(), &self.upload_id) {
+            ("youtube", Some(id)) if self.success => Some(format!("https://youtu.be/{}", id)),
+            _ => None,
+        }
+    }
+
+    /// Medusa keeps platform details in a nested metadata object; lift the ones we show.
+    /// Per-platform results may report `"status": "failed"` instead of a success flag.
+    fn normalize(mut self) -> Self {
+        if let Some(status) = self.extra.get("status").and_then(Value::as_str) {
+            if matches!(status, "failed" | "error") {
+                self.success = false;
+            }
+        }
+        if self.publish_status.is_none() {
+            self.publish_status = self
+                .extra
+                .get("metadata")
+                .and_then(|m| m.get("privacy_status"))
+                .and_then(Value::as_str)
+                .map(str::to_string);
+        }
+        self
+    }
+}
+
+/// Results keyed by platform, as in docs/DATA_FLOW_SPECIFICATION.md and medusa's
+/// TaskResult.to_dict()
+#[derive(Debug, Clone, Deserialize)]
+struct TaskResults {
+    #[serde(default)]
+    task_id: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    failed_platform: Option<String>,
+    results: Map<String, Value>,
+    #[serde(default)]
+    updated_at: Option<String>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum UploadResultsFile {
+    Task(TaskResults),
+    /// A single serialized medusa UploadResult
+    Single(UploadEntry),
+}
+
+/// Parsed `uploads/upload_results.json`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct UploadResults {
+    pub entries: Vec<UploadEntry>,
+    /// Top-level fields not covered by the model
+    pub extra: Map<String, Value>,
+}
+
+impl UploadResults {
+    /// Parse any known version of the results file
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let file: UploadResultsFile = serde_json::from_str(content)
+            .map_err(|e| format!("Unrecognized upload results format: {}", e))?;
+
+        let mut results = match file {
+            UploadResultsFile::Task(task) => Self::from_task(task),
+            UploadResultsFile::Single(entry) => Self {
+                entries: vec![entry],
+                extra: Map::new(),
+            },
+        };
+
+        results.entries = results.entries.into_iter().map(UploadEntry::normalize).collect();
+        Ok(results)
+    }
+
+    fn from_task(task: TaskResults) -> Self {
+        let mut entries: Vec<UploadEntry> = task
+            .results
+            .into_iter()
+            .map(|(platform, value)| {
+                let mut object = match value {
+                    Value::Object(object) => object,
+                    other => Map::from_iter([("value".to_string(), other)]),
+                };
+                object.entry("platform").or_insert(Value::String(platform.clone()));
+
+                serde_json::from_value(Value::Object(object.clone())).unwrap_or(UploadEntry {
+                    platform,
+                    upload_id: None,
+                    success: true,
+                    media_url: None,
+                    error: None,
+                    timestamp: None,
+                    publish_status: None,
+                    extra: object,
+                })
+            })
+            .map(|mut entry| {
+                if entry.timestamp.is_none() {
+                    entry.timestamp = task.updated_at.clone();
+                }
+                entry
+            })
+            .collect();
+
+        // A failed task may not have a per-platform result at all
+        if let Some(platform) = task.failed_platform {
+            if !entries.iter().any(|e| e.platform == platform) {
+                entries.push(UploadEntry {
+                    platform,
+                    upload_id: None,
+                    success: false,
+                    media_url: None,
+                    error: task.error.clone().or_else(|| Some("Upload failed".to_string())),
+                    timestamp: task.updated_at.clone(),
+                    publish_status: None,
+                    extra: Map::new(),
+                });
+            }
+        }
+
+        let mut extra = task.extra;
+        if let Some(task_id) = task.task_id {
+            extra.insert("task_id".to_string(), Value::String(task_id));
+        }
+        if let Some(status) = task.status {
+            extra.insert("status".to_string(), Value::String(status));
+        }
+        if let Some(error) = task.error {
+            extra.insert("error".to_string(), Value::String(error));
+        }
+        if let Some(updated_at) = task.updated_at {
+            extra.insert("updated_at".to_string(), Value::String(updated_at));
+        }
+
+        Self { entries, extra }
+    }
+
+    /// True if at least one platform upload succeeded
+    pub fn any_successful(&self) -> bool {
+        self.entries.iter().any(|e| e.success)
+    }
+
+    /// True if uploads were recorded and every one of them failed; results without
+    /// entries say nothing about the upload
+    pub fn all_failed(&self) -> bool {
+        !self.entries.is_empty() && !self.any_successful()
+    }
+
+    /// When the latest successful upload happened, as a Unix timestamp in seconds:
+    /// the entries' RFC3339 timestamps, else the documented format's `upload_time`
+    pub fn uploaded_at(&self) -> Option<u64> {
+        let latest = self
+            .entries
+            .iter()
+            .filter(|e| e.success)
+            .filter_map(|e| chrono::DateTime::parse_from_rfc3339(e.timestamp.as_deref()?).ok())
+            .map(|t| t.timestamp())
+            .max();
+        latest
+            .and_then(|t| u64::try_from(t).ok())
+            .or_else(|| self.extra.get("upload_time").and_then(Value::as_f64).map(|t| t as u64))
+    }
+
+    /// First error reported by a failed upload
+    pub fn first_error(&self) -> Option<String> {
+        self.entries
+            .iter()
+            .find(|e| !e.success)
+            .map(|e| format!("{}: {}", e.platform, e.error.clone().unwrap_or_else(|| "upload failed".to_string())))
+    }
+
+    /// Link of the first successful upload, optionally restricted to one platform
+    pub fn link(&self, platform: Option<&str>) -> Option<String> {
+        self.entries
+            .iter()
+            .filter(|e| e.success)
+            .filter(|e| platform.map_or(true, |p| e.platform.eq_ignore_ascii_case(p)))
+            .find_map(UploadEntry::link)
+    }
+
+    /// Earlier entries followed by the ones of a later upload, skipping entries the
+    /// later file repeats; new entries record `profile` unless they name one
+    pub fn merged_with(mut self, latest: UploadResults, profile: &str) -> Self {
+        for mut entry in latest.entries {
+            if self.entries.contains(&entry) {
+                continue;
+            }
+            entry.extra.entry("profile").or_insert_with(|| Value::String(profile.to_string()));
+            self.entries.push(entry);
+        }
+        self.extra.extend(latest.extra);
+        self
+    }
+
+    /// Serialize with results keyed by platform, unknown top-level fields included.
+    /// Further uploads to a platform get numbered keys; each entry names its platform.
+    pub fn to_json(&self) -> Result<String, String> {
+        let mut results = Map::new();
+        for entry in &self.entries {
+            let mut key = entry.platform.clone();
+            let mut n = 1;
+            while results.contains_key(&key) {
+                n += 1;
+                key = format!("{}_{}", entry.platform, n);
+            }
+            let value = serde_json::to_value(entry).map_err(|e| format!("Failed to serialize upload: {}", e))?;
+            results.insert(key, value);
+        }
+        let mut file = self.extra.clone();
+        file.insert("results".to_string(), Value::Object(results));
+        serde_json::to_string_pretty(&file).map_err(|e| format!("Failed to serialize upload results: {}", e))
+    }
+
+    /// Entries ordered oldest first, for an upload history view
+    pub fn history(&self) -> Vec<UploadEntry> {
+        let mut entries = self.entries.clone();
+        entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_documented_format() {
+        let content = r#"{
+            "version": "1.0",
+            "upload_time": 1642427000.123,
+            "results": {
+                "youtube": {"status": "success", "video_id": "dQw4w9WgXcQ", "url": "https://youtube.com/watch?v=dQw4w9WgXcQ"},
+                "facebook": {"status": "failed", "post_id": "123"}
+            }
+        }"#;
+
+        let results = UploadResults::parse(content).unwrap();
+        assert_eq!(results.extra["version"], "1.0");
+        let youtube = results.entries.iter().find(|e| e.platform == "youtube").unwrap();
+        assert_eq!(youtube.upload_id.as_deref(), Some("dQw4w9WgXcQ"));
+        assert_eq!(youtube.media_url.as_deref(), Some("https://youtube.com/watch?v=dQw4w9WgXcQ"));
+        assert!(!results.entries.iter().find(|e| e.platform == "facebook").unwrap().success);
+        assert!(results.any_successful());
+        assert_eq!(results.uploaded_at(), Some(1642427000));
+    }
+
+    #[test]
+    fn test_uploaded_at_is_latest_successful_upload() {
+        let content = r#"{"results": {
+            "youtube": {"success": true, "timestamp": "2024-01-10T12:00:00+00:00"},
+            "vimeo": {"success": true, "timestamp": "2024-01-12T12:00:00+00:00"},
+            "facebook": {"success": false, "timestamp": "2024-02-01T12:00:00+00:00"}
+        }}"#;
+        let results = UploadResults::parse(content).unwrap();
+        assert_eq!(results.uploaded_at(), Some(1705060800));
+    }
+
+    #[test]
+    fn test_empty_results_are_not_failures() {
+        let results = UploadResults::parse(r#"{"results": {}}"#).unwrap();
+        assert!(!results.any_successful());
+        assert!(!results.all_failed());
+    }
+
+    #[test]
+    fn test_parse_task_result() {
+        let content = r#"{
+            "task_id": "t1",
+            "status": "failed",
+            "error": "quota exceeded",
+            "failed_platform": "vimeo",
+            "results": {"youtube": {"upload_id": "xyz", "media_url": "https://youtu.be/xyz"}},
+            "created_at": "2024-01-15T12:00:00+00:00",
+            "updated_at": "2024-01-15T12:05:00+00:00"
+        }"#;
+
+        let results = UploadResults::parse(content).unwrap();
+        assert_eq!(results.entries.len(), 2);
+        assert_eq!(results.entries[0].platform, "youtube");
+        assert_eq!(results.entries[0].timestamp.as_deref(), Some("2024-01-15T12:05:00+00:00"));
+        assert_eq!(results.first_error(), Some("vimeo: quota exceeded".to_string()));
+    }
+
+    #[test]
+    fn test_parse_single_upload_result() {
+        let content = r#"{"platform": "youtube", "upload_id": "abc", "success": false, "error": "auth"}"#;
+
+        let results = UploadResults::parse(content).unwrap();
+        assert!(results.all_failed());
+    }
+
+    #[test]
+    fn test_publish_status_and_youtube_link_from_metadata() {
+        let content = r#"{
+            "platform": "youtube",
+            "upload_id": "abc123",
+            "metadata": {"privacy_status": "unlisted"}
+        }"#;
+
+        let results = UploadResults::parse(content).unwrap();
+        assert_eq!(results.entries[0].publish_status.as_deref(), Some("unlisted"));
+        assert_eq!(results.link(Some("YouTube")).as_deref(), Some("https://youtu.be/abc123"));
+        assert_eq!(results.link(Some("vimeo")), None);
+    }
+
+    #[test]
+    fn test_merge_keeps_earlier_uploads() {
+        let earlier = UploadResults::parse(r#"{"platform": "youtube", "upload_id": "abc"}"#).unwrap();
+        let latest = UploadResults::parse(r#"{"platform": "youtube", "upload_id": "def", "timestamp": "2024-02-01"}"#).unwrap();
+
+        let merged = earlier.merged_with(latest, "youtube-unlisted");
+        assert_eq!(merged.entries.len(), 2);
+        assert_eq!(merged.entries[1].extra["profile"], "youtube-unlisted");
+        assert!(!merged.entries[0].extra.contains_key("profile"));
+
+        let reparsed = UploadResults::parse(&merged.to_json().unwrap()).unwrap();
+        assert_eq!(reparsed.history(), merged.history());
+        assert_eq!(reparsed.link(None).as_deref(), Some("https://youtu.be/abc"));
+    }
+
+    #[test]
+    fn test_parse_unknown_format_fails() {
+        assert!(UploadResults::parse("{}").is_err());
+        assert!(UploadResults::parse("not json").is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use crate::services::machine_name;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Append-only log of the commands that changed the library, one JSON entry per line.
+/// Kept in the recordings root so every app sharing the library writes to the same log.
+pub const ACTIVITY_LOG_FILE: &str = ".fermata-activity.jsonl";
+
+/// Entries get_activity_log returns unless asked for more
+pub const DEFAULT_ACTIVITY_LIMIT: usize = 500;
+
+/// Arguments whose values are never written to the log
+const SECRET_ARGUMENTS: [&str; 3] = ["password", "token", "secret"];
+
+/// A command that was called to change the library or the app's setup
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ActivityEntry {
+    /// Unix timestamp in seconds
+    pub at: u64,
+    /// Machine of the app that called the command, see machine_name
+    pub host: String,
+    /// "app", or "http-api" for commands called over the HTTP API
+    pub source: String,
+    pub command: String,
+    /// As sent by the UI, with secrets redacted
+    pub arguments: Value,
+    /// Why the command was refused; the outcome of commands that ran is in the app
+    /// log and, for steps, the step logs
+    #[serde(default)]
+    pub refused: Option<String>,
+}
+
+/// Narrows get_activity_log; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ActivityFilter {
+    pub command: Option<String>,
+    /// Entries with this recording name anywhere in their arguments
+    pub recording: Option<String>,
+    pub host: Option<String>,
+    /// Unix timestamp in seconds
+    pub since: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+impl ActivityEntry {
+    pub fn new(source: &str, command: &str, arguments: Value, refused: Option<String>) -> Self {
+        Self {
+            at: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+            host: machine_name().to_string(),
+            source: source.to_string(),
+            command: command.to_string(),
+            arguments: redact(arguments),
+            refused,
+        }
+    }
+
+    fn matches(&self, filter: &ActivityFilter) -> bool {
+        filter.command.as_ref().map_or(true, |command| *command == self.command)
+            && filter.host.as_ref().map_or(true, |host| *host == self.host)
+            && filter.since.map_or(true, |since| self.at >= since)
+            && filter.recording.as_ref().map_or(true, |recording| mentions(&self.arguments, recording))
+    }
+}
+
+pub struct ActivityLog;
+
+impl ActivityLog {
+    pub fn append(root: &Path, entry: &ActivityEntry) -> Result<(), String> {
+        let path = root.join(ACTIVITY_LOG_FILE);
+        let mut line = serde_json::to_string(entry).map_err(|e| format!("Failed to serialize activity: {}", e))?;
+        line.push('\n');
+        // One write per entry, so lines of apps appending at the same time don't interleave
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Matching entries, newest first. Lines that can't be read are skipped.
+    pub fn read(root: &Path, filter: &ActivityFilter) -> Result<Vec<ActivityEntry>, String> {
+        let path = root.join(ACTIVITY_LOG_FILE);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        Ok(content
+            .lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str::<ActivityEntry>(line).ok())
+            .filter(|entry| entry.matches(filter))
+            .take(filter.limit.unwrap_or(DEFAULT_ACTIVITY_LIMIT))
+            .collect())
+    }
+}
+
+fn redact(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let secret = SECRET_ARGUMENTS.iter().any(|s| key.to_lowercase().contains(s));
+                    (key, if secret { Value::String("***".to_string()) } else { redact(value) })
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact).collect()),
+        value => value,
+    }
+}
+
+fn mentions(value: &Value, text: &str) -> bool {
+    match value {
+        Value::String(s) => s == text,
+        Value::Array(items) => items.iter().any(|item| mentions(item, text)),
+        Value::Object(map) => map.values().any(|item| mentions(item, text)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_log_keeps_every_entry_and_filters() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        ActivityLog::append(root, &ActivityEntry::new("app", "delete_recording", json!({"recordingName": "gig"}), None)).unwrap();
+        ActivityLog::append(root, &ActivityEntry::new("http-api", "bulk_rename", json!({"names": ["jam", "gig"]}), None)).unwrap();
+        let obs = json!({"connection": {"host": "localhost", "password": "hunter2"}});
+        ActivityLog::append(root, &ActivityEntry::new("app", "save_obs_connection", obs, Some("observer mode".to_string()))).unwrap();
+
+        let all = ActivityLog::read(root, &ActivityFilter::default()).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].command, "save_obs_connection");
+        assert_eq!(all[0].arguments["connection"]["password"], "***");
+
+        let gig = ActivityLog::read(root, &ActivityFilter { recording: Some("gig".to_string()), ..ActivityFilter::default() }).unwrap();
+        let commands: Vec<&str> = gig.iter().map(|entry| entry.command.as_str()).collect();
+        assert_eq!(commands, vec!["bulk_rename", "delete_recording"]);
+        let latest = ActivityLog::read(root, &ActivityFilter { limit: Some(1), ..ActivityFilter::default() }).unwrap();
+        assert_eq!(latest, all[..1]);
+    }
+}
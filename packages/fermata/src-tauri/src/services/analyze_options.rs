@@ -0,0 +1,140 @@
+use crate::models::FieldError;
+use crate::services::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Beatrix options of a recording, reused by every later analyze run
+pub const ANALYZE_OPTIONS_FILE: &str = ".fermata/analyze_options.json";
+
+const MIN_TEMPO: f64 = 20.0;
+const MAX_TEMPO: f64 = 300.0;
+
+/// Beatrix analysis overrides; unset fields keep beatrix's defaults
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AnalyzeOptions {
+    /// Tempo search range in BPM
+    pub tempo_min: Option<f64>,
+    pub tempo_max: Option<f64>,
+    /// 0.0 - 1.0, higher detects more onsets
+    pub onset_sensitivity: Option<f64>,
+    /// Named beatrix analysis profile, e.g. "speech"
+    pub profile: Option<String>,
+}
+
+impl AnalyzeOptions {
+    /// Options of a recording, the defaults when none were saved
+    pub fn load(recording_path: &Path) -> Self {
+        fs::read_to_string(recording_path.join(ANALYZE_OPTIONS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, recording_path: &Path) -> Result<(), String> {
+        self.validate()?;
+        let path = recording_path.join(ANALYZE_OPTIONS_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize analyze options: {}", e))?;
+        write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self.field_errors().into_iter().next() {
+            Some(error) => Err(error.message),
+            None => Ok(()),
+        }
+    }
+
+    /// Every out-of-range option, by field
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        for (field, tempo) in [("tempo_min", self.tempo_min), ("tempo_max", self.tempo_max)] {
+            if let Some(tempo) = tempo.filter(|tempo| !(MIN_TEMPO..=MAX_TEMPO).contains(tempo)) {
+                errors.push(FieldError::new(field, format!("Tempo {} BPM is outside {} - {}", tempo, MIN_TEMPO, MAX_TEMPO)));
+            }
+        }
+        if let (Some(min), Some(max), true) = (self.tempo_min, self.tempo_max, errors.is_empty()) {
+            if min >= max {
+                errors.push(FieldError::new("tempo_max", format!("Tempo range {} - {} BPM is empty", min, max)));
+            }
+        }
+        if let Some(sensitivity) = self.onset_sensitivity {
+            if !(0.0..=1.0).contains(&sensitivity) {
+                errors.push(FieldError::new("onset_sensitivity", format!("Onset sensitivity {} is outside 0 - 1", sensitivity)));
+            }
+        }
+        if let Some(profile) = &self.profile {
+            let valid = !profile.is_empty()
+                && profile.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                errors.push(FieldError::new("profile", format!("Invalid analysis profile '{}'", profile)));
+            }
+        }
+        errors
+    }
+
+    /// Beatrix arguments for the overridden options only
+    pub fn cli_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        let flags = [
+            ("--tempo-min", self.tempo_min),
+            ("--tempo-max", self.tempo_max),
+            ("--onset-sensitivity", self.onset_sensitivity),
+        ];
+        for (flag, value) in flags {
+            if let Some(value) = value {
+                args.push(flag.to_string());
+                args.push(value.to_string());
+            }
+        }
+        if let Some(profile) = &self.profile {
+            args.push("--profile".to_string());
+            args.push(profile.clone());
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_and_args() {
+        assert!(AnalyzeOptions::default().validate().is_ok());
+        assert!(AnalyzeOptions::default().cli_args().is_empty());
+
+        let options = AnalyzeOptions {
+            tempo_min: Some(80.0),
+            tempo_max: Some(140.5),
+            onset_sensitivity: Some(0.7),
+            profile: Some("speech".to_string()),
+        };
+        assert!(options.validate().is_ok());
+        assert_eq!(
+            options.cli_args(),
+            ["--tempo-min", "80", "--tempo-max", "140.5", "--onset-sensitivity", "0.7", "--profile", "speech"]
+        );
+
+        assert!(AnalyzeOptions { tempo_min: Some(150.0), ..options.clone() }.validate().is_err());
+        assert!(AnalyzeOptions { tempo_max: Some(500.0), ..options.clone() }.validate().is_err());
+        assert!(AnalyzeOptions { onset_sensitivity: Some(1.5), ..options.clone() }.validate().is_err());
+        assert!(AnalyzeOptions { profile: Some("--debug x".to_string()), ..options }.validate().is_err());
+    }
+
+    #[test]
+    fn test_options_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(AnalyzeOptions::load(temp_dir.path()), AnalyzeOptions::default());
+
+        let options = AnalyzeOptions { onset_sensitivity: Some(0.4), ..Default::default() };
+        options.save(temp_dir.path()).unwrap();
+        assert_eq!(AnalyzeOptions::load(temp_dir.path()), options);
+    }
+}
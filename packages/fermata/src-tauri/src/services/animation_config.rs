@@ -0,0 +1,199 @@
+use serde_yaml::{Mapping, Value};
+
+/// Top-level sections cinemon reads; the first three are required
+const REQUIRED_SECTIONS: [&str; 3] = ["project", "layout", "strip_animations"];
+const OPTIONAL_SECTIONS: [&str; 1] = ["audio_analysis"];
+
+/// Same lists as setka_common YAMLConfigLoader
+const LAYOUT_TYPES: [&str; 7] = ["random", "grid", "center", "fill", "main-pip", "cascade", "manual"];
+const ANIMATION_TYPES: [&str; 10] = [
+    "scale",
+    "shake",
+    "rotation",
+    "jitter",
+    "brightness_flicker",
+    "black_white",
+    "film_grain",
+    "vintage_color",
+    "visibility",
+    "pip_switch",
+];
+const TRIGGERS: [&str; 7] = ["bass", "beat", "energy_peaks", "one_time", "continuous", "sections", "treble"];
+
+/// Parse a cinemon animation config and check the keys cinemon knows about, so
+/// a config edited in the GUI fails here instead of during blend setup.
+/// Returns every problem found, not just the first.
+pub fn validate_animation_config(yaml: &str) -> Result<Value, Vec<String>> {
+    let config: Value = serde_yaml::from_str(yaml).map_err(|e| vec![format!("Invalid YAML: {}", e)])?;
+    let Some(root) = config.as_mapping() else {
+        return Err(vec!["The config must be a mapping of sections".to_string()]);
+    };
+
+    let mut errors = Vec::new();
+    for section in REQUIRED_SECTIONS {
+        if root.get(section).is_none() {
+            errors.push(format!("Missing required section: {}", section));
+        }
+    }
+    for key in root.keys() {
+        let name = key.as_str().unwrap_or_default();
+        if !REQUIRED_SECTIONS.contains(&name) && !OPTIONAL_SECTIONS.contains(&name) {
+            errors.push(format!("Unknown section: {}", key_name(key)));
+        }
+    }
+
+    if let Some(project) = section(root, "project", &mut errors) {
+        check_project(project, &mut errors);
+    }
+    if let Some(layout) = section(root, "layout", &mut errors) {
+        check_layout(layout, &mut errors);
+    }
+    if let Some(strips) = section(root, "strip_animations", &mut errors) {
+        check_strip_animations(strips, &mut errors);
+    }
+    if let Some(analysis) = section(root, "audio_analysis", &mut errors) {
+        if analysis.get("file").is_some_and(|f| !f.is_string() && !f.is_null()) {
+            errors.push("audio_analysis.file must be a path".to_string());
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(config)
+    } else {
+        Err(errors)
+    }
+}
+
+/// A section that is present must be a mapping
+fn section<'a>(root: &'a Mapping, name: &str, errors: &mut Vec<String>) -> Option<&'a Mapping> {
+    let value = root.get(name)?;
+    let mapping = value.as_mapping();
+    if mapping.is_none() {
+        errors.push(format!("Section {} must be a mapping", name));
+    }
+    mapping
+}
+
+fn check_project(project: &Mapping, errors: &mut Vec<String>) {
+    if let Some(fps) = project.get("fps") {
+        if !fps.as_u64().is_some_and(|fps| fps > 0) {
+            errors.push("project.fps must be a whole number greater than 0".to_string());
+        }
+    }
+    if let Some(resolution) = project.get("resolution") {
+        for dimension in ["width", "height"] {
+            if !resolution.get(dimension).and_then(Value::as_u64).is_some_and(|d| d > 0) {
+                errors.push(format!("project.resolution.{} must be a whole number greater than 0", dimension));
+            }
+        }
+    }
+    if let Some(files) = project.get("video_files") {
+        let all_strings = files.as_sequence().is_some_and(|files| files.iter().all(Value::is_string));
+        if !all_strings {
+            errors.push("project.video_files must be a list of file names".to_string());
+        }
+    }
+    for key in ["main_audio", "output_blend", "base_directory"] {
+        if project.get(key).is_some_and(|v| !v.is_string() && !v.is_null()) {
+            errors.push(format!("project.{} must be text", key));
+        }
+    }
+}
+
+fn check_layout(layout: &Mapping, errors: &mut Vec<String>) {
+    match layout.get("type").and_then(Value::as_str) {
+        Some(kind) if LAYOUT_TYPES.contains(&kind) => {}
+        Some(kind) => errors.push(format!("Unknown layout type '{}', expected one of: {}", kind, LAYOUT_TYPES.join(", "))),
+        None => errors.push("layout.type is required".to_string()),
+    }
+    if layout.get("config").is_some_and(|c| !c.is_mapping() && !c.is_null()) {
+        errors.push("layout.config must be a mapping".to_string());
+    }
+}
+
+fn check_strip_animations(strips: &Mapping, errors: &mut Vec<String>) {
+    for (strip, animations) in strips {
+        let strip = key_name(strip);
+        let Some(animations) = animations.as_sequence() else {
+            errors.push(format!("Animations for strip '{}' must be a list", strip));
+            continue;
+        };
+
+        for (index, animation) in animations.iter().enumerate() {
+            if !animation.is_mapping() {
+                errors.push(format!("Strip '{}' animation {}: must be a mapping", strip, index));
+                continue;
+            }
+            match animation.get("type").and_then(Value::as_str) {
+                Some(kind) if ANIMATION_TYPES.contains(&kind) => {}
+                Some(kind) => errors.push(format!("Strip '{}' animation {}: unknown type '{}'", strip, index, kind)),
+                None => errors.push(format!("Strip '{}' animation {}: missing required field 'type'", strip, index)),
+            }
+            match animation.get("trigger").and_then(Value::as_str) {
+                Some(trigger) if TRIGGERS.contains(&trigger) => {}
+                Some(trigger) => errors.push(format!("Strip '{}' animation {}: unknown trigger '{}'", strip, index, trigger)),
+                None => errors.push(format!("Strip '{}' animation {}: missing required field 'trigger'", strip, index)),
+            }
+        }
+    }
+}
+
+fn key_name(key: &Value) -> String {
+    match key {
+        Value::String(name) => name.clone(),
+        other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = "\
+project:
+  video_files: [Camera1.mp4, Camera2.mp4]
+  main_audio: main_audio.m4a
+  fps: 30
+  resolution: {width: 1920, height: 1080}
+audio_analysis:
+  file: analysis/audio_analysis.json
+layout:
+  type: main-pip
+  config: {seed: 42}
+strip_animations:
+  Camera1:
+    - {type: scale, trigger: bass, intensity: 0.3}
+  all:
+    - {type: film_grain, trigger: one_time}
+";
+
+    #[test]
+    fn test_valid_config_passes() {
+        let config = validate_animation_config(VALID).unwrap();
+        assert_eq!(config["layout"]["type"].as_str(), Some("main-pip"));
+    }
+
+    #[test]
+    fn test_reports_every_schema_problem() {
+        let broken = VALID
+            .replace("fps: 30", "fps: 0")
+            .replace("type: main-pip", "type: mosaic")
+            .replace("trigger: bass", "trigger: kick")
+            .replace("audio_analysis:", "audio_analysys:");
+        let errors = validate_animation_config(&broken).unwrap_err();
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+        assert!(errors.iter().any(|e| e.contains("project.fps")));
+        assert!(errors.iter().any(|e| e.contains("mosaic")));
+        assert!(errors.iter().any(|e| e.contains("unknown trigger 'kick'")));
+        assert!(errors.iter().any(|e| e.contains("Unknown section: audio_analysys")));
+    }
+
+    #[test]
+    fn test_rejects_unparseable_and_incomplete_yaml() {
+        assert!(validate_animation_config("project: [unclosed")
+            .unwrap_err()[0]
+            .starts_with("Invalid YAML"));
+        let errors = validate_animation_config("project: {fps: 30}\n").unwrap_err();
+        assert_eq!(errors, vec!["Missing required section: layout", "Missing required section: strip_animations"]);
+    }
+}
@@ -0,0 +1,147 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the log file in the app log dir; rotated files get a date suffix
+pub const APP_LOG_NAME: &str = "fermata";
+/// Size at which the log file is rotated
+pub const APP_LOG_MAX_BYTES: u128 = 5 * 1024 * 1024;
+/// Rotated files kept besides the current one
+pub const APP_LOG_KEEP_FILES: usize = 5;
+/// Lines get_app_log returns when not told otherwise
+pub const DEFAULT_LOG_TAIL_LINES: usize = 500;
+
+/// One record of the log file, as written by the log plugin:
+/// `[2025-01-31][12:00:00][app_lib::commands::operations][INFO] message`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AppLogEntry {
+    pub date: String,
+    pub time: String,
+    pub target: String,
+    pub level: String,
+    /// Includes continuation lines of multi-line messages
+    pub message: String,
+}
+
+impl AppLogEntry {
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = Vec::with_capacity(4);
+        let mut rest = line;
+        for _ in 0..4 {
+            let (field, tail) = rest.strip_prefix('[')?.split_once(']')?;
+            fields.push(field.to_string());
+            rest = tail;
+        }
+        fields[3].parse::<log::Level>().ok()?;
+        let [date, time, target, level]: [String; 4] = fields.try_into().ok()?;
+        Some(Self { date, time, target, level, message: rest.strip_prefix(' ').unwrap_or(rest).to_string() })
+    }
+
+    fn level(&self) -> Option<log::Level> {
+        self.level.parse().ok()
+    }
+}
+
+/// Reads the rotating log files in the app log dir
+pub struct AppLog;
+
+impl AppLog {
+    /// Log files oldest first, the current one last
+    fn files(log_dir: &Path) -> Vec<PathBuf> {
+        let current = format!("{}.log", APP_LOG_NAME);
+        let rotated_prefix = format!("{}_", APP_LOG_NAME);
+        let mut rotated: Vec<PathBuf> = fs::read_dir(log_dir)
+            .map(|entries| entries.flatten().map(|e| e.path()).collect())
+            .unwrap_or_default();
+        rotated.retain(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(&rotated_prefix) && n.ends_with(".log"))
+        });
+        // The date suffix sorts chronologically
+        rotated.sort();
+        rotated.push(log_dir.join(current));
+        rotated
+    }
+
+    /// The last `tail_lines` entries at `min_level` or more severe, oldest first,
+    /// reading back into rotated files when the current one has too few
+    pub fn tail(log_dir: &Path, tail_lines: usize, min_level: Option<log::Level>) -> Result<Vec<AppLogEntry>, String> {
+        let mut tail = Vec::new();
+        for file in Self::files(log_dir).iter().rev() {
+            if tail.len() >= tail_lines {
+                break;
+            }
+            let content = match fs::read(file) {
+                Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(format!("Failed to read {}: {}", file.display(), e)),
+            };
+            let entries: Vec<AppLogEntry> = parse_entries(&content)
+                .into_iter()
+                .filter(|entry| min_level.map_or(true, |min| entry.level().is_some_and(|level| level <= min)))
+                .collect();
+            let take = (tail_lines - tail.len()).min(entries.len());
+            let mut older = entries[entries.len() - take..].to_vec();
+            older.append(&mut tail);
+            tail = older;
+        }
+        Ok(tail)
+    }
+}
+
+/// Entries of a log file; lines that don't start a record continue the previous one
+fn parse_entries(content: &str) -> Vec<AppLogEntry> {
+    let mut entries: Vec<AppLogEntry> = Vec::new();
+    for line in content.lines() {
+        match (AppLogEntry::parse(line), entries.last_mut()) {
+            (Some(entry), _) => entries.push(entry),
+            (None, Some(previous)) => {
+                previous.message.push('\n');
+                previous.message.push_str(line);
+            }
+            (None, None) => {}
+        }
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_entries() {
+        let content = "\
+[2025-01-31][12:00:00][app_lib::commands::operations][INFO] 🎵 Running beatrix
+[2025-01-31][12:00:05][app_lib::services::process_runner][ERROR] Failed: Traceback
+  File \"x.py\", line 1
+[2025-01-31][12:00:06][app][WARN] [bracketed] message";
+        let entries = parse_entries(content);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].target, "app_lib::commands::operations");
+        assert_eq!(entries[1].level, "ERROR");
+        assert_eq!(entries[1].message, "Failed: Traceback\n  File \"x.py\", line 1");
+        assert_eq!(entries[2].message, "[bracketed] message");
+    }
+
+    #[test]
+    fn test_tail_reads_rotated_files_and_filters_level() {
+        let temp_dir = TempDir::new().unwrap();
+        let line = |level: &str, n: u32| format!("[2025-01-31][12:00:{:02}][app][{}] line {}\n", n, level, n);
+        fs::write(temp_dir.path().join("fermata_2025-01-30_10-00-00.log"), line("INFO", 1) + &line("ERROR", 2)).unwrap();
+        fs::write(temp_dir.path().join("fermata.log"), line("WARN", 3) + &line("INFO", 4)).unwrap();
+        fs::write(temp_dir.path().join("other.log"), line("ERROR", 9)).unwrap();
+
+        let tail = AppLog::tail(temp_dir.path(), 3, None).unwrap();
+        let messages: Vec<_> = tail.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["line 2", "line 3", "line 4"]);
+
+        let warnings = AppLog::tail(temp_dir.path(), 10, Some(log::Level::Warn)).unwrap();
+        let messages: Vec<_> = warnings.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["line 2", "line 3"]);
+
+        assert!(AppLog::tail(&temp_dir.path().join("missing"), 10, None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::services::{rendered_videos, RecordingDetection, StateManifest};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Hash stored with every checksum, so older manifests stay readable if it changes
+pub const CHECKSUM_ALGORITHM: &str = "sha256";
+
+/// Hash of one key artifact of a recording
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArtifactChecksum {
+    /// Relative to the recording, "/" separated
+    pub path: String,
+    pub algorithm: String,
+    /// Lowercase hex
+    pub hash: String,
+    pub size_bytes: u64,
+}
+
+/// Outcome of re-hashing one artifact
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumState {
+    Ok,
+    Corrupted,
+    Missing,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ChecksumCheck {
+    pub path: String,
+    pub state: ChecksumState,
+    pub expected: String,
+    /// None when the file is gone
+    pub actual: Option<String>,
+}
+
+/// What verify_recording found
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RecordingVerification {
+    pub recording: String,
+    pub checks: Vec<ChecksumCheck>,
+    /// Key artifacts present on disk that no checksum covers yet
+    pub unchecked: Vec<String>,
+    /// Every checked artifact is intact
+    pub ok: bool,
+}
+
+/// The raw video and the final render of a recording, the files worth guarding
+pub fn key_artifacts(recording_path: &Path) -> Vec<PathBuf> {
+    let detection = RecordingDetection::current();
+    let mut artifacts: Vec<PathBuf> = std::fs::read_dir(recording_path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| detection.is_video(path) && path.is_file())
+                .collect()
+        })
+        .unwrap_or_default();
+    artifacts.sort();
+    artifacts.extend(rendered_videos(&recording_path.join("blender").join("render")));
+    artifacts
+}
+
+/// Lowercase hex SHA-256 of a file, read in chunks so large videos don't fill memory
+pub fn hash_file(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Hash the key artifacts of a recording and store the checksums in its state manifest
+pub fn record_checksums(recording_path: &Path) -> Result<Vec<ArtifactChecksum>, String> {
+    let checksums = key_artifacts(recording_path)
+        .iter()
+        .map(|file| {
+            Ok(ArtifactChecksum {
+                path: relative(recording_path, file),
+                algorithm: CHECKSUM_ALGORITHM.to_string(),
+                hash: hash_file(file)?,
+                size_bytes: std::fs::metadata(file).map(|m| m.len()).unwrap_or(0),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    if checksums.is_empty() {
+        return Err("No raw video or final render to checksum".to_string());
+    }
+
+    let mut manifest = StateManifest::load(recording_path).unwrap_or_default();
+    manifest.checksums = checksums.clone();
+    manifest.save(recording_path)?;
+    Ok(checksums)
+}
+
+/// Re-hash the artifacts with a stored checksum and report the ones that changed or vanished
+pub fn verify_checksums(recording_path: &Path, recording_name: &str) -> Result<RecordingVerification, String> {
+    let checksums = StateManifest::load(recording_path).map(|m| m.checksums).unwrap_or_default();
+    if checksums.is_empty() {
+        return Err(format!("No checksums recorded for '{}' yet", recording_name));
+    }
+
+    let mut checks = Vec::new();
+    for checksum in &checksums {
+        let file = recording_path.join(&checksum.path);
+        let (state, actual) = if !file.is_file() {
+            (ChecksumState::Missing, None)
+        } else if checksum.algorithm != CHECKSUM_ALGORITHM {
+            return Err(format!("Unsupported checksum algorithm '{}' for {}", checksum.algorithm, checksum.path));
+        } else {
+            let actual = hash_file(&file)?;
+            let state = if actual == checksum.hash { ChecksumState::Ok } else { ChecksumState::Corrupted };
+            (state, Some(actual))
+        };
+        checks.push(ChecksumCheck { path: checksum.path.clone(), state, expected: checksum.hash.clone(), actual });
+    }
+    let unchecked = key_artifacts(recording_path)
+        .iter()
+        .map(|file| relative(recording_path, file))
+        .filter(|path| !checksums.iter().any(|c| &c.path == path))
+        .collect();
+
+    Ok(RecordingVerification {
+        recording: recording_name.to_string(),
+        ok: checks.iter().all(|c| c.state == ChecksumState::Ok),
+        checks,
+        unchecked,
+    })
+}
+
+fn relative(recording_path: &Path, file: &Path) -> String {
+    let relative = file.strip_prefix(recording_path).unwrap_or(file);
+    relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RecordingStatus;
+    use crate::services::StatusDetector;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_verify() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        fs::write(path.join("gig.mkv"), b"raw video").unwrap();
+        fs::create_dir_all(path.join("blender/render")).unwrap();
+        fs::write(path.join("blender/render/gig.mp4"), b"final render").unwrap();
+        assert!(verify_checksums(path, "gig").is_err());
+
+        let checksums = record_checksums(path).unwrap();
+        let paths: Vec<_> = checksums.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["gig.mkv", "blender/render/gig.mp4"]);
+        assert_eq!(checksums[0].hash.len(), 64);
+        // Checksums alone don't pin the status
+        assert_eq!(StatusDetector::detect_status(path), RecordingStatus::Rendered);
+        assert!(verify_checksums(path, "gig").unwrap().ok);
+
+        fs::write(path.join("gig.mkv"), b"raw videO").unwrap();
+        fs::remove_file(path.join("blender/render/gig.mp4")).unwrap();
+        fs::write(path.join("take2.mp4"), b"another take").unwrap();
+        let report = verify_checksums(path, "gig").unwrap();
+        assert!(!report.ok);
+        let states: Vec<_> = report.checks.iter().map(|c| c.state).collect();
+        assert_eq!(states, [ChecksumState::Corrupted, ChecksumState::Missing]);
+        assert_eq!(report.unchecked, ["take2.mp4"]);
+    }
+}
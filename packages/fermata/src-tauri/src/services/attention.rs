@@ -0,0 +1,174 @@
+use crate::models::{Recording, RecordingStatus};
+use crate::services::{rendered_videos, ConfigStore, SnoozeStore};
+use serde::Serialize;
+use std::path::Path;
+
+/// Recordings waiting longer than this without progress count as stalled
+pub const DEFAULT_STALLED_AFTER_DAYS: u64 = 7;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttentionKind {
+    Failed,
+    /// Unreadable files; the pipeline can't run on it
+    Corrupted,
+    /// No progress for longer than the stall threshold
+    Stalled,
+    /// A step's output exists but the input it was made from does not
+    MissingDependencies,
+}
+
+/// A recording that needs a person to look at it, with what they can do about it
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AttentionItem {
+    pub recording: String,
+    pub kind: AttentionKind,
+    pub status: RecordingStatus,
+    /// The error, the missing input or how long it has been waiting
+    pub detail: String,
+    /// Steps or commands that would resolve it, e.g. "retry" or "analyze"
+    pub suggested_actions: Vec<String>,
+}
+
+impl AttentionItem {
+    /// Why `recording` needs attention, None when it is fine, snoozed or just waiting for
+    /// the pipeline. Failures and corruption come first, then missing inputs, then stalls.
+    pub fn for_recording(recording: &Recording, stalled_after_days: u64, now: u64) -> Option<Self> {
+        let item = |kind, detail: String, actions: &[&str]| Self {
+            recording: recording.name.clone(),
+            kind,
+            status: recording.status.clone(),
+            detail,
+            suggested_actions: actions.iter().map(|a| a.to_string()).collect(),
+        };
+
+        if recording.status.is_in_progress() {
+            return None;
+        }
+        if SnoozeStore::load(&recording.path).is_some_and(|snooze| snooze.is_active(now)) {
+            return None;
+        }
+        if let RecordingStatus::Failed(error) = &recording.status {
+            return Some(item(AttentionKind::Failed, error.clone(), &["retry", "view_logs"]));
+        }
+        if let RecordingStatus::Corrupted(reason) = &recording.status {
+            return Some(item(AttentionKind::Corrupted, reason.clone(), &["quarantine", "delete"]));
+        }
+        if let Some((missing, action)) = missing_dependency(&recording.path) {
+            return Some(item(AttentionKind::MissingDependencies, missing, &[action, "rebuild_state"]));
+        }
+
+        // Uploaded recordings are done and blocked ones wait on purpose
+        let waiting = !matches!(recording.status, RecordingStatus::Uploaded) && recording.upload_block.is_none();
+        let idle_days = now.saturating_sub(recording.last_updated) / SECONDS_PER_DAY;
+        if waiting && idle_days >= stalled_after_days {
+            let next = recording.get_next_step().map(|step| step.id());
+            let mut actions: Vec<&str> = next.iter().map(String::as_str).collect();
+            actions.push("delete");
+            return Some(item(AttentionKind::Stalled, format!("No progress for {} days", idle_days), &actions));
+        }
+        None
+    }
+}
+
+/// Attention items of all recordings, in the order given
+pub fn attention_items(recordings: &[Recording], stalled_after_days: u64, now: u64) -> Vec<AttentionItem> {
+    recordings
+        .iter()
+        .filter_map(|recording| AttentionItem::for_recording(recording, stalled_after_days, now))
+        .collect()
+}
+
+/// The first step output found without its input, with the step that recreates the input
+fn missing_dependency(path: &Path) -> Option<(String, &'static str)> {
+    let blender = path.join("blender");
+    let has_blend = std::fs::read_dir(&blender)
+        .map(|entries| entries.flatten().any(|e| e.path().extension().is_some_and(|ext| ext == "blend")))
+        .unwrap_or(false);
+    let has_config = !ConfigStore::configs(path).is_empty();
+
+    if path.join("analysis").is_dir() && !path.join("extracted").is_dir() {
+        return Some(("analysis/ exists without extracted/".to_string(), "extract"));
+    }
+    if (has_blend || has_config) && !path.join("analysis").is_dir() {
+        return Some(("Render setup exists without analysis/".to_string(), "analyze"));
+    }
+    if !has_blend && !rendered_videos(&blender.join("render")).is_empty() {
+        return Some(("Rendered video exists without a .blend project".to_string(), "blend_setup"));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const NOW: u64 = 100 * SECONDS_PER_DAY;
+
+    fn recording(path: &Path, status: RecordingStatus, last_updated: u64) -> Recording {
+        Recording {
+            name: "gig".to_string(),
+            path: path.to_path_buf(),
+            status,
+            last_updated,
+            file_sizes: HashMap::new(),
+            upload_block: None,
+            root: path.parent().unwrap().to_path_buf(),
+            lifecycle: None,
+            media: None,
+            tree: None,
+        }
+    }
+
+    #[test]
+    fn test_categorizes_recordings() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("gig");
+        fs::create_dir_all(path.join("extracted")).unwrap();
+
+        // Waiting for the pipeline, but recently touched
+        assert!(AttentionItem::for_recording(&recording(&path, RecordingStatus::Extracted, NOW), 7, NOW).is_none());
+
+        let stalled = AttentionItem::for_recording(&recording(&path, RecordingStatus::Extracted, NOW - 10 * SECONDS_PER_DAY), 7, NOW).unwrap();
+        assert_eq!(stalled.kind, AttentionKind::Stalled);
+        assert_eq!(stalled.detail, "No progress for 10 days");
+        assert_eq!(stalled.suggested_actions, vec!["analyze", "delete"]);
+
+        let uploaded = recording(&path, RecordingStatus::Uploaded, 0);
+        assert!(AttentionItem::for_recording(&uploaded, 7, NOW).is_none());
+
+        let failed = AttentionItem::for_recording(&recording(&path, RecordingStatus::Failed("boom".to_string()), NOW), 7, NOW).unwrap();
+        assert_eq!(failed.kind, AttentionKind::Failed);
+        assert_eq!(failed.detail, "boom");
+
+        let corrupted = recording(&path, RecordingStatus::Corrupted("gig.mkv is empty".to_string()), 0);
+        let corrupted = AttentionItem::for_recording(&corrupted, 7, NOW).unwrap();
+        assert_eq!(corrupted.kind, AttentionKind::Corrupted);
+        assert_eq!(corrupted.suggested_actions, vec!["quarantine", "delete"]);
+
+        SnoozeStore::set(&path, None).unwrap();
+        assert!(AttentionItem::for_recording(&recording(&path, RecordingStatus::Failed("boom".to_string()), NOW), 7, NOW).is_none());
+    }
+
+    #[test]
+    fn test_missing_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("gig");
+        fs::create_dir_all(path.join("analysis")).unwrap();
+
+        let item = AttentionItem::for_recording(&recording(&path, RecordingStatus::Analyzed, NOW), 7, NOW).unwrap();
+        assert_eq!(item.kind, AttentionKind::MissingDependencies);
+        assert_eq!(item.suggested_actions, vec!["extract", "rebuild_state"]);
+
+        fs::create_dir_all(path.join("extracted")).unwrap();
+        fs::create_dir_all(path.join("blender/render")).unwrap();
+        fs::write(path.join("blender/render/final.mp4"), "video").unwrap();
+        let item = AttentionItem::for_recording(&recording(&path, RecordingStatus::Rendered, NOW), 7, NOW).unwrap();
+        assert_eq!(item.suggested_actions[0], "blend_setup");
+    }
+}
@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::Path;
+
+/// Audio formats OBS (or obsession) can leave in extracted/
+pub const AUDIO_EXTENSIONS: [&str; 6] = ["m4a", "aac", "wav", "flac", "mp3", "ogg"];
+
+/// Track name OBS gives PulseAudio input capture in a Polish locale
+const PULSEAUDIO_MAIN_AUDIO: &str = "Przechwytywanie wejścia dźwięku (PulseAudio).m4a";
+
+/// Main audio used when FERMATA_MAIN_AUDIO is unset. Only Linux OBS records the
+/// PulseAudio track; elsewhere recordings with several tracks need the variable.
+pub fn default_main_audio() -> &'static str {
+    if cfg!(target_os = "linux") {
+        PULSEAUDIO_MAIN_AUDIO
+    } else {
+        ""
+    }
+}
+
+pub fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| AUDIO_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// File names of the extracted audio tracks of a recording, sorted
+pub fn extracted_audio_files(recording_path: &Path) -> Result<Vec<String>, String> {
+    let extracted_dir = recording_path.join("extracted");
+    let mut files: Vec<String> = fs::read_dir(&extracted_dir)
+        .map_err(|e| format!("Failed to read extracted directory: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_audio_file(path))
+        .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// The configured main audio among `files`. The setting may name another
+/// format of the same track ("Mic.m4a" finds "Mic.wav") or just its stem, in any
+/// case as Windows and macOS file systems ignore it.
+pub fn find_main_audio<'a>(files: &'a [String], main_audio: &str) -> Option<&'a String> {
+    if main_audio.is_empty() {
+        return None;
+    }
+    let stem = |name: &str| Path::new(name).file_stem().map(|s| s.to_string_lossy().to_lowercase());
+    files
+        .iter()
+        .find(|file| *file == main_audio)
+        .or_else(|| files.iter().find(|file| stem(file) == stem(main_audio)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_extracted_audio_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let extracted = temp_dir.path().join("extracted");
+        fs::create_dir_all(&extracted).unwrap();
+        for name in ["Mic.WAV", "Desktop.aac", "Camera1.mp4", "notes.txt", "Guitar.flac"] {
+            fs::write(extracted.join(name), "").unwrap();
+        }
+
+        let files = extracted_audio_files(temp_dir.path()).unwrap();
+        assert_eq!(files, vec!["Desktop.aac", "Guitar.flac", "Mic.WAV"]);
+        assert!(extracted_audio_files(&temp_dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_find_main_audio() {
+        let files = vec!["Desktop.aac".to_string(), "Mic.wav".to_string()];
+        assert_eq!(find_main_audio(&files, "Mic.wav"), Some(&files[1]));
+        assert_eq!(find_main_audio(&files, "Mic.m4a"), Some(&files[1]));
+        assert_eq!(find_main_audio(&files, "Desktop"), Some(&files[0]));
+        assert_eq!(find_main_audio(&files, "mic.M4A"), Some(&files[1]));
+        assert_eq!(find_main_audio(&files, "Guitar.m4a"), None);
+        assert_eq!(find_main_audio(&files, ""), None);
+    }
+}
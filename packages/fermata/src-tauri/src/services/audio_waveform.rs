@@ -0,0 +1,228 @@
+use crate::services::{process_arg, write_atomic, UploadConfig};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::AsyncReadExt;
+use tokio::process::Command as AsyncCommand;
+
+/// Cached waveforms, one file per audio track and resolution
+pub const WAVEFORMS_DIR: &str = ".fermata/waveforms";
+
+/// Mono rate the track is decoded at; plenty for peaks, small enough for long recordings
+const WAVEFORM_SAMPLE_RATE: u32 = 8000;
+
+/// Samples per 10 ms block, the finest detail kept before downsampling
+const BLOCK_SAMPLES: usize = WAVEFORM_SAMPLE_RATE as usize / 100;
+
+const RESOLUTION_RANGE: (usize, usize) = (10, 20_000);
+
+/// Peaks of an audio track for drawing its waveform
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AudioWaveform {
+    /// Relative to extracted/, e.g. "Mic.m4a" or "normalized/Mic.m4a"
+    pub audio_file: String,
+    pub duration_secs: f64,
+    /// [min, max] per bucket of equal length, -1.0 to 1.0; fewer than requested for very short tracks
+    pub peaks: Vec<[f32; 2]>,
+}
+
+pub fn validate_resolution(resolution: usize) -> Result<(), String> {
+    let (min, max) = RESOLUTION_RANGE;
+    if !(min..=max).contains(&resolution) {
+        return Err(format!("Waveform resolution must be between {} and {}, got {}", min, max, resolution));
+    }
+    Ok(())
+}
+
+/// The track in extracted/, refusing paths that lead out of it
+pub fn waveform_source(recording_path: &Path, audio_file: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(audio_file);
+    if audio_file.is_empty() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(format!("Invalid audio file '{}'", audio_file));
+    }
+    let source = recording_path.join("extracted").join(relative);
+    if !source.is_file() {
+        return Err(format!("Audio file '{}' not found in extracted directory", audio_file));
+    }
+    Ok(source)
+}
+
+pub fn waveform_cache_path(recording_path: &Path, audio_file: &str, resolution: usize) -> PathBuf {
+    recording_path.join(WAVEFORMS_DIR).join(format!("{}.{}.json", audio_file.replace(['/', '\\'], "__"), resolution))
+}
+
+/// ffmpeg arguments decoding `input` to raw mono 16-bit samples on stdout
+pub fn waveform_args(input: &Path) -> Vec<OsString> {
+    vec![
+        "-v".into(),
+        "error".into(),
+        "-i".into(),
+        process_arg(input),
+        "-vn".into(),
+        "-ac".into(),
+        "1".into(),
+        "-ar".into(),
+        WAVEFORM_SAMPLE_RATE.to_string().into(),
+        "-f".into(),
+        "s16le".into(),
+        "-".into(),
+    ]
+}
+
+/// Min and max of every 10 ms block of decoded samples
+#[derive(Debug, Default)]
+pub struct PeakBlocks {
+    blocks: Vec<[f32; 2]>,
+    current: Option<[f32; 2]>,
+    in_block: usize,
+    samples: usize,
+    /// Odd byte left over from the previous chunk
+    carry: Option<u8>,
+}
+
+impl PeakBlocks {
+    /// Add little-endian 16-bit samples, split anywhere
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        let mut bytes = bytes.iter().copied();
+        if let Some(low) = self.carry.take() {
+            match bytes.next() {
+                Some(high) => self.push_sample(i16::from_le_bytes([low, high])),
+                None => {
+                    self.carry = Some(low);
+                    return;
+                }
+            }
+        }
+        loop {
+            match (bytes.next(), bytes.next()) {
+                (Some(low), Some(high)) => self.push_sample(i16::from_le_bytes([low, high])),
+                (Some(low), None) => {
+                    self.carry = Some(low);
+                    break;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn push_sample(&mut self, sample: i16) {
+        let value = sample as f32 / i16::MAX as f32;
+        let block = self.current.get_or_insert([value, value]);
+        block[0] = block[0].min(value);
+        block[1] = block[1].max(value);
+        self.samples += 1;
+        self.in_block += 1;
+        if self.in_block == BLOCK_SAMPLES {
+            self.blocks.extend(self.current.take());
+            self.in_block = 0;
+        }
+    }
+
+    /// Merge the blocks into `resolution` buckets covering the whole track
+    pub fn finish(mut self, audio_file: &str, resolution: usize) -> AudioWaveform {
+        self.blocks.extend(self.current.take());
+        let buckets = resolution.min(self.blocks.len());
+        let peaks = (0..buckets)
+            .map(|bucket| {
+                let start = bucket * self.blocks.len() / buckets;
+                let end = ((bucket + 1) * self.blocks.len() / buckets).max(start + 1);
+                self.blocks[start..end]
+                    .iter()
+                    .fold([f32::MAX, f32::MIN], |[min, max], [lo, hi]| [min.min(*lo), max.max(*hi)])
+            })
+            .collect();
+        AudioWaveform {
+            audio_file: audio_file.to_string(),
+            duration_secs: self.samples as f64 / WAVEFORM_SAMPLE_RATE as f64,
+            peaks,
+        }
+    }
+}
+
+impl AudioWaveform {
+    /// Waveform of a track in extracted/, from the cache while it is newer than the track
+    pub async fn load_or_generate(recording_path: &Path, audio_file: &str, resolution: usize) -> Result<Self, String> {
+        validate_resolution(resolution)?;
+        let source = waveform_source(recording_path, audio_file)?;
+        let cache = waveform_cache_path(recording_path, audio_file, resolution);
+        if UploadConfig::is_transcode_fresh(&source, &cache) {
+            if let Some(waveform) = fs::read_to_string(&cache).ok().and_then(|content| serde_json::from_str(&content).ok()) {
+                return Ok(waveform);
+            }
+        }
+
+        let waveform = Self::decode(&source, audio_file, resolution).await?;
+        if let Some(parent) = cache.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string(&waveform).map_err(|e| format!("Failed to serialize waveform: {}", e))?;
+        if let Err(e) = write_atomic(&cache, content) {
+            log::warn!("Failed to cache waveform {}: {}", cache.display(), e);
+        }
+        Ok(waveform)
+    }
+
+    /// Decode with ffmpeg, reducing the samples as they arrive
+    async fn decode(source: &Path, audio_file: &str, resolution: usize) -> Result<Self, String> {
+        log::info!("〰️ Generating waveform of {}", source.display());
+        let mut child = AsyncCommand::new("ffmpeg")
+            .args(waveform_args(source))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to start ffmpeg: {}", e))?;
+        let mut stdout = child.stdout.take().ok_or_else(|| "ffmpeg output unavailable".to_string())?;
+
+        let mut blocks = PeakBlocks::default();
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let read = stdout.read(&mut buffer).await.map_err(|e| format!("Failed to read ffmpeg output: {}", e))?;
+            if read == 0 {
+                break;
+            }
+            blocks.push_bytes(&buffer[..read]);
+        }
+        let output = child.wait_with_output().await.map_err(|e| format!("ffmpeg failed: {}", e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("ffmpeg can't decode {}: {}", audio_file, stderr.lines().last().unwrap_or("unknown error").trim()));
+        }
+        Ok(blocks.finish(audio_file, resolution))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peaks_from_split_chunks() {
+        // One second: the first half silent, the second a full-scale square wave
+        let mut bytes = Vec::new();
+        for i in 0..WAVEFORM_SAMPLE_RATE as usize {
+            let sample: i16 = if i < 4000 { 0 } else if i % 2 == 0 { i16::MAX } else { -i16::MAX };
+            bytes.extend(sample.to_le_bytes());
+        }
+        let mut blocks = PeakBlocks::default();
+        // Odd split points leave half a sample over
+        for chunk in bytes.chunks(333) {
+            blocks.push_bytes(chunk);
+        }
+
+        let waveform = blocks.finish("Mic.m4a", 4);
+        assert_eq!(waveform.duration_secs, 1.0);
+        assert_eq!(waveform.peaks, [[0.0, 0.0], [0.0, 0.0], [-1.0, 1.0], [-1.0, 1.0]]);
+        assert!(validate_resolution(5).is_err());
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        assert!(waveform_source(temp_dir.path(), "../metadata.json").is_err());
+        assert_eq!(
+            waveform_cache_path(temp_dir.path(), "normalized/Mic.m4a", 800),
+            temp_dir.path().join(WAVEFORMS_DIR).join("normalized__Mic.m4a.800.json")
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use crate::models::NextStep;
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Settings key holding the auto-ingest policy
+pub const AUTO_INGEST_KEY: &str = "auto_ingest";
+
+/// What to do with recordings that appear in the recordings directory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AutoIngestPolicy {
+    pub enabled: bool,
+    /// Last step to run automatically, e.g. "setup_render"
+    pub target_step: String,
+    /// Animation preset for SetupRender; cinemon's default when unset
+    pub preset: Option<String>,
+    /// How long the .mkv size must stay unchanged before OBS is considered done
+    pub settle_seconds: u64,
+}
+
+impl Default for AutoIngestPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_step: "analyze".to_string(),
+            preset: None,
+            settle_seconds: 60,
+        }
+    }
+}
+
+impl AutoIngestPolicy {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(AUTO_INGEST_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        self.validate()?;
+        settings.set(AUTO_INGEST_KEY, self)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let step: NextStep = self.target_step.parse()?;
+        if pipeline_position(&step).is_none() {
+            return Err(format!("'{}' cannot be an auto-ingest target", self.target_step));
+        }
+        Ok(())
+    }
+
+    pub fn settle_time(&self) -> Duration {
+        Duration::from_secs(self.settle_seconds)
+    }
+}
+
+/// True if `step` comes at or before `target` in the pipeline
+pub fn within_target(step: &NextStep, target: &NextStep) -> bool {
+    match (pipeline_position(step), pipeline_position(target)) {
+        (Some(step), Some(target)) => step <= target,
+        _ => false,
+    }
+}
+
+fn pipeline_position(step: &NextStep) -> Option<usize> {
+    match step {
+        NextStep::Extract => Some(0),
+        NextStep::Analyze => Some(1),
+        NextStep::GenerateConfig => Some(2),
+        // Setup ends with the blend setup
+        NextStep::SetupRender | NextStep::BlendSetup => Some(3),
+        NextStep::Render => Some(4),
+        NextStep::Upload => Some(5),
+        // Optional steps are never reached by auto-ingest
+        NextStep::Transcribe | NextStep::Retry | NextStep::Custom(_) => None,
+    }
+}
+
+/// Total size of the OBS video files directly in a recording directory
+pub fn recording_video_size(recording_path: &Path) -> u64 {
+    fs::read_dir(recording_path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "mkv"))
+                .filter_map(|e| e.metadata().ok())
+                .map(|m| m.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Tracks new recordings until their video stops growing
+#[derive(Debug, Default)]
+pub struct IngestTracker {
+    /// Last seen size and when it last changed
+    pending: HashMap<String, (u64, Instant)>,
+    /// Recordings seen before, which never become candidates again
+    known: HashSet<String>,
+}
+
+impl IngestTracker {
+    /// Remember the recordings of a full scan as not new
+    pub fn seed(&mut self, recording_names: impl IntoIterator<Item = String>) {
+        self.known.extend(recording_names);
+    }
+
+    /// True the first time a recording is seen, and while it is tracked
+    pub fn is_new(&mut self, recording_name: &str) -> bool {
+        self.pending.contains_key(recording_name) || self.known.insert(recording_name.to_string())
+    }
+
+    /// Record the current video size of a candidate recording
+    pub fn observe(&mut self, recording_name: &str, size: u64, now: Instant) {
+        match self.pending.get_mut(recording_name) {
+            Some((last_size, _)) if *last_size == size => {}
+            _ => {
+                self.pending.insert(recording_name.to_string(), (size, now));
+            }
+        }
+    }
+
+    pub fn forget(&mut self, recording_name: &str) {
+        self.pending.remove(recording_name);
+    }
+
+    /// Stop tracking a deleted recording; one created again under its name is new
+    pub fn remove(&mut self, recording_name: &str) {
+        self.pending.remove(recording_name);
+        self.known.remove(recording_name);
+    }
+
+    pub fn candidates(&self) -> Vec<String> {
+        self.pending.keys().cloned().collect()
+    }
+
+    /// Take recordings whose non-empty video has not changed for `settle`
+    pub fn drain_settled(&mut self, settle: Duration, now: Instant) -> Vec<String> {
+        let mut settled: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, (size, changed))| *size > 0 && now.duration_since(*changed) >= settle)
+            .map(|(name, _)| name.clone())
+            .collect();
+        settled.sort();
+
+        for name in &settled {
+            self.pending.remove(name);
+        }
+        settled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_policy_target_includes_earlier_steps() {
+        let target = NextStep::SetupRender;
+        assert!(within_target(&NextStep::Extract, &target));
+        assert!(within_target(&NextStep::SetupRender, &target));
+        assert!(!within_target(&NextStep::Render, &target));
+        assert!(!within_target(&NextStep::Retry, &target));
+
+        let invalid = AutoIngestPolicy {
+            target_step: "retry".to_string(),
+            ..AutoIngestPolicy::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_policy_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+        assert!(!AutoIngestPolicy::load(&settings).unwrap().enabled);
+
+        let policy = AutoIngestPolicy {
+            enabled: true,
+            preset: Some("beat-switch".to_string()),
+            ..AutoIngestPolicy::default()
+        };
+        policy.save(&settings).unwrap();
+        assert_eq!(AutoIngestPolicy::load(&settings).unwrap(), policy);
+    }
+
+    #[test]
+    fn test_tracker_waits_for_video_to_stop_growing() {
+        let mut tracker = IngestTracker::default();
+        let start = Instant::now();
+        let settle = Duration::from_secs(60);
+
+        tracker.observe("rec_1", 100, start);
+        tracker.observe("rec_1", 200, start + Duration::from_secs(30));
+        assert!(tracker.drain_settled(settle, start + Duration::from_secs(70)).is_empty());
+
+        tracker.observe("rec_1", 200, start + Duration::from_secs(80));
+        assert_eq!(tracker.drain_settled(settle, start + Duration::from_secs(90)), vec!["rec_1"]);
+        assert!(tracker.candidates().is_empty());
+    }
+
+    #[test]
+    fn test_tracker_only_takes_new_recordings() {
+        let mut tracker = IngestTracker::default();
+        tracker.seed(vec!["old".to_string()]);
+
+        assert!(!tracker.is_new("old"));
+        assert!(tracker.is_new("fresh"));
+        tracker.observe("fresh", 100, Instant::now());
+        assert!(tracker.is_new("fresh"));
+
+        // Once drained or forgotten it is no longer new
+        tracker.forget("fresh");
+        assert!(!tracker.is_new("fresh"));
+
+        tracker.remove("old");
+        assert!(tracker.is_new("old"));
+    }
+}
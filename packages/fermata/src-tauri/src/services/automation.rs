@@ -0,0 +1,272 @@
+use crate::models::{NextStep, Recording, RecordingStatus};
+use crate::services::SettingsStore;
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+/// Settings key holding the automation rules
+pub const AUTOMATION_RULES_KEY: &str = "automation_rules";
+
+/// A five-field cron expression: minute hour day-of-month month day-of-week.
+/// Fields accept `*`, numbers, ranges (`1-5`), lists (`1,15`) and steps (`*/10`).
+/// Day-of-week is 0-6 with Sunday as 0 (7 is also Sunday). As in cron, when
+/// both day fields are restricted a day matching either of them fires.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days_of_month: Vec<u32>,
+    months: Vec<u32>,
+    days_of_week: Vec<u32>,
+    /// Neither day field starts with `*`
+    both_days_restricted: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "Invalid schedule '{}': expected 5 fields (minute hour day month weekday)",
+                expression
+            ));
+        }
+
+        let mut days_of_week = parse_field(fields[4], 0, 7)?;
+        for day in days_of_week.iter_mut() {
+            *day %= 7;
+        }
+
+        Ok(Self {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days_of_month: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            days_of_week,
+            both_days_restricted: !fields[2].starts_with('*') && !fields[4].starts_with('*'),
+        })
+    }
+
+    /// True if the schedule fires in the minute of `time`
+    pub fn matches(&self, time: &NaiveDateTime) -> bool {
+        let day_of_month = self.days_of_month.contains(&time.day());
+        let day_of_week = self.days_of_week.contains(&time.weekday().num_days_from_sunday());
+        let day = if self.both_days_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        };
+
+        self.minutes.contains(&time.minute()) && self.hours.contains(&time.hour()) && self.months.contains(&time.month()) && day
+    }
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<u32>, String> {
+    let mut values = Vec::new();
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<u32>()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("Invalid step in '{}'", part))?,
+            ),
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start, min, max)?, parse_value(end, min, max)?)
+        } else {
+            let value = parse_value(range, min, max)?;
+            (value, if step > 1 { max } else { value })
+        };
+
+        if start > end {
+            return Err(format!("Invalid range '{}'", range));
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+fn parse_value(value: &str, min: u32, max: u32) -> Result<u32, String> {
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|v| (min..=max).contains(v))
+        .ok_or_else(|| format!("Value '{}' out of range {}-{}", value, min, max))
+}
+
+/// "Every Sunday 02:00 render everything in SetupRendered"
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AutomationRule {
+    pub id: String,
+    /// Cron expression, e.g. "0 2 * * 0"
+    pub schedule: String,
+    /// Step to run, e.g. "render"
+    pub step: String,
+    /// Only recordings in this status, e.g. "SetupRendered"
+    pub status: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl AutomationRule {
+    pub fn validate(&self) -> Result<(), String> {
+        CronSchedule::parse(&self.schedule)?;
+        self.step
+            .parse::<NextStep>()
+            .map_err(|e| format!("Rule '{}': {}", self.id, e))?;
+        self.target_status()?;
+        Ok(())
+    }
+
+    /// The status named by the rule, e.g. SetupRendered for "SetupRendered"
+    pub fn target_status(&self) -> Result<RecordingStatus, String> {
+        serde_json::from_value(serde_json::Value::String(self.status.clone()))
+            .map_err(|_| format!("Rule '{}': unknown status '{}'", self.id, self.status))
+    }
+
+    /// True if the rule is enabled and scheduled for the minute of `time`
+    pub fn is_due(&self, time: &NaiveDateTime) -> bool {
+        self.enabled
+            && CronSchedule::parse(&self.schedule)
+                .map(|schedule| schedule.matches(time))
+                .unwrap_or(false)
+    }
+
+    /// Recordings the rule applies to
+    pub fn select<'a>(&self, recordings: &'a [Recording]) -> Vec<&'a Recording> {
+        let status = self.target_status().ok();
+        recordings.iter().filter(|r| status.as_ref() == Some(&r.status)).collect()
+    }
+}
+
+pub struct AutomationRules;
+
+impl AutomationRules {
+    pub fn list(settings: &SettingsStore) -> Result<Vec<AutomationRule>, String> {
+        settings.get(AUTOMATION_RULES_KEY)
+    }
+
+    /// Enable or disable a rule by id
+    pub fn set_enabled(settings: &SettingsStore, id: &str, enabled: bool) -> Result<AutomationRule, String> {
+        let mut rules = Self::list(settings)?;
+        let rule = rules
+            .iter_mut()
+            .find(|r| r.id == id)
+            .ok_or_else(|| format!("Automation rule '{}' not found", id))?;
+        rule.enabled = enabled;
+        let updated = rule.clone();
+
+        settings.set(AUTOMATION_RULES_KEY, &rules)?;
+        Ok(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sunday_two_am() {
+        let schedule = CronSchedule::parse("0 2 * * 0").unwrap();
+        // 2024-01-14 was a Sunday
+        assert!(schedule.matches(&at(2024, 1, 14, 2, 0)));
+        assert!(!schedule.matches(&at(2024, 1, 14, 2, 1)));
+        assert!(!schedule.matches(&at(2024, 1, 15, 2, 0)));
+    }
+
+    #[test]
+    fn test_steps_ranges_and_lists() {
+        let schedule = CronSchedule::parse("*/15 9-17 1,15 * 1-5").unwrap();
+        assert!(schedule.matches(&at(2024, 1, 15, 9, 45)));
+        assert!(!schedule.matches(&at(2024, 1, 15, 18, 0)));
+        assert!(!schedule.matches(&at(2024, 1, 15, 9, 10)));
+        assert_eq!(CronSchedule::parse("0 0 * * 7").unwrap(), CronSchedule::parse("0 0 * * 0").unwrap());
+    }
+
+    #[test]
+    fn test_restricted_day_fields_match_either() {
+        // The 1st of the month or any Monday
+        let schedule = CronSchedule::parse("0 3 1 * 1").unwrap();
+        // 2024-02-01 was a Thursday, 2024-02-05 a Monday
+        assert!(schedule.matches(&at(2024, 2, 1, 3, 0)));
+        assert!(schedule.matches(&at(2024, 2, 5, 3, 0)));
+        assert!(!schedule.matches(&at(2024, 2, 6, 3, 0)));
+
+        // With one day field unrestricted both must still match
+        let weekdays = CronSchedule::parse("0 3 * * 1").unwrap();
+        assert!(!weekdays.matches(&at(2024, 2, 1, 3, 0)));
+    }
+
+    #[test]
+    fn test_select_compares_status() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("gig")).unwrap();
+        let mut recording = Recording::from_path(temp_dir.path().join("gig")).unwrap();
+        recording.status = RecordingStatus::SetupRendered;
+        let mut failed = recording.clone();
+        failed.status = RecordingStatus::Failed("SetupRendered".to_string());
+
+        let rule = AutomationRule {
+            id: "weekly-render".to_string(),
+            schedule: "0 2 * * 0".to_string(),
+            step: "render".to_string(),
+            status: "SetupRendered".to_string(),
+            enabled: true,
+        };
+        let recordings = [recording, failed];
+        let selected = rule.select(&recordings);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].status, RecordingStatus::SetupRendered);
+
+        let unknown = AutomationRule { status: "Renderd".to_string(), ..rule };
+        assert!(unknown.validate().is_err());
+        assert!(unknown.select(&recordings).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_schedules() {
+        assert!(CronSchedule::parse("0 2 * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+    }
+
+    #[test]
+    fn test_set_enabled_persists() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+        let rule = AutomationRule {
+            id: "weekly-render".to_string(),
+            schedule: "0 2 * * 0".to_string(),
+            step: "render".to_string(),
+            status: "SetupRendered".to_string(),
+            enabled: true,
+        };
+        settings.set(AUTOMATION_RULES_KEY, &vec![rule]).unwrap();
+
+        let updated = AutomationRules::set_enabled(&settings, "weekly-render", false).unwrap();
+        assert!(!updated.enabled);
+        assert!(!AutomationRules::list(&settings).unwrap()[0].is_due(&at(2024, 1, 14, 2, 0)));
+    }
+}
@@ -0,0 +1,22 @@
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+
+/// Settings key for keeping the app running in the tray when its window closes
+pub const BACKGROUND_SETTINGS_KEY: &str = "background";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct BackgroundSettings {
+    /// Closing the window hides it and queued jobs keep running; quit from the tray
+    pub run_in_background: bool,
+}
+
+impl BackgroundSettings {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(BACKGROUND_SETTINGS_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        settings.set(BACKGROUND_SETTINGS_KEY, self)
+    }
+}
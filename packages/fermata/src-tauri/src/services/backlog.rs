@@ -0,0 +1,215 @@
+use crate::models::{NextStep, Recording, RecordingStatus};
+use crate::services::{recording_date, within_target, Job, PipelineTemplate, PipelineTemplates, SettingsStore, SnoozeStore};
+use chrono::NaiveDate;
+use serde::Serialize;
+
+/// Built-in steps in the order a job runs them
+const PIPELINE_ORDER: [NextStep; 5] = [NextStep::Extract, NextStep::Analyze, NextStep::SetupRender, NextStep::Render, NextStep::Upload];
+
+/// A recording the backlog takes to the target, with the steps its job will run
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BacklogItem {
+    pub recording: String,
+    pub status: RecordingStatus,
+    /// YYYY-MM-DD, see recording_date
+    pub recorded_on: String,
+    pub template: Option<String>,
+    /// Preset the job sets up the render with; cinemon's default when unset
+    pub preset: Option<String>,
+    pub steps: Vec<NextStep>,
+}
+
+/// A recording made since the date that the backlog leaves alone, and why
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BacklogSkip {
+    pub recording: String,
+    pub reason: String,
+}
+
+/// What process_backlog queues, or queued once confirmed
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BacklogPlan {
+    pub since: String,
+    /// Step the jobs run up to
+    pub target_step: NextStep,
+    /// Oldest recording first
+    pub items: Vec<BacklogItem>,
+    pub skipped: Vec<BacklogSkip>,
+    /// Empty until confirmed
+    pub jobs: Vec<Job>,
+}
+
+impl BacklogPlan {
+    /// Plan the recordings made on or after `since` whose status is below `target_status`.
+    /// `template` is followed by every recording; without it each follows its own.
+    pub fn build(
+        recordings: &[Recording],
+        since: &str,
+        target_status: &RecordingStatus,
+        template: Option<&PipelineTemplate>,
+        settings: &SettingsStore
+    ) -> Result<Self, String> {
+        let since_date = NaiveDate::parse_from_str(since.trim(), "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date '{}', expected YYYY-MM-DD: {}", since, e))?;
+        let target_step = step_reaching(target_status)
+            .ok_or_else(|| format!("{:?} cannot be a backlog target", target_status))?;
+
+        let mut dated: Vec<(NaiveDate, &Recording)> = recordings
+            .iter()
+            .map(|recording| (recording_date(recording).0, recording))
+            .filter(|(date, recording)| *date >= since_date && !reached(&recording.status, &target_step))
+            .collect();
+        dated.sort_by(|(a, x), (b, y)| a.cmp(b).then_with(|| x.name.cmp(&y.name)));
+
+        let mut plan = Self { since: since_date.to_string(), target_step: target_step.clone(), items: Vec::new(), skipped: Vec::new(), jobs: Vec::new() };
+        for (date, recording) in dated {
+            let own_template = match template {
+                Some(_) => None,
+                None => match PipelineTemplates::for_recording(settings, &recording.path) {
+                    Ok(own) => own,
+                    Err(e) => {
+                        plan.skip(recording, e);
+                        continue;
+                    }
+                },
+            };
+            let template = template.or(own_template.as_ref());
+            if let Some(reason) = skip_reason(recording, &target_step) {
+                plan.skip(recording, reason);
+                continue;
+            }
+            let steps = planned_steps(recording, &target_step, template);
+            if steps.is_empty() {
+                plan.skip(recording, "Its pipeline template ends before the target".to_string());
+                continue;
+            }
+            plan.items.push(BacklogItem {
+                recording: recording.name.clone(),
+                status: recording.status.clone(),
+                recorded_on: date.to_string(),
+                template: template.map(|t| t.name.clone()),
+                preset: template.and_then(|t| t.preset.clone()),
+                steps,
+            });
+        }
+        Ok(plan)
+    }
+
+    fn skip(&mut self, recording: &Recording, reason: String) {
+        self.skipped.push(BacklogSkip { recording: recording.name.clone(), reason });
+    }
+}
+
+/// Last step of the jobs taking recordings to `status`
+fn step_reaching(status: &RecordingStatus) -> Option<NextStep> {
+    match status {
+        RecordingStatus::Extracted => Some(NextStep::Extract),
+        RecordingStatus::Analyzed => Some(NextStep::Analyze),
+        RecordingStatus::SetupRendered => Some(NextStep::SetupRender),
+        RecordingStatus::Rendered => Some(NextStep::Render),
+        RecordingStatus::Uploaded => Some(NextStep::Upload),
+        _ => None,
+    }
+}
+
+/// The recording is past the target step; failed and busy ones are planned as skipped
+fn reached(status: &RecordingStatus, target: &NextStep) -> bool {
+    match status {
+        RecordingStatus::Recorded | RecordingStatus::Failed(_) | RecordingStatus::Corrupted(_) => false,
+        RecordingStatus::Extracted => !within_target(&NextStep::Analyze, target),
+        RecordingStatus::Analyzed | RecordingStatus::ConfigGenerated => !within_target(&NextStep::SetupRender, target),
+        RecordingStatus::SetupRendered | RecordingStatus::FramesRendered => !within_target(&NextStep::Render, target),
+        RecordingStatus::Rendered => !within_target(&NextStep::Upload, target),
+        RecordingStatus::Uploaded => true,
+        _ => false,
+    }
+}
+
+fn skip_reason(recording: &Recording, target: &NextStep) -> Option<String> {
+    match &recording.status {
+        RecordingStatus::Failed(error) => Some(format!("Failed, retry it first: {}", error)),
+        RecordingStatus::Corrupted(reason) => Some(format!("Corrupted: {}", reason)),
+        status if status.is_in_progress() => Some(format!("Busy: {:?}", status)),
+        _ if *target == NextStep::Upload && recording.upload_block.is_some() => Some("Upload blocked".to_string()),
+        _ => SnoozeStore::active(&recording.path).map(|snooze| match snooze.until {
+            Some(_) => "Snoozed".to_string(),
+            None => "Ignored".to_string(),
+        }),
+    }
+}
+
+/// Steps a job runs from the recording's next step up to the target, or the end of its template
+fn planned_steps(recording: &Recording, target: &NextStep, template: Option<&PipelineTemplate>) -> Vec<NextStep> {
+    let last = template.and_then(|t| t.last_step.parse::<NextStep>().ok());
+    let in_scope = |step: &NextStep| within_target(step, target) && last.as_ref().map_or(true, |last| within_target(step, last));
+    let Some(first) = recording.get_next_step().filter(|step| in_scope(step)) else {
+        return Vec::new();
+    };
+    let later = PIPELINE_ORDER.iter().skip_while(|step| !within_target(&first, step)).skip(1);
+    std::iter::once(first.clone()).chain(later.filter(|step| in_scope(step)).cloned()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn recording(root: &Path, name: &str, status: RecordingStatus) -> Recording {
+        let path = root.join(name);
+        std::fs::create_dir_all(&path).unwrap();
+        Recording {
+            name: name.to_string(),
+            path,
+            status,
+            last_updated: 0,
+            file_sizes: HashMap::new(),
+            upload_block: None,
+            root: root.to_path_buf(),
+            lifecycle: None,
+            media: None,
+            tree: None,
+        }
+    }
+
+    #[test]
+    fn test_plans_recordings_since_date_below_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let settings = SettingsStore::new(root.join("settings.json"));
+        let recordings = vec![
+            recording(root, "2026-10-10 rehearsal", RecordingStatus::Analyzed),
+            recording(root, "2026-10-11 gig", RecordingStatus::Extracted),
+            recording(root, "2026-10-11 jam", RecordingStatus::Rendered),
+            recording(root, "2026-10-12 encore", RecordingStatus::Failed("render".to_string())),
+            recording(root, "2026-10-12 soundcheck", RecordingStatus::SetupRendered),
+        ];
+
+        let plan = BacklogPlan::build(&recordings, "2026-10-11", &RecordingStatus::Rendered, None, &settings).unwrap();
+        assert_eq!(plan.target_step, NextStep::Render);
+        let planned: Vec<(&str, &[NextStep])> = plan.items.iter().map(|i| (i.recording.as_str(), i.steps.as_slice())).collect();
+        assert_eq!(
+            planned,
+            vec![
+                ("2026-10-11 gig", &[NextStep::Analyze, NextStep::SetupRender, NextStep::Render][..]),
+                ("2026-10-12 soundcheck", &[NextStep::Render][..]),
+            ]
+        );
+        assert_eq!(plan.skipped.len(), 1);
+        assert_eq!(plan.skipped[0].recording, "2026-10-12 encore");
+
+        let analyze_only = PipelineTemplate {
+            name: "Analysis".to_string(),
+            description: None,
+            last_step: "analyze".to_string(),
+            preset: None,
+            preview: None,
+            upload_profiles: Vec::new(),
+        };
+        let plan = BacklogPlan::build(&recordings, "2026-10-11", &RecordingStatus::Rendered, Some(&analyze_only), &settings).unwrap();
+        assert_eq!(plan.items.len(), 1);
+        assert_eq!(plan.items[0].steps, vec![NextStep::Analyze]);
+        assert!(BacklogPlan::build(&recordings, "last monday", &RecordingStatus::Rendered, None, &settings).is_err());
+    }
+}
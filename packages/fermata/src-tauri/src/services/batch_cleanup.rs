@@ -0,0 +1,190 @@
+use crate::models::RecordingStatus;
+use crate::services::{
+    detect_in_progress_status, directory_size, uploaded_at, BulkDeleteItem, Cleanup, CleanupReport, CleanupTarget, FileScanner, StatusDetector, Trash
+};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A recording a batch operation left alone, with the reason
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BatchSkip {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Combined outcome of a batch delete or cleanup; with `dry_run` nothing was touched
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BatchReport {
+    pub dry_run: bool,
+    /// Recordings moved (or that would be moved) to the trash
+    pub trashed: Vec<BulkDeleteItem>,
+    /// Artifacts removed from recordings that stay
+    pub cleaned: Vec<CleanupReport>,
+    pub skipped: Vec<BatchSkip>,
+    pub freed_bytes: u64,
+}
+
+impl BatchReport {
+    fn new(dry_run: bool) -> Self {
+        Self { dry_run, trashed: Vec::new(), cleaned: Vec::new(), skipped: Vec::new(), freed_bytes: 0 }
+    }
+
+    fn skip(&mut self, name: &str, reason: impl Into<String>) {
+        self.skipped.push(BatchSkip { name: name.to_string(), reason: reason.into() });
+    }
+}
+
+/// Deletes and cleans up many recordings in one go, for reclaiming space after a
+/// busy weekend. Recordings that are busy or have a running job are skipped.
+pub struct BatchCleanup;
+
+impl BatchCleanup {
+    /// Move the named recordings to the trash
+    pub fn delete(roots: &[PathBuf], names: &[String], running: &[String], dry_run: bool) -> BatchReport {
+        let mut report = BatchReport::new(dry_run);
+        for name in names {
+            if report.trashed.iter().any(|i| i.name == *name) {
+                continue;
+            }
+            let Some(recordings_path) = FileScanner::find_root(roots, name) else {
+                report.skip(name, "not found");
+                continue;
+            };
+            Self::trash(&mut report, recordings_path, name, running);
+        }
+        report
+    }
+
+    /// Recordings uploaded at least `older_than_days` days before `now` (Unix seconds) lose
+    /// `targets`, or go to the trash entirely when no targets are given
+    pub fn cleanup_uploaded(
+        roots: &[PathBuf],
+        older_than_days: u64,
+        targets: Option<&[CleanupTarget]>,
+        running: &[String],
+        now: u64,
+        dry_run: bool
+    ) -> BatchReport {
+        let cutoff = now.saturating_sub(older_than_days * SECONDS_PER_DAY);
+        let mut report = BatchReport::new(dry_run);
+        for recording in FileScanner::scan_roots(roots).iter().filter(|r| r.status == RecordingStatus::Uploaded) {
+            if !uploaded_at(&recording.path).is_some_and(|at| at <= cutoff) {
+                continue;
+            }
+            let Some(targets) = targets else {
+                Self::trash(&mut report, &recording.root, &recording.name, running);
+                continue;
+            };
+            if let Some(reason) = Self::busy(&recording.path, &recording.name, running) {
+                report.skip(&recording.name, reason);
+                continue;
+            }
+            match Cleanup::run(&recording.path, &recording.name, targets, dry_run) {
+                Ok(cleaned) if cleaned.items.is_empty() => {}
+                Ok(cleaned) => {
+                    report.freed_bytes += cleaned.freed_bytes;
+                    report.cleaned.push(cleaned);
+                }
+                Err(e) => report.skip(&recording.name, e),
+            }
+        }
+        report
+    }
+
+    fn busy(recording_path: &Path, name: &str, running: &[String]) -> Option<String> {
+        if running.iter().any(|r| r == name) {
+            return Some("a job is running on it".to_string());
+        }
+        detect_in_progress_status(recording_path).map(|status| format!("busy ({:?})", status))
+    }
+
+    fn trash(report: &mut BatchReport, recordings_path: &Path, name: &str, running: &[String]) {
+        let path = recordings_path.join(name);
+        if let Some(reason) = Self::busy(&path, name, running) {
+            report.skip(name, reason);
+            return;
+        }
+        let uploaded = StatusDetector::detect_status(&path) == RecordingStatus::Uploaded;
+        let size_bytes = if report.dry_run {
+            directory_size(&path)
+        } else {
+            match Trash::move_to_trash(recordings_path, name) {
+                Ok(entry) => entry.size_bytes,
+                Err(e) => {
+                    report.skip(name, e);
+                    return;
+                }
+            }
+        };
+        report.freed_bytes += size_bytes;
+        report.trashed.push(BulkDeleteItem { name: name.to_string(), size_bytes, uploaded });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_recording(root: &Path, name: &str, uploaded: bool) {
+        let path = root.join(name);
+        fs::create_dir_all(path.join("extracted")).unwrap();
+        fs::write(path.join(format!("{}.mkv", name)), vec![0u8; 10]).unwrap();
+        fs::write(path.join("extracted").join("Mic.m4a"), vec![0u8; 4]).unwrap();
+        if uploaded {
+            fs::create_dir_all(path.join("uploads")).unwrap();
+            let results = r#"{"results": {"youtube": {"success": true, "timestamp": "2024-01-10T12:00:00+00:00"}}}"#;
+            fs::write(path.join("uploads/upload_results.json"), results).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_delete_dry_run_then_for_real() {
+        let temp_dir = TempDir::new().unwrap();
+        let roots = [temp_dir.path().to_path_buf()];
+        for name in ["fri", "sat", "sun"] {
+            create_recording(temp_dir.path(), name, false);
+        }
+        let names: Vec<String> = ["fri", "sat", "sun", "mon"].iter().map(|n| n.to_string()).collect();
+        let running = ["sun".to_string()];
+
+        let preview = BatchCleanup::delete(&roots, &names, &running, true);
+        assert_eq!(preview.trashed.len(), 2);
+        assert_eq!(preview.freed_bytes, 28);
+        assert_eq!(preview.skipped.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), ["sun", "mon"]);
+        assert!(temp_dir.path().join("fri").exists());
+
+        let report = BatchCleanup::delete(&roots, &names, &running, false);
+        assert_eq!(report.freed_bytes, 28);
+        assert!(!temp_dir.path().join("fri").exists());
+        assert!(temp_dir.path().join("sun").exists());
+        assert_eq!(Trash::list(temp_dir.path()).len(), 2);
+    }
+
+    #[test]
+    fn test_cleanup_uploaded_targets_or_trash() {
+        let temp_dir = TempDir::new().unwrap();
+        let roots = [temp_dir.path().to_path_buf()];
+        create_recording(temp_dir.path(), "uploaded", true);
+        create_recording(temp_dir.path(), "pending", false);
+        // The recorded upload time counts, not when uploads/ was last written to
+        let now = 1704888000 + 3 * SECONDS_PER_DAY;
+        assert_eq!(uploaded_at(&temp_dir.path().join("uploaded")), Some(1704888000));
+
+        let too_recent = BatchCleanup::cleanup_uploaded(&roots, 7, None, &[], now, true);
+        assert!(too_recent.trashed.is_empty());
+
+        let extracted = BatchCleanup::cleanup_uploaded(&roots, 2, Some(&[CleanupTarget::Extracted]), &[], now, false);
+        assert_eq!(extracted.cleaned.len(), 1);
+        assert_eq!(extracted.freed_bytes, 4);
+        assert!(!temp_dir.path().join("uploaded/extracted").exists());
+        assert!(temp_dir.path().join("pending/extracted").exists());
+
+        let trashed = BatchCleanup::cleanup_uploaded(&roots, 2, None, &[], now, false);
+        assert_eq!(trashed.trashed.iter().map(|i| i.name.as_str()).collect::<Vec<_>>(), ["uploaded"]);
+        assert!(!temp_dir.path().join("uploaded").exists());
+    }
+}
@@ -0,0 +1,227 @@
+use crate::services::{machine_name, ProcessResult};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Benchmark results of every machine sharing the library, one JSON report per line
+pub const BENCHMARK_LOG_FILE: &str = ".fermata-benchmarks.jsonl";
+
+/// Length of the synthetic sample clip beatrix analyzes
+pub const BENCHMARK_AUDIO_SECONDS: u32 = 30;
+
+/// Frames of the synthetic Cycles scene Blender renders
+pub const BENCHMARK_RENDER_FRAMES: u32 = 10;
+
+/// Frames of the 1080p test pattern ffmpeg encodes
+pub const BENCHMARK_ENCODE_FRAMES: u32 = 300;
+
+/// Sample clip in the benchmark's extracted directory, generated by ffmpeg since
+/// the app ships no media of its own
+pub const BENCHMARK_AUDIO_FILE: &str = "benchmark.wav";
+
+const DEVICE_PREFIX: &str = "FERMATA_BENCHMARK";
+
+/// Python turning Blender's startup scene into a short animated Cycles render;
+/// it runs before the render device script, which picks the device
+pub fn blender_scene_script() -> String {
+    format!(
+        "import bpy
+s = bpy.context.scene
+s.render.engine = 'CYCLES'
+s.cycles.samples = 64
+s.render.resolution_x = 1280
+s.render.resolution_y = 720
+s.render.resolution_percentage = 100
+s.render.image_settings.file_format = 'PNG'
+s.frame_start = 1
+s.frame_end = {}
+for o in s.objects:
+    if o.type == 'MESH':
+        o.keyframe_insert('rotation_euler', frame=s.frame_start)
+        o.rotation_euler[2] = 3.14
+        o.keyframe_insert('rotation_euler', frame=s.frame_end)
+",
+        BENCHMARK_RENDER_FRAMES
+    )
+}
+
+/// Prints the device Cycles renders the benchmark on, after the device script ran
+pub const BLENDER_BENCHMARK_DEVICE_SCRIPT: &str = "import bpy
+s = bpy.context.scene
+p = bpy.context.preferences.addons['cycles'].preferences
+used = [d.name for d in p.devices if d.use and d.type != 'CPU'] if s.cycles.device == 'GPU' else []
+print('FERMATA_BENCHMARK', s.cycles.device, p.compute_device_type, ', '.join(used), sep='\\t', flush=True)
+";
+
+/// ffmpeg arguments writing the sample clip: a beep every second over a sine tone
+pub fn sample_audio_args(output: &Path) -> Vec<OsString> {
+    let source = format!("sine=frequency=220:beep_factor=4:sample_rate=44100:duration={}", BENCHMARK_AUDIO_SECONDS);
+    let mut args: Vec<OsString> = ["-y", "-hide_banner", "-f", "lavfi", "-i", &source, "-ac", "2"].iter().map(OsString::from).collect();
+    args.push(output.as_os_str().to_os_string());
+    args
+}
+
+/// ffmpeg arguments encoding a 1080p30 test pattern to H.264, as finalize does
+pub fn encode_args(output: &Path) -> Vec<OsString> {
+    let source = format!("testsrc2=size=1920x1080:rate=30:duration={}", BENCHMARK_ENCODE_FRAMES / 30);
+    let mut args: Vec<OsString> = ["-y", "-hide_banner", "-f", "lavfi", "-i", &source, "-c:v", "libx264", "-preset", "medium", "-pix_fmt", "yuv420p"]
+        .iter()
+        .map(OsString::from)
+        .collect();
+    args.push(output.as_os_str().to_os_string());
+    args
+}
+
+/// How long one tool took on its part of the workload
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BenchmarkTiming {
+    /// "beatrix", "blender" or "ffmpeg"
+    pub tool: String,
+    pub task: String,
+    pub success: bool,
+    pub duration_ms: u64,
+    /// Work done per second, e.g. rendered frames; None when the tool failed
+    pub rate: Option<f64>,
+    /// Unit of the rate, e.g. "frames/s"
+    pub unit: String,
+    pub error: Option<String>,
+}
+
+impl BenchmarkTiming {
+    /// Timing of a tool's run; `work` is in the rate's unit times seconds, e.g. frames
+    pub fn from_result(tool: &str, task: &str, result: anyhow::Result<ProcessResult>, work: f64, unit: &str) -> Self {
+        let (success, duration_ms, error) = match result {
+            Ok(result) if result.success => (true, result.duration_ms, None),
+            Ok(result) => {
+                let last_line = result.stderr.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("").trim().to_string();
+                let error = match (result.timeout, result.exit_code) {
+                    (Some(timeout), _) => timeout.to_string(),
+                    (None, _) if !last_line.is_empty() => last_line,
+                    (None, Some(code)) => format!("Exited with code {}", code),
+                    (None, None) => "Exited without a code".to_string(),
+                };
+                (false, result.duration_ms, Some(error))
+            }
+            Err(e) => (false, 0, Some(format!("Failed to start {}: {}", tool, e))),
+        };
+        let rate = (success && duration_ms > 0).then(|| work / (duration_ms as f64 / 1000.0));
+        Self { tool: tool.to_string(), task: task.to_string(), success, duration_ms, rate, unit: unit.to_string(), error }
+    }
+}
+
+/// Device Blender rendered the benchmark on, as printed by BLENDER_BENCHMARK_DEVICE_SCRIPT
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BenchmarkDevice {
+    /// "GPU" or "CPU"
+    pub device: String,
+    /// Cycles compute backend, "NONE" without one
+    pub backend: String,
+    /// GPUs enabled for the render
+    pub gpus: Vec<String>,
+}
+
+impl BenchmarkDevice {
+    pub fn parse(output: &str) -> Option<Self> {
+        output.lines().find_map(|line| {
+            let fields: Vec<&str> = line.trim_end_matches(['\r', '\n']).split('\t').collect();
+            let [DEVICE_PREFIX, device, backend, gpus] = fields[..] else {
+                return None;
+            };
+            Some(Self {
+                device: device.to_string(),
+                backend: backend.to_string(),
+                gpus: gpus.split(", ").filter(|gpu| !gpu.is_empty()).map(str::to_string).collect(),
+            })
+        })
+    }
+
+    /// Cycles falls back to the CPU when the scene asks for the GPU but none is enabled
+    pub fn gpu_used(&self) -> bool {
+        self.device == "GPU" && self.backend != "NONE" && !self.gpus.is_empty()
+    }
+}
+
+/// Timings of the pipeline tools on one machine
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BenchmarkReport {
+    /// Unix timestamp in seconds
+    pub at: u64,
+    /// Machine the benchmark ran on, see machine_name
+    pub host: String,
+    pub timings: Vec<BenchmarkTiming>,
+    /// None when Blender didn't get to report it
+    pub render_device: Option<BenchmarkDevice>,
+    pub gpu_used: bool,
+}
+
+impl BenchmarkReport {
+    pub fn new(timings: Vec<BenchmarkTiming>, render_device: Option<BenchmarkDevice>) -> Self {
+        Self {
+            at: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+            host: machine_name().to_string(),
+            timings,
+            gpu_used: render_device.as_ref().is_some_and(BenchmarkDevice::gpu_used),
+            render_device,
+        }
+    }
+}
+
+/// Reports of past benchmarks, kept in the recordings root so machines can be compared
+pub struct BenchmarkLog;
+
+impl BenchmarkLog {
+    pub fn append(root: &Path, report: &BenchmarkReport) -> Result<(), String> {
+        let path = root.join(BENCHMARK_LOG_FILE);
+        let mut line = serde_json::to_string(report).map_err(|e| format!("Failed to serialize benchmark: {}", e))?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Reports of every machine, newest first. Lines that can't be read are skipped.
+    pub fn read(root: &Path) -> Result<Vec<BenchmarkReport>, String> {
+        let path = root.join(BENCHMARK_LOG_FILE);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        Ok(content.lines().rev().filter_map(|line| serde_json::from_str(line).ok()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reports_device_and_rates() {
+        let output = "Blender 4.1.1\nFERMATA_BENCHMARK\tGPU\tOPTIX\tNVIDIA GeForce RTX 3080\nFra:1 Mem:12.00M\n";
+        let device = BenchmarkDevice::parse(output).unwrap();
+        assert_eq!(device.backend, "OPTIX");
+        assert!(device.gpu_used());
+        // Asked for the GPU without a backend, Cycles renders on the CPU
+        assert!(!BenchmarkDevice::parse("FERMATA_BENCHMARK\tGPU\tNONE\t\n").unwrap().gpu_used());
+        assert!(BenchmarkDevice::parse("Blender quit\n").is_none());
+
+        let rendered = ProcessResult { duration_ms: 4_000, ..ProcessResult::dry_run() };
+        let timing = BenchmarkTiming::from_result("blender", "Render", Ok(rendered), 10.0, "frames/s");
+        assert_eq!(timing.rate, Some(2.5));
+        let failed = ProcessResult::error("Error: libx264 missing\n\n".to_string());
+        let timing = BenchmarkTiming::from_result("ffmpeg", "Encode", Ok(failed), 300.0, "frames/s");
+        assert_eq!((timing.rate, timing.error.as_deref()), (None, Some("Error: libx264 missing")));
+
+        let temp_dir = TempDir::new().unwrap();
+        BenchmarkLog::append(temp_dir.path(), &BenchmarkReport::new(vec![timing], Some(device))).unwrap();
+        let reports = BenchmarkLog::read(temp_dir.path()).unwrap();
+        assert_eq!((reports.len(), reports[0].gpu_used), (1, true));
+    }
+}
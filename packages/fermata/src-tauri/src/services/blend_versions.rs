@@ -0,0 +1,174 @@
+use crate::services::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Blender project Render uses, by file name in blender/
+pub const ACTIVE_BLEND_FILE: &str = ".fermata/active_blend.json";
+
+/// A Blender project in blender/; setups are kept as `v001_<name>.blend`, `v002_<name>.blend`, ...
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BlendVersion {
+    pub file: String,
+    /// None for a project not versioned yet, e.g. from before versioning
+    pub version: Option<u32>,
+    pub size_bytes: u64,
+    /// Unix timestamp in seconds
+    pub modified_at: u64,
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ActiveBlend {
+    file: String,
+}
+
+pub struct BlendVersions;
+
+impl BlendVersions {
+    /// Projects in blender/, oldest version first and unversioned ones last
+    pub fn list(recording_path: &Path) -> Vec<BlendVersion> {
+        let active = Self::active(recording_path);
+        let mut versions: Vec<BlendVersion> = blend_files(recording_path)
+            .into_iter()
+            .map(|path| {
+                let file = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                let metadata = fs::metadata(&path).ok();
+                BlendVersion {
+                    version: version_number(&file),
+                    size_bytes: metadata.as_ref().map_or(0, |m| m.len()),
+                    modified_at: metadata
+                        .and_then(|m| m.modified().ok())
+                        .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+                        .map_or(0, |d| d.as_secs()),
+                    active: active.as_deref() == Some(path.as_path()),
+                    file,
+                }
+            })
+            .collect();
+        versions.sort_by(|a, b| (a.version.is_none(), a.version, &a.file).cmp(&(b.version.is_none(), b.version, &b.file)));
+        versions
+    }
+
+    /// The project to render: the one set active, else the newest version, else the
+    /// first unversioned project as before versioning
+    pub fn active(recording_path: &Path) -> Option<PathBuf> {
+        let blender_dir = recording_path.join("blender");
+        let pointed = fs::read_to_string(recording_path.join(ACTIVE_BLEND_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<ActiveBlend>(&content).ok())
+            .map(|active| blender_dir.join(active.file))
+            .filter(|path| path.is_file());
+        pointed.or_else(|| {
+            let files = blend_files(recording_path);
+            let newest = files
+                .iter()
+                .filter_map(|path| Some((version_number(&path.file_name()?.to_string_lossy())?, path)))
+                .max_by_key(|(version, _)| *version)
+                .map(|(_, path)| path.clone());
+            newest.or_else(|| files.into_iter().next())
+        })
+    }
+
+    pub fn set_active(recording_path: &Path, file: &str) -> Result<BlendVersion, String> {
+        let version = Self::list(recording_path)
+            .into_iter()
+            .find(|v| v.file == file)
+            .ok_or_else(|| format!("Blender project '{}' not found in blender directory", file))?;
+        let path = recording_path.join(ACTIVE_BLEND_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(&ActiveBlend { file: file.to_string() })
+            .map_err(|e| format!("Failed to serialize active blend: {}", e))?;
+        write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(BlendVersion { active: true, ..version })
+    }
+
+    /// Give every unversioned project the next version number, so a new setup can't
+    /// overwrite it, and make the last one active. Returns the versioned file names.
+    pub fn adopt(recording_path: &Path) -> Result<Vec<String>, String> {
+        let files = blend_files(recording_path);
+        let mut next = files
+            .iter()
+            .filter_map(|path| version_number(&path.file_name()?.to_string_lossy()))
+            .max()
+            .unwrap_or(0);
+        let mut unversioned: Vec<&PathBuf> = files
+            .iter()
+            .filter(|path| path.file_name().is_some_and(|name| version_number(&name.to_string_lossy()).is_none()))
+            .collect();
+        unversioned.sort_by_key(|path| fs::metadata(path).and_then(|m| m.modified()).ok());
+
+        let mut adopted = Vec::new();
+        for path in unversioned {
+            next += 1;
+            let file = format!("v{:03}_{}", next, path.file_name().unwrap_or_default().to_string_lossy());
+            let target = path.with_file_name(&file);
+            fs::rename(path, &target)
+                .map_err(|e| format!("Failed to version {} as {}: {}", path.display(), file, e))?;
+            adopted.push(file);
+        }
+        if let Some(file) = adopted.last() {
+            Self::set_active(recording_path, file)?;
+        }
+        Ok(adopted)
+    }
+}
+
+/// `3` for `v003_gig.blend`
+fn version_number(file: &str) -> Option<u32> {
+    let (version, _) = file.strip_prefix('v')?.split_once('_')?;
+    if version.len() < 3 {
+        return None;
+    }
+    version.parse().ok()
+}
+
+fn blend_files(recording_path: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(recording_path.join("blender"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "blend"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_adopt_and_switch_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        assert_eq!(BlendVersions::active(path), None);
+        fs::create_dir_all(path.join("blender")).unwrap();
+        fs::write(path.join("blender/gig.blend"), "first").unwrap();
+        fs::write(path.join("blender/gig.blend1"), "backup").unwrap();
+        // Before versioning the unversioned project is rendered
+        assert_eq!(BlendVersions::active(path), Some(path.join("blender/gig.blend")));
+
+        assert_eq!(BlendVersions::adopt(path).unwrap(), ["v001_gig.blend"]);
+        fs::write(path.join("blender/gig.blend"), "second").unwrap();
+        assert_eq!(BlendVersions::adopt(path).unwrap(), ["v002_gig.blend"]);
+        assert_eq!(BlendVersions::active(path), Some(path.join("blender/v002_gig.blend")));
+
+        BlendVersions::set_active(path, "v001_gig.blend").unwrap();
+        let versions = BlendVersions::list(path);
+        let summary: Vec<(&str, Option<u32>, bool)> = versions.iter().map(|v| (v.file.as_str(), v.version, v.active)).collect();
+        assert_eq!(summary, [("v001_gig.blend", Some(1), true), ("v002_gig.blend", Some(2), false)]);
+        assert!(BlendVersions::set_active(path, "other.blend").is_err());
+
+        // A deleted active project falls back to the newest version
+        fs::remove_file(path.join("blender/v001_gig.blend")).unwrap();
+        assert_eq!(BlendVersions::active(path), Some(path.join("blender/v002_gig.blend")));
+    }
+}
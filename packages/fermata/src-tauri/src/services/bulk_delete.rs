@@ -0,0 +1,268 @@
+use crate::models::RecordingStatus;
+use crate::services::{detect_in_progress_status, directory_size, storage_stage, FileScanner, StatusDetector, Trash, TrashEntry};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How long a previewed deletion can be confirmed
+pub const BULK_DELETE_TTL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BulkDeleteItem {
+    pub name: String,
+    pub size_bytes: u64,
+    /// Recordings that were never uploaded are worth a second look
+    pub uploaded: bool,
+}
+
+/// What a bulk delete would remove; confirm it with `token`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BulkDeletePreview {
+    pub token: String,
+    pub items: Vec<BulkDeleteItem>,
+    pub total_bytes: u64,
+    /// Requested names with no recording behind them
+    pub missing: Vec<String>,
+    pub expires_in_secs: u64,
+}
+
+/// What deleting one recording would remove; delete_recording confirms it with `token`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DeletePreview {
+    pub token: String,
+    pub name: String,
+    pub file_count: u64,
+    pub total_bytes: u64,
+    /// Bytes per storage stage, e.g. "raw_video" or "render"
+    pub stage_bytes: BTreeMap<String, u64>,
+    pub status: RecordingStatus,
+    /// Id of the queued job running on the recording; the delete is refused while it runs
+    pub running_job: Option<u64>,
+    pub expires_in_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BulkDeleteResult {
+    pub deleted: Vec<TrashEntry>,
+    pub freed_bytes: u64,
+    /// Recordings skipped because they changed or failed to move, with the reason
+    pub errors: Vec<String>,
+}
+
+struct StagedDelete {
+    items: Vec<BulkDeleteItem>,
+    staged_at: Instant,
+}
+
+/// Previewed bulk deletions waiting for confirmation, kept in Tauri state
+pub struct BulkDeleteStaging {
+    staged: Mutex<HashMap<String, StagedDelete>>,
+}
+
+impl Default for BulkDeleteStaging {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BulkDeleteStaging {
+    pub fn new() -> Self {
+        Self { staged: Mutex::new(HashMap::new()) }
+    }
+
+    /// Measure the recordings and stage them under a new token
+    pub fn preview(&self, roots: &[PathBuf], names: &[String]) -> Result<BulkDeletePreview, String> {
+        let mut items: Vec<BulkDeleteItem> = Vec::new();
+        let mut missing = Vec::new();
+        for name in names {
+            if items.iter().any(|i| i.name == *name) {
+                continue;
+            }
+            let Some(path) = FileScanner::find_root(roots, name).map(|root| root.join(name)) else {
+                missing.push(name.clone());
+                continue;
+            };
+            items.push(BulkDeleteItem {
+                name: name.clone(),
+                size_bytes: directory_size(&path),
+                uploaded: StatusDetector::detect_status(&path) == RecordingStatus::Uploaded,
+            });
+        }
+        if items.is_empty() {
+            return Err("No recordings to delete".to_string());
+        }
+
+        Ok(BulkDeletePreview {
+            total_bytes: items.iter().map(|i| i.size_bytes).sum(),
+            token: self.stage(items.clone()),
+            items,
+            missing,
+            expires_in_secs: BULK_DELETE_TTL.as_secs(),
+        })
+    }
+
+    /// Measure one recording in `recordings_path` and stage it under a new token
+    pub fn preview_recording(&self, recordings_path: &Path, name: &str, running_job: Option<u64>) -> Result<DeletePreview, String> {
+        let path = recordings_path.join(name);
+        if !path.is_dir() {
+            return Err(format!("Recording '{}' not found at path: {}", name, path.display()));
+        }
+
+        let mut file_count = 0;
+        let mut stage_bytes: BTreeMap<String, u64> = BTreeMap::new();
+        for entry in walkdir::WalkDir::new(&path).into_iter().flatten().filter(|e| e.file_type().is_file()) {
+            let relative = entry.path().strip_prefix(&path).unwrap_or(entry.path()).to_string_lossy().to_string();
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            file_count += 1;
+            *stage_bytes.entry(storage_stage(&relative).to_string()).or_default() += size;
+        }
+        let total_bytes = stage_bytes.values().sum();
+        let status = StatusDetector::detect_status(&path);
+        let item = BulkDeleteItem { name: name.to_string(), size_bytes: total_bytes, uploaded: status == RecordingStatus::Uploaded };
+
+        Ok(DeletePreview {
+            token: self.stage(vec![item]),
+            name: name.to_string(),
+            file_count,
+            total_bytes,
+            stage_bytes,
+            status,
+            running_job,
+            expires_in_secs: BULK_DELETE_TTL.as_secs(),
+        })
+    }
+
+    fn stage(&self, items: Vec<BulkDeleteItem>) -> String {
+        let token = new_token();
+        let mut staged = self.staged.lock().unwrap();
+        staged.retain(|_, s| s.staged_at.elapsed() < BULK_DELETE_TTL);
+        staged.insert(token.clone(), StagedDelete { items, staged_at: Instant::now() });
+        token
+    }
+
+    fn take(&self, token: &str) -> Result<StagedDelete, String> {
+        self.staged
+            .lock()
+            .unwrap()
+            .remove(token)
+            .filter(|s| s.staged_at.elapsed() < BULK_DELETE_TTL)
+            .ok_or_else(|| "Unknown or expired delete token; preview the deletion again".to_string())
+    }
+
+    /// Redeem the token of preview_recording; returns the previewed size of the recording
+    pub fn confirm_recording(&self, token: &str, name: &str) -> Result<u64, String> {
+        match self.take(token)?.items.as_slice() {
+            [item] if item.name == name => Ok(item.size_bytes),
+            _ => Err(format!("The delete token was not issued for '{}'", name)),
+        }
+    }
+
+    /// Move the recordings of a previewed deletion to the trash. A token works once;
+    /// recordings that are busy or grew since the preview are left alone.
+    pub fn execute(&self, roots: &[PathBuf], token: &str) -> Result<BulkDeleteResult, String> {
+        let staged = self.take(token)?;
+
+        let mut result = BulkDeleteResult { deleted: Vec::new(), freed_bytes: 0, errors: Vec::new() };
+        for item in staged.items {
+            let Some(recordings_path) = FileScanner::find_root(roots, &item.name) else {
+                result.errors.push(format!("{}: no longer exists", item.name));
+                continue;
+            };
+            let path = recordings_path.join(&item.name);
+            if let Some(status) = detect_in_progress_status(&path) {
+                result.errors.push(format!("{}: busy ({:?})", item.name, status));
+                continue;
+            }
+            if directory_size(&path) > item.size_bytes {
+                result.errors.push(format!("{}: changed since the preview", item.name));
+                continue;
+            }
+            match Trash::move_to_trash(recordings_path, &item.name) {
+                Ok(entry) => {
+                    result.freed_bytes += entry.size_bytes;
+                    result.deleted.push(entry);
+                }
+                Err(e) => result.errors.push(format!("{}: {}", item.name, e)),
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn new_token() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("{:x}-{:x}", nanos, std::process::id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn create_recording(root: &Path, name: &str, size: usize) {
+        fs::create_dir_all(root.join(name)).unwrap();
+        fs::write(root.join(name).join(format!("{}.mkv", name)), vec![0u8; size]).unwrap();
+    }
+
+    #[test]
+    fn test_preview_then_execute_once() {
+        let temp_dir = TempDir::new().unwrap();
+        create_recording(temp_dir.path(), "spring", 10);
+        create_recording(temp_dir.path(), "summer", 5);
+        let staging = BulkDeleteStaging::new();
+
+        let names = vec!["spring".to_string(), "summer".to_string(), "autumn".to_string()];
+        let preview = staging.preview(&[temp_dir.path().to_path_buf()], &names).unwrap();
+        assert_eq!(preview.total_bytes, 15);
+        assert_eq!(preview.missing, vec!["autumn"]);
+        assert!(temp_dir.path().join("spring").exists());
+
+        let result = staging.execute(&[temp_dir.path().to_path_buf()], &preview.token).unwrap();
+        assert_eq!(result.deleted.len(), 2);
+        assert_eq!(result.freed_bytes, 15);
+        assert!(!temp_dir.path().join("spring").exists());
+        assert!(staging.execute(&[temp_dir.path().to_path_buf()], &preview.token).is_err());
+    }
+
+    #[test]
+    fn test_preview_recording_token_is_bound_to_it() {
+        let temp_dir = TempDir::new().unwrap();
+        create_recording(temp_dir.path(), "spring", 10);
+        fs::create_dir_all(temp_dir.path().join("spring/extracted")).unwrap();
+        fs::write(temp_dir.path().join("spring/extracted/Mic.m4a"), vec![0u8; 4]).unwrap();
+        let staging = BulkDeleteStaging::new();
+
+        let preview = staging.preview_recording(temp_dir.path(), "spring", None).unwrap();
+        assert_eq!((preview.file_count, preview.total_bytes), (2, 14));
+        assert_eq!(preview.stage_bytes["raw_video"], 10);
+        assert_eq!(preview.stage_bytes["extracted"], 4);
+        assert!(staging.confirm_recording(&preview.token, "summer").is_err());
+
+        let preview = staging.preview_recording(temp_dir.path(), "spring", None).unwrap();
+        assert_eq!(staging.confirm_recording(&preview.token, "spring"), Ok(14));
+        assert!(staging.confirm_recording(&preview.token, "spring").is_err());
+        assert!(staging.preview_recording(temp_dir.path(), "autumn", None).is_err());
+    }
+
+    #[test]
+    fn test_recording_changed_after_preview_is_kept() {
+        let temp_dir = TempDir::new().unwrap();
+        create_recording(temp_dir.path(), "spring", 10);
+        let staging = BulkDeleteStaging::new();
+
+        let preview = staging.preview(&[temp_dir.path().to_path_buf()], &["spring".to_string()]).unwrap();
+        fs::write(temp_dir.path().join("spring/notes.txt"), "new").unwrap();
+
+        let result = staging.execute(&[temp_dir.path().to_path_buf()], &preview.token).unwrap();
+        assert!(result.deleted.is_empty());
+        assert_eq!(result.errors, vec!["spring: changed since the preview"]);
+        assert!(temp_dir.path().join("spring").exists());
+    }
+}
@@ -0,0 +1,182 @@
+use crate::services::{directory_size, StateManifest, CONFIG_HISTORY_DIR, FRAME_EXTENSIONS, STEP_LOGS_DIR, TRANSCODED_DIR};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Intermediate artifacts that can be removed once a recording no longer needs them
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum CleanupTarget {
+    /// extracted/ sources; re-extractable from the original .mkv
+    Extracted,
+    /// blender/ project files, without the render output
+    BlenderProject,
+    /// blender/render/, frames and final video
+    RenderOutput,
+    /// Frame images in blender/render/, keeping the final video
+    RenderFrames,
+    /// Per-profile transcodes made for uploading
+    Transcoded,
+    /// Stored step output logs
+    Logs,
+    /// Superseded animation configs
+    ConfigHistory,
+}
+
+impl CleanupTarget {
+    /// Removing these before the upload would move the recording back in the pipeline
+    pub fn requires_upload(&self) -> bool {
+        matches!(self, CleanupTarget::Extracted | CleanupTarget::BlenderProject | CleanupTarget::RenderOutput)
+    }
+
+    /// Existing files or directories of this target
+    fn paths(&self, recording_path: &Path) -> Vec<PathBuf> {
+        let candidates = match self {
+            CleanupTarget::Extracted => vec![recording_path.join("extracted")],
+            CleanupTarget::BlenderProject => entries(&recording_path.join("blender"))
+                .into_iter()
+                .filter(|p| p.file_name().is_some_and(|n| n != "render"))
+                .collect(),
+            CleanupTarget::RenderOutput => vec![recording_path.join("blender").join("render")],
+            CleanupTarget::RenderFrames => entries(&recording_path.join("blender").join("render"))
+                .into_iter()
+                .filter(|p| {
+                    p.extension()
+                        .and_then(|e| e.to_str())
+                        .is_some_and(|e| FRAME_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+                })
+                .collect(),
+            CleanupTarget::Transcoded => vec![recording_path.join(TRANSCODED_DIR)],
+            CleanupTarget::Logs => vec![recording_path.join(STEP_LOGS_DIR)],
+            CleanupTarget::ConfigHistory => vec![recording_path.join(CONFIG_HISTORY_DIR)],
+        };
+        candidates.into_iter().filter(|p| p.exists()).collect()
+    }
+}
+
+/// A file or directory removed (or that would be removed) by a cleanup
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CleanupItem {
+    pub target: CleanupTarget,
+    /// Path relative to the recording directory
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CleanupReport {
+    pub recording: String,
+    pub dry_run: bool,
+    pub items: Vec<CleanupItem>,
+    pub freed_bytes: u64,
+}
+
+pub struct Cleanup;
+
+impl Cleanup {
+    /// What the targets would remove from a recording
+    pub fn plan(recording_path: &Path, targets: &[CleanupTarget]) -> Vec<CleanupItem> {
+        let mut items: Vec<CleanupItem> = Vec::new();
+        for target in targets {
+            for path in target.paths(recording_path) {
+                let path_str = path.strip_prefix(recording_path).unwrap_or(&path).to_string_lossy().to_string();
+                // RenderOutput already covers RenderFrames
+                if items.iter().any(|i| Path::new(&path_str).starts_with(&i.path)) {
+                    continue;
+                }
+                items.retain(|i| !Path::new(&i.path).starts_with(&path_str));
+                items.push(CleanupItem {
+                    target: *target,
+                    size_bytes: if path.is_dir() { directory_size(&path) } else { file_size(&path) },
+                    path: path_str,
+                });
+            }
+        }
+        items
+    }
+
+    /// Remove the targets from a recording, or only report them when `dry_run` is set
+    pub fn run(
+        recording_path: &Path,
+        recording_name: &str,
+        targets: &[CleanupTarget],
+        dry_run: bool
+    ) -> Result<CleanupReport, String> {
+        let items = Self::plan(recording_path, targets);
+
+        if !dry_run {
+            for item in &items {
+                let path = recording_path.join(&item.path);
+                let result = if path.is_dir() { fs::remove_dir_all(&path) } else { fs::remove_file(&path) };
+                result.map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+            }
+            StateManifest::refresh(recording_path)?;
+        }
+
+        Ok(CleanupReport {
+            recording: recording_name.to_string(),
+            dry_run,
+            freed_bytes: items.iter().map(|i| i.size_bytes).sum(),
+            items,
+        })
+    }
+}
+
+fn entries(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    paths.sort();
+    paths
+}
+
+fn file_size(path: &Path) -> u64 {
+    fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_uploaded_recording(root: &Path) {
+        fs::create_dir_all(root.join("extracted")).unwrap();
+        fs::create_dir_all(root.join("blender/render")).unwrap();
+        fs::create_dir_all(root.join("uploads")).unwrap();
+        fs::write(root.join("extracted/Camera.mp4"), "camera").unwrap();
+        fs::write(root.join("blender/gig.blend"), "blend").unwrap();
+        fs::write(root.join("blender/render/0001.png"), "frame").unwrap();
+        fs::write(root.join("blender/render/final.mp4"), "video").unwrap();
+        fs::write(root.join("uploads/upload_results.json"), "{}").unwrap();
+    }
+
+    #[test]
+    fn test_dry_run_reports_without_removing() {
+        let temp_dir = TempDir::new().unwrap();
+        create_uploaded_recording(temp_dir.path());
+
+        let report = Cleanup::run(temp_dir.path(), "gig", &[CleanupTarget::Extracted, CleanupTarget::RenderFrames], true)
+            .unwrap();
+        assert_eq!(report.freed_bytes, 11);
+        assert_eq!(report.items.len(), 2);
+        assert!(temp_dir.path().join("extracted/Camera.mp4").exists());
+
+        let report = Cleanup::run(temp_dir.path(), "gig", &[CleanupTarget::RenderFrames], false).unwrap();
+        assert_eq!(report.freed_bytes, 5);
+        assert!(!temp_dir.path().join("blender/render/0001.png").exists());
+        assert!(temp_dir.path().join("blender/render/final.mp4").exists());
+    }
+
+    #[test]
+    fn test_overlapping_targets_counted_once() {
+        let temp_dir = TempDir::new().unwrap();
+        create_uploaded_recording(temp_dir.path());
+
+        let items = Cleanup::plan(
+            temp_dir.path(),
+            &[CleanupTarget::RenderFrames, CleanupTarget::RenderOutput, CleanupTarget::BlenderProject],
+        );
+        let paths: Vec<&str> = items.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(paths, vec!["blender/render", "blender/gig.blend"]);
+        assert_eq!(items[0].size_bytes, 10);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::services::{SettingsStore, Toolchain};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Settings key holding the configuration profiles and the one switched to
+pub const CONFIG_PROFILES_KEY: &str = "config_profiles";
+
+/// Picks the profile for one run, e.g. of fermata-cli, over the one switched to in the app
+pub const PROFILE_ENV: &str = "FERMATA_PROFILE";
+
+/// A named workspace setup, e.g. a dev checkout or the installed workspace.
+/// Unset fields keep the value from the environment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ConfigProfile {
+    pub name: String,
+    pub recordings_path: Option<PathBuf>,
+    pub workspace_root: Option<PathBuf>,
+    pub uv_path: Option<String>,
+    /// Becomes the toolchain setting when the profile is switched to
+    pub toolchain: Option<Toolchain>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ConfigProfiles {
+    /// Profile the app starts with; None runs on the environment alone
+    pub active: Option<String>,
+    pub profiles: Vec<ConfigProfile>,
+}
+
+impl ConfigProfile {
+    /// Checked when the profile is saved; the directories must exist by then
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Config profile name cannot be empty".to_string());
+        }
+        for (label, dir) in [("Recordings path", &self.recordings_path), ("Workspace root", &self.workspace_root)] {
+            if let Some(dir) = dir.as_ref().filter(|dir| !dir.is_dir()) {
+                return Err(format!("{} is not a directory: {}", label, dir.display()));
+            }
+        }
+        if self.uv_path.as_ref().is_some_and(|uv| uv.trim().is_empty()) {
+            return Err("uv path cannot be empty".to_string());
+        }
+        self.toolchain.as_ref().map_or(Ok(()), Toolchain::validate)
+    }
+}
+
+impl ConfigProfiles {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(CONFIG_PROFILES_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        settings.set(CONFIG_PROFILES_KEY, self)
+    }
+
+    pub fn find(&self, name: &str) -> Option<&ConfigProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Name of the profile to run with: FERMATA_PROFILE when set, else the one switched to
+    pub fn active_name(&self) -> Option<String> {
+        std::env::var(PROFILE_ENV)
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .or_else(|| self.active.clone())
+    }
+
+    /// Add a profile or replace the one with the same name
+    pub fn upsert(&mut self, profile: ConfigProfile) -> Result<(), String> {
+        profile.validate()?;
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<(), String> {
+        if self.active.as_deref() == Some(name) {
+            return Err(format!("Config profile '{}' is active; switch to another one first", name));
+        }
+        let before = self.profiles.len();
+        self.profiles.retain(|p| p.name != name);
+        if self.profiles.len() == before {
+            return Err(format!("Config profile '{}' not found", name));
+        }
+        Ok(())
+    }
+
+    /// Make a profile the one the app starts with, or go back to the environment with None
+    pub fn switch(&mut self, name: Option<&str>) -> Result<Option<&ConfigProfile>, String> {
+        if let Some(name) = name {
+            if self.find(name).is_none() {
+                return Err(format!("Config profile '{}' not found", name));
+            }
+        }
+        self.active = name.map(str::to_string);
+        Ok(name.and_then(|name| self.find(name)))
+    }
+
+    /// Point the profiles using `old_root` at `new_root` after the recordings moved there
+    pub fn move_recordings_path(&mut self, old_root: &Path, new_root: &Path) -> bool {
+        let mut moved = false;
+        for profile in &mut self.profiles {
+            if profile.recordings_path.as_deref() == Some(old_root) {
+                profile.recordings_path = Some(new_root.to_path_buf());
+                moved = true;
+            }
+        }
+        moved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_upsert_switch_and_remove() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+        let mut profiles = ConfigProfiles::load(&settings).unwrap();
+
+        let dev = ConfigProfile {
+            name: "dev".to_string(),
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            toolchain: Some(Toolchain::Poetry),
+            ..ConfigProfile::default()
+        };
+        profiles.upsert(dev.clone()).unwrap();
+        let missing = ConfigProfile { name: "nas".to_string(), recordings_path: Some(temp_dir.path().join("nas")), ..ConfigProfile::default() };
+        assert!(profiles.upsert(missing).is_err());
+        profiles.upsert(ConfigProfile { uv_path: Some("/opt/uv".to_string()), ..dev }).unwrap();
+        assert_eq!(profiles.profiles.len(), 1);
+
+        assert!(profiles.switch(Some("installed")).is_err());
+        assert_eq!(profiles.switch(Some("dev")).unwrap().and_then(|p| p.uv_path.as_deref()), Some("/opt/uv"));
+        assert!(profiles.remove("dev").is_err());
+        profiles.save(&settings).unwrap();
+
+        let mut loaded = ConfigProfiles::load(&settings).unwrap();
+        assert_eq!(loaded.active.as_deref(), Some("dev"));
+        loaded.switch(None).unwrap();
+        loaded.remove("dev").unwrap();
+        assert!(loaded.profiles.is_empty());
+    }
+}
@@ -0,0 +1,324 @@
+use crate::services::write_atomic;
+use chrono::Local;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Current animation configs, one per preset
+pub const CONFIGS_DIR: &str = ".fermata/configs";
+
+/// Superseded configs, named `animation_config_<preset>_<YYYYmmdd-HHMMSS>.yaml`
+pub const CONFIG_HISTORY_DIR: &str = ".fermata/configs/history";
+
+/// Superseded versions kept per preset unless asked otherwise
+pub const DEFAULT_CONFIG_HISTORY: usize = 5;
+
+/// A superseded config kept in the history
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ConfigVersion {
+    pub preset: String,
+    /// Path relative to the recording directory
+    pub file: String,
+    /// Local time it was superseded, as in the file name
+    pub superseded_at: String,
+}
+
+/// Keeps the `animation_config_<preset>.yaml` files cinemon generates out of the
+/// recording root, where they would clutter the directory status detection reads
+pub struct ConfigStore;
+
+impl ConfigStore {
+    pub fn file_name(preset: &str) -> String {
+        format!("animation_config_{}.yaml", preset)
+    }
+
+    /// Where the current config of a preset is stored
+    pub fn config_path(recording_path: &Path, preset: &str) -> PathBuf {
+        recording_path.join(CONFIGS_DIR).join(Self::file_name(preset))
+    }
+
+    /// Current configs with their presets; configs still at the recording root
+    /// (generated before the store existed) count unless the store has the same preset
+    pub fn configs(recording_path: &Path) -> Vec<(PathBuf, String)> {
+        let mut configs = configs_in(&recording_path.join(CONFIGS_DIR));
+        for (path, preset) in configs_in(recording_path) {
+            if !configs.iter().any(|(_, p)| *p == preset) {
+                configs.push((path, preset));
+            }
+        }
+        configs.sort_by(|a, b| a.1.cmp(&b.1));
+        configs
+    }
+
+    /// The most recently written current config, the one a blend setup uses by default
+    pub fn latest(recording_path: &Path) -> Option<(PathBuf, String)> {
+        Self::configs(recording_path).into_iter().max_by_key(|(path, _)| modified(path))
+    }
+
+    /// Move a config cinemon just generated at the recording root into the store,
+    /// archiving the config it replaces. Returns the new path and the archived one.
+    pub fn adopt(recording_path: &Path, preset: &str) -> Result<(PathBuf, Option<PathBuf>), String> {
+        Self::adopt_file(recording_path, &recording_path.join(Self::file_name(preset)), preset)
+    }
+
+    /// The config cinemon just wrote: the path it reported, else the usual name at the
+    /// recording root, else the newest `animation_config*.yaml` there
+    pub fn find_generated(recording_path: &Path, preset: &str, reported: Option<&Path>) -> Option<PathBuf> {
+        if let Some(reported) = reported.filter(|path| path.is_file()) {
+            return Some(reported.to_path_buf());
+        }
+        let expected = recording_path.join(Self::file_name(preset));
+        if expected.is_file() {
+            return Some(expected);
+        }
+        fs::read_dir(recording_path)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                path.is_file() && name.starts_with("animation_config") && (name.ends_with(".yaml") || name.ends_with(".yml"))
+            })
+            .max_by_key(|path| modified(path))
+    }
+
+    /// Like `adopt`, for a config cinemon reported writing at `generated`
+    pub fn adopt_file(recording_path: &Path, generated: &Path, preset: &str) -> Result<(PathBuf, Option<PathBuf>), String> {
+        let target = Self::config_path(recording_path, preset);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+
+        let archived = if target.exists() { Some(Self::archive(recording_path, preset)?) } else { None };
+        fs::rename(generated, &target)
+            .map_err(|e| format!("Failed to move {} to {}: {}", generated.display(), target.display(), e))?;
+        log::info!("🗂️ Stored {} in {}", Self::file_name(preset), CONFIGS_DIR);
+        Ok((target, archived))
+    }
+
+    /// Store a config edited by the user, archiving the version it replaces.
+    /// Returns the new path and the archived one.
+    pub fn save_edited(recording_path: &Path, preset: &str, content: &str) -> Result<(PathBuf, Option<PathBuf>), String> {
+        // A legacy config at the root is the version being replaced
+        if recording_path.join(Self::file_name(preset)).exists() {
+            Self::adopt(recording_path, preset)?;
+        }
+
+        let target = Self::config_path(recording_path, preset);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let archived = if target.exists() { Some(Self::archive(recording_path, preset)?) } else { None };
+        write_atomic(&target, content).map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        log::info!("🗂️ Saved edited {}", Self::file_name(preset));
+        Ok((target, archived))
+    }
+
+    /// Move the current config of a preset into the history
+    fn archive(recording_path: &Path, preset: &str) -> Result<PathBuf, String> {
+        let dir = recording_path.join(CONFIG_HISTORY_DIR);
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+        let timestamp = Local::now().format("%Y%m%d-%H%M%S");
+        let mut archived = dir.join(format!("animation_config_{}_{}.yaml", preset, timestamp));
+        let mut counter = 1;
+        while archived.exists() {
+            counter += 1;
+            archived = dir.join(format!("animation_config_{}_{}-{}.yaml", preset, timestamp, counter));
+        }
+
+        let current = Self::config_path(recording_path, preset);
+        fs::rename(&current, &archived)
+            .map_err(|e| format!("Failed to archive {}: {}", current.display(), e))?;
+        Ok(archived)
+    }
+
+    /// Superseded configs, newest first
+    pub fn history(recording_path: &Path) -> Vec<ConfigVersion> {
+        let mut versions: Vec<ConfigVersion> = fs::read_dir(recording_path.join(CONFIG_HISTORY_DIR))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| {
+                        let name = entry.file_name().to_string_lossy().to_string();
+                        let stem = name.strip_prefix("animation_config_")?.strip_suffix(".yaml")?;
+                        let (preset, superseded_at) = stem.rsplit_once('_')?;
+                        Some(ConfigVersion {
+                            preset: preset.to_string(),
+                            file: format!("{}/{}", CONFIG_HISTORY_DIR, name),
+                            superseded_at: superseded_at.to_string(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        versions.sort_by(|a, b| b.superseded_at.cmp(&a.superseded_at).then_with(|| a.preset.cmp(&b.preset)));
+        versions
+    }
+
+    /// Remove superseded configs: history beyond the newest `keep` per preset and
+    /// leftover root configs the store already has a newer version of
+    pub fn prune(recording_path: &Path, keep: usize) -> Result<Vec<String>, String> {
+        let mut stale: Vec<String> = Vec::new();
+
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for version in Self::history(recording_path) {
+            let count = seen.entry(version.preset.clone()).or_default();
+            *count += 1;
+            if *count > keep {
+                stale.push(version.file);
+            }
+        }
+
+        // A root config newer than the stored one was generated outside fermata; keep it
+        for (path, preset) in configs_in(recording_path) {
+            let stored = modified(&Self::config_path(recording_path, &preset));
+            if stored.is_some() && modified(&path) <= stored {
+                if let Some(name) = path.file_name() {
+                    stale.push(name.to_string_lossy().to_string());
+                }
+            }
+        }
+
+        for file in &stale {
+            let path = recording_path.join(file);
+            fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+        }
+        stale.sort();
+        Ok(stale)
+    }
+}
+
+/// `animation_config_<preset>.yaml` files directly in `dir`
+fn configs_in(dir: &Path) -> Vec<(PathBuf, String)> {
+    let mut configs: Vec<(PathBuf, String)> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_file())
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let preset = name.strip_prefix("animation_config_")?.strip_suffix(".yaml")?.to_string();
+                    Some((entry.path(), preset))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    configs.sort();
+    configs
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn generate(recording: &Path, preset: &str, content: &str) {
+        fs::write(recording.join(ConfigStore::file_name(preset)), content).unwrap();
+    }
+
+    #[test]
+    fn test_adopt_archives_previous_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+
+        generate(recording, "beat-switch", "v1");
+        let (path, archived) = ConfigStore::adopt(recording, "beat-switch").unwrap();
+        assert!(archived.is_none());
+        assert!(!recording.join("animation_config_beat-switch.yaml").exists());
+
+        generate(recording, "beat-switch", "v2");
+        let (_, archived) = ConfigStore::adopt(recording, "beat-switch").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v2");
+        assert_eq!(fs::read_to_string(archived.unwrap()).unwrap(), "v1");
+
+        let history = ConfigStore::history(recording);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].preset, "beat-switch");
+        assert_eq!(ConfigStore::configs(recording), vec![(path, "beat-switch".to_string())]);
+    }
+
+    #[test]
+    fn test_save_edited_archives_legacy_and_stored_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+        generate(recording, "minimal", "legacy");
+
+        let (path, archived) = ConfigStore::save_edited(recording, "minimal", "edited").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "edited");
+        assert_eq!(fs::read_to_string(archived.unwrap()).unwrap(), "legacy");
+        assert!(!recording.join("animation_config_minimal.yaml").exists());
+
+        let (_, archived) = ConfigStore::save_edited(recording, "minimal", "edited again").unwrap();
+        assert_eq!(fs::read_to_string(archived.unwrap()).unwrap(), "edited");
+        assert_eq!(ConfigStore::history(recording).len(), 2);
+    }
+
+    #[test]
+    fn test_configs_include_legacy_root_configs() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+        generate(recording, "minimal", "legacy");
+
+        let configs = ConfigStore::configs(recording);
+        assert_eq!(configs, vec![(recording.join("animation_config_minimal.yaml"), "minimal".to_string())]);
+    }
+
+    #[test]
+    fn test_find_generated_falls_back_to_newest_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+        let reported = recording.join("out").join("config.yaml");
+        assert_eq!(ConfigStore::find_generated(recording, "minimal", Some(&reported)), None);
+
+        fs::write(recording.join("animation_config.yaml"), "older").unwrap();
+        fs::write(recording.join("animation_config-minimal.yml"), "newer").unwrap();
+        let hour_ago = SystemTime::now() - std::time::Duration::from_secs(3600);
+        fs::File::options().write(true).open(recording.join("animation_config.yaml")).unwrap().set_modified(hour_ago).unwrap();
+        assert_eq!(ConfigStore::find_generated(recording, "minimal", Some(&reported)), Some(recording.join("animation_config-minimal.yml")));
+
+        generate(recording, "minimal", "expected");
+        assert_eq!(ConfigStore::find_generated(recording, "minimal", None), Some(recording.join("animation_config_minimal.yaml")));
+
+        fs::create_dir_all(reported.parent().unwrap()).unwrap();
+        fs::write(&reported, "reported").unwrap();
+        assert_eq!(ConfigStore::find_generated(recording, "minimal", Some(&reported)), Some(reported));
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_versions_per_preset() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+        let history = recording.join(CONFIG_HISTORY_DIR);
+        fs::create_dir_all(&history).unwrap();
+        for stamp in ["20240101-100000", "20240102-100000", "20240103-100000"] {
+            fs::write(history.join(format!("animation_config_beat-switch_{}.yaml", stamp)), "").unwrap();
+        }
+        fs::write(history.join("animation_config_minimal_20240101-100000.yaml"), "").unwrap();
+
+        generate(recording, "minimal", "leftover");
+        let stored = ConfigStore::config_path(recording, "minimal");
+        fs::create_dir_all(stored.parent().unwrap()).unwrap();
+        fs::copy(recording.join("animation_config_minimal.yaml"), &stored).unwrap();
+        generate(recording, "beat-switch", "edited by hand, never stored");
+        fs::write(recording.join("metadata.json"), "{}").unwrap();
+
+        let removed = ConfigStore::prune(recording, 1).unwrap();
+        assert_eq!(
+            removed,
+            vec![
+                ".fermata/configs/history/animation_config_beat-switch_20240101-100000.yaml",
+                ".fermata/configs/history/animation_config_beat-switch_20240102-100000.yaml",
+                "animation_config_minimal.yaml",
+            ]
+        );
+        assert!(history.join("animation_config_minimal_20240101-100000.yaml").exists());
+        assert!(recording.join("metadata.json").exists());
+    }
+}
@@ -0,0 +1,256 @@
+use crate::services::{write_atomic, ConfigStore};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Present while the .blend project was set up from sources that changed since
+pub const SETUP_STALE_FILE: &str = ".fermata/setup_stale.json";
+
+/// Same list as setka_common FileExtensions.VIDEO, which cinemon uses for discovery
+const VIDEO_EXTENSIONS: [&str; 7] = ["mp4", "mkv", "avi", "mov", "flv", "wmv", "webm"];
+
+/// Values cinemon derives from the recording itself; never carried over from an old config
+const GENERATED_KEYS: [&[&str]; 4] = [
+    &["project", "base_directory"],
+    &["project", "video_files"],
+    &["project", "output_blend"],
+    &["audio_analysis", "file"],
+];
+
+/// Difference between an animation config and the sources currently extracted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConfigDrift {
+    /// Config file name, e.g. animation_config_beat-switch.yaml
+    pub config: String,
+    pub preset: String,
+    pub added_sources: Vec<String>,
+    pub removed_sources: Vec<String>,
+}
+
+pub struct ConfigSync;
+
+impl ConfigSync {
+    /// Animation configs in a recording, with the preset each was generated from
+    pub fn configs(recording_path: &Path) -> Vec<(PathBuf, String)> {
+        ConfigStore::configs(recording_path)
+    }
+
+    /// Configs whose video_files no longer match extracted/
+    pub fn detect(recording_path: &Path) -> Vec<ConfigDrift> {
+        let current = extracted_videos(recording_path);
+
+        Self::configs(recording_path)
+            .into_iter()
+            .filter_map(|(path, preset)| {
+                let config = read_yaml(&path).ok()?;
+                let configured = video_files(&config);
+                let added_sources: Vec<String> =
+                    current.iter().filter(|f| !configured.contains(f)).cloned().collect();
+                let removed_sources: Vec<String> =
+                    configured.iter().filter(|f| !current.contains(f)).cloned().collect();
+
+                if added_sources.is_empty() && removed_sources.is_empty() {
+                    return None;
+                }
+                Some(ConfigDrift {
+                    config: path.file_name()?.to_string_lossy().to_string(),
+                    preset,
+                    added_sources,
+                    removed_sources,
+                })
+            })
+            .collect()
+    }
+
+    /// Mark the blend setup stale when sources drifted. The mark stays until
+    /// setup render runs again, even if the config itself gets regenerated.
+    pub fn mark_if_drifted(recording_path: &Path) -> Result<Vec<ConfigDrift>, String> {
+        let drifts = Self::detect(recording_path);
+        if drifts.is_empty() {
+            return Ok(drifts);
+        }
+
+        let path = recording_path.join(SETUP_STALE_FILE);
+        let content = serde_json::to_string_pretty(&drifts)
+            .map_err(|e| format!("Failed to serialize config drift: {}", e))?;
+        // Rewriting an unchanged marker would only wake the watcher again
+        if fs::read_to_string(&path).ok().as_deref() == Some(content.as_str()) {
+            return Ok(drifts);
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        log::info!("🧩 Blend setup of {} is stale: sources changed", recording_path.display());
+        Ok(drifts)
+    }
+
+    pub fn is_setup_stale(recording_path: &Path) -> bool {
+        recording_path.join(SETUP_STALE_FILE).exists()
+    }
+
+    pub fn clear_stale(recording_path: &Path) {
+        let _ = fs::remove_file(recording_path.join(SETUP_STALE_FILE));
+    }
+}
+
+/// Overlay the user's old config onto a freshly generated one: everything the user
+/// could have edited is kept, except values cinemon derives from the recording and
+/// animations of sources that no longer exist.
+pub fn merge_preserving_overrides(old: &Value, generated: &Value, removed_sources: &[String]) -> Value {
+    let mut merged = generated.clone();
+    overlay(&mut merged, old, &mut Vec::new(), removed_sources);
+    merged
+}
+
+fn overlay(target: &mut Value, old: &Value, path: &mut Vec<String>, removed_sources: &[String]) {
+    let (Value::Mapping(target_map), Value::Mapping(old_map)) = (target, old) else {
+        return;
+    };
+
+    for (key, old_value) in old_map {
+        let Some(key_name) = key.as_str() else {
+            continue;
+        };
+        path.push(key_name.to_string());
+
+        let generated = GENERATED_KEYS
+            .iter()
+            .any(|keys| keys.len() == path.len() && keys.iter().zip(path.iter()).all(|(a, b)| a == b));
+        let removed_source = path.len() == 2 && path[0] == "strip_animations" && removed_sources.contains(&path[1]);
+
+        if !generated && !removed_source {
+            let recurse = old_value.is_mapping() && target_map.get(key).is_some_and(|v| v.is_mapping());
+            if recurse {
+                if let Some(existing) = target_map.get_mut(key) {
+                    overlay(existing, old_value, path, removed_sources);
+                }
+            } else {
+                target_map.insert(key.clone(), old_value.clone());
+            }
+        }
+        path.pop();
+    }
+}
+
+pub fn read_yaml(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_yaml::from_str(&content).map_err(|e| format!("Invalid YAML in {}: {}", path.display(), e))
+}
+
+pub fn write_yaml(path: &Path, value: &Value) -> Result<(), String> {
+    let content = serde_yaml::to_string(value).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    write_atomic(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// `project.video_files` of a config
+pub fn video_files(config: &Value) -> Vec<String> {
+    config
+        .get("project")
+        .and_then(|p| p.get("video_files"))
+        .and_then(|v| v.as_sequence())
+        .map(|files| files.iter().filter_map(|f| f.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+fn extracted_videos(recording_path: &Path) -> Vec<String> {
+    let mut videos: Vec<String> = fs::read_dir(recording_path.join("extracted"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.extension()
+                        .and_then(|e| e.to_str())
+                        .is_some_and(|e| VIDEO_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+                })
+                .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    videos.sort();
+    videos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const OLD_CONFIG: &str = r#"
+project:
+  base_directory: /old/gig
+  video_files:
+    - Camera.mp4
+    - Screen.mp4
+  fps: 60
+layout:
+  type: random
+  config:
+    margin: 0.3
+strip_animations:
+  Camera.mp4:
+    - type: shake
+      intensity: 9.0
+  Screen.mp4:
+    - type: scale
+"#;
+
+    #[test]
+    fn test_detect_new_sources() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path().join("gig");
+        fs::create_dir_all(recording.join("extracted")).unwrap();
+        fs::write(recording.join("extracted/Camera.mp4"), "").unwrap();
+        fs::write(recording.join("extracted/Phone.mp4"), "").unwrap();
+        fs::write(recording.join("extracted/Mic.m4a"), "").unwrap();
+        fs::write(recording.join("animation_config_beat-switch.yaml"), OLD_CONFIG).unwrap();
+
+        let drifts = ConfigSync::mark_if_drifted(&recording).unwrap();
+        assert_eq!(drifts.len(), 1);
+        assert_eq!(drifts[0].preset, "beat-switch");
+        assert_eq!(drifts[0].added_sources, vec!["Phone.mp4"]);
+        assert_eq!(drifts[0].removed_sources, vec!["Screen.mp4"]);
+        assert!(ConfigSync::is_setup_stale(&recording));
+
+        ConfigSync::clear_stale(&recording);
+        assert!(!ConfigSync::is_setup_stale(&recording));
+    }
+
+    #[test]
+    fn test_merge_keeps_user_edits() {
+        let old: Value = serde_yaml::from_str(OLD_CONFIG).unwrap();
+        let generated: Value = serde_yaml::from_str(
+            r#"
+project:
+  base_directory: /new/gig
+  video_files: [Camera.mp4, Phone.mp4]
+  fps: 30
+layout:
+  type: random
+  config:
+    margin: 0.15
+    overlap_allowed: false
+strip_animations:
+  Camera.mp4:
+    - type: scale
+  Phone.mp4:
+    - type: scale
+"#,
+        )
+        .unwrap();
+
+        let merged = merge_preserving_overrides(&old, &generated, &["Screen.mp4".to_string()]);
+        assert_eq!(video_files(&merged), vec!["Camera.mp4", "Phone.mp4"]);
+        assert_eq!(merged["project"]["base_directory"].as_str(), Some("/new/gig"));
+        assert_eq!(merged["project"]["fps"].as_u64(), Some(60));
+        assert_eq!(merged["layout"]["config"]["margin"].as_f64(), Some(0.3));
+        assert_eq!(merged["layout"]["config"]["overlap_allowed"].as_bool(), Some(false));
+        assert_eq!(merged["strip_animations"]["Camera.mp4"][0]["type"].as_str(), Some("shake"));
+        assert!(merged["strip_animations"].get("Phone.mp4").is_some());
+        assert!(merged["strip_animations"].get("Screen.mp4").is_none());
+    }
+}
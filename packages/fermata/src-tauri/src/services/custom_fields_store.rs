@@ -0,0 +1,69 @@
+use crate::models::CustomFieldValue;
+use crate::services::write_atomic;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Custom field values live next to the recording so they move with it on rename
+pub const CUSTOM_FIELDS_FILE: &str = ".fermata/custom_fields.json";
+
+pub type CustomFields = BTreeMap<String, CustomFieldValue>;
+
+pub struct CustomFieldsStore;
+
+impl CustomFieldsStore {
+    fn file_path(recording_path: &Path) -> PathBuf {
+        recording_path.join(CUSTOM_FIELDS_FILE)
+    }
+
+    /// Load all custom field values for a recording (empty if none were set)
+    pub fn load(recording_path: &Path) -> Result<CustomFields, String> {
+        let path = Self::file_path(recording_path);
+        if !path.exists() {
+            return Ok(CustomFields::new());
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read custom fields {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid custom fields file {}: {}", path.display(), e))
+    }
+
+    /// Persist custom field values for a recording
+    pub fn save(recording_path: &Path, fields: &CustomFields) -> Result<(), String> {
+        let path = Self::file_path(recording_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+
+        let content = serde_json::to_string_pretty(fields)
+            .map_err(|e| format!("Failed to serialize custom fields: {}", e))?;
+        write_atomic(&path, content)
+            .map_err(|e| format!("Failed to write custom fields {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(CustomFieldsStore::load(temp_dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut fields = CustomFields::new();
+        fields.insert("client".to_string(), CustomFieldValue::Text("ACME".to_string()));
+        fields.insert("paid".to_string(), CustomFieldValue::Boolean(true));
+
+        CustomFieldsStore::save(temp_dir.path(), &fields).unwrap();
+
+        assert_eq!(CustomFieldsStore::load(temp_dir.path()).unwrap(), fields);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::models::{NextStep, Recording, RecordingStatus};
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path};
+
+/// Settings key holding the custom step definitions
+pub const CUSTOM_STEPS_KEY: &str = "custom_steps";
+
+/// Built-in steps a custom step can follow
+pub const CUSTOM_STEP_ANCHORS: [&str; 4] = ["extract", "analyze", "setup_render", "render"];
+
+/// "Generate subtitles after render": a user command the pipeline runs like a built-in step
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomStep {
+    /// Step id, e.g. "subtitles"; lowercase letters, digits, `_` and `-`
+    pub id: String,
+    /// Shown in the UI, e.g. "Generate subtitles"
+    pub name: String,
+    /// Built-in step it follows, one of CUSTOM_STEP_ANCHORS
+    pub after: String,
+    /// Shell command with the placeholders of step hooks, e.g. "whisper.sh {recording_path}"
+    pub command: String,
+    /// Paths relative to the recording that must exist before the step can run;
+    /// a `*` in the file name matches any part of it, e.g. "extracted/*.m4a"
+    #[serde(default)]
+    pub inputs: Vec<String>,
+    /// Paths whose presence marks the step as done, e.g. "subtitles/*.srt"
+    pub outputs: Vec<String>,
+}
+
+impl CustomStep {
+    pub fn validate(&self) -> Result<(), String> {
+        let valid_id = !self.id.is_empty()
+            && self.id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+        if !valid_id {
+            return Err(format!("Invalid step id '{}': use lowercase letters, digits, _ and -", self.id));
+        }
+        if self.id.parse::<NextStep>().is_ok() {
+            return Err(format!("'{}' is a built-in step", self.id));
+        }
+        if !CUSTOM_STEP_ANCHORS.contains(&self.after.as_str()) {
+            return Err(format!(
+                "Step '{}' cannot follow '{}', expected one of: {}",
+                self.id,
+                self.after,
+                CUSTOM_STEP_ANCHORS.join(", ")
+            ));
+        }
+        if self.command.trim().is_empty() {
+            return Err(format!("Step '{}' has no command", self.id));
+        }
+        if self.outputs.is_empty() {
+            return Err(format!("Step '{}' needs at least one output marker", self.id));
+        }
+        for marker in self.inputs.iter().chain(&self.outputs) {
+            let inside = Path::new(marker).components().all(|c| matches!(c, Component::Normal(_)));
+            if marker.is_empty() || !inside {
+                return Err(format!("Marker '{}' of step '{}' must be a path inside the recording", marker, self.id));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn next_step(&self) -> NextStep {
+        NextStep::Custom(self.id.clone())
+    }
+
+    pub fn inputs_ready(&self, recording_path: &Path) -> bool {
+        self.inputs.iter().all(|marker| marker_exists(recording_path, marker))
+    }
+
+    pub fn is_done(&self, recording_path: &Path) -> bool {
+        self.outputs.iter().all(|marker| marker_exists(recording_path, marker))
+    }
+
+    /// The step it follows has finished and its own outputs are still missing
+    pub fn is_pending(&self, recording: &Recording) -> bool {
+        completed_step(&recording.status) == Some(self.after.as_str())
+            && self.inputs_ready(&recording.path)
+            && !self.is_done(&recording.path)
+    }
+
+    /// Can be started by hand, also to redo it
+    pub fn can_run(&self, recording: &Recording) -> bool {
+        !recording.status.is_in_progress() && self.inputs_ready(&recording.path)
+    }
+}
+
+pub struct CustomSteps;
+
+impl CustomSteps {
+    pub fn list(settings: &SettingsStore) -> Result<Vec<CustomStep>, String> {
+        settings.get(CUSTOM_STEPS_KEY)
+    }
+
+    pub fn save(settings: &SettingsStore, steps: &[CustomStep]) -> Result<(), String> {
+        for (index, step) in steps.iter().enumerate() {
+            step.validate()?;
+            if steps[..index].iter().any(|other| other.id == step.id) {
+                return Err(format!("Step id '{}' is used twice", step.id));
+            }
+        }
+        settings.set(CUSTOM_STEPS_KEY, &steps)
+    }
+
+    pub fn find<'a>(steps: &'a [CustomStep], id: &str) -> Option<&'a CustomStep> {
+        steps.iter().find(|step| step.id.eq_ignore_ascii_case(id))
+    }
+
+    /// Next step of a recording: a pending custom step runs before the next built-in step
+    pub fn next_step(recording: &Recording, steps: &[CustomStep]) -> Option<NextStep> {
+        steps
+            .iter()
+            .find(|step| step.is_pending(recording))
+            .map(CustomStep::next_step)
+            .or_else(|| recording.get_next_step())
+    }
+
+    /// Built-in steps available for a recording followed by the custom steps it can run,
+    /// with a pending custom step first
+    pub fn available_steps(recording: &Recording, steps: &[CustomStep]) -> Vec<String> {
+        let mut available = recording.get_available_steps();
+        if let Some(NextStep::Custom(id)) = Self::next_step(recording, steps) {
+            available.insert(0, id);
+        }
+        for step in steps.iter().filter(|step| step.can_run(recording)) {
+            if !available.contains(&step.id) {
+                available.push(step.id.clone());
+            }
+        }
+        available
+    }
+}
+
+/// Last built-in step a status shows as finished, among the steps custom steps can follow
+fn completed_step(status: &RecordingStatus) -> Option<&'static str> {
+    match status {
+        RecordingStatus::Extracted => Some("extract"),
+        RecordingStatus::Analyzed => Some("analyze"),
+        RecordingStatus::SetupRendered => Some("setup_render"),
+        RecordingStatus::Rendered => Some("render"),
+        _ => None,
+    }
+}
+
+/// A marker path relative to the recording; a `*` in the file name matches any part of it
+fn marker_exists(recording_path: &Path, marker: &str) -> bool {
+    let path = recording_path.join(marker);
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let Some((prefix, suffix)) = name.split_once('*') else {
+        return path.exists();
+    };
+    let Some(parent) = path.parent() else {
+        return false;
+    };
+    fs::read_dir(parent)
+        .map(|entries| {
+            entries.flatten().any(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.len() >= prefix.len() + suffix.len() && name.starts_with(prefix) && name.ends_with(suffix)
+            })
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn subtitles() -> CustomStep {
+        CustomStep {
+            id: "subtitles".to_string(),
+            name: "Generate subtitles".to_string(),
+            after: "render".to_string(),
+            command: "whisper.sh {recording_path}".to_string(),
+            inputs: vec!["extracted/*.m4a".to_string()],
+            outputs: vec!["subtitles/*.srt".to_string()],
+        }
+    }
+
+    fn recording(path: &Path, status: RecordingStatus) -> Recording {
+        Recording {
+            name: "gig".to_string(),
+            path: path.to_path_buf(),
+            status,
+            last_updated: 0,
+            file_sizes: HashMap::new(),
+            upload_block: None,
+            root: path.to_path_buf(),
+            lifecycle: None,
+            media: None,
+            tree: None,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(subtitles().validate().is_ok());
+        assert!(CustomStep { id: "render".to_string(), ..subtitles() }.validate().is_err());
+        assert!(CustomStep { id: "Sub Titles".to_string(), ..subtitles() }.validate().is_err());
+        assert!(CustomStep { after: "upload".to_string(), ..subtitles() }.validate().is_err());
+        assert!(CustomStep { outputs: vec!["../elsewhere.srt".to_string()], ..subtitles() }.validate().is_err());
+        assert!(CustomStep { outputs: Vec::new(), ..subtitles() }.validate().is_err());
+    }
+
+    #[test]
+    fn test_custom_step_runs_between_render_and_upload() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        let steps = vec![subtitles()];
+
+        // Inputs missing: the step is skipped
+        let rendered = recording(path, RecordingStatus::Rendered);
+        assert_eq!(CustomSteps::next_step(&rendered, &steps), Some(NextStep::Upload));
+
+        fs::create_dir_all(path.join("extracted")).unwrap();
+        fs::write(path.join("extracted/Mic.m4a"), "audio").unwrap();
+        assert_eq!(CustomSteps::next_step(&rendered, &steps), Some(NextStep::Custom("subtitles".to_string())));
+        assert_eq!(CustomSteps::available_steps(&rendered, &steps)[0], "subtitles");
+        assert_eq!(
+            CustomSteps::next_step(&recording(path, RecordingStatus::SetupRendered), &steps),
+            Some(NextStep::Render)
+        );
+
+        fs::create_dir_all(path.join("subtitles")).unwrap();
+        fs::write(path.join("subtitles/gig.srt"), "1").unwrap();
+        assert_eq!(CustomSteps::next_step(&rendered, &steps), Some(NextStep::Upload));
+        assert!(CustomSteps::available_steps(&rendered, &steps).contains(&"subtitles".to_string()));
+    }
+}
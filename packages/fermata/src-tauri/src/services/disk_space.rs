@@ -0,0 +1,335 @@
+use crate::models::Recording;
+use crate::services::{Trash, FRAME_EXTENSIONS, TRANSCODED_DIR};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const BYTES_PER_GB: u64 = 1024 * 1024 * 1024;
+
+/// Bytes in `gb` gigabytes, capped instead of overflowing for huge configured values
+pub fn gb_to_bytes(gb: u64) -> u64 {
+    gb.saturating_mul(BYTES_PER_GB)
+}
+
+/// Pipeline stages disk usage is grouped by, in pipeline order
+pub const STORAGE_STAGES: [&str; 7] = ["raw_video", "extracted", "analysis", "blender", "render", "uploads", "other"];
+
+const RAW_VIDEO_EXTENSIONS: [&str; 4] = ["mkv", "mp4", "flv", "mov"];
+
+/// Free space on the volume holding the recordings
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DiskSpace {
+    pub available_bytes: u64,
+    pub total_bytes: u64,
+    pub threshold_bytes: u64,
+    /// Below the threshold: new renders are paused until space is freed
+    pub low: bool,
+}
+
+impl DiskSpace {
+    /// Check free space for the volume containing `path`
+    pub fn check(path: &Path, min_free_space_gb: u64) -> Result<Self, String> {
+        let available_bytes = fs2::available_space(path)
+            .map_err(|e| format!("Failed to read free space for {}: {}", path.display(), e))?;
+        let total_bytes = fs2::total_space(path)
+            .map_err(|e| format!("Failed to read disk size for {}: {}", path.display(), e))?;
+
+        Ok(Self::new(available_bytes, total_bytes, gb_to_bytes(min_free_space_gb)))
+    }
+
+    pub fn new(available_bytes: u64, total_bytes: u64, threshold_bytes: u64) -> Self {
+        Self {
+            available_bytes,
+            total_bytes,
+            threshold_bytes,
+            low: available_bytes < threshold_bytes,
+        }
+    }
+}
+
+/// A regenerable artifact that can be removed to free space
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ReclaimableArtifact {
+    pub recording: String,
+    /// render_frames, extracted, transcoded or trash
+    pub kind: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub reason: String,
+}
+
+pub struct ReclaimableArtifacts;
+
+impl ReclaimableArtifacts {
+    /// Artifacts that can be deleted without losing anything that cannot be regenerated,
+    /// largest first
+    pub fn find(recordings_path: &Path) -> Vec<ReclaimableArtifact> {
+        let mut artifacts = Vec::new();
+
+        if let Ok(entries) = fs::read_dir(recordings_path) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let Some(name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+                    continue;
+                };
+                if !path.is_dir() || name.starts_with('.') {
+                    continue;
+                }
+
+                artifacts.extend(Self::render_frames(&path, &name));
+                artifacts.extend(Self::extracted_media(&path, &name));
+                artifacts.extend(Self::transcoded_uploads(&path, &name));
+            }
+        }
+
+        for entry in Trash::list(recordings_path) {
+            artifacts.push(ReclaimableArtifact {
+                recording: entry.name,
+                kind: "trash".to_string(),
+                path: entry.path,
+                size_bytes: entry.size_bytes,
+                reason: "Deleted recording in trash".to_string(),
+            });
+        }
+
+        artifacts.retain(|a| a.size_bytes > 0);
+        artifacts.sort_by_key(|artifact| Reverse(artifact.size_bytes));
+        artifacts
+    }
+
+    /// Frame images left in blender/render once the final video has been encoded
+    fn render_frames(recording_path: &Path, name: &str) -> Option<ReclaimableArtifact> {
+        let render_dir = recording_path.join("blender").join("render");
+        let mut size_bytes = 0;
+
+        for entry in fs::read_dir(&render_dir).ok()?.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_lowercase();
+            if file_name
+                .rsplit_once('.')
+                .is_some_and(|(_, ext)| FRAME_EXTENSIONS.contains(&ext))
+            {
+                size_bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            }
+        }
+
+        has_final_video(recording_path).then(|| ReclaimableArtifact {
+            recording: name.to_string(),
+            kind: "render_frames".to_string(),
+            path: render_dir,
+            size_bytes,
+            reason: "Frames already encoded into the final video".to_string(),
+        })
+    }
+
+    /// Extracted sources can be re-extracted from the original recording once the final
+    /// video exists; before that they are the only processed sources a render can use
+    fn extracted_media(recording_path: &Path, name: &str) -> Option<ReclaimableArtifact> {
+        let extracted = recording_path.join("extracted");
+        if !extracted.is_dir() || !has_final_video(recording_path) {
+            return None;
+        }
+
+        Some(ReclaimableArtifact {
+            recording: name.to_string(),
+            kind: "extracted".to_string(),
+            size_bytes: directory_size(&extracted),
+            path: extracted,
+            reason: "Can be re-extracted from the original recording".to_string(),
+        })
+    }
+
+    /// Per-target transcodes are re-created from the render on the next upload
+    fn transcoded_uploads(recording_path: &Path, name: &str) -> Option<ReclaimableArtifact> {
+        let transcoded = recording_path.join(TRANSCODED_DIR);
+        transcoded.is_dir().then(|| ReclaimableArtifact {
+            recording: name.to_string(),
+            kind: "transcoded".to_string(),
+            size_bytes: directory_size(&transcoded),
+            path: transcoded,
+            reason: "Upload variants can be transcoded again from the render".to_string(),
+        })
+    }
+}
+
+/// Whether the render has been encoded into a final video in blender/render
+fn has_final_video(recording_path: &Path) -> bool {
+    fs::read_dir(recording_path.join("blender").join("render"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .any(|entry| entry.file_name().to_string_lossy().to_lowercase().ends_with(".mp4"))
+        })
+        .unwrap_or(false)
+}
+
+/// Disk usage of one recording per pipeline stage
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RecordingStorage {
+    pub recording: String,
+    pub total_bytes: u64,
+    pub stages: BTreeMap<String, u64>,
+}
+
+/// Disk usage of all recordings, largest recording first
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StorageReport {
+    pub total_bytes: u64,
+    pub stage_totals: BTreeMap<String, u64>,
+    pub recordings: Vec<RecordingStorage>,
+}
+
+impl StorageReport {
+    /// Aggregate the per-file sizes collected by the scanner
+    pub fn from_recordings(recordings: &[Recording]) -> Self {
+        let mut stage_totals = empty_stages();
+        let mut storage: Vec<RecordingStorage> = recordings
+            .iter()
+            .map(|recording| {
+                let mut stages = empty_stages();
+                for (file, size) in &recording.file_sizes {
+                    *stages.entry(storage_stage(file).to_string()).or_default() += size;
+                }
+                for (stage, size) in &stages {
+                    *stage_totals.entry(stage.clone()).or_default() += size;
+                }
+
+                RecordingStorage {
+                    recording: recording.name.clone(),
+                    total_bytes: stages.values().sum(),
+                    stages,
+                }
+            })
+            .collect();
+        storage.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes).then_with(|| a.recording.cmp(&b.recording)));
+
+        Self {
+            total_bytes: stage_totals.values().sum(),
+            stage_totals,
+            recordings: storage,
+        }
+    }
+}
+
+fn empty_stages() -> BTreeMap<String, u64> {
+    STORAGE_STAGES.iter().map(|stage| (stage.to_string(), 0)).collect()
+}
+
+/// Stage a file belongs to, from its path relative to the recording directory
+pub fn storage_stage(relative_path: &str) -> &'static str {
+    let path = Path::new(relative_path);
+    let components: Vec<&str> = path
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+
+    match components.as_slice() {
+        [file] => {
+            let is_video = Path::new(file)
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| RAW_VIDEO_EXTENSIONS.contains(&e.to_lowercase().as_str()));
+            if is_video {
+                "raw_video"
+            } else {
+                "other"
+            }
+        }
+        ["extracted", ..] => "extracted",
+        ["analysis", ..] => "analysis",
+        ["blender", "render", _, ..] => "render",
+        ["blender", ..] => "blender",
+        ["uploads", ..] => "uploads",
+        _ => "other",
+    }
+}
+
+pub fn directory_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_low_when_below_threshold() {
+        assert!(DiskSpace::new(5, 100, 10).low);
+        assert!(!DiskSpace::new(10, 100, 10).low);
+        assert_eq!(gb_to_bytes(u64::MAX / 2), u64::MAX);
+    }
+
+    #[test]
+    fn test_storage_stage() {
+        assert_eq!(storage_stage("gig.mkv"), "raw_video");
+        assert_eq!(storage_stage("metadata.json"), "other");
+        assert_eq!(storage_stage("extracted/Camera.mp4"), "extracted");
+        assert_eq!(storage_stage("analysis/audio_analysis.json"), "analysis");
+        assert_eq!(storage_stage("blender/gig.blend"), "blender");
+        assert_eq!(storage_stage("blender/render/final.mp4"), "render");
+        assert_eq!(storage_stage("uploads/transcoded/portal/final.mp4"), "uploads");
+        assert_eq!(storage_stage(".fermata/logs/analyze_20240114-020000.log"), "other");
+    }
+
+    #[test]
+    fn test_storage_report_totals() {
+        let recording = |name: &str, files: &[(&str, u64)]| Recording {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            status: crate::models::RecordingStatus::Recorded,
+            last_updated: 0,
+            file_sizes: files.iter().map(|(f, s)| (f.to_string(), *s)).collect(),
+            upload_block: None,
+            root: PathBuf::new(),
+            lifecycle: None,
+            media: None,
+            tree: None,
+        };
+        let recordings = vec![
+            recording("small", &[("small.mkv", 10), ("extracted/a.m4a", 5)]),
+            recording("big", &[("big.mkv", 100), ("blender/render/final.mp4", 50), ("blender/big.blend", 1)]),
+        ];
+
+        let report = StorageReport::from_recordings(&recordings);
+        assert_eq!(report.total_bytes, 166);
+        assert_eq!(report.stage_totals["raw_video"], 110);
+        assert_eq!(report.stage_totals["uploads"], 0);
+        assert_eq!(report.recordings[0].recording, "big");
+        assert_eq!(report.recordings[0].stages["render"], 50);
+        assert_eq!(report.recordings[1].total_bytes, 15);
+    }
+
+    #[test]
+    fn test_reclaimable_artifacts_sorted_by_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let rendered = temp_dir.path().join("rendered");
+        fs::create_dir_all(rendered.join("blender/render")).unwrap();
+        fs::create_dir_all(rendered.join("extracted")).unwrap();
+        fs::write(rendered.join("blender/render/final.mp4"), "video").unwrap();
+        fs::write(rendered.join("blender/render/0001.png"), "frame-data").unwrap();
+        fs::write(rendered.join("extracted/audio.m4a"), "a").unwrap();
+
+        // Still rendering: no final video, so neither frames nor sources can go
+        let rendering = temp_dir.path().join("rendering");
+        fs::create_dir_all(rendering.join("blender/render")).unwrap();
+        fs::create_dir_all(rendering.join("extracted")).unwrap();
+        fs::write(rendering.join("blender/render/0001.png"), "frame").unwrap();
+        fs::write(rendering.join("extracted/audio.m4a"), "a").unwrap();
+
+        let artifacts = ReclaimableArtifacts::find(temp_dir.path());
+        let kinds: Vec<&str> = artifacts.iter().map(|a| a.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["render_frames", "extracted"]);
+        assert!(artifacts.iter().all(|a| a.recording == "rendered"));
+    }
+}
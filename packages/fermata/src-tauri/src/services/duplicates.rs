@@ -0,0 +1,200 @@
+use crate::models::{Recording, RecordingStatus};
+use crate::services::{hash_file, key_artifacts, metadata_start_time, FileScanner, StateManifest, CHECKSUM_ALGORITHM};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Why recordings were grouped as duplicates
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateReason {
+    /// A raw video with the same size and hash
+    SameVideo,
+    /// The same start time in metadata.json, e.g. a session imported twice
+    SameStartTime,
+}
+
+/// Suggested handling of a group member
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateAction {
+    /// The copy furthest along the pipeline
+    Keep,
+    /// Has files the kept copy lacks, move them over before deleting
+    Merge,
+    /// Nothing the kept copy lacks
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DuplicateMember {
+    pub recording: String,
+    pub status: RecordingStatus,
+    pub action: DuplicateAction,
+    /// Top-level entries the kept copy lacks
+    pub only_here: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DuplicateGroup {
+    pub reason: DuplicateReason,
+    /// The shared video hash or start time
+    pub key: String,
+    /// Kept copy first
+    pub members: Vec<DuplicateMember>,
+}
+
+/// Group recordings sharing a raw video or a start time. Only videos whose size
+/// matches another recording's are hashed, reusing checksums from the state manifest.
+pub fn find_duplicates(recordings: &[Recording]) -> Vec<DuplicateGroup> {
+    let mut by_size: HashMap<u64, Vec<(usize, String)>> = HashMap::new();
+    for (index, recording) in recordings.iter().enumerate() {
+        for video in key_artifacts(&recording.path).into_iter().filter(|path| path.parent() == Some(recording.path.as_path())) {
+            if let (Ok(meta), Some(name)) = (fs::metadata(&video), video.file_name().and_then(|n| n.to_str())) {
+                by_size.entry(meta.len()).or_default().push((index, name.to_string()));
+            }
+        }
+    }
+
+    let mut by_hash: BTreeMap<String, BTreeSet<usize>> = BTreeMap::new();
+    for (size, videos) in by_size {
+        if videos.iter().all(|(index, _)| *index == videos[0].0) {
+            continue;
+        }
+        for (index, name) in videos {
+            match video_hash(&recordings[index].path, &name, size) {
+                Ok(hash) => {
+                    by_hash.entry(hash).or_default().insert(index);
+                }
+                Err(e) => log::warn!("Skipping {} in duplicate search: {}", name, e),
+            }
+        }
+    }
+
+    let mut by_start: BTreeMap<i64, BTreeSet<usize>> = BTreeMap::new();
+    for (index, recording) in recordings.iter().enumerate() {
+        if let Some(start) = metadata_start_time(&recording.path) {
+            by_start.entry(start).or_default().insert(index);
+        }
+    }
+
+    let mut seen: Vec<BTreeSet<usize>> = Vec::new();
+    let mut groups = Vec::new();
+    let candidates = by_hash
+        .into_iter()
+        .map(|(hash, members)| (DuplicateReason::SameVideo, hash, members))
+        .chain(by_start.into_iter().map(|(start, members)| (DuplicateReason::SameStartTime, start.to_string(), members)));
+    for (reason, key, members) in candidates {
+        if members.len() < 2 || seen.contains(&members) {
+            continue;
+        }
+        let group: Vec<&Recording> = members.iter().map(|index| &recordings[*index]).collect();
+        seen.push(members);
+        groups.push(DuplicateGroup { reason, key, members: suggest_actions(group) });
+    }
+    groups
+}
+
+/// Hash recorded by checksum_recording when the file is unchanged, otherwise a fresh one
+fn video_hash(recording_path: &Path, name: &str, size: u64) -> Result<String, String> {
+    let cached = StateManifest::load(recording_path).and_then(|manifest| {
+        manifest
+            .checksums
+            .into_iter()
+            .find(|c| c.path == name && c.size_bytes == size && c.algorithm == CHECKSUM_ALGORITHM)
+    });
+    match cached {
+        Some(checksum) => Ok(checksum.hash),
+        None => hash_file(&recording_path.join(name)),
+    }
+}
+
+/// Keep the copy furthest along, preferring the shorter name over "gig (1)" copies
+fn suggest_actions(mut group: Vec<&Recording>) -> Vec<DuplicateMember> {
+    group.sort_by(|a, b| {
+        progress(&b.status)
+            .cmp(&progress(&a.status))
+            .then_with(|| a.name.len().cmp(&b.name.len()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    let kept = top_level_entries(&group[0].path);
+    group
+        .iter()
+        .enumerate()
+        .map(|(position, recording)| {
+            let only_here: Vec<String> = match position {
+                0 => Vec::new(),
+                _ => top_level_entries(&recording.path).difference(&kept).cloned().collect(),
+            };
+            let action = match position {
+                0 => DuplicateAction::Keep,
+                _ if only_here.is_empty() => DuplicateAction::Delete,
+                _ => DuplicateAction::Merge,
+            };
+            DuplicateMember { recording: recording.name.clone(), status: recording.status.clone(), action, only_here }
+        })
+        .collect()
+}
+
+/// Failed and corrupted copies are the least worth keeping
+fn progress(status: &RecordingStatus) -> u8 {
+    match status {
+        RecordingStatus::Failed(_) | RecordingStatus::Corrupted(_) => 0,
+        status => FileScanner::pipeline_rank(status) + 1,
+    }
+}
+
+/// Names in the recording directory, without Fermata's own state
+fn top_level_entries(recording_path: &Path) -> BTreeSet<String> {
+    fs::read_dir(recording_path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+                .filter(|name| name != ".fermata")
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn recording(root: &Path, name: &str, status: RecordingStatus, files: &[(&str, &str)]) -> Recording {
+        fs::create_dir_all(root.join(name)).unwrap();
+        for (file, content) in files {
+            fs::write(root.join(name).join(file), content).unwrap();
+        }
+        let mut recording = Recording::from_path(root.join(name)).unwrap();
+        recording.status = status;
+        recording
+    }
+
+    #[test]
+    fn test_groups_copies_and_keeps_the_furthest_along() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let recordings = vec![
+            recording(root, "gig (1)", RecordingStatus::Recorded, &[("gig.mkv", "same take"), ("notes.txt", "setlist")]),
+            recording(root, "gig", RecordingStatus::Analyzed, &[("gig.mkv", "same take"), ("analysis.json", "{}")]),
+            recording(root, "jam", RecordingStatus::Recorded, &[("jam.mkv", "other take")]),
+            recording(root, "jam again", RecordingStatus::Recorded, &[("jam.mkv", "other tape")]),
+            recording(root, "imported", RecordingStatus::Recorded, &[("metadata.json", r#"{"recording_start_time": 1760000000}"#)]),
+            recording(root, "imported twice", RecordingStatus::Failed("extract".to_string()), &[("metadata.json", r#"{"recording_start_time": 1760000000}"#)]),
+        ];
+
+        let groups = find_duplicates(&recordings);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].reason, DuplicateReason::SameVideo);
+        let actions: Vec<(&str, DuplicateAction)> = groups[0].members.iter().map(|m| (m.recording.as_str(), m.action)).collect();
+        assert_eq!(actions, vec![("gig", DuplicateAction::Keep), ("gig (1)", DuplicateAction::Merge)]);
+        assert_eq!(groups[0].members[1].only_here, vec!["notes.txt"]);
+
+        assert_eq!(groups[1].reason, DuplicateReason::SameStartTime);
+        assert_eq!(groups[1].members[0].recording, "imported");
+        assert_eq!(groups[1].members[1].action, DuplicateAction::Delete);
+    }
+}
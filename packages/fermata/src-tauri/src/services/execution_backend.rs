@@ -0,0 +1,220 @@
+use crate::services::blender_remap_expr;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
+use std::path::Path;
+use tokio::process::Command as AsyncCommand;
+
+/// Settings key for the machine Blender renders run on
+pub const RENDER_HOST_KEY: &str = "render_host";
+
+/// Left on the local machine when a recording is synced to the render host
+const SYNC_EXCLUDES: [&str; 1] = [".fermata/"];
+
+/// Where step commands run. Ssh runs them on another machine over `ssh user@host`,
+/// on a copy of the recording under `remote_workspace` kept in sync with rsync;
+/// the host needs Blender at the configured blender path.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind")]
+pub enum ExecutionBackend {
+    #[default]
+    Local,
+    Ssh {
+        host: String,
+        user: String,
+        /// Existing absolute directory on the host holding the synced recordings
+        remote_workspace: String,
+    },
+}
+
+impl ExecutionBackend {
+    pub fn is_remote(&self) -> bool {
+        !matches!(self, ExecutionBackend::Local)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let ExecutionBackend::Ssh { host, user, remote_workspace } = self else {
+            return Ok(());
+        };
+        let valid_name = |value: &str| !value.is_empty() && !value.starts_with('-') && !value.contains(|c: char| c.is_whitespace() || c == '@' || c == ':');
+        if !valid_name(host) {
+            return Err(format!("Invalid render host: '{}'", host));
+        }
+        if !valid_name(user) {
+            return Err(format!("Invalid render host user: '{}'", user));
+        }
+        if !remote_workspace.starts_with('/') {
+            return Err(format!("Remote workspace must be an absolute path: '{}'", remote_workspace));
+        }
+        Ok(())
+    }
+
+    /// Copy of the recording on the host, None when running locally
+    pub fn remote_recording_path(&self, recording_path: &Path) -> Option<String> {
+        let ExecutionBackend::Ssh { remote_workspace, .. } = self else {
+            return None;
+        };
+        let name = recording_path.file_name()?.to_string_lossy();
+        Some(format!("{}/{}", remote_workspace.trim_end_matches('/'), name))
+    }
+
+    fn destination(&self) -> Option<String> {
+        match self {
+            ExecutionBackend::Local => None,
+            ExecutionBackend::Ssh { host, user, .. } => Some(format!("{}@{}", user, host)),
+        }
+    }
+
+    /// Python run before a render on the host: the synced .blend still points at local
+    /// paths, so they're remapped to the host's copy and the output goes to its blender/render/
+    pub fn blender_render_scripts(&self, recording_path: &Path) -> Vec<String> {
+        let Some(remote) = self.remote_recording_path(recording_path) else {
+            return Vec::new();
+        };
+        let local = recording_path.to_string_lossy().to_string();
+        let render_dir = serde_json::to_string(&format!("{}/blender/render", remote)).unwrap_or_default();
+        vec![
+            blender_remap_expr(&remote, &[local]),
+            format!("import bpy, os; r = bpy.context.scene.render; r.filepath = os.path.join({}, os.path.basename(r.filepath))", render_dir),
+        ]
+    }
+
+    /// rsync arguments mirroring the recording onto the host. Paths are sent with
+    /// --protect-args so the remote shell never splits or expands them.
+    pub fn push_args(&self, recording_path: &Path) -> Option<Vec<String>> {
+        let remote = self.remote_recording_path(recording_path)?;
+        let mut args = vec!["-az".to_string(), "--protect-args".to_string(), "--delete".to_string()];
+        args.extend(SYNC_EXCLUDES.iter().map(|exclude| format!("--exclude={}", exclude)));
+        args.push(format!("{}/", recording_path.display()));
+        args.push(format!("{}:{}/", self.destination()?, remote));
+        Some(args)
+    }
+
+    /// rsync arguments fetching `relative_dir` of the recording back from the host.
+    /// Only outputs come back, so the host never overwrites local projects.
+    pub fn pull_args(&self, recording_path: &Path, relative_dir: &str) -> Option<Vec<String>> {
+        let remote = self.remote_recording_path(recording_path)?;
+        Some(vec![
+            "-az".to_string(),
+            "--protect-args".to_string(),
+            format!("{}:{}/{}/", self.destination()?, remote, relative_dir),
+            format!("{}/{}/", recording_path.display(), relative_dir),
+        ])
+    }
+
+    /// The command to spawn for `cmd`: itself locally, or an ssh invocation running it
+    /// on the host with paths inside the recording mapped to the host's copy
+    pub fn wrap(&self, cmd: AsyncCommand, recording_path: Option<&Path>) -> AsyncCommand {
+        let (Some(destination), Some(recording_path)) = (self.destination(), recording_path) else {
+            return cmd;
+        };
+        let Some(remote) = self.remote_recording_path(recording_path) else {
+            return cmd;
+        };
+
+        let local = recording_path.to_string_lossy();
+        let map = |value: &OsStr| {
+            let value = value.to_string_lossy();
+            match value.strip_prefix(local.as_ref()) {
+                Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("{}{}", remote, rest),
+                _ => value.to_string(),
+            }
+        };
+
+        let std_cmd = cmd.as_std();
+        let mut script = String::new();
+        if let Some(dir) = std_cmd.get_current_dir() {
+            script.push_str(&format!("cd {} && ", shell_quote(&map(dir.as_os_str()))));
+        }
+        let envs: Vec<String> = std_cmd
+            .get_envs()
+            .filter_map(|(key, value)| Some(format!("{}={}", key.to_string_lossy(), shell_quote(&map(value?)))))
+            .collect();
+        if !envs.is_empty() {
+            script.push_str(&format!("env {} ", envs.join(" ")));
+        }
+        script.push_str(&shell_quote(&map(std_cmd.get_program())));
+        for arg in std_cmd.get_args() {
+            script.push(' ');
+            script.push_str(&shell_quote(&map(arg)));
+        }
+
+        let mut ssh = AsyncCommand::new("ssh");
+        ssh.args(["-o", "BatchMode=yes"]).arg(destination).arg(script);
+        ssh
+    }
+}
+
+/// Quote for a POSIX shell, which the remote command line passes through
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::BLENDER_REMAP_MARKER;
+
+    fn backend() -> ExecutionBackend {
+        ExecutionBackend::Ssh {
+            host: "render-box".to_string(),
+            user: "setka".to_string(),
+            remote_workspace: "/srv/fermata/".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(ExecutionBackend::Local.validate().is_ok());
+        assert!(backend().validate().is_ok());
+        let ExecutionBackend::Ssh { host, user, .. } = backend() else { unreachable!() };
+        let relative = ExecutionBackend::Ssh { host: host.clone(), user: user.clone(), remote_workspace: "srv".to_string() };
+        assert!(relative.validate().is_err());
+        let option = ExecutionBackend::Ssh { host: "-oProxyCommand=x".to_string(), user, remote_workspace: "/srv".to_string() };
+        assert!(option.validate().is_err());
+    }
+
+    #[test]
+    fn test_wrap_maps_recording_paths() {
+        let recording = Path::new("/home/me/Videos/2024-05-01 gig");
+        let mut cmd = AsyncCommand::new("blender");
+        cmd.arg("--background")
+            .arg(recording.join("blender").join("gig.blend"))
+            .args(["--python-expr", "print('x')"])
+            .current_dir(recording.join("blender"));
+
+        let wrapped = backend().wrap(cmd, Some(recording));
+        let args: Vec<String> = wrapped.as_std().get_args().map(|a| a.to_string_lossy().to_string()).collect();
+        assert_eq!(args[..3], ["-o", "BatchMode=yes", "setka@render-box"]);
+        assert_eq!(
+            args[3],
+            "cd '/srv/fermata/2024-05-01 gig/blender' && 'blender' '--background' '/srv/fermata/2024-05-01 gig/blender/gig.blend' \
+             '--python-expr' 'print('\\''x'\\'')'"
+        );
+
+        let local = ExecutionBackend::Local.wrap(AsyncCommand::new("blender"), Some(recording));
+        assert_eq!(local.as_std().get_program(), "blender");
+    }
+
+    #[test]
+    fn test_sync_args() {
+        let recording = Path::new("/rec/gig");
+        assert_eq!(
+            backend().push_args(recording).unwrap(),
+            ["-az", "--protect-args", "--delete", "--exclude=.fermata/", "/rec/gig/", "setka@render-box:/srv/fermata/gig/"]
+        );
+        assert_eq!(
+            backend().pull_args(recording, "blender/render").unwrap(),
+            ["-az", "--protect-args", "setka@render-box:/srv/fermata/gig/blender/render/", "/rec/gig/blender/render/"]
+        );
+        assert!(ExecutionBackend::Local.push_args(recording).is_none());
+    }
+
+    #[test]
+    fn test_render_scripts_target_host_copy() {
+        let scripts = backend().blender_render_scripts(Path::new("/rec/gig"));
+        assert!(scripts[0].starts_with("import sys\nsys.argv = [\"blender\",\"--\",\"/srv/fermata/gig\",\"/rec/gig\"]\n"));
+        assert!(scripts[0].contains(BLENDER_REMAP_MARKER));
+        assert!(scripts[1].contains("os.path.join(\"/srv/fermata/gig/blender/render\", os.path.basename(r.filepath))"));
+        assert!(ExecutionBackend::Local.blender_render_scripts(Path::new("/rec/gig")).is_empty());
+    }
+}
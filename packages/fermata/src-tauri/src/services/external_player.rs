@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Settings key for the external player preferences
+pub const EXTERNAL_PLAYER_KEY: &str = "external_player";
+
+/// Placeholder replaced with the video path in a player command template
+pub const PATH_PLACEHOLDER: &str = "{path}";
+
+/// Player used by "Open in external player"; the OS default when unset
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ExternalPlayerSettings {
+    /// Command template, e.g. `mpv --profile=preview {path}`
+    pub command: Option<String>,
+}
+
+/// A parsed player command template
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerCommand {
+    pub program: String,
+    args: Vec<String>,
+}
+
+impl PlayerCommand {
+    /// Parse and validate a template: quoting like a shell, exactly one
+    /// `{path}` placeholder, and a program that can be found
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut words = split_words(template)?;
+        if words.is_empty() {
+            return Err("Player command is empty".to_string());
+        }
+
+        let placeholders: usize = words.iter().map(|w| w.matches(PATH_PLACEHOLDER).count()).sum();
+        if placeholders != 1 {
+            return Err(format!("Player command must contain {} exactly once", PATH_PLACEHOLDER));
+        }
+
+        let program = words.remove(0);
+        if program.contains(PATH_PLACEHOLDER) {
+            return Err(format!("{} cannot be the program", PATH_PLACEHOLDER));
+        }
+        if !program_exists(&program) {
+            return Err(format!("Player program '{}' not found", program));
+        }
+
+        Ok(Self { program, args: words })
+    }
+
+    /// Arguments with the placeholder replaced by the video path
+    pub fn args_for(&self, video: &Path) -> Vec<String> {
+        let path = video.to_string_lossy();
+        self.args.iter().map(|arg| arg.replace(PATH_PLACEHOLDER, &path)).collect()
+    }
+}
+
+/// Split on whitespace, honouring single and double quotes
+fn split_words(template: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+
+    for c in template.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err("Unbalanced quotes in player command".to_string());
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+/// An explicit path must exist; a bare name must be on PATH
+fn program_exists(program: &str) -> bool {
+    let path = Path::new(program);
+    if path.components().count() > 1 {
+        return path.is_file();
+    }
+
+    let Some(search_path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&search_path).any(|dir| {
+        let candidate = dir.join(program);
+        candidate.is_file() || (cfg!(windows) && candidate.with_extension("exe").is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_words_with_quotes() {
+        assert_eq!(
+            split_words(r#"mpv --title="My Gig" '{path}'"#).unwrap(),
+            vec!["mpv", "--title=My Gig", "{path}"]
+        );
+        assert_eq!(split_words("vlc ''").unwrap(), vec!["vlc", ""]);
+        assert!(split_words("mpv \"{path}").is_err());
+    }
+
+    #[test]
+    fn test_parse_validates_template() {
+        assert!(PlayerCommand::parse("   ").is_err());
+        assert!(PlayerCommand::parse("sh").is_err());
+        assert!(PlayerCommand::parse("sh {path} {path}").is_err());
+        assert!(PlayerCommand::parse("{path}").is_err());
+        assert!(PlayerCommand::parse("definitely-not-a-player-xyz {path}").is_err());
+
+        let command = PlayerCommand::parse("sh -c 'echo $0' --start={path}").unwrap();
+        assert_eq!(command.program, "sh");
+        assert_eq!(
+            command.args_for(Path::new("/videos/gig one.mkv")),
+            vec!["-c", "echo $0", "--start=/videos/gig one.mkv"]
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use crate::services::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Obsession options of a recording, reused by every later extract run
+pub const EXTRACT_OPTIONS_FILE: &str = ".fermata/extract_options.json";
+
+/// An OBS source listed in the recording's metadata.json
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ExtractSource {
+    pub name: String,
+    pub has_audio: bool,
+    pub has_video: bool,
+}
+
+/// Which OBS sources obsession extracts; the defaults extract everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ExtractOptions {
+    /// Source names to extract, e.g. without "Desktop Audio" or "Camera 3"; empty extracts all
+    pub include_sources: Vec<String>,
+    /// Extract the audio tracks only, no cropped videos
+    pub audio_only: bool,
+}
+
+impl ExtractOptions {
+    /// Options of a recording, the defaults when none were saved
+    pub fn load(recording_path: &Path) -> Self {
+        fs::read_to_string(recording_path.join(EXTRACT_OPTIONS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, recording_path: &Path) -> Result<(), String> {
+        self.validate(&Self::sources(recording_path)?)?;
+        let path = recording_path.join(EXTRACT_OPTIONS_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize extract options: {}", e))?;
+        write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Every included source must be in the metadata, and something must be left to extract
+    pub fn validate(&self, sources: &[ExtractSource]) -> Result<(), String> {
+        if let Some(unknown) = self.include_sources.iter().find(|name| !sources.iter().any(|s| &s.name == *name)) {
+            return Err(format!("Source '{}' is not in the recording's metadata", unknown));
+        }
+        let selected = sources.iter().filter(|s| self.include_sources.is_empty() || self.include_sources.contains(&s.name));
+        let extracted = selected.filter(|s| s.has_audio || (s.has_video && !self.audio_only)).count();
+        if extracted == 0 {
+            return Err("The selected sources leave nothing to extract".to_string());
+        }
+        Ok(())
+    }
+
+    /// Obsession arguments for the narrowed options only
+    pub fn cli_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        for source in &self.include_sources {
+            args.push("--include-source".to_string());
+            args.push(source.clone());
+        }
+        if self.audio_only {
+            args.push("--audio-only".to_string());
+        }
+        args
+    }
+
+    /// Sources in metadata.json, by name
+    pub fn sources(recording_path: &Path) -> Result<Vec<ExtractSource>, String> {
+        let path = recording_path.join("metadata.json");
+        let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let metadata: serde_json::Value =
+            serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+        let sources = metadata
+            .get("sources")
+            .and_then(|sources| sources.as_object())
+            .ok_or_else(|| format!("No sources listed in {}", path.display()))?;
+        let flag = |info: &serde_json::Value, key: &str| info.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+        Ok(sources
+            .iter()
+            .map(|(name, info)| ExtractSource {
+                name: name.clone(),
+                has_audio: flag(info, "has_audio"),
+                has_video: flag(info, "has_video"),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sources_validate_and_args() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        assert!(ExtractOptions::sources(path).is_err());
+        fs::write(
+            path.join("metadata.json"),
+            r#"{"canvas_size": [1920, 1080], "sources": {
+                "Camera 1": {"has_audio": false, "has_video": true},
+                "Camera 3": {"has_audio": false, "has_video": true},
+                "Desktop Audio": {"has_audio": true, "has_video": false}
+            }}"#,
+        )
+        .unwrap();
+        let sources = ExtractOptions::sources(path).unwrap();
+        assert_eq!(sources.len(), 3);
+        assert_eq!(ExtractOptions::load(path), ExtractOptions::default());
+        assert!(ExtractOptions::default().cli_args().is_empty());
+
+        let cameras_only = ExtractOptions { include_sources: vec!["Camera 1".to_string()], audio_only: false };
+        assert!(cameras_only.validate(&sources).is_ok());
+        assert_eq!(cameras_only.cli_args(), ["--include-source", "Camera 1"]);
+        assert!(ExtractOptions { audio_only: true, ..cameras_only.clone() }.validate(&sources).is_err());
+        assert!(ExtractOptions { include_sources: vec!["Camera 9".to_string()], audio_only: false }.validate(&sources).is_err());
+
+        cameras_only.save(path).unwrap();
+        assert_eq!(ExtractOptions::load(path), cameras_only);
+    }
+}
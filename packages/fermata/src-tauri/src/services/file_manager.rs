@@ -0,0 +1,135 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Opens directories and highlights files in the system file manager
+pub struct FileManager;
+
+impl FileManager {
+    /// Show a directory's contents
+    pub fn open_folder(dir: &Path) -> Result<(), String> {
+        if !dir.is_dir() {
+            return Err(format!("Directory not found: {}", dir.display()));
+        }
+        spawn(open_command(dir))
+    }
+
+    /// Show the directory containing a file with the file selected, where the
+    /// platform supports it; Linux file managers only get the parent directory
+    pub fn reveal(file: &Path) -> Result<(), String> {
+        if !file.exists() {
+            return Err(format!("File not found: {}", file.display()));
+        }
+        spawn(reveal_command(file))
+    }
+
+    /// Start the Blender GUI on a project, detached so it stays open when the app
+    /// quits, and in the project's directory so `//` relative paths resolve as in a render
+    pub fn open_in_blender(blender_path: &str, blend_file: &Path) -> Result<(), String> {
+        if !blend_file.is_file() {
+            return Err(format!("Blender project not found: {}", blend_file.display()));
+        }
+        let mut cmd = Command::new(blender_path);
+        cmd.arg(blend_file)
+            .current_dir(blend_file.parent().unwrap_or(Path::new(".")))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        detach(&mut cmd);
+        cmd.spawn().map(|_| ()).map_err(|e| format!("Failed to start {}: {}", blender_path, e))
+    }
+
+    /// Resolve `path` and make sure it lies inside one of the recordings roots,
+    /// so the frontend cannot make us open arbitrary locations
+    pub fn resolve_within_roots(path: &Path, roots: &[PathBuf]) -> Result<PathBuf, String> {
+        let resolved = path
+            .canonicalize()
+            .map_err(|e| format!("Cannot resolve {}: {}", path.display(), e))?;
+        let inside = roots
+            .iter()
+            .filter_map(|root| root.canonicalize().ok())
+            .any(|root| resolved.starts_with(root));
+        if !inside {
+            return Err(format!("{} is not inside a recordings folder", path.display()));
+        }
+        Ok(resolved)
+    }
+}
+
+fn spawn((program, args): (&'static str, Vec<String>)) -> Result<(), String> {
+    Command::new(program)
+        .args(&args)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to start {}: {}", program, e))
+}
+
+/// Run in its own process group, out of reach of signals sent to the app's
+#[cfg(unix)]
+fn detach(cmd: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    cmd.process_group(0);
+}
+
+#[cfg(windows)]
+fn detach(cmd: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    // DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP
+    cmd.creation_flags(0x0000_0008 | 0x0000_0200);
+}
+
+fn open_command(dir: &Path) -> (&'static str, Vec<String>) {
+    let dir = dir.to_string_lossy().to_string();
+    if cfg!(target_os = "windows") {
+        ("explorer", vec![dir])
+    } else if cfg!(target_os = "macos") {
+        ("open", vec![dir])
+    } else {
+        ("xdg-open", vec![dir])
+    }
+}
+
+fn reveal_command(file: &Path) -> (&'static str, Vec<String>) {
+    if cfg!(target_os = "windows") {
+        ("explorer", vec![format!("/select,{}", file.display())])
+    } else if cfg!(target_os = "macos") {
+        ("open", vec!["-R".to_string(), file.to_string_lossy().to_string()])
+    } else {
+        let dir = if file.is_dir() { file } else { file.parent().unwrap_or(file) };
+        ("xdg-open", vec![dir.to_string_lossy().to_string()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_within_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("recordings");
+        let outside = temp_dir.path().join("elsewhere");
+        std::fs::create_dir_all(root.join("gig/blender")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        let roots = vec![root.clone()];
+
+        let blend = root.join("gig/blender/gig.blend");
+        std::fs::write(&blend, "").unwrap();
+        assert!(FileManager::resolve_within_roots(&blend, &roots).is_ok());
+        assert!(FileManager::resolve_within_roots(&root.join("gig/../../elsewhere"), &roots).is_err());
+        assert!(FileManager::resolve_within_roots(&outside, &roots).is_err());
+        assert!(FileManager::resolve_within_roots(&root.join("missing"), &roots).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_reveal_opens_parent_on_linux() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("final.mp4");
+        std::fs::write(&file, "").unwrap();
+
+        let (program, args) = reveal_command(&file);
+        assert_eq!(program, "xdg-open");
+        assert_eq!(args, vec![temp_dir.path().to_string_lossy().to_string()]);
+    }
+}
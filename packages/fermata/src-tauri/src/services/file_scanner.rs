@@ -1,12 +1,158 @@
-use crate::models::Recording;
-use crate::services::{StatusDetector, update_recording_status};
-use std::path::Path;
+use crate::models::{Recording, RecordingStatus};
+use crate::services::{RecordingDetection, RecordingsIgnore, StatusDetector, update_recording_with};
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+
+// Scans run from many places that don't have the config at hand
+static IGNORE_SYMLINKS: AtomicBool = AtomicBool::new(false);
+
+/// How scans treat symlinks (and Windows junctions, which std reports as symlinks)
+/// in recordings roots, e.g. recordings or render outputs linked from a NAS
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum SymlinkPolicy {
+    /// Linked recordings and files count like real ones, each target once
+    #[default]
+    Follow,
+    /// Links are left out as if they weren't there
+    Ignore,
+}
+
+impl SymlinkPolicy {
+    /// "follow" or "ignore", as in FERMATA_SYMLINKS
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "follow" => Some(SymlinkPolicy::Follow),
+            "ignore" => Some(SymlinkPolicy::Ignore),
+            _ => None,
+        }
+    }
+
+    /// The policy scans use
+    pub fn current() -> Self {
+        if IGNORE_SYMLINKS.load(AtomicOrdering::Relaxed) {
+            SymlinkPolicy::Ignore
+        } else {
+            SymlinkPolicy::Follow
+        }
+    }
+
+    /// Make every later scan use this policy; AppConfig applies it at startup
+    pub fn apply(self) {
+        IGNORE_SYMLINKS.store(self == SymlinkPolicy::Ignore, AtomicOrdering::Relaxed);
+    }
+
+    /// Files under `dir`. Followed links never count a file twice: links back into
+    /// `dir` and repeated targets are skipped, and loops and broken links are left out.
+    pub fn files(self, dir: &Path) -> Vec<walkdir::DirEntry> {
+        let follow = self == SymlinkPolicy::Follow;
+        let root = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        let mut targets = HashSet::new();
+        let walker = walkdir::WalkDir::new(dir).follow_links(follow).into_iter().filter_entry(|entry| {
+            if !follow || entry.depth() == 0 || !entry.path_is_symlink() {
+                return true;
+            }
+            entry
+                .path()
+                .canonicalize()
+                .is_ok_and(|target| !target.starts_with(&root) && targets.insert(target))
+        });
+
+        let mut files = Vec::new();
+        for entry in walker {
+            match entry {
+                Ok(entry) if entry.file_type().is_file() => files.push(entry),
+                Ok(_) => {}
+                Err(e) => log::debug!("Skipping entry under {}: {}", dir.display(), e),
+            }
+        }
+        files
+    }
+}
+
+/// How much a scan reads besides each recording's status
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ScanOptions {
+    /// Size every file; the slow part of a scan on big libraries
+    pub include_sizes: bool,
+    /// Fill in each recording's file tree
+    pub include_tree: bool,
+    /// Depth of the tree, DEFAULT_TREE_DEPTH when unset
+    pub max_depth: Option<usize>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self { include_sizes: true, include_tree: false, max_depth: None }
+    }
+}
+
+impl ScanOptions {
+    /// Status only, for lists that fetch sizes on demand
+    pub fn fast() -> Self {
+        Self { include_sizes: false, ..Self::default() }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum RecordingSortBy {
+    Name,
+    #[default]
+    Date,
+    Size,
+    Status,
+}
+
+/// Inclusive range of `last_updated` timestamps (Unix seconds)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DateRange {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+/// Filter, sort order and page of a recordings query
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RecordingQuery {
+    /// Status filter as accepted by `filter_by_status`
+    pub status: Option<String>,
+    /// Case-insensitive substring of the recording name
+    pub name_contains: Option<String>,
+    pub date_range: Option<DateRange>,
+    pub sort_by: RecordingSortBy,
+    /// Defaults to newest first for Date and ascending otherwise
+    pub descending: Option<bool>,
+    /// Zero-based page index
+    pub page: usize,
+    pub page_size: Option<usize>,
+}
+
+/// One page of query results with the number of matches across all pages
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecordingPage {
+    pub items: Vec<Recording>,
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+}
 
 pub struct FileScanner;
 
 impl FileScanner {
     /// Scan a directory for recordings and return a list of Recording structs
     pub fn scan_recordings(root_path: &Path) -> Vec<Recording> {
+        Self::scan_recordings_with(root_path, SymlinkPolicy::current(), &ScanOptions::default())
+    }
+
+    /// Scan a directory for recordings, treating linked recording directories per `policy`
+    /// and reading as much of each as `options` asks
+    pub fn scan_recordings_with(root_path: &Path, policy: SymlinkPolicy, options: &ScanOptions) -> Vec<Recording> {
         let mut recordings = Vec::new();
 
         if !root_path.exists() || !root_path.is_dir() {
@@ -16,14 +162,47 @@ impl FileScanner {
 
         match std::fs::read_dir(root_path) {
             Ok(entries) => {
-                for entry in entries.flatten() {
+                let root = root_path.canonicalize().unwrap_or_else(|_| root_path.to_path_buf());
+                let mut entries: Vec<_> = entries.flatten().collect();
+                // Real directories first, so a link to one of them is the duplicate
+                entries.sort_by_key(|entry| entry.file_type().is_ok_and(|t| t.is_symlink()));
+                let mut seen = HashSet::new();
+                // Hidden directories (e.g. .trash) hold app bookkeeping, not recordings
+                let ignore = RecordingsIgnore::load(root_path);
+
+                for entry in entries {
                     let path = entry.path();
 
+                    if ignore.is_ignored(&entry.file_name().to_string_lossy()) {
+                        continue;
+                    }
+
+                    if entry.file_type().is_ok_and(|t| t.is_symlink()) {
+                        if policy == SymlinkPolicy::Ignore {
+                            continue;
+                        }
+                        match path.canonicalize() {
+                            Ok(target) if root.starts_with(&target) => {
+                                log::warn!("Skipping {}: it links back to the recordings root", path.display());
+                                continue;
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                log::warn!("Skipping broken link {}: {}", path.display(), e);
+                                continue;
+                            }
+                        }
+                    }
+                    if !seen.insert(path.canonicalize().unwrap_or_else(|_| path.clone())) {
+                        log::warn!("Skipping {}: it is the same directory as another recording", path.display());
+                        continue;
+                    }
+
                     if path.is_dir() && Self::is_valid_recording_dir(&path) {
                         match Recording::from_path(path) {
                             Ok(mut recording) => {
                                 // Update status and file sizes based on current filesystem state
-                                update_recording_status(&mut recording);
+                                update_recording_with(&mut recording, options);
                                 recordings.push(recording);
                             }
                             Err(e) => {
@@ -39,21 +218,69 @@ impl FileScanner {
         }
 
         // Sort recordings by last updated (most recent first)
-        recordings.sort_by(|a, b| b.last_updated.cmp(&a.last_updated));
+        recordings.sort_by_key(|recording| Reverse(recording.last_updated));
+
+        recordings
+    }
+
+    /// Scan several recordings roots and merge the results. A name found in more
+    /// than one root is taken from the first root listed, since commands address
+    /// recordings by name.
+    pub fn scan_roots(roots: &[PathBuf]) -> Vec<Recording> {
+        Self::scan_roots_with(roots, &ScanOptions::default())
+    }
+
+    /// Scan several recordings roots like `scan_roots`, reading as much as `options` asks
+    pub fn scan_roots_with(roots: &[PathBuf], options: &ScanOptions) -> Vec<Recording> {
+        let mut names = HashSet::new();
+        let mut recordings = Vec::new();
 
+        for root in roots {
+            for recording in Self::scan_recordings_with(root, SymlinkPolicy::current(), options) {
+                if names.insert(recording.name.clone()) {
+                    recordings.push(recording);
+                } else {
+                    log::warn!("Ignoring duplicate recording '{}' in {}", recording.name, root.display());
+                }
+            }
+        }
+
+        recordings.sort_by_key(|recording| Reverse(recording.last_updated));
         recordings
     }
 
+    /// Find the first root that contains a recording directory with the given name.
+    /// Names that could point outside a root (empty, hidden, with separators) never match.
+    pub fn find_root<'a>(roots: &'a [PathBuf], recording_name: &str) -> Option<&'a Path> {
+        if recording_name.is_empty() || recording_name.starts_with('.') || recording_name.contains(['/', '\\']) {
+            return None;
+        }
+        roots
+            .iter()
+            .map(PathBuf::as_path)
+            .find(|root| root.join(recording_name).is_dir())
+    }
+
     /// Check if a directory looks like a valid recording directory
     pub fn is_valid_recording_dir(path: &Path) -> bool {
         if !path.is_dir() {
             return false;
         }
+        if RecordingDetection::current().is_ignored(&Self::get_recording_name(path)) {
+            return false;
+        }
 
         // Use the status detector's validation
         StatusDetector::validate_recording_structure(path).is_ok()
     }
 
+    /// Check if a directory entry is hidden (name starts with a dot)
+    pub fn is_hidden(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.'))
+    }
+
     /// Extract recording name from directory path
     pub fn get_recording_name(path: &Path) -> String {
         path.file_name()
@@ -71,9 +298,17 @@ impl FileScanner {
                     "recorded" => matches!(recording.status, crate::models::RecordingStatus::Recorded),
                     "extracted" => matches!(recording.status, crate::models::RecordingStatus::Extracted),
                     "analyzed" => matches!(recording.status, crate::models::RecordingStatus::Analyzed),
+                    "configgenerated" | "config_generated" => matches!(recording.status, crate::models::RecordingStatus::ConfigGenerated),
+                    "framesrendered" | "frames_rendered" => matches!(recording.status, crate::models::RecordingStatus::FramesRendered),
                     "rendered" => matches!(recording.status, crate::models::RecordingStatus::Rendered),
                     "uploaded" => matches!(recording.status, crate::models::RecordingStatus::Uploaded),
                     "failed" => matches!(recording.status, crate::models::RecordingStatus::Failed(_)),
+                    "corrupted" => matches!(recording.status, crate::models::RecordingStatus::Corrupted(_)),
+                    "analyzing" => matches!(recording.status, crate::models::RecordingStatus::Analyzing),
+                    "settinguprender" | "setting_up_render" => matches!(recording.status, crate::models::RecordingStatus::SettingUpRender),
+                    "rendering" => matches!(recording.status, crate::models::RecordingStatus::Rendering),
+                    "uploading" => matches!(recording.status, crate::models::RecordingStatus::Uploading),
+                    "in_progress" => recording.status.is_in_progress(),
                     _ => true, // No filter or unknown filter
                 }
             })
@@ -81,22 +316,70 @@ impl FileScanner {
             .collect()
     }
 
-    /// Get recordings that need attention (failed or incomplete)
-    pub fn get_recordings_needing_attention(recordings: &[Recording]) -> Vec<Recording> {
-        recordings
-            .iter()
-            .filter(|recording| {
-                matches!(
-                    recording.status,
-                    crate::models::RecordingStatus::Failed(_) |
-                    crate::models::RecordingStatus::Recorded |
-                    crate::models::RecordingStatus::Extracted |
-                    crate::models::RecordingStatus::Analyzed |
-                    crate::models::RecordingStatus::Rendered
-                )
-            })
-            .cloned()
-            .collect()
+    /// Filter, sort and paginate recordings, e.g. the cached scan snapshot
+    pub fn query(recordings: &[Recording], query: &RecordingQuery) -> RecordingPage {
+        let mut matches = match &query.status {
+            Some(status) => Self::filter_by_status(recordings, status),
+            None => recordings.to_vec(),
+        };
+
+        if let Some(needle) = query.name_contains.as_deref().map(str::to_lowercase).filter(|n| !n.is_empty()) {
+            matches.retain(|r| r.name.to_lowercase().contains(&needle));
+        }
+        if let Some(range) = &query.date_range {
+            matches.retain(|r| {
+                !range.from.is_some_and(|from| r.last_updated < from) && !range.to.is_some_and(|to| r.last_updated > to)
+            });
+        }
+
+        let descending = query.descending.unwrap_or(query.sort_by == RecordingSortBy::Date);
+        matches.sort_by(|a, b| {
+            let ordering = Self::compare(a, b, query.sort_by).then_with(|| a.name.cmp(&b.name));
+            if descending { ordering.reverse() } else { ordering }
+        });
+
+        let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let total = matches.len();
+        let items = matches
+            .into_iter()
+            .skip(query.page.saturating_mul(page_size))
+            .take(page_size)
+            .collect();
+
+        RecordingPage { items, total, page: query.page, page_size }
+    }
+
+    fn compare(a: &Recording, b: &Recording, sort_by: RecordingSortBy) -> Ordering {
+        match sort_by {
+            RecordingSortBy::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            RecordingSortBy::Date => a.last_updated.cmp(&b.last_updated),
+            RecordingSortBy::Size => {
+                a.file_sizes.values().sum::<u64>().cmp(&b.file_sizes.values().sum::<u64>())
+            }
+            RecordingSortBy::Status => Self::pipeline_rank(&a.status).cmp(&Self::pipeline_rank(&b.status)),
+        }
+    }
+
+    /// Position of a status along the pipeline; running steps sort right after
+    /// their input and failures last
+    pub fn pipeline_rank(status: &RecordingStatus) -> u8 {
+        match status {
+            RecordingStatus::Recorded => 0,
+            RecordingStatus::Extracted => 1,
+            RecordingStatus::Analyzing => 2,
+            RecordingStatus::Analyzed => 3,
+            RecordingStatus::ConfigGenerated => 4,
+            RecordingStatus::SettingUpRender => 5,
+            RecordingStatus::SetupRendered => 6,
+            RecordingStatus::Rendering => 7,
+            RecordingStatus::FramesRendered => 8,
+            // Custom steps and transcription mostly run between render and upload
+            RecordingStatus::Rendered | RecordingStatus::RunningStep(_) | RecordingStatus::Transcribing => 9,
+            RecordingStatus::Uploading => 10,
+            RecordingStatus::Uploaded => 11,
+            RecordingStatus::Failed(_) => 12,
+            RecordingStatus::Corrupted(_) => 13,
+        }
     }
 }
 
@@ -104,7 +387,6 @@ impl FileScanner {
 mod tests {
     use super::*;
     use std::fs;
-    use std::path::PathBuf;
     use tempfile::TempDir;
 
     fn create_test_recordings_structure() -> TempDir {
@@ -172,6 +454,60 @@ mod tests {
         assert!(recordings.is_empty());
     }
 
+    #[test]
+    fn test_scan_recordings_skips_trash() {
+        let temp_dir = create_test_recordings_structure();
+        let trashed = temp_dir.path().join(".trash").join("1700000000_old_recording");
+        fs::create_dir_all(&trashed).unwrap();
+        fs::write(trashed.join("old_recording.mkv"), b"dummy content").unwrap();
+
+        let recordings = FileScanner::scan_recordings(temp_dir.path());
+        assert_eq!(recordings.len(), 3);
+    }
+
+    #[test]
+    fn test_scan_options() {
+        let temp_dir = create_test_recordings_structure();
+        let roots = vec![temp_dir.path().to_path_buf()];
+
+        let fast = FileScanner::scan_roots_with(&roots, &ScanOptions::fast());
+        assert_eq!(fast.len(), 3);
+        assert!(fast.iter().all(|r| r.file_sizes.is_empty() && r.tree.is_none()));
+        let analyzed = fast.iter().find(|r| r.name == "recording_003").unwrap();
+        assert_eq!(analyzed.status, RecordingStatus::Analyzed);
+
+        let options = ScanOptions { include_tree: true, max_depth: Some(0), ..ScanOptions::default() };
+        let full = FileScanner::scan_roots_with(&roots, &options);
+        let tree = full.iter().find(|r| r.name == "recording_003").and_then(|r| r.tree.as_ref()).unwrap();
+        assert_eq!((tree.file_count, tree.truncated), (3, true));
+        assert!(full.iter().all(|r| !r.file_sizes.is_empty()));
+    }
+
+    #[test]
+    fn test_scan_roots_merges_and_annotates_root() {
+        let ssd = create_test_recordings_structure();
+        let external = TempDir::new().unwrap();
+        for name in ["recording_001", "archive_001"] {
+            fs::create_dir_all(external.path().join(name)).unwrap();
+            fs::write(external.path().join(name).join(format!("{}.mkv", name)), b"dummy content").unwrap();
+        }
+        let roots = vec![ssd.path().to_path_buf(), external.path().to_path_buf()];
+
+        let recordings = FileScanner::scan_roots(&roots);
+        assert_eq!(recordings.len(), 4);
+
+        // The duplicate name is served from the first root
+        let duplicate = recordings.iter().find(|r| r.name == "recording_001").unwrap();
+        assert_eq!(duplicate.root, ssd.path());
+        let archived = recordings.iter().find(|r| r.name == "archive_001").unwrap();
+        assert_eq!(archived.root, external.path());
+
+        assert_eq!(FileScanner::find_root(&roots, "archive_001"), Some(external.path()));
+        assert_eq!(FileScanner::find_root(&roots, "recording_001"), Some(ssd.path()));
+        assert_eq!(FileScanner::find_root(&roots, "missing"), None);
+        assert_eq!(FileScanner::find_root(&roots, ""), None);
+    }
+
     #[test]
     fn test_is_valid_recording_dir() {
         let temp_dir = create_test_recordings_structure();
@@ -220,13 +556,50 @@ mod tests {
     }
 
     #[test]
-    fn test_get_recordings_needing_attention() {
-        let temp_dir = create_test_recordings_structure();
-        let recordings = FileScanner::scan_recordings(temp_dir.path());
-
-        // All recordings need attention (none are fully uploaded)
-        let needing_attention = FileScanner::get_recordings_needing_attention(&recordings);
-        assert_eq!(needing_attention.len(), 3);
+    fn test_query_filters_sorts_and_paginates() {
+        let recording = |name: &str, last_updated: u64, size: u64, status: RecordingStatus| Recording {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            status,
+            last_updated,
+            file_sizes: [(format!("{}.mkv", name), size)].into_iter().collect(),
+            upload_block: None,
+            root: PathBuf::new(),
+            lifecycle: None,
+            media: None,
+            tree: None,
+        };
+        let recordings = vec![
+            recording("concert_a", 100, 30, RecordingStatus::Uploaded),
+            recording("Concert_B", 300, 10, RecordingStatus::Recorded),
+            recording("rehearsal", 200, 20, RecordingStatus::Recorded),
+        ];
+
+        let page = FileScanner::query(&recordings, &RecordingQuery::default());
+        let names: Vec<&str> = page.items.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["Concert_B", "rehearsal", "concert_a"]);
+
+        let query = RecordingQuery {
+            name_contains: Some("CONCERT".to_string()),
+            sort_by: RecordingSortBy::Size,
+            ..Default::default()
+        };
+        let page = FileScanner::query(&recordings, &query);
+        assert_eq!(page.total, 2);
+        assert_eq!(page.items[0].name, "Concert_B");
+
+        let query = RecordingQuery {
+            status: Some("recorded".to_string()),
+            date_range: Some(DateRange { from: Some(150), to: None }),
+            sort_by: RecordingSortBy::Name,
+            page: 1,
+            page_size: Some(1),
+            ..Default::default()
+        };
+        let page = FileScanner::query(&recordings, &query);
+        assert_eq!(page.total, 2);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].name, "rehearsal");
     }
 
     #[test]
@@ -244,4 +617,36 @@ mod tests {
             assert!(recording.last_updated > 1000000000); // After year 2001
         }
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_symlinked_recordings() {
+        use std::os::unix::fs::symlink;
+        let temp_dir = create_test_recordings_structure();
+        let root = temp_dir.path().join("root");
+        fs::create_dir_all(&root).unwrap();
+        symlink(temp_dir.path().join("recording_001"), root.join("linked")).unwrap();
+        symlink(temp_dir.path().join("recording_001"), root.join("linked_again")).unwrap();
+        symlink(&root, root.join("loop")).unwrap();
+        symlink(temp_dir.path().join("gone"), root.join("broken")).unwrap();
+
+        let followed = FileScanner::scan_recordings_with(&root, SymlinkPolicy::Follow, &ScanOptions::default());
+        assert_eq!(followed.len(), 1);
+        assert!(followed[0].name.starts_with("linked"));
+        assert!(FileScanner::scan_recordings_with(&root, SymlinkPolicy::Ignore, &ScanOptions::default()).is_empty());
+
+        assert_eq!(SymlinkPolicy::parse(" Ignore "), Some(SymlinkPolicy::Ignore));
+        assert_eq!(SymlinkPolicy::parse("yes"), None);
+    }
+
+    #[test]
+    fn test_ignored_dirs_are_not_recordings() {
+        let temp_dir = create_test_recordings_structure();
+        let ignored = temp_dir.path().join("node_modules");
+        fs::create_dir_all(&ignored).unwrap();
+        fs::write(ignored.join("demo.mp4"), b"video").unwrap();
+
+        assert!(!FileScanner::is_valid_recording_dir(&ignored));
+        assert_eq!(FileScanner::scan_recordings(temp_dir.path()).len(), 3);
+    }
 }
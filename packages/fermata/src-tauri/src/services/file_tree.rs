@@ -0,0 +1,134 @@
+use crate::models::FileTreeNode;
+use crate::services::{storage_stage, SymlinkPolicy};
+use std::path::{Component, Path};
+
+/// Levels get_recording_tree lists when not told otherwise
+pub const DEFAULT_TREE_DEPTH: usize = 3;
+
+/// Stage of a directory whose files belong to more than one stage
+pub const MIXED_STAGE: &str = "mixed";
+
+fn node(name: &str, path: String, is_dir: bool) -> FileTreeNode {
+    FileTreeNode {
+        name: name.to_string(),
+        path,
+        is_dir,
+        size_bytes: 0,
+        modified: 0,
+        file_count: 0,
+        stage: String::new(),
+        truncated: false,
+        children: Vec::new(),
+    }
+}
+
+/// Tree of the files in `recording_path`, listed `max_depth` levels deep. Empty
+/// directories are left out; links are followed per the scan symlink policy.
+pub fn file_tree(recording_path: &Path, max_depth: usize) -> FileTreeNode {
+    let name = recording_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let mut root = node(&name, String::new(), true);
+    for entry in SymlinkPolicy::current().files(recording_path) {
+        let Ok(relative) = entry.path().strip_prefix(recording_path) else {
+            continue;
+        };
+        let parts: Vec<String> = relative
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect();
+        let metadata = entry.metadata().ok();
+        let file = FileFacts {
+            size_bytes: metadata.as_ref().map_or(0, |m| m.len()),
+            modified: metadata
+                .and_then(|m| m.modified().ok())
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_secs()),
+            stage: storage_stage(&parts.join("/")),
+        };
+        add(&mut root, &parts, &file, 0, max_depth);
+    }
+    sort(&mut root);
+    root
+}
+
+fn add(tree: &mut FileTreeNode, parts: &[String], file: &FileFacts, depth: usize, max_depth: usize) {
+    if tree.file_count == 0 {
+        tree.stage = file.stage.to_string();
+    } else if tree.stage != file.stage {
+        tree.stage = MIXED_STAGE.to_string();
+    }
+    tree.size_bytes += file.size_bytes;
+    tree.modified = tree.modified.max(file.modified);
+    tree.file_count += 1;
+
+    let Some((first, rest)) = parts.split_first() else {
+        return;
+    };
+    if depth >= max_depth {
+        tree.truncated = true;
+        return;
+    }
+    let index = match tree.children.iter().position(|c| c.name == *first) {
+        Some(index) => index,
+        None => {
+            let path = if tree.path.is_empty() { first.clone() } else { format!("{}/{}", tree.path, first) };
+            tree.children.push(node(first, path, !rest.is_empty()));
+            tree.children.len() - 1
+        }
+    };
+    add(&mut tree.children[index], rest, file, depth + 1, max_depth);
+}
+
+/// Directories first, then by name
+fn sort(tree: &mut FileTreeNode) {
+    tree.children.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+    for child in &mut tree.children {
+        sort(child);
+    }
+}
+
+struct FileFacts {
+    size_bytes: u64,
+    modified: u64,
+    stage: &'static str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_build_aggregates_and_truncates() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("gig");
+        fs::create_dir_all(path.join("extracted")).unwrap();
+        fs::create_dir_all(path.join("blender/render/frames")).unwrap();
+        fs::write(path.join("gig.mkv"), vec![0u8; 100]).unwrap();
+        fs::write(path.join("extracted/Mic.m4a"), vec![0u8; 10]).unwrap();
+        fs::write(path.join("blender/project.blend"), vec![0u8; 5]).unwrap();
+        fs::write(path.join("blender/render/frames/0001.png"), vec![0u8; 2]).unwrap();
+        fs::write(path.join("blender/render/frames/0002.png"), vec![0u8; 2]).unwrap();
+
+        let tree = file_tree(&path, 2);
+        assert_eq!((tree.name.as_str(), tree.size_bytes, tree.file_count), ("gig", 119, 5));
+        assert_eq!(tree.stage, MIXED_STAGE);
+        assert!(tree.modified > 0);
+        let names: Vec<_> = tree.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["blender", "extracted", "gig.mkv"]);
+        assert_eq!(tree.children[2].stage, "raw_video");
+
+        let blender = &tree.children[0];
+        assert_eq!((blender.size_bytes, blender.stage.as_str()), (9, MIXED_STAGE));
+        let render = blender.children.iter().find(|c| c.name == "render").unwrap();
+        assert_eq!((render.path.as_str(), render.size_bytes, render.stage.as_str()), ("blender/render", 4, "render"));
+        assert!(render.truncated && render.children.is_empty());
+
+        let shallow = file_tree(&path, 0);
+        assert!(shallow.truncated && shallow.children.is_empty());
+        assert_eq!(shallow.size_bytes, 119);
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Image extensions Blender writes for frame sequences
+pub const FRAME_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "exr", "tif", "tiff"];
+
+/// Frame count for a render directory at a given directory mtime
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameCount {
+    pub frames: usize,
+    /// Highest frame number seen in file names (e.g. frame_0420.png -> 420)
+    pub latest_frame: Option<u32>,
+}
+
+/// Counts rendered frames from directory entries only (no per-file metadata),
+/// reusing the previous count while the directory mtime is unchanged.
+#[derive(Debug, Default)]
+pub struct FrameCounter {
+    cache: Mutex<HashMap<PathBuf, (SystemTime, FrameCount)>>,
+}
+
+impl FrameCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count frames in a render directory, using the cache when nothing was added
+    pub fn count(&self, render_dir: &Path) -> FrameCount {
+        let mtime = fs::metadata(render_dir).and_then(|m| m.modified()).ok();
+
+        if let Some(mtime) = mtime {
+            if let Ok(cache) = self.cache.lock() {
+                if let Some((cached_mtime, count)) = cache.get(render_dir) {
+                    if *cached_mtime == mtime {
+                        return *count;
+                    }
+                }
+            }
+        }
+
+        let count = Self::count_uncached(render_dir);
+
+        if let (Some(mtime), Ok(mut cache)) = (mtime, self.cache.lock()) {
+            cache.insert(render_dir.to_path_buf(), (mtime, count));
+        }
+        count
+    }
+
+    /// Walk the directory entries once, looking only at file names
+    pub fn count_uncached(render_dir: &Path) -> FrameCount {
+        let mut frames = 0;
+        let mut latest_frame = None;
+
+        if let Ok(entries) = fs::read_dir(render_dir) {
+            for entry in entries.flatten() {
+                let file_name = entry.file_name();
+                let file_name = file_name.to_string_lossy();
+                let Some((stem, extension)) = file_name.rsplit_once('.') else {
+                    continue;
+                };
+
+                if !FRAME_EXTENSIONS.contains(&extension.to_lowercase().as_str()) {
+                    continue;
+                }
+
+                frames += 1;
+                if let Some(number) = frame_number(stem) {
+                    latest_frame = latest_frame.max(Some(number));
+                }
+            }
+        }
+
+        FrameCount { frames, latest_frame }
+    }
+}
+
+/// Trailing digits of a frame file stem
+pub fn frame_number(stem: &str) -> Option<u32> {
+    let digits: String = stem
+        .chars()
+        .rev()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_count_frames_and_latest_number() {
+        let temp_dir = TempDir::new().unwrap();
+        for i in [1, 2, 10] {
+            fs::write(temp_dir.path().join(format!("frame_{:04}.png", i)), "").unwrap();
+        }
+        fs::write(temp_dir.path().join("final.mp4"), "").unwrap();
+
+        let count = FrameCounter::count_uncached(temp_dir.path());
+        assert_eq!(count.frames, 3);
+        assert_eq!(count.latest_frame, Some(10));
+    }
+
+    #[test]
+    fn test_cached_count_matches_uncached() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("0001.exr"), "").unwrap();
+        let counter = FrameCounter::new();
+
+        assert_eq!(counter.count(temp_dir.path()).frames, 1);
+        assert_eq!(counter.count(temp_dir.path()).frames, 1);
+    }
+
+    #[test]
+    fn test_missing_directory_counts_zero() {
+        let temp_dir = TempDir::new().unwrap();
+        let count = FrameCounter::new().count(&temp_dir.path().join("missing"));
+        assert_eq!(count, FrameCount { frames: 0, latest_frame: None });
+    }
+}
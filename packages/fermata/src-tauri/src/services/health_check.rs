@@ -0,0 +1,115 @@
+use crate::services::DiskSpace;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    /// Some features won't work, the pipeline itself can run
+    Warning,
+    /// The pipeline can't run until this is fixed
+    Error,
+}
+
+/// Result of one diagnostic, e.g. "ffmpeg available"
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HealthCheck {
+    pub id: String,
+    pub label: String,
+    pub status: HealthStatus,
+    pub detail: String,
+}
+
+impl HealthCheck {
+    /// A check that is an error when it fails, or only a warning when `required` is false
+    pub fn from_result(id: &str, label: &str, result: Result<String, String>, required: bool) -> Self {
+        let (status, detail) = match result {
+            Ok(detail) => (HealthStatus::Ok, detail),
+            Err(detail) if required => (HealthStatus::Error, detail),
+            Err(detail) => (HealthStatus::Warning, detail),
+        };
+        Self {
+            id: id.to_string(),
+            label: label.to_string(),
+            status,
+            detail,
+        }
+    }
+}
+
+/// Environment diagnostics shown on startup
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HealthReport {
+    /// No check ended in an error
+    pub healthy: bool,
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    pub fn new(checks: Vec<HealthCheck>) -> Self {
+        Self {
+            healthy: checks.iter().all(|c| c.status != HealthStatus::Error),
+            checks,
+        }
+    }
+}
+
+/// Check that files can be created in the recordings directory; the probe goes
+/// into its `.fermata/` directory, which scans never take for a recording
+pub fn check_writable(dir: &Path) -> Result<String, String> {
+    if !dir.is_dir() {
+        return Err(format!("{} does not exist", dir.display()));
+    }
+
+    let state_dir = dir.join(".fermata");
+    fs::create_dir_all(&state_dir).map_err(|e| format!("{} is not writable: {}", dir.display(), e))?;
+    let probe = state_dir.join(".health-check");
+    fs::write(&probe, b"").map_err(|e| format!("{} is not writable: {}", dir.display(), e))?;
+    let _ = fs::remove_file(&probe);
+    Ok(format!("{} is writable", dir.display()))
+}
+
+/// Free space of the recordings volume; low space only pauses renders
+pub fn check_disk_space(dir: &Path, min_free_space_gb: u64) -> HealthCheck {
+    let result = DiskSpace::check(dir, min_free_space_gb);
+    let low = result.as_ref().map(|space| space.low).unwrap_or(false);
+    let result = result.and_then(|space| {
+        let detail = format!("{} GB free", space.available_bytes / (1024 * 1024 * 1024));
+        if space.low {
+            Err(format!("{}, below {} GB - renders are paused", detail, min_free_space_gb))
+        } else {
+            Ok(detail)
+        }
+    });
+    // Unreadable free space is an error, low space only a warning
+    HealthCheck::from_result("disk_space", "Free disk space", result, !low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_report_is_unhealthy_only_on_errors() {
+        let warning = HealthCheck::from_result("blender", "Blender", Err("not found".to_string()), false);
+        let ok = HealthCheck::from_result("uv", "uv", Ok("uv 0.4.0".to_string()), true);
+        assert_eq!(warning.status, HealthStatus::Warning);
+        assert!(HealthReport::new(vec![ok.clone(), warning]).healthy);
+
+        let error = HealthCheck::from_result("ffmpeg", "ffmpeg", Err("not found".to_string()), true);
+        assert!(!HealthReport::new(vec![ok, error]).healthy);
+    }
+
+    #[test]
+    fn test_check_writable() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(check_writable(temp_dir.path()).is_ok());
+        let entries: Vec<_> = fs::read_dir(temp_dir.path()).unwrap().flatten().map(|e| e.file_name()).collect();
+        assert_eq!(entries, [".fermata"]);
+        assert!(fs::read_dir(temp_dir.path().join(".fermata")).unwrap().next().is_none());
+        assert!(check_writable(&temp_dir.path().join("missing")).is_err());
+    }
+}
@@ -0,0 +1,435 @@
+use crate::services::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+/// Finished jobs kept around so the UI can show what ran overnight
+const FINISHED_JOBS_KEPT: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    /// Was running when the app quit or crashed; waits for resume or cancel
+    /// instead of running again on its own
+    Interrupted,
+    Done,
+    Failed(String),
+}
+
+/// A failed run of a step within a job
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JobAttempt {
+    pub step: String,
+    pub failed_at: u64, // Unix timestamp in seconds
+    pub error: String,
+    /// When the job tries the step again; None when it gave up
+    pub retry_at: Option<u64>,
+}
+
+/// Run the pipeline of one recording up to `target_step`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Job {
+    pub id: u64,
+    pub recording_name: String,
+    /// Last step to run, e.g. "setup_render"
+    pub target_step: String,
+    /// Animation preset used for the SetupRender step
+    pub preset: Option<String>,
+    /// What queued the job, e.g. "auto-ingest"
+    pub source: String,
+    pub status: JobStatus,
+    /// Unix time before which the job does not start, set by hand or by the job window
+    #[serde(default)]
+    pub run_after: Option<u64>,
+    /// Expected run time of the steps left, filled in by list_jobs
+    #[serde(default)]
+    pub estimated_ms: Option<u64>,
+    /// Failed runs of steps the job retries, e.g. uploads hit by a network blip
+    #[serde(default)]
+    pub attempts: Vec<JobAttempt>,
+}
+
+impl Job {
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status, JobStatus::Done | JobStatus::Failed(_))
+    }
+
+    fn is_due(&self, now: u64) -> bool {
+        self.status == JobStatus::Queued && self.run_after.map_or(true, |run_after| run_after <= now)
+    }
+
+    /// Failed runs of `step` so far
+    pub fn failed_attempts(&self, step: &str) -> u32 {
+        self.attempts.iter().filter(|a| a.step == step).count() as u32
+    }
+}
+
+/// Jobs processed one at a time by a background worker. Unfinished jobs are
+/// kept in the store file, once attached, so they survive restarts.
+pub struct JobQueue {
+    jobs: Mutex<Vec<Job>>,
+    next_id: AtomicU64,
+    wakeup: Notify,
+    store: Mutex<Option<PathBuf>>,
+    paused: AtomicBool,
+    /// Set once the app quits; true requeues the jobs it stops for the next launch
+    shutdown: Mutex<Option<bool>>,
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        Self {
+            jobs: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+            wakeup: Notify::new(),
+            store: Mutex::new(None),
+            paused: AtomicBool::new(false),
+            shutdown: Mutex::new(None),
+        }
+    }
+
+    /// Persist unfinished jobs to `path` from now on, first restoring the ones saved
+    /// there. Jobs the app quit during come back interrupted rather than running again.
+    pub fn attach_store(&self, path: PathBuf) -> Result<usize, String> {
+        let restored: Vec<Job> = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Invalid job store {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Failed to read job store {}: {}", path.display(), e)),
+        };
+        *self.store.lock().unwrap() = Some(path);
+
+        let mut jobs = self.jobs.lock().unwrap();
+        for mut job in restored {
+            if jobs.iter().any(|j| j.recording_name == job.recording_name && !j.is_finished()) {
+                continue;
+            }
+            job.id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            if job.status == JobStatus::Running {
+                log::warn!("Job for '{}' was interrupted while running", job.recording_name);
+                job.status = JobStatus::Interrupted;
+            } else {
+                job.status = JobStatus::Queued;
+            }
+            jobs.push(job);
+        }
+        let count = jobs.iter().filter(|j| !j.is_finished()).count();
+        self.persist(&jobs);
+        drop(jobs);
+
+        self.wakeup.notify_one();
+        Ok(count)
+    }
+
+    fn persist(&self, jobs: &[Job]) {
+        let Some(path) = self.store.lock().unwrap().clone() else {
+            return;
+        };
+        let pending: Vec<&Job> = jobs.iter().filter(|j| !j.is_finished()).collect();
+        let written = serde_json::to_string(&pending)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                write_atomic(&path, content).map_err(|e| e.to_string())
+            });
+        if let Err(e) = written {
+            log::warn!("Failed to save jobs to {}: {}", path.display(), e);
+        }
+    }
+
+    /// Queue a job; a recording with a queued or running job is not queued twice
+    pub fn enqueue(
+        &self,
+        recording_name: &str,
+        target_step: &str,
+        preset: Option<String>,
+        source: &str
+    ) -> Option<Job> {
+        self.enqueue_at(recording_name, target_step, preset, source, None)
+    }
+
+    /// Like enqueue, starting the job no earlier than `run_after` (Unix time)
+    pub fn enqueue_at(
+        &self,
+        recording_name: &str,
+        target_step: &str,
+        preset: Option<String>,
+        source: &str,
+        run_after: Option<u64>
+    ) -> Option<Job> {
+        let mut jobs = self.jobs.lock().unwrap();
+        if jobs.iter().any(|j| j.recording_name == recording_name && !j.is_finished()) {
+            return None;
+        }
+
+        let job = Job {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            recording_name: recording_name.to_string(),
+            target_step: target_step.to_string(),
+            preset,
+            source: source.to_string(),
+            status: JobStatus::Queued,
+            run_after,
+            estimated_ms: None,
+            attempts: Vec::new(),
+        };
+        jobs.push(job.clone());
+        self.persist(&jobs);
+        drop(jobs);
+
+        self.wakeup.notify_one();
+        Some(job)
+    }
+
+    /// Hold back queued jobs until unpaused; running ones finish their current step
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+        if !paused {
+            self.wakeup.notify_one();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Start no more jobs because the app quits. Jobs stopped by the quit are
+    /// queued again on the next launch with `requeue`, otherwise they come back interrupted.
+    pub fn begin_shutdown(&self, requeue: bool) {
+        *self.shutdown.lock().unwrap() = Some(requeue);
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutdown.lock().unwrap().is_some()
+    }
+
+    /// Leave a running job stopped by the quit, or all of them without `id`, as
+    /// begin_shutdown chose
+    pub fn stop_for_quit(&self, id: Option<u64>) {
+        let Some(requeue) = *self.shutdown.lock().unwrap() else {
+            return;
+        };
+        let mut jobs = self.jobs.lock().unwrap();
+        for job in jobs.iter_mut().filter(|j| id.map_or(j.status == JobStatus::Running, |id| j.id == id)) {
+            job.status = if requeue { JobStatus::Queued } else { JobStatus::Interrupted };
+            job.run_after = None;
+        }
+        self.persist(&jobs);
+    }
+
+    pub fn running_count(&self) -> usize {
+        self.jobs.lock().unwrap().iter().filter(|j| j.status == JobStatus::Running).count()
+    }
+
+    /// Mark the oldest queued job that is due at `now` as running and return it
+    pub fn start_next(&self, now: u64) -> Option<Job> {
+        if self.is_paused() || self.is_shutting_down() {
+            return None;
+        }
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs.iter_mut().find(|j| j.is_due(now))?;
+        job.status = JobStatus::Running;
+        let job = job.clone();
+        self.persist(&jobs);
+        Some(job)
+    }
+
+    /// Change when a queued job starts; None starts it as soon as possible
+    pub fn reschedule(&self, id: u64, run_after: Option<u64>) -> Result<Job, String> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .iter_mut()
+            .find(|j| j.id == id)
+            .ok_or_else(|| format!("Job {} not found", id))?;
+        if job.status != JobStatus::Queued {
+            return Err(format!("Job {} is {:?} and can no longer be rescheduled", id, job.status));
+        }
+        job.run_after = run_after;
+        let job = job.clone();
+        self.persist(&jobs);
+        drop(jobs);
+
+        self.wakeup.notify_one();
+        Ok(job)
+    }
+
+    /// Put a running job back in the queue until `run_after`, e.g. to wait for the job window
+    pub fn defer(&self, id: u64, run_after: u64) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.iter_mut().find(|j| j.id == id) {
+            job.status = JobStatus::Queued;
+            job.run_after = Some(run_after);
+        }
+        self.persist(&jobs);
+    }
+
+    /// Add a failed run of a step to a job's history
+    pub fn record_attempt(&self, id: u64, attempt: JobAttempt) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.iter_mut().find(|j| j.id == id) {
+            job.attempts.push(attempt);
+        }
+        self.persist(&jobs);
+    }
+
+    /// Queue an interrupted job again; it continues from its recording's current state
+    pub fn resume(&self, id: u64) -> Result<Job, String> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .iter_mut()
+            .find(|j| j.id == id)
+            .ok_or_else(|| format!("Job {} not found", id))?;
+        if job.status != JobStatus::Interrupted {
+            return Err(format!("Job {} is {:?} and was not interrupted", id, job.status));
+        }
+        job.status = JobStatus::Queued;
+        job.run_after = None;
+        let job = job.clone();
+        self.persist(&jobs);
+        drop(jobs);
+
+        self.wakeup.notify_one();
+        Ok(job)
+    }
+
+    /// Remove a job that has not started yet or was interrupted
+    pub fn cancel(&self, id: u64) -> Result<Job, String> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let index = jobs
+            .iter()
+            .position(|j| j.id == id)
+            .ok_or_else(|| format!("Job {} not found", id))?;
+        if !matches!(jobs[index].status, JobStatus::Queued | JobStatus::Interrupted) {
+            return Err(format!("Job {} is {:?} and can no longer be cancelled", id, jobs[index].status));
+        }
+        let job = jobs.remove(index);
+        self.persist(&jobs);
+        Ok(job)
+    }
+
+    pub fn finish(&self, id: u64, result: Result<(), String>) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.iter_mut().find(|j| j.id == id) {
+            job.status = match result {
+                Ok(()) => JobStatus::Done,
+                Err(e) => JobStatus::Failed(e),
+            };
+        }
+
+        // Forget the oldest finished jobs beyond the history limit
+        let finished = jobs.iter().filter(|j| j.is_finished()).count();
+        let mut excess = finished.saturating_sub(FINISHED_JOBS_KEPT);
+        jobs.retain(|j| {
+            if excess > 0 && j.is_finished() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+        self.persist(&jobs);
+    }
+
+    pub fn list(&self) -> Vec<Job> {
+        self.jobs.lock().unwrap().clone()
+    }
+
+    /// Wait until a job is queued
+    pub async fn wait(&self) {
+        self.wakeup.notified().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jobs_run_in_order_without_duplicates() {
+        let queue = JobQueue::new();
+        let first = queue.enqueue("rec_1", "analyze", None, "auto-ingest").unwrap();
+        assert!(queue.enqueue("rec_1", "render", None, "auto-ingest").is_none());
+        queue.enqueue("rec_2", "analyze", None, "auto-ingest").unwrap();
+
+        let running = queue.start_next(0).unwrap();
+        assert_eq!(running.id, first.id);
+        assert_eq!(queue.start_next(0).unwrap().recording_name, "rec_2");
+        assert!(queue.start_next(0).is_none());
+
+        assert_eq!(queue.running_count(), 2);
+
+        queue.finish(first.id, Err("boom".to_string()));
+        assert_eq!(queue.list()[0].status, JobStatus::Failed("boom".to_string()));
+        assert!(queue.enqueue("rec_1", "analyze", None, "auto-ingest").is_some());
+        queue.set_paused(true);
+        assert!(queue.start_next(0).is_none());
+        queue.set_paused(false);
+        assert_eq!(queue.start_next(0).unwrap().recording_name, "rec_1");
+    }
+
+    #[test]
+    fn test_scheduled_jobs_wait_and_survive_restarts() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = temp_dir.path().join("jobs.json");
+        let queue = JobQueue::new();
+        queue.attach_store(store.clone()).unwrap();
+
+        let later = queue.enqueue_at("rec_1", "render", None, "manual", Some(1_000)).unwrap();
+        assert!(queue.start_next(999).is_none());
+        queue.enqueue("rec_2", "analyze", None, "manual").unwrap();
+        let running = queue.start_next(999).unwrap();
+        assert_eq!(running.recording_name, "rec_2");
+        queue.defer(running.id, 2_000);
+        assert_eq!(queue.start_next(1_000).unwrap().id, later.id);
+        let attempt = JobAttempt { step: "upload".to_string(), failed_at: 1_000, error: "timed out".to_string(), retry_at: Some(2_000) };
+        queue.record_attempt(running.id, attempt);
+
+        let restarted = JobQueue::new();
+        assert_eq!(restarted.attach_store(store).unwrap(), 2);
+        let jobs = restarted.list();
+        // The job running when the app quit waits to be resumed
+        let interrupted = jobs.iter().find(|j| j.recording_name == "rec_1").unwrap();
+        assert_eq!(interrupted.status, JobStatus::Interrupted);
+        assert!(restarted.start_next(1_999).is_none());
+        assert!(restarted.enqueue("rec_1", "render", None, "manual").is_none());
+        assert_eq!(restarted.resume(interrupted.id).unwrap().status, JobStatus::Queued);
+        assert!(restarted.resume(interrupted.id).is_err());
+        let deferred = jobs.iter().find(|j| j.recording_name == "rec_2").unwrap();
+        assert_eq!(deferred.run_after, Some(2_000));
+        assert_eq!(deferred.failed_attempts("upload"), 1);
+        assert!(restarted.reschedule(deferred.id, None).is_ok());
+        assert_eq!(restarted.cancel(deferred.id).unwrap().recording_name, "rec_2");
+        assert!(restarted.cancel(deferred.id).is_err());
+    }
+
+    #[test]
+    fn test_quit_requeues_running_jobs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = temp_dir.path().join("jobs.json");
+        let queue = JobQueue::new();
+        queue.attach_store(store.clone()).unwrap();
+        queue.enqueue("rec_1", "render", None, "manual").unwrap();
+        queue.enqueue("rec_2", "render", None, "manual").unwrap();
+        let running = queue.start_next(0).unwrap();
+
+        queue.begin_shutdown(true);
+        assert!(queue.start_next(0).is_none());
+        queue.stop_for_quit(None);
+
+        let restarted = JobQueue::new();
+        restarted.attach_store(store).unwrap();
+        assert_eq!(restarted.start_next(0).unwrap().recording_name, running.recording_name);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::models::NextStep;
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+
+/// Settings key for the hours queued heavy steps may run in
+pub const JOB_WINDOW_KEY: &str = "job_window";
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// Local hours in which queued jobs may run `steps`, e.g. renders only between
+/// 01:00 and 07:00. A window ending before it starts spans midnight. Jobs that
+/// reach one of the steps outside the window wait for it to open.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JobWindow {
+    pub enabled: bool,
+    /// "HH:MM"
+    pub start: String,
+    /// "HH:MM", exclusive
+    pub end: String,
+    /// Step ids held back until the window opens
+    pub steps: Vec<String>,
+}
+
+impl Default for JobWindow {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: "01:00".to_string(),
+            end: "07:00".to_string(),
+            steps: vec!["render".to_string()],
+        }
+    }
+}
+
+impl JobWindow {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(JOB_WINDOW_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        self.validate()?;
+        settings.set(JOB_WINDOW_KEY, self)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let start = parse_time(&self.start)?;
+        if start == parse_time(&self.end)? {
+            return Err("The job window must not start and end at the same time".to_string());
+        }
+        for step in &self.steps {
+            step.parse::<NextStep>()?;
+        }
+        Ok(())
+    }
+
+    fn applies_to(&self, step: &NextStep) -> bool {
+        let id = step.id();
+        self.enabled && self.steps.iter().any(|s| s.eq_ignore_ascii_case(&id))
+    }
+
+    /// Seconds until `step` may run, given the local time as seconds since midnight;
+    /// 0 when it may run now
+    pub fn wait_seconds(&self, step: &NextStep, seconds_of_day: u32) -> Result<u64, String> {
+        if !self.applies_to(step) {
+            return Ok(0);
+        }
+        let (start, end) = (parse_time(&self.start)?, parse_time(&self.end)?);
+        let minute = seconds_of_day / 60 % MINUTES_PER_DAY;
+        let open = if start < end {
+            (start..end).contains(&minute)
+        } else {
+            minute >= start || minute < end
+        };
+        if open {
+            return Ok(0);
+        }
+        let minutes = (start + MINUTES_PER_DAY - minute) % MINUTES_PER_DAY;
+        Ok(u64::from(minutes) * 60 - u64::from(seconds_of_day % 60))
+    }
+}
+
+/// Minutes since midnight of "HH:MM"
+fn parse_time(value: &str) -> Result<u32, String> {
+    let invalid = || format!("Invalid time '{}', expected HH:MM", value);
+    let (hours, minutes) = value.split_once(':').ok_or_else(invalid)?;
+    let hours: u32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: u32 = minutes.parse().map_err(|_| invalid())?;
+    if hours > 23 || minutes > 59 {
+        return Err(invalid());
+    }
+    Ok(hours * 60 + minutes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(start: &str, end: &str) -> JobWindow {
+        JobWindow { enabled: true, start: start.to_string(), end: end.to_string(), ..JobWindow::default() }
+    }
+
+    #[test]
+    fn test_wait_seconds() {
+        let night = window("01:00", "07:00");
+        assert_eq!(night.wait_seconds(&NextStep::Render, 3 * 3600), Ok(0));
+        assert_eq!(night.wait_seconds(&NextStep::Render, 7 * 3600), Ok(18 * 3600));
+        assert_eq!(night.wait_seconds(&NextStep::Render, 30 * 60 + 15), Ok(30 * 60 - 15));
+        assert_eq!(night.wait_seconds(&NextStep::Analyze, 12 * 3600), Ok(0));
+
+        let overnight = window("22:00", "06:00");
+        assert_eq!(overnight.wait_seconds(&NextStep::Render, 23 * 3600), Ok(0));
+        assert_eq!(overnight.wait_seconds(&NextStep::Render, 5 * 3600), Ok(0));
+        assert_eq!(overnight.wait_seconds(&NextStep::Render, 21 * 3600), Ok(3600));
+
+        let disabled = JobWindow { enabled: false, ..night };
+        assert_eq!(disabled.wait_seconds(&NextStep::Render, 12 * 3600), Ok(0));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(window("01:00", "07:00").validate().is_ok());
+        assert!(window("1:00", "24:00").validate().is_err());
+        assert!(window("05:00", "05:00").validate().is_err());
+        let unknown_step = JobWindow { steps: vec!["nap".to_string()], ..window("01:00", "07:00") };
+        assert!(unknown_step.validate().is_err());
+    }
+}
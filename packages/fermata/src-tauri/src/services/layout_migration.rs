@@ -0,0 +1,248 @@
+use crate::services::{write_atomic, ConfigStore};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Project directory written by the old obsession `blender_vse` pipeline
+const LEGACY_BLENDER_DIR: &str = "blender_vse";
+
+/// One file or directory move needed to reach the current layout
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LayoutMove {
+    /// Paths relative to the recording directory
+    pub from: String,
+    pub to: String,
+    pub reason: String,
+}
+
+/// Detects pre-monorepo recording layouts and moves them into the current structure:
+/// `analysis/` for beatrix output and `blender/<recording>.blend` for the Blender project.
+pub struct LayoutMigration;
+
+impl LayoutMigration {
+    /// Moves needed for a recording; empty when it already uses the current layout
+    pub fn plan(recording_path: &Path) -> Vec<LayoutMove> {
+        let mut moves = Vec::new();
+        let recording_name = recording_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        // Old beatrix CLI wrote <audio>_analysis.json next to the audio file
+        for dir in ["extracted", ""] {
+            for file in files_in(&recording_path.join(dir)) {
+                let name = file_name(&file);
+                if name.ends_with("_analysis.json") {
+                    moves.push(LayoutMove {
+                        from: join_relative(dir, &name),
+                        to: join_relative("analysis", &name),
+                        reason: "Analysis stored outside analysis/".to_string(),
+                    });
+                }
+            }
+        }
+
+        // blender_vse/ project directory and loose .blend files become blender/
+        let mut blend_sources: Vec<String> = files_in(&recording_path.join(LEGACY_BLENDER_DIR))
+            .iter()
+            .map(|f| join_relative(LEGACY_BLENDER_DIR, &file_name(f)))
+            .collect();
+        blend_sources.extend(
+            files_in(recording_path)
+                .iter()
+                .map(|f| file_name(f))
+                .filter(|n| n.ends_with(".blend")),
+        );
+
+        let blend_count = blend_sources.iter().filter(|n| n.ends_with(".blend")).count();
+        for from in blend_sources {
+            let name = from.rsplit('/').next().unwrap_or(&from).to_string();
+            // A single legacy project takes the current <recording>.blend name
+            let target_name = if blend_count == 1 && is_legacy_blend_name(&name) {
+                format!("{}.blend", recording_name)
+            } else {
+                name
+            };
+            moves.push(LayoutMove {
+                from,
+                to: join_relative("blender", &target_name),
+                reason: "Legacy Blender project location".to_string(),
+            });
+        }
+
+        // Subdirectories such as blender_vse/render/ move whole, next to the project
+        for dir in dirs_in(&recording_path.join(LEGACY_BLENDER_DIR)) {
+            let name = file_name(&dir);
+            moves.push(LayoutMove {
+                from: join_relative(LEGACY_BLENDER_DIR, &name),
+                to: join_relative("blender", &name),
+                reason: "Legacy Blender project location".to_string(),
+            });
+        }
+
+        // Already-current blender/ projects with blender_vse naming
+        let current_blends: Vec<PathBuf> = files_in(&recording_path.join("blender"))
+            .into_iter()
+            .filter(|f| file_name(f).ends_with(".blend"))
+            .collect();
+        if blend_count == 0 && current_blends.len() == 1 {
+            let name = file_name(&current_blends[0]);
+            if is_legacy_blend_name(&name) {
+                moves.push(LayoutMove {
+                    from: join_relative("blender", &name),
+                    to: join_relative("blender", &format!("{}.blend", recording_name)),
+                    reason: "Legacy blender_vse project name".to_string(),
+                });
+            }
+        }
+
+        moves
+    }
+
+    /// Apply planned moves and point generated configs at the new locations.
+    /// Moves whose target already exists are skipped and reported.
+    pub fn apply(recording_path: &Path, moves: &[LayoutMove]) -> Result<Vec<String>, String> {
+        let mut skipped = Vec::new();
+        let mut applied = Vec::new();
+
+        for layout_move in moves {
+            let from = recording_path.join(&layout_move.from);
+            let to = recording_path.join(&layout_move.to);
+            if to.exists() {
+                skipped.push(format!("{} (target {} exists)", layout_move.from, layout_move.to));
+                continue;
+            }
+
+            if let Some(parent) = to.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            fs::rename(&from, &to)
+                .map_err(|e| format!("Failed to move {} to {}: {}", layout_move.from, layout_move.to, e))?;
+            log::info!("📦 Migrated {} -> {}", layout_move.from, layout_move.to);
+            applied.push(layout_move);
+        }
+
+        // Remove the legacy project directory once it is empty
+        let legacy_dir = recording_path.join(LEGACY_BLENDER_DIR);
+        if legacy_dir.is_dir() && fs::read_dir(&legacy_dir).map(|mut d| d.next().is_none()).unwrap_or(false) {
+            let _ = fs::remove_dir(&legacy_dir);
+        }
+
+        Self::rewrite_config_references(recording_path, &applied)?;
+        Ok(skipped)
+    }
+
+    /// Animation configs reference analysis files by relative path
+    fn rewrite_config_references(recording_path: &Path, applied: &[&LayoutMove]) -> Result<(), String> {
+        for (config, _) in ConfigStore::configs(recording_path) {
+            let content = fs::read_to_string(&config)
+                .map_err(|e| format!("Failed to read {}: {}", config.display(), e))?;
+            let updated = applied
+                .iter()
+                .fold(content.clone(), |text, m| text.replace(&m.from, &m.to));
+            if updated != content {
+                write_atomic(&config, updated)
+                    .map_err(|e| format!("Failed to update {}: {}", config.display(), e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_legacy_blend_name(name: &str) -> bool {
+    name.starts_with("blender_vse") || name.ends_with("_vse.blend")
+}
+
+fn files_in(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.is_file()).collect())
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn dirs_in(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect())
+        .unwrap_or_default();
+    dirs.sort();
+    dirs
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+fn join_relative(dir: &str, name: &str) -> String {
+    if dir.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_migrate_legacy_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path().join("gig");
+        fs::create_dir_all(recording.join("extracted")).unwrap();
+        fs::create_dir_all(recording.join(LEGACY_BLENDER_DIR)).unwrap();
+        fs::write(recording.join("extracted/Mic_analysis.json"), "{}").unwrap();
+        fs::write(recording.join("blender_vse/blender_vse_project.blend"), "").unwrap();
+        fs::write(
+            recording.join("animation_config_beat-switch.yaml"),
+            "audio_analysis:\n  file: extracted/Mic_analysis.json\n",
+        )
+        .unwrap();
+
+        let moves = LayoutMigration::plan(&recording);
+        assert_eq!(moves.len(), 2);
+        assert_eq!(moves[1].to, "blender/gig.blend");
+
+        let skipped = LayoutMigration::apply(&recording, &moves).unwrap();
+        assert!(skipped.is_empty());
+        assert!(recording.join("analysis/Mic_analysis.json").exists());
+        assert!(recording.join("blender/gig.blend").exists());
+        assert!(!recording.join(LEGACY_BLENDER_DIR).exists());
+
+        let config = fs::read_to_string(recording.join("animation_config_beat-switch.yaml")).unwrap();
+        assert!(config.contains("file: analysis/Mic_analysis.json"));
+        assert!(LayoutMigration::plan(&recording).is_empty());
+    }
+
+    #[test]
+    fn test_migrate_legacy_subdirectories() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path().join("gig");
+        fs::create_dir_all(recording.join("blender_vse/render/frames")).unwrap();
+        fs::write(recording.join("blender_vse/project.blend"), "").unwrap();
+        fs::write(recording.join("blender_vse/render/frames/0001.png"), "").unwrap();
+        fs::write(recording.join("blender_vse/render/gig.mp4"), "").unwrap();
+
+        let moves = LayoutMigration::plan(&recording);
+        assert!(moves.iter().any(|m| m.from == "blender_vse/render" && m.to == "blender/render"));
+
+        LayoutMigration::apply(&recording, &moves).unwrap();
+        assert!(recording.join("blender/render/frames/0001.png").exists());
+        assert!(recording.join("blender/render/gig.mp4").exists());
+        assert!(!recording.join(LEGACY_BLENDER_DIR).exists());
+    }
+
+    #[test]
+    fn test_current_layout_needs_no_moves() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path().join("gig");
+        fs::create_dir_all(recording.join("analysis")).unwrap();
+        fs::create_dir_all(recording.join("blender")).unwrap();
+        fs::write(recording.join("analysis/Mic_analysis.json"), "{}").unwrap();
+        fs::write(recording.join("blender/gig.blend"), "").unwrap();
+
+        assert!(LayoutMigration::plan(&recording).is_empty());
+    }
+}
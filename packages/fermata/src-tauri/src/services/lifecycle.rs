@@ -0,0 +1,134 @@
+use crate::models::{Lifecycle, LifecycleBadge, Recording, RecordingStatus};
+use crate::services::StatusDetector;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::SystemTime;
+
+const SECONDS_PER_HOUR: u64 = 60 * 60;
+const SECONDS_PER_DAY: u64 = 24 * SECONDS_PER_HOUR;
+
+/// Thresholds for the time-based lifecycle stages
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LifecyclePolicy {
+    /// Untouched recordings count as new for this many hours
+    pub new_within_hours: u64,
+    /// Uploaded recordings become archivable after this many days
+    pub archivable_after_days: u64,
+}
+
+impl Default for LifecyclePolicy {
+    fn default() -> Self {
+        Self {
+            new_within_hours: 24,
+            archivable_after_days: 30,
+        }
+    }
+}
+
+impl LifecyclePolicy {
+    /// Classify a recording; `uploaded_at` is when its uploads/ directory last changed
+    pub fn classify(&self, recording: &Recording, uploaded_at: Option<u64>, now: u64) -> Lifecycle {
+        if recording.status.is_in_progress() {
+            return Lifecycle::InProgress;
+        }
+
+        match recording.status {
+            RecordingStatus::Uploaded => {
+                let archivable = uploaded_at.is_some_and(|at| {
+                    now.saturating_sub(at) >= self.archivable_after_days * SECONDS_PER_DAY
+                });
+                if archivable {
+                    Lifecycle::Archivable
+                } else {
+                    Lifecycle::Published
+                }
+            }
+            // A recording blocked from upload is never ready to publish
+            RecordingStatus::Rendered if recording.upload_block.is_none() => Lifecycle::ReadyToPublish,
+            RecordingStatus::Recorded
+                if now.saturating_sub(recording.last_updated) < self.new_within_hours * SECONDS_PER_HOUR =>
+            {
+                Lifecycle::New
+            }
+            _ => Lifecycle::InProgress,
+        }
+    }
+
+    /// Badge for a recording, reading the upload time from disk
+    pub fn badge(&self, recording: &Recording, now: u64) -> LifecycleBadge {
+        LifecycleBadge {
+            stage: self.classify(recording, uploaded_at(&recording.path), now),
+            age_days: now.saturating_sub(recording.last_updated) / SECONDS_PER_DAY,
+        }
+    }
+
+    /// Fill in the lifecycle badge of every recording
+    pub fn annotate(&self, recordings: &mut [Recording], now: u64) {
+        for recording in recordings {
+            recording.lifecycle = Some(self.badge(recording, now));
+        }
+    }
+}
+
+/// When the recording was uploaded, as a Unix timestamp in seconds, from the upload
+/// results. Results written without timestamps fall back to when uploads/ last changed.
+pub fn uploaded_at(recording_path: &Path) -> Option<u64> {
+    if let Some(at) = StatusDetector::read_upload_results(recording_path).and_then(|results| results.uploaded_at()) {
+        return Some(at);
+    }
+    let modified = std::fs::metadata(recording_path.join("uploads")).and_then(|m| m.modified()).ok()?;
+    modified.duration_since(SystemTime::UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UploadBlock;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    const NOW: u64 = 100 * SECONDS_PER_DAY;
+
+    fn recording(status: RecordingStatus, last_updated: u64) -> Recording {
+        Recording {
+            name: "gig".to_string(),
+            path: PathBuf::from("gig"),
+            status,
+            last_updated,
+            file_sizes: HashMap::new(),
+            upload_block: None,
+            root: PathBuf::new(),
+            lifecycle: None,
+            media: None,
+            tree: None,
+        }
+    }
+
+    #[test]
+    fn test_classify_by_status_and_age() {
+        let policy = LifecyclePolicy::default();
+
+        let fresh = recording(RecordingStatus::Recorded, NOW - SECONDS_PER_HOUR);
+        assert_eq!(policy.classify(&fresh, None, NOW), Lifecycle::New);
+        let stale = recording(RecordingStatus::Recorded, NOW - 2 * SECONDS_PER_DAY);
+        assert_eq!(policy.classify(&stale, None, NOW), Lifecycle::InProgress);
+
+        let rendering = recording(RecordingStatus::Rendering, NOW);
+        assert_eq!(policy.classify(&rendering, None, NOW), Lifecycle::InProgress);
+
+        let mut rendered = recording(RecordingStatus::Rendered, NOW);
+        assert_eq!(policy.classify(&rendered, None, NOW), Lifecycle::ReadyToPublish);
+        rendered.upload_block = Some(UploadBlock { reason: "private".to_string(), blocked_at: NOW });
+        assert_eq!(policy.classify(&rendered, None, NOW), Lifecycle::InProgress);
+    }
+
+    #[test]
+    fn test_uploaded_becomes_archivable() {
+        let policy = LifecyclePolicy { new_within_hours: 24, archivable_after_days: 30 };
+        let uploaded = recording(RecordingStatus::Uploaded, NOW - 40 * SECONDS_PER_DAY);
+
+        assert_eq!(policy.classify(&uploaded, Some(NOW - 5 * SECONDS_PER_DAY), NOW), Lifecycle::Published);
+        assert_eq!(policy.classify(&uploaded, Some(NOW - 31 * SECONDS_PER_DAY), NOW), Lifecycle::Archivable);
+        assert_eq!(policy.badge(&uploaded, NOW).age_days, 40);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::services::{extracted_audio_files, process_arg, UploadConfig};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Directory inside extracted/ holding the loudness-normalized audio tracks
+pub const NORMALIZED_AUDIO_DIR: &str = "normalized";
+
+/// Step id normalization locks the recording with; shown as a running custom step
+pub const NORMALIZE_AUDIO_STEP: &str = "normalize_audio";
+
+/// Integrated loudness the tracks are normalized to, the usual target for streaming platforms
+pub const DEFAULT_TARGET_LUFS: f64 = -16.0;
+
+/// Loudness targets ffmpeg's loudnorm filter accepts
+const TARGET_LUFS_RANGE: (f64, f64) = (-70.0, -5.0);
+
+pub fn validate_target_lufs(target_lufs: f64) -> Result<(), String> {
+    let (min, max) = TARGET_LUFS_RANGE;
+    if !(min..=max).contains(&target_lufs) {
+        return Err(format!("Target loudness must be between {} and {} LUFS, got {}", min, max, target_lufs));
+    }
+    Ok(())
+}
+
+/// ffmpeg arguments normalizing `input` to `target_lufs` in one loudnorm pass.
+/// loudnorm resamples to 192 kHz internally, so the output is set back to 48 kHz.
+pub fn loudnorm_args(input: &Path, output: &Path, target_lufs: f64) -> Vec<OsString> {
+    vec![
+        "-y".into(),
+        "-i".into(),
+        process_arg(input),
+        "-vn".into(),
+        "-af".into(),
+        format!("loudnorm=I={}:TP=-1.5:LRA=11", target_lufs).into(),
+        "-ar".into(),
+        "48000".into(),
+        process_arg(output),
+    ]
+}
+
+/// Where the normalized copy of an extracted audio track goes
+pub fn normalized_audio_path(recording_path: &Path, audio_file: &str) -> PathBuf {
+    recording_path.join("extracted").join(NORMALIZED_AUDIO_DIR).join(audio_file)
+}
+
+/// Path relative to extracted/ of the normalized copy of `audio_file`, when one
+/// exists and is newer than the track
+pub fn normalized_audio(recording_path: &Path, audio_file: &str) -> Option<String> {
+    let source = recording_path.join("extracted").join(audio_file);
+    UploadConfig::is_transcode_fresh(&source, &normalized_audio_path(recording_path, audio_file))
+        .then(|| format!("{}/{}", NORMALIZED_AUDIO_DIR, audio_file))
+}
+
+/// Main audio for the render setup with normalized tracks preferred. Without a
+/// chosen track the only extracted track is used, as cinemon would.
+pub fn prefer_normalized_audio(recording_path: &Path, main_audio: Option<String>) -> Option<String> {
+    let chosen = main_audio.clone().or_else(|| match extracted_audio_files(recording_path).ok()?.as_slice() {
+        [single] => Some(single.clone()),
+        _ => None,
+    })?;
+    normalized_audio(recording_path, &chosen).or(main_audio)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_target_lufs() {
+        assert!(validate_target_lufs(-16.0).is_ok());
+        assert!(validate_target_lufs(-14.0).is_ok());
+        assert!(validate_target_lufs(0.0).is_err());
+        assert!(validate_target_lufs(-80.0).is_err());
+    }
+
+    #[test]
+    fn test_prefer_normalized_audio() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+        fs::create_dir_all(recording.join("extracted")).unwrap();
+        fs::write(recording.join("extracted/Mic.m4a"), "audio").unwrap();
+        assert_eq!(prefer_normalized_audio(recording, None), None);
+        assert_eq!(prefer_normalized_audio(recording, Some("Mic.m4a".to_string())), Some("Mic.m4a".to_string()));
+
+        fs::create_dir_all(recording.join("extracted/normalized")).unwrap();
+        fs::write(normalized_audio_path(recording, "Mic.m4a"), "louder").unwrap();
+        assert_eq!(prefer_normalized_audio(recording, None), Some("normalized/Mic.m4a".to_string()));
+        assert_eq!(prefer_normalized_audio(recording, Some("Mic.m4a".to_string())), Some("normalized/Mic.m4a".to_string()));
+    }
+}
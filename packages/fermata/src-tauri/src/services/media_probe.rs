@@ -0,0 +1,310 @@
+use crate::models::{MediaInfo, RecordingMedia};
+use crate::services::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+/// Probe results are cached next to the recording; ffprobe on a long
+/// recording is too slow to run every time the details view opens
+pub const MEDIA_PROBE_FILE: &str = ".fermata/media_probe.json";
+
+/// Why ffprobe could not read the main video, kept while the file is unchanged
+pub const PROBE_FAILURE_FILE: &str = ".fermata/probe_failure.json";
+
+const VIDEO_EXTENSIONS: [&str; 4] = ["mkv", "mp4", "avi", "mov"];
+const RENDER_EXTENSIONS: [&str; 3] = ["mp4", "mkv", "avi"];
+
+/// One cached probe, valid while the file keeps its size and mtime
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct CachedProbe {
+    size: u64,
+    modified: u64,
+    info: MediaInfo,
+}
+
+/// An ffprobe failure on `file`, relative to the recording directory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct ProbeFailure {
+    file: String,
+    size: u64,
+    modified: u64,
+    error: String,
+}
+
+/// Cache keyed by the file path relative to the recording directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+struct ProbeCache(BTreeMap<String, CachedProbe>);
+
+pub struct MediaProbe;
+
+impl MediaProbe {
+    /// Probe the main and rendered videos of a recording, using the cache when
+    /// the files are unchanged
+    pub fn for_recording(recording_path: &Path, recording_name: &str) -> RecordingMedia {
+        Self::for_recording_with(recording_path, recording_name, Self::probe_file)
+    }
+
+    fn for_recording_with<F>(recording_path: &Path, recording_name: &str, probe: F) -> RecordingMedia
+    where
+        F: Fn(&Path) -> Option<MediaInfo>,
+    {
+        let mut cache = load_cache(recording_path);
+        let mut changed = false;
+
+        let mut probe_cached = |file: Option<PathBuf>| -> Option<MediaInfo> {
+            let file = file?;
+            let key = file.strip_prefix(recording_path).unwrap_or(&file).to_string_lossy().to_string();
+            let (size, modified) = size_and_mtime(&file)?;
+
+            if let Some(cached) = cache.0.get(&key) {
+                if cached.size == size && cached.modified == modified {
+                    return Some(cached.info.clone());
+                }
+            }
+
+            let info = probe(&file)?;
+            cache.0.insert(key, CachedProbe { size, modified, info: info.clone() });
+            changed = true;
+            Some(info)
+        };
+
+        let media = RecordingMedia {
+            main: probe_cached(main_video(recording_path, recording_name)),
+            rendered: probe_cached(rendered_video(recording_path)),
+        };
+
+        if changed {
+            if let Err(e) = save_cache(recording_path, &cache) {
+                log::warn!("Failed to cache media probe for {}: {}", recording_name, e);
+            }
+        }
+        media
+    }
+
+    /// Run ffprobe on a single file
+    pub fn probe_file(file: &Path) -> Option<MediaInfo> {
+        let output = Command::new("ffprobe")
+            .args(["-v", "error", "-print_format", "json", "-show_format", "-show_streams"])
+            .arg(file)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            log::warn!("ffprobe failed for {}", file.display());
+            return None;
+        }
+        parse_ffprobe_json(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Run ffprobe on the main video and remember when it can't read it, so scans
+    /// report the recording as corrupted. None when the video reads fine or
+    /// ffprobe is not installed.
+    pub fn check_main_video(recording_path: &Path, recording_name: &str) -> Option<String> {
+        let file = main_video(recording_path, recording_name)?;
+        let (size, modified) = size_and_mtime(&file)?;
+        let output = Command::new("ffprobe").args(["-v", "error", "-show_format"]).arg(&file).output().ok()?;
+        if output.status.success() {
+            let _ = fs::remove_file(recording_path.join(PROBE_FAILURE_FILE));
+            return None;
+        }
+
+        let key = file.strip_prefix(recording_path).unwrap_or(&file).to_string_lossy().to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error = format!("ffprobe can't read {}: {}", key, stderr.lines().last().unwrap_or("unknown error").trim());
+        let failure = ProbeFailure { file: key, size, modified, error: error.clone() };
+        if let Err(e) = save_json(recording_path, PROBE_FAILURE_FILE, &failure) {
+            log::warn!("Failed to remember probe failure of {}: {}", recording_name, e);
+        }
+        Some(error)
+    }
+
+    /// The failure check_main_video remembered, while the video is unchanged
+    pub fn probe_failure(recording_path: &Path) -> Option<String> {
+        let content = fs::read_to_string(recording_path.join(PROBE_FAILURE_FILE)).ok()?;
+        let failure: ProbeFailure = serde_json::from_str(&content).ok()?;
+        let current = size_and_mtime(&recording_path.join(&failure.file))?;
+        (current == (failure.size, failure.modified)).then_some(failure.error)
+    }
+}
+
+/// Parse `ffprobe -print_format json -show_format -show_streams` output
+fn parse_ffprobe_json(json: &str) -> Option<MediaInfo> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    let streams = value.get("streams").and_then(|s| s.as_array()).cloned().unwrap_or_default();
+    let of_type = |kind: &str| {
+        streams
+            .iter()
+            .filter(|s| s.get("codec_type").and_then(|t| t.as_str()) == Some(kind))
+            .collect::<Vec<_>>()
+    };
+    let video = of_type("video").into_iter().next();
+    let audio = of_type("audio");
+
+    let codec = |stream: &serde_json::Value| stream.get("codec_name").and_then(|c| c.as_str()).map(str::to_string);
+    let duration = value
+        .get("format")
+        .and_then(|f| f.get("duration"))
+        .or_else(|| video.and_then(|v| v.get("duration")))
+        .and_then(|d| d.as_str())
+        .and_then(|d| d.parse().ok());
+
+    Some(MediaInfo {
+        duration_secs: duration,
+        width: video.and_then(|v| v.get("width")?.as_u64()).map(|w| w as u32),
+        height: video.and_then(|v| v.get("height")?.as_u64()).map(|h| h as u32),
+        fps: video.and_then(|v| {
+            let rate = |key: &str| parse_rate(v.get(key)?.as_str()?);
+            rate("avg_frame_rate").or_else(|| rate("r_frame_rate"))
+        }),
+        video_codec: video.and_then(codec),
+        audio_codec: audio.first().and_then(|a| codec(a)),
+        audio_tracks: audio.len(),
+    })
+}
+
+/// Frame rate as ffprobe writes it: `30000/1001`; `0/0` means unknown
+fn parse_rate(rate: &str) -> Option<f64> {
+    let fps = match rate.split_once('/') {
+        Some((num, den)) => num.parse::<f64>().ok()? / den.parse::<f64>().ok().filter(|d| *d > 0.0)?,
+        None => rate.parse().ok()?,
+    };
+    Some(fps).filter(|f| *f > 0.0)
+}
+
+/// The OBS recording: the video named after the recording, else the first one
+fn main_video(recording_path: &Path, recording_name: &str) -> Option<PathBuf> {
+    let videos = video_files(recording_path, &VIDEO_EXTENSIONS);
+    videos
+        .iter()
+        .find(|f| f.file_stem().is_some_and(|s| s == recording_name))
+        .or_else(|| videos.first())
+        .cloned()
+}
+
+fn rendered_video(recording_path: &Path) -> Option<PathBuf> {
+    video_files(&recording_path.join("blender").join("render"), &RENDER_EXTENSIONS)
+        .into_iter()
+        .next()
+}
+
+fn video_files(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| extensions.contains(&e))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn size_and_mtime(file: &Path) -> Option<(u64, u64)> {
+    let metadata = fs::metadata(file).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(SystemTime::UNIX_EPOCH).ok()?.as_secs();
+    Some((metadata.len(), modified))
+}
+
+fn load_cache(recording_path: &Path) -> ProbeCache {
+    fs::read_to_string(recording_path.join(MEDIA_PROBE_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_cache(recording_path: &Path, cache: &ProbeCache) -> Result<(), String> {
+    save_json(recording_path, MEDIA_PROBE_FILE, cache)
+}
+
+fn save_json<T: Serialize>(recording_path: &Path, file: &str, value: &T) -> Result<(), String> {
+    let path = recording_path.join(file);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize media probe: {}", e))?;
+    write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use tempfile::TempDir;
+
+    const FFPROBE_OUTPUT: &str = r#"{
+        "streams": [
+            {"codec_type": "video", "codec_name": "h264", "width": 1920, "height": 1080, "avg_frame_rate": "30000/1001", "r_frame_rate": "30/1"},
+            {"codec_type": "audio", "codec_name": "aac"},
+            {"codec_type": "audio", "codec_name": "opus"}
+        ],
+        "format": {"duration": "754.120000"}
+    }"#;
+
+    #[test]
+    fn test_parse_ffprobe_json() {
+        let info = parse_ffprobe_json(FFPROBE_OUTPUT).unwrap();
+        assert_eq!(info.duration_secs, Some(754.12));
+        assert_eq!((info.width, info.height), (Some(1920), Some(1080)));
+        assert!((info.fps.unwrap() - 29.97).abs() < 0.01);
+        assert_eq!(info.video_codec.as_deref(), Some("h264"));
+        assert_eq!(info.audio_codec.as_deref(), Some("aac"));
+        assert_eq!(info.audio_tracks, 2);
+
+        let audio_only = parse_ffprobe_json(r#"{"streams": [{"codec_type": "audio", "codec_name": "flac"}]}"#).unwrap();
+        assert_eq!(audio_only.width, None);
+        assert_eq!(audio_only.audio_tracks, 1);
+        assert!(parse_ffprobe_json("not json").is_none());
+    }
+
+    #[test]
+    fn test_probe_results_are_cached() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path().join("gig");
+        fs::create_dir_all(recording.join("blender/render")).unwrap();
+        fs::write(recording.join("gig.mkv"), "main").unwrap();
+        fs::write(recording.join("blender/render/final.mp4"), "rendered").unwrap();
+
+        let calls = Cell::new(0);
+        let probe = |_: &Path| {
+            calls.set(calls.get() + 1);
+            parse_ffprobe_json(FFPROBE_OUTPUT)
+        };
+
+        let media = MediaProbe::for_recording_with(&recording, "gig", probe);
+        assert!(media.main.is_some() && media.rendered.is_some());
+        assert_eq!(calls.get(), 2);
+
+        assert_eq!(MediaProbe::for_recording_with(&recording, "gig", probe), media);
+        assert_eq!(calls.get(), 2);
+
+        fs::write(recording.join("gig.mkv"), "re-recorded").unwrap();
+        MediaProbe::for_recording_with(&recording, "gig", probe);
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_probe_failure_expires_when_video_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+        fs::write(recording.join("gig.mkv"), "broken").unwrap();
+        assert_eq!(MediaProbe::probe_failure(recording), None);
+
+        let (size, modified) = size_and_mtime(&recording.join("gig.mkv")).unwrap();
+        let failure = ProbeFailure { file: "gig.mkv".to_string(), size, modified, error: "moov atom not found".to_string() };
+        save_json(recording, PROBE_FAILURE_FILE, &failure).unwrap();
+        assert_eq!(MediaProbe::probe_failure(recording).as_deref(), Some("moov atom not found"));
+
+        fs::write(recording.join("gig.mkv"), "re-recorded").unwrap();
+        assert_eq!(MediaProbe::probe_failure(recording), None);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::models::NextStep;
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Settings key of the language picked in the app
+pub const LOCALE_KEY: &str = "locale";
+
+/// Language for one run, e.g. of fermata-cli, over the one picked in the app
+pub const LOCALE_ENV: &str = "FERMATA_LOCALE";
+
+/// Language of backend-generated text
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Pl,
+}
+
+impl Locale {
+    /// "pl", "pl_PL.UTF-8", "en-US", ...
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().get(..2)? {
+            "en" => Some(Locale::En),
+            "pl" => Some(Locale::Pl),
+            _ => None,
+        }
+    }
+
+    /// FERMATA_LOCALE when set, else the language picked in the app, else English
+    pub fn resolve(settings: &SettingsStore) -> Self {
+        if let Some(locale) = std::env::var(LOCALE_ENV).ok().and_then(|value| Self::parse(&value)) {
+            return locale;
+        }
+        settings.get(LOCALE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        settings.set(LOCALE_KEY, self)
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Pl => PL,
+        }
+    }
+
+    /// CLDR plural category used to pick `<key>.<category>` variants
+    fn plural_category(&self, n: u64) -> &'static str {
+        match self {
+            Locale::En if n == 1 => "one",
+            Locale::En => "other",
+            Locale::Pl if n == 1 => "one",
+            Locale::Pl if (2..=4).contains(&(n % 10)) && !(12..=14).contains(&(n % 100)) => "few",
+            Locale::Pl => "many",
+        }
+    }
+}
+
+const EN: &[(&str, &str)] = &[
+    ("status.recorded", "Recorded"),
+    ("status.extracted", "Extracted"),
+    ("status.analyzed", "Analyzed"),
+    ("status.config_generated", "Animation config generated"),
+    ("status.setup_rendered", "Set up for rendering"),
+    ("status.frames_rendered", "Frames rendered, waiting to be encoded"),
+    ("status.rendered", "Rendered"),
+    ("status.uploaded", "Uploaded"),
+    ("status.failed", "Failed ({error})"),
+    ("status.corrupted", "Corrupted ({reason})"),
+    ("status.analyzing", "Analyzing audio"),
+    ("status.setting_up_render", "Setting up the render"),
+    ("status.rendering", "Rendering"),
+    ("status.uploading", "Uploading"),
+    ("status.running_step", "Running {step}"),
+    ("status.transcribing", "Transcribing"),
+    ("next.extract", "awaiting extraction"),
+    ("next.analyze", "awaiting analysis"),
+    ("next.setup_render", "awaiting render setup"),
+    ("next.generate_config", "awaiting animation config"),
+    ("next.blend_setup", "awaiting Blender project setup"),
+    ("next.render", "awaiting render"),
+    ("next.upload", "awaiting upload"),
+    ("next.transcribe", "awaiting transcription"),
+    ("next.upload_to", "awaiting upload to {target}"),
+    ("next.upload_blocked", "upload blocked: {reason}"),
+    ("next.retry", "needs a retry"),
+    ("next.custom", "awaiting {step}"),
+    ("step.extract", "Extract"),
+    ("step.analyze", "Analyze"),
+    ("step.setup_render", "Setup Render"),
+    ("step.generate_config", "Generate Config"),
+    ("step.blend_setup", "Blend Setup"),
+    ("step.render", "Render"),
+    ("step.upload", "Upload"),
+    ("step.transcribe", "Transcribe"),
+    ("step.retry", "Retry"),
+    ("notify.step_failed", "{step} failed for {recording}"),
+    ("notify.step_finished", "{step} finished for {recording}"),
+    ("notify.ready", "Ready for the next step"),
+    ("notify.interrupted", "Interrupted steps or jobs: {count}"),
+    ("notify.interrupted_hint", "Resume or resolve them in the job list"),
+    ("notify.low_disk", "Low disk space: {free} GB free"),
+    ("notify.low_disk_hint", "Renders are paused until space is freed"),
+    ("tray.show", "Show Fermata"),
+    ("tray.pause_queue", "Pause queue"),
+    ("tray.jobs_running", "Jobs running: {count}"),
+    ("tray.quit", "Quit"),
+    ("error.recording_not_found", "Recording '{name}' not found"),
+    ("error.recording_busy", "Recording '{name}' is busy"),
+    ("time.just_now", "just now"),
+    ("time.minutes_ago.one", "{n} minute ago"),
+    ("time.minutes_ago.other", "{n} minutes ago"),
+    ("time.hours_ago.one", "{n} hour ago"),
+    ("time.hours_ago.other", "{n} hours ago"),
+    ("time.days_ago.one", "{n} day ago"),
+    ("time.days_ago.other", "{n} days ago"),
+];
+
+const PL: &[(&str, &str)] = &[
+    ("status.recorded", "Nagrano"),
+    ("status.extracted", "Wyodrębniono"),
+    ("status.analyzed", "Przeanalizowano"),
+    ("status.config_generated", "Wygenerowano konfigurację animacji"),
+    ("status.setup_rendered", "Przygotowano do renderowania"),
+    ("status.frames_rendered", "Wyrenderowano klatki, czekają na zakodowanie"),
+    ("status.rendered", "Wyrenderowano"),
+    ("status.uploaded", "Wysłano"),
+    ("status.failed", "Błąd ({error})"),
+    ("status.corrupted", "Uszkodzone ({reason})"),
+    ("status.analyzing", "Trwa analiza dźwięku"),
+    ("status.setting_up_render", "Trwa przygotowanie renderowania"),
+    ("status.rendering", "Trwa renderowanie"),
+    ("status.uploading", "Trwa wysyłanie"),
+    ("status.running_step", "Trwa {step}"),
+    ("status.transcribing", "Trwa transkrypcja"),
+    ("next.extract", "czeka na wyodrębnienie"),
+    ("next.analyze", "czeka na analizę"),
+    ("next.setup_render", "czeka na przygotowanie renderowania"),
+    ("next.generate_config", "czeka na konfigurację animacji"),
+    ("next.blend_setup", "czeka na przygotowanie projektu Blendera"),
+    ("next.render", "czeka na renderowanie"),
+    ("next.upload", "czeka na wysłanie"),
+    ("next.transcribe", "czeka na transkrypcję"),
+    ("next.upload_to", "czeka na wysłanie do {target}"),
+    ("next.upload_blocked", "wysyłanie zablokowane: {reason}"),
+    ("next.retry", "wymaga ponowienia"),
+    ("next.custom", "czeka na {step}"),
+    ("step.extract", "Wyodrębnianie"),
+    ("step.analyze", "Analiza"),
+    ("step.setup_render", "Przygotowanie renderowania"),
+    ("step.generate_config", "Konfiguracja animacji"),
+    ("step.blend_setup", "Projekt Blendera"),
+    ("step.render", "Renderowanie"),
+    ("step.upload", "Wysyłanie"),
+    ("step.transcribe", "Transkrypcja"),
+    ("step.retry", "Ponowienie"),
+    ("notify.step_failed", "{step}: błąd dla {recording}"),
+    ("notify.step_finished", "{step}: zakończono dla {recording}"),
+    ("notify.ready", "Gotowe do następnego kroku"),
+    ("notify.interrupted", "Przerwane kroki lub zadania: {count}"),
+    ("notify.interrupted_hint", "Wznów je lub rozwiąż na liście zadań"),
+    ("notify.low_disk", "Mało miejsca na dysku: wolne {free} GB"),
+    ("notify.low_disk_hint", "Renderowanie wstrzymane do zwolnienia miejsca"),
+    ("tray.show", "Pokaż Fermatę"),
+    ("tray.pause_queue", "Wstrzymaj kolejkę"),
+    ("tray.jobs_running", "Uruchomione zadania: {count}"),
+    ("tray.quit", "Zakończ"),
+    ("error.recording_not_found", "Nie znaleziono nagrania '{name}'"),
+    ("error.recording_busy", "Nagranie '{name}' jest zajęte"),
+    ("time.just_now", "przed chwilą"),
+    ("time.minutes_ago.one", "{n} minutę temu"),
+    ("time.minutes_ago.few", "{n} minuty temu"),
+    ("time.minutes_ago.many", "{n} minut temu"),
+    ("time.hours_ago.one", "{n} godzinę temu"),
+    ("time.hours_ago.few", "{n} godziny temu"),
+    ("time.hours_ago.many", "{n} godzin temu"),
+    ("time.days_ago.one", "{n} dzień temu"),
+    ("time.days_ago.few", "{n} dni temu"),
+    ("time.days_ago.many", "{n} dni temu"),
+];
+
+/// Template for `key`, falling back to English and then to the key itself
+pub fn message(locale: Locale, key: &str) -> &str {
+    [locale.catalog(), EN]
+        .iter()
+        .find_map(|catalog| catalog.iter().find(|(k, _)| *k == key).map(|(_, text)| *text))
+        .unwrap_or(key)
+}
+
+/// Message with `{name}` placeholders filled in
+pub fn message_with(locale: Locale, key: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(message(locale, key).to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
+
+/// Name of a step as shown to the user; custom steps keep their id
+pub fn step_name(locale: Locale, step: &NextStep) -> String {
+    match step {
+        NextStep::Custom(id) => id.clone(),
+        step => message(locale, &format!("step.{}", step)).to_string(),
+    }
+}
+
+/// Every template in `locale`, English where it has no translation, for the frontend
+pub fn message_catalog(locale: Locale) -> BTreeMap<&'static str, &'static str> {
+    EN.iter().chain(locale.catalog()).copied().collect()
+}
+
+/// Plural form of `key` for `n`, with `{n}` filled in
+pub fn plural(locale: Locale, key: &str, n: u64) -> String {
+    let key = format!("{}.{}", key, locale.plural_category(n));
+    message_with(locale, &key, &[("n", &n.to_string())])
+}
+
+/// Language in use while the app runs; set_locale changes it without a restart
+#[derive(Debug, Default)]
+pub struct CurrentLocale(Mutex<Locale>);
+
+impl CurrentLocale {
+    pub fn new(locale: Locale) -> Self {
+        Self(Mutex::new(locale))
+    }
+
+    pub fn get(&self) -> Locale {
+        *self.0.lock().unwrap()
+    }
+
+    pub fn set(&self, locale: Locale) {
+        *self.0.lock().unwrap() = locale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polish_plural_forms() {
+        assert_eq!(plural(Locale::Pl, "time.hours_ago", 1), "1 godzinę temu");
+        assert_eq!(plural(Locale::Pl, "time.hours_ago", 2), "2 godziny temu");
+        assert_eq!(plural(Locale::Pl, "time.hours_ago", 5), "5 godzin temu");
+        assert_eq!(plural(Locale::Pl, "time.hours_ago", 12), "12 godzin temu");
+        assert_eq!(plural(Locale::Pl, "time.hours_ago", 22), "22 godziny temu");
+        assert_eq!(plural(Locale::En, "time.hours_ago", 2), "2 hours ago");
+    }
+
+    #[test]
+    fn test_message_fallbacks() {
+        assert_eq!(Locale::parse("pl_PL.UTF-8"), Some(Locale::Pl));
+        assert_eq!(Locale::parse("de"), None);
+        assert_eq!(message_with(Locale::En, "next.upload_to", &[("target", "YouTube")]), "awaiting upload to YouTube");
+        assert_eq!(message(Locale::Pl, "missing.key"), "missing.key");
+        assert_eq!(step_name(Locale::Pl, &NextStep::Render), "Renderowanie");
+        assert_eq!(step_name(Locale::Pl, &NextStep::Custom("thumbnails".to_string())), "thumbnails");
+        let catalog = message_catalog(Locale::Pl);
+        assert_eq!((catalog["status.rendered"], catalog["time.minutes_ago.other"]), ("Wyrenderowano", "{n} minutes ago"));
+    }
+}
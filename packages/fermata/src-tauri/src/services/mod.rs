@@ -1,7 +1,217 @@
 pub mod status_detector;
 pub mod file_scanner;
 pub mod process_runner;
+pub mod step_lock;
+pub mod custom_fields_store;
+pub mod scan_snapshot;
+pub mod recordings_watcher;
+pub mod recordings_poller;
+pub mod trash;
+pub mod frame_counter;
+pub mod step_artifacts;
+pub mod disk_space;
+pub mod settings_store;
+pub mod settings_bundle;
+pub mod upload_config;
+pub mod upload_metadata_store;
+pub mod notifier;
+pub mod automation;
+pub mod layout_migration;
+pub mod path_repair;
+pub mod job_queue;
+pub mod auto_ingest;
+pub mod source_offsets;
+pub mod config_sync;
+pub mod step_logs;
+pub mod step_outcome;
+pub mod step_scratch;
+pub mod stall_detection;
+pub mod upload_block_store;
+pub mod snooze_store;
+pub mod health_check;
+pub mod repair;
+pub mod config_store;
+pub mod cleanup;
+pub mod messages;
+pub mod status_summary;
+pub mod recording_archive;
+pub mod atomic_write;
+pub mod recording_import;
+pub mod recording_clone;
+pub mod lifecycle;
+pub mod pipeline_stats;
+pub mod sessions;
+pub mod bulk_delete;
+pub mod media_probe;
+pub mod video_stream;
+pub mod external_player;
+pub mod file_manager;
+pub mod render_progress;
+pub mod render_resume;
+pub mod render_preview;
+pub mod render_settings;
+pub mod render_device;
+pub mod animation_config;
+pub mod preset_catalog;
+pub mod analyze_options;
+pub mod audio_files;
+pub mod render_output;
+pub mod state_manifest;
+pub mod attention;
+pub mod pipeline_integrity;
+pub mod space_preflight;
+pub mod output_estimate;
+pub mod step_hooks;
+pub mod custom_steps;
+pub mod pipeline_templates;
+pub mod backlog;
+pub mod subtitles;
+pub mod loudness;
+pub mod obs_client;
+pub mod execution_backend;
+pub mod job_window;
+pub mod process_priority;
+pub mod process_environment;
+pub mod toolchain;
+pub mod process_path;
+pub mod app_log;
+pub mod activity_log;
+pub mod batch_cleanup;
+pub mod recording_name;
+pub mod rename_journal;
+pub mod recordings_migration;
+pub mod recording_detection;
+pub mod recordings_ignore;
+pub mod file_tree;
+pub mod recent_recordings;
+pub mod recordings_calendar;
+pub mod step_estimates;
+pub mod pipeline_report;
+pub mod artifact_checksums;
+pub mod duplicates;
+pub mod quarantine;
+pub mod status_tracker;
+pub mod upload_retry;
+pub mod retry_plan;
+pub mod config_profiles;
+pub mod obs_config;
+pub mod extract_options;
+pub mod audio_waveform;
+pub mod timeline_markers;
+pub mod render_versions;
+pub mod render_attach;
+pub mod blend_versions;
+pub mod render_finalize;
+pub mod post_process;
+pub mod background;
+pub mod process_suspend;
+pub mod benchmark;
 
 pub use status_detector::*;
 pub use file_scanner::*;
 pub use process_runner::*;
+pub use step_lock::*;
+pub use custom_fields_store::*;
+pub use scan_snapshot::*;
+pub use recordings_watcher::*;
+pub use recordings_poller::*;
+pub use trash::*;
+pub use frame_counter::*;
+pub use step_artifacts::*;
+pub use disk_space::*;
+pub use settings_store::*;
+pub use settings_bundle::*;
+pub use upload_config::*;
+pub use upload_metadata_store::*;
+pub use notifier::*;
+pub use automation::*;
+pub use layout_migration::*;
+pub use path_repair::*;
+pub use job_queue::*;
+pub use auto_ingest::*;
+pub use source_offsets::*;
+pub use config_sync::*;
+pub use step_logs::*;
+pub use step_outcome::*;
+pub use step_scratch::*;
+pub use stall_detection::*;
+pub use upload_block_store::*;
+pub use snooze_store::*;
+pub use health_check::*;
+pub use repair::*;
+pub use config_store::*;
+pub use cleanup::*;
+pub use messages::*;
+pub use status_summary::*;
+pub use recording_archive::*;
+pub use atomic_write::*;
+pub use recording_import::*;
+pub use recording_clone::*;
+pub use lifecycle::*;
+pub use pipeline_stats::*;
+pub use sessions::*;
+pub use bulk_delete::*;
+pub use media_probe::*;
+pub use video_stream::*;
+pub use external_player::*;
+pub use file_manager::*;
+pub use render_progress::*;
+pub use render_resume::*;
+pub use render_preview::*;
+pub use render_settings::*;
+pub use render_device::*;
+pub use animation_config::*;
+pub use preset_catalog::*;
+pub use analyze_options::*;
+pub use audio_files::*;
+pub use render_output::*;
+pub use state_manifest::*;
+pub use attention::*;
+pub use pipeline_integrity::*;
+pub use space_preflight::*;
+pub use output_estimate::*;
+pub use step_hooks::*;
+pub use custom_steps::*;
+pub use pipeline_templates::*;
+pub use backlog::*;
+pub use subtitles::*;
+pub use loudness::*;
+pub use obs_client::*;
+pub use execution_backend::*;
+pub use job_window::*;
+pub use process_priority::*;
+pub use process_environment::*;
+pub use toolchain::*;
+pub use process_path::*;
+pub use app_log::*;
+pub use activity_log::*;
+pub use batch_cleanup::*;
+pub use recording_name::*;
+pub use rename_journal::*;
+pub use recordings_migration::*;
+pub use recording_detection::*;
+pub use recordings_ignore::*;
+pub use file_tree::*;
+pub use recent_recordings::*;
+pub use recordings_calendar::*;
+pub use step_estimates::*;
+pub use pipeline_report::*;
+pub use artifact_checksums::*;
+pub use duplicates::*;
+pub use quarantine::*;
+pub use status_tracker::*;
+pub use upload_retry::*;
+pub use retry_plan::*;
+pub use config_profiles::*;
+pub use obs_config::*;
+pub use extract_options::*;
+pub use audio_waveform::*;
+pub use timeline_markers::*;
+pub use render_versions::*;
+pub use render_attach::*;
+pub use blend_versions::*;
+pub use render_finalize::*;
+pub use post_process::*;
+pub use background::*;
+pub use process_suspend::*;
+pub use benchmark::*;
@@ -0,0 +1,140 @@
+use crate::models::NextStep;
+use crate::services::{message, message_with, step_name, Locale};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+/// Settings key for the notification preferences
+pub const NOTIFICATION_SETTINGS_KEY: &str = "notifications";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Where notifications end up; the desktop in the app, a recorder in tests
+pub trait NotificationSink: Send + Sync {
+    fn show(&self, title: &str, body: &str) -> Result<(), String>;
+}
+
+/// Desktop notifications through the Tauri notification plugin
+pub struct DesktopNotifications {
+    app: AppHandle,
+}
+
+impl DesktopNotifications {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl NotificationSink for DesktopNotifications {
+    fn show(&self, title: &str, body: &str) -> Result<(), String> {
+        self.app
+            .notification()
+            .builder()
+            .title(title)
+            .body(body)
+            .show()
+            .map_err(|e| format!("Failed to show notification: {}", e))
+    }
+}
+
+/// Tells the user about long-running steps finishing while the app is in the background
+pub struct Notifier {
+    enabled: AtomicBool,
+    sink: Box<dyn NotificationSink>,
+}
+
+impl Notifier {
+    pub fn new(sink: Box<dyn NotificationSink>, enabled: bool) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+            sink,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Show a notification if enabled; failures are only logged
+    pub fn notify(&self, title: &str, body: &str) {
+        if !self.is_enabled() {
+            return;
+        }
+        if let Err(e) = self.sink.show(title, body) {
+            log::warn!("{}", e);
+        }
+    }
+
+    /// Report a finished step in `locale`. Failures are always reported, successes only for
+    /// the steps that take long enough for the user to switch away.
+    pub fn step_finished(&self, locale: Locale, recording_name: &str, step: &NextStep, error: Option<&str>) {
+        let name = step_name(locale, step);
+        let args = [("step", name.as_str()), ("recording", recording_name)];
+        match error {
+            Some(error) => self.notify(
+                &message_with(locale, "notify.step_failed", &args),
+                error.lines().last().unwrap_or(error),
+            ),
+            None if matches!(step, NextStep::Render | NextStep::Upload) => self.notify(
+                &message_with(locale, "notify.step_finished", &args),
+                message(locale, "notify.ready"),
+            ),
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default, Clone)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl NotificationSink for Recorder {
+        fn show(&self, title: &str, _body: &str) -> Result<(), String> {
+            self.0.lock().unwrap().push(title.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_step_finished_notifies_failures_and_long_steps() {
+        let recorder = Recorder::default();
+        let notifier = Notifier::new(Box::new(recorder.clone()), true);
+
+        notifier.step_finished(Locale::En, "rec_1", &NextStep::Analyze, None);
+        notifier.step_finished(Locale::En, "rec_1", &NextStep::Render, None);
+        notifier.step_finished(Locale::En, "rec_2", &NextStep::Analyze, Some("boom"));
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec!["Render finished for rec_1", "Analyze failed for rec_2"]
+        );
+    }
+
+    #[test]
+    fn test_disabled_notifier_is_silent() {
+        let recorder = Recorder::default();
+        let notifier = Notifier::new(Box::new(recorder.clone()), true);
+        notifier.set_enabled(false);
+
+        notifier.step_finished(Locale::En, "rec_1", &NextStep::Render, Some("boom"));
+        assert!(recorder.0.lock().unwrap().is_empty());
+    }
+}
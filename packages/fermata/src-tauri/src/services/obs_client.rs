@@ -0,0 +1,404 @@
+use crate::services::SettingsStore;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+/// Settings key holding the obs-websocket connection
+pub const OBS_CONNECTION_KEY: &str = "obs_connection";
+
+/// obs-websocket protocol version the client speaks
+const RPC_VERSION: u64 = 1;
+
+/// Event subscription bit for output events, RecordStateChanged among them
+const EVENT_SUBSCRIPTION_OUTPUTS: u64 = 1 << 6;
+
+/// Limit for connecting and for every request
+const OBS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// OBS output state reported once the recording file is closed
+const OUTPUT_STOPPED: &str = "OBS_WEBSOCKET_OUTPUT_STOPPED";
+
+/// How to reach obs-websocket (OBS 28+: Tools > WebSocket Server Settings)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ObsConnection {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    /// Only needed when authentication is enabled in OBS
+    pub password: Option<String>,
+}
+
+impl Default for ObsConnection {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "localhost".to_string(),
+            port: 4455,
+            password: None,
+        }
+    }
+}
+
+impl ObsConnection {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(OBS_CONNECTION_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        self.validate()?;
+        settings.set(OBS_CONNECTION_KEY, self)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.host.trim().is_empty() || self.host.contains(['/', ' ']) {
+            return Err(format!("Invalid OBS host '{}'", self.host));
+        }
+        if self.port == 0 {
+            return Err("OBS port must not be 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// What OBS is doing right now, from get_obs_status
+#[derive(Debug, Clone, Serialize, Default, PartialEq)]
+pub struct ObsStatus {
+    pub connected: bool,
+    /// Why OBS could not be reached, when not connected
+    pub error: Option<String>,
+    pub recording: bool,
+    pub recording_paused: bool,
+    pub streaming: bool,
+    pub current_scene: Option<String>,
+    /// Directory OBS writes recordings to
+    pub record_directory: Option<String>,
+    /// File of the last recording OBS finished while Fermata was listening
+    pub last_output_path: Option<String>,
+}
+
+/// A RecordStateChanged event, emitted to the frontend as `obs-recording-state`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ObsRecordingEvent {
+    pub active: bool,
+    /// OBS output state, e.g. "OBS_WEBSOCKET_OUTPUT_STARTED"
+    pub state: String,
+    pub output_path: Option<String>,
+}
+
+impl ObsRecordingEvent {
+    /// From the data of a RecordStateChanged event
+    pub fn parse(data: &Value) -> Option<Self> {
+        Some(Self {
+            active: data.get("outputActive")?.as_bool()?,
+            state: data.get("outputState")?.as_str()?.to_string(),
+            output_path: data.get("outputPath").and_then(Value::as_str).map(str::to_string),
+        })
+    }
+
+    /// OBS closed the file: the recording is complete on disk
+    pub fn finished_file(&self) -> Option<&str> {
+        (self.state == OUTPUT_STOPPED).then_some(self.output_path.as_deref()).flatten()
+    }
+}
+
+/// The authentication string of obs-websocket 5:
+/// base64(sha256(base64(sha256(password + salt)) + challenge))
+pub fn obs_auth_response(password: &str, salt: &str, challenge: &str) -> String {
+    let secret = BASE64.encode(Sha256::digest(format!("{}{}", password, salt)));
+    BASE64.encode(Sha256::digest(format!("{}{}", secret, challenge)))
+}
+
+/// A connection to obs-websocket, identified and ready for requests
+pub struct ObsClient {
+    socket: WebSocket,
+    next_request_id: u64,
+}
+
+impl ObsClient {
+    /// Connect and identify; `subscribe_outputs` receives RecordStateChanged events
+    pub async fn connect(connection: &ObsConnection, subscribe_outputs: bool) -> Result<Self, String> {
+        let address = format!("{}:{}", connection.host, connection.port);
+        let mut socket = timeout(OBS_TIMEOUT, WebSocket::connect(&connection.host, connection.port))
+            .await
+            .map_err(|_| format!("Timed out connecting to OBS at {}", address))??;
+
+        let hello = timeout(OBS_TIMEOUT, socket.read_message())
+            .await
+            .map_err(|_| "OBS did not say hello".to_string())??;
+        if hello.get("op").and_then(Value::as_u64) != Some(0) {
+            return Err(format!("Unexpected first message from OBS: {}", hello));
+        }
+
+        let mut identify = json!({
+            "rpcVersion": RPC_VERSION,
+            "eventSubscriptions": if subscribe_outputs { EVENT_SUBSCRIPTION_OUTPUTS } else { 0 },
+        });
+        if let Some(auth) = hello.pointer("/d/authentication") {
+            let password = connection
+                .password
+                .as_deref()
+                .ok_or_else(|| "OBS requires a password, none is configured".to_string())?;
+            let salt = auth.get("salt").and_then(Value::as_str).unwrap_or_default();
+            let challenge = auth.get("challenge").and_then(Value::as_str).unwrap_or_default();
+            identify["authentication"] = json!(obs_auth_response(password, salt, challenge));
+        }
+        socket.send_text(&json!({ "op": 1, "d": identify }).to_string()).await?;
+
+        // A wrong password closes the connection instead of answering
+        let identified = timeout(OBS_TIMEOUT, socket.read_message())
+            .await
+            .map_err(|_| "OBS did not confirm the identification".to_string())?
+            .map_err(|e| format!("OBS refused the connection, check the password: {}", e))?;
+        if identified.get("op").and_then(Value::as_u64) != Some(2) {
+            return Err(format!("Unexpected reply to identify from OBS: {}", identified));
+        }
+        Ok(Self { socket, next_request_id: 0 })
+    }
+
+    /// Send a request and wait for its response data; events arriving meanwhile are dropped
+    pub async fn request(&mut self, request_type: &str) -> Result<Value, String> {
+        self.next_request_id += 1;
+        let request_id = self.next_request_id.to_string();
+        let request = json!({ "op": 6, "d": { "requestType": request_type, "requestId": request_id } });
+        self.socket.send_text(&request.to_string()).await?;
+
+        let response = timeout(OBS_TIMEOUT, async {
+            loop {
+                let message = self.socket.read_message().await?;
+                if message.get("op").and_then(Value::as_u64) == Some(7)
+                    && message.pointer("/d/requestId").and_then(Value::as_str) == Some(request_id.as_str())
+                {
+                    return Ok::<Value, String>(message["d"].clone());
+                }
+            }
+        })
+        .await
+        .map_err(|_| format!("OBS did not answer {}", request_type))??;
+
+        if response.pointer("/requestStatus/result").and_then(Value::as_bool) != Some(true) {
+            let comment = response.pointer("/requestStatus/comment").and_then(Value::as_str).unwrap_or("no details");
+            return Err(format!("OBS request {} failed: {}", request_type, comment));
+        }
+        Ok(response.get("responseData").cloned().unwrap_or(Value::Null))
+    }
+
+    pub async fn status(&mut self) -> Result<ObsStatus, String> {
+        let record = self.request("GetRecordStatus").await?;
+        let stream = self.request("GetStreamStatus").await?;
+        let scene = self.request("GetCurrentProgramScene").await?;
+        let directory = self.request("GetRecordDirectory").await?;
+        let text = |value: &Value, key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+        Ok(ObsStatus {
+            connected: true,
+            error: None,
+            recording: record.get("outputActive").and_then(Value::as_bool).unwrap_or(false),
+            recording_paused: record.get("outputPaused").and_then(Value::as_bool).unwrap_or(false),
+            streaming: stream.get("outputActive").and_then(Value::as_bool).unwrap_or(false),
+            // sceneName is the field of obs-websocket 5.0, before currentProgramSceneName
+            current_scene: text(&scene, "currentProgramSceneName").or_else(|| text(&scene, "sceneName")),
+            record_directory: text(&directory, "recordDirectory"),
+            last_output_path: None,
+        })
+    }
+
+    /// Wait for the next RecordStateChanged event; other events are skipped
+    pub async fn next_recording_event(&mut self) -> Result<ObsRecordingEvent, String> {
+        loop {
+            let message = self.socket.read_message().await?;
+            let is_record_event = message.get("op").and_then(Value::as_u64) == Some(5)
+                && message.pointer("/d/eventType").and_then(Value::as_str) == Some("RecordStateChanged");
+            if let Some(event) = message.pointer("/d/eventData").filter(|_| is_record_event).and_then(ObsRecordingEvent::parse) {
+                return Ok(event);
+            }
+        }
+    }
+}
+
+/// The client side of RFC 6455, as much of it as obs-websocket needs: masked text
+/// frames out; text, fragmented, ping and close frames in
+struct WebSocket {
+    stream: TcpStream,
+}
+
+impl WebSocket {
+    async fn connect(host: &str, port: u16) -> Result<Self, String> {
+        let address = format!("{}:{}", host, port);
+        let mut stream = TcpStream::connect(&address)
+            .await
+            .map_err(|e| format!("Could not connect to OBS at {}: {}", address, e))?;
+
+        let mut key = [0u8; 16];
+        getrandom::getrandom(&mut key).map_err(|e| e.to_string())?;
+        let handshake = format!(
+            "GET / HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\nSec-WebSocket-Protocol: obswebsocket.json\r\n\r\n",
+            address,
+            BASE64.encode(key)
+        );
+        stream.write_all(handshake.as_bytes()).await.map_err(|e| e.to_string())?;
+
+        // The response has no body, read it up to the blank line
+        let mut response = Vec::new();
+        let mut byte = [0u8; 1];
+        while !response.ends_with(b"\r\n\r\n") {
+            if stream.read(&mut byte).await.map_err(|e| e.to_string())? == 0 || response.len() > 8192 {
+                return Err(format!("{} closed the connection during the WebSocket handshake", address));
+            }
+            response.push(byte[0]);
+        }
+        let status_line = String::from_utf8_lossy(&response).lines().next().unwrap_or_default().to_string();
+        if !status_line.contains(" 101 ") {
+            return Err(format!("{} is not obs-websocket: {}", address, status_line));
+        }
+        Ok(Self { stream })
+    }
+
+    async fn send_text(&mut self, text: &str) -> Result<(), String> {
+        self.send_frame(0x1, text.as_bytes()).await
+    }
+
+    async fn send_frame(&mut self, opcode: u8, payload: &[u8]) -> Result<(), String> {
+        let mut mask = [0u8; 4];
+        getrandom::getrandom(&mut mask).map_err(|e| e.to_string())?;
+        let frame = encode_frame(opcode, payload, mask);
+        self.stream.write_all(&frame).await.map_err(|e| format!("Lost connection to OBS: {}", e))
+    }
+
+    /// Next complete text message, parsed as JSON; pings are answered on the way
+    async fn read_message(&mut self) -> Result<Value, String> {
+        let mut message = Vec::new();
+        loop {
+            let (fin, opcode, payload) = self.read_frame().await?;
+            match opcode {
+                // Continuation, text and binary frames
+                0x0..=0x2 => message.extend_from_slice(&payload),
+                0x8 => {
+                    let reason = payload.get(2..).map(String::from_utf8_lossy).unwrap_or_default();
+                    return Err(format!("OBS closed the connection: {}", reason));
+                }
+                0x9 => {
+                    self.send_frame(0xA, &payload).await?;
+                    continue;
+                }
+                _ => continue,
+            }
+            if fin {
+                return serde_json::from_slice(&message).map_err(|e| format!("Invalid message from OBS: {}", e));
+            }
+        }
+    }
+
+    async fn read_frame(&mut self) -> Result<(bool, u8, Vec<u8>), String> {
+        let lost = |e: std::io::Error| format!("Lost connection to OBS: {}", e);
+        let mut header = [0u8; 2];
+        self.stream.read_exact(&mut header).await.map_err(lost)?;
+        let length = match header[1] & 0x7F {
+            126 => {
+                let mut bytes = [0u8; 2];
+                self.stream.read_exact(&mut bytes).await.map_err(lost)?;
+                u64::from(u16::from_be_bytes(bytes))
+            }
+            127 => {
+                let mut bytes = [0u8; 8];
+                self.stream.read_exact(&mut bytes).await.map_err(lost)?;
+                u64::from_be_bytes(bytes)
+            }
+            length => u64::from(length),
+        };
+        // Servers never mask, but a mask would not hurt either
+        let mask = if header[1] & 0x80 != 0 {
+            let mut mask = [0u8; 4];
+            self.stream.read_exact(&mut mask).await.map_err(lost)?;
+            Some(mask)
+        } else {
+            None
+        };
+        let mut payload = vec![0u8; usize::try_from(length).map_err(|e| e.to_string())?];
+        self.stream.read_exact(&mut payload).await.map_err(lost)?;
+        if let Some(mask) = mask {
+            apply_mask(&mut payload, mask);
+        }
+        Ok((header[0] & 0x80 != 0, header[0] & 0x0F, payload))
+    }
+}
+
+/// A single final client frame; clients must mask what they send
+fn encode_frame(opcode: u8, payload: &[u8], mask: [u8; 4]) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        length @ 0..=125 => frame.push(0x80 | length as u8),
+        length @ 126..=0xFFFF => {
+            frame.push(0x80 | 126);
+            frame.extend_from_slice(&(length as u16).to_be_bytes());
+        }
+        length => {
+            frame.push(0x80 | 127);
+            frame.extend_from_slice(&(length as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(&mask);
+    let start = frame.len();
+    frame.extend_from_slice(payload);
+    apply_mask(&mut frame[start..], mask);
+    frame
+}
+
+fn apply_mask(payload: &mut [u8], mask: [u8; 4]) {
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_response() {
+        // Example from the obs-websocket 5 protocol documentation
+        assert_eq!(
+            obs_auth_response(
+                "supersecretpassword",
+                "lM1GncleQOaCu9lT1yeUZhFYnqhsLLP1G5lAGo3ixaI=",
+                "+IxH4CnCiqpX1rM9scsNynZzbOe4KhDeYcTNS3PDaeY="
+            ),
+            "1Ct943GAT+6YQUUX47Ia/ncufilbe6+oD6lY+5kaCu4="
+        );
+    }
+
+    #[test]
+    fn test_encode_frame() {
+        let mask = [1, 2, 3, 4];
+        let frame = encode_frame(0x1, b"Hi", mask);
+        assert_eq!(frame[..6], [0x81, 0x82, 1, 2, 3, 4]);
+        let mut payload = frame[6..].to_vec();
+        apply_mask(&mut payload, mask);
+        assert_eq!(payload, b"Hi");
+
+        let frame = encode_frame(0x1, &[b'x'; 300], mask);
+        assert_eq!(frame[..4], [0x81, 0x80 | 126, 0x01, 0x2C]);
+        assert_eq!(frame.len(), 4 + 4 + 300);
+    }
+
+    #[test]
+    fn test_recording_event() {
+        let stopped = json!({
+            "outputActive": false,
+            "outputState": "OBS_WEBSOCKET_OUTPUT_STOPPED",
+            "outputPath": "/recordings/2025-01-05 14-30-22.mkv"
+        });
+        let event = ObsRecordingEvent::parse(&stopped).unwrap();
+        assert_eq!(event.finished_file(), Some("/recordings/2025-01-05 14-30-22.mkv"));
+
+        let started = json!({ "outputActive": true, "outputState": "OBS_WEBSOCKET_OUTPUT_STARTED", "outputPath": null });
+        assert_eq!(ObsRecordingEvent::parse(&started).unwrap().finished_file(), None);
+        assert!(ObsRecordingEvent::parse(&json!({})).is_none());
+    }
+}
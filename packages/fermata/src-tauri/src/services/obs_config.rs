@@ -0,0 +1,134 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// OBS Studio config directory to read instead of the platform default, e.g. for portable installs
+pub const OBS_CONFIG_DIR_ENV: &str = "FERMATA_OBS_CONFIG_DIR";
+
+/// Recording folder of the OBS profile in use
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ObsOutputPath {
+    pub path: PathBuf,
+    /// Profile directory name under basic/profiles
+    pub profile: String,
+    /// "Simple" or "Advanced", as in Settings > Output
+    pub output_mode: String,
+    pub config_dir: PathBuf,
+    pub exists: bool,
+}
+
+/// Sections of an INI file, keys by section; later duplicates win as in OBS
+pub fn parse_ini(content: &str) -> HashMap<String, HashMap<String, String>> {
+    let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut section = String::new();
+    for line in content.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+            section = name.trim().to_string();
+        } else if let Some((key, value)) = line.split_once('=') {
+            sections.entry(section.clone()).or_default().insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+    sections
+}
+
+/// Places OBS Studio keeps its config on this platform, the native install first
+pub fn obs_config_dirs() -> Vec<PathBuf> {
+    if let Some(dir) = std::env::var_os(OBS_CONFIG_DIR_ENV) {
+        return vec![PathBuf::from(dir)];
+    }
+    let home = PathBuf::from(std::env::var_os("HOME").unwrap_or_default());
+    if cfg!(target_os = "windows") {
+        vec![PathBuf::from(std::env::var_os("APPDATA").unwrap_or_default()).join("obs-studio")]
+    } else if cfg!(target_os = "macos") {
+        vec![home.join("Library").join("Application Support").join("obs-studio")]
+    } else {
+        let config = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from).unwrap_or_else(|| home.join(".config"));
+        vec![
+            config.join("obs-studio"),
+            // Flatpak
+            home.join(".var/app/com.obsproject.Studio/config/obs-studio"),
+        ]
+    }
+}
+
+impl ObsOutputPath {
+    /// Recording folder of the first OBS install found
+    pub fn discover() -> Result<Self, String> {
+        let dirs = obs_config_dirs();
+        let config_dir = dirs
+            .iter()
+            .find(|dir| dir.is_dir())
+            .ok_or_else(|| format!("OBS Studio config not found in {}", display_list(&dirs)))?;
+        Self::read(config_dir)
+    }
+
+    /// Recording folder of the current profile in an OBS config directory. The profile comes
+    /// from user.ini (OBS 31+) or global.ini; its basic.ini holds the path for the output mode.
+    pub fn read(config_dir: &Path) -> Result<Self, String> {
+        let profile = ["user.ini", "global.ini"]
+            .iter()
+            .filter_map(|file| fs::read_to_string(config_dir.join(file)).ok())
+            .find_map(|content| {
+                let basic = parse_ini(&content).remove("Basic")?;
+                basic.get("ProfileDir").or_else(|| basic.get("Profile")).cloned()
+            })
+            .ok_or_else(|| format!("No OBS profile selected in {}", config_dir.display()))?;
+
+        let basic_ini = config_dir.join("basic").join("profiles").join(&profile).join("basic.ini");
+        let content = fs::read_to_string(&basic_ini).map_err(|e| format!("Failed to read {}: {}", basic_ini.display(), e))?;
+        let sections = parse_ini(&content);
+        let get = |section: &str, key: &str| sections.get(section).and_then(|keys| keys.get(key)).filter(|v| !v.is_empty());
+
+        let output_mode = get("Output", "Mode").cloned().unwrap_or_else(|| "Simple".to_string());
+        let path = if output_mode.eq_ignore_ascii_case("Advanced") {
+            // Custom FFmpeg output records to its own folder
+            match get("AdvOut", "RecType").map(String::as_str) {
+                Some("FFmpeg") => get("AdvOut", "FFFilePath"),
+                _ => get("AdvOut", "RecFilePath"),
+            }
+        } else {
+            get("SimpleOutput", "FilePath")
+        }
+        .map(PathBuf::from)
+        .ok_or_else(|| format!("OBS profile '{}' has no recording path set; OBS then records to its default folder", profile))?;
+
+        Ok(Self { exists: path.is_dir(), path, profile, output_mode, config_dir: config_dir.to_path_buf() })
+    }
+}
+
+fn display_list(dirs: &[PathBuf]) -> String {
+    dirs.iter().map(|dir| dir.display().to_string()).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_output_path_by_mode() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = temp_dir.path();
+        assert!(ObsOutputPath::read(config).is_err());
+
+        fs::write(config.join("global.ini"), "[General]\nFirstRun=true\n\n[Basic]\nProfile=Gig Setup\nProfileDir=Gig_Setup\n").unwrap();
+        let profile = config.join("basic/profiles/Gig_Setup");
+        fs::create_dir_all(&profile).unwrap();
+        fs::write(
+            profile.join("basic.ini"),
+            "\u{feff}[Output]\nMode=Simple\n\n[SimpleOutput]\nFilePath=/home/me/Videos/obs = gigs\n\n[AdvOut]\nRecType=Standard\nRecFilePath=/mnt/nas/obs\n",
+        )
+        .unwrap();
+        let simple = ObsOutputPath::read(config).unwrap();
+        assert_eq!((simple.path, simple.profile.as_str()), (PathBuf::from("/home/me/Videos/obs = gigs"), "Gig_Setup"));
+
+        let advanced = fs::read_to_string(profile.join("basic.ini")).unwrap().replace("Mode=Simple", "Mode=Advanced");
+        fs::write(profile.join("basic.ini"), advanced).unwrap();
+        assert_eq!(ObsOutputPath::read(config).unwrap().path, PathBuf::from("/mnt/nas/obs"));
+    }
+}
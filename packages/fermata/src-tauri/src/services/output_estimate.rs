@@ -0,0 +1,226 @@
+use crate::models::{MediaInfo, Recording};
+use crate::services::{rendered_videos, MediaProbe, RenderSettings, SettingsStore, StepLogs, UploadProfile, VideoEncoder};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Settings key holding the configured upload bandwidth
+pub const UPLOAD_BANDWIDTH_KEY: &str = "upload_bandwidth";
+
+/// Used when neither the render settings nor the recording say otherwise
+const DEFAULT_SIZE: (u32, u32) = (1920, 1080);
+const DEFAULT_FPS: f64 = 30.0;
+
+/// Audio Blender muxes into the render, and ffmpeg's AAC default for transcodes
+const RENDER_AUDIO_BPS: u64 = 192_000;
+const TRANSCODE_AUDIO_BPS: u64 = 128_000;
+
+/// Upload bandwidth; measured from earlier uploads when unset
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct UploadBandwidth {
+    /// Megabits per second
+    pub mbps: Option<f64>,
+}
+
+impl UploadBandwidth {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(UPLOAD_BANDWIDTH_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        if self.mbps.is_some_and(|mbps| !mbps.is_finite() || mbps <= 0.0) {
+            return Err("Upload bandwidth must be above 0 Mbit/s".to_string());
+        }
+        settings.set(UPLOAD_BANDWIDTH_KEY, self)
+    }
+
+    /// Configured bandwidth, else the one measured in `recording_paths`, with its basis
+    pub fn resolve<'a>(&self, recording_paths: impl IntoIterator<Item = &'a Path>) -> Option<(f64, String)> {
+        if let Some(mbps) = self.mbps {
+            return Some((mbps, "configured".to_string()));
+        }
+        let (mbps, runs) = measured_upload_bandwidth(recording_paths)?;
+        Some((mbps, format!("measured from {} earlier uploads", runs)))
+    }
+}
+
+/// Expected size of a video
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SizeEstimate {
+    pub bytes: u64,
+    pub bitrate_kbps: u64,
+    /// How the size was worked out
+    pub basis: String,
+}
+
+/// Expected render and upload sizes of a recording, and how long the upload takes
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct OutputEstimate {
+    /// Of the OBS recording; None when it can't be probed, leaving sizes unknown
+    pub duration_secs: Option<f64>,
+    pub render: Option<SizeEstimate>,
+    /// What gets uploaded: the transcode of the profile, else the render
+    pub upload: Option<SizeEstimate>,
+    pub upload_mbps: Option<f64>,
+    pub bandwidth_basis: Option<String>,
+    pub upload_secs: Option<u64>,
+}
+
+impl OutputEstimate {
+    /// Estimate from the probed OBS recording; `bandwidth` is (Mbit/s, basis)
+    pub fn for_recording(
+        recording: &Recording,
+        settings: &RenderSettings,
+        profile: Option<&UploadProfile>,
+        bandwidth: Option<(f64, String)>
+    ) -> Self {
+        let source = MediaProbe::for_recording(&recording.path, &recording.name).main;
+        Self::build(&recording.path, source.as_ref(), settings, profile, bandwidth)
+    }
+
+    pub fn build(
+        recording_path: &Path,
+        source: Option<&MediaInfo>,
+        settings: &RenderSettings,
+        profile: Option<&UploadProfile>,
+        bandwidth: Option<(f64, String)>
+    ) -> Self {
+        let duration = source.and_then(|s| s.duration_secs).filter(|d| *d > 0.0);
+        let (render_bps, render_basis) = render_bitrate(settings, source);
+        let render = duration.map(|secs| SizeEstimate::from_bitrate(render_bps, secs, render_basis));
+        let rendered = rendered_videos(&recording_path.join("blender").join("render")).into_iter().next();
+
+        let upload = match profile.and_then(|p| p.transcode.as_ref()) {
+            Some(transcode) => {
+                let video_bps = transcode.video_bitrate.as_deref().and_then(parse_bitrate).unwrap_or_else(|| {
+                    // The codec default, guessed as the render's bitrate scaled down to max_height
+                    let height = settings.height.or(source.and_then(|s| s.height)).unwrap_or(DEFAULT_SIZE.1);
+                    let scale = transcode.max_height.filter(|max| *max < height).map_or(1.0, |max| max as f64 / height as f64);
+                    ((render_bps - RENDER_AUDIO_BPS) as f64 * scale * scale) as u64
+                });
+                let audio_bps = transcode.audio_bitrate.as_deref().and_then(parse_bitrate).unwrap_or(TRANSCODE_AUDIO_BPS);
+                let basis = format!("{} transcode at {} kbps", transcode.video_codec, (video_bps + audio_bps) / 1000);
+                duration.map(|secs| SizeEstimate::from_bitrate(video_bps + audio_bps, secs, basis))
+            }
+            None => match rendered.as_deref().and_then(|video| std::fs::metadata(video).ok()) {
+                Some(metadata) => Some(SizeEstimate {
+                    bytes: metadata.len(),
+                    bitrate_kbps: duration.map_or(0, |secs| (metadata.len() as f64 * 8.0 / secs / 1000.0) as u64),
+                    basis: "size of the rendered video".to_string(),
+                }),
+                None => render.clone(),
+            },
+        };
+
+        let upload_secs = match (&upload, &bandwidth) {
+            (Some(upload), Some((mbps, _))) => Some((upload.bytes as f64 * 8.0 / (mbps * 1_000_000.0)).ceil() as u64),
+            _ => None,
+        };
+        let (upload_mbps, bandwidth_basis) = bandwidth.unzip();
+        Self { duration_secs: duration, render, upload, upload_mbps, bandwidth_basis, upload_secs }
+    }
+}
+
+impl SizeEstimate {
+    fn from_bitrate(bits_per_second: u64, duration_secs: f64, basis: String) -> Self {
+        Self {
+            bytes: (bits_per_second as f64 * duration_secs / 8.0) as u64,
+            bitrate_kbps: bits_per_second / 1000,
+            basis,
+        }
+    }
+}
+
+/// Bits per second of a render: the output size, frame rate and encoder give the video
+/// bitrate at a typical bits-per-pixel, plus Blender's audio
+fn render_bitrate(settings: &RenderSettings, source: Option<&MediaInfo>) -> (u64, String) {
+    let width = settings.width.or(source.and_then(|s| s.width)).unwrap_or(DEFAULT_SIZE.0);
+    let height = settings.height.or(source.and_then(|s| s.height)).unwrap_or(DEFAULT_SIZE.1);
+    let fps = settings.fps.map(f64::from).or(source.and_then(|s| s.fps)).unwrap_or(DEFAULT_FPS);
+    let encoder = settings.encoder.unwrap_or(VideoEncoder::H264);
+    let bits_per_pixel = match encoder {
+        VideoEncoder::H264 => 0.12,
+        VideoEncoder::Hevc => 0.08,
+        VideoEncoder::Av1 => 0.06,
+        VideoEncoder::Prores => 2.4,
+    };
+    let video = (width as f64 * height as f64 * fps * bits_per_pixel) as u64;
+    (video + RENDER_AUDIO_BPS, format!("{}x{} at {} fps in {:?}", width, height, fps, encoder))
+}
+
+/// ffmpeg bitrate like "4M", "800k" or "128000", in bits per second
+pub fn parse_bitrate(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let (number, multiplier) = match value.chars().last()? {
+        'k' | 'K' => (&value[..value.len() - 1], 1_000.0),
+        'm' | 'M' => (&value[..value.len() - 1], 1_000_000.0),
+        'g' | 'G' => (&value[..value.len() - 1], 1_000_000_000.0),
+        _ => (value, 1.0),
+    };
+    number.parse::<f64>().ok().filter(|n| *n > 0.0).map(|n| (n * multiplier) as u64)
+}
+
+/// Mbit/s of the successful uploads in `recording_paths` and how many there were,
+/// from the rendered video size and the step duration. Transcoding counts too,
+/// so it errs on the slow side.
+pub fn measured_upload_bandwidth<'a>(recording_paths: impl IntoIterator<Item = &'a Path>) -> Option<(f64, usize)> {
+    let (mut bytes, mut ms, mut runs) = (0u64, 0u64, 0usize);
+    for path in recording_paths {
+        let Some(size) = rendered_videos(&path.join("blender").join("render"))
+            .first()
+            .and_then(|video| std::fs::metadata(video).ok())
+            .map(|m| m.len())
+        else {
+            continue;
+        };
+        for log in StepLogs::list(path).into_iter().filter(|log| log.success && log.step == "upload") {
+            if let Some(duration) = log.duration_ms.filter(|ms| *ms > 0) {
+                bytes += size;
+                ms += duration;
+                runs += 1;
+            }
+        }
+    }
+    (runs > 0).then(|| (bytes as f64 * 8.0 / 1000.0 / ms as f64, runs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::TranscodeProfile;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_estimates_render_and_transcoded_upload() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = MediaInfo { duration_secs: Some(600.0), width: Some(1280), height: Some(720), fps: Some(25.0), ..MediaInfo::default() };
+        let settings = RenderSettings { width: Some(1920), height: Some(1080), fps: Some(30), ..RenderSettings::default() };
+
+        let estimate = OutputEstimate::build(temp_dir.path(), Some(&source), &settings, None, Some((8.0, "configured".to_string())));
+        let render = estimate.render.unwrap();
+        // 1920 * 1080 * 30 * 0.12 + 192k = 7656 kbps
+        assert_eq!(render.bitrate_kbps, 7656);
+        assert_eq!(render.bytes, 574_272_000);
+        assert_eq!(estimate.upload.as_ref().map(|u| u.bytes), Some(render.bytes));
+        assert_eq!(estimate.upload_secs, Some(575));
+
+        let profile = UploadProfile {
+            name: "portal".to_string(),
+            config_path: temp_dir.path().join("portal.json"),
+            description: None,
+            transcode: Some(TranscodeProfile {
+                video_codec: "libx264".to_string(),
+                video_bitrate: Some("2M".to_string()),
+                audio_codec: "aac".to_string(),
+                audio_bitrate: None,
+                max_height: Some(720),
+            }),
+            post_process: None,
+        };
+        let estimate = OutputEstimate::build(temp_dir.path(), Some(&source), &settings, Some(&profile), None);
+        assert_eq!(estimate.upload.unwrap().bytes, 159_600_000);
+        assert_eq!(estimate.upload_secs, None);
+        assert!(OutputEstimate::build(temp_dir.path(), None, &settings, None, None).render.is_none());
+        assert_eq!(parse_bitrate("800k"), Some(800_000));
+    }
+}
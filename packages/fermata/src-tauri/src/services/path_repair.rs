@@ -0,0 +1,330 @@
+use crate::services::{CONFIGS_DIR, CONFIG_HISTORY_DIR};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Recording subdirectories that generated files point into
+const RECORDING_SUBDIRS: [&str; 4] = ["extracted", "analysis", "blender", "uploads"];
+
+/// Printed by the remap script so the number of changed paths can be reported
+pub const BLENDER_REMAP_MARKER: &str = "FERMATA_REMAPPED";
+
+/// Headless Blender script: `-- <new_root> <old_root>...` rewrites every external
+/// file path under an old root and saves the project if anything changed.
+pub const BLENDER_REMAP_SCRIPT: &str = r#"
+import os
+import sys
+import bpy
+
+args = sys.argv[sys.argv.index("--") + 1:]
+new_root, old_roots = args[0], args[1:]
+remapped = 0
+
+def remap(path):
+    global remapped
+    if not path or path.startswith("//"):
+        return path
+    for old_root in old_roots:
+        # A separator must follow, so /rec/gig never matches /rec/gig2
+        if path == old_root or path.startswith(old_root + os.sep):
+            remapped += 1
+            return new_root + path[len(old_root):]
+    return path
+
+for collection in (bpy.data.images, bpy.data.sounds, bpy.data.movieclips, bpy.data.libraries):
+    for item in collection:
+        item.filepath = remap(item.filepath)
+
+for scene in bpy.data.scenes:
+    scene.render.filepath = remap(scene.render.filepath)
+    if scene.sequence_editor:
+        for strip in scene.sequence_editor.sequences_all:
+            if hasattr(strip, "filepath"):
+                strip.filepath = remap(strip.filepath)
+            if hasattr(strip, "directory"):
+                strip.directory = remap(strip.directory)
+
+if remapped:
+    bpy.ops.wm.save_mainfile()
+print("FERMATA_REMAPPED", remapped)
+"#;
+
+/// BLENDER_REMAP_SCRIPT as a `--python-expr` for another Blender run, e.g. a render,
+/// with its arguments embedded instead of read from the command line
+pub fn blender_remap_expr(new_root: &str, old_roots: &[String]) -> String {
+    let mut argv = vec!["blender", "--", new_root];
+    argv.extend(old_roots.iter().map(String::as_str));
+    // A JSON list of strings is also a valid Python list literal
+    let argv = serde_json::to_string(&argv).unwrap_or_default();
+    format!("import sys\nsys.argv = {}\n{}", argv, BLENDER_REMAP_SCRIPT)
+}
+
+/// Finds and rewrites absolute paths that generated artifacts still hold after a
+/// recording was renamed or moved.
+pub struct PathRepair;
+
+impl PathRepair {
+    /// Previous locations of the recording referenced by its metadata and configs,
+    /// longest first so nested roots are replaced before their parents
+    pub fn stale_roots(recording_path: &Path) -> Vec<String> {
+        let current = recording_path.to_string_lossy().to_string();
+        let mut roots: Vec<String> = files_with_path_references(recording_path)
+            .iter()
+            .flat_map(|file| string_values(file))
+            .filter_map(|value| recording_root(&value).map(str::to_string))
+            .filter(|root| *root != current)
+            .collect();
+        roots.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        roots.dedup();
+        roots
+    }
+
+    /// Text replacements moving `old_roots` to the recording's current location.
+    /// The main .mkv name is only rewritten when the file itself was renamed.
+    pub fn replacements(recording_path: &Path, old_roots: &[String]) -> Vec<(String, String)> {
+        let current = recording_path.to_string_lossy().to_string();
+        let current_name = recording_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let mut replacements = Vec::new();
+        for root in old_roots {
+            replacements.push((root.clone(), current.clone()));
+
+            let old_name = root.rsplit(['/', '\\']).next().unwrap_or(root);
+            let old_video = format!("{}.mkv", old_name);
+            let new_video = format!("{}.mkv", current_name);
+            if old_name != current_name
+                && !recording_path.join(&old_video).exists()
+                && recording_path.join(&new_video).exists()
+            {
+                replacements.push((old_video, new_video));
+            }
+        }
+        replacements
+    }
+}
+
+/// Recording directory an absolute artifact path points into, if recognisable
+fn recording_root(value: &str) -> Option<&str> {
+    let is_absolute = value.starts_with('/')
+        || value.get(1..3).is_some_and(|p| p == ":\\" || p == ":/");
+    if !is_absolute {
+        return None;
+    }
+
+    let subdir_start = RECORDING_SUBDIRS
+        .iter()
+        .flat_map(|dir| [format!("/{}/", dir), format!("\\{}\\", dir)])
+        .filter_map(|needle| value.rfind(&needle))
+        .max();
+
+    match subdir_start {
+        Some(index) => Some(&value[..index]),
+        None if value.ends_with(".mkv") => value.rfind(['/', '\\']).map(|index| &value[..index]),
+        None => None,
+    }
+}
+
+/// String values of a JSON document, or `key: value` / `- value` entries of a YAML file
+fn string_values(file: &Path) -> Vec<String> {
+    fn walk(value: &serde_json::Value, values: &mut Vec<String>) {
+        match value {
+            serde_json::Value::String(text) => values.push(text.clone()),
+            serde_json::Value::Array(items) => items.iter().for_each(|item| walk(item, values)),
+            serde_json::Value::Object(map) => map.values().for_each(|item| walk(item, values)),
+            _ => {}
+        }
+    }
+
+    let Ok(content) = fs::read_to_string(file) else {
+        return Vec::new();
+    };
+
+    let mut values = Vec::new();
+    if file.extension().is_some_and(|e| e == "json") {
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(&content) {
+            walk(&value, &mut values);
+        }
+        return values;
+    }
+
+    for line in content.lines() {
+        let line = line.trim_start();
+        let value = line
+            .strip_prefix("- ")
+            .or_else(|| line.split_once(": ").map(|(_, value)| value));
+        if let Some(value) = value {
+            values.push(value.trim().trim_matches(|c| c == '"' || c == '\'').to_string());
+        }
+    }
+    values
+}
+
+/// Files known to embed recording paths: metadata.json and generated animation
+/// configs, including the stored ones and their history
+pub fn files_with_path_references(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for config_dir in [dir.to_path_buf(), dir.join(CONFIGS_DIR), dir.join(CONFIG_HISTORY_DIR)] {
+        let Ok(entries) = fs::read_dir(&config_dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+
+            let is_metadata = config_dir == dir && file_name == "metadata.json";
+            let is_animation_config = file_name.starts_with("animation_config_")
+                && (file_name.ends_with(".yaml") || file_name.ends_with(".yml"));
+
+            if path.is_file() && (is_metadata || is_animation_config) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Replace whole occurrences of each path or file name: a match next to a character
+/// that continues a name is left alone, so "/rec/gig" keeps "/rec/gig2" and "/rec/gig_old"
+pub fn rewrite_text(content: &str, replacements: &[(&str, &str)]) -> (String, usize) {
+    let mut result = content.to_string();
+    let mut occurrences = 0;
+    for (from, to) in replacements.iter().filter(|(from, _)| !from.is_empty()) {
+        let mut rewritten = String::with_capacity(result.len());
+        let mut copied = 0;
+        for (index, _) in result.match_indices(from) {
+            let before = result[..index].chars().next_back();
+            let after = result[index + from.len()..].chars().next();
+            if continues_name(before) || continues_name(after) {
+                continue;
+            }
+            rewritten.push_str(&result[copied..index]);
+            rewritten.push_str(to);
+            copied = index + from.len();
+            occurrences += 1;
+        }
+        rewritten.push_str(&result[copied..]);
+        result = rewritten;
+    }
+    (result, occurrences)
+}
+
+fn continues_name(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Rewrite string values inside JSON so escaped paths (e.g. Windows backslashes) still match
+pub fn rewrite_json_strings(content: &str, replacements: &[(&str, &str)]) -> Option<(String, usize)> {
+    fn walk(value: &mut serde_json::Value, replacements: &[(&str, &str)], occurrences: &mut usize) {
+        match value {
+            serde_json::Value::String(text) => {
+                let (new_text, count) = rewrite_text(text, replacements);
+                if count > 0 {
+                    *text = new_text;
+                    *occurrences += count;
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    walk(item, replacements, occurrences);
+                }
+            }
+            serde_json::Value::Object(map) => {
+                for item in map.values_mut() {
+                    walk(item, replacements, occurrences);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut value: serde_json::Value = serde_json::from_str(content).ok()?;
+    let mut occurrences = 0;
+    walk(&mut value, replacements, &mut occurrences);
+    let new_content = serde_json::to_string_pretty(&value).ok()?;
+    Some((new_content, occurrences))
+}
+
+/// .blend files embed absolute paths in a binary format and need Blender to remap them
+pub fn find_blend_files(dir: &Path) -> Vec<String> {
+    let blender_dir = dir.join("blender");
+    let mut blend_files: Vec<String> = fs::read_dir(&blender_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "blend"))
+                .filter_map(|e| e.path().strip_prefix(dir).ok().map(|p| p.to_string_lossy().to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    blend_files.sort();
+    blend_files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rewrite_text_matches_whole_names() {
+        let content = "video: gig.mkv\nold: old_gig.mkv\ndir: /rec/gig/extracted\nsibling: /rec/gig2/extracted\nbackup: /rec/gig_old\n";
+        let (rewritten, occurrences) = rewrite_text(content, &[("/rec/gig", "/rec/show"), ("gig.mkv", "show.mkv")]);
+        assert_eq!(occurrences, 2);
+        assert_eq!(
+            rewritten,
+            "video: show.mkv\nold: old_gig.mkv\ndir: /rec/show/extracted\nsibling: /rec/gig2/extracted\nbackup: /rec/gig_old\n"
+        );
+        assert_eq!(rewrite_text("\"/rec/gig\"", &[("/rec/gig", "/rec/show")]), ("\"/rec/show\"".to_string(), 1));
+    }
+
+    #[test]
+    fn test_recording_root() {
+        assert_eq!(recording_root("/old/gig/extracted/Mic.m4a"), Some("/old/gig"));
+        assert_eq!(recording_root("/old/gig/blender/render/frame_0001.png"), Some("/old/gig"));
+        assert_eq!(recording_root("/old/gig/gig.mkv"), Some("/old/gig"));
+        assert_eq!(recording_root("C:\\obs\\gig\\analysis\\Mic_analysis.json"), Some("C:\\obs\\gig"));
+        assert_eq!(recording_root("extracted/Mic.m4a"), None);
+        assert_eq!(recording_root("/usr/share/fonts/font.ttf"), None);
+    }
+
+    #[test]
+    fn test_stale_roots_after_move() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path().join("gig");
+        fs::create_dir_all(&recording).unwrap();
+        fs::write(recording.join("gig.mkv"), "video").unwrap();
+        fs::write(
+            recording.join("metadata.json"),
+            r#"{"recording": "/old/gig/gig.mkv", "sources": {"Mic": "/old/gig/extracted/Mic.m4a"}}"#,
+        )
+        .unwrap();
+        let current_audio = recording.join("extracted/Mic.m4a");
+        fs::write(
+            recording.join("animation_config_beat-switch.yaml"),
+            format!(
+                "audio_analysis:\n  file: \"/older/gig/analysis/Mic_analysis.json\"\nsources:\n  - {}\n",
+                current_audio.display()
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(PathRepair::stale_roots(&recording), vec!["/older/gig", "/old/gig"]);
+    }
+
+    #[test]
+    fn test_replacements_follow_renamed_video() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path().join("new-gig");
+        fs::create_dir_all(&recording).unwrap();
+        fs::write(recording.join("new-gig.mkv"), "video").unwrap();
+
+        let replacements = PathRepair::replacements(&recording, &["/old/gig".to_string()]);
+        assert_eq!(replacements[0], ("/old/gig".to_string(), recording.to_string_lossy().to_string()));
+        assert_eq!(replacements[1], ("gig.mkv".to_string(), "new-gig.mkv".to_string()));
+    }
+}
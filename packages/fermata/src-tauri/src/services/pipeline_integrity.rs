@@ -0,0 +1,216 @@
+use crate::services::{find_blend_files, rendered_videos, ConfigStore, StateManifest, StatusDetector};
+use serde::Serialize;
+use serde_yaml::Value;
+use std::fs;
+use std::path::Path;
+
+/// Printed by BLENDER_LIST_MEDIA_SCRIPT before every external file path
+pub const BLENDER_MEDIA_MARKER: &str = "FERMATA_MEDIA";
+
+/// Headless Blender script printing the absolute path of every external file a
+/// project uses, one `FERMATA_MEDIA\t<path>` line each.
+pub const BLENDER_LIST_MEDIA_SCRIPT: &str = r#"
+import bpy
+
+paths = set()
+for collection in (bpy.data.images, bpy.data.sounds, bpy.data.movieclips, bpy.data.libraries):
+    for item in collection:
+        if item.filepath and getattr(item, "packed_file", None) is None:
+            paths.add(bpy.path.abspath(item.filepath, library=item.library))
+
+for scene in bpy.data.scenes:
+    if scene.sequence_editor:
+        for strip in scene.sequence_editor.sequences_all:
+            if hasattr(strip, "filepath") and strip.filepath:
+                paths.add(bpy.path.abspath(strip.filepath))
+            elif hasattr(strip, "directory") and strip.directory and strip.elements:
+                paths.add(bpy.path.abspath(strip.directory + strip.elements[0].filename))
+
+for path in sorted(paths):
+    print("FERMATA_MEDIA\t" + path)
+"#;
+
+/// An inconsistency between the artifacts of a recording
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct IntegrityFinding {
+    /// Stable identifier of the check, e.g. "analysis_without_extracted"
+    pub check: String,
+    pub detail: String,
+    /// File the finding is about, relative to the recording
+    pub file: Option<String>,
+    /// Step or command that repairs it, e.g. "extract" or "repair_paths"
+    pub suggested_action: String,
+}
+
+impl IntegrityFinding {
+    fn new(check: &str, detail: String, file: Option<String>, suggested_action: &str) -> Self {
+        Self { check: check.to_string(), detail, file, suggested_action: suggested_action.to_string() }
+    }
+}
+
+/// Checks that every step output of a recording still has the inputs it was made
+/// from, for recordings edited outside the app
+pub struct PipelineIntegrity;
+
+impl PipelineIntegrity {
+    /// Findings that need only the filesystem; .blend media is checked with `missing_media`
+    pub fn check_files(recording_path: &Path) -> Vec<IntegrityFinding> {
+        let mut findings = Vec::new();
+        let has_dir = |dir: &str| recording_path.join(dir).is_dir();
+        let blend_files = find_blend_files(recording_path);
+        let configs = ConfigStore::configs(recording_path);
+        let has_video = !rendered_videos(&recording_path.join("blender").join("render")).is_empty();
+
+        if has_dir("analysis") && !has_dir("extracted") {
+            findings.push(IntegrityFinding::new(
+                "analysis_without_extracted",
+                "analysis/ exists but extracted/ is missing".to_string(),
+                Some("analysis".to_string()),
+                "extract",
+            ));
+        }
+        if (!blend_files.is_empty() || !configs.is_empty()) && !has_dir("analysis") {
+            findings.push(IntegrityFinding::new(
+                "setup_without_analysis",
+                "A render setup exists but analysis/ is missing".to_string(),
+                None,
+                "analyze",
+            ));
+        }
+        if has_video && blend_files.is_empty() {
+            findings.push(IntegrityFinding::new(
+                "video_without_blend",
+                "A rendered video exists but no .blend project".to_string(),
+                Some("blender/render".to_string()),
+                "blend_setup",
+            ));
+        }
+        if has_dir("uploads") && !has_video {
+            findings.push(IntegrityFinding::new(
+                "uploads_without_video",
+                "uploads/ exists but no rendered video was found".to_string(),
+                Some("uploads".to_string()),
+                "render",
+            ));
+        }
+        for (config_path, _) in &configs {
+            findings.extend(Self::missing_config_inputs(recording_path, config_path));
+        }
+        if let Some(status) = StateManifest::load(recording_path).and_then(|manifest| manifest.status) {
+            let detected = StatusDetector::detect_from_files(recording_path);
+            if status != detected {
+                findings.push(IntegrityFinding::new(
+                    "stale_state_manifest",
+                    format!("State manifest says {:?} but the files say {:?}", status, detected),
+                    None,
+                    "rebuild_state",
+                ));
+            }
+        }
+        findings
+    }
+
+    /// Files a cinemon config names that are not in extracted/
+    fn missing_config_inputs(recording_path: &Path, config_path: &Path) -> Vec<IntegrityFinding> {
+        let file = config_path
+            .strip_prefix(recording_path)
+            .unwrap_or(config_path)
+            .to_string_lossy()
+            .to_string();
+        let Some(project) = fs::read_to_string(config_path)
+            .ok()
+            .and_then(|content| serde_yaml::from_str::<Value>(&content).ok())
+            .and_then(|config| config.get("project").cloned())
+        else {
+            return Vec::new();
+        };
+
+        let videos = project.get("video_files").and_then(Value::as_sequence).cloned().unwrap_or_default();
+        let main_audio = project.get("main_audio").cloned();
+        videos
+            .iter()
+            .chain(main_audio.iter())
+            .filter_map(Value::as_str)
+            .filter(|name| !recording_path.join("extracted").join(name).exists())
+            .map(|name| {
+                IntegrityFinding::new(
+                    "config_missing_input",
+                    format!("{} uses extracted/{}, which does not exist", file, name),
+                    Some(file.clone()),
+                    "generate_config",
+                )
+            })
+            .collect()
+    }
+
+    /// Findings for the media paths BLENDER_LIST_MEDIA_SCRIPT printed for `blend_file`
+    pub fn missing_media(blend_file: &str, stdout: &str) -> Vec<IntegrityFinding> {
+        stdout
+            .lines()
+            .filter_map(|line| line.strip_prefix(BLENDER_MEDIA_MARKER)?.strip_prefix('\t'))
+            .filter(|path| !Path::new(path).exists())
+            .map(|path| {
+                IntegrityFinding::new(
+                    "blend_missing_media",
+                    format!("{} references {}, which does not exist", blend_file, path),
+                    Some(blend_file.to_string()),
+                    "repair_paths",
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn checks(findings: &[IntegrityFinding]) -> Vec<&str> {
+        findings.iter().map(|f| f.check.as_str()).collect()
+    }
+
+    #[test]
+    fn test_check_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+        assert!(PipelineIntegrity::check_files(recording).is_empty());
+
+        fs::create_dir_all(recording.join("analysis")).unwrap();
+        fs::create_dir_all(recording.join("uploads")).unwrap();
+        fs::write(
+            recording.join("animation_config_beat-switch.yaml"),
+            "project:\n  video_files: [Camera1.mp4]\n  main_audio: Mic.m4a\n",
+        )
+        .unwrap();
+        fs::create_dir_all(recording.join("extracted")).unwrap();
+        fs::write(recording.join("extracted/Camera1.mp4"), "video").unwrap();
+
+        let findings = PipelineIntegrity::check_files(recording);
+        assert_eq!(checks(&findings), vec!["uploads_without_video", "config_missing_input"]);
+        assert_eq!(findings[1].detail, "animation_config_beat-switch.yaml uses extracted/Mic.m4a, which does not exist");
+
+        fs::remove_dir_all(recording.join("extracted")).unwrap();
+        let findings = PipelineIntegrity::check_files(recording);
+        assert_eq!(findings[0].check, "analysis_without_extracted");
+        assert_eq!(findings[0].suggested_action, "extract");
+    }
+
+    #[test]
+    fn test_missing_media() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing = temp_dir.path().join("Camera1.mp4");
+        fs::write(&existing, "video").unwrap();
+        let stdout = format!(
+            "Blender 4.2\n{}\t{}\n{}\t/old/place/Mic.m4a\n",
+            BLENDER_MEDIA_MARKER,
+            existing.display(),
+            BLENDER_MEDIA_MARKER
+        );
+
+        let findings = PipelineIntegrity::missing_media("blender/project.blend", &stdout);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].detail, "blender/project.blend references /old/place/Mic.m4a, which does not exist");
+        assert_eq!(findings[0].suggested_action, "repair_paths");
+    }
+}
@@ -0,0 +1,135 @@
+use crate::models::{Recording, RecordingStatus};
+use crate::services::{status_name, StatusDetector, StepLogs};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// File format of an exported pipeline report
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+/// One recording in a pipeline report
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ReportRow {
+    pub name: String,
+    /// Status name as in the pipeline stats, e.g. "Rendered"
+    pub status: String,
+    /// Why the recording failed, for "Failed" and "Corrupted"
+    pub error: Option<String>,
+    pub last_updated: u64, // Unix timestamp in seconds
+    pub total_bytes: u64,
+    /// Duration of the latest successful run of each step, by step name
+    pub step_durations_ms: BTreeMap<String, u64>,
+    /// Links to the published media
+    pub upload_urls: Vec<String>,
+}
+
+impl ReportRow {
+    /// Read step logs and upload results of a scanned recording
+    pub fn collect(recording: &Recording) -> Self {
+        let mut step_durations_ms = BTreeMap::new();
+        // Newest first, so the first run of a step is its latest
+        for log in StepLogs::list(&recording.path).into_iter().filter(|log| log.success) {
+            if let Some(ms) = log.duration_ms {
+                step_durations_ms.entry(log.step).or_insert(ms);
+            }
+        }
+        let upload_urls = StatusDetector::read_upload_results(&recording.path)
+            .map(|results| results.entries.iter().filter_map(|entry| entry.link()).collect())
+            .unwrap_or_default();
+
+        Self {
+            name: recording.name.clone(),
+            status: status_name(&recording.status).to_string(),
+            error: match &recording.status {
+                RecordingStatus::Failed(error) | RecordingStatus::Corrupted(error) => Some(error.clone()),
+                _ => None,
+            },
+            last_updated: recording.last_updated,
+            total_bytes: recording.file_sizes.values().sum(),
+            step_durations_ms,
+            upload_urls,
+        }
+    }
+}
+
+/// Render report rows as pretty JSON or as CSV with one `<step>_ms` column per step
+/// found in any row and the upload URLs separated by spaces
+pub fn render_report(rows: &[ReportRow], format: ReportFormat) -> Result<String, String> {
+    if format == ReportFormat::Json {
+        return serde_json::to_string_pretty(rows).map_err(|e| format!("Failed to serialize report: {}", e));
+    }
+
+    let steps: BTreeSet<&str> = rows.iter().flat_map(|row| row.step_durations_ms.keys().map(String::as_str)).collect();
+    let mut header = vec!["name".to_string(), "status".to_string(), "error".to_string(), "last_updated".to_string(), "total_bytes".to_string()];
+    header.extend(steps.iter().map(|step| format!("{}_ms", step)));
+    header.push("upload_urls".to_string());
+
+    let mut csv = csv_line(&header);
+    for row in rows {
+        let mut fields = vec![
+            row.name.clone(),
+            row.status.clone(),
+            row.error.clone().unwrap_or_default(),
+            row.last_updated.to_string(),
+            row.total_bytes.to_string(),
+        ];
+        fields.extend(steps.iter().map(|step| row.step_durations_ms.get(*step).map(u64::to_string).unwrap_or_default()));
+        fields.push(row.upload_urls.join(" "));
+        csv.push_str(&csv_line(&fields));
+    }
+    Ok(csv)
+}
+
+/// Fields quoted when they hold a separator, quote or line break
+fn csv_line(fields: &[String]) -> String {
+    let quoted: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect();
+    format!("{}\n", quoted.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(name: &str, steps: &[(&str, u64)]) -> ReportRow {
+        ReportRow {
+            name: name.to_string(),
+            status: "Uploaded".to_string(),
+            error: None,
+            last_updated: 1_700_000_000,
+            total_bytes: 42,
+            step_durations_ms: steps.iter().map(|(step, ms)| (step.to_string(), *ms)).collect(),
+            upload_urls: vec!["https://youtu.be/a".to_string(), "https://example.com/b".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_csv_columns_and_quoting() {
+        let mut failed = row("gig, \"live\"", &[("render", 9000)]);
+        failed.status = "Failed".to_string();
+        failed.error = Some("boom\nagain".to_string());
+        failed.upload_urls.clear();
+        let rows = vec![row("rehearsal", &[("analyze", 1500)]), failed];
+
+        let csv = render_report(&rows, ReportFormat::Csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("name,status,error,last_updated,total_bytes,analyze_ms,render_ms,upload_urls"));
+        assert_eq!(lines.next(), Some("rehearsal,Uploaded,,1700000000,42,1500,,https://youtu.be/a https://example.com/b"));
+        assert!(csv.contains("\"gig, \"\"live\"\"\",Failed,\"boom\nagain\",1700000000,42,,9000,\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&render_report(&rows, ReportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json[0]["step_durations_ms"]["analyze"], 1500);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::models::{Recording, RecordingStatus};
+use crate::services::{uploaded_at, StepLogEntry, StepLogs};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+const WEEK_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Average duration of successful runs of one step
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StepDuration {
+    pub step: String,
+    pub runs: usize,
+    pub average_ms: u64,
+}
+
+/// Overview of all recordings for the dashboard
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PipelineStats {
+    pub total_recordings: usize,
+    /// Count per status name, e.g. "Rendered"; all failures count as "Failed"
+    pub status_counts: BTreeMap<String, usize>,
+    pub total_bytes: u64,
+    pub failed: usize,
+    /// Uploaded within the last 7 days
+    pub uploaded_this_week: usize,
+    pub step_durations: Vec<StepDuration>,
+}
+
+impl PipelineStats {
+    /// Gather statistics, reading upload times and step logs from disk
+    pub fn collect(recordings: &[Recording], now: u64) -> Self {
+        let uploaded_this_week = recordings
+            .iter()
+            .filter(|r| r.status == RecordingStatus::Uploaded)
+            .filter_map(|r| uploaded_at(&r.path))
+            .filter(|at| now.saturating_sub(*at) < WEEK_SECS)
+            .count();
+        let logs: Vec<StepLogEntry> = recordings.iter().flat_map(|r| StepLogs::list(&r.path)).collect();
+
+        Self::from_parts(recordings, uploaded_this_week, &logs)
+    }
+
+    fn from_parts(recordings: &[Recording], uploaded_this_week: usize, logs: &[StepLogEntry]) -> Self {
+        let status_counts = status_counts(recordings);
+
+        let mut durations: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+        for log in logs.iter().filter(|l| l.success) {
+            if let Some(ms) = log.duration_ms {
+                durations.entry(log.step.as_str()).or_default().push(ms);
+            }
+        }
+
+        Self {
+            total_recordings: recordings.len(),
+            failed: status_counts.get("Failed").copied().unwrap_or(0),
+            status_counts,
+            total_bytes: recordings.iter().flat_map(|r| r.file_sizes.values()).sum(),
+            uploaded_this_week,
+            step_durations: durations
+                .into_iter()
+                .map(|(step, runs)| StepDuration {
+                    step: step.to_string(),
+                    runs: runs.len(),
+                    average_ms: runs.iter().sum::<u64>() / runs.len() as u64,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Number of recordings per status name, e.g. "Failed": 2
+pub fn status_counts(recordings: &[Recording]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for recording in recordings {
+        *counts.entry(status_name(&recording.status).to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Name of a status without its details, e.g. "Failed" for every failure
+pub fn status_name(status: &RecordingStatus) -> &'static str {
+    match status {
+        RecordingStatus::Recorded => "Recorded",
+        RecordingStatus::Extracted => "Extracted",
+        RecordingStatus::Analyzed => "Analyzed",
+        RecordingStatus::ConfigGenerated => "ConfigGenerated",
+        RecordingStatus::SetupRendered => "SetupRendered",
+        RecordingStatus::FramesRendered => "FramesRendered",
+        RecordingStatus::Rendered => "Rendered",
+        RecordingStatus::Uploaded => "Uploaded",
+        RecordingStatus::Failed(_) => "Failed",
+        RecordingStatus::Corrupted(_) => "Corrupted",
+        RecordingStatus::Analyzing => "Analyzing",
+        RecordingStatus::SettingUpRender => "SettingUpRender",
+        RecordingStatus::Rendering => "Rendering",
+        RecordingStatus::Uploading => "Uploading",
+        RecordingStatus::Transcribing => "Transcribing",
+        RecordingStatus::RunningStep(_) => "RunningStep",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn recording(status: RecordingStatus, size: u64) -> Recording {
+        Recording {
+            name: "gig".to_string(),
+            path: PathBuf::from("gig"),
+            status,
+            last_updated: 0,
+            file_sizes: HashMap::from([("gig.mkv".to_string(), size)]),
+            upload_block: None,
+            root: PathBuf::new(),
+            lifecycle: None,
+            media: None,
+            tree: None,
+        }
+    }
+
+    fn log(step: &str, success: bool, duration_ms: Option<u64>) -> StepLogEntry {
+        StepLogEntry {
+            id: format!("{}_20240114-020000.log", step),
+            step: step.to_string(),
+            timestamp: "20240114-020000".to_string(),
+            success,
+            duration_ms,
+            size_bytes: 0,
+            options: None,
+            outcome: None,
+        }
+    }
+
+    #[test]
+    fn test_counts_storage_and_average_durations() {
+        let recordings = vec![
+            recording(RecordingStatus::Rendered, 100),
+            recording(RecordingStatus::Failed("boom".to_string()), 50),
+            recording(RecordingStatus::Failed("again".to_string()), 25),
+        ];
+        let logs = vec![
+            log("render", true, Some(1_000)),
+            log("render", true, Some(3_000)),
+            log("render", false, Some(10)),
+            log("analyze", true, None),
+        ];
+
+        let stats = PipelineStats::from_parts(&recordings, 1, &logs);
+        assert_eq!(stats.total_recordings, 3);
+        assert_eq!(stats.status_counts.get("Failed"), Some(&2));
+        assert_eq!(stats.failed, 2);
+        assert_eq!(stats.total_bytes, 175);
+        assert_eq!(stats.uploaded_this_week, 1);
+        assert_eq!(stats.step_durations, vec![StepDuration { step: "render".to_string(), runs: 2, average_ms: 2_000 }]);
+    }
+}
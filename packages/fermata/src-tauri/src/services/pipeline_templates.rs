@@ -0,0 +1,193 @@
+use crate::models::{NextStep, Recording};
+use crate::services::{within_target, write_atomic, CustomStep, CustomSteps, PreviewOptions, PreviewStatus, RenderPreview, SettingsStore};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Settings key holding the pipeline templates
+pub const PIPELINE_TEMPLATES_KEY: &str = "pipeline_templates";
+
+/// Per-recording template selection, moves with the recording on rename
+pub const RECORDING_PIPELINE_TEMPLATE_FILE: &str = ".fermata/pipeline_template";
+
+/// "Quick demo": analyze, set up with the music-video preset and render a preview, no upload
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PipelineTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Last built-in step the pipeline runs, e.g. "render"; "upload" runs all of them
+    pub last_step: String,
+    /// Animation preset for SetupRender; cinemon's default when unset
+    #[serde(default)]
+    pub preset: Option<String>,
+    /// Render a preview instead of the video; the template then ends with it
+    #[serde(default)]
+    pub preview: Option<PreviewOptions>,
+    /// Upload profiles uploaded to in turn; the recording's own profile when empty
+    #[serde(default)]
+    pub upload_profiles: Vec<String>,
+}
+
+impl PipelineTemplate {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Pipeline template name cannot be empty".to_string());
+        }
+        let last: NextStep = self.last_step.parse()?;
+        if !within_target(&last, &last) {
+            return Err(format!("Template '{}' cannot end with {}", self.name, self.last_step));
+        }
+        if let Some(preview) = &self.preview {
+            PreviewOptions::new(preview.scale_percent, preview.frame_step)?;
+            if last != NextStep::Render {
+                return Err(format!("Template '{}' renders a preview, so it must end with render", self.name));
+            }
+        }
+        if !self.upload_profiles.is_empty() && last != NextStep::Upload {
+            return Err(format!("Template '{}' has upload profiles but does not end with upload", self.name));
+        }
+        Ok(())
+    }
+
+    /// Next step of a recording following the template, None once its last step ran.
+    /// Custom steps run when the step they follow is part of the template.
+    pub fn next_step(&self, recording: &Recording, custom_steps: &[CustomStep]) -> Option<NextStep> {
+        let last: NextStep = self.last_step.parse().ok()?;
+        match CustomSteps::next_step(recording, custom_steps)? {
+            NextStep::Retry => Some(NextStep::Retry),
+            NextStep::Custom(id) => {
+                let after: NextStep = CustomSteps::find(custom_steps, &id)?.after.parse().ok()?;
+                within_target(&after, &last).then_some(NextStep::Custom(id))
+            }
+            NextStep::Render if self.preview.is_some() && preview_done(&recording.path) => None,
+            step => within_target(&step, &last).then_some(step),
+        }
+    }
+}
+
+fn preview_done(recording_path: &Path) -> bool {
+    RenderPreview::load(recording_path).is_some_and(|preview| preview.status == PreviewStatus::Done)
+}
+
+pub struct PipelineTemplates;
+
+impl PipelineTemplates {
+    pub fn list(settings: &SettingsStore) -> Result<Vec<PipelineTemplate>, String> {
+        settings.get(PIPELINE_TEMPLATES_KEY)
+    }
+
+    pub fn save(settings: &SettingsStore, templates: &[PipelineTemplate]) -> Result<(), String> {
+        for (index, template) in templates.iter().enumerate() {
+            template.validate()?;
+            if templates[..index].iter().any(|other| other.name == template.name) {
+                return Err(format!("Template name '{}' is used twice", template.name));
+            }
+        }
+        settings.set(PIPELINE_TEMPLATES_KEY, &templates)
+    }
+
+    /// Template assigned to a recording, if any
+    pub fn recording_template(recording_path: &Path) -> Option<String> {
+        fs::read_to_string(recording_path.join(RECORDING_PIPELINE_TEMPLATE_FILE))
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Assign (or clear with `None`) the template a recording follows
+    pub fn set_recording_template(recording_path: &Path, template: Option<&str>) -> Result<(), String> {
+        let path = recording_path.join(RECORDING_PIPELINE_TEMPLATE_FILE);
+        match template {
+            Some(name) => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)
+                        .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+                }
+                write_atomic(&path, name).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+            }
+            None if path.exists() => {
+                fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// The template a recording follows; an error when the assigned one was deleted
+    pub fn for_recording(settings: &SettingsStore, recording_path: &Path) -> Result<Option<PipelineTemplate>, String> {
+        let Some(name) = Self::recording_template(recording_path) else {
+            return Ok(None);
+        };
+        Self::list(settings)?
+            .into_iter()
+            .find(|template| template.name == name)
+            .map(Some)
+            .ok_or_else(|| format!("Pipeline template '{}' not found", name))
+    }
+
+    /// Next step of a recording, following its template when it has one
+    pub fn next_step(recording: &Recording, custom_steps: &[CustomStep], template: Option<&PipelineTemplate>) -> Option<NextStep> {
+        match template {
+            Some(template) => template.next_step(recording, custom_steps),
+            None => CustomSteps::next_step(recording, custom_steps),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RecordingStatus;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn quick_demo() -> PipelineTemplate {
+        PipelineTemplate {
+            name: "Quick demo".to_string(),
+            description: None,
+            last_step: "render".to_string(),
+            preset: Some("music-video".to_string()),
+            preview: Some(PreviewOptions { scale_percent: 25, frame_step: 4 }),
+            upload_profiles: Vec::new(),
+        }
+    }
+
+    fn recording(path: &Path, status: RecordingStatus) -> Recording {
+        Recording {
+            name: "gig".to_string(),
+            path: path.to_path_buf(),
+            status,
+            last_updated: 0,
+            file_sizes: HashMap::new(),
+            upload_block: None,
+            root: path.to_path_buf(),
+            lifecycle: None,
+            media: None,
+            tree: None,
+        }
+    }
+
+    #[test]
+    fn test_template_limits_the_pipeline() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        let template = quick_demo();
+        assert!(template.validate().is_ok());
+        assert!(PipelineTemplate { last_step: "upload".to_string(), ..quick_demo() }.validate().is_err());
+        assert!(PipelineTemplate { upload_profiles: vec!["youtube".to_string()], ..quick_demo() }.validate().is_err());
+
+        let analyzed = recording(path, RecordingStatus::Analyzed);
+        assert_eq!(template.next_step(&analyzed, &[]), Some(NextStep::SetupRender));
+        let set_up = recording(path, RecordingStatus::SetupRendered);
+        assert_eq!(template.next_step(&set_up, &[]), Some(NextStep::Render));
+
+        let mut preview = RenderPreview::started(path, template.preview.unwrap());
+        preview.finish(PreviewStatus::Done);
+        preview.save(path).unwrap();
+        assert_eq!(template.next_step(&set_up, &[]), None);
+
+        let render_only = PipelineTemplate { preview: None, preset: None, ..quick_demo() };
+        assert_eq!(render_only.next_step(&recording(path, RecordingStatus::Rendered), &[]), None);
+        assert_eq!(PipelineTemplates::next_step(&recording(path, RecordingStatus::Rendered), &[], None), Some(NextStep::Upload));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::services::{process_arg, write_atomic, SubtitleFiles, UploadConfig};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Copies of the render with overlays burned in, next to the files ffmpeg reads them from
+pub const POST_PROCESSED_DIR: &str = "uploads/post_processed";
+
+/// Options the copies in POST_PROCESSED_DIR were made with
+const OPTIONS_FILE: &str = "options.json";
+
+/// Fixed names in POST_PROCESSED_DIR, so ffmpeg filters never need to escape paths
+const TITLE_FILE: &str = "title.txt";
+const SUBTITLES_FILE: &str = "subtitles.srt";
+
+/// Overlays burned into a copy of the render before it is uploaded
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PostProcessOptions {
+    /// Burn the transcribed subtitles into the picture, for platforms without captions
+    pub burn_subtitles: bool,
+    /// Show the recording name and date at the start
+    pub title_card: bool,
+    pub title_seconds: f64,
+    pub font_size: u32,
+}
+
+impl Default for PostProcessOptions {
+    fn default() -> Self {
+        Self { burn_subtitles: false, title_card: false, title_seconds: 5.0, font_size: 48 }
+    }
+}
+
+impl PostProcessOptions {
+    /// Nothing to burn in, the render is uploaded as it is
+    pub fn is_empty(&self) -> bool {
+        !self.burn_subtitles && !self.title_card
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.title_seconds > 0.0 && self.title_seconds <= 60.0) {
+            return Err(format!("Title card must show for 0-60 seconds, got {}", self.title_seconds));
+        }
+        if !(8..=200).contains(&self.font_size) {
+            return Err(format!("Font size must be between 8 and 200, got {}", self.font_size));
+        }
+        Ok(())
+    }
+
+    /// Post-processed copy of a render video
+    pub fn output_path(recording_path: &Path, video: &Path) -> PathBuf {
+        let stem = video.file_stem().unwrap_or_default().to_string_lossy();
+        recording_path.join(POST_PROCESSED_DIR).join(format!("{}_post.mp4", stem))
+    }
+
+    /// True if `output` was made from `video` with these options and the current subtitles
+    pub fn is_fresh(&self, recording_path: &Path, video: &Path, output: &Path) -> bool {
+        let dir = recording_path.join(POST_PROCESSED_DIR);
+        let same_options = fs::read_to_string(dir.join(OPTIONS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<PostProcessOptions>(&content).ok())
+            .is_some_and(|saved| saved == *self);
+        let subtitles_fresh = !self.burn_subtitles
+            || SubtitleFiles::find(recording_path)
+                .and_then(|files| files.srt)
+                .is_some_and(|srt| UploadConfig::is_transcode_fresh(&srt, output));
+        same_options && subtitles_fresh && UploadConfig::is_transcode_fresh(video, output)
+    }
+
+    /// Write the files the filters read into POST_PROCESSED_DIR, the directory ffmpeg runs in
+    pub fn prepare(&self, recording_path: &Path, title: &str, date: NaiveDate) -> Result<PathBuf, String> {
+        self.validate()?;
+        let dir = recording_path.join(POST_PROCESSED_DIR);
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        if self.burn_subtitles {
+            let srt = SubtitleFiles::find(recording_path)
+                .and_then(|files| files.srt)
+                .ok_or_else(|| "No SRT subtitles to burn in - run the transcribe step first".to_string())?;
+            fs::copy(&srt, dir.join(SUBTITLES_FILE)).map_err(|e| format!("Failed to copy {}: {}", srt.display(), e))?;
+        }
+        if self.title_card {
+            let text = format!("{}\n{}", title, date.format("%Y-%m-%d"));
+            write_atomic(&dir.join(TITLE_FILE), text).map_err(|e| format!("Failed to write title card: {}", e))?;
+        }
+        Ok(dir)
+    }
+
+    /// Remember the options a finished copy was made with
+    pub fn mark_done(&self, recording_path: &Path) -> Result<(), String> {
+        let path = recording_path.join(POST_PROCESSED_DIR).join(OPTIONS_FILE);
+        let content = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize post-process options: {}", e))?;
+        write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Video filters reading the files `prepare` wrote
+    pub fn video_filters(&self) -> String {
+        let mut filters = Vec::new();
+        if self.burn_subtitles {
+            filters.push(format!("subtitles={}", SUBTITLES_FILE));
+        }
+        if self.title_card {
+            filters.push(format!(
+                "drawtext=textfile={}:fontsize={}:fontcolor=white:box=1:boxcolor=black@0.5:boxborderw=20:x=(w-text_w)/2:y=(h-text_h)/2:enable='lt(t,{})'",
+                TITLE_FILE, self.font_size, self.title_seconds
+            ));
+        }
+        filters.join(",")
+    }
+
+    /// ffmpeg arguments re-encoding the picture with the overlays, keeping the audio
+    pub fn ffmpeg_args(&self, video: &Path, output: &Path) -> Vec<OsString> {
+        vec![
+            "-y".into(),
+            "-i".into(),
+            process_arg(video),
+            "-vf".into(),
+            self.video_filters().into(),
+            "-c:v".into(),
+            "libx264".into(),
+            "-crf".into(),
+            "18".into(),
+            "-pix_fmt".into(),
+            "yuv420p".into(),
+            "-c:a".into(),
+            "copy".into(),
+            "-movflags".into(),
+            "+faststart".into(),
+            process_arg(output),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::SUBTITLES_DIR;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_prepare_filters_and_freshness() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        let options = PostProcessOptions { burn_subtitles: true, title_card: true, ..PostProcessOptions::default() };
+        let date = NaiveDate::from_ymd_opt(2025, 6, 14).unwrap();
+        assert!(options.prepare(path, "Main Stage", date).is_err());
+
+        fs::create_dir_all(path.join(SUBTITLES_DIR)).unwrap();
+        fs::write(path.join(SUBTITLES_DIR).join("Mic.srt"), "1\n00:00:01,000 --> 00:00:02,000\nHello\n").unwrap();
+        let dir = options.prepare(path, "Main Stage", date).unwrap();
+        assert_eq!(fs::read_to_string(dir.join(TITLE_FILE)).unwrap(), "Main Stage\n2025-06-14");
+        assert!(dir.join(SUBTITLES_FILE).is_file());
+        assert!(options.video_filters().starts_with("subtitles=subtitles.srt,drawtext=textfile=title.txt:fontsize=48"));
+
+        let video = path.join("blender/render/final.mp4");
+        fs::create_dir_all(video.parent().unwrap()).unwrap();
+        fs::write(&video, "video").unwrap();
+        let output = PostProcessOptions::output_path(path, &video);
+        assert_eq!(output, dir.join("final_post.mp4"));
+        fs::write(&output, "burned").unwrap();
+        assert!(!options.is_fresh(path, &video, &output));
+        options.mark_done(path).unwrap();
+        assert!(options.is_fresh(path, &video, &output));
+        assert!(!PostProcessOptions { font_size: 32, ..options.clone() }.is_fresh(path, &video, &output));
+        assert!(PostProcessOptions { title_seconds: 0.0, ..options }.validate().is_err());
+    }
+}
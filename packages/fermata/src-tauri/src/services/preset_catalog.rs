@@ -0,0 +1,145 @@
+use serde::Serialize;
+use serde_yaml::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Built-in cinemon presets, relative to the workspace root
+pub const CINEMON_PRESETS_DIR: &str = "packages/cinemon/blender_addon/example_presets";
+
+const DESCRIPTION_PREFIX: &str = "# ABOUTME:";
+
+/// An animation preset as shown in the preset picker
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PresetInfo {
+    pub name: String,
+    pub description: Option<String>,
+    /// Layout type, e.g. "main-pip"
+    pub layout: Option<String>,
+    pub default_seed: Option<u64>,
+}
+
+impl PresetInfo {
+    /// Preset names printed by `cinemon-generate-config --list-presets`
+    pub fn parse_names(output: &str) -> Vec<String> {
+        output
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("- "))
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    /// Details read from the preset's YAML template. Custom presets live
+    /// outside the workspace, so only their name is known.
+    pub fn load(name: &str, presets_dir: &Path) -> Self {
+        match fs::read_to_string(presets_dir.join(format!("{}.yaml", name))) {
+            Ok(content) => Self::from_yaml(name, &content),
+            Err(_) => Self::named(name),
+        }
+    }
+
+    pub fn from_yaml(name: &str, content: &str) -> Self {
+        // The first ABOUTME line of a preset describes it
+        let description = content
+            .lines()
+            .find_map(|line| line.strip_prefix(DESCRIPTION_PREFIX))
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+        let layout = serde_yaml::from_str::<Value>(content)
+            .ok()
+            .and_then(|config| config.get("layout").cloned());
+
+        Self {
+            name: name.to_string(),
+            description,
+            layout: layout
+                .as_ref()
+                .and_then(|layout| layout.get("type"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            default_seed: layout
+                .as_ref()
+                .and_then(|layout| layout.get("config"))
+                .and_then(|config| config.get("seed"))
+                .and_then(Value::as_u64),
+        }
+    }
+
+    fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: None,
+            layout: None,
+            default_seed: None,
+        }
+    }
+}
+
+/// Presets listed by cinemon, kept in Tauri state so the picker does not
+/// start a uv process every time it opens
+pub struct PresetCatalog {
+    presets: Mutex<Option<Vec<PresetInfo>>>,
+}
+
+impl Default for PresetCatalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PresetCatalog {
+    pub fn new() -> Self {
+        Self { presets: Mutex::new(None) }
+    }
+
+    pub fn get(&self) -> Option<Vec<PresetInfo>> {
+        self.presets.lock().ok()?.clone()
+    }
+
+    pub fn set(&self, presets: Vec<PresetInfo>) {
+        if let Ok(mut cached) = self.presets.lock() {
+            *cached = Some(presets);
+        }
+    }
+
+    pub fn presets_dir(workspace_root: &Path) -> PathBuf {
+        workspace_root.join(CINEMON_PRESETS_DIR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_names() {
+        let output = "Available presets:\n\n  - minimal\n  - multi_pip\n  -  \n";
+        assert_eq!(PresetInfo::parse_names(output), vec!["minimal", "multi_pip"]);
+    }
+
+    #[test]
+    fn test_load_preset_details() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("minimal.yaml"),
+            "# ABOUTME: Minimal preset with subtle animations\n# ABOUTME: Second line\n\
+             layout:\n  type: \"random\"\n  config:\n    seed: 42\n",
+        )
+        .unwrap();
+
+        let preset = PresetInfo::load("minimal", temp_dir.path());
+        assert_eq!(preset.description.as_deref(), Some("Minimal preset with subtle animations"));
+        assert_eq!(preset.layout.as_deref(), Some("random"));
+        assert_eq!(preset.default_seed, Some(42));
+
+        let custom = PresetInfo::load("my-style", temp_dir.path());
+        assert_eq!(custom, PresetInfo::named("my-style"));
+
+        let catalog = PresetCatalog::new();
+        assert!(catalog.get().is_none());
+        catalog.set(vec![preset.clone()]);
+        assert_eq!(catalog.get(), Some(vec![preset]));
+    }
+}
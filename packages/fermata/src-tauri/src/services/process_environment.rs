@@ -0,0 +1,117 @@
+use crate::models::NextStep;
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Settings key for environment variables set for spawned processes
+pub const PROCESS_ENVIRONMENT_KEY: &str = "process_environment";
+
+/// Variables set for the processes steps spawn, on top of whatever environment the
+/// app inherited from the desktop session. Values may use `${NAME}` for the inherited
+/// value, e.g. "/opt/cuda/bin:${PATH}". Step overrides win over global ones.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProcessEnvironment {
+    #[serde(default)]
+    pub global: BTreeMap<String, String>,
+    /// Overrides keyed by step id, e.g. "render"
+    #[serde(default)]
+    pub steps: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl ProcessEnvironment {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(PROCESS_ENVIRONMENT_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        self.validate()?;
+        settings.set(PROCESS_ENVIRONMENT_KEY, self)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (step, vars) in std::iter::once(("*", &self.global)).chain(self.steps.iter().map(|(s, v)| (s.as_str(), v))) {
+            if step.trim().is_empty() {
+                return Err("Environment overrides need a step id".to_string());
+            }
+            for (name, value) in vars {
+                if name.is_empty() || name.contains(['=', '\0']) {
+                    return Err(format!("Invalid environment variable name '{}'", name));
+                }
+                if value.contains('\0') {
+                    return Err(format!("Value of {} contains a NUL character", name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Variables for the processes of `step`, or the global ones for processes outside steps
+    pub fn for_step(&self, step: Option<&NextStep>) -> Vec<(String, String)> {
+        let mut vars = self.global.clone();
+        if let Some(overrides) = step.and_then(|step| self.steps.get(&step.id())) {
+            vars.extend(overrides.clone());
+        }
+        vars.into_iter()
+            .map(|(name, value)| {
+                let value = expand(&value, |name| std::env::var(name).ok());
+                (name, value)
+            })
+            .collect()
+    }
+}
+
+/// Replace `${NAME}` with `lookup(NAME)`, or nothing when it is unset
+fn expand(value: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&lookup(&rest[start + 2..start + 2 + len]).unwrap_or_default());
+        rest = &rest[start + 3 + len..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_for_step_merges_overrides() {
+        let environment = ProcessEnvironment {
+            global: vars(&[("LANG", "en_US.UTF-8"), ("CUDA_VISIBLE_DEVICES", "0")]),
+            steps: [("render".to_string(), vars(&[("CUDA_VISIBLE_DEVICES", "1")]))].into_iter().collect(),
+        };
+        assert_eq!(
+            environment.for_step(Some(&NextStep::Render)),
+            [("CUDA_VISIBLE_DEVICES".to_string(), "1".to_string()), ("LANG".to_string(), "en_US.UTF-8".to_string())]
+        );
+        assert_eq!(environment.for_step(Some(&NextStep::Analyze))[0].1, "0");
+        assert_eq!(environment.for_step(None).len(), 2);
+    }
+
+    #[test]
+    fn test_expand() {
+        let lookup = |name: &str| (name == "PATH").then(|| "/usr/bin".to_string());
+        assert_eq!(expand("/opt/cuda/bin:${PATH}", lookup), "/opt/cuda/bin:/usr/bin");
+        assert_eq!(expand("${MISSING}x${PATH}", lookup), "x/usr/bin");
+        assert_eq!(expand("${PATH", lookup), "${PATH");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(ProcessEnvironment::default().validate().is_ok());
+        let bad_name = ProcessEnvironment { global: vars(&[("A=B", "1")]), ..Default::default() };
+        assert!(bad_name.validate().is_err());
+        let no_step = ProcessEnvironment { steps: [(" ".to_string(), vars(&[]))].into_iter().collect(), ..Default::default() };
+        assert!(no_step.validate().is_err());
+    }
+}
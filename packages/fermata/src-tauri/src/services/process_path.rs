@@ -0,0 +1,58 @@
+use std::ffi::OsString;
+use std::path::Path;
+
+/// Longest path Windows programs accept without the `\\?\` prefix
+const MAX_PATH: usize = 260;
+
+/// `path` as an argument of a child process. On Windows, absolute paths at or past MAX_PATH
+/// get the verbatim `\\?\` (or `\\?\UNC\`) form ffmpeg, Blender and Python accept;
+/// shorter ones, including plain UNC shares, pass through unchanged.
+pub fn process_arg(path: &Path) -> OsString {
+    match path.to_str().filter(|_| cfg!(windows)).and_then(verbatim) {
+        Some(long) => long.into(),
+        None => path.as_os_str().to_os_string(),
+    }
+}
+
+/// Verbatim form of a long absolute Windows path, None when it needs none or can't have one
+fn verbatim(path: &str) -> Option<String> {
+    if path.len() < MAX_PATH || path.starts_with(r"\\?\") || path.starts_with("//?/") {
+        return None;
+    }
+    // Verbatim paths skip normalization, so separators must be backslashes and no
+    // component may be relative
+    let path = path.replace('/', "\\");
+    if path.split('\\').any(|c| c == "." || c == "..") {
+        return None;
+    }
+    if let Some(share) = path.strip_prefix(r"\\") {
+        return Some(format!(r"\\?\UNC\{}", share));
+    }
+    let bytes = path.as_bytes();
+    let is_drive_absolute = bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\';
+    is_drive_absolute.then(|| format!(r"\\?\{}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbatim() {
+        let dir = "nagranie z koncertu ".repeat(15);
+        assert_eq!(verbatim(r"C:\Nagrania\a.mkv"), None);
+        assert_eq!(verbatim(&format!(r"C:\{}\a.mkv", dir)), Some(format!(r"\\?\C:\{}\a.mkv", dir)));
+        assert_eq!(verbatim(&format!("D:/{}/a.mkv", dir)), Some(format!(r"\\?\D:\{}\a.mkv", dir)));
+        assert_eq!(verbatim(&format!(r"\\nas\obs\{}\a.mkv", dir)), Some(format!(r"\\?\UNC\nas\obs\{}\a.mkv", dir)));
+        assert_eq!(verbatim(&format!(r"\\?\C:\{}", dir)), None);
+        assert_eq!(verbatim(&format!(r"C:\{}\..\a.mkv", dir)), None);
+        assert_eq!(verbatim(&format!(r"{}\a.mkv", dir)), None);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_process_arg_unchanged_off_windows() {
+        let path = Path::new("/srv/recordings").join("x".repeat(300));
+        assert_eq!(process_arg(&path), path.as_os_str());
+    }
+}
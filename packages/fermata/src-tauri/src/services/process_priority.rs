@@ -0,0 +1,135 @@
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+use tokio::process::Command as AsyncCommand;
+
+/// Settings key for how pipeline processes share the machine with the desktop
+pub const PROCESS_PRIORITY_KEY: &str = "process_priority";
+
+/// Highest niceness nice accepts
+const MAX_NICENESS: u8 = 19;
+
+/// Scheduling of the processes steps spawn, so the app stays usable during a render.
+/// Options a platform lacks are ignored there.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProcessPriority {
+    /// Niceness 1-19 on Unix; any value means below-normal priority on Windows.
+    /// None keeps normal priority.
+    pub niceness: Option<u8>,
+    /// Only use the disk when nothing else does, with ionice's idle class (Linux)
+    #[serde(default)]
+    pub idle_io: bool,
+    /// CPU cores processes may run on, a taskset list such as "0-3,6" (Linux)
+    #[serde(default)]
+    pub cpu_cores: Option<String>,
+}
+
+impl ProcessPriority {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(PROCESS_PRIORITY_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        self.validate()?;
+        settings.set(PROCESS_PRIORITY_KEY, self)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(niceness) = self.niceness {
+            if niceness == 0 || niceness > MAX_NICENESS {
+                return Err(format!("Niceness must be between 1 and {}", MAX_NICENESS));
+            }
+        }
+        if let Some(cores) = &self.cpu_cores {
+            let valid = cores.split(',').all(|range| {
+                let mut bounds = range.splitn(2, '-');
+                bounds.all(|bound| !bound.is_empty() && bound.bytes().all(|b| b.is_ascii_digit()))
+            });
+            if cores.is_empty() || !valid {
+                return Err(format!("Invalid CPU core list '{}', expected e.g. \"0-3,6\"", cores));
+            }
+        }
+        Ok(())
+    }
+
+    /// Wrapper commands that launch a program with this priority on this platform
+    fn launcher(&self) -> Vec<String> {
+        let mut launcher = Vec::new();
+        if let (Some(niceness), true) = (self.niceness, cfg!(unix)) {
+            launcher.extend(["nice".to_string(), "-n".to_string(), niceness.to_string()]);
+        }
+        if cfg!(target_os = "linux") {
+            if self.idle_io {
+                launcher.extend(["ionice".to_string(), "-c".to_string(), "3".to_string()]);
+            }
+            if let Some(cores) = &self.cpu_cores {
+                launcher.extend(["taskset".to_string(), "-c".to_string(), cores.clone()]);
+            }
+        }
+        launcher
+    }
+
+    /// `cmd` launched with this priority
+    pub fn apply(&self, cmd: AsyncCommand) -> AsyncCommand {
+        #[cfg(windows)]
+        let cmd = {
+            // BELOW_NORMAL_PRIORITY_CLASS
+            let mut cmd = cmd;
+            if self.niceness.is_some() {
+                cmd.creation_flags(0x0000_4000);
+            }
+            cmd
+        };
+
+        let launcher = self.launcher();
+        let Some((program, launcher_args)) = launcher.split_first() else {
+            return cmd;
+        };
+
+        let std_cmd = cmd.as_std();
+        let mut wrapped = AsyncCommand::new(program);
+        wrapped.args(launcher_args).arg(std_cmd.get_program()).args(std_cmd.get_args());
+        if let Some(dir) = std_cmd.get_current_dir() {
+            wrapped.current_dir(dir);
+        }
+        for (key, value) in std_cmd.get_envs() {
+            match value {
+                Some(value) => wrapped.env(key, value),
+                None => wrapped.env_remove(key),
+            };
+        }
+        wrapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(ProcessPriority::default().validate().is_ok());
+        assert!(ProcessPriority { niceness: Some(10), cpu_cores: Some("0-3,6".to_string()), ..Default::default() }.validate().is_ok());
+        assert!(ProcessPriority { niceness: Some(20), ..Default::default() }.validate().is_err());
+        assert!(ProcessPriority { cpu_cores: Some("0-".to_string()), ..Default::default() }.validate().is_err());
+        assert!(ProcessPriority { cpu_cores: Some("all".to_string()), ..Default::default() }.validate().is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_apply_wraps_command() {
+        let priority = ProcessPriority { niceness: Some(10), idle_io: true, cpu_cores: Some("0-3".to_string()) };
+        let mut cmd = AsyncCommand::new("blender");
+        cmd.arg("--background").current_dir("/tmp").env("CUDA_VISIBLE_DEVICES", "0");
+
+        let wrapped = priority.apply(cmd);
+        let std_cmd = wrapped.as_std();
+        assert_eq!(std_cmd.get_program(), "nice");
+        let args: Vec<_> = std_cmd.get_args().map(|a| a.to_string_lossy().to_string()).collect();
+        assert_eq!(args, ["-n", "10", "ionice", "-c", "3", "taskset", "-c", "0-3", "blender", "--background"]);
+        assert_eq!(std_cmd.get_current_dir(), Some(std::path::Path::new("/tmp")));
+        assert_eq!(std_cmd.get_envs().count(), 1);
+
+        let unchanged = ProcessPriority::default().apply(AsyncCommand::new("blender"));
+        assert_eq!(unchanged.as_std().get_program(), "blender");
+    }
+}
@@ -1,6 +1,28 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt;
 use std::path::{Path, PathBuf};
-use tokio::process::Command as AsyncCommand;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::{Child, Command as AsyncCommand};
 use serde::{Serialize, Deserialize};
+use crate::models::{NextStep, UploadMetadata};
+use crate::services::{forget_child_pid, signal_group, loudnorm_args, process_arg, processes_suspended, record_child_pid, track_process, untrack_process, AnalyzeOptions, ConfigStore, EntryPoint, ExecutionBackend, FrameSequence, PreviewOptions, ProcessPriority, Toolchain, BEATRIX, CINEMON_BLEND_SETUP, CINEMON_GENERATE_CONFIG, MEDUSA, PACKAGE_CHECK_ENTRY_POINTS, RenderDevice, RenderSettings, StallTracker, StallWatch, StepOutcome, StepScratch, TranscodeProfile, STALL_MARKER, BLENDER_FRAME_RANGE_SCRIPT,
+    BLENDER_LIST_DEVICES_SCRIPT, BLENDER_LIST_MEDIA_SCRIPT, BLENDER_REMAP_SCRIPT, BLENDER_BENCHMARK_DEVICE_SCRIPT, blender_scene_script};
+
+/// Workspace packages fermata runs through uv
+pub const WORKSPACE_PACKAGES: [&str; 3] = ["beatrix", "cinemon", "medusa"];
+
+/// How often a running process is checked against its limits
+const WATCHDOG_TICK: Duration = Duration::from_millis(250);
+
+/// Called with every complete stdout line while a process runs
+pub type LineCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Output still buffered in the pipes is collected for at most this long after a kill
+const KILL_GRACE: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessResult {
@@ -8,11 +30,279 @@ pub struct ProcessResult {
     pub stdout: String,
     pub stderr: String,
     pub exit_code: Option<i32>,
+    /// Set when the process was killed by the watchdog instead of exiting
+    #[serde(default)]
+    pub timeout: Option<ProcessTimeout>,
+    /// Unix timestamp in milliseconds
+    #[serde(default)]
+    pub started_at: u64,
+    /// Unix timestamp in milliseconds
+    #[serde(default)]
+    pub finished_at: u64,
+    #[serde(default)]
+    pub duration_ms: u64,
+    /// Timed parts of a composite step, e.g. config generation and blend setup
+    #[serde(default)]
+    pub phases: Vec<ProcessPhase>,
+    /// What the tool reported about its work, when its output says
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<StepOutcome>,
+}
+
+/// One timed part of a composite step
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProcessPhase {
+    pub name: String,
+    pub success: bool,
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub duration_ms: u64,
+}
+
+impl ProcessResult {
+    /// What a dry run reports for a command it did not start
+    pub fn dry_run() -> Self {
+        let now = unix_millis(SystemTime::now());
+        Self {
+            success: true,
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: Some(0),
+            timeout: None,
+            started_at: now,
+            finished_at: now,
+            duration_ms: 0,
+            phases: Vec::new(),
+            outcome: None,
+        }
+    }
+
+    /// A failure that happened before or instead of running a process
+    pub fn error(message: String) -> Self {
+        let now = unix_millis(SystemTime::now());
+        Self {
+            success: false,
+            stdout: String::new(),
+            stderr: message,
+            exit_code: Some(1),
+            timeout: None,
+            started_at: now,
+            finished_at: now,
+            duration_ms: 0,
+            phases: Vec::new(),
+            outcome: None,
+        }
+    }
+
+    /// The animation config cinemon reported generating
+    pub fn config_file(&self) -> Option<&Path> {
+        match &self.outcome {
+            Some(StepOutcome::AnimationConfig { file, .. }) => Some(file),
+            _ => None,
+        }
+    }
+
+    /// Combine sequential phases into one result spanning all of them; output
+    /// and exit status are those of the last phase, the outcome that of the last
+    /// phase reporting one
+    pub fn from_phases(phases: Vec<(&str, ProcessResult)>) -> Self {
+        let started_at = phases.first().map(|(_, r)| r.started_at).unwrap_or_default();
+        let outcome = phases.iter().rev().find_map(|(_, r)| r.outcome.clone());
+        let timed: Vec<ProcessPhase> = phases
+            .iter()
+            .map(|(name, r)| ProcessPhase {
+                name: name.to_string(),
+                success: r.success,
+                started_at: r.started_at,
+                finished_at: r.finished_at,
+                duration_ms: r.duration_ms,
+            })
+            .collect();
+
+        let mut result = phases
+            .into_iter()
+            .last()
+            .map(|(_, r)| r)
+            .unwrap_or_else(|| Self::error("No phases ran".to_string()));
+        result.started_at = started_at;
+        result.duration_ms = result.finished_at.saturating_sub(started_at);
+        result.phases = timed;
+        result.outcome = outcome;
+        result
+    }
+}
+
+/// A command a dry run would have started, resolved as it would be spawned
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CommandPreview {
+    pub program: String,
+    pub args: Vec<String>,
+    pub working_dir: Option<PathBuf>,
+    /// Variables set for the command on top of the app's environment; None removes one
+    pub env: Vec<(String, Option<String>)>,
+    /// The whole command, quoted for pasting into a POSIX shell
+    pub command_line: String,
+}
+
+impl CommandPreview {
+    fn from_command(cmd: &AsyncCommand) -> Self {
+        let cmd = cmd.as_std();
+        let text = |value: &std::ffi::OsStr| value.to_string_lossy().to_string();
+        let program = text(cmd.get_program());
+        let args: Vec<String> = cmd.get_args().map(text).collect();
+        let working_dir = cmd.get_current_dir().map(Path::to_path_buf);
+        let env: Vec<(String, Option<String>)> = cmd.get_envs().map(|(key, value)| (text(key), value.map(text))).collect();
+
+        let mut parts = Vec::new();
+        if let Some(dir) = &working_dir {
+            parts.push(format!("cd {} &&", paste_quote(&dir.to_string_lossy())));
+        }
+        parts.extend(env.iter().filter_map(|(key, value)| Some(format!("{}={}", key, paste_quote(value.as_ref()?)))));
+        parts.push(paste_quote(&program));
+        parts.extend(args.iter().map(|arg| paste_quote(arg)));
+        Self { program, args, working_dir, env, command_line: parts.join(" ") }
+    }
+}
+
+/// Quote a word for a shell only when it needs it, so previews stay readable
+fn paste_quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Commands collected by dry-run runners instead of being started, see ProcessRunner::with_dry_run
+#[derive(Debug, Clone, Default)]
+pub struct DryRun(Arc<Mutex<Vec<CommandPreview>>>);
+
+impl DryRun {
+    pub fn commands(&self) -> Vec<CommandPreview> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Why the watchdog killed a process
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProcessTimeout {
+    /// Ran longer than the step's time limit
+    Deadline { seconds: u64 },
+    /// Printed nothing for too long
+    NoOutput { seconds: u64 },
+    /// Wrote nothing to its output directory for too long, see StallDetection
+    Stalled { seconds: u64 },
+}
+
+impl fmt::Display for ProcessTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessTimeout::Deadline { seconds } => write!(f, "exceeded the {} s time limit", seconds),
+            ProcessTimeout::NoOutput { seconds } => write!(f, "no output for {} s", seconds),
+            ProcessTimeout::Stalled { seconds } => write!(f, "{} {} s", STALL_MARKER, seconds),
+        }
+    }
+}
+
+/// Watchdog limits for processes started by a runner; `None` means unlimited
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProcessLimits {
+    pub timeout: Option<Duration>,
+    pub idle_timeout: Option<Duration>,
+}
+
+impl ProcessLimits {
+    fn exceeded(&self, started: Instant, last_output: Instant, now: Instant) -> Option<ProcessTimeout> {
+        if let Some(timeout) = self.timeout {
+            if now.duration_since(started) >= timeout {
+                return Some(ProcessTimeout::Deadline { seconds: timeout.as_secs() });
+            }
+        }
+        if let Some(idle) = self.idle_timeout {
+            if now.duration_since(last_output) >= idle {
+                return Some(ProcessTimeout::NoOutput { seconds: idle.as_secs() });
+            }
+        }
+        None
+    }
+}
+
+/// Per-step time limits in minutes, e.g. "analyze=60,setup_render=30"; 0 disables a limit.
+/// Generate config and blend setup, the parts of setup render, share its limit unless set.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StepTimeouts {
+    pub minutes: HashMap<String, u64>,
+    pub idle_minutes: u64,
+}
+
+impl Default for StepTimeouts {
+    fn default() -> Self {
+        let minutes = [("extract", 60), ("analyze", 60), ("setup_render", 30), ("render", 720), ("upload", 180), ("transcribe", 240)]
+            .into_iter()
+            .map(|(step, minutes)| (step.to_string(), minutes))
+            .collect();
+        Self {
+            minutes,
+            idle_minutes: 30,
+        }
+    }
+}
+
+impl StepTimeouts {
+    /// Defaults overridden by the entries of `spec`
+    pub fn parse(spec: &str, idle_minutes: u64) -> Result<Self, String> {
+        let mut timeouts = Self {
+            idle_minutes,
+            ..Self::default()
+        };
+
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (step, minutes) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid step timeout '{}', expected step=minutes", entry))?;
+            let step: NextStep = step.trim().parse()?;
+            let minutes: u64 = minutes
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid number of minutes in '{}'", entry))?;
+            timeouts.minutes.insert(step.id(), minutes);
+        }
+        Ok(timeouts)
+    }
+
+    /// Limits of `step`; uploads have no idle limit, medusa prints nothing while it sends a file
+    pub fn limits_for(&self, step: &NextStep) -> ProcessLimits {
+        let minutes = |m: u64| (m > 0).then(|| Duration::from_secs(m * 60));
+        let timeout = self.minutes.get(&step.id()).or_else(|| match step {
+            NextStep::GenerateConfig | NextStep::BlendSetup => self.minutes.get(&NextStep::SetupRender.id()),
+            _ => None,
+        });
+        ProcessLimits {
+            timeout: timeout.copied().and_then(minutes),
+            idle_timeout: match step {
+                NextStep::Upload => None,
+                _ => minutes(self.idle_minutes),
+            },
+        }
+    }
 }
 
 pub struct ProcessRunner {
     workspace_root: PathBuf,
     uv_path: String,
+    limits: ProcessLimits,
+    stall_watch: Option<StallWatch>,
+    render_device: Option<RenderDevice>,
+    backend: ExecutionBackend,
+    recording_path: Option<PathBuf>,
+    priority: ProcessPriority,
+    environment: Vec<(String, String)>,
+    toolchain: Toolchain,
+    step_lock: Option<PathBuf>,
+    dry_run: Option<DryRun>,
 }
 
 impl ProcessRunner {
@@ -20,78 +310,184 @@ impl ProcessRunner {
         Self {
             workspace_root,
             uv_path,
+            limits: ProcessLimits::default(),
+            stall_watch: None,
+            render_device: None,
+            backend: ExecutionBackend::Local,
+            recording_path: None,
+            priority: ProcessPriority::default(),
+            environment: Vec::new(),
+            toolchain: Toolchain::Uv,
+            step_lock: None,
+            dry_run: None,
         }
     }
 
+    /// Kill processes that run too long or go quiet
+    pub fn with_limits(mut self, limits: ProcessLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Flag, and unless only notifying kill, processes whose output stops growing
+    pub fn with_stall_watch(mut self, watch: Option<StallWatch>) -> Self {
+        self.stall_watch = watch;
+        self
+    }
+
+    /// Render Blender scenes on this device instead of the one saved in the file
+    pub fn with_render_device(mut self, device: Option<RenderDevice>) -> Self {
+        self.render_device = device;
+        self
+    }
+
+    /// Launch local processes with this niceness, I/O class and CPU cores
+    pub fn with_priority(mut self, priority: ProcessPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Run workspace packages with `toolchain` instead of uv
+    pub fn with_toolchain(mut self, toolchain: Toolchain) -> Self {
+        self.toolchain = toolchain;
+        self
+    }
+
+    /// Set these variables for every process, e.g. from ProcessEnvironment::for_step
+    pub fn with_environment(mut self, environment: Vec<(String, String)>) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Note launched processes in the step lock of `recording_path`, so ones a crash
+    /// leaves running are found on the next start
+    pub fn with_step_lock(mut self, recording_path: &Path) -> Self {
+        self.step_lock = Some(recording_path.to_path_buf());
+        self
+    }
+
+    /// Note commands in `dry_run` instead of starting them; each reports success with no output
+    pub fn with_dry_run(mut self, dry_run: &DryRun) -> Self {
+        self.dry_run = Some(dry_run.clone());
+        self
+    }
+
+    /// Run commands through `backend`; remote backends work on their copy of `recording_path`,
+    /// which run_rsync keeps in sync
+    pub fn with_backend(mut self, backend: ExecutionBackend, recording_path: &Path) -> Self {
+        self.backend = backend;
+        self.recording_path = Some(recording_path.to_path_buf());
+        self
+    }
+
     /// Run beatrix analyze command
-    pub async fn run_beatrix_analyze(&self, recording_path: &Path, audio_file: &str) -> anyhow::Result<ProcessResult> {
+    pub async fn run_beatrix_analyze(&self, recording_path: &Path, audio_file: &str, options: &AnalyzeOptions) -> anyhow::Result<ProcessResult> {
         let audio_path = recording_path.join("extracted").join(audio_file);
         let analysis_dir = recording_path.join("analysis");
 
         log::info!("🎵 Running beatrix analyze: audio={}, output={}", audio_path.display(), analysis_dir.display());
 
-        let mut cmd = AsyncCommand::new(&self.uv_path);
-        cmd.args(&["run", "--package", "beatrix", "beatrix"])
-            .arg(&audio_path)
-            .arg(&analysis_dir)
+        let mut cmd = self.workspace_command(&BEATRIX);
+        cmd.arg(process_arg(&audio_path))
+            .arg(process_arg(&analysis_dir))
+            .args(options.cli_args())
             .current_dir(&self.workspace_root);
 
-        self.execute_command(cmd).await
+        let mut result = self.execute_command(cmd).await?;
+        if result.success {
+            result.outcome = StepOutcome::from_beatrix(&result.stdout, &self.workspace_root);
+        }
+        Ok(result)
     }
 
     /// Generate YAML config and setup Blender project (2-step process)
     pub async fn run_cinemon_render(&self, recording_path: &Path, preset: &str, main_audio: Option<&str>) -> anyhow::Result<ProcessResult> {
         // Step 1: Generate YAML configuration
-        log::info!("🎬 Generating cinemon config: preset={}, main_audio={:?}", preset, main_audio);
-        let config_result = self.run_cinemon_generate_config(recording_path, preset, main_audio).await?;
-
+        let config_result = self.run_cinemon_config(recording_path, preset, main_audio).await?;
         if !config_result.success {
-            log::error!("❌ Config generation failed: {}", config_result.stderr);
-            return Ok(config_result);
+            return Ok(ProcessResult::from_phases(vec![("generate_config", config_result)]));
         }
 
         // Step 2: Setup Blender project with generated config
-        let config_filename = format!("animation_config_{}.yaml", preset);
-        let config_path = recording_path.join(&config_filename);
+        let config_path = config_result
+            .config_file()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| ConfigStore::config_path(recording_path, preset));
+        let setup_result = self.run_cinemon_blend_setup(recording_path, &config_path).await?;
+        log::info!(
+            "⏱️ Setup render took {} ms (config {} ms, blend setup {} ms)",
+            config_result.duration_ms + setup_result.duration_ms,
+            config_result.duration_ms,
+            setup_result.duration_ms
+        );
+        Ok(ProcessResult::from_phases(vec![("generate_config", config_result), ("blend_setup", setup_result)]))
+    }
+
+    /// Generate the config of a preset and keep it in the ConfigStore, out of the recording root
+    pub async fn run_cinemon_config(&self, recording_path: &Path, preset: &str, main_audio: Option<&str>) -> anyhow::Result<ProcessResult> {
+        log::info!("🎬 Generating cinemon config: preset={}, main_audio={:?}", preset, main_audio);
+        let scratch = StepScratch::new(recording_path, &NextStep::GenerateConfig);
+        let mut result = self.run_cinemon_generate_config(recording_path, preset, main_audio, scratch.path()).await?;
+        if !result.success {
+            log::error!("❌ Config generation failed: {}", result.stderr);
+            scratch.finish(false);
+            return Ok(result);
+        }
 
-        if !config_path.exists() {
-            return Ok(ProcessResult {
-                success: false,
-                stdout: String::new(),
-                stderr: format!("Generated config file not found: {}", config_path.display()),
-                exit_code: Some(1),
-            });
+        if self.dry_run.is_some() {
+            return Ok(result);
         }
+        let Some(generated) = ConfigStore::find_generated(recording_path, preset, result.config_file()) else {
+            scratch.finish(false);
+            return Ok(ProcessResult::error(format!("cinemon finished without writing an animation config for preset '{}'", preset)));
+        };
+        match ConfigStore::adopt_file(recording_path, &generated, preset) {
+            Ok((stored, _)) => result.outcome = Some(StepOutcome::AnimationConfig { file: stored, preset: preset.to_string() }),
+            Err(e) => {
+                scratch.finish(false);
+                return Ok(ProcessResult::error(format!("Generated config not stored: {}", e)));
+            }
+        }
+        scratch.finish(true);
+        Ok(result)
+    }
 
+    /// Build the Blender project from an existing animation config
+    pub async fn run_cinemon_blend_setup(&self, recording_path: &Path, config_path: &Path) -> anyhow::Result<ProcessResult> {
         log::info!("🎬 Setting up Blender project with config: {}", config_path.display());
-        let mut cmd = AsyncCommand::new(&self.uv_path);
-        cmd.args(&["run", "--package", "cinemon", "cinemon-blend-setup"])
-            .arg(recording_path)
-            .args(&["--config", &config_path.to_string_lossy()])
+        let mut cmd = self.workspace_command(&CINEMON_BLEND_SETUP);
+        cmd.arg(process_arg(recording_path))
+            .arg("--config")
+            .arg(process_arg(config_path))
             .current_dir(&self.workspace_root);
 
         self.execute_command(cmd).await
     }
 
-    /// Generate cinemon YAML configuration
-    pub async fn run_cinemon_generate_config(&self, recording_path: &Path, preset: &str, main_audio: Option<&str>) -> anyhow::Result<ProcessResult> {
-        let mut cmd = AsyncCommand::new(&self.uv_path);
-        cmd.args(&["run", "--package", "cinemon", "cinemon-generate-config"])
-            .arg(recording_path)
-            .args(&["--preset", preset]);
+    /// Generate cinemon YAML configuration into `output_dir`, e.g. a StepScratch directory
+    pub async fn run_cinemon_generate_config(&self, recording_path: &Path, preset: &str, main_audio: Option<&str>, output_dir: &Path) -> anyhow::Result<ProcessResult> {
+        let mut cmd = self.workspace_command(&CINEMON_GENERATE_CONFIG);
+        cmd.arg(process_arg(recording_path))
+            .args(["--preset", preset])
+            .arg("--output-dir")
+            .arg(process_arg(output_dir));
 
         if let Some(audio_file) = main_audio {
-            cmd.args(&["--main-audio", audio_file]);
+            cmd.args(["--main-audio", audio_file]);
         }
 
         cmd.current_dir(&self.workspace_root);
-        self.execute_command(cmd).await
+        let mut result = self.execute_command(cmd).await?;
+        if result.success {
+            result.outcome = StepOutcome::from_cinemon_config(&result.stdout, &self.workspace_root, preset);
+        }
+        Ok(result)
     }
 
     /// List available cinemon presets
     pub async fn list_cinemon_presets(&self) -> anyhow::Result<ProcessResult> {
-        let mut cmd = AsyncCommand::new(&self.uv_path);
-        cmd.args(&["run", "--package", "cinemon", "cinemon-generate-config", "--list-presets"])
+        let mut cmd = self.workspace_command(&CINEMON_GENERATE_CONFIG);
+        cmd.arg("--list-presets")
             .current_dir(&self.workspace_root);
 
         self.execute_command(cmd).await
@@ -112,26 +508,361 @@ impl ProcessRunner {
     }
 
     /// Run medusa upload command
-    pub async fn run_medusa_upload(&self, video_path: &Path, config_path: &Path) -> anyhow::Result<ProcessResult> {
-        let mut cmd = AsyncCommand::new(&self.uv_path);
-        cmd.args(&["run", "medusa", "upload"])
-            .arg(video_path)
-            .args(&["--config", &config_path.to_string_lossy()])
+    pub async fn run_medusa_upload(&self, video_path: &Path, config_path: &Path, metadata: &UploadMetadata) -> anyhow::Result<ProcessResult> {
+        let mut cmd = self.workspace_command(&MEDUSA);
+        cmd.arg("upload")
+            .arg(process_arg(video_path))
+            .arg("--config")
+            .arg(process_arg(config_path))
+            .args(metadata.medusa_args())
+            .current_dir(&self.workspace_root);
+
+        let mut result = self.execute_command(cmd).await?;
+        if result.success {
+            result.outcome = StepOutcome::from_medusa(&result.stdout);
+        }
+        Ok(result)
+    }
+
+    /// Transcode a video with ffmpeg for an upload target
+    pub async fn run_ffmpeg_transcode(&self, input: &Path, output: &Path, profile: &TranscodeProfile) -> anyhow::Result<ProcessResult> {
+        log::info!("🎞️ Transcoding {} -> {}", input.display(), output.display());
+
+        let mut cmd = AsyncCommand::new("ffmpeg");
+        cmd.args(profile.ffmpeg_args(input, output))
+            .current_dir(&self.workspace_root);
+
+        self.execute_command(cmd).await
+    }
+
+    /// Encode or remux a render into its final video, see FinalizeOptions::ffmpeg_args
+    pub async fn run_ffmpeg_finalize(&self, args: Vec<OsString>) -> anyhow::Result<ProcessResult> {
+        log::info!("🎞️ Finalizing render: ffmpeg {}", args.iter().map(|arg| arg.to_string_lossy()).collect::<Vec<_>>().join(" "));
+
+        let mut cmd = AsyncCommand::new("ffmpeg");
+        cmd.args(args)
+            .current_dir(&self.workspace_root);
+
+        self.execute_command(cmd).await
+    }
+
+    /// Run one of the benchmark's ffmpeg jobs on generated media in `work_dir`
+    pub async fn run_ffmpeg_benchmark(&self, work_dir: &Path, args: Vec<OsString>) -> anyhow::Result<ProcessResult> {
+        let mut cmd = AsyncCommand::new("ffmpeg");
+        cmd.args(args)
+            .current_dir(work_dir);
+
+        self.execute_command(cmd).await
+    }
+
+    /// Burn overlays into a copy of a render, in the directory holding the files the filters read
+    pub async fn run_ffmpeg_post_process(&self, work_dir: &Path, args: Vec<OsString>) -> anyhow::Result<ProcessResult> {
+        log::info!("🔥 Burning overlays into a copy in {}", work_dir.display());
+
+        let mut cmd = AsyncCommand::new("ffmpeg");
+        cmd.args(args)
+            .current_dir(work_dir);
+
+        self.execute_command(cmd).await
+    }
+
+    /// Normalize the loudness of an audio track with ffmpeg's loudnorm filter
+    pub async fn run_ffmpeg_loudnorm(&self, input: &Path, output: &Path, target_lufs: f64) -> anyhow::Result<ProcessResult> {
+        log::info!("🔊 Normalizing {} to {} LUFS -> {}", input.display(), target_lufs, output.display());
+
+        let mut cmd = AsyncCommand::new("ffmpeg");
+        cmd.args(loudnorm_args(input, output, target_lufs))
+            .current_dir(&self.workspace_root);
+
+        self.execute_command(cmd).await
+    }
+
+    /// Remap absolute paths inside a .blend file with Blender in headless mode
+    pub async fn run_blender_remap_paths(&self, blender_path: &str, blend_file: &Path, new_root: &Path, old_roots: &[String]) -> anyhow::Result<ProcessResult> {
+        log::info!("🔧 Remapping paths in {}", blend_file.display());
+
+        let mut cmd = AsyncCommand::new(blender_path);
+        cmd.arg("--background")
+            .arg(process_arg(blend_file))
+            .args(["--python-expr", BLENDER_REMAP_SCRIPT, "--"])
+            .arg(process_arg(new_root))
+            .args(old_roots)
+            .current_dir(&self.workspace_root);
+
+        self.execute_command(cmd).await
+    }
+
+    /// Render the animation of a .blend file in the background, reporting stdout
+    /// lines as they arrive. `frames` limits the render to an inclusive range and
+    /// `settings` overrides the file's output. The frame range is printed first for progress parsing.
+    pub async fn run_blender_render(
+        &self,
+        blender_path: &str,
+        blend_file: &Path,
+        frames: Option<(u32, u32)>,
+        settings: &RenderSettings,
+        on_line: LineCallback,
+    ) -> anyhow::Result<ProcessResult> {
+        log::info!("🎥 Rendering {} (frames {:?})", blend_file.display(), frames);
+
+        let mut cmd = AsyncCommand::new(blender_path);
+        cmd.arg("--background").arg(process_arg(blend_file));
+        // Blender applies arguments in order: the range must precede the script and the render
+        if let Some((start, end)) = frames {
+            cmd.args(["-s", &start.to_string(), "-e", &end.to_string()]);
+        }
+        if let Some(recording_path) = &self.recording_path {
+            for script in self.backend.blender_render_scripts(recording_path) {
+                cmd.arg("--python-expr").arg(script);
+            }
+        }
+        self.add_device_script(&mut cmd);
+        if let Some(script) = settings.blender_script() {
+            cmd.arg("--python-expr").arg(script);
+        }
+        cmd.args(["--python-expr", BLENDER_FRAME_RANGE_SCRIPT])
+            .arg("--render-anim");
+        if let Some(blender_dir) = blend_file.parent() {
+            cmd.current_dir(blender_dir);
+        }
+
+        self.execute_streaming(cmd, Some(on_line)).await
+    }
+
+    /// List the compute devices Cycles can use, see BLENDER_LIST_DEVICES_SCRIPT
+    pub async fn run_blender_list_devices(&self, blender_path: &str) -> anyhow::Result<ProcessResult> {
+        let mut cmd = AsyncCommand::new(blender_path);
+        cmd.arg("--background")
+            .args(["--python-expr", BLENDER_LIST_DEVICES_SCRIPT]);
+
+        self.execute_command(cmd).await
+    }
+
+    /// Render the benchmark's synthetic Cycles scene into `output_dir` on the configured
+    /// device; factory settings keep the scene the same on every machine
+    pub async fn run_blender_benchmark(&self, blender_path: &str, output_dir: &Path) -> anyhow::Result<ProcessResult> {
+        log::info!("⏱️ Rendering the benchmark scene into {}", output_dir.display());
+
+        let mut cmd = AsyncCommand::new(blender_path);
+        cmd.args(["--background", "--factory-startup"])
+            .arg("--python-expr").arg(blender_scene_script());
+        self.add_device_script(&mut cmd);
+        cmd.args(["--python-expr", BLENDER_BENCHMARK_DEVICE_SCRIPT])
+            .arg("-o").arg(output_dir.join("frame_####"))
+            .arg("--render-anim")
+            .current_dir(output_dir);
+
+        self.execute_command(cmd).await
+    }
+
+    fn add_device_script(&self, cmd: &mut AsyncCommand) {
+        if let Some(device) = &self.render_device {
+            cmd.arg("--python-expr").arg(device.blender_script());
+        }
+    }
+
+    /// Print the scene's frame range and frame rate without rendering
+    pub async fn run_blender_frame_range(&self, blender_path: &str, blend_file: &Path) -> anyhow::Result<ProcessResult> {
+        let mut cmd = AsyncCommand::new(blender_path);
+        cmd.arg("--background")
+            .arg(process_arg(blend_file))
+            .args(["--python-expr", BLENDER_FRAME_RANGE_SCRIPT]);
+
+        self.execute_command(cmd).await
+    }
+
+    /// Run a user command (a hook or a custom step) through the shell
+    pub async fn run_shell_command(&self, command_line: &str, env: &[(String, String)], cwd: &Path) -> anyhow::Result<ProcessResult> {
+        log::info!("🐚 Running user command: {}", command_line);
+
+        let mut cmd = if cfg!(windows) {
+            let mut cmd = AsyncCommand::new("cmd");
+            cmd.args(["/C", command_line]);
+            cmd
+        } else {
+            let mut cmd = AsyncCommand::new("sh");
+            cmd.args(["-c", command_line]);
+            cmd
+        };
+        cmd.envs(env.iter().map(|(name, value)| (name, value)))
+            .current_dir(cwd);
+
+        self.execute_command(cmd).await
+    }
+
+    /// Transcribe an audio file with whisper into an SRT file in `output_dir`.
+    /// `whisper_path` is the whisper CLI, or "uv" to run the `whisper` workspace package.
+    pub async fn run_whisper_transcribe(&self, whisper_path: &str, audio: &Path, output_dir: &Path) -> anyhow::Result<ProcessResult> {
+        log::info!("📝 Transcribing {} -> {}", audio.display(), output_dir.display());
+
+        let mut cmd = AsyncCommand::new(whisper_path);
+        if whisper_path == self.uv_path {
+            cmd.args(["run", "whisper"]);
+        }
+        cmd.arg(process_arg(audio))
+            .args(["--output_format", "srt", "--output_dir"])
+            .arg(process_arg(output_dir))
             .current_dir(&self.workspace_root);
 
         self.execute_command(cmd).await
     }
 
-    /// Execute a command and capture output
-    async fn execute_command(&self, mut cmd: AsyncCommand) -> anyhow::Result<ProcessResult> {
+    /// Print the external files a .blend project uses, see BLENDER_LIST_MEDIA_SCRIPT
+    pub async fn run_blender_list_media(&self, blender_path: &str, blend_file: &Path) -> anyhow::Result<ProcessResult> {
+        let mut cmd = AsyncCommand::new(blender_path);
+        cmd.arg("--background")
+            .arg(process_arg(blend_file))
+            .args(["--python-expr", BLENDER_LIST_MEDIA_SCRIPT]);
+
+        self.execute_command(cmd).await
+    }
+
+    /// Render a cheap preview of the animation into `output_dir`
+    pub async fn run_blender_preview(&self, blender_path: &str, blend_file: &Path, options: &PreviewOptions, output_dir: &Path) -> anyhow::Result<ProcessResult> {
+        log::info!("🎥 Rendering preview of {} ({:?})", blend_file.display(), options);
+
+        let mut cmd = AsyncCommand::new(blender_path);
+        cmd.arg("--background")
+            .arg(process_arg(blend_file));
+        self.add_device_script(&mut cmd);
+        cmd.args(options.blender_args(output_dir))
+            .arg("--render-anim");
+        if let Some(blender_dir) = blend_file.parent() {
+            cmd.current_dir(blender_dir);
+        }
+
+        self.execute_command(cmd).await
+    }
+
+    /// Encode a rendered frame sequence into a video with ffmpeg
+    pub async fn run_ffmpeg_encode_frames(&self, sequence: &FrameSequence, start: u32, fps: f64, audio: Option<&Path>, output: &Path) -> anyhow::Result<ProcessResult> {
+        log::info!("🎞️ Encoding {} -> {}", sequence.ffmpeg_pattern().display(), output.display());
+
+        let mut cmd = AsyncCommand::new("ffmpeg");
+        cmd.args(sequence.ffmpeg_encode_args(start, fps, audio, output))
+            .current_dir(&self.workspace_root);
+
+        self.execute_command(cmd).await
+    }
+
+    /// Execute a command and capture output, killing it when it exceeds the runner's limits
+    async fn execute_command(&self, cmd: AsyncCommand) -> anyhow::Result<ProcessResult> {
+        self.execute_streaming(cmd, None).await
+    }
+
+    /// Like execute_command, additionally passing stdout lines to `on_line` as they arrive
+    async fn execute_streaming(&self, mut cmd: AsyncCommand, on_line: Option<LineCallback>) -> anyhow::Result<ProcessResult> {
+        cmd.envs(self.environment.iter().cloned());
+        // Priority is about this machine; a remote host schedules its own processes
+        let cmd = if self.backend.is_remote() {
+            self.backend.wrap(cmd, self.recording_path.as_deref())
+        } else {
+            self.priority.apply(cmd)
+        };
+        self.execute_here(cmd, on_line).await
+    }
+
+    /// Run rsync on this machine, whatever the backend, e.g. with ExecutionBackend::push_args
+    pub async fn run_rsync(&self, args: &[String]) -> anyhow::Result<ProcessResult> {
+        let mut cmd = AsyncCommand::new("rsync");
+        cmd.args(args);
+
+        self.execute_here(cmd, None).await
+    }
+
+    async fn execute_here(&self, mut cmd: AsyncCommand, on_line: Option<LineCallback>) -> anyhow::Result<ProcessResult> {
+        if let Some(dry_run) = &self.dry_run {
+            dry_run.0.lock().unwrap().push(CommandPreview::from_command(&cmd));
+            return Ok(ProcessResult::dry_run());
+        }
         log::info!("Executing command: {:?}", cmd);
 
-        let output = cmd.output().await?;
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).kill_on_drop(true);
+        // Its own group, so suspend_processes reaches the tools it starts
+        #[cfg(unix)]
+        cmd.process_group(0);
+        let mut child = cmd.spawn()?;
+        let child_pid = child.id();
+        if let Some(pid) = child_pid {
+            track_process(pid);
+        }
+        if let (Some(recording_path), Some(pid)) = (&self.step_lock, child_pid) {
+            record_child_pid(recording_path, pid);
+        }
+
+        let last_output = Arc::new(Mutex::new(Instant::now()));
+        let stdout_buffer = Arc::new(Mutex::new(Vec::new()));
+        let stderr_buffer = Arc::new(Mutex::new(Vec::new()));
+        let stdout_reader = tokio::spawn(read_stream(child.stdout.take(), stdout_buffer.clone(), last_output.clone(), on_line));
+        let stderr_reader = tokio::spawn(read_stream(child.stderr.take(), stderr_buffer.clone(), last_output.clone(), None));
+
+        let started_at = SystemTime::now();
+        let started = Instant::now();
+        // Time spent suspended doesn't count towards the limits
+        let mut limits_started = started;
+        let mut suspended_since = None;
+        let mut timeout = None;
+        let mut stall_tracker = self.stall_watch.as_ref().map(|watch| StallTracker::new(watch, started));
+        let status = loop {
+            tokio::select! {
+                status = child.wait() => break Some(status),
+                _ = tokio::time::sleep(WATCHDOG_TICK) => {
+                    let now = Instant::now();
+                    if processes_suspended() {
+                        suspended_since.get_or_insert(now);
+                        continue;
+                    }
+                    if let Some(since) = suspended_since.take() {
+                        limits_started += now.duration_since(since);
+                        *last_output.lock().unwrap() = now;
+                        if let Some(tracker) = &mut stall_tracker {
+                            tracker.resume(now);
+                        }
+                    }
+                    let last = *last_output.lock().unwrap();
+                    timeout = self.limits.exceeded(limits_started, last, now);
+                    if let (Some(watch), Some(tracker), None) = (&self.stall_watch, &mut stall_tracker, &timeout) {
+                        if let Some(seconds) = tracker.check(watch, now) {
+                            log::warn!("🐢 No new output in {:?} for {} s", watch.outputs, seconds);
+                            if let Some(on_stall) = &watch.on_stall {
+                                on_stall(&watch.step, seconds);
+                            }
+                            timeout = watch.kill.then_some(ProcessTimeout::Stalled { seconds });
+                        }
+                    }
+                    if let Some(timeout) = &timeout {
+                        log::error!("⏱️ Killing process: {}", timeout);
+                        kill_process_tree(&mut child, child_pid).await;
+                        break None;
+                    }
+                }
+            }
+        };
+        if let Some(pid) = child_pid {
+            untrack_process(pid);
+        }
+        if let (Some(recording_path), Some(pid)) = (&self.step_lock, child_pid) {
+            forget_child_pid(recording_path, pid);
+        }
+        let status = status.transpose()?;
+
+        // Grandchildren may keep the pipes open after a kill, so don't wait for EOF forever
+        let _ = tokio::time::timeout(KILL_GRACE, async {
+            let _ = stdout_reader.await;
+            let _ = stderr_reader.await;
+        })
+        .await;
 
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        let success = output.status.success();
-        let exit_code = output.status.code();
+        let stdout = String::from_utf8_lossy(&stdout_buffer.lock().unwrap()).to_string();
+        let mut stderr = String::from_utf8_lossy(&stderr_buffer.lock().unwrap()).to_string();
+        let success = status.is_some_and(|s| s.success());
+        let exit_code = status.and_then(|s| s.code());
+        if let Some(timeout) = &timeout {
+            if !stderr.is_empty() && !stderr.ends_with('\n') {
+                stderr.push('\n');
+            }
+            stderr.push_str(&format!("Process killed by watchdog: {}\n", timeout));
+        }
 
         log::info!("Command finished - success: {}, exit_code: {:?}", success, exit_code);
         if !stdout.is_empty() {
@@ -146,34 +877,143 @@ impl ProcessRunner {
             stdout,
             stderr,
             exit_code,
+            timeout,
+            started_at: unix_millis(started_at),
+            finished_at: unix_millis(SystemTime::now()),
+            duration_ms: started.elapsed().as_millis() as u64,
+            phases: Vec::new(),
+            outcome: None,
         })
     }
 
+    fn workspace_command(&self, entry: &EntryPoint) -> AsyncCommand {
+        self.toolchain.command(&self.uv_path, entry)
+    }
+
     /// Check if required CLI tools are available
     pub async fn validate_cli_tools(&self) -> anyhow::Result<()> {
-        // Check if uv is available
-        let mut cmd = AsyncCommand::new(&self.uv_path);
-        cmd.arg("--version");
+        self.toolchain_version().await.map_err(|e| anyhow::anyhow!(e))?;
+        for package in WORKSPACE_PACKAGES {
+            self.check_package(package).await.map_err(|e| anyhow::anyhow!(e))?;
+        }
+        Ok(())
+    }
+
+    /// Version reported by the toolchain's program, e.g. uv
+    pub async fn toolchain_version(&self) -> Result<String, String> {
+        let program = self.toolchain.program(&self.uv_path);
+        let mut cmd = AsyncCommand::new(program);
+        cmd.arg("--version").envs(self.environment.iter().cloned());
 
-        let output = cmd.output().await?;
-        if !output.status.success() {
-            return Err(anyhow::anyhow!("UV tool not found at: {}", self.uv_path));
+        match cmd.output().await {
+            // Python before 3.4 printed its version to stderr
+            Ok(output) if output.status.success() => Ok(first_line(if output.stdout.is_empty() { &output.stderr } else { &output.stdout })),
+            _ => Err(format!("{} not found at: {}", self.toolchain.label(), program)),
         }
+    }
+
+    /// Check that a workspace package can be run through the toolchain
+    pub async fn check_package(&self, package: &str) -> Result<(), String> {
+        let entry = PACKAGE_CHECK_ENTRY_POINTS
+            .iter()
+            .find(|entry| entry.package == package)
+            .ok_or_else(|| format!("Unknown workspace package '{}'", package))?;
+        let mut cmd = self.toolchain.check_command(&self.uv_path, entry);
+        cmd.envs(self.environment.iter().cloned())
+            .current_dir(&self.workspace_root);
+
+        match cmd.output().await {
+            Ok(output) if output.status.success() => Ok(()),
+            Ok(output) => Err(format!(
+                "Package '{}' not available in workspace: {}",
+                package,
+                first_line(&output.stderr)
+            )),
+            Err(e) => Err(format!("Package '{}' not available in workspace: {}", package, e)),
+        }
+    }
+}
 
-        // Check if workspace packages are available
-        let packages = ["beatrix", "cinemon", "medusa"];
-        for package in packages {
-            let mut cmd = AsyncCommand::new(&self.uv_path);
-            cmd.args(&["run", "--package", package, "--help"])
-                .current_dir(&self.workspace_root);
+/// Version line printed by an external tool, e.g. `ffmpeg -version`
+pub async fn tool_version(program: &str, version_arg: &str) -> Result<String, String> {
+    let output = AsyncCommand::new(program)
+        .arg(version_arg)
+        .output()
+        .await
+        .map_err(|e| format!("{} not found: {}", program, e))?;
+    if !output.status.success() {
+        return Err(format!("{} {} exited with {:?}", program, version_arg, output.status.code()));
+    }
+    Ok(first_line(&output.stdout))
+}
 
-            let output = cmd.output().await?;
-            if !output.status.success() {
-                return Err(anyhow::anyhow!("Package '{}' not available in workspace", package));
+/// SIGTERM the child's process group, so Blender, ffmpeg or medusa started by uv stop
+/// with it, then SIGKILL whatever of the group is left after KILL_GRACE
+async fn kill_process_tree(child: &mut Child, pid: Option<u32>) {
+    #[cfg(unix)]
+    if let Some(pid) = pid {
+        if let Err(e) = signal_group(pid, "TERM") {
+            log::warn!("{}", e);
+        }
+        let _ = tokio::time::timeout(KILL_GRACE, child.wait()).await;
+        // Signal 0 only checks whether any process of the group is still alive
+        if signal_group(pid, "0").is_ok() {
+            if let Err(e) = signal_group(pid, "KILL") {
+                log::warn!("{}", e);
             }
         }
+    }
+    if let Err(e) = child.kill().await {
+        log::warn!("Failed to kill process: {}", e);
+    }
+}
 
-        Ok(())
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+fn first_line(output: &[u8]) -> String {
+    String::from_utf8_lossy(output).lines().next().unwrap_or("").trim().to_string()
+}
+
+/// Collect a pipe into `buffer`, recording when output last arrived and
+/// passing complete lines to `on_line`
+async fn read_stream<R: AsyncRead + Unpin>(
+    stream: Option<R>,
+    buffer: Arc<Mutex<Vec<u8>>>,
+    last_output: Arc<Mutex<Instant>>,
+    on_line: Option<LineCallback>,
+) {
+    let Some(mut stream) = stream else {
+        return;
+    };
+    let mut chunk = [0u8; 8192];
+    let mut pending_line = Vec::new();
+    loop {
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                buffer.lock().unwrap().extend_from_slice(&chunk[..n]);
+                *last_output.lock().unwrap() = Instant::now();
+
+                if let Some(on_line) = &on_line {
+                    pending_line.extend_from_slice(&chunk[..n]);
+                    // Blender rewrites progress with \r on a terminal, so split on both
+                    while let Some(end) = pending_line.iter().position(|b| *b == b'\n' || *b == b'\r') {
+                        let line: Vec<u8> = pending_line.drain(..=end).collect();
+                        let line = String::from_utf8_lossy(&line[..end]);
+                        if !line.is_empty() {
+                            on_line(&line);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if let Some(on_line) = &on_line {
+        if !pending_line.is_empty() {
+            on_line(&String::from_utf8_lossy(&pending_line));
+        }
     }
 }
 
@@ -220,7 +1060,7 @@ mod tests {
     async fn test_execute_command_failure() {
         let (runner, _temp_dir) = create_test_runner();
 
-        let mut cmd = AsyncCommand::new("false"); // Command that always fails
+        let cmd = AsyncCommand::new("false"); // Command that always fails
 
         let result = runner.execute_command(cmd).await.unwrap();
 
@@ -228,6 +1068,166 @@ mod tests {
         assert_eq!(result.exit_code, Some(1));
     }
 
+    #[tokio::test]
+    async fn test_execute_command_timing() {
+        let (runner, _temp_dir) = create_test_runner();
+
+        let mut cmd = AsyncCommand::new("sleep");
+        cmd.arg("0.2");
+
+        let result = runner.execute_command(cmd).await.unwrap();
+
+        assert!(result.duration_ms >= 200);
+        assert!(result.finished_at >= result.started_at + 200);
+        assert!(result.phases.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_execute_streaming_reports_lines() {
+        let (runner, _temp_dir) = create_test_runner();
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let collected = lines.clone();
+
+        let mut cmd = AsyncCommand::new("printf");
+        cmd.arg("Fra:1\\nFra:2\\rFra:3");
+        let on_line: LineCallback = Arc::new(move |line| collected.lock().unwrap().push(line.to_string()));
+        let result = runner.execute_streaming(cmd, Some(on_line)).await.unwrap();
+
+        assert!(result.success);
+        assert_eq!(*lines.lock().unwrap(), vec!["Fra:1", "Fra:2", "Fra:3"]);
+    }
+
+    #[test]
+    fn test_from_phases_spans_all_phases() {
+        let phase = |success: bool, started_at: u64, duration_ms: u64| ProcessResult {
+            started_at,
+            finished_at: started_at + duration_ms,
+            duration_ms,
+            success,
+            ..ProcessResult::error(String::new())
+        };
+
+        let result = ProcessResult::from_phases(vec![
+            ("generate_config", phase(true, 1_000, 300)),
+            ("blend_setup", phase(false, 1_400, 2_000)),
+        ]);
+
+        assert!(!result.success);
+        assert_eq!(result.started_at, 1_000);
+        assert_eq!(result.finished_at, 3_400);
+        assert_eq!(result.duration_ms, 2_400);
+        let names: Vec<&str> = result.phases.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["generate_config", "blend_setup"]);
+        assert_eq!(result.phases[1].duration_ms, 2_000);
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_timeout() {
+        let (runner, _temp_dir) = create_test_runner();
+        let runner = runner.with_limits(ProcessLimits {
+            timeout: Some(Duration::from_millis(500)),
+            idle_timeout: None,
+        });
+
+        let mut cmd = AsyncCommand::new("sleep");
+        cmd.arg("10");
+
+        let started = Instant::now();
+        let result = runner.execute_command(cmd).await.unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(!result.success);
+        assert_eq!(result.timeout, Some(ProcessTimeout::Deadline { seconds: 0 }));
+        assert!(result.stderr.contains("killed by watchdog"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_watchdog_kills_grandchildren() {
+        let (runner, _temp_dir) = create_test_runner();
+        let runner = runner.with_limits(ProcessLimits {
+            timeout: Some(Duration::from_millis(500)),
+            idle_timeout: None,
+        });
+
+        // Like uv starting Blender: the grandchild must not outlive the killed wrapper
+        let mut cmd = AsyncCommand::new("sh");
+        cmd.args(["-c", "sleep 30 & echo $!; wait"]);
+        let result = runner.execute_command(cmd).await.unwrap();
+        assert!(matches!(result.timeout, Some(ProcessTimeout::Deadline { .. })));
+
+        let grandchild: u32 = result.stdout.trim().parse().unwrap();
+        let dead = || {
+            std::fs::read_to_string(format!("/proc/{}/stat", grandchild))
+                .map_or(true, |stat| stat.rsplit_once(") ").is_some_and(|(_, rest)| rest.starts_with('Z')))
+        };
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while !dead() && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert!(dead());
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_idle_watchdog() {
+        let (runner, _temp_dir) = create_test_runner();
+        let runner = runner.with_limits(ProcessLimits {
+            timeout: None,
+            idle_timeout: Some(Duration::from_secs(1)),
+        });
+
+        let mut cmd = AsyncCommand::new("sh");
+        cmd.args(["-c", "echo started; exec sleep 10"]);
+
+        let result = runner.execute_command(cmd).await.unwrap();
+
+        assert_eq!(result.timeout, Some(ProcessTimeout::NoOutput { seconds: 1 }));
+        assert_eq!(result.stdout.trim(), "started");
+    }
+
+    #[test]
+    fn test_every_step_has_default_limits() {
+        let defaults = StepTimeouts::default();
+        // Retry resolves to the failed step before anything runs
+        let steps = [
+            NextStep::Extract,
+            NextStep::Analyze,
+            NextStep::SetupRender,
+            NextStep::GenerateConfig,
+            NextStep::BlendSetup,
+            NextStep::Render,
+            NextStep::Upload,
+            NextStep::Transcribe,
+        ];
+        for step in steps {
+            assert!(defaults.limits_for(&step).timeout.is_some(), "{} has no time limit", step);
+        }
+    }
+
+    #[test]
+    fn test_step_timeouts_parse() {
+        let timeouts = StepTimeouts::parse("analyze=5, setuprender=0", 0).unwrap();
+
+        let analyze = timeouts.limits_for(&NextStep::Analyze);
+        assert_eq!(analyze.timeout, Some(Duration::from_secs(300)));
+        assert_eq!(analyze.idle_timeout, None);
+        assert_eq!(timeouts.limits_for(&NextStep::SetupRender).timeout, None);
+        assert_eq!(timeouts.limits_for(&NextStep::Upload).timeout, Some(Duration::from_secs(180 * 60)));
+
+        let defaults = StepTimeouts::default();
+        assert_eq!(defaults.limits_for(&NextStep::Render).idle_timeout, Some(Duration::from_secs(30 * 60)));
+        assert_eq!(defaults.limits_for(&NextStep::Upload).idle_timeout, None);
+
+        // The setup substeps follow setup render unless given their own limit
+        assert_eq!(timeouts.limits_for(&NextStep::BlendSetup).timeout, None);
+        let timeouts = StepTimeouts::parse("setup_render=45, generate_config=10", 30).unwrap();
+        assert_eq!(timeouts.limits_for(&NextStep::BlendSetup).timeout, Some(Duration::from_secs(45 * 60)));
+        assert_eq!(timeouts.limits_for(&NextStep::GenerateConfig).timeout, Some(Duration::from_secs(10 * 60)));
+
+        assert!(StepTimeouts::parse("analyze", 30).is_err());
+        assert!(StepTimeouts::parse("analyze=soon", 30).is_err());
+    }
+
     #[tokio::test]
     async fn test_beatrix_analyze_command_structure() {
         let (runner, temp_dir) = create_test_runner();
@@ -241,12 +1241,51 @@ mod tests {
         fs::write(extracted_dir.join(audio_file), "test audio").unwrap();
 
         // This will fail because we're using echo instead of uv, but we can test the structure
-        let result = runner.run_beatrix_analyze(&recording_path, audio_file).await;
+        let result = runner.run_beatrix_analyze(&recording_path, audio_file, &AnalyzeOptions::default()).await;
 
         // Should not panic and should return some result
         assert!(result.is_ok());
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_paths_with_spaces_stay_one_argument() {
+        let (runner, temp_dir) = create_test_runner();
+        let recording_path = temp_dir.path().join("Nagranie z próby");
+        fs::create_dir_all(recording_path.join("extracted")).unwrap();
+
+        let result = runner.run_beatrix_analyze(&recording_path, "Mikrofon wejście.m4a", &AnalyzeOptions::default()).await.unwrap();
+
+        let audio_path = recording_path.join("extracted").join("Mikrofon wejście.m4a");
+        assert!(result.stdout.contains(&format!(" {} ", audio_path.display())));
+    }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn test_windows_paths_with_spaces() {
+        let (runner, temp_dir) = create_test_runner();
+        let recording_path = temp_dir.path().join("Nagranie z próby");
+        fs::create_dir_all(&recording_path).unwrap();
+        fs::write(recording_path.join("Mikrofon wejście.m4a"), "").unwrap();
+
+        let mut cmd = AsyncCommand::new("cmd");
+        cmd.args(["/C", "dir", "/B"]).arg(process_arg(&recording_path));
+        let result = runner.execute_command(cmd).await.unwrap();
+
+        assert!(result.success, "{}", result.stderr);
+        assert!(result.stdout.contains("Mikrofon"));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_long_and_unc_paths() {
+        let long = PathBuf::from(r"C:\Nagrania").join("koncert ".repeat(40)).join("render.mp4");
+        assert!(process_arg(&long).to_string_lossy().starts_with(r"\\?\C:\Nagrania\koncert "));
+        let unc = PathBuf::from(r"\\nas\obs").join("koncert ".repeat(40));
+        assert!(process_arg(&unc).to_string_lossy().starts_with(r"\\?\UNC\nas\obs\koncert "));
+        assert_eq!(process_arg(Path::new(r"\\nas\obs\a.mkv")), r"\\nas\obs\a.mkv");
+    }
+
     #[tokio::test]
     async fn test_cinemon_render_command_structure() {
         let (runner, temp_dir) = create_test_runner();
@@ -254,7 +1293,7 @@ mod tests {
         let recording_path = temp_dir.path().join("test_recording");
         fs::create_dir_all(&recording_path).unwrap();
 
-        let result = runner.run_cinemon_render(&recording_path, "beat-switch").await;
+        let result = runner.run_cinemon_render(&recording_path, "beat-switch", None).await;
 
         // Should not panic and should return some result
         assert!(result.is_ok());
@@ -270,7 +1309,7 @@ mod tests {
         fs::write(&video_path, "test video").unwrap();
         fs::write(&config_path, "{}").unwrap();
 
-        let result = runner.run_medusa_upload(&video_path, &config_path).await;
+        let result = runner.run_medusa_upload(&video_path, &config_path, &UploadMetadata::default()).await;
 
         // Should not panic and should return some result
         assert!(result.is_ok());
@@ -0,0 +1,162 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Local processes the pipeline runs right now, each leading its own process group
+static RUNNING: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+/// Set between suspend_processes and resume_processes
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+/// Remember a started process; one started while suspended is stopped right away
+pub fn track_process(pid: u32) {
+    RUNNING.lock().unwrap().push(pid);
+    if processes_suspended() {
+        if let Err(e) = signal_group(pid, "STOP") {
+            log::warn!("{}", e);
+        }
+    }
+}
+
+pub fn untrack_process(pid: u32) {
+    RUNNING.lock().unwrap().retain(|p| *p != pid);
+}
+
+pub fn processes_suspended() -> bool {
+    SUSPENDED.load(Ordering::Relaxed)
+}
+
+/// Stop the running processes where they are, e.g. a render keeps its progress.
+/// Returns how many were stopped.
+pub fn suspend_processes() -> Result<usize, String> {
+    if !cfg!(unix) {
+        return Err("Suspending running processes is only supported on Unix".to_string());
+    }
+    SUSPENDED.store(true, Ordering::Relaxed);
+    Ok(signal_all("STOP"))
+}
+
+/// Let suspended processes continue; returns how many were continued
+pub fn resume_processes() -> usize {
+    if !SUSPENDED.swap(false, Ordering::Relaxed) {
+        return 0;
+    }
+    signal_all("CONT")
+}
+
+/// Ask the running processes to exit with SIGTERM and kill the ones still alive after
+/// `grace`; blocks until then. Returns how many were running.
+pub fn terminate_processes(grace: Duration) -> usize {
+    let pids = RUNNING.lock().unwrap().clone();
+    if pids.is_empty() || !cfg!(unix) {
+        return 0;
+    }
+    let terminated = signal_all("TERM");
+    // Stopped processes only act on the signal once continued
+    if SUSPENDED.swap(false, Ordering::Relaxed) {
+        signal_all("CONT");
+    }
+
+    let deadline = Instant::now() + grace;
+    // The runner forgets a process once it was reaped; an unreaped one still answers signal 0
+    let alive = |pid: &u32| RUNNING.lock().unwrap().contains(pid) && signal_group(*pid, "0").is_ok();
+    while pids.iter().any(alive) && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    for pid in pids.iter().filter(|pid| alive(pid)) {
+        log::warn!("Process {} ignored SIGTERM for {:?}, killing it", pid, grace);
+        if let Err(e) = signal_group(*pid, "KILL") {
+            log::warn!("{}", e);
+        }
+    }
+    RUNNING.lock().unwrap().clear();
+    terminated
+}
+
+fn signal_all(signal: &str) -> usize {
+    let pids = RUNNING.lock().unwrap().clone();
+    pids.into_iter()
+        .filter(|pid| match signal_group(*pid, signal) {
+            Ok(()) => true,
+            // The process may have exited in the meantime
+            Err(e) => {
+                log::warn!("{}", e);
+                false
+            }
+        })
+        .count()
+}
+
+/// Signal the whole group, so the tools uv or a shell started stop too
+pub fn signal_group(pid: u32, signal: &str) -> Result<(), String> {
+    #[cfg(unix)]
+    {
+        let output = std::process::Command::new("kill")
+            .args([format!("-{}", signal), "--".to_string(), format!("-{}", pid)])
+            .output()
+            .map_err(|e| format!("Failed to send SIG{} to {}: {}", signal, pid, e))?;
+        if !output.status.success() {
+            return Err(format!("Failed to send SIG{} to {}: {}", signal, pid, String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    {
+        Err(format!("Cannot send SIG{} to {} on this platform", signal, pid))
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::CommandExt;
+
+    // The tests signal every tracked process, so they must not overlap
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    fn state(pid: u32) -> char {
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).unwrap();
+        stat.rsplit_once(") ").unwrap().1.chars().next().unwrap()
+    }
+
+    #[test]
+    fn test_suspend_and_resume_process_group() {
+        let _serial = SERIAL.lock().unwrap();
+        let mut child = std::process::Command::new("sleep").arg("30").process_group(0).spawn().unwrap();
+        let pid = child.id();
+        track_process(pid);
+
+        assert!(suspend_processes().unwrap() >= 1);
+        assert!(processes_suspended());
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert_eq!(state(pid), 'T');
+
+        assert!(resume_processes() >= 1);
+        assert!(!processes_suspended());
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert_ne!(state(pid), 'T');
+
+        untrack_process(pid);
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+
+    #[test]
+    fn test_terminate_kills_processes_ignoring_sigterm() {
+        let _serial = SERIAL.lock().unwrap();
+        let mut child = std::process::Command::new("sh")
+            .args(["-c", "trap '' TERM; sleep 30"])
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        track_process(pid);
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        let started = Instant::now();
+        assert!(terminate_processes(Duration::from_millis(300)) >= 1);
+        assert!(started.elapsed() >= Duration::from_millis(300));
+        assert!(child.wait().unwrap().code().is_none());
+    }
+}
@@ -0,0 +1,73 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Quarantine directory inside the recordings root; hidden so the scanner skips it
+pub const QUARANTINE_DIR: &str = ".quarantine";
+
+/// A recording moved aside because its files can't be read
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct QuarantineEntry {
+    /// Directory name inside the quarantine: `<timestamp>_<name>`
+    pub id: String,
+    pub name: String,
+    pub quarantined_at: u64, // Unix timestamp in seconds
+    pub path: PathBuf,
+    /// Why it was quarantined, e.g. the corruption found by the scan
+    pub reason: Option<String>,
+}
+
+/// Move a recording directory into the quarantine of its root, out of the way of
+/// scans and the pipeline but kept for inspection or recovery
+pub fn quarantine_recording(recordings_path: &Path, recording_name: &str, reason: Option<String>) -> Result<QuarantineEntry, String> {
+    let source = recordings_path.join(recording_name);
+    if !source.is_dir() {
+        return Err(format!("Recording '{}' not found", recording_name));
+    }
+    let quarantine_path = recordings_path.join(QUARANTINE_DIR);
+    fs::create_dir_all(&quarantine_path).map_err(|e| format!("Failed to create quarantine directory: {}", e))?;
+
+    let quarantined_at = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let id = format!("{}_{}", quarantined_at, recording_name);
+    let target = quarantine_path.join(&id);
+    if target.exists() {
+        return Err(format!("Quarantine entry '{}' already exists", id));
+    }
+
+    fs::rename(&source, &target)
+        .map_err(|e| format!("Failed to move recording '{}' to quarantine: {}", recording_name, e))?;
+
+    log::warn!(
+        "☣️ Quarantined recording '{}' as '{}': {}",
+        recording_name,
+        id,
+        reason.as_deref().unwrap_or("no reason given")
+    );
+    Ok(QuarantineEntry { id, name: recording_name.to_string(), quarantined_at, path: target, reason })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::FileScanner;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_quarantined_recording_leaves_the_scan() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("gig")).unwrap();
+        fs::write(root.join("gig/gig.mkv"), "").unwrap();
+        assert_eq!(FileScanner::scan_recordings(root).len(), 1);
+
+        let entry = quarantine_recording(root, "gig", Some("gig.mkv is empty".to_string())).unwrap();
+        assert!(entry.path.join("gig.mkv").exists());
+        assert!(entry.path.starts_with(root.join(QUARANTINE_DIR)));
+        assert!(FileScanner::scan_recordings(root).is_empty());
+        assert!(quarantine_recording(root, "gig", None).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+
+/// Settings key holding recently used and pinned recordings
+pub const RECENT_RECORDINGS_KEY: &str = "recent_recordings";
+/// Unpinned recordings remembered; older ones are forgotten
+pub const RECENT_RECORDINGS_MAX_ENTRIES: usize = 20;
+
+/// A recording that was opened or operated on, or pinned
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecentRecording {
+    pub name: String,
+    pub used_at: Option<u64>, // Unix timestamp in seconds, None when pinned but never used
+    pub pinned: bool,
+}
+
+/// Recently used recordings, most recent first
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct RecentRecordings(Vec<RecentRecording>);
+
+impl RecentRecordings {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(RECENT_RECORDINGS_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        settings.set(RECENT_RECORDINGS_KEY, self)
+    }
+
+    /// Pinned recordings first, then the rest; most recently used first within each
+    pub fn list(&self) -> Vec<RecentRecording> {
+        let mut list = self.0.clone();
+        list.sort_by(|a, b| b.pinned.cmp(&a.pinned).then_with(|| b.used_at.cmp(&a.used_at)));
+        list
+    }
+
+    /// Note that a recording was just used
+    pub fn touch(&mut self, recording_name: &str, now: u64) {
+        let pinned = self.remove(recording_name).is_some_and(|entry| entry.pinned);
+        self.0.insert(0, RecentRecording { name: recording_name.to_string(), used_at: Some(now), pinned });
+        let mut unpinned = 0;
+        self.0.retain(|entry| {
+            unpinned += usize::from(!entry.pinned);
+            entry.pinned || unpinned <= RECENT_RECORDINGS_MAX_ENTRIES
+        });
+    }
+
+    /// Returns false when the recording was already in that state
+    pub fn set_pinned(&mut self, recording_name: &str, pinned: bool) -> bool {
+        match self.0.iter_mut().find(|entry| entry.name == recording_name) {
+            Some(entry) if entry.pinned == pinned => false,
+            Some(entry) => {
+                entry.pinned = pinned;
+                true
+            }
+            None if pinned => {
+                self.0.push(RecentRecording { name: recording_name.to_string(), used_at: None, pinned });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Keep a recording's history when it is renamed; returns whether it had one
+    pub fn rename_recording(&mut self, old_name: &str, new_name: &str) -> bool {
+        let mut changed = false;
+        for entry in self.0.iter_mut().filter(|entry| entry.name == old_name) {
+            entry.name = new_name.to_string();
+            changed = true;
+        }
+        changed
+    }
+
+    pub fn remove(&mut self, recording_name: &str) -> Option<RecentRecording> {
+        let index = self.0.iter().position(|entry| entry.name == recording_name)?;
+        Some(self.0.remove(index))
+    }
+
+    /// Touch a recording in the stored list; failures are only logged since tracking
+    /// must never get in the way of the operation itself
+    pub fn record_use(settings: &SettingsStore, recording_name: &str) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let result = Self::load(settings).and_then(|mut recent| {
+            recent.touch(recording_name, now);
+            recent.save(settings)
+        });
+        if let Err(e) = result {
+            log::warn!("Failed to remember '{}' as recently used: {}", recording_name, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn names(recent: &RecentRecordings) -> Vec<String> {
+        recent.list().into_iter().map(|entry| entry.name).collect()
+    }
+
+    #[test]
+    fn test_pinned_first_and_kept_past_the_limit() {
+        let mut recent = RecentRecordings::default();
+        assert!(recent.set_pinned("old", true));
+        assert!(!recent.set_pinned("old", true));
+        for n in 0..RECENT_RECORDINGS_MAX_ENTRIES + 5 {
+            recent.touch(&n.to_string(), n as u64);
+        }
+        recent.touch("3", 100);
+
+        let list = names(&recent);
+        assert_eq!(list.len(), RECENT_RECORDINGS_MAX_ENTRIES + 1);
+        assert_eq!(&list[..3], ["old", "3", "24"]);
+        assert!(!list.contains(&"2".to_string()));
+
+        assert!(recent.set_pinned("old", false));
+        assert!(recent.rename_recording("3", "three"));
+        assert_eq!(names(&recent)[0], "three");
+    }
+
+    #[test]
+    fn test_record_use_persists() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+        RecentRecordings::record_use(&settings, "gig");
+        RecentRecordings::record_use(&settings, "rehearsal");
+
+        let loaded = RecentRecordings::load(&settings).unwrap();
+        assert_eq!(names(&loaded), ["rehearsal", "gig"]);
+        assert!(loaded.list()[0].used_at.is_some());
+    }
+}
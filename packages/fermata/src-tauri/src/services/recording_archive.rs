@@ -0,0 +1,260 @@
+use crate::services::{STATE_MANIFEST_FILE, STEP_LOCK_FILE};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest, the first entry of every archive
+pub const ARCHIVE_MANIFEST: &str = "manifest.json";
+
+/// Progress is reported at least this often while a large file is copied
+const PROGRESS_STEP_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Which parts of a recording go into an archive
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ExportScope {
+    /// The original recording and its metadata; everything else can be regenerated
+    Raw,
+    /// The whole recording directory with every pipeline artifact
+    Full,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchivedFile {
+    /// Path inside the recording directory
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// Written into the archive so it can be checked without unpacking it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchiveManifest {
+    pub recording: String,
+    pub scope: ExportScope,
+    /// Unix timestamp in seconds
+    pub created_at: u64,
+    pub total_bytes: u64,
+    pub files: Vec<ArchivedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ArchiveProgress {
+    pub recording: String,
+    pub current_file: String,
+    pub bytes_written: u64,
+    pub total_bytes: u64,
+}
+
+pub struct RecordingArchive;
+
+impl RecordingArchive {
+    /// Files of a recording included in `scope`, sorted
+    pub fn files(recording_path: &Path, scope: ExportScope) -> Vec<ArchivedFile> {
+        let mut files: Vec<ArchivedFile> = walkdir::WalkDir::new(recording_path)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| {
+                let relative = entry.path().strip_prefix(recording_path).ok()?.to_path_buf();
+                if relative == Path::new(STEP_LOCK_FILE) || !in_scope(&relative, scope) {
+                    return None;
+                }
+                Some(ArchivedFile {
+                    path: relative.to_string_lossy().replace('\\', "/"),
+                    size_bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                })
+            })
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files
+    }
+
+    /// Pack a recording into `<destination>/<name>.tar` with a manifest; the archive
+    /// only appears under its final name once it is complete
+    pub fn export<F>(
+        recording_path: &Path,
+        recording_name: &str,
+        destination: &Path,
+        scope: ExportScope,
+        created_at: u64,
+        mut progress: F,
+    ) -> Result<(PathBuf, ArchiveManifest), String>
+    where
+        F: FnMut(ArchiveProgress),
+    {
+        let target = destination.join(format!("{}.tar", recording_name));
+        if target.exists() {
+            return Err(format!("{} already exists", target.display()));
+        }
+        fs::create_dir_all(destination)
+            .map_err(|e| format!("Failed to create {}: {}", destination.display(), e))?;
+
+        let files = Self::files(recording_path, scope);
+        let manifest = ArchiveManifest {
+            recording: recording_name.to_string(),
+            scope,
+            created_at,
+            total_bytes: files.iter().map(|f| f.size_bytes).sum(),
+            files,
+        };
+
+        let partial = destination.join(format!("{}.tar.part", recording_name));
+        let result = write_archive(recording_path, &partial, &manifest, &mut progress)
+            .and_then(|_| fs::rename(&partial, &target).map_err(|e| format!("Failed to finish archive: {}", e)));
+        if let Err(e) = result {
+            let _ = fs::remove_file(&partial);
+            return Err(e);
+        }
+        Ok((target, manifest))
+    }
+}
+
+fn in_scope(relative: &Path, scope: ExportScope) -> bool {
+    match scope {
+        ExportScope::Full => true,
+        // Files at the recording root (the .mkv, metadata.json) plus fermata's own sidecars,
+        // except the state manifest describing outputs that are left out
+        ExportScope::Raw => {
+            relative.components().count() == 1
+                || (relative.starts_with(".fermata") && relative != Path::new(STATE_MANIFEST_FILE))
+        }
+    }
+}
+
+fn write_archive<F>(
+    recording_path: &Path,
+    target: &Path,
+    manifest: &ArchiveManifest,
+    progress: &mut F,
+) -> Result<(), String>
+where
+    F: FnMut(ArchiveProgress),
+{
+    let file = File::create(target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+    let mut builder = tar::Builder::new(BufWriter::new(file));
+    let root = Path::new(&manifest.recording);
+
+    let manifest_json =
+        serde_json::to_vec_pretty(manifest).map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(manifest.created_at);
+    builder
+        .append_data(&mut header, root.join(ARCHIVE_MANIFEST), manifest_json.as_slice())
+        .map_err(|e| format!("Failed to write manifest: {}", e))?;
+
+    let mut bytes_written = 0;
+    for archived in &manifest.files {
+        let path = recording_path.join(&archived.path);
+        let file = File::open(&path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        let metadata = file.metadata().map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata(&metadata);
+        // The manifest lists the size seen when planning; a file still growing would break the entry
+        header.set_size(archived.size_bytes);
+
+        let mut reader = ProgressReader {
+            inner: file.take(archived.size_bytes),
+            reported: bytes_written,
+            written: bytes_written,
+            on_progress: |written| {
+                progress(ArchiveProgress {
+                    recording: manifest.recording.clone(),
+                    current_file: archived.path.clone(),
+                    bytes_written: written,
+                    total_bytes: manifest.total_bytes,
+                })
+            },
+        };
+        builder
+            .append_data(&mut header, root.join(&archived.path), &mut reader)
+            .map_err(|e| format!("Failed to archive {}: {}", archived.path, e))?;
+        bytes_written += archived.size_bytes;
+        (reader.on_progress)(bytes_written);
+    }
+
+    let mut writer = builder.into_inner().map_err(|e| format!("Failed to finish archive: {}", e))?;
+    writer.flush().map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+    writer
+        .get_ref()
+        .sync_all()
+        .map_err(|e| format!("Failed to write {}: {}", target.display(), e))
+}
+
+/// Reports the running byte count every PROGRESS_STEP_BYTES while a file is read
+struct ProgressReader<R, F> {
+    inner: R,
+    reported: u64,
+    written: u64,
+    on_progress: F,
+}
+
+impl<R: Read, F: FnMut(u64)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.written += n as u64;
+        if self.written - self.reported >= PROGRESS_STEP_BYTES {
+            self.reported = self.written;
+            (self.on_progress)(self.written);
+        }
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_recording(root: &Path) -> PathBuf {
+        let recording = root.join("gig");
+        fs::create_dir_all(recording.join("extracted")).unwrap();
+        fs::create_dir_all(recording.join(".fermata")).unwrap();
+        fs::write(recording.join("gig.mkv"), "raw video").unwrap();
+        fs::write(recording.join("metadata.json"), "{}").unwrap();
+        fs::write(recording.join("extracted/Camera.mp4"), "camera").unwrap();
+        fs::write(recording.join(".fermata/custom_fields.json"), "{}").unwrap();
+        recording
+    }
+
+    #[test]
+    fn test_raw_scope_keeps_original_and_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = create_recording(temp_dir.path());
+
+        let raw: Vec<String> =
+            RecordingArchive::files(&recording, ExportScope::Raw).into_iter().map(|f| f.path).collect();
+        assert_eq!(raw, vec![".fermata/custom_fields.json", "gig.mkv", "metadata.json"]);
+        assert_eq!(RecordingArchive::files(&recording, ExportScope::Full).len(), 4);
+    }
+
+    #[test]
+    fn test_export_writes_manifest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = create_recording(temp_dir.path());
+        let destination = temp_dir.path().join("cold");
+
+        let mut updates = Vec::new();
+        let (archive, manifest) =
+            RecordingArchive::export(&recording, "gig", &destination, ExportScope::Full, 1_700_000_000, |p| {
+                updates.push(p.bytes_written)
+            })
+            .unwrap();
+        assert_eq!(manifest.total_bytes, 19);
+        assert_eq!(updates.last(), Some(&19));
+        assert!(!destination.join("gig.tar.part").exists());
+
+        let mut tar = tar::Archive::new(File::open(&archive).unwrap());
+        let names: Vec<String> = tar
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names[0], "gig/manifest.json");
+        assert!(names.contains(&"gig/extracted/Camera.mp4".to_string()));
+
+        let again = RecordingArchive::export(&recording, "gig", &destination, ExportScope::Raw, 0, |_| {});
+        assert!(again.is_err());
+    }
+}
@@ -0,0 +1,216 @@
+use crate::services::{rewrite_json_strings, validate_recording_name, write_atomic, CUSTOM_FIELDS_FILE, SOURCE_OFFSETS_FILE};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// How far along the pipeline a clone starts
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum CloneScope {
+    /// The original video and metadata only
+    Raw,
+    /// Plus extracted/ sources
+    Extracted,
+    /// Plus analysis/, ready for a render setup with another preset
+    Analyzed,
+}
+
+impl CloneScope {
+    fn directories(&self) -> &'static [&'static str] {
+        match self {
+            CloneScope::Raw => &[],
+            CloneScope::Extracted => &["extracted"],
+            CloneScope::Analyzed => &["extracted", "analysis"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CloneReport {
+    pub source: String,
+    pub name: String,
+    pub scope: CloneScope,
+    pub files: usize,
+    pub copied_bytes: u64,
+}
+
+pub struct RecordingClone;
+
+impl RecordingClone {
+    /// Copy the parts of a recording in `scope` to `<recordings_path>/<new_name>/`.
+    /// Render setups, renders and uploads stay behind so the clone can be set up
+    /// with a different preset. The clone is assembled in a hidden directory and only
+    /// appears under its name once complete.
+    pub fn clone(
+        recordings_path: &Path,
+        name: &str,
+        new_name: &str,
+        scope: CloneScope,
+    ) -> Result<CloneReport, String> {
+        validate_recording_name(new_name)?;
+        let source = recordings_path.join(name);
+        if !source.is_dir() {
+            return Err(format!("Recording '{}' not found", name));
+        }
+        let target = recordings_path.join(new_name);
+        if target.exists() {
+            return Err(format!("Recording with name '{}' already exists", new_name));
+        }
+
+        let staging = recordings_path.join(format!(".{}.cloning", new_name));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)
+                .map_err(|e| format!("Failed to clear {}: {}", staging.display(), e))?;
+        }
+        let mut report = CloneReport {
+            source: name.to_string(),
+            name: new_name.to_string(),
+            scope,
+            files: 0,
+            copied_bytes: 0,
+        };
+        let result = copy_recording(&source, &staging, name, new_name, scope, &mut report)
+            .and_then(|_| rewrite_metadata(&source, &target, &staging, name, new_name))
+            .and_then(|_| fs::rename(&staging, &target).map_err(|e| format!("Failed to finish clone: {}", e)));
+        if let Err(e) = result {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+        Ok(report)
+    }
+}
+
+fn copy_recording(
+    source: &Path,
+    staging: &Path,
+    name: &str,
+    new_name: &str,
+    scope: CloneScope,
+    report: &mut CloneReport,
+) -> Result<(), String> {
+    let mut copy = |from: &Path, to: &Path| -> Result<(), String> {
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let bytes = fs::copy(from, to)
+            .map_err(|e| format!("Failed to copy {} to {}: {}", from.display(), to.display(), e))?;
+        report.files += 1;
+        report.copied_bytes += bytes;
+        Ok(())
+    };
+
+    // Root files: the recording itself (renamed along with the directory) and metadata.json;
+    // animation configs belong to the render setup the clone is meant to redo
+    let entries = fs::read_dir(source).map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if !path.is_file() || file_name.starts_with("animation_config_") {
+            continue;
+        }
+        let target_name = match path.file_stem().zip(path.extension()) {
+            Some((stem, ext)) if stem == name => format!("{}.{}", new_name, ext.to_string_lossy()),
+            _ => file_name,
+        };
+        copy(&path, &staging.join(target_name))?;
+    }
+
+    for dir in scope.directories() {
+        for entry in walkdir::WalkDir::new(source.join(dir)).into_iter().flatten() {
+            if entry.file_type().is_file() {
+                let relative = entry.path().strip_prefix(source).unwrap_or(entry.path());
+                copy(entry.path(), &staging.join(relative))?;
+            }
+        }
+    }
+
+    // Offsets only stay valid alongside the extracted files they were computed from
+    let sidecars: &[&str] = match scope {
+        CloneScope::Raw => &[CUSTOM_FIELDS_FILE],
+        _ => &[CUSTOM_FIELDS_FILE, SOURCE_OFFSETS_FILE],
+    };
+    for sidecar in sidecars {
+        if source.join(sidecar).is_file() {
+            copy(&source.join(sidecar), &staging.join(sidecar))?;
+        }
+    }
+    Ok(())
+}
+
+/// Point metadata.json at the clone's directory and video
+fn rewrite_metadata(source: &Path, target: &Path, staging: &Path, name: &str, new_name: &str) -> Result<(), String> {
+    let path = staging.join("metadata.json");
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(());
+    };
+    let source_str = source.to_string_lossy().to_string();
+    let target_str = target.to_string_lossy().to_string();
+    let replacements = [
+        (source_str.as_str(), target_str.as_str()),
+        (&*format!("{}.mkv", name), &*format!("{}.mkv", new_name)),
+    ];
+    if let Some((new_content, occurrences)) = rewrite_json_strings(&content, &replacements) {
+        if occurrences > 0 {
+            write_atomic(&path, new_content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_rendered_recording(root: &Path) -> std::path::PathBuf {
+        let recording = root.join("gig");
+        for dir in ["extracted", "analysis", "blender/render", ".fermata"] {
+            fs::create_dir_all(recording.join(dir)).unwrap();
+        }
+        fs::write(recording.join("gig.mkv"), "raw").unwrap();
+        fs::write(
+            recording.join("metadata.json"),
+            format!(r#"{{"video": "{}"}}"#, recording.join("gig.mkv").display()),
+        )
+        .unwrap();
+        fs::write(recording.join("animation_config_minimal.yaml"), "preset: minimal").unwrap();
+        fs::write(recording.join("extracted/Camera.mp4"), "camera").unwrap();
+        fs::write(recording.join("analysis/main_analysis.json"), "{}").unwrap();
+        fs::write(recording.join("blender/gig.blend"), "blend").unwrap();
+        fs::write(recording.join(CUSTOM_FIELDS_FILE), "{}").unwrap();
+        recording
+    }
+
+    #[test]
+    fn test_clone_copies_analysis_but_not_render() {
+        let temp_dir = TempDir::new().unwrap();
+        create_rendered_recording(temp_dir.path());
+
+        let report = RecordingClone::clone(temp_dir.path(), "gig", "gig-b", CloneScope::Analyzed).unwrap();
+        assert_eq!(report.files, 5);
+
+        let clone = temp_dir.path().join("gig-b");
+        assert!(clone.join("gig-b.mkv").exists());
+        assert!(clone.join("extracted/Camera.mp4").exists());
+        assert!(clone.join("analysis/main_analysis.json").exists());
+        assert!(clone.join(CUSTOM_FIELDS_FILE).exists());
+        assert!(!clone.join("blender").exists());
+        assert!(!clone.join("animation_config_minimal.yaml").exists());
+
+        let metadata = fs::read_to_string(clone.join("metadata.json")).unwrap();
+        assert!(metadata.contains(&clone.join("gig-b.mkv").to_string_lossy().to_string()));
+        assert!(temp_dir.path().join("gig/blender/gig.blend").exists());
+    }
+
+    #[test]
+    fn test_raw_clone_and_existing_target() {
+        let temp_dir = TempDir::new().unwrap();
+        create_rendered_recording(temp_dir.path());
+
+        RecordingClone::clone(temp_dir.path(), "gig", "gig-raw", CloneScope::Raw).unwrap();
+        assert!(!temp_dir.path().join("gig-raw/extracted").exists());
+
+        let again = RecordingClone::clone(temp_dir.path(), "gig", "gig-raw", CloneScope::Raw);
+        assert!(again.unwrap_err().contains("already exists"));
+        assert!(!temp_dir.path().join(".gig-raw.cloning").exists());
+    }
+}
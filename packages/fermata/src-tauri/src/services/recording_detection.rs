@@ -0,0 +1,154 @@
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::RwLock;
+
+/// Settings key holding the recording detection rules
+pub const RECORDING_DETECTION_KEY: &str = "recording_detection";
+
+/// Marker matching a video file with one of the video extensions in the directory itself
+pub const VIDEO_MARKER: &str = "video";
+
+// Scans run from many places that don't have the settings at hand
+static CURRENT: RwLock<Option<RecordingDetection>> = RwLock::new(None);
+
+/// What makes a directory in a recordings root a recording
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RecordingDetection {
+    /// A directory is a recording when any marker matches: "video", or a path relative
+    /// to the directory whose last part may hold one "*", e.g. "extracted" or "blender/*.blend"
+    pub markers: Vec<String>,
+    /// Extensions of the main recording video, without the dot
+    pub video_extensions: Vec<String>,
+    /// Directory names never treated as recordings; hidden directories never are
+    pub ignored_dirs: Vec<String>,
+}
+
+impl Default for RecordingDetection {
+    fn default() -> Self {
+        Self {
+            markers: vec![VIDEO_MARKER.to_string(), "extracted".to_string()],
+            video_extensions: vec!["mkv".to_string(), "mp4".to_string()],
+            ignored_dirs: vec!["node_modules".to_string()],
+        }
+    }
+}
+
+impl RecordingDetection {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(RECORDING_DETECTION_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        self.validate()?;
+        settings.set(RECORDING_DETECTION_KEY, self)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.markers.iter().all(|m| m.trim().is_empty()) {
+            return Err("At least one recording marker is needed".to_string());
+        }
+        if let Some(marker) = self.markers.iter().find(|m| {
+            let m = m.trim();
+            Path::new(m).is_absolute() || m.split(['/', '\\']).any(|part| part == "..") || m.matches('*').count() > 1
+        }) {
+            return Err(format!("Invalid recording marker '{}': use a relative path with at most one '*'", marker));
+        }
+        if self.markers.iter().any(|m| m.trim() == VIDEO_MARKER) && self.video_extensions.is_empty() {
+            return Err("The \"video\" marker needs at least one video extension".to_string());
+        }
+        Ok(())
+    }
+
+    /// The rules scans and status detection use
+    pub fn current() -> Self {
+        CURRENT.read().ok().and_then(|current| current.clone()).unwrap_or_default()
+    }
+
+    /// Make every later scan use these rules
+    pub fn apply(self) {
+        if let Ok(mut current) = CURRENT.write() {
+            *current = Some(self);
+        }
+    }
+
+    pub fn is_video(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.video_extensions.iter().any(|v| v.trim_start_matches('.').eq_ignore_ascii_case(ext)))
+    }
+
+    pub fn is_ignored(&self, dir_name: &str) -> bool {
+        self.ignored_dirs.iter().any(|ignored| ignored == dir_name)
+    }
+
+    /// The first marker found in `path`, None when it isn't a recording
+    pub fn matching_marker(&self, path: &Path) -> Option<&str> {
+        self.markers.iter().map(|m| m.trim()).filter(|m| !m.is_empty()).find(|marker| self.matches(path, marker))
+    }
+
+    fn matches(&self, path: &Path, marker: &str) -> bool {
+        if marker == VIDEO_MARKER {
+            return std::fs::read_dir(path)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        // Through links, so a broken one doesn't count as a video
+                        .any(|e| self.is_video(&e.path()) && std::fs::metadata(e.path()).is_ok_and(|m| m.is_file()))
+                })
+                .unwrap_or(false);
+        }
+        let (dir, last) = marker.rsplit_once(['/', '\\']).unwrap_or(("", marker));
+        let Some((prefix, suffix)) = last.split_once('*') else {
+            return path.join(marker).exists();
+        };
+        std::fs::read_dir(path.join(dir))
+            .map(|entries| {
+                entries.flatten().any(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    name.len() >= prefix.len() + suffix.len() && name.starts_with(prefix) && name.ends_with(suffix)
+                })
+            })
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_markers() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        let detection = RecordingDetection {
+            markers: vec!["video".to_string(), "blender/*.blend".to_string()],
+            video_extensions: vec!["MOV".to_string()],
+            ignored_dirs: Vec::new(),
+        };
+        fs::write(path.join("gig.mkv"), b"video").unwrap();
+        assert_eq!(detection.matching_marker(path), None);
+
+        fs::create_dir_all(path.join("blender")).unwrap();
+        fs::write(path.join("blender/project.blend"), b"blend").unwrap();
+        assert_eq!(detection.matching_marker(path), Some("blender/*.blend"));
+
+        fs::write(path.join("take.mov"), b"video").unwrap();
+        assert_eq!(detection.matching_marker(path), Some("video"));
+        assert_eq!(RecordingDetection::default().matching_marker(path), Some("video"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(RecordingDetection::default().validate().is_ok());
+        for markers in [vec![], vec!["../other".to_string()], vec!["a/*b*".to_string()], vec!["/abs".to_string()]] {
+            let detection = RecordingDetection { markers, ..RecordingDetection::default() };
+            assert!(detection.validate().is_err());
+        }
+        let no_extensions = RecordingDetection { video_extensions: Vec::new(), ..RecordingDetection::default() };
+        assert!(no_extensions.validate().is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::services::{validate_recording_name, write_atomic};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Containers status detection recognizes as a recording's main video
+pub const IMPORTABLE_EXTENSIONS: [&str; 2] = ["mkv", "mp4"];
+
+/// Canvas of an imported video, normally probed with ffprobe
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VideoGeometry {
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+}
+
+impl Default for VideoGeometry {
+    fn default() -> Self {
+        Self { width: 1920, height: 1080, fps: 30.0 }
+    }
+}
+
+pub struct RecordingImport;
+
+impl RecordingImport {
+    /// Create `<recordings_path>/<new_name>/` around an existing video so it enters the
+    /// pipeline at the extract step. The video is copied, cloned where the filesystem
+    /// supports it; metadata.json describes it as a single source covering the whole canvas.
+    pub fn import(
+        recordings_path: &Path,
+        source: &Path,
+        new_name: &str,
+        geometry: VideoGeometry,
+        timestamp: u64,
+    ) -> Result<PathBuf, String> {
+        validate_recording_name(new_name)?;
+        if !source.is_file() {
+            return Err(format!("Video file not found: {}", source.display()));
+        }
+        let extension = source
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .filter(|e| IMPORTABLE_EXTENSIONS.contains(&e.as_str()))
+            .ok_or_else(|| format!("Unsupported video format: {} (expected .mkv or .mp4)", source.display()))?;
+
+        let recording_path = recordings_path.join(new_name);
+        if recording_path.exists() {
+            return Err(format!("Recording with name '{}' already exists", new_name));
+        }
+        fs::create_dir_all(&recording_path)
+            .map_err(|e| format!("Failed to create {}: {}", recording_path.display(), e))?;
+
+        let result = Self::populate(&recording_path, source, new_name, &extension, geometry, timestamp);
+        if result.is_err() {
+            let _ = fs::remove_dir_all(&recording_path);
+        }
+        result.map(|_| recording_path)
+    }
+
+    fn populate(
+        recording_path: &Path,
+        source: &Path,
+        new_name: &str,
+        extension: &str,
+        geometry: VideoGeometry,
+        timestamp: u64,
+    ) -> Result<(), String> {
+        // Named after the directory, as OBS recordings are, so renames keep them in step.
+        // A copy, so pipeline steps never write through to the imported file.
+        let video = recording_path.join(format!("{}.{}", new_name, extension));
+        fs::copy(source, &video)
+            .map_err(|e| format!("Failed to copy {} to {}: {}", source.display(), video.display(), e))?;
+
+        let metadata = minimal_metadata(source, new_name, geometry, timestamp);
+        let content = serde_json::to_string_pretty(&metadata)
+            .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+        let path = recording_path.join("metadata.json");
+        write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// Width, height and frame rate of the first video stream
+pub fn probe_video_geometry(file: &Path) -> Option<VideoGeometry> {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v", "error",
+            "-select_streams", "v:0",
+            "-show_entries", "stream=width,height,r_frame_rate",
+            "-of", "csv=p=0",
+        ])
+        .arg(file)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_geometry(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+/// ffprobe csv output: `1920,1080,30000/1001`
+fn parse_geometry(line: &str) -> Option<VideoGeometry> {
+    let mut fields = line.split(',');
+    let width = fields.next()?.trim().parse().ok()?;
+    let height = fields.next()?.trim().parse().ok()?;
+    let rate = fields.next()?.trim();
+    let fps = match rate.split_once('/') {
+        Some((num, den)) => num.parse::<f64>().ok()? / den.parse::<f64>().ok().filter(|d| *d > 0.0)?,
+        None => rate.parse().ok()?,
+    };
+    Some(VideoGeometry { width, height, fps })
+}
+
+/// The fields obsession's extractor reads, in the shape the OBS script writes them
+fn minimal_metadata(source: &Path, new_name: &str, geometry: VideoGeometry, timestamp: u64) -> serde_json::Value {
+    json!({
+        "canvas_size": [geometry.width, geometry.height],
+        "fps": geometry.fps,
+        "timestamp": timestamp,
+        "sources": {
+            new_name: {
+                "name": new_name,
+                "has_audio": true,
+                "has_video": true,
+                "position": { "x": 0, "y": 0 },
+                "scale": { "x": 1.0, "y": 1.0 },
+                "dimensions": { "source_width": geometry.width, "source_height": geometry.height },
+            }
+        },
+        "imported_from": source.to_string_lossy(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RecordingStatus;
+    use crate::services::StatusDetector;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_import_creates_recorded_recording() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("phone clip.MP4");
+        fs::write(&source, "video").unwrap();
+        let recordings = temp_dir.path().join("recordings");
+
+        let path = RecordingImport::import(&recordings, &source, "gig", VideoGeometry::default(), 1_700_000_000).unwrap();
+        assert_eq!(fs::read_to_string(path.join("gig.mp4")).unwrap(), "video");
+        assert!(source.exists());
+        assert_eq!(StatusDetector::detect_status(&path), RecordingStatus::Recorded);
+
+        let metadata: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path.join("metadata.json")).unwrap()).unwrap();
+        assert_eq!(metadata["canvas_size"], json!([1920, 1080]));
+        assert_eq!(metadata["sources"]["gig"]["has_audio"], json!(true));
+
+        let again = RecordingImport::import(&recordings, &source, "gig", VideoGeometry::default(), 0);
+        assert!(again.unwrap_err().contains("already exists"));
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("clip.avi");
+        fs::write(&source, "video").unwrap();
+
+        assert!(RecordingImport::import(temp_dir.path(), &source, "gig", VideoGeometry::default(), 0).is_err());
+        assert!(RecordingImport::import(temp_dir.path(), &source, "../gig", VideoGeometry::default(), 0).is_err());
+        assert!(!temp_dir.path().join("gig").exists());
+        assert_eq!(
+            parse_geometry("1280,720,30000/1001").map(|g| (g.width, g.height, (g.fps * 100.0).round())),
+            Some((1280, 720, 2997.0))
+        );
+    }
+}
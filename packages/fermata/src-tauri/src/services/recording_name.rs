@@ -0,0 +1,214 @@
+use chrono::{DateTime, Local};
+use std::path::Path;
+
+/// Longest recording name, leaving room for the main file's extension and the
+/// "-N" suffix within the 255 characters exFAT and NTFS allow
+pub const MAX_RECORDING_NAME_CHARS: usize = 200;
+
+/// Characters Windows and exFAT refuse in file names
+const INVALID_CHARS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Device names Windows reserves, with or without an extension
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9", "LPT1", "LPT2",
+    "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+fn is_reserved(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name).trim_end();
+    RESERVED_NAMES.iter().any(|reserved| stem.eq_ignore_ascii_case(reserved))
+}
+
+/// Check that `name` works as a directory name on every filesystem recordings end up on
+pub fn validate_recording_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Recording name cannot be empty".to_string());
+    }
+    let invalid = |reason: &str| {
+        Err(format!("Invalid recording name '{}': {}; try '{}'", name, reason, sanitize_recording_name(name)))
+    };
+    if let Some(c) = name.chars().find(|c| INVALID_CHARS.contains(c) || c.is_control()) {
+        return invalid(&format!("{:?} is not allowed", c));
+    }
+    if name.starts_with('.') {
+        return invalid("it must not start with a dot");
+    }
+    if name.ends_with(['.', ' ']) || name.starts_with(' ') {
+        return invalid("it must not start with a space or end with a space or dot");
+    }
+    if is_reserved(name) {
+        return invalid("the name is reserved on Windows");
+    }
+    if name.chars().count() > MAX_RECORDING_NAME_CHARS {
+        return invalid(&format!("it is longer than {} characters", MAX_RECORDING_NAME_CHARS));
+    }
+    Ok(())
+}
+
+/// Closest valid name to `name`: invalid characters become "_", whitespace runs a
+/// single space, and leading dots and trailing dots or spaces are dropped
+pub fn sanitize_recording_name(name: &str) -> String {
+    let mut sanitized = String::new();
+    for c in name.chars() {
+        if c.is_whitespace() {
+            if !sanitized.is_empty() && !sanitized.ends_with(' ') {
+                sanitized.push(' ');
+            }
+        } else if INVALID_CHARS.contains(&c) || c.is_control() {
+            sanitized.push('_');
+        } else {
+            sanitized.push(c);
+        }
+    }
+    let mut sanitized: String = sanitized.trim_start_matches(['.', ' ']).chars().take(MAX_RECORDING_NAME_CHARS).collect();
+    sanitized.truncate(sanitized.trim_end_matches(['.', ' ']).len());
+    if sanitized.is_empty() {
+        return "recording".to_string();
+    }
+    if is_reserved(&sanitized) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+/// `base`, or `base-2`, `base-3`... for the first name `taken` does not report as used
+pub fn unique_recording_name(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| {
+            let suffix = format!("-{}", n);
+            let stem: String = base.chars().take(MAX_RECORDING_NAME_CHARS - suffix.len()).collect();
+            format!("{}{}", stem.trim_end_matches(['.', ' ']), suffix)
+        })
+        .find(|candidate| !taken(candidate))
+        .expect("some suffix is free")
+}
+
+/// Values recording names are built from, read from a recording's metadata.json
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameFields {
+    /// Local date the recording started, "YYYY-MM-DD"
+    pub date: String,
+    /// OBS scene recorded, empty when unknown
+    pub scene: String,
+}
+
+impl NameFields {
+    /// `fallback_time` (Unix seconds) dates recordings whose metadata has no start time
+    pub fn read(recording_path: &Path, fallback_time: u64) -> Self {
+        let metadata: Option<serde_json::Value> = std::fs::read_to_string(recording_path.join("metadata.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+        let field = |key: &str| metadata.as_ref().and_then(|m| m.get(key));
+
+        let started = field("recording_start_time")
+            .or_else(|| field("timestamp"))
+            .and_then(|t| t.as_f64())
+            .map(|t| t as i64)
+            .unwrap_or(fallback_time as i64);
+        let date = DateTime::from_timestamp(started, 0)
+            .map(|t| t.with_timezone(&Local).format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let scene = field("scene_name").and_then(|s| s.as_str()).unwrap_or_default().to_string();
+        Self { date, scene }
+    }
+}
+
+/// Descriptive name for a recording from its metadata.json: the local recording
+/// date and the OBS scene, e.g. "2025-06-14 Main Stage"
+pub fn suggest_recording_name(recording_path: &Path, fallback_time: u64) -> String {
+    let fields = NameFields::read(recording_path, fallback_time);
+    sanitize_recording_name(&format!("{} {}", fields.date, fields.scene))
+}
+
+/// Expand a bulk rename pattern such as "{date} {scene} #{index}". `index` is
+/// 1-based and zero-padded to `width` digits; token values are sanitized, the
+/// literal text is kept as typed.
+pub fn expand_name_pattern(pattern: &str, fields: &NameFields, original: &str, index: usize, width: usize) -> Result<String, String> {
+    let mut expanded = String::new();
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("Unclosed '{{' in pattern '{}'", pattern))?;
+        let value = match &rest[start + 1..start + end] {
+            "date" => fields.date.clone(),
+            "scene" => fields.scene.clone(),
+            "original" => original.to_string(),
+            "index" => format!("{:0width$}", index, width = width),
+            token => {
+                return Err(format!("Unknown token {{{}}} in pattern; use {{date}}, {{scene}}, {{index}} or {{original}}", token))
+            }
+        };
+        if !value.is_empty() {
+            expanded.push_str(&sanitize_recording_name(&value));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_recording_name() {
+        assert!(validate_recording_name("2025-06-14 Main Stage").is_ok());
+        assert!(validate_recording_name("Próba zespołu").is_ok());
+        for name in ["", "  ", "a/b", "..\\up", ".hidden", "gig:2", "gig.", " gig", "CON", "nul.mkv", "tab\there"] {
+            assert!(validate_recording_name(name).is_err(), "{:?}", name);
+        }
+        assert!(validate_recording_name(&"x".repeat(MAX_RECORDING_NAME_CHARS + 1)).is_err());
+        assert!(validate_recording_name("gig: live").unwrap_err().contains("try 'gig_ live'"));
+    }
+
+    #[test]
+    fn test_sanitize_and_unique() {
+        assert_eq!(sanitize_recording_name("  ../Main:Stage?  \n2025. "), "_Main_Stage_ 2025");
+        assert_eq!(sanitize_recording_name("..."), "recording");
+        assert_eq!(sanitize_recording_name("aux"), "_aux");
+        for name in ["a/b", "CON", " . ", &"ż".repeat(300)] {
+            assert!(validate_recording_name(&sanitize_recording_name(name)).is_ok(), "{:?}", name);
+        }
+
+        let taken = ["gig", "gig-2"];
+        assert_eq!(unique_recording_name("gig", |n| taken.contains(&n)), "gig-3");
+        assert_eq!(unique_recording_name("new", |n| taken.contains(&n)), "new");
+    }
+
+    #[test]
+    fn test_suggest_recording_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let noon = 1_718_366_400; // 2024-06-14 12:00 UTC
+        std::fs::write(
+            temp_dir.path().join("metadata.json"),
+            format!(r#"{{"recording_start_time": {}.5, "scene_name": "Main: Stage"}}"#, noon),
+        )
+        .unwrap();
+        let date = DateTime::from_timestamp(noon, 0).unwrap().with_timezone(&Local).format("%Y-%m-%d").to_string();
+        assert_eq!(suggest_recording_name(temp_dir.path(), 0), format!("{} Main_ Stage", date));
+
+        let empty = TempDir::new().unwrap();
+        assert_eq!(suggest_recording_name(empty.path(), noon as u64), date);
+    }
+
+    #[test]
+    fn test_expand_name_pattern() {
+        let fields = NameFields { date: "2025-06-14".to_string(), scene: "Main: Stage".to_string() };
+        assert_eq!(
+            expand_name_pattern("{date} {scene} #{index}", &fields, "2025-06-14 20-01-33", 7, 2),
+            Ok("2025-06-14 Main_ Stage #07".to_string())
+        );
+        assert_eq!(expand_name_pattern("fest-{original}", &fields, "take", 1, 1), Ok("fest-take".to_string()));
+        let no_scene = NameFields { scene: String::new(), ..fields.clone() };
+        assert_eq!(expand_name_pattern("{date} {scene}", &no_scene, "x", 1, 1), Ok("2025-06-14".to_string()));
+        assert!(expand_name_pattern("{day}", &fields, "x", 1, 1).is_err());
+        assert!(expand_name_pattern("{date", &fields, "x", 1, 1).is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use crate::models::{Recording, RecordingStatus};
+use crate::services::status_name;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Where a recording's calendar date comes from
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DateSource {
+    /// Start time in metadata.json
+    Metadata,
+    /// OBS timestamped directory name, e.g. "2025-06-14 20-01-33"
+    Name,
+    /// Modification time of the recording directory
+    Modified,
+}
+
+/// Inclusive range of local dates, "YYYY-MM-DD"; open-ended when a bound is missing
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct CalendarRange {
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// A recording on its day of the calendar
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CalendarEntry {
+    pub name: String,
+    pub status: RecordingStatus,
+    pub date_source: DateSource,
+}
+
+/// Recordings made on one day, by name
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CalendarDay {
+    pub date: String,
+    pub count: usize,
+    /// Count per status name as in the pipeline stats
+    pub status_counts: BTreeMap<String, usize>,
+    pub recordings: Vec<CalendarEntry>,
+}
+
+impl CalendarRange {
+    fn bound(value: &Option<String>) -> Result<Option<NaiveDate>, String> {
+        value
+            .as_deref()
+            .map(|date| {
+                NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                    .map_err(|_| format!("Invalid date '{}': use YYYY-MM-DD", date))
+            })
+            .transpose()
+    }
+}
+
+/// Bucket recordings by the local date they were recorded, days in order
+pub fn recordings_calendar(recordings: &[Recording], range: &CalendarRange) -> Result<Vec<CalendarDay>, String> {
+    let from = CalendarRange::bound(&range.from)?;
+    let to = CalendarRange::bound(&range.to)?;
+
+    let mut days: BTreeMap<NaiveDate, Vec<CalendarEntry>> = BTreeMap::new();
+    for recording in recordings {
+        let (date, date_source) = recording_date(recording);
+        if from.is_some_and(|from| date < from) || to.is_some_and(|to| date > to) {
+            continue;
+        }
+        days.entry(date).or_default().push(CalendarEntry {
+            name: recording.name.clone(),
+            status: recording.status.clone(),
+            date_source,
+        });
+    }
+
+    Ok(days
+        .into_iter()
+        .map(|(date, mut recordings)| {
+            recordings.sort_by(|a, b| a.name.cmp(&b.name));
+            let mut status_counts = BTreeMap::new();
+            for entry in &recordings {
+                *status_counts.entry(status_name(&entry.status).to_string()).or_insert(0) += 1;
+            }
+            CalendarDay { date: date.format("%Y-%m-%d").to_string(), count: recordings.len(), status_counts, recordings }
+        })
+        .collect())
+}
+
+/// Local date a recording was made: its metadata start time, else the date its
+/// OBS name starts with, else when the directory was last modified
+pub fn recording_date(recording: &Recording) -> (NaiveDate, DateSource) {
+    if let Some(date) = metadata_start_time(&recording.path).and_then(local_date) {
+        return (date, DateSource::Metadata);
+    }
+    if let Some(date) = recording.name.get(..10).and_then(|prefix| NaiveDate::parse_from_str(prefix, "%Y-%m-%d").ok()) {
+        return (date, DateSource::Name);
+    }
+    let modified = local_date(recording.last_updated as i64).unwrap_or_default();
+    (modified, DateSource::Modified)
+}
+
+/// Start time in metadata.json, as a Unix timestamp
+pub fn metadata_start_time(recording_path: &Path) -> Option<i64> {
+    let content = std::fs::read_to_string(recording_path.join("metadata.json")).ok()?;
+    let metadata: serde_json::Value = serde_json::from_str(&content).ok()?;
+    metadata
+        .get("recording_start_time")
+        .or_else(|| metadata.get("timestamp"))
+        .and_then(|t| t.as_f64())
+        .map(|t| t as i64)
+}
+
+fn local_date(timestamp: i64) -> Option<NaiveDate> {
+    DateTime::from_timestamp(timestamp, 0).map(|t| t.with_timezone(&Local).date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn recording(root: &Path, name: &str, status: RecordingStatus) -> Recording {
+        fs::create_dir_all(root.join(name)).unwrap();
+        let mut recording = Recording::from_path(root.join(name)).unwrap();
+        recording.status = status;
+        recording
+    }
+
+    #[test]
+    fn test_dates_from_metadata_name_and_mtime() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let named = recording(root, "2025-06-14 20-01-33", RecordingStatus::Recorded);
+        assert_eq!(recording_date(&named), (NaiveDate::from_ymd_opt(2025, 6, 14).unwrap(), DateSource::Name));
+
+        let mut renamed = recording(root, "Main Stage", RecordingStatus::Recorded);
+        renamed.last_updated = DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z").unwrap().timestamp() as u64;
+        assert_eq!(recording_date(&renamed).1, DateSource::Modified);
+        assert_eq!(recording_date(&renamed).0, local_date(renamed.last_updated as i64).unwrap());
+
+        let noon = DateTime::parse_from_rfc3339("2025-05-01T12:00:00Z").unwrap().timestamp();
+        fs::write(root.join("Main Stage/metadata.json"), format!("{{\"recording_start_time\": {}}}", noon)).unwrap();
+        assert_eq!(recording_date(&renamed), (local_date(noon).unwrap(), DateSource::Metadata));
+    }
+
+    #[test]
+    fn test_days_counts_and_range() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let recordings = vec![
+            recording(root, "2025-06-14 21-00-00", RecordingStatus::Failed("render".to_string())),
+            recording(root, "2025-06-14 20-00-00", RecordingStatus::Uploaded),
+            recording(root, "2025-06-15 20-00-00", RecordingStatus::Recorded),
+            recording(root, "2025-07-01 20-00-00", RecordingStatus::Recorded),
+        ];
+
+        let days = recordings_calendar(&recordings, &CalendarRange::default()).unwrap();
+        let dates: Vec<_> = days.iter().map(|d| d.date.as_str()).collect();
+        assert_eq!(dates, ["2025-06-14", "2025-06-15", "2025-07-01"]);
+        assert_eq!(days[0].count, 2);
+        assert_eq!(days[0].recordings[0].name, "2025-06-14 20-00-00");
+        assert_eq!(days[0].status_counts.get("Failed"), Some(&1));
+
+        let range = CalendarRange { from: Some("2025-06-15".to_string()), to: Some("2025-06-30".to_string()) };
+        let june = recordings_calendar(&recordings, &range).unwrap();
+        assert_eq!(june.len(), 1);
+        assert_eq!(june[0].date, "2025-06-15");
+
+        let invalid = CalendarRange { from: Some("15.06.2025".to_string()), to: None };
+        assert!(recordings_calendar(&recordings, &invalid).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use std::path::Path;
+
+/// Ignore file in a recordings root, with gitignore-style patterns of directory names
+pub const IGNORE_FILE_NAME: &str = ".fermataignore";
+
+/// System folders drives and NAS shares keep at the top level; a "!name" line in the
+/// ignore file brings one back
+const DEFAULT_IGNORED: [&str; 5] = ["$RECYCLE.BIN", "System Volume Information", "lost+found", "@eaDir", "#recycle"];
+
+#[derive(Debug, Clone, PartialEq)]
+struct IgnorePattern {
+    glob: String,
+    negated: bool,
+}
+
+/// Which top-level directories of a recordings root are not recordings. Hidden
+/// directories (backups, .trash, sync caches like .stfolder) never are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordingsIgnore {
+    patterns: Vec<IgnorePattern>,
+}
+
+impl Default for RecordingsIgnore {
+    fn default() -> Self {
+        Self::parse("")
+    }
+}
+
+impl RecordingsIgnore {
+    /// Rules of `root`'s ignore file, the defaults alone when it has none
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(IGNORE_FILE_NAME);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                log::warn!("Failed to read {}: {}", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    /// One pattern per line; "#" starts a comment, "!" negates, "*", "**" and "?" are
+    /// wildcards, and a leading or trailing "/" is allowed. Patterns reaching below the
+    /// top level can't match a recording and are skipped.
+    pub fn parse(content: &str) -> Self {
+        let mut patterns: Vec<IgnorePattern> = DEFAULT_IGNORED
+            .iter()
+            .map(|name| IgnorePattern { glob: name.to_string(), negated: false })
+            .collect();
+        for line in content.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, glob) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line.strip_prefix('\\').unwrap_or(line)),
+            };
+            let glob = glob.trim_start_matches('/').trim_end_matches('/').replace("**", "*");
+            if glob.is_empty() || glob.contains('/') {
+                continue;
+            }
+            patterns.push(IgnorePattern { glob, negated });
+        }
+        Self { patterns }
+    }
+
+    /// Whether the top-level directory `name` is not a recording; the last matching pattern wins
+    pub fn is_ignored(&self, name: &str) -> bool {
+        if name.starts_with('.') {
+            return true;
+        }
+        self.patterns
+            .iter()
+            .rev()
+            .find(|pattern| glob_match(pattern.glob.as_bytes(), name.as_bytes()))
+            .is_some_and(|pattern| !pattern.negated)
+    }
+}
+
+/// `*` matches any run of characters, `?` exactly one
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => glob_match(&pattern[1..], name) || (!name.is_empty() && glob_match(pattern, &name[1..])),
+        (Some(b'?'), Some(_)) => glob_match(&pattern[1..], &name[utf8_len(name[0])..]),
+        (Some(p), Some(n)) if p == n => glob_match(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+/// Length of the UTF-8 sequence starting with `first`, so "?" matches one character
+fn utf8_len(first: u8) -> usize {
+    match first {
+        0xF0..=0xFF => 4,
+        0xE0..=0xEF => 3,
+        0xC0..=0xDF => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_patterns() {
+        let ignore = RecordingsIgnore::parse(
+            "# backups\nbackup-*\n/archive/\n*.zip\n\\!odd\nt?pe\nkeep-*\n!keep-this\nnested/dir\n!lost+found\n",
+        );
+        for name in [".stfolder", ".sync", "backup-2024", "archive", "old.zip", "!odd", "tape", "tąpe", "keep-x", "@eaDir"] {
+            assert!(ignore.is_ignored(name), "{}", name);
+        }
+        for name in ["2025-06-14 gig", "archived", "keep-this", "nested", "dir", "lost+found", "tape2"] {
+            assert!(!ignore.is_ignored(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_load() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(!RecordingsIgnore::load(temp_dir.path()).is_ignored("sync-cache"));
+        assert!(RecordingsIgnore::load(temp_dir.path()).is_ignored("$RECYCLE.BIN"));
+        std::fs::write(temp_dir.path().join(IGNORE_FILE_NAME), "sync-*\n").unwrap();
+        assert!(RecordingsIgnore::load(temp_dir.path()).is_ignored("sync-cache"));
+    }
+}
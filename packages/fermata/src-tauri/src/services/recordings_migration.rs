@@ -0,0 +1,263 @@
+use crate::services::{detect_in_progress_status, directory_size, BatchSkip, SettingsStore};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Settings key holding the recordings location chosen in the app
+pub const RECORDINGS_LOCATION_KEY: &str = "recordings_location";
+
+/// Primary recordings root set by migrate_recordings; overrides FERMATA_RECORDINGS_PATH
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RecordingsLocation {
+    pub recordings_path: Option<PathBuf>,
+}
+
+impl RecordingsLocation {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(RECORDINGS_LOCATION_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        settings.set(RECORDINGS_LOCATION_KEY, self)
+    }
+}
+
+/// How recordings get to the new root
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MigrationOptions {
+    /// Remove the originals once their copy is verified
+    pub move_files: bool,
+    /// Leave a symlink to the new location where a moved recording was
+    pub leave_symlinks: bool,
+}
+
+/// Reported after every copied file
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MigrationProgress {
+    pub recording: String,
+    pub recordings_done: usize,
+    pub recordings_total: usize,
+    pub copied_bytes: u64,
+    pub total_bytes: u64,
+}
+
+/// A recording that now lives in the new root
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MigratedRecording {
+    pub name: String,
+    pub bytes: u64,
+    /// The original is gone (or replaced by a symlink)
+    pub moved: bool,
+    pub symlinked: bool,
+}
+
+/// What a recordings migration did
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RootMigrationReport {
+    pub old_root: PathBuf,
+    pub new_root: PathBuf,
+    pub migrated: Vec<MigratedRecording>,
+    pub skipped: Vec<BatchSkip>,
+    pub warnings: Vec<String>,
+    /// The new root was saved as the recordings location, effective after a restart
+    pub config_updated: bool,
+}
+
+/// Copies or moves recordings to another root, e.g. when the library moves to a new drive
+pub struct RecordingsMigration;
+
+impl RecordingsMigration {
+    /// Migrate the named recordings of `old_root`. Each recording is copied into a hidden
+    /// directory, verified file by file and only then appears under its name; a copy
+    /// already in the new root with the same size counts as done, so an interrupted
+    /// migration can simply be run again.
+    pub fn migrate(
+        old_root: &Path,
+        new_root: &Path,
+        names: &[String],
+        running: &[String],
+        options: MigrationOptions,
+        mut on_progress: impl FnMut(&MigrationProgress),
+    ) -> RootMigrationReport {
+        let mut report = RootMigrationReport {
+            old_root: old_root.to_path_buf(),
+            new_root: new_root.to_path_buf(),
+            migrated: Vec::new(),
+            skipped: Vec::new(),
+            warnings: Vec::new(),
+            config_updated: false,
+        };
+        let total_bytes = names.iter().map(|name| directory_size(&old_root.join(name))).sum();
+        let mut progress = MigrationProgress {
+            recording: String::new(),
+            recordings_done: 0,
+            recordings_total: names.len(),
+            copied_bytes: 0,
+            total_bytes,
+        };
+
+        for name in names {
+            let source = old_root.join(name);
+            progress.recording = name.clone();
+            let busy = if running.contains(name) {
+                Some("a job is running on it".to_string())
+            } else {
+                detect_in_progress_status(&source).map(|status| format!("busy ({:?})", status))
+            };
+            let result = match busy {
+                Some(reason) => Err(reason),
+                None => Self::migrate_one(&source, &new_root.join(name), options, &mut progress, &mut on_progress),
+            };
+            match result {
+                Ok(migrated) => {
+                    if options.leave_symlinks && migrated.moved && !migrated.symlinked {
+                        report.warnings.push(format!("Failed to leave a symlink for '{}'", name));
+                    }
+                    report.migrated.push(migrated);
+                }
+                Err(reason) => report.skipped.push(BatchSkip { name: name.clone(), reason }),
+            }
+            progress.recordings_done += 1;
+        }
+        report
+    }
+
+    fn migrate_one(
+        source: &Path,
+        target: &Path,
+        options: MigrationOptions,
+        progress: &mut MigrationProgress,
+        on_progress: &mut impl FnMut(&MigrationProgress),
+    ) -> Result<MigratedRecording, String> {
+        let name = progress.recording.clone();
+        let bytes = directory_size(source);
+        let renamed = options.move_files && !target.exists() && fs::rename(source, target).is_ok();
+        if renamed {
+            // Same volume: nothing to copy
+            progress.copied_bytes += bytes;
+            on_progress(progress);
+        } else if target.exists() {
+            if directory_size(target) != bytes {
+                return Err(format!("A different copy already exists in {}", target.display()));
+            }
+            progress.copied_bytes += bytes;
+            on_progress(progress);
+        } else {
+            let staging = target.with_file_name(format!(".{}.migrating", name));
+            let result = copy_verified(source, &staging, progress, on_progress)
+                .and_then(|_| fs::rename(&staging, target).map_err(|e| format!("Failed to finish copy: {}", e)));
+            if let Err(e) = result {
+                let _ = fs::remove_dir_all(&staging);
+                return Err(e);
+            }
+        }
+
+        let mut migrated = MigratedRecording { name, bytes, moved: renamed, symlinked: false };
+        if options.move_files && !renamed {
+            fs::remove_dir_all(source)
+                .map_err(|e| format!("Copied, but failed to remove the original {}: {}", source.display(), e))?;
+            migrated.moved = true;
+        }
+        if options.leave_symlinks && migrated.moved {
+            migrated.symlinked = link_dir(target, source).is_ok();
+        }
+        Ok(migrated)
+    }
+}
+
+/// Copy a directory tree, checking every copied file has its source's size
+fn copy_verified(
+    source: &Path,
+    target: &Path,
+    progress: &mut MigrationProgress,
+    on_progress: &mut impl FnMut(&MigrationProgress),
+) -> Result<(), String> {
+    for entry in walkdir::WalkDir::new(source) {
+        let entry = entry.map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+        let relative = entry.path().strip_prefix(source).unwrap_or(entry.path());
+        let to = target.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+        } else if entry.file_type().is_file() {
+            let expected = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            let copied = fs::copy(entry.path(), &to)
+                .map_err(|e| format!("Failed to copy {}: {}", entry.path().display(), e))?;
+            let written = fs::metadata(&to).map(|m| m.len()).unwrap_or(0);
+            if copied != expected || written != expected {
+                return Err(format!("Size mismatch copying {}: {} of {} bytes", entry.path().display(), written, expected));
+            }
+            progress.copied_bytes += copied;
+            on_progress(progress);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn link_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn link_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    // Needs Developer Mode or admin rights; failures become warnings
+    std::os::windows::fs::symlink_dir(target, link)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_recording(root: &Path, name: &str) {
+        fs::create_dir_all(root.join(name).join("extracted")).unwrap();
+        fs::write(root.join(name).join(format!("{}.mkv", name)), vec![0u8; 10]).unwrap();
+        fs::write(root.join(name).join("extracted/Mic.m4a"), vec![0u8; 4]).unwrap();
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_copy_keeps_originals_and_reports_progress() {
+        let old_root = TempDir::new().unwrap();
+        let new_root = TempDir::new().unwrap();
+        create_recording(old_root.path(), "fri");
+        create_recording(old_root.path(), "sat");
+        let options = MigrationOptions { move_files: false, leave_symlinks: false };
+
+        let mut last = None;
+        let report = RecordingsMigration::migrate(old_root.path(), new_root.path(), &names(&["fri", "sat"]), &names(&["sat"]), options, |p| {
+            last = Some(p.clone())
+        });
+        assert_eq!(report.migrated.len(), 1);
+        assert_eq!(report.skipped[0].name, "sat");
+        assert_eq!(last.map(|p| (p.copied_bytes, p.total_bytes)), Some((14, 28)));
+        assert!(new_root.path().join("fri/extracted/Mic.m4a").is_file());
+        assert!(old_root.path().join("fri/fri.mkv").is_file());
+        assert!(!new_root.path().join(".fri.migrating").exists());
+
+        // Running again picks up where it left off
+        let again = RecordingsMigration::migrate(old_root.path(), new_root.path(), &names(&["fri", "sat"]), &[], options, |_| {});
+        assert_eq!(again.migrated.len(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_move_leaves_symlinks() {
+        let old_root = TempDir::new().unwrap();
+        let new_root = TempDir::new().unwrap();
+        create_recording(old_root.path(), "fri");
+        fs::create_dir_all(new_root.path().join("sat")).unwrap();
+        create_recording(old_root.path(), "sat");
+        let options = MigrationOptions { move_files: true, leave_symlinks: true };
+
+        let report = RecordingsMigration::migrate(old_root.path(), new_root.path(), &names(&["fri", "sat"]), &[], options, |_| {});
+        assert_eq!(report.migrated, vec![MigratedRecording { name: "fri".to_string(), bytes: 14, moved: true, symlinked: true }]);
+        assert!(report.skipped[0].reason.contains("different copy"));
+        assert!(fs::symlink_metadata(old_root.path().join("fri")).unwrap().file_type().is_symlink());
+        assert!(old_root.path().join("fri/fri.mkv").is_file());
+        assert!(new_root.path().join("fri/fri.mkv").is_file());
+    }
+}
@@ -0,0 +1,138 @@
+use crate::services::{FileScanner, RecordingsIgnore, SettingsStore};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Settings key for rescanning the recordings on a timer
+pub const POLLING_SETTINGS_KEY: &str = "polling";
+
+/// Polling faster than this rescans network shares more than it helps
+pub const MIN_POLLING_INTERVAL_SECONDS: u64 = 5;
+
+/// How deep into a recording polling looks for changes; steps write into
+/// directories such as blender/render
+const POLL_DEPTH: usize = 2;
+
+/// Which recordings roots are rescanned on a timer
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PollingMode {
+    /// Only roots the filesystem watcher couldn't watch, e.g. some network shares
+    #[default]
+    Fallback,
+    Always,
+    Off,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PollingSettings {
+    pub mode: PollingMode,
+    pub interval_seconds: u64,
+}
+
+impl Default for PollingSettings {
+    fn default() -> Self {
+        Self { mode: PollingMode::Fallback, interval_seconds: 30 }
+    }
+}
+
+impl PollingSettings {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(POLLING_SETTINGS_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        if self.interval_seconds < MIN_POLLING_INTERVAL_SECONDS {
+            return Err(format!("Polling interval must be at least {} seconds", MIN_POLLING_INTERVAL_SECONDS));
+        }
+        settings.set(POLLING_SETTINGS_KEY, self)
+    }
+}
+
+/// Modification times of the recordings under each root, compared between polls
+#[derive(Debug, Default)]
+pub struct RecordingsPoller {
+    seen: Mutex<HashMap<PathBuf, HashMap<String, SystemTime>>>,
+}
+
+impl RecordingsPoller {
+    /// Recordings under `root` added, removed or modified since its last poll, by name;
+    /// the first poll of a root only takes note of them
+    pub fn poll(&self, root: &Path) -> Vec<String> {
+        let current = fingerprints(root);
+        let Some(previous) = self.seen.lock().unwrap().insert(root.to_path_buf(), current.clone()) else {
+            return Vec::new();
+        };
+
+        let mut changed: Vec<String> = current
+            .iter()
+            .filter(|(name, modified)| previous.get(*name) != Some(modified))
+            .map(|(name, _)| name.clone())
+            .chain(previous.keys().filter(|name| !current.contains_key(*name)).cloned())
+            .collect();
+        changed.sort();
+        changed
+    }
+}
+
+/// Latest modification time within each recording under `root`
+fn fingerprints(root: &Path) -> HashMap<String, SystemTime> {
+    let ignore = RecordingsIgnore::load(root);
+    fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let path = entry.path();
+                    if name.starts_with('.') || ignore.is_ignored(&name) || !FileScanner::is_valid_recording_dir(&path) {
+                        return None;
+                    }
+                    Some((name, latest_modified(&path, POLL_DEPTH)))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn latest_modified(path: &Path, depth: usize) -> SystemTime {
+    let own = fs::metadata(path).and_then(|m| m.modified()).unwrap_or(SystemTime::UNIX_EPOCH);
+    if depth == 0 || !path.is_dir() {
+        return own;
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| latest_modified(&entry.path(), depth - 1)).fold(own, SystemTime::max))
+        .unwrap_or(own)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_poll_reports_changed_recordings() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for name in ["gig", "rehearsal"] {
+            fs::create_dir_all(root.join(name)).unwrap();
+            fs::write(root.join(name).join(format!("{}.mkv", name)), "video").unwrap();
+        }
+
+        let poller = RecordingsPoller::default();
+        assert!(poller.poll(root).is_empty());
+        assert!(poller.poll(root).is_empty());
+
+        fs::create_dir_all(root.join("gig/blender/render")).unwrap();
+        fs::write(root.join("gig/blender/render/frame_0001.png"), "png").unwrap();
+        fs::remove_dir_all(root.join("rehearsal")).unwrap();
+        fs::create_dir_all(root.join("new")).unwrap();
+        fs::write(root.join("new/new.mkv"), "video").unwrap();
+        assert_eq!(poller.poll(root), vec!["gig", "new", "rehearsal"]);
+        assert!(poller.poll(root).is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+use crate::models::Recording;
+use crate::services::{update_recording_status, FileScanner, RecordingsIgnore, IGNORE_FILE_NAME};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Payload of the `recording-changed` event
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingChange {
+    pub name: String,
+    /// None when the recording directory disappeared
+    pub recording: Option<Recording>,
+}
+
+/// Collects filesystem events per recording and releases each recording
+/// only after it has been quiet for the debounce window.
+#[derive(Debug)]
+pub struct ChangeCoalescer {
+    debounce: Duration,
+    pending: HashMap<String, Instant>,
+}
+
+impl ChangeCoalescer {
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Register an event for a recording, restarting its quiet period
+    pub fn record(&mut self, recording_name: String, now: Instant) {
+        self.pending.insert(recording_name, now);
+    }
+
+    /// Take all recordings that have been quiet for at least the debounce window
+    pub fn drain_ready(&mut self, now: Instant) -> Vec<String> {
+        let ready: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, last)| now.duration_since(**last) >= self.debounce)
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        for name in &ready {
+            self.pending.remove(name);
+        }
+        ready
+    }
+
+    /// How long to wait before the next recording may become ready
+    pub fn next_wakeup(&self, now: Instant) -> Option<Duration> {
+        self.pending
+            .values()
+            .map(|last| (*last + self.debounce).saturating_duration_since(now))
+            .min()
+    }
+}
+
+/// Map a changed path to the recording directory it belongs to
+pub fn recording_name_for_path(recordings_path: &Path, changed: &Path) -> Option<String> {
+    let relative = changed.strip_prefix(recordings_path).ok()?;
+    let first = relative.components().next()?;
+    let name = first.as_os_str().to_str()?;
+
+    // Hidden entries (e.g. .trash) are app bookkeeping, not recordings
+    if name.starts_with('.') {
+        return None;
+    }
+    Some(name.to_string())
+}
+
+/// Watches the recordings directory and reports coalesced per-recording changes
+pub struct RecordingsWatcher {
+    _watcher: RecommendedWatcher,
+    root: PathBuf,
+}
+
+impl RecordingsWatcher {
+    /// Start watching; `on_change` is called from a background thread after each debounce window
+    pub fn start<F>(recordings_path: PathBuf, debounce: Duration, on_change: F) -> Result<Self, String>
+    where
+        F: Fn(RecordingChange) + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel::<PathBuf>();
+
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+            match result {
+                Ok(event) => {
+                    for path in event.paths {
+                        let _ = tx.send(path);
+                    }
+                }
+                Err(e) => log::warn!("Watcher error: {}", e),
+            }
+        })
+        .map_err(|e| format!("Failed to create recordings watcher: {}", e))?;
+
+        watcher
+            .watch(&recordings_path, RecursiveMode::Recursive)
+            .map_err(|e| format!("Failed to watch {}: {}", recordings_path.display(), e))?;
+
+        log::info!("👀 Watching {} (debounce {:?})", recordings_path.display(), debounce);
+        let root = recordings_path.clone();
+
+        std::thread::spawn(move || {
+            let mut coalescer = ChangeCoalescer::new(debounce);
+            let ignore_file = recordings_path.join(IGNORE_FILE_NAME);
+            let mut ignore = RecordingsIgnore::load(&recordings_path);
+            let record = |path: PathBuf, ignore: &mut RecordingsIgnore, coalescer: &mut ChangeCoalescer| {
+                if path == ignore_file {
+                    *ignore = RecordingsIgnore::load(&recordings_path);
+                    log::info!("Reloaded {}", ignore_file.display());
+                }
+                if let Some(name) = recording_name_for_path(&recordings_path, &path).filter(|name| !ignore.is_ignored(name)) {
+                    coalescer.record(name, Instant::now());
+                }
+            };
+
+            loop {
+                let received = match coalescer.next_wakeup(Instant::now()) {
+                    Some(wait) => rx.recv_timeout(wait),
+                    None => rx.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+                };
+
+                match received {
+                    Ok(path) => {
+                        record(path, &mut ignore, &mut coalescer);
+                        // Drain whatever else is queued before re-detecting anything
+                        while let Ok(path) = rx.try_recv() {
+                            record(path, &mut ignore, &mut coalescer);
+                        }
+                    }
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+
+                for name in coalescer.drain_ready(Instant::now()) {
+                    on_change(load_recording_change(&recordings_path, name));
+                }
+            }
+
+            log::info!("Recordings watcher stopped");
+        });
+
+        Ok(Self { _watcher: watcher, root })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+/// The current state of a recording under `recordings_path`, as reported to the frontend
+pub fn load_recording_change(recordings_path: &Path, name: String) -> RecordingChange {
+    let path = recordings_path.join(&name);
+    let recording = if FileScanner::is_valid_recording_dir(&path) {
+        Recording::from_path(path).ok().map(|mut recording| {
+            update_recording_status(&mut recording);
+            recording
+        })
+    } else {
+        None
+    };
+
+    RecordingChange { name, recording }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coalescer_waits_for_quiet_period() {
+        let start = Instant::now();
+        let mut coalescer = ChangeCoalescer::new(Duration::from_millis(500));
+
+        // A burst of frame writes for the same recording
+        for i in 0..1000 {
+            coalescer.record("rec".to_string(), start + Duration::from_micros(i));
+        }
+
+        assert!(coalescer.drain_ready(start + Duration::from_millis(100)).is_empty());
+        assert_eq!(coalescer.drain_ready(start + Duration::from_millis(600)), vec!["rec".to_string()]);
+        assert!(coalescer.next_wakeup(start).is_none());
+    }
+
+    #[test]
+    fn test_coalescer_tracks_recordings_independently() {
+        let start = Instant::now();
+        let mut coalescer = ChangeCoalescer::new(Duration::from_millis(500));
+
+        coalescer.record("a".to_string(), start);
+        coalescer.record("b".to_string(), start + Duration::from_millis(400));
+
+        assert_eq!(coalescer.drain_ready(start + Duration::from_millis(500)), vec!["a".to_string()]);
+        assert_eq!(coalescer.next_wakeup(start + Duration::from_millis(500)), Some(Duration::from_millis(400)));
+    }
+
+    #[test]
+    fn test_recording_name_for_path() {
+        let root = Path::new("/recordings");
+
+        assert_eq!(
+            recording_name_for_path(root, Path::new("/recordings/rec_1/blender/render/frame_0001.png")),
+            Some("rec_1".to_string())
+        );
+        assert_eq!(recording_name_for_path(root, Path::new("/recordings/.trash/x")), None);
+        assert_eq!(recording_name_for_path(root, Path::new("/elsewhere/rec_1")), None);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+
+/// Settings key holding the rename journal
+pub const RENAME_JOURNAL_KEY: &str = "rename_journal";
+/// Renames remembered for undo; older ones are forgotten
+pub const RENAME_JOURNAL_MAX_ENTRIES: usize = 100;
+
+/// One rename of a recording
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RenameJournalEntry {
+    pub old_name: String,
+    pub new_name: String,
+    pub renamed_at: u64, // Unix timestamp in seconds
+    /// Files whose path references were rewritten, relative to the recording
+    pub files_touched: Vec<String>,
+}
+
+/// Recent renames, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct RenameJournal(Vec<RenameJournalEntry>);
+
+impl RenameJournal {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(RENAME_JOURNAL_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        settings.set(RENAME_JOURNAL_KEY, self)
+    }
+
+    pub fn list(&self) -> &[RenameJournalEntry] {
+        &self.0
+    }
+
+    pub fn record(&mut self, entry: RenameJournalEntry) {
+        self.0.push(entry);
+        let excess = self.0.len().saturating_sub(RENAME_JOURNAL_MAX_ENTRIES);
+        self.0.drain(..excess);
+    }
+
+    /// The latest rename that gave a recording its current name
+    pub fn last_for(&self, recording_name: &str) -> Option<&RenameJournalEntry> {
+        self.0.iter().rev().find(|e| e.new_name == recording_name)
+    }
+
+    /// Forget the latest rename to `recording_name`, once it has been undone
+    pub fn remove_last_for(&mut self, recording_name: &str) -> Option<RenameJournalEntry> {
+        let index = self.0.iter().rposition(|e| e.new_name == recording_name)?;
+        Some(self.0.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(old_name: &str, new_name: &str) -> RenameJournalEntry {
+        RenameJournalEntry {
+            old_name: old_name.to_string(),
+            new_name: new_name.to_string(),
+            renamed_at: 0,
+            files_touched: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_undo_walks_back_a_chain_of_renames() {
+        let mut journal = RenameJournal::default();
+        journal.record(entry("a", "b"));
+        journal.record(entry("x", "y"));
+        journal.record(entry("b", "c"));
+
+        assert_eq!(journal.last_for("c").map(|e| e.old_name.as_str()), Some("b"));
+        assert!(journal.last_for("a").is_none());
+        assert_eq!(journal.remove_last_for("c").unwrap().old_name, "b");
+        assert_eq!(journal.remove_last_for("b").unwrap().old_name, "a");
+        assert_eq!(journal.list(), &[entry("x", "y")]);
+    }
+
+    #[test]
+    fn test_record_keeps_recent_entries_and_persists() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+        let mut journal = RenameJournal::load(&settings).unwrap();
+        for n in 0..RENAME_JOURNAL_MAX_ENTRIES + 2 {
+            journal.record(entry(&n.to_string(), &(n + 1).to_string()));
+        }
+        journal.save(&settings).unwrap();
+
+        let loaded = RenameJournal::load(&settings).unwrap();
+        assert_eq!(loaded.list().len(), RENAME_JOURNAL_MAX_ENTRIES);
+        assert_eq!(loaded.list()[0].old_name, "2");
+    }
+}
@@ -0,0 +1,117 @@
+use crate::models::MediaInfo;
+use crate::services::{rendered_videos, MediaProbe, StateManifest, RENDER_VIDEO_EXTENSIONS};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// A render made on another machine and copied back into the recording
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttachedRender {
+    /// The file as it was picked, before it was copied or moved
+    pub source: String,
+    /// Relative to the recording directory
+    pub video: String,
+    pub size_bytes: u64,
+    /// The source was moved rather than copied
+    pub moved: bool,
+    /// Unix timestamp in seconds
+    pub attached_at: u64,
+    pub media: MediaInfo,
+}
+
+/// Place an externally rendered video into blender/render and mark the recording Rendered
+pub fn attach_render(recording_path: &Path, source: &Path, move_file: bool) -> Result<AttachedRender, String> {
+    attach_render_with(recording_path, source, move_file, MediaProbe::probe_file)
+}
+
+fn attach_render_with<F>(recording_path: &Path, source: &Path, move_file: bool, probe: F) -> Result<AttachedRender, String>
+where
+    F: Fn(&Path) -> Option<MediaInfo>,
+{
+    let file_name = source
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|_| source.is_file())
+        .ok_or_else(|| format!("{} is not a file", source.display()))?;
+    let is_video = source
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| RENDER_VIDEO_EXTENSIONS.contains(&e.to_lowercase().as_str()));
+    if !is_video {
+        return Err(format!("{} is not a video ({})", file_name, RENDER_VIDEO_EXTENSIONS.join(", ")));
+    }
+    let media = probe(source).ok_or_else(|| format!("ffprobe can't read {}", source.display()))?;
+    if media.width.is_none() {
+        return Err(format!("{} has no video stream", file_name));
+    }
+
+    let render_dir = recording_path.join("blender").join("render");
+    if let Some(existing) = rendered_videos(&render_dir).first() {
+        return Err(format!("The recording already has a render: {}", existing.display()));
+    }
+    fs::create_dir_all(&render_dir).map_err(|e| format!("Failed to create {}: {}", render_dir.display(), e))?;
+    let target = render_dir.join(file_name);
+    let moved = move_file && fs::rename(source, &target).is_ok();
+    if !moved {
+        // Copied under a .part. name that doesn't count as a render until it's complete
+        let partial = render_dir.join(format!(".{}.part.tmp", file_name));
+        let copied = fs::copy(source, &partial)
+            .and_then(|_| fs::rename(&partial, &target))
+            .map_err(|e| format!("Failed to copy {}: {}", source.display(), e));
+        if let Err(e) = copied {
+            let _ = fs::remove_file(&partial);
+            return Err(e);
+        }
+        if move_file {
+            fs::remove_file(source)
+                .map_err(|e| format!("Copied, but failed to remove the original {}: {}", source.display(), e))?;
+        }
+    }
+
+    let attached = AttachedRender {
+        source: source.to_string_lossy().to_string(),
+        video: target.strip_prefix(recording_path).unwrap_or(&target).to_string_lossy().to_string(),
+        size_bytes: fs::metadata(&target).map(|m| m.len()).unwrap_or(0),
+        moved: move_file,
+        attached_at: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+        media,
+    };
+    StateManifest::record_attached_render(recording_path, attached.clone())?;
+    Ok(attached)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RecordingStatus;
+    use crate::services::StatusDetector;
+    use tempfile::TempDir;
+
+    fn probed(_: &Path) -> Option<MediaInfo> {
+        Some(MediaInfo { width: Some(1920), height: Some(1080), ..MediaInfo::default() })
+    }
+
+    #[test]
+    fn test_attached_render_makes_the_recording_rendered() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path().join("gig");
+        fs::create_dir_all(&recording).unwrap();
+        fs::write(recording.join("gig.mkv"), "raw").unwrap();
+        let source = temp_dir.path().join("gig_final.mp4");
+        fs::write(&source, "rendered on the desktop").unwrap();
+
+        assert!(attach_render_with(&recording, &temp_dir.path().join("notes.txt"), false, probed).is_err());
+        assert!(attach_render_with(&recording, &source, false, |_| None).is_err());
+
+        let attached = attach_render_with(&recording, &source, true, probed).unwrap();
+        assert_eq!(attached.video, "blender/render/gig_final.mp4");
+        assert!(!source.exists() && recording.join(&attached.video).is_file());
+        assert_eq!(StatusDetector::detect_status(&recording), RecordingStatus::Rendered);
+        let manifest = StateManifest::load(&recording).unwrap();
+        assert_eq!(manifest.attached_render, Some(attached));
+
+        fs::write(&source, "another one").unwrap();
+        assert!(attach_render_with(&recording, &source, false, probed).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+/// Settings key for the preferred render device
+pub const RENDER_DEVICE_KEY: &str = "render_device";
+
+/// Prints one line per Cycles compute device of every backend this machine supports
+pub const BLENDER_LIST_DEVICES_SCRIPT: &str = "import bpy
+p = bpy.context.preferences.addons['cycles'].preferences
+for backend in ('OPTIX', 'CUDA', 'HIP', 'ONEAPI', 'METAL'):
+    try:
+        p.compute_device_type = backend
+    except TypeError:
+        continue
+    p.get_devices()
+    for d in p.devices:
+        print('FERMATA_DEVICE', backend, d.type, d.id, d.name, sep='\\t', flush=True)
+";
+
+const DEVICE_PREFIX: &str = "FERMATA_DEVICE";
+
+/// Backend of the CPU pseudo-device, always available
+pub const CPU_BACKEND: &str = "CPU";
+
+const BACKENDS: [&str; 6] = [CPU_BACKEND, "OPTIX", "CUDA", "HIP", "ONEAPI", "METAL"];
+
+/// A Cycles compute device. Eevee always renders on the GPU driving the
+/// display, so the choice only affects Cycles scenes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RenderDevice {
+    /// Cycles compute backend, e.g. "OPTIX", or "CPU"
+    pub backend: String,
+    pub id: String,
+    pub name: String,
+}
+
+/// Preferred device for renders; the .blend file's choice when unset
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RenderDeviceSettings {
+    pub device: Option<RenderDevice>,
+}
+
+impl RenderDevice {
+    pub fn cpu() -> Self {
+        Self {
+            backend: CPU_BACKEND.to_string(),
+            id: CPU_BACKEND.to_string(),
+            name: "CPU".to_string(),
+        }
+    }
+
+    /// Devices found in the output of BLENDER_LIST_DEVICES_SCRIPT, CPU first.
+    /// Blender lists the CPU under every backend; it is reported once.
+    pub fn parse_list(output: &str) -> Vec<Self> {
+        let mut devices = vec![Self::cpu()];
+        for line in output.lines() {
+            let fields: Vec<&str> = line.trim_end().split('\t').collect();
+            let [DEVICE_PREFIX, backend, kind, id, name] = fields[..] else {
+                continue;
+            };
+            if kind == CPU_BACKEND {
+                continue;
+            }
+            let device = Self {
+                backend: backend.to_string(),
+                id: id.to_string(),
+                name: name.to_string(),
+            };
+            if !devices.contains(&device) {
+                devices.push(device);
+            }
+        }
+        devices
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !BACKENDS.contains(&self.backend.as_str()) {
+            return Err(format!("Unknown compute backend '{}'", self.backend));
+        }
+        if self.id.trim().is_empty() {
+            return Err("Render device has no id".to_string());
+        }
+        Ok(())
+    }
+
+    /// Python selecting this device, run before the render starts
+    pub fn blender_script(&self) -> String {
+        if self.backend == CPU_BACKEND {
+            return "import bpy; bpy.context.scene.cycles.device = 'CPU'".to_string();
+        }
+        // A JSON string is also a valid Python string literal
+        let id = serde_json::to_string(&self.id).unwrap_or_default();
+        format!(
+            "import bpy
+p = bpy.context.preferences.addons['cycles'].preferences
+p.compute_device_type = '{}'
+p.get_devices()
+for d in p.devices:
+    d.use = d.id == {}
+bpy.context.scene.cycles.device = 'GPU'
+",
+            self.backend, id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_device_list() {
+        let output = "Blender 4.1.1\n\
+            FERMATA_DEVICE\tOPTIX\tOPTIX\tOPTIX_0000:01:00\tNVIDIA GeForce RTX 3080\n\
+            FERMATA_DEVICE\tCUDA\tCUDA\tCUDA_0000:01:00\tNVIDIA GeForce RTX 3080\n\
+            FERMATA_DEVICE\tCUDA\tCPU\tCPU\tAMD Ryzen 9 5950X\n\
+            FERMATA_DEVICE\tCUDA\tCUDA\tCUDA_0000:01:00\tNVIDIA GeForce RTX 3080\n\
+            FERMATA_DEVICE\tbroken line\n";
+
+        let devices = RenderDevice::parse_list(output);
+        assert_eq!(devices.len(), 3);
+        assert_eq!(devices[0], RenderDevice::cpu());
+        assert_eq!(devices[1].backend, "OPTIX");
+        assert_eq!(devices[2].id, "CUDA_0000:01:00");
+        assert_eq!(devices[2].name, "NVIDIA GeForce RTX 3080");
+    }
+
+    #[test]
+    fn test_device_script_and_validation() {
+        assert!(RenderDevice::cpu().blender_script().ends_with("device = 'CPU'"));
+
+        let gpu = RenderDevice {
+            backend: "HIP".to_string(),
+            id: "HIP_0000:03:00".to_string(),
+            name: "AMD Radeon".to_string(),
+        };
+        let script = gpu.blender_script();
+        assert!(script.contains("p.compute_device_type = 'HIP'"));
+        assert!(script.contains("d.use = d.id == \"HIP_0000:03:00\""));
+        assert!(gpu.validate().is_ok());
+
+        assert!(RenderDevice { backend: "VULKAN".to_string(), ..gpu.clone() }.validate().is_err());
+        assert!(RenderDevice { id: " ".to_string(), ..gpu }.validate().is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use crate::services::{find_frame_sequence, process_arg, rendered_videos, FrameSequence, RESUMED_RENDER_FILE};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// How finalize_render turns the render output into the final mp4
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct FinalizeOptions {
+    /// ffmpeg encoder, e.g. "libx264" or "libx265"; "copy" remuxes a rendered video as it is
+    pub video_codec: String,
+    /// Quality of the encode, 0 (lossless) to 51
+    pub crf: u32,
+    /// Mux in the extracted main audio instead of the render's own
+    pub mux_main_audio: bool,
+    pub audio_codec: String,
+    /// ffmpeg bitrate, e.g. "192k"; codec default when unset
+    pub audio_bitrate: Option<String>,
+    /// Frame rate of a frame sequence; the render settings' or the project's when unset
+    pub fps: Option<f64>,
+}
+
+impl Default for FinalizeOptions {
+    fn default() -> Self {
+        Self {
+            video_codec: "libx264".to_string(),
+            crf: 18,
+            mux_main_audio: true,
+            audio_codec: "aac".to_string(),
+            audio_bitrate: None,
+            fps: None,
+        }
+    }
+}
+
+/// What gets finalized: the frames of an image-sequence render, else a rendered video
+#[derive(Debug, Clone, PartialEq)]
+pub enum FinalizeInput {
+    Frames(FrameSequence),
+    Video(PathBuf),
+}
+
+impl FinalizeInput {
+    /// Frames in `render_dir` win, Blender was then used in image-sequence mode
+    pub fn find(render_dir: &Path) -> Result<Self, String> {
+        if let Some(sequence) = find_frame_sequence(render_dir) {
+            let (first, last) = match (sequence.frames.first(), sequence.frames.last()) {
+                (Some(first), Some(last)) => (*first, *last),
+                _ => return Err("Frame sequence is empty".to_string()),
+            };
+            let missing = (last - first + 1) as usize - sequence.frames.len();
+            if missing > 0 {
+                return Err(format!("{} frames between {} and {} are missing - resume the render first", missing, first, last));
+            }
+            return Ok(Self::Frames(sequence));
+        }
+        rendered_videos(render_dir)
+            .into_iter()
+            .next()
+            .map(Self::Video)
+            .ok_or_else(|| "No rendered frames or video found - run the render step first".to_string())
+    }
+}
+
+impl FinalizeOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.video_codec.trim().is_empty() || self.audio_codec.trim().is_empty() {
+            return Err("Codecs cannot be empty".to_string());
+        }
+        if self.crf > 51 {
+            return Err(format!("CRF must be between 0 and 51, got {}", self.crf));
+        }
+        if self.fps.is_some_and(|fps| !fps.is_finite() || fps <= 0.0) {
+            return Err("Frame rate must be positive".to_string());
+        }
+        Ok(())
+    }
+
+    /// The finalized video, next to the frames or video it is made from
+    pub fn output_path(render_dir: &Path) -> PathBuf {
+        render_dir.join(RESUMED_RENDER_FILE)
+    }
+
+    /// ffmpeg arguments writing `input` to `output`; `fps` is only used for frames
+    pub fn ffmpeg_args(&self, input: &FinalizeInput, fps: f64, audio: Option<&Path>, output: &Path) -> Vec<OsString> {
+        let mut args: Vec<OsString> = vec!["-y".into()];
+        match input {
+            FinalizeInput::Frames(sequence) => args.extend([
+                "-framerate".into(),
+                fps.to_string().into(),
+                "-start_number".into(),
+                sequence.frames.first().copied().unwrap_or(1).to_string().into(),
+                "-i".into(),
+                process_arg(&sequence.ffmpeg_pattern()),
+            ]),
+            FinalizeInput::Video(video) => args.extend(["-i".into(), process_arg(video)]),
+        }
+
+        let copy_video = self.video_codec == "copy" && matches!(input, FinalizeInput::Video(_));
+        match audio {
+            Some(audio) => args.extend([
+                "-i".into(),
+                process_arg(audio),
+                "-map".into(),
+                "0:v".into(),
+                "-map".into(),
+                "1:a".into(),
+                "-shortest".into(),
+            ]),
+            // The render's own audio, if it has any
+            None => args.extend(["-map".into(), "0:v".into(), "-map".into(), "0:a?".into()]),
+        }
+        if audio.is_some() || matches!(input, FinalizeInput::Video(_)) {
+            args.extend(["-c:a".into(), self.audio_codec.clone().into()]);
+            if let Some(bitrate) = &self.audio_bitrate {
+                args.extend(["-b:a".into(), bitrate.into()]);
+            }
+        }
+
+        if copy_video {
+            args.extend(["-c:v".into(), "copy".into()]);
+        } else {
+            // Frames can't be copied into a video stream, so "copy" encodes them with the default
+            let codec = if self.video_codec == "copy" { Self::default().video_codec } else { self.video_codec.clone() };
+            args.extend([
+                "-c:v".into(),
+                codec.into(),
+                "-crf".into(),
+                self.crf.to_string().into(),
+                "-pix_fmt".into(),
+                "yuv420p".into(),
+            ]);
+        }
+        args.extend(["-movflags".into(), "+faststart".into(), process_arg(output)]);
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn strings(args: Vec<OsString>) -> Vec<String> {
+        args.into_iter().map(|arg| arg.to_string_lossy().to_string()).collect()
+    }
+
+    #[test]
+    fn test_input_and_args() {
+        let temp_dir = TempDir::new().unwrap();
+        let render_dir = temp_dir.path();
+        assert!(FinalizeInput::find(render_dir).is_err());
+
+        fs::write(render_dir.join("render.mkv"), "video").unwrap();
+        let video = FinalizeInput::find(render_dir).unwrap();
+        assert_eq!(video, FinalizeInput::Video(render_dir.join("render.mkv")));
+        let remux = FinalizeOptions { video_codec: "copy".to_string(), mux_main_audio: false, ..FinalizeOptions::default() };
+        let args = strings(remux.ffmpeg_args(&video, 30.0, None, Path::new("final.mp4")));
+        assert!(args.windows(2).any(|w| w == ["-c:v", "copy"]));
+        assert!(args.windows(2).any(|w| w == ["-map", "0:a?"]));
+
+        for frame in [1, 2, 4] {
+            fs::write(render_dir.join(format!("frame_{:04}.exr", frame)), "exr").unwrap();
+        }
+        assert!(FinalizeInput::find(render_dir).unwrap_err().contains("1 frames"));
+        fs::write(render_dir.join("frame_0003.exr"), "exr").unwrap();
+        let frames = FinalizeInput::find(render_dir).unwrap();
+        let args = strings(FinalizeOptions::default().ffmpeg_args(&frames, 25.0, Some(Path::new("Mic.m4a")), Path::new("final.mp4")));
+        assert!(args.windows(2).any(|w| w == ["-framerate", "25"]));
+        assert!(args.windows(2).any(|w| w == ["-crf", "18"]));
+        assert!(args.windows(2).any(|w| w == ["-map", "1:a"]));
+
+        assert!(FinalizeOptions { crf: 60, ..FinalizeOptions::default() }.validate().is_err());
+    }
+}
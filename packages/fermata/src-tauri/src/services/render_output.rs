@@ -0,0 +1,101 @@
+use crate::services::{BlenderProgressSnapshot, FrameSequence, RESUMED_RENDER_FILE};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Containers a finished render can be written in
+pub const RENDER_VIDEO_EXTENSIONS: [&str; 5] = ["mp4", "mkv", "avi", "mov", "webm"];
+
+/// Rendered videos anywhere under `render_dir`, sorted by path with a finalized
+/// `final.mp4` first. Empty files and `.part.` files of an encode still running do not count.
+pub fn rendered_videos(render_dir: &Path) -> Vec<PathBuf> {
+    let mut videos: Vec<PathBuf> = WalkDir::new(render_dir)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| entry.metadata().is_ok_and(|m| m.len() > 0))
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            let is_video = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| RENDER_VIDEO_EXTENSIONS.contains(&e.to_lowercase().as_str()));
+            is_video && !path.to_string_lossy().contains(".part.")
+        })
+        .collect();
+    let finalized = render_dir.join(RESUMED_RENDER_FILE);
+    videos.sort_by(|a, b| (*a != finalized).cmp(&(*b != finalized)).then_with(|| a.cmp(b)));
+    videos
+}
+
+/// The largest frame sequence in `render_dir` or any folder below it
+pub fn find_frame_sequence(render_dir: &Path) -> Option<FrameSequence> {
+    WalkDir::new(render_dir)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_dir())
+        .filter_map(|entry| FrameSequence::scan(entry.path()))
+        .max_by_key(|sequence| sequence.frames.len())
+}
+
+/// A frame sequence with every frame the last Blender run announced and no gaps,
+/// waiting to be encoded into a video
+pub fn complete_frame_sequence(recording_path: &Path) -> Option<FrameSequence> {
+    let sequence = find_frame_sequence(&recording_path.join("blender").join("render"))?;
+    let total = BlenderProgressSnapshot::load(recording_path)?.progress.total_frames?;
+    let (first, last) = (*sequence.frames.first()?, *sequence.frames.last()?);
+    let contiguous = (last - first + 1) as usize == sequence.frames.len();
+    (contiguous && sequence.frames.len() >= total as usize).then_some(sequence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::BlenderProgress;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rendered_videos_are_found_in_subfolders() {
+        let temp_dir = TempDir::new().unwrap();
+        let render_dir = temp_dir.path();
+        fs::create_dir_all(render_dir.join("take2/out")).unwrap();
+        fs::write(render_dir.join("take2/out/final.MOV"), "video").unwrap();
+        fs::write(render_dir.join("final.part.mp4"), "partial").unwrap();
+        fs::write(render_dir.join("empty.mp4"), "").unwrap();
+        fs::write(render_dir.join("frame_0001.png"), "png").unwrap();
+
+        assert_eq!(rendered_videos(render_dir), vec![render_dir.join("take2/out/final.MOV")]);
+
+        // A finalized video comes before the render it was made from
+        fs::write(render_dir.join(RESUMED_RENDER_FILE), "video").unwrap();
+        assert_eq!(rendered_videos(render_dir)[0], render_dir.join(RESUMED_RENDER_FILE));
+    }
+
+    #[test]
+    fn test_complete_frame_sequence() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+        let frames_dir = recording.join("blender/render/frames");
+        fs::create_dir_all(&frames_dir).unwrap();
+        for frame in 1..=3 {
+            fs::write(frames_dir.join(format!("frame_{:04}.png", frame)), "png").unwrap();
+        }
+
+        // Without knowing the frame count a sequence is never complete
+        assert!(complete_frame_sequence(recording).is_none());
+        assert_eq!(find_frame_sequence(&recording.join("blender/render")).unwrap().dir, frames_dir);
+
+        let snapshot = |total_frames| BlenderProgressSnapshot {
+            progress: BlenderProgress { current_frame: 3, total_frames: Some(total_frames), eta_seconds: None },
+            updated_at: 0,
+        };
+        snapshot(4).save(recording).unwrap();
+        assert!(complete_frame_sequence(recording).is_none());
+
+        snapshot(3).save(recording).unwrap();
+        assert_eq!(complete_frame_sequence(recording).unwrap().frames.len(), 3);
+
+        fs::remove_file(frames_dir.join("frame_0002.png")).unwrap();
+        assert!(complete_frame_sequence(recording).is_none());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::services::{process_arg, write_atomic};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Preview frames go here, next to but never mixed with blender/render/
+pub const RENDER_PREVIEW_DIR: &str = "render_preview";
+
+/// State of the last preview render, kept apart from the recording status
+pub const RENDER_PREVIEW_FILE: &str = ".fermata/render_preview.json";
+
+/// How much cheaper than the final render a preview is
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct PreviewOptions {
+    /// Resolution as a percentage of the scene's, 1-100
+    pub scale_percent: u32,
+    /// Render every Nth frame
+    pub frame_step: u32,
+}
+
+impl PreviewOptions {
+    pub fn new(scale_percent: u32, frame_step: u32) -> Result<Self, String> {
+        if !(1..=100).contains(&scale_percent) {
+            return Err(format!("Preview scale must be between 1 and 100%, got {}", scale_percent));
+        }
+        if frame_step == 0 {
+            return Err("Preview frame step must be at least 1".to_string());
+        }
+        Ok(Self { scale_percent, frame_step })
+    }
+
+    /// Blender arguments (before --render-anim) rendering the preview into `output_dir`
+    pub fn blender_args(&self, output_dir: &Path) -> Vec<OsString> {
+        vec![
+            "-o".into(),
+            process_arg(&output_dir.join("preview_####")),
+            "-j".into(),
+            self.frame_step.to_string().into(),
+            "--python-expr".into(),
+            format!("import bpy; bpy.context.scene.render.resolution_percentage = {}", self.scale_percent).into(),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviewStatus {
+    Running,
+    Done,
+    Failed(String),
+}
+
+/// Last preview render of a recording
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RenderPreview {
+    pub status: PreviewStatus,
+    pub options: PreviewOptions,
+    pub output_dir: PathBuf,
+    /// Unix timestamps in seconds
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+}
+
+impl RenderPreview {
+    pub fn started(recording_path: &Path, options: PreviewOptions) -> Self {
+        Self {
+            status: PreviewStatus::Running,
+            options,
+            output_dir: recording_path.join("blender").join(RENDER_PREVIEW_DIR),
+            started_at: now(),
+            finished_at: None,
+        }
+    }
+
+    pub fn finish(&mut self, status: PreviewStatus) {
+        self.status = status;
+        self.finished_at = Some(now());
+    }
+
+    pub fn load(recording_path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(recording_path.join(RENDER_PREVIEW_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self, recording_path: &Path) -> Result<(), String> {
+        let path = recording_path.join(RENDER_PREVIEW_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize render preview: {}", e))?;
+        write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_options_validation_and_args() {
+        assert!(PreviewOptions::new(0, 1).is_err());
+        assert!(PreviewOptions::new(101, 1).is_err());
+        assert!(PreviewOptions::new(25, 0).is_err());
+
+        let options = PreviewOptions::new(25, 10).unwrap();
+        let args = options.blender_args(Path::new("/rec/blender/render_preview"));
+        assert_eq!(args[..4], ["-o", "/rec/blender/render_preview/preview_####", "-j", "10"]);
+        assert!(args[5].to_string_lossy().ends_with("resolution_percentage = 25"));
+    }
+
+    #[test]
+    fn test_preview_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(RenderPreview::load(temp_dir.path()).is_none());
+
+        let mut preview = RenderPreview::started(temp_dir.path(), PreviewOptions::new(50, 5).unwrap());
+        assert!(preview.output_dir.ends_with("blender/render_preview"));
+        preview.finish(PreviewStatus::Failed("Blender crashed".to_string()));
+        preview.save(temp_dir.path()).unwrap();
+
+        let loaded = RenderPreview::load(temp_dir.path()).unwrap();
+        assert_eq!(loaded, preview);
+        assert!(loaded.finished_at.is_some());
+    }
+}
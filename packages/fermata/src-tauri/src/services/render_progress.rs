@@ -0,0 +1,222 @@
+use crate::services::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Last progress of the Blender render, kept so a reopened app can show how far it got
+pub const RENDER_PROGRESS_FILE: &str = ".fermata/render_progress.json";
+
+/// Printed before rendering so the parser knows the frame range and frame rate
+pub const BLENDER_FRAME_RANGE_SCRIPT: &str = "import bpy; s = bpy.context.scene; \
+    print('FERMATA_FRAME_RANGE', s.frame_start, s.frame_end, s.frame_step, s.render.fps / s.render.fps_base, flush=True)";
+
+const FRAME_RANGE_PREFIX: &str = "FERMATA_FRAME_RANGE";
+
+/// Progress events are sent on every new frame, otherwise at most this often
+const MIN_EVENT_INTERVAL_SECS: f64 = 1.0;
+
+/// Structured Blender render progress
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BlenderProgress {
+    pub current_frame: u32,
+    pub total_frames: Option<u32>,
+    pub eta_seconds: Option<u64>,
+}
+
+/// Frame range of the scene as printed by BLENDER_FRAME_RANGE_SCRIPT
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameRange {
+    pub start: u32,
+    pub end: u32,
+    pub step: u32,
+    pub fps: Option<f64>,
+}
+
+impl FrameRange {
+    /// `FERMATA_FRAME_RANGE 1 7200 1 29.97002997`
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let mut fields = line.trim().strip_prefix(FRAME_RANGE_PREFIX)?.split_whitespace();
+        let start: u32 = fields.next()?.parse().ok()?;
+        let end: u32 = fields.next()?.parse().ok()?;
+        let step: u32 = fields.next()?.parse().ok()?;
+        Some(Self {
+            start,
+            end: end.max(start),
+            step: step.max(1),
+            fps: fields.next().and_then(|f| f.parse().ok()).filter(|f: &f64| *f > 0.0),
+        })
+    }
+
+    /// Find the range line in a process's output
+    pub fn find_in(output: &str) -> Option<Self> {
+        output.lines().find_map(Self::parse_line)
+    }
+
+    pub fn frame_count(&self) -> u32 {
+        (self.end - self.start) / self.step + 1
+    }
+}
+
+/// Receives progress while a render runs, e.g. to forward it to the frontend
+pub type ProgressCallback = Arc<dyn Fn(&BlenderProgress) + Send + Sync>;
+
+/// Persisted progress with the time it was recorded
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BlenderProgressSnapshot {
+    #[serde(flatten)]
+    pub progress: BlenderProgress,
+    /// Unix timestamp in seconds
+    pub updated_at: u64,
+}
+
+impl BlenderProgressSnapshot {
+    pub fn load(recording_path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(recording_path.join(RENDER_PROGRESS_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self, recording_path: &Path) -> Result<(), String> {
+        let path = recording_path.join(RENDER_PROGRESS_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize render progress: {}", e))?;
+        write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// Turns Blender's stdout, fed line by line, into progress events
+#[derive(Debug, Default)]
+pub struct BlenderProgressTracker {
+    frame_range: Option<FrameRange>,
+    current_frame: Option<u32>,
+    /// Elapsed seconds when the first frame started
+    first_frame_at: Option<f64>,
+    last_event_at: Option<f64>,
+}
+
+impl BlenderProgressTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one stdout line; `elapsed_secs` is the time since the render started.
+    /// Returns an event when there is something new worth showing.
+    pub fn feed(&mut self, line: &str, elapsed_secs: f64) -> Option<BlenderProgress> {
+        if let Some(range) = FrameRange::parse_line(line) {
+            self.frame_range = Some(range);
+            return None;
+        }
+
+        let frame = parse_frame(line)?;
+        let new_frame = self.current_frame != Some(frame);
+        self.current_frame = Some(frame);
+        self.first_frame_at.get_or_insert(elapsed_secs);
+
+        let due = !self.last_event_at.is_some_and(|at| elapsed_secs - at < MIN_EVENT_INTERVAL_SECS);
+        if !new_frame && !due {
+            return None;
+        }
+        self.last_event_at = Some(elapsed_secs);
+
+        Some(BlenderProgress {
+            current_frame: frame,
+            total_frames: self.frame_range.map(|range| range.frame_count()),
+            eta_seconds: self.eta(frame, line, elapsed_secs),
+        })
+    }
+
+    fn eta(&self, frame: u32, line: &str, elapsed_secs: f64) -> Option<u64> {
+        let range = self.frame_range?;
+        let frames_done = frame.saturating_sub(range.start) / range.step;
+        let frames_left = range.end.saturating_sub(frame) / range.step + 1;
+
+        let eta = if frames_done > 0 {
+            let per_frame = (elapsed_secs - self.first_frame_at?) / frames_done as f64;
+            per_frame * frames_left as f64
+        } else {
+            // First frame: extrapolate from Cycles' own estimate for it
+            let remaining = field_seconds(line, "Remaining:")?;
+            let frame_time = field_seconds(line, "Time:").unwrap_or(0.0) + remaining;
+            remaining + frame_time * (frames_left - 1) as f64
+        };
+        Some(eta.max(0.0).round() as u64)
+    }
+}
+
+/// `Fra:123 Mem:...` -> 123
+fn parse_frame(line: &str) -> Option<u32> {
+    let rest = line.trim_start().strip_prefix("Fra:")?;
+    rest.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()
+}
+
+/// Duration after a label such as `Remaining:00:30.45` or `Time:01:02:03.50`
+fn field_seconds(line: &str, label: &str) -> Option<f64> {
+    let value = line.split('|').find_map(|part| part.trim().strip_prefix(label))?;
+    parse_duration(value.split_whitespace().next()?)
+}
+
+/// `MM:SS.ss` or `HH:MM:SS.ss`
+fn parse_duration(value: &str) -> Option<f64> {
+    value
+        .split(':')
+        .try_fold(0.0, |total, part| part.parse::<f64>().ok().map(|n| total * 60.0 + n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_frame_and_durations() {
+        assert_eq!(parse_frame("Fra:123 Mem:26.59M (Peak 27.15M) | Time:00:00.05 | Syncing Cube"), Some(123));
+        assert_eq!(parse_frame("Saved: '/tmp/0001.png'"), None);
+        assert_eq!(parse_duration("00:30.50"), Some(30.5));
+        assert_eq!(parse_duration("01:02:03.00"), Some(3723.0));
+        assert_eq!(field_seconds("Fra:1 | Time:00:10.00 | Remaining:00:30.00 | Sample 1/128", "Remaining:"), Some(30.0));
+    }
+
+    #[test]
+    fn test_parse_frame_range() {
+        let range = FrameRange::find_in("Blender 4.1\nFERMATA_FRAME_RANGE 1 7200 2 29.97\n").unwrap();
+        assert_eq!((range.start, range.end, range.step), (1, 7200, 2));
+        assert_eq!(range.fps, Some(29.97));
+        assert_eq!(range.frame_count(), 3600);
+        assert_eq!(FrameRange::parse_line("FERMATA_FRAME_RANGE 1 10 1").unwrap().fps, None);
+        assert!(FrameRange::parse_line("Fra:1").is_none());
+    }
+
+    #[test]
+    fn test_tracker_reports_frames_and_eta() {
+        let mut tracker = BlenderProgressTracker::new();
+        assert!(tracker.feed("FERMATA_FRAME_RANGE 1 100 1 25.0", 0.0).is_none());
+
+        let first = tracker.feed("Fra:1 Mem:1M | Time:00:01.00 | Remaining:00:01.00 | Sample 1/64", 0.0).unwrap();
+        assert_eq!(first.total_frames, Some(100));
+        assert_eq!(first.eta_seconds, Some(1 + 2 * 99));
+
+        // Same frame within the event interval stays quiet
+        assert!(tracker.feed("Fra:1 Mem:1M | Time:00:01.50 | Remaining:00:00.50", 0.5).is_none());
+
+        let later = tracker.feed("Fra:11 Mem:1M | Time:00:00.10 | Syncing", 20.0).unwrap();
+        assert_eq!(later.current_frame, 11);
+        assert_eq!(later.eta_seconds, Some(2 * 90));
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(BlenderProgressSnapshot::load(temp_dir.path()).is_none());
+
+        let snapshot = BlenderProgressSnapshot {
+            progress: BlenderProgress { current_frame: 42, total_frames: Some(100), eta_seconds: None },
+            updated_at: 1_700_000_000,
+        };
+        snapshot.save(temp_dir.path()).unwrap();
+        assert_eq!(BlenderProgressSnapshot::load(temp_dir.path()), Some(snapshot));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::services::{frame_number, process_arg, FrameRange, FRAME_EXTENSIONS};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Video encoded from the frame sequence once all frames exist
+pub const RESUMED_RENDER_FILE: &str = "final.mp4";
+
+/// Frames Blender has written so far, e.g. `frame_0001.png` .. `frame_0420.png`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameSequence {
+    pub dir: PathBuf,
+    pub prefix: String,
+    /// Zero-padded width of the frame number
+    pub digits: usize,
+    pub extension: String,
+    pub frames: BTreeSet<u32>,
+}
+
+/// What resume_render did or would do
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ResumePlan {
+    pub total_frames: u32,
+    pub existing_frames: usize,
+    /// Inclusive ranges of frames still to render
+    pub missing_ranges: Vec<(u32, u32)>,
+}
+
+impl FrameSequence {
+    /// The most common frame naming in `dir`; empty files (a frame cut short by
+    /// a crash) do not count as rendered
+    pub fn scan(dir: &Path) -> Option<Self> {
+        let mut sequences: Vec<FrameSequence> = Vec::new();
+
+        for entry in fs::read_dir(dir).ok()?.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let Some((stem, extension)) = file_name.rsplit_once('.') else {
+                continue;
+            };
+            if !FRAME_EXTENSIONS.contains(&extension.to_lowercase().as_str()) {
+                continue;
+            }
+            if entry.metadata().map(|m| m.len() == 0).unwrap_or(true) {
+                continue;
+            }
+            let Some(number) = frame_number(stem) else {
+                continue;
+            };
+
+            let digits = stem.len() - stem.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+            let prefix = &stem[..stem.len() - digits];
+            match sequences
+                .iter_mut()
+                .find(|s| s.prefix == prefix && s.extension == extension && s.digits == digits)
+            {
+                Some(sequence) => {
+                    sequence.frames.insert(number);
+                }
+                None => sequences.push(FrameSequence {
+                    dir: dir.to_path_buf(),
+                    prefix: prefix.to_string(),
+                    digits,
+                    extension: extension.to_string(),
+                    frames: BTreeSet::from([number]),
+                }),
+            }
+        }
+
+        sequences.into_iter().max_by_key(|s| s.frames.len())
+    }
+
+    /// Contiguous runs of frames of `range` that are not on disk yet
+    pub fn missing_ranges(&self, range: &FrameRange) -> Vec<(u32, u32)> {
+        let mut missing: Vec<(u32, u32)> = Vec::new();
+        let mut frame = range.start;
+        while frame <= range.end {
+            if !self.frames.contains(&frame) {
+                match missing.last_mut() {
+                    Some((_, end)) if *end + range.step == frame => *end = frame,
+                    _ => missing.push((frame, frame)),
+                }
+            }
+            frame += range.step;
+        }
+        missing
+    }
+
+    pub fn plan(&self, range: &FrameRange) -> ResumePlan {
+        ResumePlan {
+            total_frames: range.frame_count(),
+            existing_frames: self.frames.range(range.start..=range.end).count(),
+            missing_ranges: self.missing_ranges(range),
+        }
+    }
+
+    /// ffmpeg input pattern, e.g. `render/frame_%04d.png`
+    pub fn ffmpeg_pattern(&self) -> PathBuf {
+        self.dir.join(format!("{}%0{}d.{}", self.prefix, self.digits, self.extension))
+    }
+
+    /// ffmpeg arguments encoding the frames from `start` into an H.264 mp4,
+    /// muxing in `audio` when given
+    pub fn ffmpeg_encode_args(&self, start: u32, fps: f64, audio: Option<&Path>, output: &Path) -> Vec<OsString> {
+        let mut args = vec![
+            "-y".into(),
+            "-framerate".into(),
+            fps.to_string().into(),
+            "-start_number".into(),
+            start.to_string().into(),
+            "-i".into(),
+            process_arg(&self.ffmpeg_pattern()),
+        ];
+        if let Some(audio) = audio {
+            args.extend([
+                "-i".into(),
+                process_arg(audio),
+                "-map".into(),
+                "0:v".into(),
+                "-map".into(),
+                "1:a".into(),
+                "-c:a".into(),
+                "aac".into(),
+                "-shortest".into(),
+            ]);
+        }
+        args.extend([
+            "-c:v".into(),
+            "libx264".into(),
+            "-pix_fmt".into(),
+            "yuv420p".into(),
+            "-movflags".into(),
+            "+faststart".into(),
+            process_arg(output),
+        ]);
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn range(start: u32, end: u32) -> FrameRange {
+        FrameRange { start, end, step: 1, fps: Some(30.0) }
+    }
+
+    #[test]
+    fn test_scan_ignores_empty_and_foreign_files() {
+        let temp_dir = TempDir::new().unwrap();
+        for frame in [1, 2, 3, 6] {
+            fs::write(temp_dir.path().join(format!("frame_{:04}.png", frame)), "png").unwrap();
+        }
+        fs::write(temp_dir.path().join("frame_0007.png"), "").unwrap();
+        fs::write(temp_dir.path().join("thumb_01.jpg"), "jpg").unwrap();
+        fs::write(temp_dir.path().join("final.mp4"), "mp4").unwrap();
+
+        let sequence = FrameSequence::scan(temp_dir.path()).unwrap();
+        assert_eq!(sequence.prefix, "frame_");
+        assert_eq!(sequence.digits, 4);
+        assert_eq!(sequence.frames, BTreeSet::from([1, 2, 3, 6]));
+        assert_eq!(sequence.ffmpeg_pattern(), temp_dir.path().join("frame_%04d.png"));
+
+        let plan = sequence.plan(&range(1, 8));
+        assert_eq!(plan.total_frames, 8);
+        assert_eq!(plan.existing_frames, 4);
+        assert_eq!(plan.missing_ranges, vec![(4, 5), (7, 8)]);
+    }
+
+    #[test]
+    fn test_encode_args_mux_audio() {
+        let sequence = FrameSequence {
+            dir: PathBuf::from("render"),
+            prefix: "frame_".to_string(),
+            digits: 4,
+            extension: "png".to_string(),
+            frames: BTreeSet::from([1]),
+        };
+        let args = sequence.ffmpeg_encode_args(1, 30.0, Some(Path::new("main.m4a")), Path::new("final.mp4"));
+        assert_eq!(args[..7], ["-y", "-framerate", "30", "-start_number", "1", "-i", "render/frame_%04d.png"]);
+        assert!(args.windows(2).any(|w| w == ["-map", "1:a"]));
+        assert_eq!(args.last().unwrap(), "final.mp4");
+
+        let silent = sequence.ffmpeg_encode_args(1, 25.0, None, Path::new("final.mp4"));
+        assert!(!silent.iter().any(|a| a == "-shortest"));
+    }
+
+    #[test]
+    fn test_missing_ranges_follow_frame_step() {
+        let sequence = FrameSequence {
+            dir: PathBuf::new(),
+            prefix: String::new(),
+            digits: 4,
+            extension: "png".to_string(),
+            frames: BTreeSet::from([1, 3, 9]),
+        };
+        let stepped = FrameRange { start: 1, end: 11, step: 2, fps: None };
+        assert_eq!(sequence.missing_ranges(&stepped), vec![(5, 7), (11, 11)]);
+        assert!(sequence.missing_ranges(&range(1, 1)).is_empty());
+    }
+}
@@ -0,0 +1,224 @@
+use crate::models::FieldError;
+use crate::services::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Per-recording overrides of the .blend file's output settings
+pub const RENDER_SETTINGS_FILE: &str = ".fermata/render_settings.json";
+
+const MIN_DIMENSION: u32 = 16;
+const MAX_WIDTH: u32 = 7680;
+const MAX_HEIGHT: u32 = 4320;
+const MAX_FPS: u32 = 240;
+
+/// Video encoders Blender's FFmpeg output supports
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VideoEncoder {
+    H264,
+    Hevc,
+    Av1,
+    Prores,
+}
+
+impl VideoEncoder {
+    fn blender_name(&self) -> &'static str {
+        match self {
+            VideoEncoder::H264 => "H264",
+            VideoEncoder::Hevc => "HEVC",
+            VideoEncoder::Av1 => "AV1",
+            VideoEncoder::Prores => "PRORES",
+        }
+    }
+}
+
+/// Output containers Blender's FFmpeg output supports
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VideoContainer {
+    Mp4,
+    Mkv,
+    Mov,
+    Webm,
+}
+
+impl VideoContainer {
+    fn blender_name(&self) -> &'static str {
+        match self {
+            VideoContainer::Mp4 => "MPEG4",
+            VideoContainer::Mkv => "MKV",
+            VideoContainer::Mov => "QUICKTIME",
+            VideoContainer::Webm => "WEBM",
+        }
+    }
+}
+
+/// Render output overrides; unset fields keep what the .blend file contains
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RenderSettings {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fps: Option<u32>,
+    pub encoder: Option<VideoEncoder>,
+    pub container: Option<VideoContainer>,
+}
+
+impl RenderSettings {
+    /// Settings of a recording, the defaults when none were saved
+    pub fn load(recording_path: &Path) -> Self {
+        fs::read_to_string(recording_path.join(RENDER_SETTINGS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, recording_path: &Path) -> Result<(), String> {
+        self.validate()?;
+        let path = recording_path.join(RENDER_SETTINGS_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize render settings: {}", e))?;
+        write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self.field_errors().into_iter().next() {
+            Some(error) => Err(error.message),
+            None => Ok(()),
+        }
+    }
+
+    /// Every invalid override, by field; a resolution is reported on the dimension that's off
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        match (self.width, self.height) {
+            (Some(width), Some(height)) => {
+                let width_ok = (MIN_DIMENSION..=MAX_WIDTH).contains(&width);
+                if !width_ok || !(MIN_DIMENSION..=MAX_HEIGHT).contains(&height) {
+                    let message = format!(
+                        "Resolution {}x{} is outside {}x{} - {}x{}",
+                        width, height, MIN_DIMENSION, MIN_DIMENSION, MAX_WIDTH, MAX_HEIGHT
+                    );
+                    errors.push(FieldError::new(if width_ok { "height" } else { "width" }, message));
+                } else if width % 2 != 0 || height % 2 != 0 {
+                    // 4:2:0 chroma subsampling needs even dimensions
+                    let message = format!("Resolution {}x{} must have even dimensions", width, height);
+                    errors.push(FieldError::new(if width % 2 != 0 { "width" } else { "height" }, message));
+                }
+            }
+            (None, None) => {}
+            (None, _) => errors.push(FieldError::new("width", "Set both width and height, or neither")),
+            (_, None) => errors.push(FieldError::new("height", "Set both width and height, or neither")),
+        }
+
+        if let Some(fps) = self.fps {
+            if !(1..=MAX_FPS).contains(&fps) {
+                errors.push(FieldError::new("fps", format!("Frame rate {} is outside 1 - {}", fps, MAX_FPS)));
+            }
+        }
+
+        match (self.encoder, self.container) {
+            (Some(VideoEncoder::Prores), Some(container)) if container != VideoContainer::Mov => {
+                errors.push(FieldError::new("container", "ProRes can only be written to a .mov container"));
+            }
+            (Some(encoder), Some(VideoContainer::Webm)) if encoder != VideoEncoder::Av1 => {
+                errors.push(FieldError::new("encoder", "WebM only supports the AV1 encoder"));
+            }
+            _ => {}
+        }
+        errors
+    }
+
+    /// Python applying the overrides before rendering, None when nothing is overridden
+    pub fn blender_script(&self) -> Option<String> {
+        let mut statements = Vec::new();
+        if let (Some(width), Some(height)) = (self.width, self.height) {
+            statements.push(format!("r.resolution_x = {}", width));
+            statements.push(format!("r.resolution_y = {}", height));
+            statements.push("r.resolution_percentage = 100".to_string());
+        }
+        if let Some(fps) = self.fps {
+            statements.push(format!("r.fps = {}", fps));
+            statements.push("r.fps_base = 1.0".to_string());
+        }
+        if self.encoder.is_some() || self.container.is_some() {
+            statements.push("r.image_settings.file_format = 'FFMPEG'".to_string());
+        }
+        if let Some(container) = self.container {
+            statements.push(format!("r.ffmpeg.format = '{}'", container.blender_name()));
+        }
+        if let Some(encoder) = self.encoder {
+            statements.push(format!("r.ffmpeg.codec = '{}'", encoder.blender_name()));
+        }
+
+        if statements.is_empty() {
+            return None;
+        }
+        Some(format!("import bpy; r = bpy.context.scene.render; {}", statements.join("; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_bounds() {
+        assert!(RenderSettings::default().validate().is_ok());
+
+        let resolution = |width, height| RenderSettings { width, height, ..Default::default() };
+        assert!(resolution(Some(1920), Some(1080)).validate().is_ok());
+        assert!(resolution(Some(1920), None).validate().is_err());
+        assert!(resolution(Some(8), Some(8)).validate().is_err());
+        assert!(resolution(Some(1921), Some(1080)).validate().is_err());
+
+        assert!(RenderSettings { fps: Some(0), ..Default::default() }.validate().is_err());
+        assert!(RenderSettings { fps: Some(60), ..Default::default() }.validate().is_ok());
+
+        let codec = |encoder, container| RenderSettings {
+            encoder: Some(encoder),
+            container: Some(container),
+            ..Default::default()
+        };
+        assert!(codec(VideoEncoder::Prores, VideoContainer::Mp4).validate().is_err());
+        assert!(codec(VideoEncoder::Prores, VideoContainer::Mov).validate().is_ok());
+        assert!(codec(VideoEncoder::H264, VideoContainer::Webm).validate().is_err());
+    }
+
+    #[test]
+    fn test_blender_script() {
+        assert_eq!(RenderSettings::default().blender_script(), None);
+
+        let settings = RenderSettings {
+            width: Some(1280),
+            height: Some(720),
+            fps: Some(25),
+            encoder: Some(VideoEncoder::Hevc),
+            container: Some(VideoContainer::Mkv),
+        };
+        assert_eq!(
+            settings.blender_script().unwrap(),
+            "import bpy; r = bpy.context.scene.render; r.resolution_x = 1280; r.resolution_y = 720; \
+             r.resolution_percentage = 100; r.fps = 25; r.fps_base = 1.0; r.image_settings.file_format = 'FFMPEG'; \
+             r.ffmpeg.format = 'MKV'; r.ffmpeg.codec = 'HEVC'"
+        );
+    }
+
+    #[test]
+    fn test_settings_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(RenderSettings::load(temp_dir.path()), RenderSettings::default());
+
+        let settings = RenderSettings { fps: Some(30), encoder: Some(VideoEncoder::H264), ..Default::default() };
+        settings.save(temp_dir.path()).unwrap();
+        assert_eq!(RenderSettings::load(temp_dir.path()), settings);
+
+        let invalid = RenderSettings { fps: Some(1000), ..Default::default() };
+        assert!(invalid.save(temp_dir.path()).is_err());
+    }
+}
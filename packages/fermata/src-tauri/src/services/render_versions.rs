@@ -0,0 +1,184 @@
+use crate::services::{rendered_videos, write_atomic, RenderSettings, StepLogs};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Folder under blender/ the Render step writes to, uploaded unless another version is promoted
+pub const DEFAULT_RENDER_VERSION: &str = "render";
+
+/// Render version picked for upload; missing means the default one
+pub const PROMOTED_RENDER_FILE: &str = ".fermata/promoted_render.json";
+
+/// What a render ran with, recorded in its step log
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RenderRunOptions {
+    /// Preset of the animation config the blend was set up from
+    pub preset: Option<String>,
+    pub settings: RenderSettings,
+}
+
+/// A render output kept next to the others, e.g. blender/render_v2 from another preset
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RenderVersion {
+    /// Folder under blender/, e.g. "render", "render_v2" or "render_preview"
+    pub name: String,
+    /// Relative to the recording directory
+    pub video: String,
+    pub size_bytes: u64,
+    /// Local time the video was written, in the format of step log ids
+    pub rendered_at: Option<String>,
+    /// Step log of the render that wrote the video, when one is found
+    pub log_id: Option<String>,
+    /// None for renders logged before the options were recorded
+    pub run: Option<RenderRunOptions>,
+    pub promoted: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PromotedRender {
+    version: String,
+}
+
+pub struct RenderVersions;
+
+impl RenderVersions {
+    /// Render folders holding a video, the default one first
+    pub fn list(recording_path: &Path) -> Vec<RenderVersion> {
+        let promoted = Self::promoted(recording_path);
+        let logs: Vec<_> = StepLogs::list(recording_path)
+            .into_iter()
+            .rev()
+            .filter(|log| log.success && log.step == "render")
+            .collect();
+
+        version_dirs(recording_path)
+            .into_iter()
+            .filter_map(|(name, dir)| {
+                let video = rendered_videos(&dir).into_iter().next()?;
+                let metadata = fs::metadata(&video).ok()?;
+                let rendered_at = metadata
+                    .modified()
+                    .ok()
+                    .map(|time| DateTime::<Local>::from(time).format("%Y%m%d-%H%M%S").to_string());
+                // Logs are written as the render finishes: the first one after the video wrote it
+                let log = rendered_at.as_ref().and_then(|at| logs.iter().find(|log| log.timestamp >= *at));
+                Some(RenderVersion {
+                    video: video.strip_prefix(recording_path).unwrap_or(&video).to_string_lossy().to_string(),
+                    size_bytes: metadata.len(),
+                    rendered_at,
+                    log_id: log.map(|log| log.id.clone()),
+                    run: log.and_then(|log| serde_json::from_value(log.options.clone()?).ok()),
+                    promoted: name == promoted,
+                    name,
+                })
+            })
+            .collect()
+    }
+
+    /// Version the Upload step uses; the default one when the promoted folder is gone
+    pub fn promoted(recording_path: &Path) -> String {
+        fs::read_to_string(recording_path.join(PROMOTED_RENDER_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<PromotedRender>(&content).ok())
+            .map(|promoted| promoted.version)
+            .filter(|version| recording_path.join("blender").join(version).is_dir())
+            .unwrap_or_else(|| DEFAULT_RENDER_VERSION.to_string())
+    }
+
+    /// Make a version with a video the one the Upload step uses
+    pub fn promote(recording_path: &Path, version: &str) -> Result<RenderVersion, String> {
+        let versions = Self::list(recording_path);
+        let promoted = versions
+            .into_iter()
+            .find(|v| v.name == version)
+            .ok_or_else(|| format!("Render version '{}' not found or has no video", version))?;
+
+        let path = recording_path.join(PROMOTED_RENDER_FILE);
+        if version == DEFAULT_RENDER_VERSION {
+            if path.exists() {
+                fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+            }
+        } else {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            let content = serde_json::to_string_pretty(&PromotedRender { version: version.to_string() })
+                .map_err(|e| format!("Failed to serialize promoted render: {}", e))?;
+            write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        }
+        Ok(RenderVersion { promoted: true, ..promoted })
+    }
+
+    /// Name to transcode a video of `version` under, so versions with the same
+    /// file name don't share a transcoded copy
+    pub fn transcode_source_name(version: &str, video: &Path) -> PathBuf {
+        let file_name = video.file_name().unwrap_or_default().to_string_lossy();
+        if version == DEFAULT_RENDER_VERSION {
+            PathBuf::from(file_name.as_ref())
+        } else {
+            PathBuf::from(format!("{}_{}", version, file_name))
+        }
+    }
+}
+
+/// `render` and the `render_*` folders under blender/, the default one first
+fn version_dirs(recording_path: &Path) -> Vec<(String, PathBuf)> {
+    let mut dirs: Vec<(String, PathBuf)> = fs::read_dir(recording_path.join("blender"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| (entry.file_name().to_string_lossy().to_string(), entry.path()))
+                .filter(|(name, _)| name == DEFAULT_RENDER_VERSION || name.starts_with("render_"))
+                .collect()
+        })
+        .unwrap_or_default();
+    dirs.sort_by(|a, b| (a.0 != DEFAULT_RENDER_VERSION).cmp(&(b.0 != DEFAULT_RENDER_VERSION)).then_with(|| a.0.cmp(&b.0)));
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::STEP_LOGS_DIR;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_list_and_promote_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        for dir in ["render", "render_v2", "render_frames", "renders"] {
+            fs::create_dir_all(path.join("blender").join(dir)).unwrap();
+        }
+        fs::write(path.join("blender/render_v2/final.mp4"), "v2").unwrap();
+        fs::write(path.join("blender/render/final.mp4"), "v1").unwrap();
+        fs::write(path.join("blender/renders/final.mp4"), "not a version").unwrap();
+        fs::create_dir_all(path.join(STEP_LOGS_DIR)).unwrap();
+        fs::write(
+            path.join(STEP_LOGS_DIR).join("render_99991231-235959.log"),
+            "step: render\nsuccess: true\noptions: {\"preset\":\"minimal\",\"settings\":{\"width\":1280,\"height\":720,\"fps\":null,\"encoder\":null,\"container\":null}}\n=== STDOUT ===\n",
+        )
+        .unwrap();
+
+        let versions = RenderVersions::list(path);
+        let names: Vec<&str> = versions.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["render", "render_v2"]);
+        assert!(versions[0].promoted);
+        assert_eq!(versions[1].video, Path::new("blender/render_v2/final.mp4").to_string_lossy());
+        assert_eq!(versions[1].run.as_ref().and_then(|run| run.preset.as_deref()), Some("minimal"));
+
+        assert!(RenderVersions::promote(path, "render_frames").is_err());
+        assert!(RenderVersions::promote(path, "render_v2").unwrap().promoted);
+        assert_eq!(RenderVersions::promoted(path), "render_v2");
+        assert_eq!(
+            RenderVersions::transcode_source_name("render_v2", Path::new("final.mp4")),
+            PathBuf::from("render_v2_final.mp4")
+        );
+
+        fs::remove_dir_all(path.join("blender/render_v2")).unwrap();
+        assert_eq!(RenderVersions::promoted(path), DEFAULT_RENDER_VERSION);
+        RenderVersions::promote(path, "render").unwrap();
+        assert!(!path.join(PROMOTED_RENDER_FILE).exists());
+    }
+}
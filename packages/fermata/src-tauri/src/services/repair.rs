@@ -0,0 +1,203 @@
+use crate::services::{
+    check_writable, unreadable_settings, ScanSnapshotStore, SettingsStore, StateManifest, MEDIA_PROBE_FILE, STATE_MANIFEST_FILE
+};
+use chrono::Local;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairOutcome {
+    /// Nothing wrong
+    Ok,
+    Repaired,
+    /// Found broken; a dry run leaves it as it is
+    WouldRepair,
+    /// Broken and not repaired, see the detail for what to do
+    Failed,
+}
+
+/// One check of diagnose_and_repair and what it did
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RepairAction {
+    /// e.g. "settings", "recordings_root" or "state_manifest"
+    pub check: String,
+    pub outcome: RepairOutcome,
+    pub detail: String,
+}
+
+/// What diagnose_and_repair found and fixed. Each check stands on its own,
+/// so one that fails doesn't keep the others from running.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RepairReport {
+    pub dry_run: bool,
+    pub actions: Vec<RepairAction>,
+}
+
+impl RepairReport {
+    pub fn new(dry_run: bool) -> Self {
+        Self { dry_run, actions: Vec::new() }
+    }
+
+    /// Something was (or would be) repaired
+    pub fn repaired(&self) -> bool {
+        self.actions.iter().any(|a| matches!(a.outcome, RepairOutcome::Repaired | RepairOutcome::WouldRepair))
+    }
+
+    fn push(&mut self, check: &str, outcome: RepairOutcome, detail: String) {
+        self.actions.push(RepairAction { check: check.to_string(), outcome, detail });
+    }
+
+    /// Run `repair` unless this is a dry run
+    fn repair(&mut self, check: &str, problem: String, repair: impl FnOnce() -> Result<String, String>) {
+        if self.dry_run {
+            return self.push(check, RepairOutcome::WouldRepair, problem);
+        }
+        match repair() {
+            Ok(done) => self.push(check, RepairOutcome::Repaired, format!("{}; {}", problem, done)),
+            Err(e) => self.push(check, RepairOutcome::Failed, format!("{}; {}", problem, e)),
+        }
+    }
+
+    /// An unreadable settings file is moved aside, settings that don't parse are reset
+    /// after backing up the file
+    pub fn check_settings(&mut self, settings: &SettingsStore) {
+        let suffix = format!(".broken-{}", Local::now().format("%Y%m%d-%H%M%S"));
+        if let Err(e) = settings.check() {
+            return self.repair("settings", e, || {
+                settings.reset(&suffix).map(|backup| format!("moved to {}, defaults apply", backup.display()))
+            });
+        }
+        let unreadable = match unreadable_settings(settings) {
+            Ok(unreadable) => unreadable,
+            Err(e) => return self.push("settings", RepairOutcome::Failed, e),
+        };
+        if unreadable.is_empty() {
+            return self.push("settings", RepairOutcome::Ok, "Settings are readable".to_string());
+        }
+        let problem = unreadable.iter().map(|(key, e)| format!("'{}': {}", key, e)).collect::<Vec<_>>().join("; ");
+        self.repair("settings", format!("Unreadable settings {}", problem), || {
+            let backup = settings.backup(&suffix)?;
+            for (key, _) in &unreadable {
+                settings.remove(key)?;
+            }
+            Ok(format!("reset to defaults, the old file is in {}", backup.display()))
+        });
+    }
+
+    /// Roots can't be repaired from here, e.g. an unmounted drive, but are reported
+    pub fn check_roots(&mut self, roots: &[PathBuf]) {
+        for root in roots {
+            match check_writable(root) {
+                Ok(detail) => self.push("recordings_root", RepairOutcome::Ok, detail),
+                Err(e) => self.push("recordings_root", RepairOutcome::Failed, format!("{}; check the path and its permissions", e)),
+            }
+        }
+    }
+
+    pub fn check_scan_cache(&mut self, snapshots: &ScanSnapshotStore) {
+        if !snapshots.is_corrupt() {
+            return self.push("scan_cache", RepairOutcome::Ok, "Recordings cache is readable".to_string());
+        }
+        self.repair("scan_cache", "Recordings cache is unreadable".to_string(), || {
+            snapshots.clear().map(|_| "cleared, the next scan rebuilds it".to_string())
+        });
+    }
+
+    /// Rebuild unreadable state manifests and drop unreadable media probe caches of the
+    /// recordings in `roots`, without going through the scanner
+    pub fn check_recordings(&mut self, roots: &[PathBuf]) {
+        let mut checked = 0;
+        for recording in roots.iter().flat_map(|root| recording_dirs(root)) {
+            checked += 1;
+            let name = recording.file_name().unwrap_or_default().to_string_lossy().to_string();
+            if recording.join(STATE_MANIFEST_FILE).exists() && StateManifest::load(&recording).is_none() {
+                self.repair("state_manifest", format!("State of '{}' is unreadable", name), || {
+                    StateManifest::rebuild(&recording).map(|manifest| match manifest.status {
+                        Some(status) => format!("rebuilt from its files as {:?}", status),
+                        None => "rebuilt from its files".to_string(),
+                    })
+                });
+            }
+            let probe_cache = recording.join(MEDIA_PROBE_FILE);
+            if unreadable_json(&probe_cache) {
+                self.repair("media_cache", format!("Media cache of '{}' is unreadable", name), || {
+                    fs::remove_file(&probe_cache)
+                        .map(|_| "cleared".to_string())
+                        .map_err(|e| format!("Failed to remove {}: {}", probe_cache.display(), e))
+                });
+            }
+        }
+        if !self.actions.iter().any(|a| a.check == "state_manifest" || a.check == "media_cache") {
+            self.push("state_manifest", RepairOutcome::Ok, format!("State of {} recordings is readable", checked));
+        }
+    }
+}
+
+/// Visible directories of a root; unreadable roots were already reported by check_roots
+fn recording_dirs(root: &Path) -> Vec<PathBuf> {
+    fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn unreadable_json(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .map(|content| serde_json::from_str::<serde_json::Value>(&content).is_err())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RecordingStatus;
+    use crate::services::STEP_HOOKS_KEY;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_repairs_broken_settings_cache_and_manifests() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+        settings.set(STEP_HOOKS_KEY, &"not a list").unwrap();
+        let snapshots = ScanSnapshotStore::new(temp_dir.path().join("last_scan.json"));
+        fs::write(temp_dir.path().join("last_scan.json"), "{\"recordings\": [").unwrap();
+        let root = temp_dir.path().join("recordings");
+        fs::create_dir_all(root.join("gig/.fermata")).unwrap();
+        fs::write(root.join("gig").join(STATE_MANIFEST_FILE), "").unwrap();
+        let roots = vec![root.clone(), temp_dir.path().join("unmounted")];
+
+        let mut preview = RepairReport::new(true);
+        preview.check_settings(&settings);
+        preview.check_scan_cache(&snapshots);
+        assert!(preview.repaired());
+        assert!(settings.get::<serde_json::Value>(STEP_HOOKS_KEY).unwrap().is_string());
+
+        let mut report = RepairReport::new(false);
+        report.check_settings(&settings);
+        report.check_roots(&roots);
+        report.check_scan_cache(&snapshots);
+        report.check_recordings(&roots);
+        let outcomes: Vec<(&str, RepairOutcome)> = report.actions.iter().map(|a| (a.check.as_str(), a.outcome)).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("settings", RepairOutcome::Repaired),
+                ("recordings_root", RepairOutcome::Ok),
+                ("recordings_root", RepairOutcome::Failed),
+                ("scan_cache", RepairOutcome::Repaired),
+                ("state_manifest", RepairOutcome::Repaired),
+            ]
+        );
+        assert!(settings.get::<Option<serde_json::Value>>(STEP_HOOKS_KEY).unwrap().is_none());
+        assert!(!snapshots.is_corrupt());
+        assert_eq!(StateManifest::load(&root.join("gig")).and_then(|m| m.status), Some(RecordingStatus::Recorded));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::models::NextStep;
+use crate::services::{ConfigStore, StateManifest, StatusDetector, StepLogs};
+use serde::Serialize;
+use std::path::Path;
+
+/// Which step Retry runs again for a failed recording
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RetryPlan {
+    /// None when nothing records which step failed; one of `candidates` is picked by hand then
+    pub step: Option<NextStep>,
+    /// Where `step` was read from: "failed_marker", "state_manifest", "step_log" or "upload_results"
+    pub source: Option<String>,
+    /// Steps whose inputs are on disk, most advanced first
+    pub candidates: Vec<NextStep>,
+}
+
+impl RetryPlan {
+    /// Resolve the failed step from the `step:` line of a `.failed` marker, the state
+    /// manifest, the newest step log when it is a failure, or uploads that all failed
+    pub fn for_recording(recording_path: &Path) -> Self {
+        let recorded = marker_step(recording_path)
+            .map(|step| (step, "failed_marker"))
+            .or_else(|| StateManifest::load(recording_path).and_then(|m| m.failed_step).map(|step| (step, "state_manifest")))
+            .or_else(|| failed_step_log(recording_path).map(|step| (step, "step_log")))
+            .or_else(|| uploads_failed(recording_path).then_some((NextStep::Upload, "upload_results")));
+
+        Self {
+            step: recorded.as_ref().map(|(step, _)| step.clone()),
+            source: recorded.map(|(_, source)| source.to_string()),
+            candidates: candidates(recording_path),
+        }
+    }
+
+    /// The recorded step, or an error listing the candidates to choose from
+    pub fn resolve(&self, recording_name: &str) -> Result<NextStep, String> {
+        self.step.clone().ok_or_else(|| {
+            let candidates: Vec<String> = self.candidates.iter().map(|step| step.id()).collect();
+            format!("Cannot tell which step failed for '{}'; run one of: {}", recording_name, candidates.join(", "))
+        })
+    }
+}
+
+/// Step log names and custom step ids both name a step
+fn parse_step(name: &str) -> Option<NextStep> {
+    match name.trim().parse::<NextStep>() {
+        Ok(NextStep::Retry) => None,
+        Ok(step) => Some(step),
+        Err(_) if !name.trim().is_empty() => Some(NextStep::Custom(name.trim().to_string())),
+        Err(_) => None,
+    }
+}
+
+fn marker_step(recording_path: &Path) -> Option<NextStep> {
+    let content = std::fs::read_to_string(recording_path.join(".failed")).ok()?;
+    content.lines().next()?.strip_prefix("step:").and_then(parse_step)
+}
+
+fn failed_step_log(recording_path: &Path) -> Option<NextStep> {
+    // Newest first; a later success means that failure was already retried
+    StepLogs::list(recording_path).into_iter().next().filter(|log| !log.success).and_then(|log| parse_step(&log.step))
+}
+
+fn uploads_failed(recording_path: &Path) -> bool {
+    StatusDetector::read_upload_results(recording_path).is_some_and(|results| results.all_failed())
+}
+
+fn candidates(recording_path: &Path) -> Vec<NextStep> {
+    let blender = recording_path.join("blender");
+    let mut candidates = Vec::new();
+    if blender.join("render").exists() {
+        candidates.push(NextStep::Upload);
+    }
+    if blender.exists() {
+        candidates.push(NextStep::Render);
+    }
+    if !ConfigStore::configs(recording_path).is_empty() {
+        // Keeps the (possibly hand-edited) config
+        candidates.push(NextStep::BlendSetup);
+    }
+    if recording_path.join("analysis").exists() || blender.exists() {
+        candidates.push(NextStep::SetupRender);
+    }
+    if recording_path.join("extracted").exists() {
+        candidates.push(NextStep::Analyze);
+    }
+    candidates.push(NextStep::Extract);
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::ProcessResult;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_recorded_step_wins_over_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        fs::create_dir_all(path.join("blender/render")).unwrap();
+        fs::create_dir_all(path.join("extracted")).unwrap();
+
+        let unknown = RetryPlan::for_recording(path);
+        assert_eq!(unknown.step, None);
+        assert_eq!(unknown.candidates, [NextStep::Upload, NextStep::Render, NextStep::SetupRender, NextStep::Analyze, NextStep::Extract]);
+        assert!(unknown.resolve("gig").unwrap_err().contains("upload, render, setup_render, analyze, extract"));
+
+        let failed = ProcessResult::error("blender crashed".to_string());
+        StepLogs::write(path, &NextStep::Render, &failed).unwrap();
+        assert_eq!(RetryPlan::for_recording(path).resolve("gig"), Ok(NextStep::Render));
+
+        StateManifest::record_failure(path, &NextStep::SetupRender).unwrap();
+        let plan = RetryPlan::for_recording(path);
+        assert_eq!((plan.step, plan.source.as_deref()), (Some(NextStep::SetupRender), Some("state_manifest")));
+
+        fs::write(path.join(".failed"), "step: intro_cards\nffmpeg exited with 1").unwrap();
+        assert_eq!(RetryPlan::for_recording(path).step, Some(NextStep::Custom("intro_cards".to_string())));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::models::Recording;
+use crate::services::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Result of the last successful scan, persisted so the UI has data right at startup
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScanSnapshot {
+    pub recordings_path: PathBuf,
+    pub scanned_at: u64, // Unix timestamp in seconds
+    pub recordings: Vec<Recording>,
+    /// True when served from disk and a refresh has not completed yet
+    #[serde(default)]
+    pub stale: bool,
+}
+
+impl ScanSnapshot {
+    pub fn new(recordings_path: &Path, recordings: Vec<Recording>) -> Self {
+        Self {
+            recordings_path: recordings_path.to_path_buf(),
+            scanned_at: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            recordings,
+            stale: false,
+        }
+    }
+
+    /// Give recordings scanned without sizes the sizes this snapshot has for them,
+    /// as long as the recording directory hasn't been modified since
+    pub fn fill_sizes(&self, recordings: &mut [Recording]) {
+        for recording in recordings.iter_mut().filter(|r| r.file_sizes.is_empty()) {
+            if let Some(cached) = self
+                .recordings
+                .iter()
+                .find(|c| c.path == recording.path && c.last_updated == recording.last_updated)
+            {
+                recording.file_sizes = cached.file_sizes.clone();
+            }
+        }
+    }
+}
+
+/// Stores the last scan snapshot in a JSON file in the app cache directory
+#[derive(Debug)]
+pub struct ScanSnapshotStore {
+    file_path: PathBuf,
+}
+
+impl ScanSnapshotStore {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self { file_path }
+    }
+
+    /// Load the snapshot for the given recordings path, marked as stale.
+    /// Snapshots taken for a different recordings path are ignored.
+    pub fn load(&self, recordings_path: &Path) -> Option<ScanSnapshot> {
+        let content = fs::read_to_string(&self.file_path).ok()?;
+        let mut snapshot: ScanSnapshot = match serde_json::from_str(&content) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                log::warn!("Ignoring unreadable scan snapshot {}: {}", self.file_path.display(), e);
+                return None;
+            }
+        };
+
+        if snapshot.recordings_path != recordings_path {
+            return None;
+        }
+
+        snapshot.stale = true;
+        Some(snapshot)
+    }
+
+    /// The snapshot file exists but can't be read
+    pub fn is_corrupt(&self) -> bool {
+        fs::read_to_string(&self.file_path)
+            .ok()
+            .is_some_and(|content| serde_json::from_str::<ScanSnapshot>(&content).is_err())
+    }
+
+    /// Forget the snapshot; the next scan writes a new one
+    pub fn clear(&self) -> Result<(), String> {
+        match fs::remove_file(&self.file_path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to remove scan snapshot {}: {}", self.file_path.display(), e))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Persist a fresh snapshot
+    pub fn save(&self, snapshot: &ScanSnapshot) -> Result<(), String> {
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create snapshot directory {}: {}", parent.display(), e))?;
+        }
+
+        let content = serde_json::to_string(snapshot)
+            .map_err(|e| format!("Failed to serialize scan snapshot: {}", e))?;
+        write_atomic(&self.file_path, content)
+            .map_err(|e| format!("Failed to write scan snapshot {}: {}", self.file_path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_snapshot_roundtrip_is_marked_stale() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ScanSnapshotStore::new(temp_dir.path().join("cache/last_scan.json"));
+        let recordings_path = temp_dir.path().join("recordings");
+
+        assert!(store.load(&recordings_path).is_none());
+
+        let snapshot = ScanSnapshot::new(&recordings_path, Vec::new());
+        store.save(&snapshot).unwrap();
+
+        let loaded = store.load(&recordings_path).unwrap();
+        assert!(loaded.stale);
+        assert_eq!(loaded.scanned_at, snapshot.scanned_at);
+    }
+
+    #[test]
+    fn test_fill_sizes_only_for_unchanged_recordings() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = |name: &str, last_updated: u64, size: Option<u64>| {
+            let mut recording = Recording::from_path(temp_dir.path().to_path_buf()).unwrap();
+            recording.path = temp_dir.path().join(name);
+            recording.last_updated = last_updated;
+            recording.file_sizes = size.map(|s| [(format!("{}.mkv", name), s)].into()).unwrap_or_default();
+            recording
+        };
+        let snapshot = ScanSnapshot::new(temp_dir.path(), vec![recording("fri", 10, Some(5)), recording("sat", 10, Some(7))]);
+
+        let mut recordings = vec![recording("fri", 10, None), recording("sat", 20, None)];
+        snapshot.fill_sizes(&mut recordings);
+        assert_eq!(recordings[0].file_sizes.get("fri.mkv"), Some(&5));
+        assert!(recordings[1].file_sizes.is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_for_other_path_is_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ScanSnapshotStore::new(temp_dir.path().join("last_scan.json"));
+
+        store.save(&ScanSnapshot::new(Path::new("/old/path"), Vec::new())).unwrap();
+
+        assert!(store.load(Path::new("/new/path")).is_none());
+    }
+}
@@ -0,0 +1,155 @@
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+
+/// Settings key holding the recording sessions
+pub const SESSIONS_KEY: &str = "sessions";
+
+/// Recordings that belong together, e.g. several takes of one concert
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Session {
+    /// Slug of the name, used to address the session
+    pub id: String,
+    pub name: String,
+    pub created_at: u64, // Unix timestamp in seconds
+    /// Recording names in the order they were assigned
+    pub recordings: Vec<String>,
+}
+
+/// All sessions; a recording belongs to at most one
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct Sessions(Vec<Session>);
+
+impl Sessions {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(SESSIONS_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        settings.set(SESSIONS_KEY, self)
+    }
+
+    pub fn list(&self) -> &[Session] {
+        &self.0
+    }
+
+    pub fn get(&self, session_id: &str) -> Result<&Session, String> {
+        self.0
+            .iter()
+            .find(|s| s.id == session_id)
+            .ok_or_else(|| format!("Session '{}' not found", session_id))
+    }
+
+    pub fn create(&mut self, name: &str, now: u64) -> Result<Session, String> {
+        let name = name.trim();
+        let id = slug(name);
+        if id.is_empty() {
+            return Err(format!("Invalid session name '{}'", name));
+        }
+        if self.0.iter().any(|s| s.id == id) {
+            return Err(format!("Session '{}' already exists", id));
+        }
+
+        let session = Session {
+            id,
+            name: name.to_string(),
+            created_at: now,
+            recordings: Vec::new(),
+        };
+        self.0.push(session.clone());
+        Ok(session)
+    }
+
+    pub fn delete(&mut self, session_id: &str) -> Result<Session, String> {
+        let index = self
+            .0
+            .iter()
+            .position(|s| s.id == session_id)
+            .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        Ok(self.0.remove(index))
+    }
+
+    /// Add recordings to a session, moving them out of any other session
+    pub fn assign(&mut self, session_id: &str, recording_names: &[String]) -> Result<Session, String> {
+        self.get(session_id)?;
+        self.unassign(recording_names);
+
+        let session = self.0.iter_mut().find(|s| s.id == session_id).expect("session checked above");
+        for name in recording_names {
+            if !session.recordings.contains(name) {
+                session.recordings.push(name.clone());
+            }
+        }
+        Ok(session.clone())
+    }
+
+    /// Take recordings out of whatever session they are in
+    pub fn unassign(&mut self, recording_names: &[String]) {
+        for session in &mut self.0 {
+            session.recordings.retain(|r| !recording_names.contains(r));
+        }
+    }
+
+    /// Follow a recording rename; returns whether any session changed
+    pub fn rename_recording(&mut self, old_name: &str, new_name: &str) -> bool {
+        let mut changed = false;
+        for name in self.0.iter_mut().flat_map(|s| s.recordings.iter_mut()) {
+            if name == old_name {
+                *name = new_name.to_string();
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// "Koncert Jazzowy 2024!" -> "koncert-jazzowy-2024"
+fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_assign_moves_recordings_between_sessions() {
+        let mut sessions = Sessions::default();
+        let spring = sessions.create("Spring Gig", 1).unwrap();
+        assert_eq!(spring.id, "spring-gig");
+        sessions.create("Summer Gig", 2).unwrap();
+        assert!(sessions.create("spring gig!", 3).is_err());
+
+        sessions.assign("spring-gig", &names(&["take_1", "take_2"])).unwrap();
+        let summer = sessions.assign("summer-gig", &names(&["take_2"])).unwrap();
+
+        assert_eq!(summer.recordings, names(&["take_2"]));
+        assert_eq!(sessions.get("spring-gig").unwrap().recordings, names(&["take_1"]));
+        assert!(sessions.assign("autumn", &names(&["take_3"])).is_err());
+
+        assert!(sessions.rename_recording("take_1", "opening"));
+        assert_eq!(sessions.get("spring-gig").unwrap().recordings, names(&["opening"]));
+    }
+
+    #[test]
+    fn test_sessions_persist_in_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+
+        let mut sessions = Sessions::load(&settings).unwrap();
+        sessions.create("Concert", 1).unwrap();
+        sessions.assign("concert", &names(&["take_1"])).unwrap();
+        sessions.save(&settings).unwrap();
+
+        assert_eq!(Sessions::load(&settings).unwrap(), sessions);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::services::{
+    write_atomic, AutoIngestPolicy, AutomationRule, ConfigProfiles, CustomStep, JobWindow, PipelineTemplate, PollingSettings, ProcessEnvironment,
+    ProcessPriority, SettingsStore, StallDetection, StepHook, Toolchain, UploadProfile, UploadRetryPolicy, AUTOMATION_RULES_KEY, AUTO_INGEST_KEY,
+    CONFIG_PROFILES_KEY, CUSTOM_STEPS_KEY, JOB_WINDOW_KEY, PIPELINE_TEMPLATES_KEY, POLLING_SETTINGS_KEY, PROCESS_ENVIRONMENT_KEY, PROCESS_PRIORITY_KEY,
+    STALL_DETECTION_KEY, STEP_HOOKS_KEY, TOOLCHAIN_KEY, UPLOAD_PROFILES_KEY, UPLOAD_RETRY_KEY
+};
+use chrono::Local;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+/// Bumped when a bundle can no longer be read by older releases
+pub const SETTINGS_BUNDLE_VERSION: u32 = 1;
+
+/// Fails when a setting's value can't be read as its type
+type SettingCheck = fn(&Value) -> Result<(), String>;
+
+/// Settings that describe a setup rather than this machine's state, each with a check
+/// that its value is readable. Recent recordings, sessions and journals stay behind.
+/// The auto-ingest policy carries the default animation preset.
+const PORTABLE_SETTINGS: [(&str, SettingCheck); 14] = [
+    (CONFIG_PROFILES_KEY, parses::<ConfigProfiles>),
+    (UPLOAD_PROFILES_KEY, parses::<Vec<UploadProfile>>),
+    (STEP_HOOKS_KEY, parses::<Vec<StepHook>>),
+    (CUSTOM_STEPS_KEY, parses::<Vec<CustomStep>>),
+    (PIPELINE_TEMPLATES_KEY, parses::<Vec<PipelineTemplate>>),
+    (AUTOMATION_RULES_KEY, parses::<Vec<AutomationRule>>),
+    (AUTO_INGEST_KEY, parses::<AutoIngestPolicy>),
+    (TOOLCHAIN_KEY, parses::<Toolchain>),
+    (PROCESS_ENVIRONMENT_KEY, parses::<ProcessEnvironment>),
+    (PROCESS_PRIORITY_KEY, parses::<ProcessPriority>),
+    (UPLOAD_RETRY_KEY, parses::<UploadRetryPolicy>),
+    (STALL_DETECTION_KEY, parses::<StallDetection>),
+    (JOB_WINDOW_KEY, parses::<JobWindow>),
+    (POLLING_SETTINGS_KEY, parses::<PollingSettings>),
+];
+
+fn parses<T: DeserializeOwned>(value: &Value) -> Result<(), String> {
+    serde_json::from_value::<T>(value.clone()).map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// Portable copy of the app settings, for setting up Fermata on another machine
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SettingsBundle {
+    pub version: u32,
+    pub exported_at: String,
+    /// Settings values by settings key, as stored in settings.json
+    pub settings: Map<String, Value>,
+}
+
+impl SettingsBundle {
+    /// The portable settings that are set
+    pub fn collect(settings: &SettingsStore) -> Result<Self, String> {
+        let mut values = Map::new();
+        for (key, _) in PORTABLE_SETTINGS {
+            if let Some(value) = settings.get::<Option<Value>>(key)? {
+                values.insert(key.to_string(), value);
+            }
+        }
+        Ok(Self { version: SETTINGS_BUNDLE_VERSION, exported_at: Local::now().to_rfc3339(), settings: values })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        write_atomic(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let bundle: Self = serde_json::from_str(&content).map_err(|e| format!("Invalid settings bundle {}: {}", path.display(), e))?;
+        if bundle.version > SETTINGS_BUNDLE_VERSION {
+            return Err(format!("{} was exported by a newer Fermata (version {})", path.display(), bundle.version));
+        }
+        Ok(bundle)
+    }
+
+    /// Replace the settings the bundle holds and return their keys; nothing is written
+    /// unless every value is readable. Unknown keys are skipped. Paths in profiles are
+    /// kept as exported, so they may need fixing on the new machine.
+    pub fn apply(&self, settings: &SettingsStore) -> Result<Vec<String>, String> {
+        let mut applied = Vec::new();
+        for (key, check) in PORTABLE_SETTINGS {
+            if let Some(value) = self.settings.get(key) {
+                check(value).map_err(|e| format!("Invalid '{}' in settings bundle: {}", key, e))?;
+                applied.push((key, value));
+            }
+        }
+        for key in self.settings.keys().filter(|key| !PORTABLE_SETTINGS.iter().any(|(portable, _)| portable == key)) {
+            log::warn!("Skipping unknown setting '{}' in settings bundle", key);
+        }
+
+        for (key, value) in &applied {
+            settings.set(key, value)?;
+        }
+        Ok(applied.into_iter().map(|(key, _)| key.to_string()).collect())
+    }
+}
+
+/// Portable settings that are set but can't be read as their type, with why
+pub fn unreadable_settings(settings: &SettingsStore) -> Result<Vec<(&'static str, String)>, String> {
+    let mut unreadable = Vec::new();
+    for (key, check) in PORTABLE_SETTINGS {
+        if let Some(value) = settings.get::<Option<Value>>(key)? {
+            if let Err(e) = check(&value) {
+                unreadable.push((key, e));
+            }
+        }
+    }
+    Ok(unreadable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{HookTiming, StepHooks};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bundle_round_trip_between_machines() {
+        let temp_dir = TempDir::new().unwrap();
+        let laptop = SettingsStore::new(temp_dir.path().join("laptop/settings.json"));
+        let hooks = vec![StepHook {
+            step: "render".to_string(),
+            when: HookTiming::After,
+            command: "notify.sh {recording}".to_string(),
+            enabled: true,
+        }];
+        laptop.set(STEP_HOOKS_KEY, &hooks).unwrap();
+        laptop.set("recent_recordings", &vec!["gig"]).unwrap();
+
+        let path = temp_dir.path().join("fermata-settings.json");
+        SettingsBundle::collect(&laptop).unwrap().write(&path).unwrap();
+
+        let studio = SettingsStore::new(temp_dir.path().join("studio/settings.json"));
+        let bundle = SettingsBundle::read(&path).unwrap();
+        assert_eq!(bundle.apply(&studio).unwrap(), vec![STEP_HOOKS_KEY]);
+        assert_eq!(StepHooks::list(&studio).unwrap(), hooks);
+        assert!(studio.get::<Vec<String>>("recent_recordings").unwrap().is_empty());
+
+        let mut broken = bundle.clone();
+        broken.settings.insert(JOB_WINDOW_KEY.to_string(), Value::String("nightly".to_string()));
+        broken.settings.insert(TOOLCHAIN_KEY.to_string(), serde_json::json!({}));
+        assert!(broken.apply(&studio).is_err());
+        assert!(Toolchain::load(&studio).is_ok_and(|toolchain| toolchain == Toolchain::default()));
+    }
+}
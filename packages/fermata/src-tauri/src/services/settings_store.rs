@@ -0,0 +1,128 @@
+use crate::services::write_atomic;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// User settings persisted as one JSON object in the app config directory.
+/// Each subsystem owns a top-level key and stores its own serde type under it.
+#[derive(Debug)]
+pub struct SettingsStore {
+    file_path: PathBuf,
+    // Serializes read-modify-write cycles between concurrent commands
+    write_lock: Mutex<()>,
+}
+
+impl SettingsStore {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    fn read_all(&self) -> Result<Map<String, Value>, String> {
+        if !self.file_path.exists() {
+            return Ok(Map::new());
+        }
+
+        let content = fs::read_to_string(&self.file_path)
+            .map_err(|e| format!("Failed to read settings {}: {}", self.file_path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid settings file {}: {}", self.file_path.display(), e))
+    }
+
+    /// Read the value stored under a key, or its default when unset
+    pub fn get<T: DeserializeOwned + Default>(&self, key: &str) -> Result<T, String> {
+        match self.read_all()?.remove(key) {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| format!("Invalid settings for '{}': {}", key, e)),
+            None => Ok(T::default()),
+        }
+    }
+
+    /// Replace the value stored under a key, keeping all other keys
+    pub fn set<T: Serialize>(&self, key: &str, value: &T) -> Result<(), String> {
+        let _guard = self.write_lock.lock().map_err(|_| "Settings lock poisoned".to_string())?;
+
+        let mut settings = self.read_all()?;
+        let value = serde_json::to_value(value)
+            .map_err(|e| format!("Failed to serialize settings for '{}': {}", key, e))?;
+        settings.insert(key.to_string(), value);
+        self.write_all(&settings)
+    }
+
+    /// Drop a key, so its subsystem falls back to the default
+    pub fn remove(&self, key: &str) -> Result<(), String> {
+        let _guard = self.write_lock.lock().map_err(|_| "Settings lock poisoned".to_string())?;
+
+        let mut settings = self.read_all()?;
+        if settings.remove(key).is_some() {
+            self.write_all(&settings)?;
+        }
+        Ok(())
+    }
+
+    /// Fails when the settings file exists but can't be read as settings
+    pub fn check(&self) -> Result<(), String> {
+        self.read_all().map(|_| ())
+    }
+
+    /// Copy the settings file next to itself with `suffix` appended, e.g. before a repair
+    pub fn backup(&self, suffix: &str) -> Result<PathBuf, String> {
+        let backup = self.backup_path(suffix);
+        fs::copy(&self.file_path, &backup)
+            .map_err(|e| format!("Failed to back up settings to {}: {}", backup.display(), e))?;
+        Ok(backup)
+    }
+
+    /// Move the settings file aside with `suffix` appended, so every setting is back to
+    /// its default; the old file is kept for recovering what can be read of it
+    pub fn reset(&self, suffix: &str) -> Result<PathBuf, String> {
+        let _guard = self.write_lock.lock().map_err(|_| "Settings lock poisoned".to_string())?;
+        let backup = self.backup_path(suffix);
+        fs::rename(&self.file_path, &backup)
+            .map_err(|e| format!("Failed to move settings to {}: {}", backup.display(), e))?;
+        Ok(backup)
+    }
+
+    fn backup_path(&self, suffix: &str) -> PathBuf {
+        let mut name = self.file_path.file_name().unwrap_or_default().to_os_string();
+        name.push(suffix);
+        self.file_path.with_file_name(name)
+    }
+
+    fn write_all(&self, settings: &Map<String, Value>) -> Result<(), String> {
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create settings directory {}: {}", parent.display(), e))?;
+        }
+
+        let content = serde_json::to_string_pretty(settings)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        write_atomic(&self.file_path, content)
+            .map_err(|e| format!("Failed to write settings {}: {}", self.file_path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_get_defaults_and_set_keeps_other_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = SettingsStore::new(temp_dir.path().join("config/settings.json"));
+
+        assert_eq!(store.get::<Vec<String>>("names").unwrap(), Vec::<String>::new());
+
+        store.set("names", &vec!["a".to_string()]).unwrap();
+        store.set("count", &3).unwrap();
+
+        assert_eq!(store.get::<Vec<String>>("names").unwrap(), vec!["a".to_string()]);
+        assert_eq!(store.get::<u32>("count").unwrap(), 3);
+    }
+}
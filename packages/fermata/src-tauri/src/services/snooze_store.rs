@@ -0,0 +1,83 @@
+use crate::models::RecordingSnooze;
+use crate::services::write_atomic;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Stored with the recording so the flag survives renames and moves
+pub const SNOOZE_FILE: &str = ".fermata/snooze.json";
+
+pub struct SnoozeStore;
+
+impl SnoozeStore {
+    pub fn load(recording_path: &Path) -> Option<RecordingSnooze> {
+        let path = recording_path.join(SNOOZE_FILE);
+        let content = fs::read_to_string(&path).ok()?;
+        serde_json::from_str(&content)
+            .map_err(|e| log::warn!("Invalid snooze file {}: {}", path.display(), e))
+            .ok()
+    }
+
+    /// The recording's snooze if it is in effect now
+    pub fn active(recording_path: &Path) -> Option<RecordingSnooze> {
+        Self::load(recording_path).filter(|snooze| snooze.is_active(unix_now()))
+    }
+
+    /// Snooze until `until`, a Unix timestamp in seconds, or ignore for good when None
+    pub fn set(recording_path: &Path, until: Option<u64>) -> Result<RecordingSnooze, String> {
+        let now = unix_now();
+        if until.is_some_and(|until| until <= now) {
+            return Err("Snooze until a time in the future".to_string());
+        }
+        let snooze = RecordingSnooze { until, snoozed_at: now };
+
+        let path = recording_path.join(SNOOZE_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(&snooze)
+            .map_err(|e| format!("Failed to serialize snooze: {}", e))?;
+        write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(snooze)
+    }
+
+    pub fn clear(recording_path: &Path) -> Result<(), String> {
+        let path = recording_path.join(SNOOZE_FILE);
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Failed to remove {}: {}", path.display(), e)),
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_snooze_ignore_and_clear() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        assert!(SnoozeStore::set(path, Some(1)).is_err());
+        assert!(SnoozeStore::active(path).is_none());
+
+        let snooze = SnoozeStore::set(path, Some(unix_now() + 3600)).unwrap();
+        assert_eq!(SnoozeStore::active(path), Some(snooze.clone()));
+        assert!(!snooze.is_active(unix_now() + 3600));
+
+        let ignored = SnoozeStore::set(path, None).unwrap();
+        assert!(ignored.is_active(u64::MAX));
+        SnoozeStore::clear(path).unwrap();
+        assert!(SnoozeStore::load(path).is_none());
+    }
+}
@@ -0,0 +1,305 @@
+use crate::services::{is_audio_file, write_atomic};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+/// Computed offsets are cached next to the recording
+pub const SOURCE_OFFSETS_FILE: &str = ".fermata/source_offsets.json";
+
+/// Mono rate sources are decoded at for alignment
+const ALIGN_SAMPLE_RATE: usize = 8000;
+
+/// Envelope values per second; 10 ms is well under a video frame
+pub const ENVELOPE_RATE: usize = 100;
+
+/// Stretch of each source compared, from its start
+const ALIGN_SECONDS: usize = 300;
+
+/// Largest offset searched for between a source and the main audio
+const MAX_OFFSET_SECONDS: usize = 60;
+
+/// Correlation below which a source counts as not aligned, e.g. a camera with a muted mic
+const MIN_CONFIDENCE: f64 = 0.3;
+
+/// Where one extracted source sits relative to the main audio, found by cross-correlating
+/// their audio. Sources without audio or without a clear match are not aligned.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SourceOffset {
+    /// OBS source name (file stem of the extracted file)
+    pub source: String,
+    /// Path relative to the recording directory
+    pub file: String,
+    /// Start of the source in seconds after the earliest aligned source started
+    pub start_seconds: Option<f64>,
+    /// `start_seconds` minus the reference start; a main-audio time `t`
+    /// is at `t - offset_seconds` inside this source
+    pub offset_seconds: Option<f64>,
+    /// Peak normalized correlation with the main audio, 0 to 1
+    pub confidence: f64,
+}
+
+/// Per-source offsets relative to the main audio, used to map trims and markers
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SourceOffsets {
+    /// OBS `recording_start_time` (Unix seconds) when metadata.json has it
+    pub session_start: Option<f64>,
+    /// Extracted file the offsets are relative to
+    pub reference: String,
+    pub sources: Vec<SourceOffset>,
+}
+
+impl SourceOffsets {
+    /// Align every extracted file with the main audio. `envelope` returns the audio
+    /// envelope of a file at ENVELOPE_RATE, None for files without audio.
+    pub fn compute<F>(recording_path: &Path, main_audio: &str, envelope: F) -> Result<Self, String>
+    where
+        F: Fn(&Path) -> Option<Vec<f32>>,
+    {
+        let files = extracted_files(recording_path);
+        if files.is_empty() {
+            return Err("No extracted sources; run the extract step first".to_string());
+        }
+        let reference_index = reference_index(&files, main_audio);
+        let reference_file = &files[reference_index];
+        let reference = envelope(reference_file)
+            .filter(|e| !e.is_empty())
+            .ok_or_else(|| format!("No audio in {} to align the sources with", reference_file.display()))?;
+
+        let session_start = read_metadata(recording_path)
+            .and_then(|m| m.get("recording_start_time")?.as_f64());
+
+        let max_lag = MAX_OFFSET_SECONDS * ENVELOPE_RATE;
+        let mut sources: Vec<SourceOffset> = files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| {
+                let (offset_seconds, confidence) = if index == reference_index {
+                    (Some(0.0), 1.0)
+                } else {
+                    match envelope(file).and_then(|e| best_lag(&reference, &e, max_lag)) {
+                        Some((lag, confidence)) if confidence >= MIN_CONFIDENCE => (Some(lag as f64 / ENVELOPE_RATE as f64), round_thousandths(confidence)),
+                        Some((_, confidence)) => (None, round_thousandths(confidence)),
+                        None => (None, 0.0),
+                    }
+                };
+                SourceOffset {
+                    source: file.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default(),
+                    file: file.strip_prefix(recording_path).unwrap_or(file).to_string_lossy().to_string(),
+                    start_seconds: None,
+                    offset_seconds,
+                    confidence,
+                }
+            })
+            .collect();
+
+        let earliest = sources.iter().filter_map(|s| s.offset_seconds).fold(0.0, f64::min);
+        for source in sources.iter_mut() {
+            source.start_seconds = source.offset_seconds.map(|offset| round_thousandths(offset - earliest));
+        }
+
+        Ok(Self {
+            session_start,
+            reference: sources[reference_index].file.clone(),
+            sources,
+        })
+    }
+
+    pub fn load(recording_path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(recording_path.join(SOURCE_OFFSETS_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self, recording_path: &Path) -> Result<(), String> {
+        let path = recording_path.join(SOURCE_OFFSETS_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize source offsets: {}", e))?;
+        write_atomic(&path, content)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Stored offsets unless an extracted file changed since they were computed
+    pub fn load_or_compute<F>(recording_path: &Path, main_audio: &str, envelope: F) -> Result<Self, String>
+    where
+        F: Fn(&Path) -> Option<Vec<f32>>,
+    {
+        let stored_at = modified(&recording_path.join(SOURCE_OFFSETS_FILE));
+        let newest_input = extracted_files(recording_path)
+            .iter()
+            .filter_map(|f| modified(f))
+            .max();
+
+        if let (Some(stored_at), Some(offsets)) = (stored_at, Self::load(recording_path)) {
+            if offsets.reference_is(main_audio) && newest_input.map_or(true, |newest| newest <= stored_at) {
+                return Ok(offsets);
+            }
+        }
+
+        let offsets = Self::compute(recording_path, main_audio, envelope)?;
+        offsets.save(recording_path)?;
+        Ok(offsets)
+    }
+
+    fn reference_is(&self, main_audio: &str) -> bool {
+        main_audio.is_empty() || Path::new(&self.reference).file_name().is_some_and(|name| name == main_audio)
+    }
+}
+
+/// Loudness of the first ALIGN_SECONDS of a file's audio per 1/ENVELOPE_RATE s, as
+/// rises only, so the correlation locks onto claps, drum hits and speech onsets.
+/// None when ffmpeg finds no audio stream.
+pub fn audio_envelope(file: &Path) -> Option<Vec<f32>> {
+    let output = Command::new("ffmpeg")
+        .args(["-v", "error", "-i"])
+        .arg(file)
+        .args(["-map", "0:a:0", "-ac", "1", "-ar", &ALIGN_SAMPLE_RATE.to_string(), "-t", &ALIGN_SECONDS.to_string(), "-f", "s16le", "-"])
+        .output()
+        .ok()?;
+    if !output.status.success() || output.stdout.is_empty() {
+        return None;
+    }
+    let samples: Vec<f32> = output
+        .stdout
+        .chunks_exact(2)
+        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]) as f32 / i16::MAX as f32)
+        .collect();
+    Some(onset_envelope(&samples, ALIGN_SAMPLE_RATE / ENVELOPE_RATE))
+}
+
+/// Mean absolute level per block of `block` samples, keeping only its increases
+fn onset_envelope(samples: &[f32], block: usize) -> Vec<f32> {
+    let levels: Vec<f32> = samples
+        .chunks(block)
+        .map(|chunk| chunk.iter().map(|s| s.abs()).sum::<f32>() / chunk.len() as f32)
+        .collect();
+    let mut onsets = Vec::with_capacity(levels.len());
+    onsets.push(0.0);
+    onsets.extend(levels.windows(2).map(|pair| (pair[1] - pair[0]).max(0.0)));
+    onsets
+}
+
+/// Lag in envelope steps at which `source` best matches `reference` within `max_lag`,
+/// with the normalized correlation there. Positive lags: the source started later.
+fn best_lag(reference: &[f32], source: &[f32], max_lag: usize) -> Option<(i64, f64)> {
+    let centered = |values: &[f32]| -> Vec<f64> {
+        let mean = values.iter().map(|&v| v as f64).sum::<f64>() / values.len().max(1) as f64;
+        values.iter().map(|&v| v as f64 - mean).collect()
+    };
+    let (reference, source) = (centered(reference), centered(source));
+    let norm = |values: &[f64]| values.iter().map(|v| v * v).sum::<f64>().sqrt();
+    let scale = norm(&reference) * norm(&source);
+    if scale == 0.0 {
+        return None;
+    }
+
+    let max_lag = max_lag as i64;
+    (-max_lag..=max_lag)
+        .map(|lag| {
+            // reference[i + lag] lines up with source[i]
+            let sum: f64 = source
+                .iter()
+                .enumerate()
+                .filter_map(|(i, s)| reference.get(usize::try_from(i as i64 + lag).ok()?).map(|r| r * s))
+                .sum();
+            (lag, sum / scale)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Keeps the cached JSON exact when read back
+fn round_thousandths(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+fn extracted_files(recording_path: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(recording_path.join("extracted"))
+        .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.is_file()).collect())
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// The configured main audio, else the first audio file, else the first file
+fn reference_index(files: &[PathBuf], main_audio: &str) -> usize {
+    files
+        .iter()
+        .position(|f| !main_audio.is_empty() && f.file_name().is_some_and(|n| n == main_audio))
+        .or_else(|| files.iter().position(|f| is_audio_file(f)))
+        .unwrap_or(0)
+}
+
+fn read_metadata(recording_path: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(recording_path.join("metadata.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Onsets of a performance: irregular hits, so only one lag lines them up
+    fn performance(len: usize) -> Vec<f32> {
+        (0..len).map(|i| if (i * i) % 37 == 0 || i % 53 == 0 { 1.0 } else { 0.0 }).collect()
+    }
+
+    fn create_recording(temp_dir: &TempDir) -> PathBuf {
+        let recording = temp_dir.path().join("gig");
+        fs::create_dir_all(recording.join("extracted")).unwrap();
+        for file in ["Camera.mp4", "Mic.m4a", "Phone.mp4", "Slides.mp4"] {
+            fs::write(recording.join("extracted").join(file), "media").unwrap();
+        }
+        fs::write(recording.join("metadata.json"), r#"{"recording_start_time": 1000.0}"#).unwrap();
+        recording
+    }
+
+    /// The mic started first; the camera 0.3 s and the phone 3 s after it; the slides have no audio
+    fn envelope(file: &Path) -> Option<Vec<f32>> {
+        let hits = performance(2000);
+        match file.file_stem()?.to_str()? {
+            "Mic" => Some(hits),
+            "Camera" => Some(hits[30..].to_vec()),
+            "Phone" => Some(hits[300..].to_vec()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_offsets_relative_to_main_audio() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = create_recording(&temp_dir);
+
+        let offsets = SourceOffsets::compute(&recording, "Mic.m4a", envelope).unwrap();
+        assert_eq!(offsets.session_start, Some(1000.0));
+        assert_eq!(offsets.reference, "extracted/Mic.m4a");
+
+        let source = |name: &str| offsets.sources.iter().find(|s| s.source == name).unwrap().clone();
+        assert_eq!(source("Mic").offset_seconds, Some(0.0));
+        assert!((source("Camera").offset_seconds.unwrap() - 0.3).abs() < 1e-9);
+        assert!((source("Phone").offset_seconds.unwrap() - 3.0).abs() < 1e-9);
+        assert!(source("Phone").confidence > 0.9);
+        assert_eq!((source("Slides").offset_seconds, source("Slides").start_seconds), (None, None));
+    }
+
+    #[test]
+    fn test_offsets_are_cached() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = create_recording(&temp_dir);
+
+        let computed = SourceOffsets::load_or_compute(&recording, "", envelope).unwrap();
+        assert_eq!(computed.reference, "extracted/Mic.m4a");
+        assert!(recording.join(SOURCE_OFFSETS_FILE).exists());
+
+        let cached = SourceOffsets::load_or_compute(&recording, "", |_| None).unwrap();
+        assert_eq!(cached, computed);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::services::{directory_size, rendered_videos, DiskSpace, ExportScope, RecordingArchive, SizeEstimate, TranscodeProfile, UploadConfig};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Render size relative to the extracted sources when no earlier render is known
+const DEFAULT_RENDER_RATIO: f64 = 1.5;
+
+/// Estimates are padded by this factor, they come from heuristics
+const ESTIMATE_MARGIN: f64 = 1.2;
+
+/// Whether the target volume of an operation has room for its output
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SpacePreflight {
+    /// "render", "upload" or "export"
+    pub operation: String,
+    /// Directory the output is written to
+    pub target: PathBuf,
+    pub required_bytes: u64,
+    /// Free space that has to remain afterwards
+    pub reserve_bytes: u64,
+    pub available_bytes: u64,
+    /// How required_bytes was estimated
+    pub basis: String,
+    pub sufficient: bool,
+}
+
+impl SpacePreflight {
+    pub fn new(operation: &str, target: &Path, required_bytes: u64, reserve_bytes: u64, available_bytes: u64, basis: String) -> Self {
+        Self {
+            operation: operation.to_string(),
+            target: target.to_path_buf(),
+            required_bytes,
+            reserve_bytes,
+            available_bytes,
+            basis,
+            sufficient: available_bytes >= required_bytes.saturating_add(reserve_bytes),
+        }
+    }
+
+    /// Check `required_bytes` against the volume holding `target`, or its closest existing parent
+    pub fn check(operation: &str, target: &Path, required_bytes: u64, reserve_bytes: u64, basis: String) -> Result<Self, String> {
+        let existing = target.ancestors().find(|p| p.exists()).unwrap_or(target);
+        let space = DiskSpace::check(existing, 0)?;
+        Ok(Self::new(operation, target, required_bytes, reserve_bytes, space.available_bytes, basis))
+    }
+
+    /// Space a render of `recording_path` needs, estimated from the size of its
+    /// extracted sources. `history` holds (extracted, render) byte sizes of earlier renders;
+    /// a larger bitrate `estimate` of the video, see OutputEstimate, takes precedence.
+    pub fn render(recording_path: &Path, history: &[(u64, u64)], estimate: Option<&SizeEstimate>, reserve_bytes: u64) -> Result<Self, String> {
+        let render_dir = recording_path.join("blender").join("render");
+        let sources = directory_size(&recording_path.join("extracted"));
+        let ratios: Vec<f64> = history
+            .iter()
+            .filter(|(extracted, render)| *extracted > 0 && *render > 0)
+            .map(|(extracted, render)| *render as f64 / *extracted as f64)
+            .collect();
+        let (ratio, mut basis) = if ratios.is_empty() {
+            (DEFAULT_RENDER_RATIO, format!("{:.1}x the extracted sources", DEFAULT_RENDER_RATIO))
+        } else {
+            let ratio = ratios.iter().sum::<f64>() / ratios.len() as f64;
+            (ratio, format!("{:.1}x the extracted sources, from {} earlier renders", ratio, ratios.len()))
+        };
+        let mut estimated = sources as f64 * ratio;
+        if let Some(estimate) = estimate.filter(|e| e.bytes as f64 > estimated) {
+            estimated = estimate.bytes as f64;
+            basis = estimate.basis.clone();
+        }
+        // Frames already rendered are kept by a resumed render
+        let required = ((estimated * ESTIMATE_MARGIN) as u64).saturating_sub(directory_size(&render_dir));
+        Self::check("render", &render_dir, required, reserve_bytes, basis)
+    }
+
+    /// Space the upload transcode needs; nothing when the profile uploads the render as is.
+    /// The `estimate` of the transcoded video replaces the size of the render when known.
+    pub fn upload(
+        recording_path: &Path,
+        profile_name: &str,
+        transcode: Option<&TranscodeProfile>,
+        estimate: Option<&SizeEstimate>,
+        reserve_bytes: u64
+    ) -> Result<Self, String> {
+        let video = rendered_videos(&recording_path.join("blender").join("render")).into_iter().next();
+        let (target, required, basis) = match (video, transcode) {
+            (Some(video), Some(_)) => {
+                let transcoded = UploadConfig::transcoded_path(recording_path, profile_name, &video)?;
+                let (bytes, basis) = match estimate {
+                    Some(estimate) => (estimate.bytes, estimate.basis.clone()),
+                    None => (file_size(&video), "size of the rendered video".to_string()),
+                };
+                let required = if UploadConfig::is_transcode_fresh(&video, &transcoded) { 0 } else { (bytes as f64 * ESTIMATE_MARGIN) as u64 };
+                (transcoded.parent().unwrap_or(recording_path).to_path_buf(), required, basis)
+            }
+            _ => (recording_path.to_path_buf(), 0, "no transcode".to_string()),
+        };
+        Self::check("upload", &target, required, reserve_bytes, basis)
+    }
+
+    /// Space an archive of `recording_path` needs in `destination`
+    pub fn export(recording_path: &Path, destination: &Path, scope: ExportScope) -> Result<Self, String> {
+        let files = RecordingArchive::files(recording_path, scope);
+        let required = files.iter().map(|f| f.size_bytes).sum();
+        Self::check("export", destination, required, 0, format!("{} files in scope", files.len()))
+    }
+
+    /// Ok when there is room, otherwise an error saying how much is missing
+    pub fn ensure(&self) -> Result<(), String> {
+        if self.sufficient {
+            return Ok(());
+        }
+        let reserve = if self.reserve_bytes > 0 {
+            format!(" plus a {:.1} GB reserve", self.reserve_bytes as f64 / BYTES_PER_GB)
+        } else {
+            String::new()
+        };
+        Err(format!(
+            "Not enough disk space to {}: needs about {:.1} GB ({}){}, only {:.1} GB free for {}",
+            self.operation,
+            self.required_bytes as f64 / BYTES_PER_GB,
+            self.basis,
+            reserve,
+            self.available_bytes as f64 / BYTES_PER_GB,
+            self.target.display()
+        ))
+    }
+}
+
+/// (extracted, render) sizes of recordings with a rendered video, for SpacePreflight::render
+pub fn render_size_history<'a>(recording_paths: impl IntoIterator<Item = &'a Path>) -> Vec<(u64, u64)> {
+    recording_paths
+        .into_iter()
+        .filter(|path| !rendered_videos(&path.join("blender").join("render")).is_empty())
+        .map(|path| (directory_size(&path.join("extracted")), directory_size(&path.join("blender").join("render"))))
+        .collect()
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render_estimate_uses_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+        fs::create_dir_all(recording.join("extracted")).unwrap();
+        fs::write(recording.join("extracted/Camera1.mp4"), vec![0u8; 1000]).unwrap();
+
+        let preflight = SpacePreflight::render(recording, &[], None, 0).unwrap();
+        assert_eq!(preflight.required_bytes, 1800);
+        assert_eq!(preflight.target, recording.join("blender/render"));
+
+        let preflight = SpacePreflight::render(recording, &[(100, 300), (100, 100), (0, 50)], None, 0).unwrap();
+        assert_eq!(preflight.required_bytes, 2400);
+        assert!(preflight.basis.contains("from 2 earlier renders"));
+
+        let estimate = SizeEstimate { bytes: 5000, bitrate_kbps: 8000, basis: "1920x1080 at 30 fps in H264".to_string() };
+        let preflight = SpacePreflight::render(recording, &[], Some(&estimate), 0).unwrap();
+        assert_eq!((preflight.required_bytes, preflight.basis.as_str()), (6000, "1920x1080 at 30 fps in H264"));
+    }
+
+    #[test]
+    fn test_ensure_reports_the_shortfall() {
+        let target = Path::new("/recordings/gig/blender/render");
+        assert!(SpacePreflight::new("render", target, 100, 50, 150, String::new()).ensure().is_ok());
+
+        let gb = BYTES_PER_GB as u64;
+        let preflight = SpacePreflight::new("render", target, 3 * gb, 2 * gb, gb, "1.5x the extracted sources".to_string());
+        assert!(!preflight.sufficient);
+        assert_eq!(
+            preflight.ensure().unwrap_err(),
+            "Not enough disk space to render: needs about 3.0 GB (1.5x the extracted sources) plus a 2.0 GB reserve, \
+             only 1.0 GB free for /recordings/gig/blender/render"
+        );
+    }
+}
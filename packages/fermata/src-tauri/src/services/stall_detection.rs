@@ -0,0 +1,171 @@
+use crate::models::NextStep;
+use crate::services::{directory_size, SettingsStore, StepScratch};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Settings key for flagging steps whose output stops growing
+pub const STALL_DETECTION_KEY: &str = "stall_detection";
+
+/// Start of the watchdog's note when it killed a stalled step, see ProcessTimeout::Stalled
+pub const STALL_MARKER: &str = "output stalled for";
+
+/// Output directories are measured at most this often
+const STALL_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Called with the step and the seconds without new bytes when its output stops growing
+pub type StallCallback = Arc<dyn Fn(&NextStep, u64) + Send + Sync>;
+
+/// What happens to a step once it stalls
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StallAction {
+    /// Only flag it, e.g. to look at Blender before deciding
+    #[default]
+    Notify,
+    /// Flag and kill it, failing the step
+    Kill,
+    /// Flag and kill it; a queued job runs the step once more
+    Retry,
+}
+
+/// Flags analyze, blend setup and render when their output directory gets no new
+/// bytes for `minutes`, e.g. Blender hanging on a GPU driver without printing anything
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct StallDetection {
+    /// 0 disables the check
+    pub minutes: u64,
+    pub action: StallAction,
+}
+
+impl Default for StallDetection {
+    fn default() -> Self {
+        Self { minutes: 15, action: StallAction::Notify }
+    }
+}
+
+impl StallDetection {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(STALL_DETECTION_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        settings.set(STALL_DETECTION_KEY, self)
+    }
+
+    /// Watch for `step` of the recording, None for steps with no output to watch
+    pub fn watch_for(&self, recording_path: &Path, step: &NextStep, on_stall: Option<StallCallback>) -> Option<StallWatch> {
+        if self.minutes == 0 {
+            return None;
+        }
+        let output = match step {
+            NextStep::Analyze => recording_path.join("analysis"),
+            NextStep::SetupRender | NextStep::BlendSetup => recording_path.join("blender"),
+            NextStep::Render => recording_path.join("blender").join("render"),
+            _ => return None,
+        };
+        Some(StallWatch {
+            step: step.clone(),
+            // Partial outputs grow in the scratch directory until they are complete
+            outputs: vec![output, StepScratch::new(recording_path, step).path().to_path_buf()],
+            period: Duration::from_secs(self.minutes * 60),
+            kill: self.action != StallAction::Notify,
+            on_stall,
+        })
+    }
+}
+
+/// Whether a step failed because the watchdog killed it as stalled
+pub fn is_stall_error(error: &str) -> bool {
+    error.contains(STALL_MARKER)
+}
+
+/// Output of a running step that is expected to keep growing
+#[derive(Clone)]
+pub struct StallWatch {
+    pub step: NextStep,
+    pub outputs: Vec<PathBuf>,
+    pub period: Duration,
+    /// Kill the process once it stalls instead of only flagging it
+    pub kill: bool,
+    pub on_stall: Option<StallCallback>,
+}
+
+/// Size of a watch's outputs between checks
+pub struct StallTracker {
+    size: u64,
+    grown_at: Instant,
+    checked_at: Instant,
+    flagged: bool,
+}
+
+impl StallTracker {
+    pub fn new(watch: &StallWatch, now: Instant) -> Self {
+        Self { size: output_size(watch), grown_at: now, checked_at: now, flagged: false }
+    }
+
+    /// Seconds without new bytes the first time the outputs stop growing for the
+    /// watch's period; flagged again only after they grew in between
+    pub fn check(&mut self, watch: &StallWatch, now: Instant) -> Option<u64> {
+        if now.duration_since(self.checked_at) < STALL_CHECK_INTERVAL.min(watch.period / 4) {
+            return None;
+        }
+        self.checked_at = now;
+        let size = output_size(watch);
+        if size != self.size {
+            self.size = size;
+            self.grown_at = now;
+            self.flagged = false;
+            return None;
+        }
+        if self.flagged || now.duration_since(self.grown_at) < watch.period {
+            return None;
+        }
+        self.flagged = true;
+        Some(now.duration_since(self.grown_at).as_secs())
+    }
+
+    /// Time spent suspended doesn't count as stalled
+    pub fn resume(&mut self, now: Instant) {
+        self.grown_at = now;
+        self.checked_at = now;
+    }
+}
+
+fn output_size(watch: &StallWatch) -> u64 {
+    watch.outputs.iter().map(|output| directory_size(output)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_flags_output_that_stops_growing() {
+        let temp_dir = TempDir::new().unwrap();
+        let detection = StallDetection { minutes: 10, action: StallAction::Kill };
+        let watch = detection.watch_for(temp_dir.path(), &NextStep::Render, None).unwrap();
+        assert!(watch.kill);
+        assert!(detection.watch_for(temp_dir.path(), &NextStep::Upload, None).is_none());
+        let render_dir = temp_dir.path().join("blender").join("render");
+        fs::create_dir_all(&render_dir).unwrap();
+
+        let start = Instant::now();
+        let mut tracker = StallTracker::new(&watch, start);
+        let minutes = |m: u64| start + Duration::from_secs(m * 60);
+        assert_eq!(tracker.check(&watch, minutes(5)), None);
+        fs::write(render_dir.join("frame_0001.png"), "frame").unwrap();
+        assert_eq!(tracker.check(&watch, minutes(6)), None);
+        assert_eq!(tracker.check(&watch, minutes(15)), None);
+        assert_eq!(tracker.check(&watch, minutes(16)), Some(600));
+        assert_eq!(tracker.check(&watch, minutes(20)), None);
+
+        tracker.resume(minutes(30));
+        assert_eq!(tracker.check(&watch, minutes(39)), None);
+        assert!(is_stall_error("Process killed by watchdog: output stalled for 600 s"));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::models::{NextStep, RecordingStatus};
+use crate::services::{write_atomic, ArtifactChecksum, AttachedRender, StatusDetector};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Status recorded after each successful step, preferred over directory heuristics
+pub const STATE_MANIFEST_FILE: &str = ".fermata/state.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct StateManifest {
+    /// None when the manifest only holds checksums; the status then comes from the files
+    #[serde(default)]
+    pub status: Option<RecordingStatus>,
+    /// Step that produced the status; None when rebuilt from the filesystem
+    pub step: Option<NextStep>,
+    /// Unix timestamp in seconds
+    pub updated_at: u64,
+    /// Hashes of the raw video and final render, see record_checksums
+    #[serde(default)]
+    pub checksums: Vec<ArtifactChecksum>,
+    /// Step whose last run failed, the one Retry runs again; cleared once it succeeds
+    #[serde(default)]
+    pub failed_step: Option<NextStep>,
+    /// Where the render came from when it was made elsewhere, see attach_render
+    #[serde(default)]
+    pub attached_render: Option<AttachedRender>,
+}
+
+impl StateManifest {
+    pub fn load(recording_path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(recording_path.join(STATE_MANIFEST_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self, recording_path: &Path) -> Result<(), String> {
+        let path = recording_path.join(STATE_MANIFEST_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize state manifest: {}", e))?;
+        write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Record the status a successfully finished step leaves the recording in
+    pub fn record_step(recording_path: &Path, step: &NextStep) -> Result<Option<Self>, String> {
+        let status = match step {
+            NextStep::Analyze => RecordingStatus::Analyzed,
+            NextStep::GenerateConfig => RecordingStatus::ConfigGenerated,
+            NextStep::SetupRender | NextStep::BlendSetup => RecordingStatus::SetupRendered,
+            // Frames or a video, and whether every upload failed, show on disk
+            NextStep::Render | NextStep::Upload => StatusDetector::detect_from_files(recording_path),
+            // Subtitles and custom steps are detected from their outputs, not the manifest
+            NextStep::Extract | NextStep::Transcribe | NextStep::Retry | NextStep::Custom(_) => {
+                Self::clear_failure(recording_path, step)?;
+                return Ok(None);
+            }
+        };
+        let manifest = Self {
+            status: Some(status),
+            step: Some(step.clone()),
+            updated_at: now(),
+            checksums: Self::checksums(recording_path),
+            failed_step: None,
+            // A render made here replaces the attached one
+            attached_render: match step {
+                NextStep::Render => None,
+                _ => Self::load(recording_path).and_then(|manifest| manifest.attached_render),
+            },
+        };
+        manifest.save(recording_path)?;
+        Ok(Some(manifest))
+    }
+
+    /// Regenerate the manifest from the files of the recording
+    pub fn rebuild(recording_path: &Path) -> Result<Self, String> {
+        let previous = Self::load(recording_path).unwrap_or_default();
+        let manifest = Self {
+            status: Some(StatusDetector::detect_from_files(recording_path)),
+            step: None,
+            updated_at: now(),
+            checksums: previous.checksums,
+            failed_step: previous.failed_step,
+            attached_render: previous.attached_render,
+        };
+        manifest.save(recording_path)?;
+        Ok(manifest)
+    }
+
+    /// Remember the step that failed, leaving the recorded status as it was
+    pub fn record_failure(recording_path: &Path, step: &NextStep) -> Result<(), String> {
+        let mut manifest = Self::load(recording_path).unwrap_or_default();
+        manifest.failed_step = Some(step.clone());
+        manifest.updated_at = now();
+        manifest.save(recording_path)
+    }
+
+    /// Record a render made elsewhere as the Render step, so the Upload step can run next
+    pub fn record_attached_render(recording_path: &Path, attached: AttachedRender) -> Result<Self, String> {
+        let manifest = Self {
+            status: Some(RecordingStatus::Rendered),
+            step: Some(NextStep::Render),
+            updated_at: now(),
+            checksums: Self::checksums(recording_path),
+            failed_step: None,
+            attached_render: Some(attached),
+        };
+        manifest.save(recording_path)?;
+        Ok(manifest)
+    }
+
+    fn clear_failure(recording_path: &Path, step: &NextStep) -> Result<(), String> {
+        match Self::load(recording_path) {
+            Some(mut manifest) if manifest.failed_step.as_ref() == Some(step) => {
+                manifest.failed_step = None;
+                manifest.save(recording_path)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Rebuild an existing manifest after step outputs were removed
+    pub fn refresh(recording_path: &Path) -> Result<(), String> {
+        if Self::load(recording_path).is_some_and(|manifest| manifest.status.is_some()) {
+            Self::rebuild(recording_path)?;
+        }
+        Ok(())
+    }
+
+    /// Checksums already stored, kept when the status is rewritten
+    fn checksums(recording_path: &Path) -> Vec<ArtifactChecksum> {
+        Self::load(recording_path).map(|manifest| manifest.checksums).unwrap_or_default()
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_manifest_overrides_heuristics() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+        // An empty analysis/ from an interrupted run looks analyzed to the heuristics
+        fs::create_dir_all(recording.join("extracted")).unwrap();
+        fs::write(recording.join("extracted/audio.m4a"), "audio").unwrap();
+        fs::create_dir_all(recording.join("analysis")).unwrap();
+        fs::write(recording.join("analysis/partial.json"), "{").unwrap();
+        assert_eq!(StatusDetector::detect_status(recording), RecordingStatus::Analyzed);
+
+        let manifest = StateManifest { status: Some(RecordingStatus::Extracted), ..StateManifest::default() };
+        manifest.save(recording).unwrap();
+        assert_eq!(StatusDetector::detect_status(recording), RecordingStatus::Extracted);
+
+        StateManifest::record_step(recording, &NextStep::Analyze).unwrap();
+        let loaded = StateManifest::load(recording).unwrap();
+        assert_eq!(loaded.status, Some(RecordingStatus::Analyzed));
+        assert_eq!(loaded.step, Some(NextStep::Analyze));
+
+        StateManifest::record_failure(recording, &NextStep::SetupRender).unwrap();
+        let failed = StateManifest::load(recording).unwrap();
+        assert_eq!((failed.status, failed.failed_step), (Some(RecordingStatus::Analyzed), Some(NextStep::SetupRender)));
+        StateManifest::record_step(recording, &NextStep::SetupRender).unwrap();
+        assert_eq!(StateManifest::load(recording).unwrap().failed_step, None);
+    }
+
+    #[test]
+    fn test_rebuild_and_refresh() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+
+        // Nothing to refresh without a manifest
+        StateManifest::refresh(recording).unwrap();
+        assert!(StateManifest::load(recording).is_none());
+
+        StateManifest { status: Some(RecordingStatus::Analyzed), step: Some(NextStep::Analyze), ..StateManifest::default() }
+            .save(recording)
+            .unwrap();
+        StateManifest::refresh(recording).unwrap();
+        let rebuilt = StateManifest::load(recording).unwrap();
+        assert_eq!(rebuilt.status, Some(RecordingStatus::Recorded));
+        assert_eq!(rebuilt.step, None);
+    }
+}
@@ -1,30 +1,67 @@
-use crate::models::{Recording, RecordingStatus};
+use crate::models::{Recording, RecordingStatus, UploadBlock, UploadResults};
+use crate::services::{
+    complete_frame_sequence, detect_in_progress_status, BlendVersions, file_tree, rendered_videos, ConfigStore, MediaProbe, RecordingDetection,
+    ScanOptions, StateManifest, SymlinkPolicy, UploadBlockStore, DEFAULT_TREE_DEPTH
+};
 use std::collections::HashMap;
 use std::path::Path;
 
 pub struct StatusDetector;
 
 impl StatusDetector {
-    /// Detect the status of a recording based on file system structure
+    /// Detect the status of a recording: a running step, corruption or failure
+    /// first, then the state manifest, then the file system structure
     pub fn detect_status(recording_path: &Path) -> RecordingStatus {
+        // A running step takes precedence over anything left on disk
+        if let Some(status) = detect_in_progress_status(recording_path) {
+            return status;
+        }
+
+        if let Some(reason) = Self::check_for_corruption(recording_path) {
+            return RecordingStatus::Corrupted(reason);
+        }
+
         // Check for failure indicators first
         if let Some(error) = Self::check_for_errors(recording_path) {
             return RecordingStatus::Failed(error);
         }
 
+        if let Some(status) = StateManifest::load(recording_path).and_then(|manifest| manifest.status) {
+            return status;
+        }
+
+        Self::detect_from_files(recording_path)
+    }
+
+    /// Status from directory heuristics alone, ignoring the state manifest
+    pub fn detect_from_files(recording_path: &Path) -> RecordingStatus {
         // Check for completion indicators in reverse order (most advanced first)
         if Self::has_uploads(recording_path) {
-            return RecordingStatus::Uploaded;
+            // Files in an unknown format still count as uploaded, as before typed parsing
+            return match Self::read_upload_results(recording_path) {
+                Some(results) if results.all_failed() => RecordingStatus::Failed(
+                    results.first_error().unwrap_or_else(|| "Upload failed".to_string()),
+                ),
+                _ => RecordingStatus::Uploaded,
+            };
         }
 
         if Self::has_rendered_video(recording_path) {
             return RecordingStatus::Rendered;
         }
 
+        if complete_frame_sequence(recording_path).is_some() {
+            return RecordingStatus::FramesRendered;
+        }
+
         if Self::has_render_setup(recording_path) {
             return RecordingStatus::SetupRendered;
         }
 
+        if !ConfigStore::configs(recording_path).is_empty() {
+            return RecordingStatus::ConfigGenerated;
+        }
+
         if Self::has_analysis_files(recording_path) {
             return RecordingStatus::Analyzed;
         }
@@ -39,29 +76,57 @@ impl StatusDetector {
 
     /// Get file size information for a recording
     pub fn get_file_info(recording_path: &Path) -> HashMap<String, u64> {
-        let mut file_sizes = HashMap::new();
+        Self::get_file_info_with(recording_path, SymlinkPolicy::current())
+    }
 
-        // Recursively scan all files in the recording directory
-        if let Ok(entries) = walkdir::WalkDir::new(recording_path).into_iter().collect::<Result<Vec<_>, _>>() {
-            for entry in entries {
-                if entry.file_type().is_file() {
-                    if let Ok(metadata) = entry.metadata() {
-                        // Get relative path from recording directory
-                        if let Ok(relative_path) = entry.path().strip_prefix(recording_path) {
-                            let path_str = relative_path.to_string_lossy().to_string();
-                            file_sizes.insert(path_str, metadata.len());
-                        }
-                    }
+    /// File sizes by path relative to the recording, following links per `policy`
+    pub fn get_file_info_with(recording_path: &Path, policy: SymlinkPolicy) -> HashMap<String, u64> {
+        let mut file_sizes = HashMap::new();
+        for entry in policy.files(recording_path) {
+            if let Ok(metadata) = entry.metadata() {
+                // Get relative path from recording directory
+                if let Ok(relative_path) = entry.path().strip_prefix(recording_path) {
+                    let path_str = relative_path.to_string_lossy().to_string();
+                    file_sizes.insert(path_str, metadata.len());
                 }
             }
         }
-
         file_sizes
     }
 
+    /// Links under a recording whose target is gone, as "relative/path -> target"
+    pub fn broken_links(recording_path: &Path) -> Vec<String> {
+        walkdir::WalkDir::new(recording_path)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.path_is_symlink() && std::fs::metadata(entry.path()).is_err())
+            .map(|entry| {
+                let relative = entry.path().strip_prefix(recording_path).unwrap_or(entry.path());
+                let target = std::fs::read_link(entry.path()).unwrap_or_default();
+                format!("{} -> {}", relative.display(), target.display())
+            })
+            .collect()
+    }
+
+    /// Parse uploads/upload_results.json if present and in a known format
+    pub fn read_upload_results(recording_path: &Path) -> Option<UploadResults> {
+        let results_path = recording_path.join("uploads").join("upload_results.json");
+        let content = std::fs::read_to_string(&results_path).ok()?;
+        match UploadResults::parse(&content) {
+            Ok(results) => Some(results),
+            Err(e) => {
+                log::warn!("Could not parse {}: {}", results_path.display(), e);
+                None
+            }
+        }
+    }
+
     /// Validate that a recording has the expected structure
     pub fn validate_recording_structure(path: &Path) -> Result<(), String> {
         if !path.exists() {
+            if let Ok(target) = std::fs::read_link(path) {
+                return Err(format!("Recording directory {} is a broken link to {}", path.display(), target.display()));
+            }
             return Err(format!("Recording directory does not exist: {}", path.display()));
         }
 
@@ -69,17 +134,46 @@ impl StatusDetector {
             return Err(format!("Recording path is not a directory: {}", path.display()));
         }
 
-        // Check if it looks like a recording directory (has video file or extracted content)
-        let has_video = Self::get_video_file_size(path).is_some();
-        let has_extracted = path.join("extracted").exists();
-
-        if !has_video && !has_extracted {
-            return Err("Directory does not appear to be a recording (no video file or extracted directory)".to_string());
+        // Check if it looks like a recording directory, e.g. has a video file or extracted content
+        let detection = RecordingDetection::current();
+        if detection.matching_marker(path).is_none() {
+            let broken = Self::broken_links(path);
+            if !broken.is_empty() {
+                return Err(format!("Recording markers are broken links: {}", broken.join(", ")));
+            }
+            return Err(format!(
+                "Directory does not appear to be a recording (none of: {})",
+                detection.markers.join(", ")
+            ));
         }
 
         Ok(())
     }
 
+    /// Why a recording can't be processed at all: unreadable metadata.json, an empty
+    /// OBS video or a video ffprobe failed to read
+    pub fn check_for_corruption(path: &Path) -> Option<String> {
+        let metadata = path.join("metadata.json");
+        if metadata.exists() {
+            let parsed = std::fs::read_to_string(&metadata)
+                .map_err(|e| e.to_string())
+                .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).map_err(|e| e.to_string()));
+            if let Err(e) = parsed {
+                return Some(format!("metadata.json is unreadable: {}", e));
+            }
+        }
+
+        let detection = RecordingDetection::current();
+        let empty_video = std::fs::read_dir(path).into_iter().flatten().flatten().map(|entry| entry.path()).find(|file| {
+            detection.is_video(file) && std::fs::metadata(file).is_ok_and(|m| m.is_file() && m.len() == 0)
+        });
+        if let Some(video) = empty_video {
+            return Some(format!("{} is empty", video.file_name().unwrap_or_default().to_string_lossy()));
+        }
+
+        MediaProbe::probe_failure(path)
+    }
+
     // Private helper methods
     fn has_extracted_files(path: &Path) -> bool {
         let extracted_path = path.join("extracted");
@@ -111,38 +205,13 @@ impl StatusDetector {
             return false;
         }
 
-        // Check for .blend file in blender directory (setup complete)
-        if let Ok(entries) = std::fs::read_dir(&blender_path) {
-            for entry in entries.flatten() {
-                if let Some(extension) = entry.path().extension() {
-                    if extension == "blend" {
-                        return true;
-                    }
-                }
-            }
-        }
-
-        false
+        // The .blend project Render uses (setup complete)
+        BlendVersions::active(path).is_some()
     }
 
+    /// A video anywhere under blender/render/, Blender may write into subfolders
     fn has_rendered_video(path: &Path) -> bool {
-        let render_path = path.join("blender").join("render");
-        if !render_path.exists() || !render_path.is_dir() {
-            return false;
-        }
-
-        // Check for actual video files in render directory (fully rendered)
-        if let Ok(entries) = std::fs::read_dir(&render_path) {
-            for entry in entries.flatten() {
-                if let Some(extension) = entry.path().extension() {
-                    if matches!(extension.to_str(), Some("mp4") | Some("mkv") | Some("avi")) {
-                        return true;
-                    }
-                }
-            }
-        }
-
-        false
+        !rendered_videos(&path.join("blender").join("render")).is_empty()
     }
 
     fn has_uploads(path: &Path) -> bool {
@@ -170,6 +239,11 @@ impl StatusDetector {
         let failed_marker = path.join(".failed");
         if failed_marker.exists() {
             if let Ok(content) = std::fs::read_to_string(&failed_marker) {
+                // A leading "step: <name>" line says which step to retry, see RetryPlan
+                let content = match content.split_once('\n') {
+                    Some((first, rest)) if first.starts_with("step:") => rest,
+                    _ => content.as_str(),
+                };
                 return Some(content.trim().to_string());
             }
             return Some("Process failed".to_string());
@@ -177,55 +251,33 @@ impl StatusDetector {
 
         None
     }
-
-    fn get_video_file_size(path: &Path) -> Option<u64> {
-        if let Ok(entries) = std::fs::read_dir(path) {
-            for entry in entries.flatten() {
-                if let Some(extension) = entry.path().extension() {
-                    // Support both .mkv and .mp4 files for OBS recordings
-                    if matches!(extension.to_str(), Some("mkv") | Some("mp4")) {
-                        if let Ok(metadata) = entry.metadata() {
-                            return Some(metadata.len());
-                        }
-                    }
-                }
-            }
-        }
-        None
-    }
-
-    fn get_directory_size(path: &Path) -> Option<u64> {
-        if !path.exists() || !path.is_dir() {
-            return None;
-        }
-
-        let mut total_size = 0u64;
-        if let Ok(entries) = walkdir::WalkDir::new(path).into_iter().collect::<Result<Vec<_>, _>>() {
-            for entry in entries {
-                if entry.file_type().is_file() {
-                    if let Ok(metadata) = entry.metadata() {
-                        total_size += metadata.len();
-                    }
-                }
-            }
-            Some(total_size)
-        } else {
-            None
-        }
-    }
 }
 
 /// Update a recording's status and file sizes
 pub fn update_recording_status(recording: &mut Recording) {
+    update_recording_with(recording, &ScanOptions::default());
+}
+
+/// Update a recording's status, and its file sizes and tree as far as `options` asks
+pub fn update_recording_with(recording: &mut Recording, options: &ScanOptions) {
     recording.status = StatusDetector::detect_status(&recording.path);
-    recording.file_sizes = StatusDetector::get_file_info(&recording.path);
+    if options.include_sizes {
+        recording.file_sizes = StatusDetector::get_file_info(&recording.path);
+    }
+    if options.include_tree {
+        recording.tree = Some(file_tree(&recording.path, options.max_depth.unwrap_or(DEFAULT_TREE_DEPTH)));
+    }
+    // Shown as blocked when the flag can't be read, as upload refuses to run then
+    recording.upload_block = UploadBlockStore::load(&recording.path).unwrap_or_else(|e| {
+        log::warn!("{}", e);
+        Some(UploadBlock { reason: e, blocked_at: 0 })
+    });
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
-    use std::path::PathBuf;
     use tempfile::TempDir;
 
     fn create_test_recording_structure() -> TempDir {
@@ -274,6 +326,22 @@ mod tests {
         assert_eq!(status, RecordingStatus::Analyzed);
     }
 
+    #[test]
+    fn test_detect_status_config_generated() {
+        let temp_dir = create_test_recording_structure();
+        let recording_path = temp_dir.path().join("test_recording");
+
+        // Config generated, blend setup not done (or failed)
+        fs::create_dir_all(recording_path.join("analysis")).unwrap();
+        fs::write(recording_path.join("analysis/audio_analysis.json"), b"{}").unwrap();
+        fs::create_dir_all(recording_path.join(".fermata/configs")).unwrap();
+        fs::write(recording_path.join(".fermata/configs/animation_config_beat-switch.yaml"), b"project: {}").unwrap();
+        fs::create_dir_all(recording_path.join("blender")).unwrap();
+
+        let status = StatusDetector::detect_status(&recording_path);
+        assert_eq!(status, RecordingStatus::ConfigGenerated);
+    }
+
     #[test]
     fn test_detect_status_setup_rendered() {
         let temp_dir = create_test_recording_structure();
@@ -308,6 +376,25 @@ mod tests {
         assert_eq!(status, RecordingStatus::Rendered);
     }
 
+    #[test]
+    fn test_detect_status_frames_rendered() {
+        let temp_dir = create_test_recording_structure();
+        let recording_path = temp_dir.path().join("test_recording");
+
+        fs::create_dir_all(recording_path.join("blender/render/frames")).unwrap();
+        fs::write(recording_path.join("blender/project.blend"), b"dummy blend").unwrap();
+        for frame in 1..=2 {
+            fs::write(recording_path.join(format!("blender/render/frames/frame_{:04}.png", frame)), b"png").unwrap();
+        }
+        fs::create_dir_all(recording_path.join(".fermata")).unwrap();
+        fs::write(recording_path.join(".fermata/render_progress.json"), r#"{"current_frame": 2, "total_frames": 2, "eta_seconds": null, "updated_at": 0}"#).unwrap();
+        assert_eq!(StatusDetector::detect_status(&recording_path), RecordingStatus::FramesRendered);
+
+        // A video in a subfolder means the render is finished
+        fs::write(recording_path.join("blender/render/frames/final.mp4"), b"dummy video").unwrap();
+        assert_eq!(StatusDetector::detect_status(&recording_path), RecordingStatus::Rendered);
+    }
+
     #[test]
     fn test_detect_status_uploaded() {
         let temp_dir = create_test_recording_structure();
@@ -326,6 +413,21 @@ mod tests {
         assert_eq!(status, RecordingStatus::Uploaded);
     }
 
+    #[test]
+    fn test_detect_status_all_uploads_failed() {
+        let temp_dir = create_test_recording_structure();
+        let recording_path = temp_dir.path().join("test_recording");
+
+        fs::create_dir_all(recording_path.join("uploads")).unwrap();
+        fs::write(
+            recording_path.join("uploads/upload_results.json"),
+            br#"{"results": {"youtube": {"success": false, "error": "quota"}}}"#,
+        ).unwrap();
+
+        let status = StatusDetector::detect_status(&recording_path);
+        assert_eq!(status, RecordingStatus::Failed("youtube: quota".to_string()));
+    }
+
     #[test]
     fn test_detect_status_failed() {
         let temp_dir = create_test_recording_structure();
@@ -342,6 +444,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_detect_status_corrupted() {
+        let temp_dir = create_test_recording_structure();
+        let recording_path = temp_dir.path().join("test_recording");
+        fs::write(recording_path.join("error.log"), b"Audio analysis failed").unwrap();
+
+        fs::write(recording_path.join("metadata.json"), b"{\"recording_start_time\": ").unwrap();
+        let status = StatusDetector::detect_status(&recording_path);
+        assert!(matches!(status, RecordingStatus::Corrupted(reason) if reason.starts_with("metadata.json is unreadable")));
+
+        fs::write(recording_path.join("metadata.json"), b"{}").unwrap();
+        fs::write(recording_path.join("test_recording.mp4"), b"").unwrap();
+        let status = StatusDetector::detect_status(&recording_path);
+        assert_eq!(status, RecordingStatus::Corrupted("test_recording.mp4 is empty".to_string()));
+    }
+
+    #[test]
+    fn test_detect_status_in_progress() {
+        let temp_dir = create_test_recording_structure();
+        let recording_path = temp_dir.path().join("test_recording");
+
+        fs::create_dir_all(recording_path.join("extracted")).unwrap();
+        let _lock = crate::services::StepLock::acquire(&recording_path, &crate::models::NextStep::Analyze).unwrap();
+
+        let status = StatusDetector::detect_status(&recording_path);
+        assert_eq!(status, RecordingStatus::Analyzing);
+    }
+
     #[test]
     fn test_get_file_info() {
         let temp_dir = create_test_recording_structure();
@@ -383,6 +513,11 @@ mod tests {
             status: RecordingStatus::Failed("old error".to_string()),
             last_updated: std::time::SystemTime::now().duration_since(std::time::SystemTime::UNIX_EPOCH).unwrap().as_secs(),
             file_sizes: HashMap::new(),
+            upload_block: None,
+            root: temp_dir.path().to_path_buf(),
+            lifecycle: None,
+            media: None,
+            tree: None,
         };
 
         update_recording_status(&mut recording);
@@ -390,4 +525,45 @@ mod tests {
         assert_eq!(recording.status, RecordingStatus::Recorded);
         assert!(!recording.file_sizes.is_empty());
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_file_info_follows_links_once() {
+        use std::os::unix::fs::symlink;
+        let temp_dir = TempDir::new().unwrap();
+        let recording_path = temp_dir.path().join("gig");
+        let nas = temp_dir.path().join("nas");
+        fs::create_dir_all(recording_path.join("blender")).unwrap();
+        fs::create_dir_all(&nas).unwrap();
+        fs::write(recording_path.join("gig.mkv"), b"video").unwrap();
+        fs::write(nas.join("final.mp4"), b"render").unwrap();
+        symlink(&nas, recording_path.join("blender/render")).unwrap();
+        symlink(&nas, recording_path.join("render_copy")).unwrap();
+        symlink(recording_path.join("gig.mkv"), recording_path.join("alias.mkv")).unwrap();
+        symlink(&recording_path, recording_path.join("blender/loop")).unwrap();
+
+        let followed = StatusDetector::get_file_info_with(&recording_path, SymlinkPolicy::Follow);
+        let mut files: Vec<_> = followed.keys().cloned().collect();
+        files.sort();
+        assert_eq!(files, ["blender/render/final.mp4", "gig.mkv"]);
+
+        let ignored = StatusDetector::get_file_info_with(&recording_path, SymlinkPolicy::Ignore);
+        assert_eq!(ignored.keys().collect::<Vec<_>>(), ["gig.mkv"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_reports_broken_links() {
+        use std::os::unix::fs::symlink;
+        let temp_dir = TempDir::new().unwrap();
+        let recording_path = temp_dir.path().join("gig");
+        fs::create_dir_all(&recording_path).unwrap();
+        symlink("/nas/offline/gig.mkv", recording_path.join("gig.mkv")).unwrap();
+        let error = StatusDetector::validate_recording_structure(&recording_path).unwrap_err();
+        assert!(error.contains("gig.mkv -> /nas/offline/gig.mkv"), "{}", error);
+
+        symlink(temp_dir.path().join("gone"), temp_dir.path().join("linked")).unwrap();
+        let error = StatusDetector::validate_recording_structure(&temp_dir.path().join("linked")).unwrap_err();
+        assert!(error.contains("broken link"), "{}", error);
+    }
 }
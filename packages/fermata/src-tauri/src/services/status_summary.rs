@@ -0,0 +1,146 @@
+use crate::models::{NextStep, Recording, RecordingStatus};
+use crate::services::{message, message_with, plural, Locale};
+
+/// One sentence describing a recording, for screen readers and notification bodies,
+/// e.g. "Rendered 2 hours ago; awaiting upload to youtube; 8.2 GB"
+pub fn status_summary(recording: &Recording, locale: Locale, now: u64, upload_target: Option<&str>) -> String {
+    let status = match &recording.status {
+        RecordingStatus::Failed(error) => message_with(locale, "status.failed", &[("error", error)]),
+        RecordingStatus::Corrupted(reason) => message_with(locale, "status.corrupted", &[("reason", reason)]),
+        RecordingStatus::RunningStep(step) => message_with(locale, "status.running_step", &[("step", step)]),
+        status => message(locale, status_key(status)).to_string(),
+    };
+    let mut parts = vec![format!("{} {}", status, time_ago(locale, now.saturating_sub(recording.last_updated)))];
+
+    let next = match (recording.get_next_step(), &recording.upload_block) {
+        (Some(NextStep::Upload), Some(block)) => {
+            Some(message_with(locale, "next.upload_blocked", &[("reason", &block.reason)]))
+        }
+        (Some(NextStep::Upload), None) => Some(match upload_target {
+            Some(target) => message_with(locale, "next.upload_to", &[("target", target)]),
+            None => message(locale, "next.upload").to_string(),
+        }),
+        (Some(NextStep::Custom(step)), _) => Some(message_with(locale, "next.custom", &[("step", &step)])),
+        (Some(step), _) => Some(message(locale, next_key(&step)).to_string()),
+        (None, _) => None,
+    };
+    parts.extend(next);
+
+    let total_bytes: u64 = recording.file_sizes.values().sum();
+    if total_bytes > 0 {
+        parts.push(format_size(total_bytes));
+    }
+    parts.join("; ")
+}
+
+fn status_key(status: &RecordingStatus) -> &'static str {
+    match status {
+        RecordingStatus::Recorded => "status.recorded",
+        RecordingStatus::Extracted => "status.extracted",
+        RecordingStatus::Analyzed => "status.analyzed",
+        RecordingStatus::ConfigGenerated => "status.config_generated",
+        RecordingStatus::SetupRendered => "status.setup_rendered",
+        RecordingStatus::FramesRendered => "status.frames_rendered",
+        RecordingStatus::Rendered => "status.rendered",
+        RecordingStatus::Uploaded => "status.uploaded",
+        RecordingStatus::Failed(_) => "status.failed",
+        RecordingStatus::Corrupted(_) => "status.corrupted",
+        RecordingStatus::Analyzing => "status.analyzing",
+        RecordingStatus::SettingUpRender => "status.setting_up_render",
+        RecordingStatus::Rendering => "status.rendering",
+        RecordingStatus::Uploading => "status.uploading",
+        RecordingStatus::Transcribing => "status.transcribing",
+        RecordingStatus::RunningStep(_) => "status.running_step",
+    }
+}
+
+fn next_key(step: &NextStep) -> &'static str {
+    match step {
+        NextStep::Extract => "next.extract",
+        NextStep::Analyze => "next.analyze",
+        NextStep::SetupRender => "next.setup_render",
+        NextStep::GenerateConfig => "next.generate_config",
+        NextStep::BlendSetup => "next.blend_setup",
+        NextStep::Render => "next.render",
+        NextStep::Upload => "next.upload",
+        NextStep::Transcribe => "next.transcribe",
+        NextStep::Retry => "next.retry",
+        NextStep::Custom(_) => "next.custom",
+    }
+}
+
+fn time_ago(locale: Locale, seconds: u64) -> String {
+    match seconds {
+        0..=59 => message(locale, "time.just_now").to_string(),
+        60..=3_599 => plural(locale, "time.minutes_ago", seconds / 60),
+        3_600..=86_399 => plural(locale, "time.hours_ago", seconds / 3_600),
+        _ => plural(locale, "time.days_ago", seconds / 86_400),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UploadBlock;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn recording(status: RecordingStatus) -> Recording {
+        Recording {
+            name: "gig".to_string(),
+            path: PathBuf::from("gig"),
+            status,
+            last_updated: 1_000,
+            file_sizes: HashMap::from([("gig.mkv".to_string(), 8_804_682_957)]),
+            upload_block: None,
+            root: PathBuf::new(),
+            lifecycle: None,
+            media: None,
+            tree: None,
+        }
+    }
+
+    #[test]
+    fn test_rendered_summary() {
+        let rendered = recording(RecordingStatus::Rendered);
+        assert_eq!(
+            status_summary(&rendered, Locale::En, 1_000 + 2 * 3_600, Some("YouTube")),
+            "Rendered 2 hours ago; awaiting upload to YouTube; 8.2 GB"
+        );
+        assert_eq!(
+            status_summary(&rendered, Locale::Pl, 1_000 + 2 * 3_600, Some("YouTube")),
+            "Wyrenderowano 2 godziny temu; czeka na wysłanie do YouTube; 8.2 GB"
+        );
+    }
+
+    #[test]
+    fn test_blocked_and_uploaded_summaries() {
+        let mut blocked = recording(RecordingStatus::Rendered);
+        blocked.upload_block = Some(UploadBlock {
+            reason: "cover song".to_string(),
+            blocked_at: 0,
+        });
+        assert_eq!(
+            status_summary(&blocked, Locale::En, 1_030, None),
+            "Rendered just now; upload blocked: cover song; 8.2 GB"
+        );
+
+        let mut uploaded = recording(RecordingStatus::Uploaded);
+        uploaded.file_sizes.clear();
+        assert_eq!(status_summary(&uploaded, Locale::En, 1_000 + 86_400, None), "Uploaded 1 day ago");
+    }
+}
@@ -0,0 +1,63 @@
+use crate::models::{Recording, RecordingStatus};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Payload of the `status-changed` event
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StatusChange {
+    pub recording: String,
+    /// None for a recording not seen before
+    pub old: Option<RecordingStatus>,
+    /// None when the recording directory disappeared
+    pub new: Option<RecordingStatus>,
+}
+
+/// Last status seen of every recording, to tell real transitions from rescans
+#[derive(Default)]
+pub struct StatusTracker {
+    statuses: Mutex<HashMap<String, RecordingStatus>>,
+}
+
+impl StatusTracker {
+    /// Remember the statuses of a full scan without reporting them as changes
+    pub fn seed(&self, recordings: &[Recording]) {
+        let mut statuses = self.statuses.lock().unwrap();
+        statuses.clear();
+        statuses.extend(recordings.iter().map(|r| (r.name.clone(), r.status.clone())));
+    }
+
+    /// Record the current status of a recording, None once it is gone; returns
+    /// the change when it differs from the last one seen
+    pub fn observe(&self, recording_name: &str, status: Option<RecordingStatus>) -> Option<StatusChange> {
+        let mut statuses = self.statuses.lock().unwrap();
+        let old = match &status {
+            Some(status) => statuses.insert(recording_name.to_string(), status.clone()),
+            None => statuses.remove(recording_name),
+        };
+        (old != status).then(|| StatusChange { recording: recording_name.to_string(), old, new: status })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reports_only_transitions() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("gig")).unwrap();
+        let tracker = StatusTracker::default();
+        tracker.seed(&[Recording::from_path(temp_dir.path().join("gig")).unwrap()]);
+
+        assert_eq!(tracker.observe("gig", Some(RecordingStatus::Recorded)), None);
+        let change = tracker.observe("gig", Some(RecordingStatus::Extracted)).unwrap();
+        assert_eq!((change.old, change.new), (Some(RecordingStatus::Recorded), Some(RecordingStatus::Extracted)));
+
+        let gone = tracker.observe("gig", None).unwrap();
+        assert_eq!(gone.old, Some(RecordingStatus::Extracted));
+        assert_eq!(tracker.observe("gig", None), None);
+        assert_eq!(tracker.observe("new", Some(RecordingStatus::Recorded)).unwrap().old, None);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::models::NextStep;
+use crate::services::{ConfigStore, SUBTITLES_DIR};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Markers that put a recording into the Failed state
+pub const FAILURE_MARKERS: [&str; 2] = [".failed", "error.log"];
+
+/// Knows which files each pipeline step produces inside a recording directory
+pub struct StepArtifacts;
+
+impl StepArtifacts {
+    /// Steps in pipeline order, starting from (and including) the given step
+    pub fn downstream_steps(step: &NextStep) -> Vec<NextStep> {
+        // SetupRender covers GenerateConfig and BlendSetup
+        if *step == NextStep::SetupRender {
+            return [vec![NextStep::SetupRender], Self::downstream_steps(&NextStep::Render)].concat();
+        }
+
+        let pipeline = [
+            NextStep::Analyze,
+            NextStep::GenerateConfig,
+            NextStep::BlendSetup,
+            NextStep::Render,
+            NextStep::Upload,
+        ];
+        match pipeline.iter().position(|s| s == step) {
+            Some(index) => pipeline[index..].to_vec(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Existing artifacts produced by a single step
+    pub fn artifacts_for_step(recording_path: &Path, step: &NextStep) -> Vec<PathBuf> {
+        let candidates: Vec<PathBuf> = match step {
+            NextStep::Analyze => vec![recording_path.join("analysis")],
+            NextStep::SetupRender => [
+                Self::artifacts_for_step(recording_path, &NextStep::GenerateConfig),
+                Self::artifacts_for_step(recording_path, &NextStep::BlendSetup),
+            ]
+            .concat(),
+            NextStep::GenerateConfig => Self::animation_configs(recording_path),
+            // The render output lives inside blender/, so only the project files are ours
+            NextStep::BlendSetup => match fs::read_dir(recording_path.join("blender")) {
+                Ok(entries) => entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.file_name().is_some_and(|n| n != "render"))
+                    .collect(),
+                Err(_) => Vec::new(),
+            },
+            NextStep::Render => vec![recording_path.join("blender").join("render")],
+            NextStep::Upload => vec![recording_path.join("uploads")],
+            NextStep::Transcribe => vec![recording_path.join(SUBTITLES_DIR)],
+            // Outputs of custom steps are only known from their definitions
+            NextStep::Extract | NextStep::Retry | NextStep::Custom(_) => Vec::new(),
+        };
+
+        let mut existing: Vec<PathBuf> = candidates.into_iter().filter(|p| p.exists()).collect();
+        existing.sort();
+        existing
+    }
+
+    /// Existing artifacts of the step and every step after it
+    pub fn artifacts_from_step(recording_path: &Path, step: &NextStep) -> Vec<PathBuf> {
+        Self::downstream_steps(step)
+            .iter()
+            .flat_map(|s| Self::artifacts_for_step(recording_path, s))
+            .collect()
+    }
+
+    /// Remove files or directories, returning the ones actually removed
+    pub fn remove(paths: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+        let mut removed = Vec::new();
+        for path in paths {
+            let result = if path.is_dir() {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_file(path)
+            };
+            result.map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+            removed.push(path.clone());
+        }
+        Ok(removed)
+    }
+
+    /// Current configs only; superseded ones stay in the config history
+    fn animation_configs(recording_path: &Path) -> Vec<PathBuf> {
+        ConfigStore::configs(recording_path).into_iter().map(|(path, _)| path).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_full_pipeline(root: &Path) {
+        fs::create_dir_all(root.join("extracted")).unwrap();
+        fs::create_dir_all(root.join("analysis")).unwrap();
+        fs::write(root.join("analysis/audio_analysis.json"), "{}").unwrap();
+        fs::write(root.join("animation_config_beat-switch.yaml"), "").unwrap();
+        fs::create_dir_all(root.join("blender/render")).unwrap();
+        fs::write(root.join("blender/project.blend"), "").unwrap();
+        fs::write(root.join("blender/render/final.mp4"), "").unwrap();
+        fs::create_dir_all(root.join("uploads")).unwrap();
+    }
+
+    #[test]
+    fn test_downstream_steps() {
+        assert_eq!(
+            StepArtifacts::downstream_steps(&NextStep::SetupRender),
+            vec![NextStep::SetupRender, NextStep::Render, NextStep::Upload]
+        );
+        assert!(StepArtifacts::downstream_steps(&NextStep::Extract).is_empty());
+        assert_eq!(
+            StepArtifacts::downstream_steps(&NextStep::BlendSetup),
+            vec![NextStep::BlendSetup, NextStep::Render, NextStep::Upload]
+        );
+    }
+
+    #[test]
+    fn test_blend_setup_artifacts_keep_config() {
+        let temp_dir = TempDir::new().unwrap();
+        create_full_pipeline(temp_dir.path());
+
+        let artifacts = StepArtifacts::artifacts_for_step(temp_dir.path(), &NextStep::BlendSetup);
+        assert_eq!(artifacts, vec![temp_dir.path().join("blender/project.blend")]);
+        assert_eq!(
+            StepArtifacts::artifacts_for_step(temp_dir.path(), &NextStep::GenerateConfig),
+            vec![temp_dir.path().join("animation_config_beat-switch.yaml")]
+        );
+    }
+
+    #[test]
+    fn test_setup_render_artifacts_exclude_render_output() {
+        let temp_dir = TempDir::new().unwrap();
+        create_full_pipeline(temp_dir.path());
+
+        let artifacts = StepArtifacts::artifacts_for_step(temp_dir.path(), &NextStep::SetupRender);
+        assert_eq!(artifacts.len(), 2);
+        assert!(artifacts.iter().all(|p| !p.ends_with("render")));
+    }
+
+    #[test]
+    fn test_artifacts_from_analyze_cover_everything_downstream() {
+        let temp_dir = TempDir::new().unwrap();
+        create_full_pipeline(temp_dir.path());
+
+        let artifacts = StepArtifacts::artifacts_from_step(temp_dir.path(), &NextStep::Analyze);
+        StepArtifacts::remove(&artifacts).unwrap();
+
+        assert!(temp_dir.path().join("extracted").exists());
+        assert!(!temp_dir.path().join("analysis").exists());
+        assert!(!temp_dir.path().join("blender/project.blend").exists());
+        assert!(!temp_dir.path().join("blender/render").exists());
+        assert!(!temp_dir.path().join("uploads").exists());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::models::{NextStep, Recording};
+use crate::services::{recording_video_size, update_recording_status, within_target, StepLogs};
+use serde::Serialize;
+use std::path::Path;
+
+/// How long a step is expected to take, from earlier successful runs
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StepEstimate {
+    pub step: String,
+    pub estimated_ms: u64,
+    /// Earlier runs the estimate is based on
+    pub runs: usize,
+    /// How estimated_ms was worked out
+    pub basis: String,
+}
+
+impl StepEstimate {
+    /// Estimate for a recording with `video_bytes` of OBS video. `history` holds
+    /// (video bytes, duration ms) of earlier runs; runs of recordings with a known
+    /// size give a time per byte, otherwise their durations are averaged.
+    pub fn from_history(step: &str, video_bytes: u64, history: &[(u64, u64)]) -> Option<Self> {
+        if history.is_empty() {
+            return None;
+        }
+        let sized: Vec<&(u64, u64)> = history.iter().filter(|(bytes, _)| *bytes > 0).collect();
+        let (estimated_ms, runs, basis) = if video_bytes > 0 && !sized.is_empty() {
+            let bytes: u64 = sized.iter().map(|(bytes, _)| bytes).sum();
+            let ms: u64 = sized.iter().map(|(_, ms)| ms).sum();
+            let estimated = (ms as f64 / bytes as f64 * video_bytes as f64) as u64;
+            (estimated, sized.len(), format!("scaled by video size from {} earlier runs", sized.len()))
+        } else {
+            let ms: u64 = history.iter().map(|(_, ms)| ms).sum();
+            (ms / history.len() as u64, history.len(), format!("average of {} earlier runs", history.len()))
+        };
+        Some(Self { step: step.to_string(), estimated_ms, runs, basis })
+    }
+}
+
+/// (video bytes, duration ms) of the successful runs of `step` with a recorded
+/// duration, for StepEstimate::from_history
+pub fn step_duration_history<'a>(recording_paths: impl IntoIterator<Item = &'a Path>, step: &str) -> Vec<(u64, u64)> {
+    recording_paths
+        .into_iter()
+        .flat_map(|path| {
+            let video_bytes = recording_video_size(path);
+            StepLogs::list(path)
+                .into_iter()
+                .filter(|log| log.success && log.step == step)
+                .filter_map(move |log| log.duration_ms.map(|ms| (video_bytes, ms)))
+        })
+        .collect()
+}
+
+/// Expected run time of the steps left up to `target`, learned from the runs in
+/// `recording_paths`; None when a step has never run before
+pub fn estimate_remaining(recording_path: &Path, target: &NextStep, recording_paths: &[&Path]) -> Option<u64> {
+    let video_bytes = recording_video_size(recording_path);
+    remaining_steps(recording_path, target)
+        .iter()
+        .map(|step| {
+            // As named in step logs
+            let step = step.id();
+            let history = step_duration_history(recording_paths.iter().copied(), &step);
+            StepEstimate::from_history(&step, video_bytes, &history).map(|estimate| estimate.estimated_ms)
+        })
+        .sum()
+}
+
+/// Steps a job up to `target` still runs on the recording at `recording_path`,
+/// in order; empty once the recording is past the target
+pub fn remaining_steps(recording_path: &Path, target: &NextStep) -> Vec<NextStep> {
+    let Ok(mut recording) = Recording::from_path(recording_path.to_path_buf()) else {
+        return Vec::new();
+    };
+    update_recording_status(&mut recording);
+
+    let mut steps = Vec::new();
+    let mut step = recording.get_next_step();
+    while let Some(current) = step.filter(|s| within_target(s, target)) {
+        step = match current {
+            NextStep::Extract => Some(NextStep::Analyze),
+            NextStep::Analyze => Some(NextStep::SetupRender),
+            NextStep::GenerateConfig => Some(NextStep::BlendSetup),
+            NextStep::SetupRender | NextStep::BlendSetup => Some(NextStep::Render),
+            NextStep::Render => Some(NextStep::Upload),
+            _ => None,
+        };
+        steps.push(current);
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::ProcessResult;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_estimate_scales_by_video_size() {
+        assert_eq!(StepEstimate::from_history("render", 100, &[]), None);
+
+        let scaled = StepEstimate::from_history("render", 300, &[(100, 1000), (200, 2000), (0, 50_000)]).unwrap();
+        assert_eq!((scaled.estimated_ms, scaled.runs), (3000, 2));
+
+        let averaged = StepEstimate::from_history("upload", 0, &[(100, 1000), (0, 3000)]).unwrap();
+        assert_eq!((averaged.estimated_ms, averaged.runs), (2000, 2));
+    }
+
+    #[test]
+    fn test_remaining_steps_and_estimate() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("gig");
+        fs::create_dir_all(path.join("extracted")).unwrap();
+        fs::write(path.join("gig.mkv"), vec![0u8; 10]).unwrap();
+
+        assert_eq!(remaining_steps(&path, &NextStep::Render), vec![NextStep::Analyze, NextStep::SetupRender, NextStep::Render]);
+        assert!(remaining_steps(&path, &NextStep::Extract).is_empty());
+
+        let earlier = temp_dir.path().join("earlier");
+        fs::create_dir_all(&earlier).unwrap();
+        fs::write(earlier.join("earlier.mkv"), vec![0u8; 5]).unwrap();
+        let run = ProcessResult { success: true, duration_ms: 400, ..ProcessResult::error(String::new()) };
+        StepLogs::write(&earlier, &NextStep::Analyze, &run).unwrap();
+
+        let paths = [earlier.as_path()];
+        assert_eq!(estimate_remaining(&path, &NextStep::Analyze, &paths), Some(800));
+        assert_eq!(estimate_remaining(&path, &NextStep::Render, &paths), None);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::models::{NextStep, Recording};
+use crate::services::{CustomSteps, ProcessPhase, ProcessResult, SettingsStore};
+use serde::{Deserialize, Serialize};
+
+/// Settings key holding the step hooks
+pub const STEP_HOOKS_KEY: &str = "step_hooks";
+
+/// Placeholders a hook command may use, with the environment variable each one reads
+const PLACEHOLDERS: [(&str, &str); 4] = [
+    ("{recording}", "FERMATA_RECORDING"),
+    ("{recording_path}", "FERMATA_RECORDING_PATH"),
+    ("{step}", "FERMATA_STEP"),
+    ("{result}", "FERMATA_STEP_RESULT"),
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookTiming {
+    /// Runs before the step; a failing hook fails the step without running it
+    Before,
+    /// Runs after the step whether it succeeded or not
+    After,
+}
+
+/// "After render, run my-notify.sh {recording} {step}"
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StepHook {
+    /// Step id, e.g. "render" or a custom step, or "*" for every step
+    pub step: String,
+    pub when: HookTiming,
+    /// Shell command; {recording}, {recording_path}, {step} and {result} are
+    /// replaced by the matching FERMATA_* environment variables
+    pub command: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl StepHook {
+    /// `custom_steps` are the ids of custom steps a hook may also name
+    pub fn validate(&self, custom_steps: &[&str]) -> Result<(), String> {
+        if self.step != "*" && !custom_steps.contains(&self.step.as_str()) {
+            self.step.parse::<NextStep>()?;
+        }
+        if self.command.trim().is_empty() {
+            return Err(format!("Hook for '{}' has no command", self.step));
+        }
+        Ok(())
+    }
+
+    pub fn applies_to(&self, step: &NextStep, when: HookTiming) -> bool {
+        self.enabled
+            && self.when == when
+            && (self.step == "*"
+                || self.step.parse::<NextStep>().is_ok_and(|s| s == *step)
+                || matches!(step, NextStep::Custom(id) if *id == self.step))
+    }
+
+    pub fn command_line(&self) -> String {
+        command_line(&self.command)
+    }
+}
+
+/// A user command with placeholders turned into quoted environment references,
+/// so names with spaces or quotes reach the script as one argument
+pub fn command_line(command: &str) -> String {
+    PLACEHOLDERS.iter().fold(command.to_string(), |command, (placeholder, variable)| {
+        let reference = if cfg!(windows) {
+            format!("\"%{}%\"", variable)
+        } else {
+            format!("\"${}\"", variable)
+        };
+        command.replace(placeholder, &reference)
+    })
+}
+
+/// Environment user commands run with; `result` is "success" or "failure" after the step
+pub fn step_environment(recording: &Recording, step: &NextStep, result: Option<&ProcessResult>) -> Vec<(String, String)> {
+    let outcome = match result {
+        Some(result) if result.success => "success",
+        Some(_) => "failure",
+        None => "",
+    };
+    [
+        ("FERMATA_RECORDING", recording.name.clone()),
+        ("FERMATA_RECORDING_PATH", recording.path.display().to_string()),
+        ("FERMATA_STEP", step.id()),
+        ("FERMATA_STEP_RESULT", outcome.to_string()),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value))
+    .collect()
+}
+
+/// Environment a hook runs with: that of the step, plus FERMATA_HOOK
+pub fn hook_environment(recording: &Recording, step: &NextStep, when: HookTiming, result: Option<&ProcessResult>) -> Vec<(String, String)> {
+    let when = match when {
+        HookTiming::Before => "before",
+        HookTiming::After => "after",
+    };
+    let mut env = step_environment(recording, step, result);
+    env.push(("FERMATA_HOOK".to_string(), when.to_string()));
+    env
+}
+
+/// One finished hook, kept for the step log
+#[derive(Debug, Clone)]
+pub struct HookRun {
+    pub when: HookTiming,
+    pub command: String,
+    pub result: ProcessResult,
+}
+
+impl HookRun {
+    fn section(&self) -> String {
+        let when = if self.when == HookTiming::Before { "before" } else { "after" };
+        let exit_code = self.result.exit_code.map_or("none".to_string(), |c| c.to_string());
+        let mut section = format!("=== HOOK {}: {} (exit {}) ===\n{}", when, self.command, exit_code, self.result.stdout);
+        if !self.result.stderr.is_empty() {
+            section.push_str(&format!("\n--- stderr ---\n{}", self.result.stderr));
+        }
+        section
+    }
+
+    fn phase(&self) -> ProcessPhase {
+        ProcessPhase {
+            name: if self.when == HookTiming::Before { "before_hook" } else { "after_hook" }.to_string(),
+            success: self.result.success,
+            started_at: self.result.started_at,
+            finished_at: self.result.finished_at,
+            duration_ms: self.result.duration_ms,
+        }
+    }
+}
+
+/// Put the output and timing of hooks around those of the step, so one step log shows all of it
+pub fn attach_hook_runs(result: &mut ProcessResult, before: &[HookRun], after: &[HookRun]) {
+    if before.is_empty() && after.is_empty() {
+        return;
+    }
+    let sections = |runs: &[HookRun]| runs.iter().map(|run| run.section() + "\n").collect::<String>();
+    result.stdout = format!("{}{}\n{}", sections(before), result.stdout, sections(after));
+
+    let mut phases: Vec<ProcessPhase> = before.iter().map(HookRun::phase).collect();
+    phases.append(&mut result.phases);
+    phases.extend(after.iter().map(HookRun::phase));
+    result.phases = phases;
+}
+
+pub struct StepHooks;
+
+impl StepHooks {
+    pub fn list(settings: &SettingsStore) -> Result<Vec<StepHook>, String> {
+        settings.get(STEP_HOOKS_KEY)
+    }
+
+    pub fn save(settings: &SettingsStore, hooks: &[StepHook]) -> Result<(), String> {
+        let custom_steps = CustomSteps::list(settings)?;
+        let custom_ids: Vec<&str> = custom_steps.iter().map(|step| step.id.as_str()).collect();
+        for hook in hooks {
+            hook.validate(&custom_ids)?;
+        }
+        settings.set(STEP_HOOKS_KEY, &hooks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(step: &str, when: HookTiming, command: &str) -> StepHook {
+        StepHook { step: step.to_string(), when, command: command.to_string(), enabled: true }
+    }
+
+    #[test]
+    fn test_hook_matching_and_validation() {
+        let after_render = hook("render", HookTiming::After, "my-notify.sh {recording} {step}");
+        assert!(after_render.applies_to(&NextStep::Render, HookTiming::After));
+        assert!(!after_render.applies_to(&NextStep::Render, HookTiming::Before));
+        assert!(!after_render.applies_to(&NextStep::Upload, HookTiming::After));
+        assert!(hook("*", HookTiming::Before, "true").applies_to(&NextStep::Upload, HookTiming::Before));
+        assert!(!StepHook { enabled: false, ..after_render.clone() }.applies_to(&NextStep::Render, HookTiming::After));
+
+        assert!(after_render.validate(&[]).is_ok());
+        assert!(hook("paint", HookTiming::After, "true").validate(&[]).is_err());
+        assert!(hook("render", HookTiming::After, "  ").validate(&[]).is_err());
+
+        let subtitles = hook("subtitles", HookTiming::After, "true");
+        assert!(subtitles.validate(&["subtitles"]).is_ok());
+        assert!(subtitles.applies_to(&NextStep::Custom("subtitles".to_string()), HookTiming::After));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_line_references_environment() {
+        let hook = hook("render", HookTiming::After, "my-notify.sh {recording} {step}");
+        assert_eq!(hook.command_line(), "my-notify.sh \"$FERMATA_RECORDING\" \"$FERMATA_STEP\"");
+    }
+
+    #[test]
+    fn test_attach_hook_runs() {
+        let mut step = ProcessResult::error("boom".to_string());
+        step.stdout = "rendering".to_string();
+        let mut hook_result = ProcessResult::error(String::new());
+        hook_result.success = true;
+        hook_result.exit_code = Some(0);
+        hook_result.stdout = "normalized".to_string();
+        let run = HookRun { when: HookTiming::Before, command: "normalize.sh".to_string(), result: hook_result };
+
+        attach_hook_runs(&mut step, &[run], &[]);
+        assert_eq!(step.stdout, "=== HOOK before: normalize.sh (exit 0) ===\nnormalized\nrendering\n");
+        assert_eq!(step.phases.len(), 1);
+        assert_eq!(step.phases[0].name, "before_hook");
+        assert_eq!(step.stderr, "boom");
+    }
+}
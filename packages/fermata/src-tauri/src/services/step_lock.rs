@@ -0,0 +1,563 @@
+use crate::models::{NextStep, RecordingStatus};
+use crate::services::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+/// Lock file held in a recording directory while a step is running
+pub const STEP_LOCK_FILE: &str = ".fermata/lock";
+
+/// A lock file this young that can't be read may still be being written by an older
+/// version, which wrote it in place; it counts as held
+const UNREADABLE_LOCK_GRACE: Duration = Duration::from_secs(5);
+
+/// Step locks this app holds right now, i.e. steps it is running
+static HELD_LOCKS: AtomicUsize = AtomicUsize::new(0);
+
+/// Tells apart the temporary files of lock operations within this process
+static SEQUENCE: AtomicUsize = AtomicUsize::new(0);
+
+/// Contents of the lock file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StepLockInfo {
+    pub pid: u32,
+    /// Machine of the app holding the lock; empty in locks from before shared libraries
+    #[serde(default)]
+    pub host: String,
+    pub step: String,
+    pub acquired_at: u64, // Unix timestamp in seconds
+    /// Processes the step launched, so ones left running by a crash can be found
+    #[serde(default)]
+    pub child_pids: Vec<u32>,
+}
+
+impl StepLockInfo {
+    /// Parsed step, if the lock was written by a compatible version
+    pub fn next_step(&self) -> Option<NextStep> {
+        self.step.parse().ok()
+    }
+
+    /// Held by an app on this machine, whose processes can be checked
+    pub fn is_local(&self) -> bool {
+        self.host.is_empty() || self.host == machine_name()
+    }
+
+    /// Same acquisition of a lock; the child PIDs change while the step runs
+    fn same_holder(&self, other: &StepLockInfo) -> bool {
+        self.pid == other.pid && self.host == other.host && self.acquired_at == other.acquired_at
+    }
+
+    /// Whether the app holding the lock still runs. Apps on other machines sharing the
+    /// library can't be checked from here, so their locks count as held.
+    fn holder_alive(&self) -> bool {
+        !self.is_local() || is_process_alive(self.pid)
+    }
+
+    fn orphans(&self) -> Vec<u32> {
+        if !self.is_local() {
+            return Vec::new();
+        }
+        self.child_pids.iter().copied().filter(|pid| is_process_alive(*pid)).collect()
+    }
+}
+
+/// Name of this machine, telling apart the locks of apps sharing a recordings library;
+/// FERMATA_MACHINE_NAME overrides it, e.g. for containers with generated host names
+pub fn machine_name() -> &'static str {
+    static NAME: OnceLock<String> = OnceLock::new();
+    NAME.get_or_init(|| {
+        std::env::var("FERMATA_MACHINE_NAME")
+            .or_else(|_| std::env::var("COMPUTERNAME"))
+            .ok()
+            .or_else(|| fs::read_to_string("/proc/sys/kernel/hostname").ok())
+            .or_else(|| {
+                std::process::Command::new("hostname")
+                    .output()
+                    .ok()
+                    .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+            })
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "localhost".to_string())
+    })
+}
+
+/// Exclusive per-recording lock for the duration of a step.
+/// The lock file is removed when the guard is dropped, including on error paths.
+#[derive(Debug)]
+pub struct StepLock {
+    lock_path: PathBuf,
+    info: StepLockInfo,
+}
+
+impl StepLock {
+    /// Lock the recording for a step, failing with a "busy" error if another live process holds it.
+    /// Locks left behind by processes that no longer exist are treated as stale and replaced.
+    pub fn acquire(recording_path: &Path, step: &NextStep) -> Result<Self, String> {
+        let lock_path = recording_path.join(STEP_LOCK_FILE);
+        if let Some(parent) = lock_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+
+        let info = StepLockInfo {
+            pid: std::process::id(),
+            host: machine_name().to_string(),
+            // The step id ("setup_render"), not its UI label
+            step: step.id(),
+            acquired_at: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            child_pids: Vec::new(),
+        };
+        let content = serde_json::to_string(&info)
+            .map_err(|e| format!("Failed to serialize step lock: {}", e))?;
+
+        // Later attempts only happen after a stale lock was removed or replaced meanwhile
+        for _ in 0..3 {
+            match place_lock(&lock_path, content.as_bytes()) {
+                Ok(()) => {
+                    log::info!("🔒 Locked {} for '{}'", recording_path.display(), step);
+                    HELD_LOCKS.fetch_add(1, Ordering::Relaxed);
+                    return Ok(Self { lock_path, info });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    let holder = read_step_lock(recording_path);
+                    match &holder {
+                        Some(holder) if holder.holder_alive() => {
+                            return Err(format!(
+                                "Recording is busy: step '{}' is already running (pid {} on {})",
+                                holder.step, holder.pid, holder.host
+                            ));
+                        }
+                        Some(holder) if !holder.orphans().is_empty() => {
+                            return Err(format!(
+                                "Recording is busy: processes of the interrupted step '{}' are still running - resolve it first",
+                                holder.step
+                            ));
+                        }
+                        None if modified_within(&lock_path, UNREADABLE_LOCK_GRACE) => {
+                            return Err(format!("Recording is busy: {} is being written", lock_path.display()));
+                        }
+                        _ => {
+                            // Another app may have judged it stale too and replaced it already
+                            let still_stale = |moved: Option<&StepLockInfo>| match (&holder, moved) {
+                                (Some(holder), Some(moved)) => holder.same_holder(moved),
+                                (None, None) => true,
+                                _ => false,
+                            };
+                            match remove_lock_if(&lock_path, still_stale) {
+                                Ok(true) => log::warn!("Removed stale step lock: {}", lock_path.display()),
+                                Ok(false) => {}
+                                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                                Err(e) => return Err(format!("Failed to remove stale step lock: {}", e)),
+                            }
+                        }
+                    }
+                }
+                Err(e) => return Err(format!("Failed to create step lock: {}", e)),
+            }
+        }
+
+        Err(format!("Recording is busy: could not acquire {}", lock_path.display()))
+    }
+}
+
+impl Drop for StepLock {
+    fn drop(&mut self) {
+        HELD_LOCKS.fetch_sub(1, Ordering::Relaxed);
+        // The lock may have been broken by hand and taken by another step since
+        match remove_lock_if(&self.lock_path, |held| held.is_some_and(|held| held.same_holder(&self.info))) {
+            Ok(true) => {}
+            Ok(false) => log::warn!("Step lock {} is held by another step, leaving it", self.lock_path.display()),
+            Err(e) => log::warn!("Failed to remove step lock {}: {}", self.lock_path.display(), e),
+        }
+    }
+}
+
+/// `lock.<pid>.<seq>.<suffix>` next to the lock file
+fn unique_sibling(lock_path: &Path, suffix: &str) -> PathBuf {
+    lock_path.with_file_name(format!(
+        "lock.{}.{}.{}",
+        std::process::id(),
+        SEQUENCE.fetch_add(1, Ordering::Relaxed),
+        suffix
+    ))
+}
+
+/// Write the lock under a temporary name and link it into place, so the lock file never
+/// exists without its full contents; fails with AlreadyExists while another lock is held.
+/// Filesystems without hard links (exFAT, FAT32, many network shares) create the file
+/// exclusively instead; a reader seeing it half-written takes it as held.
+fn place_lock(lock_path: &Path, content: &[u8]) -> io::Result<()> {
+    let temp = unique_sibling(lock_path, "tmp");
+    let placed = fs::write(&temp, content).and_then(|_| fs::hard_link(&temp, lock_path));
+    let _ = fs::remove_file(&temp);
+    match placed {
+        Err(e) if matches!(e.kind(), ErrorKind::Unsupported | ErrorKind::PermissionDenied) => {
+            let mut file = fs::OpenOptions::new().write(true).create_new(true).open(lock_path)?;
+            file.write_all(content).and_then(|_| file.sync_all())
+        }
+        placed => placed,
+    }
+}
+
+/// Move the lock aside under a unique name, which is atomic, and delete it if it is the
+/// lock `expected` accepts (None for unreadable ones). Anything else is put back, unless
+/// another lock took its place meanwhile. True if the lock was deleted.
+fn remove_lock_if(lock_path: &Path, expected: impl Fn(Option<&StepLockInfo>) -> bool) -> io::Result<bool> {
+    let aside = unique_sibling(lock_path, "old");
+    fs::rename(lock_path, &aside)?;
+    let content = fs::read(&aside)?;
+    let moved = serde_json::from_slice(&content).ok();
+    if !expected(moved.as_ref()) {
+        match place_lock(lock_path, &content) {
+            Ok(()) => {
+                let _ = fs::remove_file(&aside);
+            }
+            Err(e) => log::warn!("Failed to put back step lock {}, kept as {}: {}", lock_path.display(), aside.display(), e),
+        }
+        return Ok(false);
+    }
+    fs::remove_file(&aside)?;
+    Ok(true)
+}
+
+fn modified_within(path: &Path, age: Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| modified.elapsed().map_or(true, |elapsed| elapsed < age))
+}
+
+/// Number of steps this app is running
+pub fn held_step_locks() -> usize {
+    HELD_LOCKS.load(Ordering::Relaxed)
+}
+
+/// Read the lock currently held on a recording, if any
+pub fn read_step_lock(recording_path: &Path) -> Option<StepLockInfo> {
+    let content = fs::read_to_string(recording_path.join(STEP_LOCK_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Remember a process launched for the step holding the lock, if this process holds it
+pub fn record_child_pid(recording_path: &Path, pid: u32) {
+    update_own_lock(recording_path, |info| info.child_pids.push(pid));
+}
+
+/// Forget a launched process once it has exited
+pub fn forget_child_pid(recording_path: &Path, pid: u32) {
+    update_own_lock(recording_path, |info| info.child_pids.retain(|p| *p != pid));
+}
+
+fn update_own_lock(recording_path: &Path, update: impl FnOnce(&mut StepLockInfo)) {
+    let Some(mut info) = read_step_lock(recording_path) else {
+        return;
+    };
+    if info.pid != std::process::id() || !info.is_local() {
+        return;
+    }
+    update(&mut info);
+    let path = recording_path.join(STEP_LOCK_FILE);
+    let written = serde_json::to_string(&info)
+        .map_err(|e| e.to_string())
+        .and_then(|content| write_atomic(&path, content).map_err(|e| e.to_string()));
+    if let Err(e) = written {
+        log::warn!("Failed to update step lock {}: {}", path.display(), e);
+    }
+}
+
+/// A step whose app quit or crashed while it ran, found by the lock it left behind
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct InterruptedStep {
+    pub recording_name: String,
+    pub step: String,
+    /// The app process that ran the step
+    pub pid: u32,
+    pub acquired_at: u64, // Unix timestamp in seconds
+    /// Processes the step launched that are still running, e.g. a Blender render.
+    /// PIDs can be reused after a reboot, so these are only killed when asked to.
+    pub orphan_pids: Vec<u32>,
+}
+
+/// The step interrupted in a recording, if its lock outlived the process holding it
+pub fn find_interrupted_step(recording_path: &Path) -> Option<InterruptedStep> {
+    let lock = read_step_lock(recording_path)?;
+    if lock.holder_alive() {
+        return None;
+    }
+    Some(InterruptedStep {
+        recording_name: recording_path.file_name()?.to_string_lossy().to_string(),
+        orphan_pids: lock.orphans(),
+        step: lock.step,
+        pid: lock.pid,
+        acquired_at: lock.acquired_at,
+    })
+}
+
+/// Remove the lock of an interrupted step so it can run again. Fails while its processes
+/// still run, unless `kill_orphans` stops them first.
+pub fn resolve_interrupted_step(recording_path: &Path, kill_orphans: bool) -> Result<InterruptedStep, String> {
+    let interrupted = find_interrupted_step(recording_path)
+        .ok_or_else(|| format!("No interrupted step in {}", recording_path.display()))?;
+    if !interrupted.orphan_pids.is_empty() {
+        if !kill_orphans {
+            return Err(format!(
+                "Processes {:?} of the interrupted step '{}' are still running",
+                interrupted.orphan_pids, interrupted.step
+            ));
+        }
+        for pid in &interrupted.orphan_pids {
+            log::warn!("Killing orphaned process {} of '{}'", pid, interrupted.step);
+            kill_process(*pid)?;
+        }
+    }
+    let lock_path = recording_path.join(STEP_LOCK_FILE);
+    fs::remove_file(&lock_path).map_err(|e| format!("Failed to remove {}: {}", lock_path.display(), e))?;
+    Ok(interrupted)
+}
+
+/// A step running in a recording, here or in another app sharing the library
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ActiveStep {
+    pub recording_name: String,
+    pub step: String,
+    pub host: String,
+    pub pid: u32,
+    pub acquired_at: u64, // Unix timestamp in seconds
+    /// Run by this app rather than one on another machine
+    pub local: bool,
+}
+
+/// The step running in a recording, if its lock is held
+pub fn find_active_step(recording_path: &Path) -> Option<ActiveStep> {
+    let lock = read_step_lock(recording_path)?;
+    if !lock.holder_alive() {
+        return None;
+    }
+    Some(ActiveStep {
+        recording_name: recording_path.file_name()?.to_string_lossy().to_string(),
+        local: lock.is_local(),
+        host: if lock.host.is_empty() { machine_name().to_string() } else { lock.host },
+        step: lock.step,
+        pid: lock.pid,
+        acquired_at: lock.acquired_at,
+    })
+}
+
+/// Transient status for a recording based on a live step lock, or on processes an
+/// interrupted step left running
+pub fn detect_in_progress_status(recording_path: &Path) -> Option<RecordingStatus> {
+    let lock = read_step_lock(recording_path)?;
+    if !lock.holder_alive() && lock.orphans().is_empty() {
+        return None;
+    }
+    // Steps that do not parse are custom steps from the settings
+    match lock.next_step() {
+        Some(step) => RecordingStatus::in_progress(&step),
+        None => Some(RecordingStatus::RunningStep(lock.step)),
+    }
+}
+
+fn kill_process(pid: u32) -> Result<(), String> {
+    #[cfg(unix)]
+    let output = std::process::Command::new("kill").args(["-TERM", &pid.to_string()]).output();
+
+    #[cfg(windows)]
+    let output = std::process::Command::new("taskkill").args(["/PID", &pid.to_string(), "/T", "/F"]).output();
+
+    #[cfg(not(any(unix, windows)))]
+    let output: std::io::Result<std::process::Output> = Err(std::io::Error::from(ErrorKind::Unsupported));
+
+    match output {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => Err(format!("Failed to kill process {}: {}", pid, String::from_utf8_lossy(&output.stderr).trim())),
+        Err(e) => Err(format!("Failed to kill process {}: {}", pid, e)),
+    }
+}
+
+/// Check whether a process with the given PID still exists
+fn is_process_alive(pid: u32) -> bool {
+    if pid == std::process::id() {
+        return true;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Path::new("/proc").join(pid.to_string()).exists()
+    }
+
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        std::process::Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(true)
+    }
+
+    #[cfg(windows)]
+    {
+        std::process::Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+            .unwrap_or(true)
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lock_is_written_and_released() {
+        let temp_dir = TempDir::new().unwrap();
+
+        {
+            let _lock = StepLock::acquire(temp_dir.path(), &NextStep::SetupRender).unwrap();
+            let info = read_step_lock(temp_dir.path()).unwrap();
+            assert_eq!(info.pid, std::process::id());
+            assert_eq!(info.next_step(), Some(NextStep::SetupRender));
+            assert_eq!(detect_in_progress_status(temp_dir.path()), Some(RecordingStatus::SettingUpRender));
+        }
+
+        assert!(!temp_dir.path().join(STEP_LOCK_FILE).exists());
+        assert_eq!(detect_in_progress_status(temp_dir.path()), None);
+    }
+
+    #[test]
+    fn test_second_acquire_is_busy() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let _lock = StepLock::acquire(temp_dir.path(), &NextStep::Analyze).unwrap();
+        let result = StepLock::acquire(temp_dir.path(), &NextStep::SetupRender);
+
+        assert!(result.unwrap_err().contains("busy"));
+    }
+
+    #[test]
+    fn test_stale_lock_is_replaced() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".fermata")).unwrap();
+        let stale = StepLockInfo { pid: u32::MAX, host: String::new(), step: "render".to_string(), acquired_at: 0, child_pids: Vec::new() };
+        fs::write(temp_dir.path().join(STEP_LOCK_FILE), serde_json::to_string(&stale).unwrap()).unwrap();
+
+        assert_eq!(detect_in_progress_status(temp_dir.path()), None);
+
+        let _lock = StepLock::acquire(temp_dir.path(), &NextStep::Analyze).unwrap();
+        assert_eq!(read_step_lock(temp_dir.path()).unwrap().step, "analyze");
+    }
+
+    #[test]
+    fn test_lock_replaced_meanwhile_is_not_removed() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join(STEP_LOCK_FILE);
+        let stale = StepLockInfo { pid: u32::MAX, host: String::new(), step: "render".to_string(), acquired_at: 0, child_pids: Vec::new() };
+        // Another app removed the stale lock and placed its own before this one got to it
+        let _lock = StepLock::acquire(temp_dir.path(), &NextStep::Analyze).unwrap();
+        let fresh = read_step_lock(temp_dir.path()).unwrap();
+
+        let removed = remove_lock_if(&lock_path, |moved| moved.is_some_and(|moved| stale.same_holder(moved))).unwrap();
+        assert!(!removed);
+        assert_eq!(read_step_lock(temp_dir.path()).unwrap(), fresh);
+        assert_eq!(fs::read_dir(temp_dir.path().join(".fermata")).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_drop_leaves_lock_of_another_step() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock = StepLock::acquire(temp_dir.path(), &NextStep::Analyze).unwrap();
+        // Broken by hand and taken by a step in another app
+        let other = StepLockInfo { pid: 1, host: "studio".to_string(), step: "render".to_string(), acquired_at: 5, child_pids: Vec::new() };
+        fs::write(temp_dir.path().join(STEP_LOCK_FILE), serde_json::to_string(&other).unwrap()).unwrap();
+
+        drop(lock);
+        assert_eq!(read_step_lock(temp_dir.path()), Some(other));
+    }
+
+    #[test]
+    fn test_unreadable_lock_is_stale_only_when_old() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".fermata")).unwrap();
+        let lock_path = temp_dir.path().join(STEP_LOCK_FILE);
+        fs::write(&lock_path, "{\"pid\": 12").unwrap();
+
+        let result = StepLock::acquire(temp_dir.path(), &NextStep::Analyze);
+        assert!(result.unwrap_err().contains("busy"));
+
+        let old = SystemTime::now() - UNREADABLE_LOCK_GRACE * 2;
+        fs::File::options().write(true).open(&lock_path).unwrap().set_modified(old).unwrap();
+        let _lock = StepLock::acquire(temp_dir.path(), &NextStep::Analyze).unwrap();
+        assert_eq!(read_step_lock(temp_dir.path()).unwrap().step, "analyze");
+        let leftovers = fs::read_dir(temp_dir.path().join(".fermata")).unwrap().count();
+        assert_eq!(leftovers, 1);
+    }
+
+    #[test]
+    fn test_interrupted_step_with_orphans() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("rec_1");
+        fs::create_dir_all(path.join(".fermata")).unwrap();
+        // A live process stands in for the render the crashed app left running
+        let orphan = std::process::id();
+        let crashed = StepLockInfo {
+            pid: u32::MAX,
+            host: machine_name().to_string(),
+            step: "render".to_string(),
+            acquired_at: 7,
+            child_pids: vec![orphan, u32::MAX - 1],
+        };
+        fs::write(path.join(STEP_LOCK_FILE), serde_json::to_string(&crashed).unwrap()).unwrap();
+
+        let interrupted = find_interrupted_step(&path).unwrap();
+        assert_eq!(interrupted.recording_name, "rec_1");
+        assert_eq!(interrupted.orphan_pids, [orphan]);
+        assert_eq!(detect_in_progress_status(&path), Some(RecordingStatus::Rendering));
+        assert!(StepLock::acquire(&path, &NextStep::Render).unwrap_err().contains("interrupted"));
+        assert!(resolve_interrupted_step(&path, false).is_err());
+
+        let crashed = StepLockInfo { child_pids: vec![u32::MAX - 1], ..crashed };
+        fs::write(path.join(STEP_LOCK_FILE), serde_json::to_string(&crashed).unwrap()).unwrap();
+        assert_eq!(resolve_interrupted_step(&path, false).unwrap().step, "render");
+        assert!(find_interrupted_step(&path).is_none());
+
+        let _lock = StepLock::acquire(&path, &NextStep::Render).unwrap();
+        record_child_pid(&path, 42);
+        assert_eq!(read_step_lock(&path).unwrap().child_pids, [42]);
+        forget_child_pid(&path, 42);
+        assert!(read_step_lock(&path).unwrap().child_pids.is_empty());
+    }
+
+    #[test]
+    fn test_lock_of_another_machine_is_held() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("rec_1");
+        fs::create_dir_all(path.join(".fermata")).unwrap();
+        let elsewhere = StepLockInfo {
+            pid: u32::MAX,
+            host: format!("{}-nas-peer", machine_name()),
+            step: "render".to_string(),
+            acquired_at: 7,
+            child_pids: vec![std::process::id()],
+        };
+        fs::write(path.join(STEP_LOCK_FILE), serde_json::to_string(&elsewhere).unwrap()).unwrap();
+
+        let active = find_active_step(&path).unwrap();
+        assert!(!active.local);
+        assert_eq!(detect_in_progress_status(&path), Some(RecordingStatus::Rendering));
+        assert!(find_interrupted_step(&path).is_none());
+        assert!(StepLock::acquire(&path, &NextStep::Render).unwrap_err().contains("nas-peer"));
+    }
+}
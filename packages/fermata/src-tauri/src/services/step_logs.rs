@@ -0,0 +1,200 @@
+use crate::models::NextStep;
+use crate::services::{ProcessResult, StepOutcome};
+use chrono::Local;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Full output of every step run, kept with the recording
+pub const STEP_LOGS_DIR: &str = ".fermata/logs";
+
+/// A stored step log; `id` is the file name, e.g. `analyze_20240114-020000.log`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StepLogEntry {
+    pub id: String,
+    pub step: String,
+    /// Local time the step finished, as in the file name
+    pub timestamp: String,
+    pub success: bool,
+    /// Missing in logs written before durations were recorded
+    pub duration_ms: Option<u64>,
+    pub size_bytes: u64,
+    /// Options the step ran with, when it takes any
+    pub options: Option<serde_json::Value>,
+    /// What the tool reported, see ProcessResult::outcome
+    pub outcome: Option<StepOutcome>,
+}
+
+pub struct StepLogs;
+
+impl StepLogs {
+    fn dir(recording_path: &Path) -> PathBuf {
+        recording_path.join(STEP_LOGS_DIR)
+    }
+
+    /// Write the output of a finished step and return the log id
+    pub fn write(recording_path: &Path, step: &NextStep, result: &ProcessResult) -> Result<String, String> {
+        Self::write_with_options(recording_path, step, result, None)
+    }
+
+    /// Like `write`, also recording the options the step ran with so the run can be repeated
+    pub fn write_with_options(
+        recording_path: &Path,
+        step: &NextStep,
+        result: &ProcessResult,
+        options: Option<&serde_json::Value>,
+    ) -> Result<String, String> {
+        let dir = Self::dir(recording_path);
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+        let timestamp = Local::now().format("%Y%m%d-%H%M%S");
+        let mut id = format!("{}_{}.log", step, timestamp);
+        // Two runs of the same step within a second must not overwrite each other
+        let mut counter = 1;
+        while dir.join(&id).exists() {
+            counter += 1;
+            id = format!("{}_{}-{}.log", step, timestamp, counter);
+        }
+
+        let content = format!(
+            "step: {}\nsuccess: {}\nexit_code: {}\nduration_ms: {}\n{}{}{}\n=== STDOUT ===\n{}\n=== STDERR ===\n{}\n",
+            step,
+            result.success,
+            result.exit_code.map_or("none".to_string(), |c| c.to_string()),
+            result.duration_ms,
+            options.map_or(String::new(), |options| format!("options: {}\n", options)),
+            result
+                .outcome
+                .as_ref()
+                .and_then(|outcome| serde_json::to_string(outcome).ok())
+                .map_or(String::new(), |outcome| format!("outcome: {}\n", outcome)),
+            result
+                .phases
+                .iter()
+                .map(|p| format!("phase {}: {} ms{}\n", p.name, p.duration_ms, if p.success { "" } else { " (failed)" }))
+                .collect::<String>(),
+            result.stdout,
+            result.stderr
+        );
+        let path = dir.join(&id);
+        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(id)
+    }
+
+    /// Stored logs, newest first
+    pub fn list(recording_path: &Path) -> Vec<StepLogEntry> {
+        let mut entries: Vec<StepLogEntry> = fs::read_dir(Self::dir(recording_path))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| {
+                        let id = entry.file_name().to_string_lossy().to_string();
+                        let (step, timestamp) = id.strip_suffix(".log")?.rsplit_once('_')?;
+                        let content = fs::read_to_string(entry.path()).unwrap_or_default();
+                        let success = content.lines().nth(1) == Some("success: true");
+                        let header = || content.lines().take_while(|line| !line.starts_with("==="));
+                        let duration_ms = header()
+                            .find_map(|line| line.strip_prefix("duration_ms: "))
+                            .and_then(|ms| ms.parse().ok());
+                        let options = header()
+                            .find_map(|line| line.strip_prefix("options: "))
+                            .and_then(|options| serde_json::from_str(options).ok());
+                        let outcome = header()
+                            .find_map(|line| line.strip_prefix("outcome: "))
+                            .and_then(|outcome| serde_json::from_str(outcome).ok());
+
+                        Some(StepLogEntry {
+                            step: step.to_string(),
+                            timestamp: timestamp.to_string(),
+                            success,
+                            duration_ms,
+                            size_bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                            options,
+                            outcome,
+                            id,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| a.step.cmp(&b.step)));
+        entries
+    }
+
+    /// Content of a log, optionally only its last `tail_lines` lines
+    pub fn read(recording_path: &Path, log_id: &str, tail_lines: Option<usize>) -> Result<String, String> {
+        if log_id.is_empty() || log_id.contains(['/', '\\']) || log_id.contains("..") || !log_id.ends_with(".log") {
+            return Err(format!("Invalid log id '{}'", log_id));
+        }
+
+        let path = Self::dir(recording_path).join(log_id);
+        let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read log '{}': {}", log_id, e))?;
+
+        Ok(match tail_lines {
+            Some(count) => {
+                let lines: Vec<&str> = content.lines().collect();
+                lines[lines.len().saturating_sub(count)..].join("\n")
+            }
+            None => content,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn result(success: bool, stderr: &str) -> ProcessResult {
+        ProcessResult {
+            success,
+            stdout: "line 1\nline 2".to_string(),
+            exit_code: Some(if success { 0 } else { 1 }),
+            duration_ms: 1_500,
+            ..ProcessResult::error(stderr.to_string())
+        }
+    }
+
+    #[test]
+    fn test_write_list_and_read() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = StepLogs::write(temp_dir.path(), &NextStep::Analyze, &result(true, "")).unwrap();
+        let second = StepLogs::write(temp_dir.path(), &NextStep::Analyze, &result(false, "Traceback\nboom")).unwrap();
+        assert_ne!(first, second);
+        assert!(first.starts_with("analyze_"));
+
+        let logs = StepLogs::list(temp_dir.path());
+        assert_eq!(logs.len(), 2);
+        assert!(logs.iter().any(|l| l.id == second && !l.success));
+        assert!(logs.iter().all(|l| l.step == "analyze"));
+        assert!(logs.iter().all(|l| l.duration_ms == Some(1_500)));
+
+        assert!(logs.iter().all(|l| l.options.is_none()));
+
+        assert_eq!(StepLogs::read(temp_dir.path(), &second, Some(2)).unwrap(), "Traceback\nboom");
+        assert!(StepLogs::read(temp_dir.path(), &first, None).unwrap().contains("line 2"));
+    }
+
+    #[test]
+    fn test_read_rejects_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(StepLogs::read(temp_dir.path(), "../lock", None).is_err());
+        assert!(StepLogs::read(temp_dir.path(), "../../etc/passwd.log", None).is_err());
+    }
+
+    #[test]
+    fn test_options_are_kept_in_the_log() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = serde_json::json!({"tempo_min": 80.0, "profile": "speech"});
+        let outcome = StepOutcome::Analysis { file: temp_dir.path().join("analysis/Mic_analysis.json") };
+        let run = ProcessResult { outcome: Some(outcome.clone()), ..result(true, "") };
+        StepLogs::write_with_options(temp_dir.path(), &NextStep::Analyze, &run, Some(&options)).unwrap();
+
+        let logs = StepLogs::list(temp_dir.path());
+        assert_eq!(logs[0].options, Some(options));
+        assert_eq!(logs[0].outcome, Some(outcome));
+        assert!(logs[0].success);
+        assert_eq!(logs[0].duration_ms, Some(1_500));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::models::UploadEntry;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// What a tool reported about its work, parsed from its output so later code
+/// doesn't have to guess file names
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StepOutcome {
+    /// beatrix wrote the analysis of an audio file
+    Analysis { file: PathBuf },
+    /// cinemon generated the animation config of a preset
+    AnimationConfig { file: PathBuf, preset: String },
+    /// medusa published the video
+    Upload { uploads: Vec<UploadEntry> },
+}
+
+impl StepOutcome {
+    /// beatrix prints `Analysis complete: <path>` once the analysis is saved
+    pub fn from_beatrix(stdout: &str, cwd: &Path) -> Option<Self> {
+        let file = last_value(stdout, "Analysis complete:")?;
+        Some(StepOutcome::Analysis { file: cwd.join(file) })
+    }
+
+    /// cinemon-generate-config prints `✅ Configuration generated: <path>` and then
+    /// `Preset: <name>`; the preset it was asked for stands in for a missing one
+    pub fn from_cinemon_config(stdout: &str, cwd: &Path, preset: &str) -> Option<Self> {
+        let file = last_value(stdout, "Configuration generated:")?;
+        let preset = last_value(stdout, "Preset:").unwrap_or(preset);
+        Some(StepOutcome::AnimationConfig { file: cwd.join(file), preset: preset.to_string() })
+    }
+
+    /// medusa prints `✅ Upload successful: <url>` once the video is published
+    pub fn from_medusa(stdout: &str) -> Option<Self> {
+        let url = last_value(stdout, "Upload successful:")?;
+        let upload = UploadEntry {
+            platform: "youtube".to_string(),
+            upload_id: None,
+            success: true,
+            media_url: Some(url.to_string()),
+            error: None,
+            timestamp: None,
+            publish_status: None,
+            extra: Default::default(),
+        };
+        Some(StepOutcome::Upload { uploads: vec![upload] })
+    }
+}
+
+/// What follows `label` on the last line containing it
+fn last_value<'a>(output: &'a str, label: &str) -> Option<&'a str> {
+    output
+        .lines()
+        .rev()
+        .find_map(|line| line.split_once(label))
+        .map(|(_, value)| value.trim())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_tool_output() {
+        let cwd = Path::new("/workspace");
+        assert_eq!(
+            StepOutcome::from_beatrix("Analysis complete: /gig/analysis/Mic_analysis.json\n", cwd),
+            Some(StepOutcome::Analysis { file: PathBuf::from("/gig/analysis/Mic_analysis.json") })
+        );
+
+        let cinemon = "✅ Configuration generated: gig/animation_config_minimal.yaml\n   Preset: minimal\n";
+        assert_eq!(
+            StepOutcome::from_cinemon_config(cinemon, cwd, "beat-switch"),
+            Some(StepOutcome::AnimationConfig { file: PathBuf::from("/workspace/gig/animation_config_minimal.yaml"), preset: "minimal".to_string() })
+        );
+        assert_eq!(StepOutcome::from_cinemon_config("Error: boom\n", cwd, "minimal"), None);
+
+        let Some(StepOutcome::Upload { uploads }) = StepOutcome::from_medusa("✅ Upload successful: https://youtu.be/abc\n") else {
+            panic!("expected an upload");
+        };
+        assert_eq!(uploads[0].link().as_deref(), Some("https://youtu.be/abc"));
+        assert_eq!(StepOutcome::from_medusa("❌ Upload failed: quota exceeded\n"), None);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::models::NextStep;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Scratch files of running steps, one directory per step
+pub const SCRATCH_DIR: &str = ".fermata/tmp";
+
+/// A step's scratch directory under SCRATCH_DIR, for files it writes before they are
+/// complete. It is removed once the step succeeds and kept after a failure, so the
+/// files can be inspected; the next run of the step writes over them.
+pub struct StepScratch {
+    dir: PathBuf,
+}
+
+impl StepScratch {
+    pub fn new(recording_path: &Path, step: &NextStep) -> Self {
+        Self { dir: recording_path.join(SCRATCH_DIR).join(step.id()) }
+    }
+
+    /// Create the directory; tools given `path` may also create it themselves
+    pub fn create(&self) -> Result<(), String> {
+        fs::create_dir_all(&self.dir).map_err(|e| format!("Failed to create {}: {}", self.dir.display(), e))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Where to write `output` until it is complete; the name is kept so tools
+    /// such as ffmpeg still pick the format from the extension
+    pub fn partial(&self, output: &Path) -> PathBuf {
+        self.dir.join(output.file_name().unwrap_or(output.as_os_str()))
+    }
+
+    /// Remove the directory after a successful step, keep it after a failed one
+    pub fn finish(self, success: bool) {
+        if !success {
+            log::info!("🧪 Kept scratch files of the failed step in {}", self.dir.display());
+            return;
+        }
+        match fs::remove_dir_all(&self.dir) {
+            Err(e) if e.kind() != ErrorKind::NotFound => log::warn!("Failed to remove {}: {}", self.dir.display(), e),
+            _ => {
+                // Fails while other steps keep scratch files, which is fine
+                if let Some(parent) = self.dir.parent() {
+                    let _ = fs::remove_dir(parent);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scratch_is_kept_only_after_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+
+        let scratch = StepScratch::new(recording, &NextStep::Upload);
+        scratch.create().unwrap();
+        let partial = scratch.partial(&recording.join("uploads").join("youtube.mp4"));
+        assert_eq!(partial, recording.join(".fermata/tmp/upload/youtube.mp4"));
+        fs::write(&partial, "half").unwrap();
+        scratch.finish(false);
+        assert!(partial.exists());
+
+        StepScratch::new(recording, &NextStep::Upload).finish(true);
+        assert!(!recording.join(SCRATCH_DIR).exists());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::services::write_atomic;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory of a recording the transcribe step writes to
+pub const SUBTITLES_DIR: &str = "subtitles";
+
+/// Subtitle files of a recording, found in subtitles/
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SubtitleFiles {
+    pub srt: Option<PathBuf>,
+    pub vtt: Option<PathBuf>,
+}
+
+impl SubtitleFiles {
+    /// `None` when the recording has not been transcribed
+    pub fn find(recording_path: &Path) -> Option<Self> {
+        let files = Self {
+            srt: first_with_extension(&recording_path.join(SUBTITLES_DIR), "srt"),
+            vtt: first_with_extension(&recording_path.join(SUBTITLES_DIR), "vtt"),
+        };
+        (files.srt.is_some() || files.vtt.is_some()).then_some(files)
+    }
+}
+
+/// Write a WebVTT copy next to the SRT file whisper produced
+pub fn write_vtt(srt_path: &Path) -> Result<PathBuf, String> {
+    let srt = fs::read_to_string(srt_path).map_err(|e| format!("Failed to read {}: {}", srt_path.display(), e))?;
+    let vtt_path = srt_path.with_extension("vtt");
+    write_atomic(&vtt_path, srt_to_vtt(&srt)).map_err(|e| format!("Failed to write {}: {}", vtt_path.display(), e))?;
+    Ok(vtt_path)
+}
+
+/// WebVTT from SRT: a header, no cue numbers and `.` as the millisecond separator
+pub fn srt_to_vtt(srt: &str) -> String {
+    let mut vtt = String::from("WEBVTT\n");
+    for cue in srt.replace("\r\n", "\n").split("\n\n").map(str::trim).filter(|c| !c.is_empty()) {
+        let mut lines = cue.lines().peekable();
+        if lines.peek().is_some_and(|line| line.trim().parse::<u32>().is_ok()) {
+            lines.next();
+        }
+        vtt.push('\n');
+        for line in lines {
+            if line.contains("-->") {
+                vtt.push_str(&line.replace(',', "."));
+            } else {
+                vtt.push_str(line);
+            }
+            vtt.push('\n');
+        }
+    }
+    vtt
+}
+
+fn first_with_extension(dir: &Path, extension: &str) -> Option<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e.eq_ignore_ascii_case(extension)))
+        .collect();
+    files.sort();
+    files.into_iter().next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_srt_to_vtt() {
+        let srt = "1\r\n00:00:01,000 --> 00:00:03,500\r\nHello, world\r\n\r\n2\r\n00:00:04,000 --> 00:00:05,000\r\nNext song\r\n";
+        assert_eq!(
+            srt_to_vtt(srt),
+            "WEBVTT\n\n00:00:01.000 --> 00:00:03.500\nHello, world\n\n00:00:04.000 --> 00:00:05.000\nNext song\n"
+        );
+    }
+
+    #[test]
+    fn test_find_subtitle_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let recording = temp_dir.path();
+        assert_eq!(SubtitleFiles::find(recording), None);
+
+        fs::create_dir_all(recording.join(SUBTITLES_DIR)).unwrap();
+        fs::write(recording.join("subtitles/Mic.srt"), "1\n00:00:01,000 --> 00:00:02,000\nHi\n").unwrap();
+        write_vtt(&recording.join("subtitles/Mic.srt")).unwrap();
+        let files = SubtitleFiles::find(recording).unwrap();
+        assert_eq!(files.srt, Some(recording.join("subtitles/Mic.srt")));
+        assert_eq!(files.vtt, Some(recording.join("subtitles/Mic.vtt")));
+    }
+}
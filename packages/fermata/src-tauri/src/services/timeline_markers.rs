@@ -0,0 +1,148 @@
+use crate::services::{extracted_audio_files, find_main_audio, SourceOffsets};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A section of the beatrix analysis on the video timeline
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TimelineSection {
+    pub start: f64,
+    pub end: f64,
+    pub label: String,
+}
+
+/// Beats, sections and energy peaks of the main audio analysis, in seconds of the
+/// main video so a scrubber can draw them as they are
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TimelineMarkers {
+    /// Analysis file the markers come from, relative to analysis/
+    pub analysis_file: String,
+    /// Added to every analysis time: where the main audio starts in the video
+    pub offset_seconds: f64,
+    /// Length of the analyzed audio
+    pub duration: Option<f64>,
+    pub bpm: Option<f64>,
+    pub beats: Vec<f64>,
+    pub sections: Vec<TimelineSection>,
+    pub energy_peaks: Vec<f64>,
+}
+
+impl TimelineMarkers {
+    /// Markers from a beatrix analysis, every beat included rather than the
+    /// divided animation beats
+    pub fn from_analysis(analysis_file: &str, analysis: &Value, offset_seconds: f64) -> Self {
+        let events = &analysis["animation_events"];
+        let shift = |time: f64| round_ms(time + offset_seconds);
+        let times = |values: Option<&Vec<Value>>| -> Vec<f64> {
+            values.map(|values| values.iter().filter_map(Value::as_f64).map(shift).collect()).unwrap_or_default()
+        };
+
+        let sections = events["sections"]
+            .as_array()
+            .map(|sections| {
+                sections
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, section)| {
+                        let start = section["start"].as_f64()?;
+                        Some(TimelineSection {
+                            start: shift(start),
+                            end: shift(section["end"].as_f64().unwrap_or(start)),
+                            label: section["label"].as_str().map(str::to_string).unwrap_or_else(|| format!("section_{}", index + 1)),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            analysis_file: analysis_file.to_string(),
+            offset_seconds,
+            duration: analysis["duration"].as_f64(),
+            bpm: analysis["tempo"]["bpm"].as_f64(),
+            beats: times(analysis["tempo"]["beat_times"].as_array().or_else(|| events["beats"].as_array())),
+            sections,
+            energy_peaks: times(events["energy_peaks"].as_array()),
+        }
+    }
+
+    /// Markers of the main audio's analysis. `offset_seconds` is None when the
+    /// source offsets can't be worked out; the analysis times are used as they are then.
+    pub fn load(recording_path: &Path, main_audio: &str, offset_seconds: Option<f64>) -> Result<Self, String> {
+        let analysis_path = main_analysis_file(recording_path, main_audio)
+            .ok_or_else(|| "No audio analysis found - run the analyze step first".to_string())?;
+        let content = fs::read_to_string(&analysis_path)
+            .map_err(|e| format!("Failed to read {}: {}", analysis_path.display(), e))?;
+        let analysis: Value =
+            serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", analysis_path.display(), e))?;
+        let file_name = analysis_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        Ok(Self::from_analysis(&file_name, &analysis, offset_seconds.unwrap_or(0.0)))
+    }
+}
+
+/// Where the main audio starts in the video, from the source offsets
+pub fn main_audio_start(offsets: &SourceOffsets) -> Option<f64> {
+    offsets.sources.iter().find(|source| source.file == offsets.reference).and_then(|source| source.start_seconds)
+}
+
+/// `<main audio stem>_analysis.json` as beatrix names it, else the first analysis file
+fn main_analysis_file(recording_path: &Path, main_audio: &str) -> Option<PathBuf> {
+    let analysis_dir = recording_path.join("analysis");
+    let audio_files = extracted_audio_files(recording_path).unwrap_or_default();
+    let named = find_main_audio(&audio_files, main_audio)
+        .or(audio_files.first())
+        .and_then(|audio| Path::new(audio).file_stem().map(|stem| analysis_dir.join(format!("{}_analysis.json", stem.to_string_lossy()))))
+        .filter(|path| path.is_file());
+    named.or_else(|| {
+        let mut files: Vec<PathBuf> = fs::read_dir(&analysis_dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+        files.into_iter().next()
+    })
+}
+
+fn round_ms(seconds: f64) -> f64 {
+    (seconds * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_markers_shifted_onto_video() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        fs::create_dir_all(path.join("extracted")).unwrap();
+        fs::create_dir_all(path.join("analysis")).unwrap();
+        fs::write(path.join("extracted/Mic.m4a"), "audio").unwrap();
+        fs::write(path.join("analysis/Guitar_analysis.json"), "{}").unwrap();
+        fs::write(
+            path.join("analysis/Mic_analysis.json"),
+            r#"{
+                "duration": 10.0,
+                "tempo": {"bpm": 120.0, "beat_times": [0.5, 1.0, 1.5]},
+                "animation_events": {
+                    "beats": [0.5, 1.5],
+                    "sections": [{"start": 0.0, "end": 4.0, "label": "intro"}, {"start": 4.0, "end": 10.0}],
+                    "energy_peaks": [2.25]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let markers = TimelineMarkers::load(path, "Mic", Some(0.25)).unwrap();
+        assert_eq!(markers.analysis_file, "Mic_analysis.json");
+        assert_eq!(markers.beats, [0.75, 1.25, 1.75]);
+        assert_eq!(markers.sections[1], TimelineSection { start: 4.25, end: 10.25, label: "section_2".to_string() });
+        assert_eq!((markers.energy_peaks.as_slice(), markers.bpm), ([2.5].as_slice(), Some(120.0)));
+
+        assert!(TimelineMarkers::load(temp_dir.path().join("missing").as_path(), "Mic", None).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+use tokio::process::Command as AsyncCommand;
+
+/// Settings key for how workspace packages are run
+pub const TOOLCHAIN_KEY: &str = "toolchain";
+
+/// A console script of a workspace package, with the module behind it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntryPoint {
+    pub package: &'static str,
+    pub script: &'static str,
+    pub module: &'static str,
+}
+
+pub const BEATRIX: EntryPoint = EntryPoint { package: "beatrix", script: "beatrix", module: "beatrix.cli.analyze_audio" };
+pub const CINEMON_BLEND_SETUP: EntryPoint =
+    EntryPoint { package: "cinemon", script: "cinemon-blend-setup", module: "cinemon.cli.blend_setup" };
+pub const CINEMON_GENERATE_CONFIG: EntryPoint =
+    EntryPoint { package: "cinemon", script: "cinemon-generate-config", module: "cinemon.cli.generate_config" };
+pub const MEDUSA: EntryPoint = EntryPoint { package: "medusa", script: "medusa", module: "medusa.main" };
+
+/// Entry point run to check that each workspace package is available
+pub const PACKAGE_CHECK_ENTRY_POINTS: [EntryPoint; 3] = [BEATRIX, CINEMON_BLEND_SETUP, MEDUSA];
+
+/// How the workspace's Python packages are run; all run from the workspace root
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind")]
+pub enum Toolchain {
+    /// `uv run --package <package> <script>` with the uv from FERMATA_UV_PATH
+    #[default]
+    Uv,
+    /// `poetry run <script>` in the workspace's poetry environment
+    Poetry,
+    /// `<interpreter> -m <module>` of an environment the packages are installed in
+    DirectPython { interpreter: String },
+}
+
+impl Toolchain {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(TOOLCHAIN_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        self.validate()?;
+        settings.set(TOOLCHAIN_KEY, self)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Toolchain::DirectPython { interpreter } if interpreter.trim().is_empty() => {
+                Err("The Python toolchain needs an interpreter".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Toolchain::Uv => "uv",
+            Toolchain::Poetry => "Poetry",
+            Toolchain::DirectPython { .. } => "Python",
+        }
+    }
+
+    /// Program behind the toolchain, for version checks and error messages
+    pub fn program<'a>(&'a self, uv_path: &'a str) -> &'a str {
+        match self {
+            Toolchain::Uv => uv_path,
+            Toolchain::Poetry => "poetry",
+            Toolchain::DirectPython { interpreter } => interpreter,
+        }
+    }
+
+    /// Command running `entry`; callers add its arguments and the working directory
+    pub fn command(&self, uv_path: &str, entry: &EntryPoint) -> AsyncCommand {
+        let mut cmd = AsyncCommand::new(self.program(uv_path));
+        match self {
+            Toolchain::Uv => cmd.args(["run", "--package", entry.package, entry.script]),
+            Toolchain::Poetry => cmd.args(["run", entry.script]),
+            Toolchain::DirectPython { .. } => cmd.args(["-m", entry.module]),
+        };
+        cmd
+    }
+
+    /// Command that succeeds when the package of `entry` can be run; uv would
+    /// answer a bare `--help` itself, so it runs the entry point's help
+    pub fn check_command(&self, uv_path: &str, entry: &EntryPoint) -> AsyncCommand {
+        let mut cmd = AsyncCommand::new(self.program(uv_path));
+        let import = format!("import {}", entry.package);
+        match self {
+            Toolchain::Uv => cmd.args(["run", "--package", entry.package, entry.script, "--help"]),
+            Toolchain::Poetry => cmd.args(["run", "python", "-c", &import]),
+            Toolchain::DirectPython { .. } => cmd.args(["-c", &import]),
+        };
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command_line(cmd: &AsyncCommand) -> Vec<String> {
+        let cmd = cmd.as_std();
+        std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|a| a.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_commands_per_toolchain() {
+        assert_eq!(
+            command_line(&Toolchain::Uv.command("/opt/uv", &CINEMON_BLEND_SETUP)),
+            ["/opt/uv", "run", "--package", "cinemon", "cinemon-blend-setup"]
+        );
+        assert_eq!(command_line(&Toolchain::Poetry.command("uv", &MEDUSA)), ["poetry", "run", "medusa"]);
+        let python = Toolchain::DirectPython { interpreter: "python3".to_string() };
+        assert_eq!(command_line(&python.command("uv", &BEATRIX)), ["python3", "-m", "beatrix.cli.analyze_audio"]);
+        assert_eq!(command_line(&python.check_command("uv", &CINEMON_BLEND_SETUP)), ["python3", "-c", "import cinemon"]);
+        assert_eq!(
+            command_line(&Toolchain::Uv.check_command("uv", &MEDUSA)),
+            ["uv", "run", "--package", "medusa", "medusa", "--help"]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Toolchain::Poetry.validate().is_ok());
+        assert!(Toolchain::DirectPython { interpreter: " ".to_string() }.validate().is_err());
+    }
+}
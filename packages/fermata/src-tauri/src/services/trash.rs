@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Trash directory inside the recordings root; hidden so the scanner skips it
+pub const TRASH_DIR: &str = ".trash";
+
+/// A deleted recording waiting in the trash
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrashEntry {
+    /// Directory name inside the trash: `<timestamp>_<name>`
+    pub id: String,
+    pub name: String,
+    pub deleted_at: u64, // Unix timestamp in seconds
+    pub path: PathBuf,
+    pub size_bytes: u64,
+}
+
+pub struct Trash;
+
+impl Trash {
+    fn trash_path(recordings_path: &Path) -> PathBuf {
+        recordings_path.join(TRASH_DIR)
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+
+    /// Move a recording directory into the trash
+    pub fn move_to_trash(recordings_path: &Path, recording_name: &str) -> Result<TrashEntry, String> {
+        let source = recordings_path.join(recording_name);
+        let trash_path = Self::trash_path(recordings_path);
+        fs::create_dir_all(&trash_path)
+            .map_err(|e| format!("Failed to create trash directory: {}", e))?;
+
+        let deleted_at = Self::now();
+        let id = format!("{}_{}", deleted_at, recording_name);
+        let target = trash_path.join(&id);
+        if target.exists() {
+            return Err(format!("Trash entry '{}' already exists", id));
+        }
+
+        let size_bytes = directory_size(&source);
+        fs::rename(&source, &target)
+            .map_err(|e| format!("Failed to move recording '{}' to trash: {}", recording_name, e))?;
+
+        log::info!("🗑️ Moved recording '{}' to trash as '{}'", recording_name, id);
+        Ok(TrashEntry {
+            id,
+            name: recording_name.to_string(),
+            deleted_at,
+            path: target,
+            size_bytes,
+        })
+    }
+
+    /// List trash entries, most recently deleted first
+    pub fn list(recordings_path: &Path) -> Vec<TrashEntry> {
+        let mut entries: Vec<TrashEntry> = match fs::read_dir(Self::trash_path(recordings_path)) {
+            Ok(read_dir) => read_dir
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| {
+                    let id = entry.file_name().to_str()?.to_string();
+                    let (timestamp, name) = id.split_once('_')?;
+                    let path = entry.path();
+                    Some(TrashEntry {
+                        deleted_at: timestamp.parse().ok()?,
+                        name: name.to_string(),
+                        size_bytes: directory_size(&path),
+                        id,
+                        path,
+                    })
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        entries.sort_by_key(|entry| Reverse(entry.deleted_at));
+        entries
+    }
+
+    /// Move a trash entry back to the recordings directory under its original name
+    pub fn restore(recordings_path: &Path, trash_id: &str) -> Result<String, String> {
+        let entry = Self::list(recordings_path)
+            .into_iter()
+            .find(|e| e.id == trash_id)
+            .ok_or_else(|| format!("Trash entry '{}' not found", trash_id))?;
+
+        let target = recordings_path.join(&entry.name);
+        if target.exists() {
+            return Err(format!("Recording with name '{}' already exists", entry.name));
+        }
+
+        fs::rename(&entry.path, &target)
+            .map_err(|e| format!("Failed to restore '{}': {}", entry.name, e))?;
+
+        log::info!("♻️ Restored recording '{}' from trash", entry.name);
+        Ok(entry.name)
+    }
+
+    /// Permanently delete trash entries, optionally only those older than the given number of days
+    pub fn empty(recordings_path: &Path, older_than_days: Option<u64>) -> Result<Vec<TrashEntry>, String> {
+        let cutoff = older_than_days.map(|days| Self::now().saturating_sub(days * 24 * 60 * 60));
+        let mut removed = Vec::new();
+
+        for entry in Self::list(recordings_path) {
+            if cutoff.is_some_and(|cutoff| entry.deleted_at > cutoff) {
+                continue;
+            }
+
+            fs::remove_dir_all(&entry.path)
+                .map_err(|e| format!("Failed to remove trash entry '{}': {}", entry.id, e))?;
+            removed.push(entry);
+        }
+
+        log::info!("Emptied {} trash entries", removed.len());
+        Ok(removed)
+    }
+}
+
+fn directory_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup_recording(root: &Path, name: &str) {
+        fs::create_dir_all(root.join(name)).unwrap();
+        fs::write(root.join(name).join(format!("{}.mkv", name)), "video").unwrap();
+    }
+
+    #[test]
+    fn test_move_to_trash_and_restore() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_recording(temp_dir.path(), "rec_1");
+
+        let entry = Trash::move_to_trash(temp_dir.path(), "rec_1").unwrap();
+        assert!(!temp_dir.path().join("rec_1").exists());
+        assert_eq!(entry.size_bytes, 5);
+
+        let listed = Trash::list(temp_dir.path());
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "rec_1");
+
+        let restored = Trash::restore(temp_dir.path(), &entry.id).unwrap();
+        assert_eq!(restored, "rec_1");
+        assert!(temp_dir.path().join("rec_1").join("rec_1.mkv").exists());
+        assert!(Trash::list(temp_dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_restore_refuses_to_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_recording(temp_dir.path(), "rec_1");
+        let entry = Trash::move_to_trash(temp_dir.path(), "rec_1").unwrap();
+        setup_recording(temp_dir.path(), "rec_1");
+
+        let result = Trash::restore(temp_dir.path(), &entry.id);
+        assert!(result.unwrap_err().contains("already exists"));
+    }
+
+    #[test]
+    fn test_empty_trash_respects_age() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash = temp_dir.path().join(TRASH_DIR);
+        fs::create_dir_all(trash.join("100_old_recording")).unwrap();
+        setup_recording(temp_dir.path(), "new_recording");
+        Trash::move_to_trash(temp_dir.path(), "new_recording").unwrap();
+
+        let removed = Trash::empty(temp_dir.path(), Some(30)).unwrap();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].name, "old_recording");
+
+        let removed = Trash::empty(temp_dir.path(), None).unwrap();
+        assert_eq!(removed.len(), 1);
+        assert!(Trash::list(temp_dir.path()).is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+use crate::models::UploadBlock;
+use crate::services::write_atomic;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Stored with the recording so the block survives renames, moves and reinstalls
+pub const UPLOAD_BLOCK_FILE: &str = ".fermata/do_not_upload.json";
+
+pub struct UploadBlockStore;
+
+impl UploadBlockStore {
+    /// The recording's upload block, if any. A file that can't be read is an error
+    /// and an invalid one still blocks: publishing by accident is worse than a spurious block.
+    pub fn load(recording_path: &Path) -> Result<Option<UploadBlock>, String> {
+        let path = recording_path.join(UPLOAD_BLOCK_FILE);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("Failed to read upload block {}: {}", path.display(), e)),
+        };
+        Ok(Some(serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Invalid upload block file {}: {}", path.display(), e);
+            UploadBlock {
+                reason: format!("Unreadable {} - fix or clear the flag", UPLOAD_BLOCK_FILE),
+                blocked_at: 0,
+            }
+        })))
+    }
+
+    pub fn set(recording_path: &Path, reason: &str) -> Result<UploadBlock, String> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err("A reason is required to block uploads".to_string());
+        }
+
+        let block = UploadBlock {
+            reason: reason.to_string(),
+            blocked_at: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+
+        let path = recording_path.join(UPLOAD_BLOCK_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(&block)
+            .map_err(|e| format!("Failed to serialize upload block: {}", e))?;
+        write_atomic(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(block)
+    }
+
+    pub fn clear(recording_path: &Path) -> Result<(), String> {
+        let path = recording_path.join(UPLOAD_BLOCK_FILE);
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Failed to remove {}: {}", path.display(), e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_set_and_clear() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(UploadBlockStore::load(temp_dir.path()).unwrap().is_none());
+        assert!(UploadBlockStore::set(temp_dir.path(), "  ").is_err());
+
+        UploadBlockStore::set(temp_dir.path(), "Copyrighted backing track").unwrap();
+        assert_eq!(UploadBlockStore::load(temp_dir.path()).unwrap().unwrap().reason, "Copyrighted backing track");
+
+        UploadBlockStore::clear(temp_dir.path()).unwrap();
+        assert!(UploadBlockStore::load(temp_dir.path()).unwrap().is_none());
+        UploadBlockStore::clear(temp_dir.path()).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_file_still_blocks() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".fermata")).unwrap();
+        fs::write(temp_dir.path().join(UPLOAD_BLOCK_FILE), "not json").unwrap();
+        assert!(UploadBlockStore::load(temp_dir.path()).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_unreadable_file_is_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(UPLOAD_BLOCK_FILE)).unwrap();
+        assert!(UploadBlockStore::load(temp_dir.path()).is_err());
+    }
+}
@@ -0,0 +1,312 @@
+use crate::services::{process_arg, write_atomic, PostProcessOptions, SettingsStore};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Settings key holding the registered upload profiles
+pub const UPLOAD_PROFILES_KEY: &str = "upload_profiles";
+
+/// Profile used when neither the run nor the recording picks one
+pub const DEFAULT_UPLOAD_PROFILE: &str = "default";
+
+/// Per-recording profile selection, moves with the recording on rename
+pub const RECORDING_UPLOAD_PROFILE_FILE: &str = ".fermata/upload_profile";
+
+/// Transcoded upload variants, one subdirectory per upload profile
+pub const TRANSCODED_DIR: &str = "uploads/transcoded";
+
+/// Medusa config shipped with the workspace, used by the built-in default profile
+const EXAMPLE_MEDUSA_CONFIG: &str = "packages/medusa/examples/config_example.json";
+
+/// A named Medusa configuration (channel, platforms, credentials)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UploadProfile {
+    pub name: String,
+    /// Path to the Medusa config JSON passed as `--config`
+    pub config_path: PathBuf,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Re-encode the rendered video before uploading to this target
+    #[serde(default)]
+    pub transcode: Option<TranscodeProfile>,
+    /// Subtitles or a title card burned in before transcoding and uploading
+    #[serde(default)]
+    pub post_process: Option<PostProcessOptions>,
+}
+
+/// ffmpeg settings for an upload target, e.g. a smaller H.264 for a community portal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranscodeProfile {
+    #[serde(default = "default_video_codec")]
+    pub video_codec: String,
+    /// ffmpeg bitrate, e.g. "4M"; codec default when unset
+    #[serde(default)]
+    pub video_bitrate: Option<String>,
+    #[serde(default = "default_audio_codec")]
+    pub audio_codec: String,
+    #[serde(default)]
+    pub audio_bitrate: Option<String>,
+    /// Downscale to at most this height, keeping aspect ratio; never upscales
+    #[serde(default)]
+    pub max_height: Option<u32>,
+}
+
+fn default_video_codec() -> String {
+    "libx264".to_string()
+}
+
+fn default_audio_codec() -> String {
+    "aac".to_string()
+}
+
+impl TranscodeProfile {
+    /// Arguments for `ffmpeg` transcoding `input` into `output`
+    pub fn ffmpeg_args(&self, input: &Path, output: &Path) -> Vec<OsString> {
+        let mut args = vec![
+            "-y".into(),
+            "-i".into(),
+            process_arg(input),
+            "-c:v".into(),
+            self.video_codec.clone().into(),
+        ];
+        if let Some(bitrate) = &self.video_bitrate {
+            args.extend(["-b:v".into(), bitrate.into()]);
+        }
+        if let Some(max_height) = self.max_height {
+            args.extend(["-vf".into(), format!("scale=-2:'min(ih,{})'", max_height).into()]);
+        }
+        args.extend(["-c:a".into(), self.audio_codec.clone().into()]);
+        if let Some(bitrate) = &self.audio_bitrate {
+            args.extend(["-b:a".into(), bitrate.into()]);
+        }
+        args.extend([
+            "-movflags".into(),
+            "+faststart".into(),
+            process_arg(output),
+        ]);
+        args
+    }
+}
+
+pub struct UploadConfig;
+
+impl UploadConfig {
+    fn default_profile(workspace_root: &Path) -> UploadProfile {
+        UploadProfile {
+            name: DEFAULT_UPLOAD_PROFILE.to_string(),
+            config_path: workspace_root.join(EXAMPLE_MEDUSA_CONFIG),
+            description: Some("Medusa example config".to_string()),
+            transcode: None,
+            post_process: None,
+        }
+    }
+
+    /// Profile names become directory names, so only letters, digits, `-` and `_`
+    pub fn validate_profile_name(name: &str) -> Result<(), String> {
+        let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(format!("Invalid upload profile name '{}': use letters, digits, '-' and '_'", name));
+        }
+        Ok(())
+    }
+
+    /// Registered profiles, plus the built-in default unless it was overridden.
+    /// Profiles with invalid names, e.g. from a hand-edited settings file, are left out.
+    pub fn list(settings: &SettingsStore, workspace_root: &Path) -> Result<Vec<UploadProfile>, String> {
+        let mut profiles: Vec<UploadProfile> = settings.get(UPLOAD_PROFILES_KEY)?;
+        profiles.retain(|p| match Self::validate_profile_name(&p.name) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Ignoring upload profile: {}", e);
+                false
+            }
+        });
+        if !profiles.iter().any(|p| p.name == DEFAULT_UPLOAD_PROFILE) {
+            profiles.insert(0, Self::default_profile(workspace_root));
+        }
+        Ok(profiles)
+    }
+
+    /// Add or replace a profile by name
+    pub fn save_profile(settings: &SettingsStore, profile: UploadProfile) -> Result<(), String> {
+        Self::validate_profile_name(&profile.name)?;
+        if let Some(post_process) = &profile.post_process {
+            post_process.validate()?;
+        }
+
+        let mut profiles: Vec<UploadProfile> = settings.get(UPLOAD_PROFILES_KEY)?;
+        profiles.retain(|p| p.name != profile.name);
+        profiles.push(profile);
+        settings.set(UPLOAD_PROFILES_KEY, &profiles)
+    }
+
+    /// Remove a registered profile
+    pub fn remove_profile(settings: &SettingsStore, name: &str) -> Result<(), String> {
+        let mut profiles: Vec<UploadProfile> = settings.get(UPLOAD_PROFILES_KEY)?;
+        let before = profiles.len();
+        profiles.retain(|p| p.name != name);
+        if profiles.len() == before {
+            return Err(format!("Upload profile '{}' not found", name));
+        }
+        settings.set(UPLOAD_PROFILES_KEY, &profiles)
+    }
+
+    /// Profile selected for a recording, if any
+    pub fn recording_profile(recording_path: &Path) -> Option<String> {
+        fs::read_to_string(recording_path.join(RECORDING_UPLOAD_PROFILE_FILE))
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Select (or clear with `None`) the profile a recording uploads with
+    pub fn set_recording_profile(recording_path: &Path, profile: Option<&str>) -> Result<(), String> {
+        let path = recording_path.join(RECORDING_UPLOAD_PROFILE_FILE);
+        match profile {
+            Some(name) => {
+                Self::validate_profile_name(name)?;
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)
+                        .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+                }
+                write_atomic(&path, name).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+            }
+            None if path.exists() => {
+                fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Where the transcoded variant of a video for an upload profile is kept
+    pub fn transcoded_path(recording_path: &Path, profile_name: &str, video_path: &Path) -> Result<PathBuf, String> {
+        Self::validate_profile_name(profile_name)?;
+        let file_stem = video_path.file_stem().unwrap_or_default().to_string_lossy();
+        Ok(recording_path
+            .join(TRANSCODED_DIR)
+            .join(profile_name)
+            .join(format!("{}.mp4", file_stem)))
+    }
+
+    /// True if the transcoded file exists and is newer than its source
+    pub fn is_transcode_fresh(source: &Path, transcoded: &Path) -> bool {
+        let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+        match (modified(source), modified(transcoded)) {
+            (Some(source), Some(transcoded)) => transcoded >= source,
+            _ => false,
+        }
+    }
+
+    /// Upload profile for a run: the run's profile, else the recording's, else the default
+    pub fn resolve(
+        settings: &SettingsStore,
+        workspace_root: &Path,
+        recording_path: &Path,
+        profile: Option<&str>
+    ) -> Result<UploadProfile, String> {
+        let name = profile
+            .map(str::to_string)
+            .or_else(|| Self::recording_profile(recording_path))
+            .unwrap_or_else(|| DEFAULT_UPLOAD_PROFILE.to_string());
+
+        let profile = Self::list(settings, workspace_root)?
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| format!("Upload profile '{}' not found", name))?;
+
+        if !profile.config_path.exists() {
+            return Err(format!(
+                "Medusa config for upload profile '{}' not found: {}",
+                profile.name,
+                profile.config_path.display()
+            ));
+        }
+
+        log::info!("📤 Using upload profile '{}' ({})", profile.name, profile.config_path.display());
+        Ok(profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn profile(name: &str, config_path: PathBuf) -> UploadProfile {
+        UploadProfile {
+            name: name.to_string(),
+            config_path,
+            description: None,
+            transcode: None,
+            post_process: None,
+        }
+    }
+
+    #[test]
+    fn test_list_includes_builtin_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+        UploadConfig::save_profile(&settings, profile("music", temp_dir.path().join("music.json"))).unwrap();
+
+        let names: Vec<String> = UploadConfig::list(&settings, temp_dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["default", "music"]);
+    }
+
+    #[test]
+    fn test_resolve_prefers_run_then_recording_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+        let recording = temp_dir.path().join("rec");
+        fs::create_dir_all(&recording).unwrap();
+        for name in ["music", "vlog"] {
+            let config = temp_dir.path().join(format!("{}.json", name));
+            fs::write(&config, "{}").unwrap();
+            UploadConfig::save_profile(&settings, profile(name, config)).unwrap();
+        }
+
+        UploadConfig::set_recording_profile(&recording, Some("vlog")).unwrap();
+        let resolved = UploadConfig::resolve(&settings, temp_dir.path(), &recording, None).unwrap();
+        assert_eq!(resolved.name, "vlog");
+
+        let resolved = UploadConfig::resolve(&settings, temp_dir.path(), &recording, Some("music")).unwrap();
+        assert!(resolved.config_path.ends_with("music.json"));
+
+        UploadConfig::set_recording_profile(&recording, None).unwrap();
+        let result = UploadConfig::resolve(&settings, temp_dir.path(), &recording, None);
+        assert!(result.unwrap_err().contains("not found"));
+    }
+
+    #[test]
+    fn test_profile_names_are_slugs() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = SettingsStore::new(temp_dir.path().join("settings.json"));
+        for name in ["", "../escape", "a/b", "with space"] {
+            assert!(UploadConfig::save_profile(&settings, profile(name, temp_dir.path().join("x.json"))).is_err());
+            assert!(UploadConfig::transcoded_path(temp_dir.path(), name, Path::new("video.mp4")).is_err());
+        }
+
+        // Ones already in the settings file are ignored
+        settings.set(UPLOAD_PROFILES_KEY, &vec![profile("..", temp_dir.path().join("x.json"))]).unwrap();
+        let names: Vec<String> = UploadConfig::list(&settings, temp_dir.path()).unwrap().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["default"]);
+
+        let transcoded = UploadConfig::transcoded_path(temp_dir.path(), "music_hd", Path::new("video.mkv")).unwrap();
+        assert!(transcoded.ends_with("music_hd/video.mp4"));
+    }
+
+    #[test]
+    fn test_transcode_args_cap_resolution() {
+        let profile: TranscodeProfile = serde_json::from_str(r#"{"video_bitrate": "2M", "max_height": 720}"#).unwrap();
+        let args = profile.ffmpeg_args(Path::new("in.mp4"), Path::new("out.mp4"));
+
+        assert_eq!(args[4], "libx264");
+        assert!(args.windows(2).any(|w| w == ["-b:v", "2M"]));
+        assert!(args.iter().any(|a| a == "scale=-2:'min(ih,720)'"));
+        assert_eq!(args.last().unwrap(), "out.mp4");
+    }
+}
@@ -0,0 +1,70 @@
+use crate::models::UploadMetadata;
+use crate::services::write_atomic;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Upload metadata lives next to the recording so it moves with it on rename
+pub const UPLOAD_META_FILE: &str = ".fermata/upload_meta.json";
+
+pub struct UploadMetadataStore;
+
+impl UploadMetadataStore {
+    fn file_path(recording_path: &Path) -> PathBuf {
+        recording_path.join(UPLOAD_META_FILE)
+    }
+
+    /// Load saved upload metadata, if the user has edited it
+    pub fn load(recording_path: &Path) -> Result<Option<UploadMetadata>, String> {
+        let path = Self::file_path(recording_path);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read upload metadata {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Invalid upload metadata file {}: {}", path.display(), e))
+    }
+
+    /// Saved metadata, or defaults derived from the recording name
+    pub fn load_or_default(recording_path: &Path, recording_name: &str) -> Result<UploadMetadata, String> {
+        Ok(Self::load(recording_path)?.unwrap_or_else(|| UploadMetadata::for_recording(recording_name)))
+    }
+
+    /// Persist upload metadata for a recording
+    pub fn save(recording_path: &Path, metadata: &UploadMetadata) -> Result<(), String> {
+        let path = Self::file_path(recording_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+
+        let content = serde_json::to_string_pretty(metadata)
+            .map_err(|e| format!("Failed to serialize upload metadata: {}", e))?;
+        write_atomic(&path, content)
+            .map_err(|e| format!("Failed to write upload metadata {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_defaults_until_saved() {
+        let temp_dir = TempDir::new().unwrap();
+        let defaults = UploadMetadataStore::load_or_default(temp_dir.path(), "rec_1").unwrap();
+        assert_eq!(defaults.title.as_deref(), Some("rec_1"));
+
+        let metadata = UploadMetadata {
+            title: Some("Final title".to_string()),
+            tags: vec!["live".to_string()],
+            ..UploadMetadata::default()
+        };
+        UploadMetadataStore::save(temp_dir.path(), &metadata).unwrap();
+
+        assert_eq!(UploadMetadataStore::load_or_default(temp_dir.path(), "rec_1").unwrap(), metadata);
+    }
+}
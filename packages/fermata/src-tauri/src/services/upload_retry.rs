@@ -0,0 +1,121 @@
+use crate::services::SettingsStore;
+use serde::{Deserialize, Serialize};
+
+/// Settings key for the automatic retries of failed uploads
+pub const UPLOAD_RETRY_KEY: &str = "upload_retry";
+
+/// Medusa errors that mean retrying can't help, checked before the transient ones
+const PERMANENT_PATTERNS: [&str; 10] = [
+    "401",
+    "403",
+    "unauthorized",
+    "forbidden",
+    "invalid_grant",
+    "invalid credentials",
+    "authentication",
+    "token has been expired or revoked",
+    "quotaexceeded",
+    "quota exceeded",
+];
+
+/// Network trouble and server errors that usually pass
+const TRANSIENT_PATTERNS: [&str; 14] = [
+    "timed out",
+    "timeout",
+    "connection reset",
+    "connection refused",
+    "connection aborted",
+    "broken pipe",
+    "network is unreachable",
+    "temporary failure in name resolution",
+    "name or service not known",
+    "temporarily unavailable",
+    "500 internal server error",
+    "502",
+    "503",
+    "504",
+];
+
+/// How queued jobs retry an upload that failed for a transient reason, waiting
+/// `initial_backoff_secs` and then twice as long after every further failure
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UploadRetryPolicy {
+    pub enabled: bool,
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    pub initial_backoff_secs: u64,
+    /// Upper bound of a single wait
+    pub max_backoff_secs: u64,
+}
+
+impl Default for UploadRetryPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_retries: 3,
+            initial_backoff_secs: 60,
+            max_backoff_secs: 60 * 60,
+        }
+    }
+}
+
+impl UploadRetryPolicy {
+    pub fn load(settings: &SettingsStore) -> Result<Self, String> {
+        settings.get(UPLOAD_RETRY_KEY)
+    }
+
+    pub fn save(&self, settings: &SettingsStore) -> Result<(), String> {
+        self.validate()?;
+        settings.set(UPLOAD_RETRY_KEY, self)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.initial_backoff_secs == 0 {
+            return Err("The first retry must wait at least a second".to_string());
+        }
+        if self.max_backoff_secs < self.initial_backoff_secs {
+            return Err("The longest wait must not be shorter than the first one".to_string());
+        }
+        Ok(())
+    }
+
+    /// Seconds to wait before retrying after `failed_attempts` failed uploads of
+    /// which the last one said `error`; None when the job should give up
+    pub fn retry_delay(&self, failed_attempts: u32, error: &str) -> Option<u64> {
+        if !self.enabled || failed_attempts == 0 || failed_attempts > self.max_retries || !is_transient_upload_error(error) {
+            return None;
+        }
+        let factor = 2u64.saturating_pow(failed_attempts - 1);
+        Some(self.initial_backoff_secs.saturating_mul(factor).min(self.max_backoff_secs))
+    }
+}
+
+/// Whether a failed upload is worth retrying, judged from medusa's output.
+/// Unknown errors count as permanent so a broken setup is not retried for hours.
+pub fn is_transient_upload_error(error: &str) -> bool {
+    let error = error.to_lowercase();
+    !PERMANENT_PATTERNS.iter().any(|p| error.contains(p)) && TRANSIENT_PATTERNS.iter().any(|p| error.contains(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transient_and_permanent_errors() {
+        assert!(is_transient_upload_error("requests.exceptions.ConnectionError: Connection reset by peer"));
+        assert!(is_transient_upload_error("HttpError 503 when requesting https://youtube.googleapis.com"));
+        assert!(!is_transient_upload_error("HttpError 401: Invalid Credentials"));
+        assert!(!is_transient_upload_error("Request timed out, then 403 Forbidden"));
+        assert!(!is_transient_upload_error("Video file is missing"));
+    }
+
+    #[test]
+    fn test_exponential_backoff() {
+        let policy = UploadRetryPolicy { max_backoff_secs: 200, ..UploadRetryPolicy::default() };
+        let delays: Vec<_> = (1..=4).map(|attempt| policy.retry_delay(attempt, "read timed out")).collect();
+        assert_eq!(delays, [Some(60), Some(120), Some(200), None]);
+        assert_eq!(policy.retry_delay(1, "401 Unauthorized"), None);
+        assert_eq!(UploadRetryPolicy { enabled: false, ..policy }.retry_delay(1, "read timed out"), None);
+    }
+}
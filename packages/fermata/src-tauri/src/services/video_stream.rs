@@ -0,0 +1,178 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// URI scheme the webview loads recording videos from
+pub const VIDEO_PROTOCOL: &str = "fermata-video";
+
+/// Largest slice served per request; the <video> element asks for the rest
+const MAX_CHUNK_BYTES: u64 = 4 * 1024 * 1024;
+
+/// One slice of a video file, ready to be sent as a 206 response
+#[derive(Debug, PartialEq)]
+pub struct VideoChunk {
+    pub data: Vec<u8>,
+    pub start: u64,
+    /// Inclusive, as in the Content-Range header
+    pub end: u64,
+    pub total: u64,
+}
+
+impl VideoChunk {
+    pub fn content_range(&self) -> String {
+        format!("bytes {}-{}/{}", self.start, self.end, self.total)
+    }
+}
+
+/// URL under which the webview can stream a recording's playable video.
+/// WebView2 and Android only allow custom schemes as `http://<scheme>.localhost`.
+pub fn video_url(recording_name: &str) -> String {
+    let encoded = percent_encode(recording_name);
+    if cfg!(any(windows, target_os = "android")) {
+        format!("http://{}.localhost/{}", VIDEO_PROTOCOL, encoded)
+    } else {
+        format!("{}://localhost/{}", VIDEO_PROTOCOL, encoded)
+    }
+}
+
+/// Recording name from the path of a protocol request, e.g. `/Koncert%202024`
+pub fn recording_name_from_uri_path(path: &str) -> Option<String> {
+    let name = percent_decode(path.trim_start_matches('/'))?;
+    if name.is_empty() || name.starts_with('.') || name.contains('/') || name.contains('\\') {
+        return None;
+    }
+    Some(name)
+}
+
+/// Parse a `Range: bytes=start-end` header against a file of `total` bytes.
+/// A missing header reads from the start; returns None when unsatisfiable.
+pub fn parse_range(header: Option<&str>, total: u64) -> Option<(u64, u64)> {
+    if total == 0 {
+        return None;
+    }
+    let (start, end) = match header {
+        None => (0, total - 1),
+        Some(header) => {
+            // Only the first range of a multi-range request is served
+            let spec = header.trim().strip_prefix("bytes=")?.split(',').next()?.trim();
+            let (start, end) = spec.split_once('-')?;
+            match (start.trim(), end.trim()) {
+                // Suffix range: the last N bytes
+                ("", suffix) => {
+                    let suffix: u64 = suffix.parse().ok()?;
+                    if suffix == 0 {
+                        return None;
+                    }
+                    (total.saturating_sub(suffix), total - 1)
+                }
+                (start, "") => (start.parse().ok()?, total - 1),
+                (start, end) => (start.parse().ok()?, end.parse::<u64>().ok()?.min(total - 1)),
+            }
+        }
+    };
+
+    if start > end {
+        return None;
+    }
+    Some((start, end.min(start + MAX_CHUNK_BYTES - 1)))
+}
+
+/// Read the requested byte range of a file
+pub fn read_chunk(file: &Path, range_header: Option<&str>) -> Result<Option<VideoChunk>, String> {
+    let mut handle = File::open(file).map_err(|e| format!("Failed to open {}: {}", file.display(), e))?;
+    let total = handle
+        .metadata()
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?
+        .len();
+    let Some((start, end)) = parse_range(range_header, total) else {
+        return Ok(None);
+    };
+
+    let mut data = vec![0; (end - start + 1) as usize];
+    handle
+        .seek(SeekFrom::Start(start))
+        .and_then(|_| handle.read_exact(&mut data))
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    Ok(Some(VideoChunk { data, start, end, total }))
+}
+
+pub fn content_type(file: &Path) -> &'static str {
+    match file.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()).as_deref() {
+        Some("mp4") => "video/mp4",
+        Some("mkv") => "video/x-matroska",
+        Some("mov") => "video/quicktime",
+        Some("avi") => "video/x-msvideo",
+        _ => "application/octet-stream",
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range(None, 100), Some((0, 99)));
+        assert_eq!(parse_range(Some("bytes=10-19"), 100), Some((10, 19)));
+        assert_eq!(parse_range(Some("bytes=90-"), 100), Some((90, 99)));
+        assert_eq!(parse_range(Some("bytes=-10"), 100), Some((90, 99)));
+        assert_eq!(parse_range(Some("bytes=50-500"), 100), Some((50, 99)));
+        assert_eq!(parse_range(Some("bytes=100-"), 100), None);
+        assert_eq!(parse_range(Some("items=0-1"), 100), None);
+        assert_eq!(parse_range(None, 0), None);
+
+        let big = 10 * MAX_CHUNK_BYTES;
+        assert_eq!(parse_range(Some("bytes=0-"), big), Some((0, MAX_CHUNK_BYTES - 1)));
+    }
+
+    #[test]
+    fn test_read_chunk() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("gig.mp4");
+        std::fs::write(&file, b"0123456789").unwrap();
+
+        let chunk = read_chunk(&file, Some("bytes=2-5")).unwrap().unwrap();
+        assert_eq!(chunk.data, b"2345");
+        assert_eq!(chunk.content_range(), "bytes 2-5/10");
+        assert!(read_chunk(&file, Some("bytes=20-")).unwrap().is_none());
+        assert_eq!(content_type(&file), "video/mp4");
+    }
+
+    #[test]
+    fn test_url_round_trip() {
+        let url = video_url("Koncert 2024");
+        assert!(url.ends_with("/Koncert%202024"));
+        let path = format!("/{}", percent_encode("Koncert ąę"));
+        assert_eq!(recording_name_from_uri_path(&path).as_deref(), Some("Koncert ąę"));
+        assert_eq!(recording_name_from_uri_path("/..%2Fetc"), None);
+        assert_eq!(recording_name_from_uri_path("/"), None);
+    }
+}
@@ -16,7 +16,9 @@ const STATUS_OPTIONS: { value: RecordingStatus | 'all'; label: string }[] = [
   { value: 'Recorded', label: '📹 Recorded' },
   { value: 'Extracted', label: '📁 Extracted' },
   { value: 'Analyzed', label: '📊 Analyzed' },
+  { value: 'ConfigGenerated', label: '📝 Config' },
   { value: 'SetupRendered', label: '🎬 Setup' },
+  { value: 'FramesRendered', label: '🎞️ Frames' },
   { value: 'Rendered', label: '✅ Rendered' },
   { value: 'Uploaded', label: '🚀 Uploaded' },
   { value: { Failed: '' }, label: '❌ Failed' }
@@ -49,6 +51,10 @@ export function ControlsBar({
     if (typeof status === 'object' && 'Failed' in status) {
       return 'failed';
     }
+    // Running custom steps are not offered as a filter
+    if (typeof status === 'object') {
+      return 'all';
+    }
     return status;
   };
 
@@ -93,7 +99,9 @@ export function ControlsBar({
           <option value="Recorded">📹 Recorded</option>
           <option value="Extracted">📁 Extracted</option>
           <option value="Analyzed">📊 Analyzed</option>
+          <option value="ConfigGenerated">📝 Config</option>
           <option value="SetupRendered">🎬 Setup</option>
+          <option value="FramesRendered">🎞️ Frames</option>
           <option value="Rendered">✅ Rendered</option>
           <option value="Uploaded">🚀 Uploaded</option>
           <option value="failed">❌ Failed</option>
@@ -6,6 +6,7 @@ interface DeletionConfirmDialogProps {
   onConfirm: () => void;
   onCancel: () => void;
   isDeleting: boolean;
+  canConfirm?: boolean; // false until the delete preview has loaded
 }
 
 export function DeletionConfirmDialog({
@@ -13,7 +14,8 @@ export function DeletionConfirmDialog({
   recording,
   onConfirm,
   onCancel,
-  isDeleting
+  isDeleting,
+  canConfirm = true
 }: DeletionConfirmDialogProps) {
   if (!isOpen || !recording) {
     return null;
@@ -107,7 +109,7 @@ export function DeletionConfirmDialog({
 
           <button
             onClick={onConfirm}
-            disabled={isDeleting}
+            disabled={isDeleting || !canConfirm}
             style={{
               padding: '10px 20px',
               fontSize: '0.875rem',
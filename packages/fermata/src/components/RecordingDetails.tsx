@@ -1,6 +1,6 @@
 import { useState, useEffect } from 'react';
-import { ArrowLeft, Play, RotateCcw, Eye, Edit, Film } from 'lucide-react';
-import { Recording, RecordingStatus } from '../types';
+import { ArrowLeft, Play, RotateCcw, Eye, Edit, Film, FolderOpen } from 'lucide-react';
+import { PlayableVideo, Recording, RecordingStatus } from '../types';
 import { useRecordingOperations, useRenameRecording } from '../hooks/useRecordings';
 import { invoke } from '@tauri-apps/api/core';
 import { RenameRecordingDialog } from './RenameRecordingDialog';
@@ -20,7 +20,7 @@ export function RecordingDetails({ recordingName, onBack, onRecordingRenamed }:
   const [selectedPreset, setSelectedPreset] = useState("beat-switch");
   const [showPresetConfig, setShowPresetConfig] = useState(false);
   const [showVideoPlayer, setShowVideoPlayer] = useState(false);
-  const [videoPath, setVideoPath] = useState<string | null>(null);
+  const [video, setVideo] = useState<PlayableVideo | null>(null);
   const { runNextStep, runSpecificStep, runSetupRenderWithPreset, running, output, error: operationError } = useRecordingOperations();
   const { renameState, showRenameDialog, hideRenameDialog, renameRecording } = useRenameRecording();
 
@@ -74,6 +74,9 @@ export function RecordingDetails({ recordingName, onBack, onRecordingRenamed }:
     // Existing logic for other actions
     if (action === 'Next Step') {
       await runNextStep(recordingName);
+    } else if (action === 'Blend Setup') {
+      // Rebuilds the .blend from the existing (possibly hand-edited) config
+      await runSpecificStep(recordingName, 'blend_setup');
     } else {
       await runSpecificStep(recordingName, action.toLowerCase());
     }
@@ -109,19 +112,27 @@ export function RecordingDetails({ recordingName, onBack, onRecordingRenamed }:
 
   const handlePlayVideo = async () => {
     try {
-      const path = await invoke('get_playable_video_path', { recordingName }) as string;
-      console.log('🎬 Video path from backend:', path);
-      console.log('🎬 Setting videoPath to:', path);
-      setVideoPath(path);
+      const playable = await invoke('get_playable_video_path', { recordingName }) as PlayableVideo;
+      console.log('🎬 Video from backend:', playable);
+      setVideo(playable);
       console.log('🎬 Setting showVideoPlayer to true');
       setShowVideoPlayer(true);
-      console.log('🎬 State after setting - showVideoPlayer should be true, videoPath should be:', path);
+      console.log('🎬 State after setting - showVideoPlayer should be true, video should be:', playable);
     } catch (error) {
       console.error('🚨 Video error:', error);
       alert(error instanceof Error ? error.message : 'Failed to find video file');
     }
   };
 
+  const handleOpenFolder = async () => {
+    try {
+      await invoke('open_recording_folder', { recordingName });
+    } catch (error) {
+      console.error('🚨 Open folder error:', error);
+      alert(error instanceof Error ? error.message : String(error));
+    }
+  };
+
   const getStatusIcon = (status: RecordingStatus) => {
     if (typeof status === 'object' && 'Failed' in status) {
       return '❌';
@@ -131,7 +142,9 @@ export function RecordingDetails({ recordingName, onBack, onRecordingRenamed }:
       case 'Recorded': return '📹';
       case 'Extracted': return '📁';
       case 'Analyzed': return '📊';
+      case 'ConfigGenerated': return '📝';
       case 'SetupRendered': return '🎬';
+      case 'FramesRendered': return '🎞️';
       case 'Rendered': return '🎥';
       case 'Uploaded': return '🚀';
       default: return '❓';
@@ -147,7 +160,9 @@ export function RecordingDetails({ recordingName, onBack, onRecordingRenamed }:
       case 'Recorded': return 'recorded';
       case 'Extracted': return 'extracted';
       case 'Analyzed': return 'analyzed';
+      case 'ConfigGenerated': return 'analyzed';
       case 'SetupRendered': return 'setup';
+      case 'FramesRendered': return 'setup';
       case 'Rendered': return 'rendered';
       case 'Uploaded': return 'uploaded';
       default: return 'failed';
@@ -184,7 +199,9 @@ export function RecordingDetails({ recordingName, onBack, onRecordingRenamed }:
         case 'Recorded': currentIndex = 0; break;
         case 'Extracted': currentIndex = 1; break;
         case 'Analyzed': currentIndex = 2; break;
+        case 'ConfigGenerated': currentIndex = 2; break;
         case 'SetupRendered': currentIndex = 3; break;
+        case 'FramesRendered': currentIndex = 3; break;
         case 'Rendered': currentIndex = 4; break;
         case 'Uploaded': currentIndex = 5; break;
       }
@@ -234,7 +251,13 @@ export function RecordingDetails({ recordingName, onBack, onRecordingRenamed }:
       case 'Analyzed':
         actions.push('Analyze', 'Setup');
         break;
+      case 'ConfigGenerated':
+        actions.push('Analyze', 'Setup', 'Blend Setup');
+        break;
       case 'SetupRendered':
+        actions.push('Analyze', 'Setup', 'Blend Setup', 'Render');
+        break;
+      case 'FramesRendered':
         actions.push('Analyze', 'Setup', 'Render');
         break;
       case 'Rendered':
@@ -384,6 +407,11 @@ export function RecordingDetails({ recordingName, onBack, onRecordingRenamed }:
               Play Video
             </button>
 
+            <button className="btn btn-secondary" onClick={handleOpenFolder}>
+              <FolderOpen size={16} />
+              Open Folder
+            </button>
+
             {availableActions.map((action) => (
               <button
                 key={action}
@@ -566,15 +594,15 @@ export function RecordingDetails({ recordingName, onBack, onRecordingRenamed }:
 
       {/* Video Player Modal */}
       {(() => {
-        console.log('🎬 VideoPlayer render check - showVideoPlayer:', showVideoPlayer, 'videoPath:', videoPath);
-        return showVideoPlayer && videoPath && (
+        console.log('🎬 VideoPlayer render check - showVideoPlayer:', showVideoPlayer, 'video:', video);
+        return showVideoPlayer && video && (
           <VideoPlayer
-            videoPath={videoPath}
+            video={video}
             recordingName={recording.name}
             onClose={() => {
               console.log('🎬 Closing VideoPlayer');
               setShowVideoPlayer(false);
-              setVideoPath(null);
+              setVideo(null);
             }}
           />
         );
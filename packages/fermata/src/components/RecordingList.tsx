@@ -1,4 +1,4 @@
-import { Recording, RecordingStatus } from '../types';
+import { PlayableVideo, Recording, RecordingStatus } from '../types';
 import { useRecordings, useRecordingOperations } from '../hooks/useRecordings';
 import { useSortingAndFiltering } from '../hooks/useSortingAndFiltering';
 import { DeletionConfirmDialog } from './DeletionConfirmDialog';
@@ -34,6 +34,9 @@ function getStatusDisplay(status: RecordingStatus): { text: string; emoji: strin
   if (typeof status === 'object' && 'Failed' in status) {
     return { text: 'Failed', emoji: '❌', className: 'failed' };
   }
+  if (typeof status === 'object' && 'Corrupted' in status) {
+    return { text: 'Corrupted', emoji: '☣️', className: 'failed' };
+  }
 
   switch (status) {
     case 'Recorded':
@@ -42,8 +45,12 @@ function getStatusDisplay(status: RecordingStatus): { text: string; emoji: strin
       return { text: 'Extracted', emoji: '📁', className: 'extracted' };
     case 'Analyzed':
       return { text: 'Analyzed', emoji: '📊', className: 'analyzed' };
+    case 'ConfigGenerated':
+      return { text: 'Config', emoji: '📝', className: 'analyzed' };
     case 'SetupRendered':
       return { text: 'Setup', emoji: '🎬', className: 'setup' };
+    case 'FramesRendered':
+      return { text: 'Frames', emoji: '🎞️', className: 'setup' };
     case 'Rendered':
       return { text: 'Rendered', emoji: '✅', className: 'rendered' };
     case 'Uploaded':
@@ -65,7 +72,10 @@ function getNextAction(status: RecordingStatus): string | null {
       return 'Analyze';
     case 'Analyzed':
       return 'Setup';
+    case 'ConfigGenerated':
+      return 'Blend Setup';
     case 'SetupRendered':
+    case 'FramesRendered':
       return 'Render';
     case 'Rendered':
       return 'Upload';
@@ -114,6 +124,19 @@ function RecordingCard({ recording, onAction, isOperationRunning }: {
         </div>
       )}
 
+      {typeof recording.status === 'object' && 'Corrupted' in recording.status && (
+        <div style={{
+          background: '#fee2e2',
+          color: '#dc2626',
+          padding: '8px 12px',
+          borderRadius: '6px',
+          fontSize: '0.875rem',
+          marginBottom: '16px'
+        }}>
+          <strong>Corrupted:</strong> {recording.status.Corrupted}
+        </div>
+      )}
+
       <div className="recording-actions">
         <button
           className="btn btn-secondary"
@@ -168,7 +191,7 @@ export function RecordingList({ onSelectRecording }: RecordingListProps) {
   } = useSortingAndFiltering(recordings);
 
   const [showVideoPlayer, setShowVideoPlayer] = useState(false);
-  const [videoPath, setVideoPath] = useState<string | null>(null);
+  const [video, setVideo] = useState<PlayableVideo | null>(null);
   const [currentRecordingName, setCurrentRecordingName] = useState<string | null>(null);
 
   const handleAction = async (recordingName: string, action: string) => {
@@ -181,9 +204,9 @@ export function RecordingList({ onSelectRecording }: RecordingListProps) {
 
     if (action === 'Play Video') {
       try {
-        const path = await invoke('get_playable_video_path', { recordingName }) as string;
-        console.log('🎬 Video path from backend:', path);
-        setVideoPath(path);
+        const playable = await invoke('get_playable_video_path', { recordingName }) as PlayableVideo;
+        console.log('🎬 Video from backend:', playable);
+        setVideo(playable);
         setCurrentRecordingName(recordingName);
         setShowVideoPlayer(true);
       } catch (error) {
@@ -209,6 +232,7 @@ export function RecordingList({ onSelectRecording }: RecordingListProps) {
         'Extract': 'extract',
         'Analyze': 'analyze',
         'Setup': 'setup_render',
+        'Blend Setup': 'blend_setup',
         'Render': 'render',
         'Upload': 'upload'
       };
@@ -224,8 +248,9 @@ export function RecordingList({ onSelectRecording }: RecordingListProps) {
   };
 
   const handleDeleteConfirm = () => {
-    if (deletionState.recording) {
-      deleteRecording(deletionState.recording.name);
+    // Only once the preview the dialog asked for has arrived
+    if (deletionState.recording && deletionState.preview) {
+      deleteRecording(deletionState.recording.name, deletionState.preview.token);
     }
   };
 
@@ -325,16 +350,17 @@ export function RecordingList({ onSelectRecording }: RecordingListProps) {
         onConfirm={handleDeleteConfirm}
         onCancel={hideDeletionDialog}
         isDeleting={deletionState.isDeleting}
+        canConfirm={Boolean(deletionState.preview)}
       />
 
       {/* Video Player Modal */}
-      {showVideoPlayer && videoPath && currentRecordingName && (
+      {showVideoPlayer && video && currentRecordingName && (
         <VideoPlayer
-          videoPath={videoPath}
+          video={video}
           recordingName={currentRecordingName}
           onClose={() => {
             setShowVideoPlayer(false);
-            setVideoPath(null);
+            setVideo(null);
             setCurrentRecordingName(null);
           }}
         />
@@ -1,33 +1,35 @@
 import { invoke } from '@tauri-apps/api/core';
-import { useEffect } from 'react';
+import { PlayableVideo } from '../types';
 
 interface VideoPlayerProps {
-  videoPath: string;
+  video: PlayableVideo;
   recordingName: string;
   onClose: () => void;
 }
 
-export function VideoPlayer({ videoPath, recordingName, onClose }: VideoPlayerProps) {
-  useEffect(() => {
-    // Bezpośrednio otwórz w zewnętrznym odtwarzaczu
-    const openVideo = async () => {
-      try {
-        console.log('🎬 Opening video in external player:', videoPath);
-        await invoke('open_video_external', { filePath: videoPath });
-        console.log('✅ Video opened successfully');
-        // Zamknij modal od razu po otwarciu
-        onClose();
-      } catch (error) {
-        console.error('❌ Failed to open video:', error);
-        alert(`Failed to open video: ${error}`);
-        onClose();
-      }
-    };
+export function VideoPlayer({ video, recordingName, onClose }: VideoPlayerProps) {
+  const openExternal = async () => {
+    try {
+      console.log('🎬 Opening video in external player:', video.path);
+      await invoke('open_video_external', { filePath: video.path });
+      console.log('✅ Video opened successfully');
+      onClose();
+    } catch (error) {
+      console.error('❌ Failed to open video:', error);
+      alert(`Failed to open video: ${error}`);
+    }
+  };
 
-    openVideo();
-  }, [videoPath, onClose]);
+  const buttonStyle = {
+    background: 'rgba(255, 255, 255, 0.1)',
+    border: '1px solid rgba(255, 255, 255, 0.3)',
+    borderRadius: '6px',
+    color: 'white',
+    padding: '8px 16px',
+    cursor: 'pointer'
+  };
 
-  // Prosty modal z informacją o ładowaniu
+  // Odtwarzanie w aplikacji; wideo jest strumieniowane przez backend
   return (
     <div
       style={{
@@ -44,26 +46,25 @@ export function VideoPlayer({ videoPath, recordingName, onClose }: VideoPlayerPr
       }}
       onClick={onClose}
     >
-      <div style={{ textAlign: 'center' }}>
-        <div style={{ fontSize: '48px', marginBottom: '20px' }}>🎬</div>
-        <div>Opening video in external player...</div>
-        <div style={{ fontSize: '14px', marginTop: '10px', opacity: 0.7 }}>
+      <div style={{ textAlign: 'center', maxWidth: '90vw' }} onClick={(e) => e.stopPropagation()}>
+        <div style={{ fontSize: '14px', marginBottom: '10px', opacity: 0.7 }}>
           {recordingName}
         </div>
-        <button
-          onClick={onClose}
-          style={{
-            marginTop: '30px',
-            background: 'rgba(255, 255, 255, 0.1)',
-            border: '1px solid rgba(255, 255, 255, 0.3)',
-            borderRadius: '6px',
-            color: 'white',
-            padding: '8px 16px',
-            cursor: 'pointer'
-          }}
-        >
-          Cancel
-        </button>
+        <video
+          src={video.url}
+          controls
+          autoPlay
+          style={{ maxWidth: '90vw', maxHeight: '75vh', background: 'black' }}
+          onError={() => console.error('🚨 In-app playback failed for', video.url)}
+        />
+        <div style={{ display: 'flex', gap: '10px', justifyContent: 'center', marginTop: '20px' }}>
+          <button onClick={openExternal} style={buttonStyle}>
+            Open in external player
+          </button>
+          <button onClick={onClose} style={buttonStyle}>
+            Close
+          </button>
+        </div>
       </div>
     </div>
   );
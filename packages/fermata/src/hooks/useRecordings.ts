@@ -1,5 +1,5 @@
 import { useState, useCallback, useEffect } from 'react';
-import { Recording, RecordingListState, DeletionConfirmationState, RenameConfirmationState, RenderOptions } from '../types';
+import { Recording, RecordingListState, ScanOptions, DeletePreview, DeletionConfirmationState, RenameConfirmationState, SetupRenderOptions } from '../types';
 import { invoke } from '@tauri-apps/api/core';
 
 // Tauri API wrapper with fallback for development
@@ -75,13 +75,27 @@ export function useRecordings() {
     setState(prev => ({ ...prev, loading: true, error: null }));
 
     try {
-      const recordings = await invokeCommand('get_recordings') as Recording[];
+      // Status-only scan; sizes missing from the cache are fetched one by one below
+      const options: ScanOptions = { include_sizes: false };
+      const recordings = await invokeCommand('get_recordings', { options }) as Recording[];
 
       setState({
         recordings,
         loading: false,
         error: null
       });
+
+      for (const recording of recordings.filter(r => Object.keys(r.file_sizes).length === 0)) {
+        try {
+          const file_sizes = await invokeCommand('get_recording_sizes', { recordingName: recording.name }) as Record<string, number>;
+          setState(prev => ({
+            ...prev,
+            recordings: prev.recordings.map(r => r.name === recording.name ? { ...r, file_sizes } : r)
+          }));
+        } catch (error) {
+          console.warn(`Failed to get sizes of ${recording.name}:`, error);
+        }
+      }
     } catch (error) {
       // Fallback to mock data if Tauri is not available (dev mode)
       console.warn('Tauri not available, using mock data:', error);
@@ -104,10 +118,15 @@ export function useRecordings() {
     isDeleting: false
   });
 
-  const deleteRecording = useCallback(async (recordingName: string) => {
+  // token is the one of the preview the user confirmed; without it nothing is deleted
+  const deleteRecording = useCallback(async (recordingName: string, token: string) => {
+    if (!token) {
+      console.error(`Delete of ${recordingName} refused: no confirmed preview`);
+      return;
+    }
     setDeletionState(prev => ({ ...prev, isDeleting: true }));
     try {
-      await invoke('delete_recording', { recordingName });
+      await invoke('delete_recording', { recordingName, token });
       setDeletionState({ isOpen: false, recording: undefined, isDeleting: false });
       refreshRecordings(); // Odśwież listę
     } catch (error) {
@@ -124,6 +143,9 @@ export function useRecordings() {
     deleteRecording,
     showDeletionDialog: (recording: Recording) => {
       setDeletionState({ isOpen: true, recording, isDeleting: false });
+      invoke<DeletePreview>('preview_delete', { recordingName: recording.name })
+        .then(preview => setDeletionState(prev => (prev.recording === recording ? { ...prev, preview } : prev)))
+        .catch(error => console.error('Delete preview failed:', error));
     },
     hideDeletionDialog: () => {
       setDeletionState({ isOpen: false, recording: undefined, isDeleting: false });
@@ -212,7 +234,7 @@ export function useRecordingOperations() {
     }));
 
     try {
-      const options: RenderOptions = { preset, main_audio: mainAudio };
+      const options: SetupRenderOptions = { preset, main_audio: mainAudio };
       const result = await invokeCommand('run_specific_step_with_options', {
         recordingName,
         step: 'setuprender',
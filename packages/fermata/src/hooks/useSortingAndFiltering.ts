@@ -24,11 +24,17 @@ function getStatusPriority(status: RecordingStatus): number {
     'Recorded': 1,
     'Extracted': 2,
     'Analyzed': 3,
-    'SetupRendered': 4,
-    'Rendered': 5,
-    'Uploaded': 6
+    'ConfigGenerated': 4,
+    'SetupRendered': 5,
+    'FramesRendered': 6,
+    'Rendered': 7,
+    'Uploaded': 8
   };
 
+  // Custom steps mostly run between render and upload
+  if (typeof status === 'object') {
+    return statusPriority['Rendered'];
+  }
   return statusPriority[status] || 0;
 }
 
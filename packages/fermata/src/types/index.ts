@@ -5,21 +5,99 @@ export interface Recording {
   status: RecordingStatus;
   last_updated: number;
   file_sizes: Record<string, number>;
+  upload_block?: UploadBlock | null;
+  // Recordings root the recording was found in
+  root?: string;
+  lifecycle?: LifecycleBadge | null;
+  // Only filled in by get_recording_details
+  media?: RecordingMedia | null;
+  // Only filled in by scans with include_tree
+  tree?: FileTreeNode | null;
+}
+
+// Video details probed with ffprobe
+export interface MediaInfo {
+  duration_secs: number | null;
+  width: number | null;
+  height: number | null;
+  fps: number | null;
+  video_codec: string | null;
+  audio_codec: string | null;
+  audio_tracks: number;
+}
+
+export interface RecordingMedia {
+  main: MediaInfo | null;
+  rendered: MediaInfo | null;
+}
+
+// Returned by get_playable_video_path
+export interface PlayableVideo {
+  // Streaming URL served by the backend, for the <video> element
+  url: string;
+  // File on disk, for the external player
+  path: string;
+}
+
+export type Lifecycle = 'New' | 'InProgress' | 'ReadyToPublish' | 'Published' | 'Archivable';
+
+// Lifecycle badge computed by the backend
+export interface LifecycleBadge {
+  stage: Lifecycle;
+  age_days: number;
+}
+
+// "Do not upload" flag; the Upload step refuses to run while it is set
+export interface UploadBlock {
+  reason: string;
+  blocked_at: number;
 }
 
 export type RecordingStatus =
   | 'Recorded'
   | 'Extracted'
   | 'Analyzed'
+  | 'ConfigGenerated'
   | 'SetupRendered'
+  | 'FramesRendered'
   | 'Rendered'
   | 'Uploaded'
-  | { Failed: string };
+  | 'Analyzing'
+  | 'SettingUpRender'
+  | 'Rendering'
+  | 'Uploading'
+  | 'Transcribing'
+  | { Failed: string }
+  | { Corrupted: string } // unreadable metadata or video; quarantine_recording moves it aside
+  | { RunningStep: string }; // custom step id
+
+// Query types for query_recordings
+export type RecordingSortBy = 'Name' | 'Date' | 'Size' | 'Status';
+
+export interface RecordingQuery {
+  status?: string | null;
+  name_contains?: string | null;
+  date_range?: { from?: number | null; to?: number | null } | null;
+  sort_by?: RecordingSortBy;
+  descending?: boolean | null;
+  page?: number; // zero-based
+  page_size?: number | null;
+}
+
+export interface RecordingPage {
+  items: Recording[];
+  total: number;
+  page: number;
+  page_size: number;
+}
 
 // Configuration types
 export interface AppConfig {
   recordings_path: string;
+  recordings_roots: string[];
   cli_paths: CliPaths;
+  profile?: string | null; // config profile in use, null on the environment alone
+  observer?: boolean; // read-only observer of a shared library (FERMATA_OBSERVER)
 }
 
 export interface CliPaths {
@@ -33,8 +111,36 @@ export interface ProcessResult {
   stdout: string;
   stderr: string;
   exit_code: number | null;
+  timeout?: ProcessTimeout | null;
+  // Unix timestamps in milliseconds
+  started_at: number;
+  finished_at: number;
+  duration_ms: number;
+  phases: ProcessPhase[];
+  outcome?: StepOutcome; // what the tool reported, when its output says
+}
+
+// Parsed tool output attached to a ProcessResult and kept in the step log
+export type StepOutcome =
+  | { kind: 'analysis'; file: string }
+  | { kind: 'animation_config'; file: string; preset: string }
+  | { kind: 'upload'; uploads: { platform: string; upload_id?: string | null; success: boolean; media_url?: string | null }[] };
+
+// Timed part of a composite step, e.g. generate_config and blend_setup of SetupRender
+export interface ProcessPhase {
+  name: string;
+  success: boolean;
+  started_at: number;
+  finished_at: number;
+  duration_ms: number;
 }
 
+// Set when the watchdog killed a hung process
+export type ProcessTimeout =
+  | { kind: 'deadline'; seconds: number }
+  | { kind: 'no_output'; seconds: number }
+  | { kind: 'stalled'; seconds: number };
+
 export type NextStep =
   | 'Extract'
   | 'Analyze'
@@ -52,6 +158,7 @@ export interface DeletionState {
   isOpen: boolean;
   recording: Recording | null;
   isDeleting: boolean;
+  preview?: DeletePreview; // from preview_delete, its token confirms the delete
 }
 
 export interface RenameState {
@@ -152,3 +259,824 @@ export interface ResultsCounterProps {
   totalCount: number;
   hasActiveFilters: boolean;
 }
+
+// Dashboard overview returned by get_pipeline_stats
+export interface PipelineStats {
+  total_recordings: number;
+  status_counts: Record<string, number>;
+  total_bytes: number;
+  failed: number;
+  uploaded_this_week: number;
+  step_durations: StepDuration[];
+}
+
+export interface StepDuration {
+  step: string;
+  runs: number;
+  average_ms: number;
+}
+
+// Recordings that belong together, e.g. several takes of one concert
+export interface Session {
+  id: string;
+  name: string;
+  created_at: number;
+  recordings: string[];
+}
+
+// Blender render progress, also the payload of the `render-progress` event
+export interface BlenderProgress {
+  current_frame: number;
+  total_frames: number | null;
+  eta_seconds: number | null;
+}
+
+export interface RenderProgressEvent extends BlenderProgress {
+  recording_name: string;
+}
+
+// Returned by get_render_progress
+export interface RenderProgress {
+  rendered_frames: number;
+  latest_frame: number | null;
+  last_snapshot: (BlenderProgress & { updated_at: number }) | null;
+}
+
+export interface ResumePlan {
+  total_frames: number;
+  existing_frames: number;
+  missing_ranges: [number, number][];
+}
+
+export type VideoEncoder = 'h264' | 'hevc' | 'av1' | 'prores';
+export type VideoContainer = 'mp4' | 'mkv' | 'mov' | 'webm';
+
+// Overrides of the .blend output; null keeps the file's value
+export interface RenderSettings {
+  width: number | null;
+  height: number | null;
+  fps: number | null;
+  encoder: VideoEncoder | null;
+  container: VideoContainer | null;
+}
+
+// Animation config as text, for the config editor
+export interface AnimationConfigText {
+  preset: string;
+  file: string;
+  yaml: string;
+}
+
+export interface AnimationConfigSaved {
+  file: string;
+  backup: string | null;
+  blend_outdated: boolean;
+}
+
+// Options of the setuprender step; config_path skips config generation
+export interface SetupRenderOptions {
+  preset?: string;
+  main_audio?: string;
+  config_path?: string;
+}
+
+// Animation preset from list_animation_presets / refresh_animation_presets
+export interface PresetInfo {
+  name: string;
+  description: string | null;
+  layout: string | null;
+  default_seed: number | null;
+}
+
+// Beatrix overrides saved per recording; null keeps beatrix's default
+export interface AnalyzeOptions {
+  tempo_min: number | null;
+  tempo_max: number | null;
+  onset_sensitivity: number | null;
+  profile: string | null;
+}
+
+// Obsession source selection saved per recording; empty include_sources extracts all
+export interface ExtractOptions {
+  include_sources: string[];
+  audio_only: boolean;
+}
+
+// From list_extract_sources, read from metadata.json
+export interface ExtractSource {
+  name: string;
+  has_audio: boolean;
+  has_video: boolean;
+}
+
+// .fermata/state.json, preferred over directory heuristics when present
+export interface StateManifest {
+  status: RecordingStatus | null; // null when it only holds checksums
+  step: NextStep | null;
+  updated_at: number;
+  checksums?: ArtifactChecksum[];
+  failed_step?: NextStep | null; // the step Retry runs again
+  attached_render?: AttachedRender | null; // set when the render was made elsewhere
+}
+
+// From checksum_recording; the raw video and the final render
+export interface ArtifactChecksum {
+  path: string;
+  algorithm: string; // 'sha256'
+  hash: string;
+  size_bytes: number;
+}
+
+// From verify_recording
+export interface RecordingVerification {
+  recording: string;
+  checks: {
+    path: string;
+    state: 'ok' | 'corrupted' | 'missing';
+    expected: string;
+    actual: string | null;
+  }[];
+  unchecked: string[]; // artifacts without a checksum yet
+  ok: boolean;
+}
+
+export type AttentionKind = 'failed' | 'corrupted' | 'stalled' | 'missing_dependencies';
+
+// A recording that needs a person, from get_attention_items
+export interface AttentionItem {
+  recording: string;
+  kind: AttentionKind;
+  status: RecordingStatus;
+  detail: string;
+  suggested_actions: string[];
+}
+
+// Whether a render, upload or export fits on its target volume, from preflight_disk_space
+export interface SpacePreflight {
+  operation: 'render' | 'upload' | 'export';
+  target: string;
+  required_bytes: number;
+  reserve_bytes: number;
+  available_bytes: number;
+  basis: string;
+  sufficient: boolean;
+}
+
+// Artifacts that do not fit together, from validate_pipeline_integrity
+export interface IntegrityFinding {
+  check: string;
+  detail: string;
+  file: string | null;
+  suggested_action: string;
+}
+
+// User-defined pipeline step, from list_custom_steps
+export interface CustomStep {
+  id: string;
+  name: string;
+  after: 'extract' | 'analyze' | 'setup_render' | 'render';
+  command: string; // same placeholders as step hooks
+  inputs: string[]; // paths inside the recording, '*' allowed in the file name
+  outputs: string[]; // present once the step is done
+}
+
+export type HookTiming = 'before' | 'after';
+
+// Shell command run around a pipeline step, from list_step_hooks
+export interface StepHook {
+  step: string; // step id or '*'
+  when: HookTiming;
+  command: string; // may use {recording}, {recording_path}, {step}, {result}
+  enabled: boolean;
+}
+
+// Cycles compute device from list_render_devices
+export interface RenderDevice {
+  backend: string;
+  id: string;
+  name: string;
+}
+
+// Machine the render step runs on, from get_render_host; remote_workspace is an
+// existing absolute directory on the host that recordings are synced into
+export type ExecutionBackend =
+  | { kind: 'Local' }
+  | { kind: 'Ssh'; host: string; user: string; remote_workspace: string };
+
+export type PreviewStatus = 'running' | 'done' | { failed: string };
+
+export interface RenderPreview {
+  status: PreviewStatus;
+  options: { scale_percent: number; frame_step: number };
+  output_dir: string;
+  started_at: number;
+  finished_at: number | null;
+}
+
+// Output of the transcribe step, from get_subtitles
+export interface SubtitleFiles {
+  srt: string | null;
+  vtt: string | null;
+}
+
+// obs-websocket connection, from get_obs_connection
+export interface ObsConnection {
+  enabled: boolean;
+  host: string;
+  port: number;
+  password: string | null;
+}
+
+// From get_obs_status; error says why OBS is not connected
+export interface ObsStatus {
+  connected: boolean;
+  error: string | null;
+  recording: boolean;
+  recording_paused: boolean;
+  streaming: boolean;
+  current_scene: string | null;
+  record_directory: string | null;
+  last_output_path: string | null;
+}
+
+// From get_obs_output_path, read from the OBS profile config rather than the websocket
+export interface ObsOutputPath {
+  path: string;
+  profile: string;
+  output_mode: string; // 'Simple' | 'Advanced'
+  config_dir: string;
+  exists: boolean;
+}
+
+// From sync_recordings_path_with_obs
+export interface ObsPathSync {
+  obs: ObsOutputPath;
+  restart_required: boolean;
+}
+
+// Payload of the obs-recording-state event
+export interface ObsRecordingEvent {
+  active: boolean;
+  state: string; // e.g. 'OBS_WEBSOCKET_OUTPUT_STOPPED'
+  output_path: string | null;
+}
+
+// 'Interrupted': the app quit while it ran, waits for resume_job or cancel_job
+export type JobStatus = 'Queued' | 'Running' | 'Interrupted' | 'Done' | { Failed: string };
+
+// Pipeline job from list_jobs / list_scheduled_jobs
+export interface Job {
+  id: number;
+  recording_name: string;
+  target_step: string;
+  preset: string | null;
+  source: string; // e.g. 'auto-ingest', 'manual'
+  status: JobStatus;
+  run_after: number | null; // Unix seconds
+  estimated_ms?: number | null; // ETA of the steps left, from list_jobs
+  attempts?: JobAttempt[]; // failed uploads the job retries
+}
+
+export interface JobAttempt {
+  step: string;
+  failed_at: number; // Unix seconds
+  error: string;
+  retry_at: number | null; // null once the job gave up
+}
+
+// Automatic retries of uploads that failed on network or server errors
+export interface UploadRetryPolicy {
+  enabled: boolean;
+  max_retries: number;
+  initial_backoff_secs: number; // doubles after every further failure
+  max_backoff_secs: number;
+}
+
+// Local hours queued jobs may run the listed steps in; end before start spans midnight
+export interface JobWindow {
+  enabled: boolean;
+  start: string; // 'HH:MM'
+  end: string; // 'HH:MM'
+  steps: string[];
+}
+
+// From get_process_priority; options a platform lacks are ignored there
+export interface ProcessPriority {
+  niceness: number | null; // 1-19; any value means below-normal priority on Windows
+  idle_io: boolean; // ionice idle class (Linux)
+  cpu_cores: string | null; // taskset list such as '0-3,6' (Linux)
+}
+
+// From get_process_environment; values may use ${NAME} for the inherited value
+export interface ProcessEnvironment {
+  global: Record<string, string>;
+  steps: Record<string, Record<string, string>>; // keyed by step id, e.g. 'render'
+}
+
+// From get_toolchain; how workspace packages are run from the workspace root
+export type Toolchain =
+  | { kind: 'Uv' }
+  | { kind: 'Poetry' }
+  | { kind: 'DirectPython'; interpreter: string };
+
+// From get_app_log, oldest first
+export interface AppLogEntry {
+  date: string; // 'YYYY-MM-DD'
+  time: string; // 'HH:MM:SS', UTC
+  target: string; // Rust module, e.g. 'app_lib::commands::operations'
+  level: 'ERROR' | 'WARN' | 'INFO' | 'DEBUG' | 'TRACE';
+  message: string; // may span several lines
+}
+
+// From preview_delete; pass token to delete_recording within expires_in_secs
+export interface DeletePreview {
+  token: string;
+  name: string;
+  file_count: number;
+  total_bytes: number;
+  stage_bytes: Record<string, number>; // e.g. raw_video, extracted, render
+  status: RecordingStatus;
+  running_job: number | null; // delete_recording is refused while it runs
+  expires_in_secs: number;
+}
+
+// Artifacts cleanup_recording and cleanup_uploaded can remove
+export type CleanupTarget =
+  | 'Extracted'
+  | 'BlenderProject'
+  | 'RenderOutput'
+  | 'RenderFrames'
+  | 'Transcoded'
+  | 'Logs'
+  | 'ConfigHistory';
+
+export interface CleanupReport {
+  recording: string;
+  dry_run: boolean;
+  items: { target: CleanupTarget; path: string; size_bytes: number }[];
+  freed_bytes: number;
+}
+
+// From delete_recordings and cleanup_uploaded; with dry_run nothing was touched
+export interface BatchReport {
+  dry_run: boolean;
+  trashed: { name: string; size_bytes: number; uploaded: boolean }[];
+  cleaned: CleanupReport[];
+  skipped: { name: string; reason: string }[];
+  freed_bytes: number;
+}
+
+export interface RenameReport {
+  old_name: string;
+  new_name: string;
+  dry_run: boolean;
+  rewrites: { file: string; occurrences: number }[];
+  needs_manual_repair: string[];
+  warnings: string[];
+}
+
+// From bulk_rename; any entry error blocks the whole batch
+export interface BulkRenameReport {
+  dry_run: boolean;
+  entries: { old_name: string; new_name: string; error: string | null }[];
+  renamed: RenameReport[];
+}
+
+// From get_rename_history; undo_last_rename backs out the latest one per recording
+export interface RenameJournalEntry {
+  old_name: string;
+  new_name: string;
+  renamed_at: number;
+  files_touched: string[];
+}
+
+// Payload of the recordings-migration-progress event
+export interface MigrationProgress {
+  recording: string;
+  recordings_done: number;
+  recordings_total: number;
+  copied_bytes: number;
+  total_bytes: number;
+}
+
+// From migrate_recordings; config_updated means the new root applies after a restart
+export interface RootMigrationReport {
+  old_root: string;
+  new_root: string;
+  migrated: { name: string; bytes: number; moved: boolean; symlinked: boolean }[];
+  skipped: { name: string; reason: string }[];
+  warnings: string[];
+  config_updated: boolean;
+}
+
+// get_recording_detection/set_recording_detection; markers are "video" or relative
+// paths like "extracted" or "blender/*.blend", any one of them is enough
+export interface RecordingDetection {
+  markers: string[];
+  video_extensions: string[];
+  ignored_dirs: string[];
+}
+
+// From get_recording_tree; directories carry the totals of everything below
+export interface FileTreeNode {
+  name: string;
+  path: string; // relative to the recording, "/" separated
+  is_dir: boolean;
+  size_bytes: number;
+  modified: number; // Unix seconds, latest below
+  file_count: number;
+  stage: string; // storage stage or "mixed"
+  truncated: boolean; // deeper than max_depth, children not listed
+  children: FileTreeNode[];
+}
+
+// Optional options of get_recordings; without sizes only statuses are read
+export interface ScanOptions {
+  include_sizes?: boolean; // default true
+  include_tree?: boolean;
+  max_depth?: number | null;
+}
+
+// From get_recent_recordings, pin_recording and unpin_recording; pinned first
+export interface RecentRecording {
+  name: string;
+  used_at: number | null; // Unix seconds, null when pinned but never opened
+  pinned: boolean;
+}
+
+// get_recordings_calendar; dates are local "YYYY-MM-DD", bounds inclusive
+export interface CalendarRange {
+  from?: string | null;
+  to?: string | null;
+}
+
+export interface CalendarEntry {
+  name: string;
+  status: RecordingStatus;
+  date_source: 'metadata' | 'name' | 'modified';
+}
+
+export interface CalendarDay {
+  date: string;
+  count: number;
+  status_counts: Record<string, number>;
+  recordings: CalendarEntry[];
+}
+
+// From estimate_step_duration; null when the step has never run before
+export interface StepEstimate {
+  step: string;
+  estimated_ms: number;
+  runs: number;
+  basis: string;
+}
+
+// export_report; `filter` is a status filter as in get_recordings_by_status
+export type ReportFormat = 'json' | 'csv';
+
+export interface ReportExport {
+  path: string;
+  recordings: number;
+}
+
+// From quarantine_recording; the directory now lives in <root>/.quarantine
+export interface QuarantineEntry {
+  id: string; // <timestamp>_<name>
+  name: string;
+  quarantined_at: number;
+  path: string;
+  reason: string | null;
+}
+
+// Payload of the 'status-changed' event; get_status_counts gives the badge counts
+export interface StatusChange {
+  recording: string;
+  old: RecordingStatus | null; // null for a recording not seen before
+  new: RecordingStatus | null; // null once the recording is gone
+}
+
+// From get_retry_plan; Retry fails listing the candidates when step is null
+export interface RetryPlan {
+  step: NextStep | null;
+  source: 'failed_marker' | 'state_manifest' | 'step_log' | 'upload_results' | null;
+  candidates: NextStep[]; // most advanced first
+}
+
+// Named workspace setup; unset fields keep the environment's value
+export interface ConfigProfile {
+  name: string;
+  recordings_path?: string | null;
+  workspace_root?: string | null;
+  uv_path?: string | null;
+  toolchain?: Toolchain | null; // becomes the toolchain setting on switch
+}
+
+// From list_config_profiles; switch_config_profile restarts the app
+export interface ConfigProfiles {
+  active: string | null;
+  profiles: ConfigProfile[];
+}
+
+// From get_audio_waveform; peaks are [min, max] pairs in -1..1 spread over duration_secs
+export interface AudioWaveform {
+  audio_file: string; // relative to extracted/
+  duration_secs: number;
+  peaks: [number, number][];
+}
+
+// From get_timeline_markers; every time is in seconds of the main video
+export interface TimelineMarkers {
+  analysis_file: string;
+  offset_seconds: number; // where the main audio starts in the video
+  duration: number | null;
+  bpm: number | null;
+  beats: number[];
+  sections: { start: number; end: number; label: string }[];
+  energy_peaks: number[];
+}
+
+// What a render ran with, from its step log
+export interface RenderRunOptions {
+  preset: string | null;
+  settings: RenderSettings;
+}
+
+// A render output under blender/, e.g. render or render_v2
+export interface RenderVersion {
+  name: string;
+  video: string;
+  size_bytes: number;
+  rendered_at: string | null;
+  log_id: string | null;
+  run: RenderRunOptions | null;
+  promoted: boolean;
+}
+
+// A Blender project in blender/, e.g. v002_gig.blend
+export interface BlendVersion {
+  file: string;
+  version: number | null; // null when not versioned yet
+  size_bytes: number;
+  modified_at: number;
+  active: boolean;
+}
+
+// Options of finalize_render; video_codec "copy" remuxes a rendered video
+export interface FinalizeOptions {
+  video_codec: string;
+  crf: number;
+  mux_main_audio: boolean;
+  audio_codec: string;
+  audio_bitrate: string | null;
+  fps: number | null;
+}
+
+// Overlays post_process_render or an upload profile burns into a copy of the render
+export interface PostProcessOptions {
+  burn_subtitles: boolean;
+  title_card: boolean;
+  title_seconds: number;
+  font_size: number;
+}
+
+// Language of backend text, see get_locale / set_locale
+export type Locale = 'en' | 'pl';
+
+// Templates by key ("status.rendered", "step.upload", ...) with {name} placeholders
+export type MessageCatalog = Record<string, string>;
+
+// Step the app quit or crashed during, from list_interrupted_steps
+export interface InterruptedStep {
+  recording_name: string;
+  step: string;
+  pid: number;
+  acquired_at: number; // Unix seconds
+  orphan_pids: number[]; // processes it left running, killed by resolve_interrupted_step with kill_orphans
+}
+
+// Step running in the library, from list_active_steps; local is false for another app sharing it
+export interface ActiveStep {
+  recording_name: string;
+  step: string;
+  host: string;
+  pid: number;
+  acquired_at: number; // Unix seconds
+  local: boolean;
+}
+
+// Payload of the 'quit-requested' event: ask, then call confirm_quit with a QuitMode
+export interface QuitRequest {
+  running: number; // jobs or steps quitting would stop
+}
+
+// wait: finish the running steps first; stop: SIGTERM, then SIGKILL after a grace period,
+// jobs resume by hand; persist: stop and queue the jobs again on the next launch
+export type QuitMode = 'wait' | 'stop' | 'persist';
+
+// Result of pause_all_jobs / resume_all_jobs
+export interface PipelinePause {
+  queue_paused: boolean;
+  processes_suspended: boolean; // running processes frozen with SIGSTOP (Unix only)
+  processes: number; // stopped or continued by the call
+}
+
+// A command preview_step would run, resolved as it would be spawned
+export interface CommandPreview {
+  program: string;
+  args: string[];
+  working_dir: string | null;
+  env: [string, string | null][]; // overrides of the app's environment; null removes a variable
+  command_line: string; // quoted for pasting into a POSIX shell
+}
+
+// Result of preview_step; nothing is run or written
+export interface StepPreview {
+  step: NextStep;
+  commands: CommandPreview[]; // hooks included, in order
+  outputs: string[];
+  notes: string[]; // where running the step may differ
+}
+
+// Which recordings roots are rescanned on a timer; fallback polls only roots without a watcher
+export type PollingMode = 'fallback' | 'always' | 'off';
+
+// Read by get_polling_settings / set_polling_settings; window_focused rescans unless off
+export interface PollingSettings {
+  mode: PollingMode;
+  interval_seconds: number; // at least 5
+}
+
+// File written by export_settings and read by import_settings; both return the settings keys
+export interface SettingsBundle {
+  version: number;
+  exported_at: string;
+  settings: Record<string, unknown>; // config_profiles, upload_profiles, step_hooks, auto_ingest, ...
+}
+
+// A named subset of the pipeline a recording can follow, see set_recording_pipeline_template
+export interface PipelineTemplate {
+  name: string;
+  description: string | null;
+  last_step: string; // e.g. 'render'; 'upload' runs every step
+  preset: string | null; // animation preset for setup_render
+  preview: { scale_percent: number; frame_step: number } | null; // preview render instead of the video
+  upload_profiles: string[]; // uploaded to in turn; the recording's profile when empty
+}
+
+// Result of process_backlog; jobs stay empty until called with confirm: true
+export interface BacklogPlan {
+  since: string;
+  target_step: NextStep;
+  items: {
+    recording: string;
+    status: RecordingStatus;
+    recorded_on: string; // YYYY-MM-DD
+    template: string | null;
+    preset: string | null;
+    steps: NextStep[];
+  }[]; // oldest recording first
+  skipped: { recording: string; reason: string }[];
+  jobs: Job[];
+}
+
+// Recordings holding the same raw video or start time, from find_duplicates
+export interface DuplicateGroup {
+  reason: 'same_video' | 'same_start_time';
+  key: string; // shared video hash or start time
+  members: {
+    recording: string;
+    status: RecordingStatus;
+    action: 'keep' | 'merge' | 'delete'; // merge: move only_here into the kept copy first
+    only_here: string[];
+  }[]; // kept copy first
+}
+
+// From attach_render; a video rendered on another machine
+export interface AttachedRender {
+  source: string; // path as picked
+  video: string; // relative to the recording, under blender/render
+  size_bytes: number;
+  moved: boolean;
+  attached_at: number;
+  media: MediaInfo;
+}
+
+// Expected size of a video, see OutputEstimate
+export interface SizeEstimate {
+  bytes: number;
+  bitrate_kbps: number;
+  basis: string; // e.g. '1920x1080 at 30 fps in H264'
+}
+
+// From estimate_outputs; sizes are null when the recording's duration is unknown
+export interface OutputEstimate {
+  duration_secs: number | null;
+  render: SizeEstimate | null;
+  upload: SizeEstimate | null; // the profile's transcode, else the render
+  upload_mbps: number | null;
+  bandwidth_basis: string | null; // 'configured' or 'measured from N earlier uploads'
+  upload_secs: number | null;
+}
+
+// get_upload_bandwidth / set_upload_bandwidth; measured from earlier uploads when null
+export interface UploadBandwidth {
+  mbps: number | null;
+}
+
+// From get_activity_log, newest first; every app sharing the library appends to it
+export interface ActivityEntry {
+  at: number;
+  host: string;
+  source: 'app' | 'http-api';
+  command: string;
+  arguments: Record<string, unknown>; // as sent, with passwords and tokens redacted
+  refused: string | null; // e.g. in observer mode
+}
+
+// Filter of get_activity_log; unset fields match everything
+export interface ActivityFilter {
+  command?: string;
+  recording?: string; // recording name anywhere in the arguments
+  host?: string;
+  since?: number;
+  limit?: number; // 500 by default
+}
+
+// From diagnose_and_repair; each check runs even when an earlier one failed
+export interface RepairReport {
+  dry_run: boolean;
+  actions: {
+    check: string; // 'settings', 'recordings_root', 'scan_cache', 'state_manifest' or 'media_cache'
+    outcome: 'ok' | 'repaired' | 'would_repair' | 'failed';
+    detail: string;
+  }[];
+}
+
+// What happens to a step whose output stops growing
+export type StallAction = 'notify' | 'kill' | 'retry';
+
+// Flags steps whose output directory gets no new bytes for `minutes`; 0 disables
+export interface StallDetection {
+  minutes: number;
+  action: StallAction;
+}
+
+// Payload of the `step-stalled` event
+export interface StepStalledEvent {
+  recording_name: string;
+  step: string;
+  seconds: number;
+}
+
+// Options of the upload step; profile overrides the recording's or the default one
+export interface UploadOptions {
+  profile?: string;
+}
+
+// Options run_specific_step_with_options takes, by step
+export type StepOptions = AnalyzeOptions | SetupRenderOptions | RenderSettings | UploadOptions;
+
+// One invalid field from validate_step_options; field is empty when the options as a whole are wrong
+export interface FieldError {
+  field: string;
+  message: string;
+}
+
+// Keeps a recording off the attention list and out of automatic processing; until is null when ignored
+export interface RecordingSnooze {
+  until: number | null;
+  snoozed_at: number;
+}
+
+// From list_snoozed_recordings
+export interface SnoozedRecording {
+  recording: string;
+  snooze: RecordingSnooze;
+}
+
+// How long one tool took in run_benchmark; rate is null when it failed
+export interface BenchmarkTiming {
+  tool: 'beatrix' | 'blender' | 'ffmpeg';
+  task: string;
+  success: boolean;
+  duration_ms: number;
+  rate: number | null;
+  unit: string;
+  error: string | null;
+}
+
+// Device Blender rendered the benchmark on; backend is 'NONE' without a GPU backend
+export interface BenchmarkDevice {
+  device: 'GPU' | 'CPU';
+  backend: string;
+  gpus: string[];
+}
+
+// From run_benchmark and list_benchmarks
+export interface BenchmarkReport {
+  at: number;
+  host: string;
+  timings: BenchmarkTiming[];
+  render_device: BenchmarkDevice | null;
+  gpu_used: boolean;
+}
@@ -35,6 +35,8 @@ Examples:
   %(prog)s recording.mp4 --auto --delay 5
   %(prog)s recording.mp4 metadata.json --skip-audio-pattern "RPI.*"
   %(prog)s recording.mp4 --auto --skip-audio-pattern "^(RPI|Camera).*"
+  %(prog)s recording.mp4 metadata.json --include-source "Camera 1" --include-source "Mic"
+  %(prog)s recording.mp4 metadata.json --audio-only
         """,
     )
 
@@ -75,6 +77,20 @@ Examples:
         help="Skip audio extraction for sources matching regex pattern (default: 'RPI.*')",
     )
 
+    parser.add_argument(
+        "--include-source",
+        action="append",
+        default=[],
+        metavar="NAME",
+        help="Extract only the named source; repeat for several (default: all sources)",
+    )
+
+    parser.add_argument(
+        "--audio-only",
+        action="store_true",
+        help="Extract audio tracks only, skipping the cropped videos",
+    )
+
     return parser.parse_args(args)
 
 
@@ -255,6 +271,42 @@ def apply_audio_pattern_filter(metadata: dict, pattern: str) -> dict:
     return new_metadata
 
 
+def apply_source_selection(
+    metadata: dict, include_sources: List[str], audio_only: bool
+) -> dict:
+    """
+    Narrow the sources to extract.
+
+    Args:
+        metadata: The loaded metadata dictionary
+        include_sources: Source names to keep; empty keeps all sources
+        audio_only: Drop the video of every kept source
+
+    Returns:
+        Modified metadata dictionary with only the selected sources
+    """
+    sources = metadata.get("sources", {})
+    missing = [name for name in include_sources if name not in sources]
+    if missing:
+        print(
+            f"Warning: sources not in metadata: {', '.join(missing)}", file=sys.stderr
+        )
+
+    new_sources = {}
+    for source_name, source_info in sources.items():
+        if include_sources and source_name not in include_sources:
+            print(f"Skipping source: {source_name}")
+            continue
+        source_copy = source_info.copy()
+        if audio_only:
+            source_copy["has_video"] = False
+        new_sources[source_name] = source_copy
+
+    new_metadata = metadata.copy()
+    new_metadata["sources"] = new_sources
+    return new_metadata
+
+
 def main() -> int:
     """
     Main CLI entry point.
@@ -338,6 +390,11 @@ def main() -> int:
         if args.skip_audio_pattern:
             metadata = apply_audio_pattern_filter(metadata, args.skip_audio_pattern)
 
+        if args.include_source or args.audio_only:
+            metadata = apply_source_selection(
+                metadata, args.include_source, args.audio_only
+            )
+
         # Print verbose info if requested
         if args.verbose:
             print(f"Input video: {video_path}")
@@ -7,7 +7,7 @@ import tempfile
 from pathlib import Path
 from unittest.mock import patch
 
-from obsession.cli.extract import main, parse_args
+from obsession.cli.extract import apply_source_selection, main, parse_args
 from obsession.core.extractor import ExtractionResult
 
 
@@ -173,3 +173,33 @@ class TestCLIExtract:
 
                 # Then
                 assert result == 1
+
+    def test_source_selection_arguments(self):
+        """Test --include-source and --audio-only narrow the extracted sources."""
+        # Given
+        args = parse_args(
+            [
+                "video.mp4",
+                "metadata.json",
+                "--include-source",
+                "Camera 1",
+                "--include-source",
+                "Mic",
+                "--audio-only",
+            ]
+        )
+        metadata = {
+            "sources": {
+                "Camera 1": {"has_audio": True, "has_video": True},
+                "Camera 3": {"has_audio": False, "has_video": True},
+                "Mic": {"has_audio": True, "has_video": False},
+            }
+        }
+
+        # When
+        result = apply_source_selection(metadata, args.include_source, args.audio_only)
+
+        # Then
+        assert list(result["sources"]) == ["Camera 1", "Mic"]
+        assert result["sources"]["Camera 1"]["has_video"] is False
+        assert metadata["sources"]["Camera 1"]["has_video"] is True